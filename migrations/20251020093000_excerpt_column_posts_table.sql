@@ -0,0 +1,12 @@
+ALTER TABLE posts
+    ADD COLUMN excerpt TEXT NOT NULL DEFAULT '';
+
+-- Backfill existing posts with their first 40 words, HTML tags stripped
+UPDATE posts
+SET excerpt = array_to_string(
+    (regexp_split_to_array(
+        trim(regexp_replace(regexp_replace(post_text, '<[^>]*>', ' ', 'g'), '\s+', ' ', 'g')),
+        ' '
+    ))[1:40],
+    ' '
+);
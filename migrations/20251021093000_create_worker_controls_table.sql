@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS worker_controls(
+name TEXT PRIMARY KEY NOT NULL,
+is_paused BOOLEAN NOT NULL DEFAULT FALSE,
+updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO worker_controls (name) VALUES ('newsletter_delivery');
+
+ALTER TABLE issue_delivery_queue
+    ADD COLUMN last_error TEXT,
+    ADD COLUMN last_attempted_at TIMESTAMPTZ;
@@ -0,0 +1,19 @@
+-- Raw feed impressions, one row per post shown in a sampled listing response
+CREATE TABLE impressions (
+    post_id UUID NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    user_id UUID REFERENCES users(id) ON DELETE CASCADE,
+    position INT NOT NULL,
+    shown_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_impressions_post_id_shown_at ON impressions (post_id, shown_at);
+CREATE INDEX idx_impressions_shown_at ON impressions (shown_at);
+
+-- Anonymous per-day rollup kept after raw impressions expire
+CREATE TABLE post_engagement_daily (
+    post_id UUID NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    day DATE NOT NULL,
+    impressions BIGINT NOT NULL DEFAULT 0,
+    views BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (post_id, day)
+);
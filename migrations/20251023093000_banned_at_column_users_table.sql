@@ -0,0 +1,5 @@
+ALTER TABLE users
+    ADD COLUMN banned_at TIMESTAMPTZ;
+
+ALTER TABLE comments
+    ADD COLUMN deleted_at TIMESTAMPTZ;
@@ -0,0 +1,9 @@
+-- Delivery tasks are enqueued by the worker in chunks after publishing.
+-- Issues published before this change were enqueued synchronously, hence 'done'.
+ALTER TABLE newsletter_issues
+    ADD COLUMN fan_out_status TEXT NOT NULL DEFAULT 'done' CHECK (fan_out_status IN ('pending', 'done')),
+    ADD COLUMN fan_out_cursor UUID,
+    ADD COLUMN enqueued_count BIGINT NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS newsletter_issues_fan_out_pending_idx
+    ON newsletter_issues USING btree (created_at) WHERE fan_out_status = 'pending';
@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS worker_heartbeats(
+worker_id UUID PRIMARY KEY NOT NULL,
+hostname TEXT NOT NULL,
+started_at TIMESTAMPTZ NOT NULL,
+last_seen_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
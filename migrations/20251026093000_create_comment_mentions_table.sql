@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS comment_mentions(
+comment_id UUID NOT NULL REFERENCES comments(id) ON DELETE CASCADE,
+mentioned_user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+PRIMARY KEY (comment_id, mentioned_user_id)
+);
+
+CREATE INDEX idx_comment_mentions_user_created_at ON comment_mentions (mentioned_user_id, created_at DESC);
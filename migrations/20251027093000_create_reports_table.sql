@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS reports(
+id UUID PRIMARY KEY NOT NULL,
+post_id UUID REFERENCES posts(id) ON DELETE CASCADE,
+comment_id UUID REFERENCES comments(id) ON DELETE CASCADE,
+reporter_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+reason TEXT NOT NULL,
+created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+-- A report flags exactly one piece of content
+CONSTRAINT reports_single_target CHECK (num_nonnulls(post_id, comment_id) = 1)
+);
+
+CREATE UNIQUE INDEX reports_post_reporter_idx ON reports (post_id, reporter_id) WHERE post_id IS NOT NULL;
+CREATE UNIQUE INDEX reports_comment_reporter_idx ON reports (comment_id, reporter_id) WHERE comment_id IS NOT NULL;
+CREATE INDEX reports_created_at_idx ON reports (created_at);
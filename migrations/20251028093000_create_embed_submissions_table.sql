@@ -0,0 +1,18 @@
+-- Anonymous comments posted through the embeddable widget, held until an admin reviews them
+CREATE TABLE IF NOT EXISTS embed_submissions(
+id UUID PRIMARY KEY NOT NULL,
+post_id UUID NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+author_name TEXT NOT NULL,
+author_email TEXT NOT NULL,
+text TEXT NOT NULL,
+origin TEXT NOT NULL,
+ip_address TEXT NOT NULL,
+status TEXT NOT NULL DEFAULT 'pending',
+comment_id UUID REFERENCES comments(id) ON DELETE SET NULL,
+created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+reviewed_at TIMESTAMPTZ,
+CONSTRAINT embed_submissions_status_check CHECK (status IN ('pending', 'approved', 'rejected'))
+);
+
+CREATE INDEX embed_submissions_pending_idx ON embed_submissions (created_at) WHERE status = 'pending';
+CREATE INDEX embed_submissions_ip_created_at_idx ON embed_submissions (ip_address, created_at);
@@ -0,0 +1,23 @@
+-- Large post bodies live here keyed by their SHA-256, so identical bodies are only stored once
+CREATE TABLE IF NOT EXISTS post_bodies(
+hash TEXT PRIMARY KEY NOT NULL,
+body TEXT NOT NULL,
+-- Refreshed whenever a post points at the body, orphans are only pruned once this is old
+touched_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- When set, post_text is left empty and the body is read from post_bodies
+ALTER TABLE posts ADD COLUMN IF NOT EXISTS body_hash TEXT REFERENCES post_bodies(hash);
+
+CREATE INDEX IF NOT EXISTS posts_body_hash_idx ON posts (body_hash) WHERE body_hash IS NOT NULL;
+
+-- Move existing large bodies over, using the same 4 KiB threshold as the application
+INSERT INTO post_bodies (hash, body)
+SELECT DISTINCT encode(sha256(convert_to(post_text, 'UTF8')), 'hex'), post_text
+FROM posts
+WHERE octet_length(post_text) >= 4096
+ON CONFLICT (hash) DO NOTHING;
+
+UPDATE posts
+SET body_hash = encode(sha256(convert_to(post_text, 'UTF8')), 'hex'), post_text = ''
+WHERE octet_length(post_text) >= 4096;
@@ -0,0 +1,23 @@
+-- Embed and anonymous comments share one moderation queue
+ALTER TABLE embed_submissions RENAME TO comment_submissions;
+ALTER TABLE comment_submissions RENAME CONSTRAINT embed_submissions_pkey TO comment_submissions_pkey;
+ALTER TABLE comment_submissions RENAME CONSTRAINT embed_submissions_post_id_fkey TO comment_submissions_post_id_fkey;
+ALTER TABLE comment_submissions RENAME CONSTRAINT embed_submissions_comment_id_fkey TO comment_submissions_comment_id_fkey;
+ALTER TABLE comment_submissions RENAME CONSTRAINT embed_submissions_status_check TO comment_submissions_status_check;
+ALTER INDEX embed_submissions_pending_idx RENAME TO comment_submissions_pending_idx;
+ALTER INDEX embed_submissions_ip_created_at_idx RENAME TO comment_submissions_ip_created_at_idx;
+
+-- Anonymous submissions come from the site itself, with a display name but no email
+ALTER TABLE comment_submissions ADD COLUMN source TEXT NOT NULL DEFAULT 'embed';
+ALTER TABLE comment_submissions ALTER COLUMN source DROP DEFAULT;
+ALTER TABLE comment_submissions ADD CONSTRAINT comment_submissions_source_check CHECK (source IN ('embed', 'anonymous'));
+ALTER TABLE comment_submissions ALTER COLUMN author_email DROP NOT NULL;
+ALTER TABLE comment_submissions ALTER COLUMN origin DROP NOT NULL;
+
+-- Name shown instead of the placeholder author's for approved anonymous comments
+ALTER TABLE comments ADD COLUMN author_display_name TEXT;
+
+-- Placeholder author for anonymous comments, it can never log in
+INSERT INTO users (id, user_name, email, password_hash)
+VALUES ('00000000-0000-4000-8000-000000000001', 'anonymous', 'anonymous@techhub.invalid', '!anonymous')
+ON CONFLICT (id) DO NOTHING;
@@ -0,0 +1,10 @@
+-- One row per activation reminder sent, so each user gets every reminder at most once
+CREATE TABLE IF NOT EXISTS activation_reminders (
+user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+reminder_number SMALLINT NOT NULL CHECK (reminder_number > 0),
+sent_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+PRIMARY KEY (user_id, reminder_number)
+);
+
+-- Lets reminder conversion be measured, left empty for accounts activated before it existed
+ALTER TABLE users ADD COLUMN activated_at TIMESTAMPTZ;
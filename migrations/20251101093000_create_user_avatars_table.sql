@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS user_avatars (
+user_id UUID PRIMARY KEY NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+content_type TEXT NOT NULL,
+image BYTEA NOT NULL,
+updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Copied onto users so listings can build avatar URLs without joining the images
+ALTER TABLE users ADD COLUMN avatar_updated_at TIMESTAMPTZ;
@@ -0,0 +1,4 @@
+ALTER TABLE users
+    ADD COLUMN bio TEXT,
+    ADD COLUMN website TEXT,
+    ADD COLUMN location TEXT;
@@ -0,0 +1,5 @@
+-- Posts written before licenses existed keep every right reserved, the only safe assumption.
+-- New posts are always given a license by the application, defaulting to the configured one.
+ALTER TABLE posts
+    ADD COLUMN license TEXT NOT NULL DEFAULT 'all-rights-reserved'
+    CHECK (license IN ('cc0', 'cc-by', 'cc-by-sa', 'cc-by-nc', 'cc-by-nd', 'all-rights-reserved'));
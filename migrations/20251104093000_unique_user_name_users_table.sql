@@ -0,0 +1,12 @@
+-- User names are used to log in and to mention people, so each one has to identify a single
+-- account. Existing duplicates keep the name for the oldest account and suffix the rest.
+UPDATE users u
+SET user_name = u.user_name || '-' || LEFT(u.id::TEXT, 8)
+WHERE EXISTS (
+    SELECT 1
+    FROM users o
+    WHERE LOWER(o.user_name) = LOWER(u.user_name)
+    AND (o.created_at, o.id) < (u.created_at, u.id)
+);
+
+CREATE UNIQUE INDEX users_user_name_unique_idx ON users (LOWER(user_name));
@@ -0,0 +1,4 @@
+-- Email change links carry the address they confirm, the account keeps its current email until
+-- the link is clicked
+ALTER TABLE tokens ADD COLUMN is_email_change BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE tokens ADD COLUMN pending_email TEXT;
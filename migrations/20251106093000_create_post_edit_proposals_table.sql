@@ -0,0 +1,18 @@
+-- Community posts accept edit proposals from any signed-in user, applied once the author approves
+ALTER TABLE posts ADD COLUMN is_community BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE TABLE IF NOT EXISTS post_edit_proposals(
+id UUID PRIMARY KEY NOT NULL,
+post_id UUID NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+proposed_by UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+title TEXT NOT NULL,
+post_text TEXT NOT NULL,
+img TEXT NOT NULL,
+-- Version of the post the proposal was written against, approving it fails once the post moved on
+base_version INTEGER NOT NULL,
+status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'approved', 'rejected')),
+created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+reviewed_at TIMESTAMPTZ
+);
+
+CREATE INDEX post_edit_proposals_pending_idx ON post_edit_proposals (post_id, created_at) WHERE status = 'pending';
@@ -0,0 +1,3 @@
+-- Deleted accounts are kept as anonymized rows, so content reassigned or soft deleted with them
+-- still has an author to point at
+ALTER TABLE users ADD COLUMN deleted_at TIMESTAMPTZ;
@@ -0,0 +1,2 @@
+-- The resource a saved response points at, so a replay can tell whether it still exists
+ALTER TABLE idempotency ADD COLUMN resource_id UUID;
@@ -0,0 +1,10 @@
+-- Who follows whom, the personalized feed lists posts by followed authors
+CREATE TABLE IF NOT EXISTS follows(
+follower_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+followed_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+PRIMARY KEY (follower_id, followed_id),
+CHECK (follower_id <> followed_id)
+);
+
+CREATE INDEX follows_followed_id_idx ON follows (followed_id);
@@ -0,0 +1,26 @@
+-- One conversation per pair of users, the smaller id goes first so a pair can only have one row
+CREATE TABLE IF NOT EXISTS conversations(
+id UUID PRIMARY KEY NOT NULL,
+user_a UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+user_b UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+last_message_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+UNIQUE (user_a, user_b),
+CHECK (user_a < user_b)
+);
+
+CREATE INDEX conversations_user_b_idx ON conversations (user_b);
+
+CREATE TABLE IF NOT EXISTS messages(
+id UUID PRIMARY KEY NOT NULL,
+conversation_id UUID NOT NULL REFERENCES conversations(id) ON DELETE CASCADE,
+sender_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+text TEXT NOT NULL,
+created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+read_at TIMESTAMPTZ
+);
+
+CREATE INDEX messages_conversation_id_idx ON messages (conversation_id, created_at);
+CREATE INDEX messages_unread_idx ON messages (conversation_id) WHERE read_at IS NULL;
+-- Backs the per-sender rate limit
+CREATE INDEX messages_sender_id_idx ON messages (sender_id, created_at);
@@ -0,0 +1,11 @@
+-- A blocker no longer sees the blocked user's comments, and the blocked user can no longer
+-- comment on the blocker's posts or message them
+CREATE TABLE IF NOT EXISTS user_blocks(
+blocker_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+blocked_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+PRIMARY KEY (blocker_id, blocked_id),
+CHECK (blocker_id <> blocked_id)
+);
+
+CREATE INDEX user_blocks_blocked_id_idx ON user_blocks (blocked_id);
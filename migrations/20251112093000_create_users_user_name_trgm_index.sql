@@ -0,0 +1,4 @@
+-- Serves both the prefix and the fuzzy matching of user search
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX IF NOT EXISTS users_user_name_trgm_idx ON users USING gin (user_name gin_trgm_ops);
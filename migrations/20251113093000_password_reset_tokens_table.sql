@@ -0,0 +1,2 @@
+-- Reset links are single use and expire, the expiry is checked against `created_at`
+ALTER TABLE tokens ADD COLUMN is_password_reset BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,10 @@
+-- Hashes a user's password had before it was changed, newest first by `replaced_at`. The
+-- current hash stays in `users`, only as many old ones are kept as reuse checks look at.
+CREATE TABLE IF NOT EXISTS password_history(
+id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+password_hash TEXT NOT NULL,
+replaced_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX password_history_user_id_replaced_at_idx ON password_history (user_id, replaced_at DESC);
@@ -0,0 +1,9 @@
+-- Login attempts per client address and per user name in the current fixed window, kept in the
+-- db so every instance counts towards the same limit
+CREATE TABLE IF NOT EXISTS login_throttles(
+key TEXT PRIMARY KEY,
+window_started_at TIMESTAMPTZ NOT NULL,
+attempts INTEGER NOT NULL
+);
+
+CREATE INDEX login_throttles_window_started_at_idx ON login_throttles (window_started_at);
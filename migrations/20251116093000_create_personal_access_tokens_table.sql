@@ -0,0 +1,14 @@
+-- Named, scoped API tokens for CLI tools and integrations. Only a SHA-256 hash of each token is
+-- kept, and revoking a token sets `revoked_at` rather than deleting it.
+CREATE TABLE IF NOT EXISTS personal_access_tokens(
+id UUID PRIMARY KEY,
+user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+name TEXT NOT NULL,
+token_hash TEXT NOT NULL UNIQUE,
+scopes TEXT[] NOT NULL,
+created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+last_used_at TIMESTAMPTZ,
+revoked_at TIMESTAMPTZ
+);
+
+CREATE INDEX personal_access_tokens_user_id_idx ON personal_access_tokens (user_id);
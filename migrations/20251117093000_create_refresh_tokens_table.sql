@@ -0,0 +1,18 @@
+-- Refresh tokens of bearer-token clients. Every refresh replaces the token with a new one in the
+-- same family, one family per sign-in. A rotated token that comes back means it was copied, so
+-- its whole family is revoked. Only a SHA-256 hash of each token is kept.
+CREATE TABLE IF NOT EXISTS refresh_tokens(
+id UUID PRIMARY KEY,
+family_id UUID NOT NULL,
+user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+token_hash TEXT NOT NULL UNIQUE,
+user_agent TEXT,
+ip_address TEXT NOT NULL,
+created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+expires_at TIMESTAMPTZ NOT NULL,
+rotated_at TIMESTAMPTZ,
+revoked_at TIMESTAMPTZ
+);
+
+CREATE INDEX refresh_tokens_family_id_idx ON refresh_tokens (family_id);
+CREATE INDEX refresh_tokens_user_id_idx ON refresh_tokens (user_id);
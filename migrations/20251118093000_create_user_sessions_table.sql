@@ -0,0 +1,14 @@
+-- Cookie sessions live in Redis, keyed by an opaque id that cannot be looked up by user. Each
+-- login also gets a row here so a user can see where they are signed in and revoke a session,
+-- which is then logged out on its next request.
+CREATE TABLE IF NOT EXISTS user_sessions(
+id UUID PRIMARY KEY,
+user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+user_agent TEXT,
+ip_address TEXT NOT NULL,
+created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+last_seen_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+revoked_at TIMESTAMPTZ
+);
+
+CREATE INDEX user_sessions_user_id_idx ON user_sessions (user_id);
@@ -0,0 +1,3 @@
+-- Sessions logged in with "remember me" get a long-lived cookie holding a token that resumes the
+-- session once the browser has dropped the session cookie. Only a SHA-256 hash of it is kept.
+ALTER TABLE user_sessions ADD COLUMN remember_token_hash TEXT UNIQUE;
@@ -0,0 +1,9 @@
+-- Roles replace the admin flag so that parts of administration, e.g. comment moderation, can be
+-- granted without the rest. What each role may do is decided in code, see `Role::permissions`.
+CREATE TYPE user_role AS ENUM ('user', 'moderator', 'admin');
+
+ALTER TABLE users ADD COLUMN role user_role NOT NULL DEFAULT 'user';
+
+UPDATE users SET role = 'admin' WHERE is_admin;
+
+ALTER TABLE users DROP COLUMN is_admin;
@@ -0,0 +1,4 @@
+-- A ban without `banned_until` lasts until an admin lifts it
+ALTER TABLE users
+    ADD COLUMN banned_until TIMESTAMPTZ,
+    ADD COLUMN ban_reason TEXT;
@@ -0,0 +1,14 @@
+-- Emailed links stop working once they expire, and the worker purges them afterwards. Links sent
+-- before this existed get the lifetimes new ones have.
+ALTER TABLE tokens ADD COLUMN expires_at TIMESTAMPTZ;
+
+UPDATE tokens
+SET expires_at = created_at + CASE
+    WHEN is_password_reset THEN INTERVAL '1 hour'
+    WHEN is_activation THEN INTERVAL '48 hours'
+    ELSE INTERVAL '24 hours'
+END;
+
+ALTER TABLE tokens ALTER COLUMN expires_at SET NOT NULL;
+
+CREATE INDEX tokens_expires_at_idx ON tokens (expires_at);
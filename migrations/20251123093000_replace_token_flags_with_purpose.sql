@@ -0,0 +1,21 @@
+-- Every token is issued for exactly one purpose and only accepted for it
+CREATE TYPE token_purpose AS ENUM ('activation', 'subscription', 'email_change', 'password_reset');
+
+ALTER TABLE tokens ADD COLUMN purpose token_purpose;
+
+UPDATE tokens
+SET purpose = CASE
+    WHEN is_password_reset THEN 'password_reset'
+    WHEN is_email_change THEN 'email_change'
+    WHEN is_activation THEN 'activation'
+    ELSE 'subscription'
+END::token_purpose;
+
+ALTER TABLE tokens
+    ALTER COLUMN purpose SET NOT NULL,
+    DROP COLUMN is_subscription,
+    DROP COLUMN is_activation,
+    DROP COLUMN is_email_change,
+    DROP COLUMN is_password_reset;
+
+CREATE INDEX tokens_user_id_purpose_idx ON tokens (user_id, purpose);
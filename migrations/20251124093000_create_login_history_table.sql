@@ -0,0 +1,14 @@
+-- Every password check against an existing account, kept for a while so users can spot sign-ins
+-- that were not theirs
+ALTER TABLE users ADD COLUMN last_login_at TIMESTAMPTZ;
+
+CREATE TABLE IF NOT EXISTS login_history(
+id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+succeeded BOOLEAN NOT NULL,
+ip_address TEXT NOT NULL,
+user_agent TEXT,
+occurred_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX login_history_user_id_occurred_at_idx ON login_history (user_id, occurred_at DESC);
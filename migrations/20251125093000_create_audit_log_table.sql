@@ -0,0 +1,33 @@
+-- Security-relevant actions, kept for review by admins. Actors and targets are not foreign keys so
+-- entries outlive the accounts and posts they mention.
+CREATE TYPE audit_action AS ENUM (
+    'login',
+    'password_change',
+    'role_change',
+    'post_hard_delete',
+    'newsletter_publish'
+);
+
+CREATE TABLE IF NOT EXISTS audit_log(
+id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+action audit_action NOT NULL,
+actor_id UUID,
+target_id UUID,
+metadata JSONB NOT NULL DEFAULT '{}',
+occurred_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX audit_log_occurred_at_idx ON audit_log (occurred_at DESC);
+CREATE INDEX audit_log_actor_id_idx ON audit_log (actor_id);
+CREATE INDEX audit_log_target_id_idx ON audit_log (target_id);
+
+-- Entries are only ever added, even the application cannot rewrite history
+CREATE FUNCTION reject_audit_log_changes() RETURNS trigger AS $$
+BEGIN
+    RAISE EXCEPTION 'audit_log is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER audit_log_append_only
+BEFORE UPDATE OR DELETE ON audit_log
+FOR EACH ROW EXECUTE FUNCTION reject_audit_log_changes();
@@ -0,0 +1,4 @@
+-- Unactivated users can ask not to be reminded again, through a link in each reminder
+ALTER TYPE token_purpose ADD VALUE 'reminder_opt_out';
+
+ALTER TABLE users ADD COLUMN activation_reminders_opted_out_at TIMESTAMPTZ;
@@ -0,0 +1,2 @@
+-- Emailed links that log the user in without their password
+ALTER TYPE token_purpose ADD VALUE 'magic_link';
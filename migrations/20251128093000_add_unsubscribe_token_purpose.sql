@@ -0,0 +1,3 @@
+-- Every newsletter issue carries a link that unsubscribes its recipient without logging in. The
+-- token behind it is reused across issues and never expires.
+ALTER TYPE token_purpose ADD VALUE 'unsubscribe';
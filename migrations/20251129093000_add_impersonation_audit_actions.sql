@@ -0,0 +1,3 @@
+-- Admins can act as another user for support, every impersonation is recorded from start to end
+ALTER TYPE audit_action ADD VALUE 'impersonation_start';
+ALTER TYPE audit_action ADD VALUE 'impersonation_end';
@@ -0,0 +1,11 @@
+-- Delivery tasks leave the queue once they are done, so their outcome is kept here for the
+-- status of the issue. Removed together with the issue.
+CREATE TABLE IF NOT EXISTS newsletter_delivery_log(
+newsletter_issue_id UUID NOT NULL REFERENCES newsletter_issues(id) ON DELETE CASCADE,
+user_email TEXT NOT NULL,
+outcome TEXT NOT NULL CHECK (outcome IN ('sent', 'failed', 'skipped')),
+error TEXT,
+attempts INT NOT NULL,
+recorded_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+PRIMARY KEY (newsletter_issue_id, user_email)
+);
@@ -0,0 +1,8 @@
+-- Issues can be saved as drafts and published later. Drafts are never fanned out or cleaned up.
+ALTER TABLE newsletter_issues
+    DROP CONSTRAINT newsletter_issues_fan_out_status_check,
+    ADD CONSTRAINT newsletter_issues_fan_out_status_check
+        CHECK (fan_out_status IN ('draft', 'pending', 'done')),
+    ADD COLUMN published_at TIMESTAMPTZ;
+
+UPDATE newsletter_issues SET published_at = created_at;
@@ -0,0 +1,2 @@
+-- Issues published for a later time are only fanned out once it has passed
+ALTER TABLE newsletter_issues ADD COLUMN scheduled_at TIMESTAMPTZ;
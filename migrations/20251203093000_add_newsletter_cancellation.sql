@@ -0,0 +1,7 @@
+-- Issues published by mistake can be cancelled, which stops their fan-out and empties their queue
+ALTER TABLE newsletter_issues
+    DROP CONSTRAINT newsletter_issues_fan_out_status_check,
+    ADD CONSTRAINT newsletter_issues_fan_out_status_check
+        CHECK (fan_out_status IN ('draft', 'pending', 'done', 'cancelled'));
+
+ALTER TYPE audit_action ADD VALUE 'newsletter_cancel';
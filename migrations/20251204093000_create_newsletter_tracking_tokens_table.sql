@@ -0,0 +1,11 @@
+-- Each delivered issue carries a token of its own, so opens can be attributed to one recipient
+-- of one issue. Removed together with the issue.
+CREATE TABLE IF NOT EXISTS newsletter_tracking_tokens(
+token TEXT PRIMARY KEY,
+newsletter_issue_id UUID NOT NULL REFERENCES newsletter_issues(id) ON DELETE CASCADE,
+user_email TEXT NOT NULL,
+open_count INT NOT NULL DEFAULT 0,
+first_opened_at TIMESTAMPTZ,
+created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+UNIQUE (newsletter_issue_id, user_email)
+);
@@ -0,0 +1,11 @@
+-- Every click on a link of a delivered issue, attributed to the recipient through their tracking
+-- token. Links are identified by their position in the issue. Removed together with the issue.
+CREATE TABLE IF NOT EXISTS newsletter_link_clicks(
+id BIGSERIAL PRIMARY KEY,
+token TEXT NOT NULL REFERENCES newsletter_tracking_tokens(token) ON DELETE CASCADE,
+link_position INT NOT NULL,
+url TEXT NOT NULL,
+clicked_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS newsletter_link_clicks_token_idx ON newsletter_link_clicks(token);
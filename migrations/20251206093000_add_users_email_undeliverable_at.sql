@@ -0,0 +1,3 @@
+-- Set once the email provider reports a permanent bounce for the user's address, newsletters are
+-- no longer sent to it. Cleared when the user changes their email.
+ALTER TABLE users ADD COLUMN IF NOT EXISTS email_undeliverable_at TIMESTAMPTZ;
@@ -0,0 +1,6 @@
+-- Addresses no email is sent to anymore, newsletters nor transactional emails. Stored lowercased.
+CREATE TABLE IF NOT EXISTS email_suppressions(
+email TEXT PRIMARY KEY,
+reason TEXT NOT NULL CHECK (reason IN ('spam_complaint')),
+created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
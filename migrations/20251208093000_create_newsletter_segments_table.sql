@@ -0,0 +1,13 @@
+-- Subsets of the subscribers an issue can be sent to instead of all of them. Criteria left NULL
+-- match everyone. Segments are kept as long as an issue refers to them.
+CREATE TABLE IF NOT EXISTS newsletter_segments(
+id UUID PRIMARY KEY,
+name TEXT NOT NULL UNIQUE,
+signed_up_after TIMESTAMPTZ,
+signed_up_before TIMESTAMPTZ,
+active_within_days INT CHECK (active_within_days > 0),
+created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+ALTER TABLE newsletter_issues
+ADD COLUMN IF NOT EXISTS segment_id UUID REFERENCES newsletter_segments(id) ON DELETE RESTRICT;
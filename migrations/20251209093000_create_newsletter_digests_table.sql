@@ -0,0 +1,8 @@
+-- One row per scheduled run of the weekly digest, claimed by the first worker instance to reach it
+-- so the digest is created once however many instances run. The issue is NULL for weeks without
+-- posts worth sending, and once the issue is cleaned up.
+CREATE TABLE IF NOT EXISTS newsletter_digests(
+scheduled_for TIMESTAMPTZ PRIMARY KEY,
+newsletter_issue_id UUID REFERENCES newsletter_issues(id) ON DELETE SET NULL,
+created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,16 @@
+-- Dead-letter queue: delivery tasks that failed for good, moved out of `issue_delivery_queue`
+-- with the error of their last attempt. Removed together with the issue.
+CREATE TABLE IF NOT EXISTS issue_delivery_failures(
+newsletter_issue_id UUID NOT NULL REFERENCES newsletter_issues(id) ON DELETE CASCADE,
+user_email TEXT NOT NULL,
+n_retries INT NOT NULL,
+last_error TEXT NOT NULL,
+failed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+PRIMARY KEY (newsletter_issue_id, user_email)
+);
+
+INSERT INTO issue_delivery_failures (newsletter_issue_id, user_email, n_retries, last_error, failed_at)
+SELECT newsletter_issue_id, user_email, attempts, COALESCE(error, 'Unknown error'), recorded_at
+FROM newsletter_delivery_log
+WHERE outcome = 'failed'
+ON CONFLICT DO NOTHING;
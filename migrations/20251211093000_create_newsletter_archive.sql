@@ -0,0 +1,21 @@
+-- Published issues are kept here once cleaned up from `newsletter_issues`, for the public archive.
+-- Drafts, cancelled issues and issues sent to a segment only are never public.
+CREATE TABLE IF NOT EXISTS newsletter_archive(
+id UUID PRIMARY KEY,
+title TEXT NOT NULL,
+html_content TEXT NOT NULL,
+published_at TIMESTAMPTZ NOT NULL,
+archived_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS newsletter_archive_published_at_idx ON newsletter_archive (published_at);
+
+-- Every public issue, whether still in `newsletter_issues` or archived. Scheduled issues are listed
+-- once `published_at` has passed.
+CREATE OR REPLACE VIEW public_newsletters AS
+SELECT id, title, html_content, published_at
+FROM newsletter_issues
+WHERE fan_out_status IN ('pending', 'done') AND segment_id IS NULL AND published_at IS NOT NULL
+UNION ALL
+SELECT id, title, html_content, published_at
+FROM newsletter_archive;
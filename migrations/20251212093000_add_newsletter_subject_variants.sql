@@ -0,0 +1,6 @@
+-- Issues can be sent with a second subject line to half of their recipients, see `SubjectVariant`
+ALTER TABLE newsletter_issues ADD COLUMN alternative_title TEXT;
+
+-- Existing deliveries all went out with the issue title
+ALTER TABLE newsletter_tracking_tokens
+    ADD COLUMN subject_variant TEXT NOT NULL DEFAULT 'a' CHECK (subject_variant IN ('a', 'b'));
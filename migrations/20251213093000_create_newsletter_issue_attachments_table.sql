@@ -0,0 +1,10 @@
+-- Files sent along with every email of an issue, in the order they were attached. Removed
+-- together with the issue.
+CREATE TABLE IF NOT EXISTS newsletter_issue_attachments(
+newsletter_issue_id UUID NOT NULL REFERENCES newsletter_issues(id) ON DELETE CASCADE,
+position INT NOT NULL,
+name TEXT NOT NULL,
+content_type TEXT NOT NULL,
+content BYTEA NOT NULL,
+PRIMARY KEY (newsletter_issue_id, position)
+);
@@ -0,0 +1,15 @@
+-- The suppression list now also holds permanently bounced addresses and addresses admins
+-- suppressed by hand. Every email, newsletters and account emails alike, is checked against it.
+ALTER TABLE email_suppressions RENAME TO suppressed_emails;
+ALTER INDEX email_suppressions_pkey RENAME TO suppressed_emails_pkey;
+ALTER TABLE suppressed_emails DROP CONSTRAINT email_suppressions_reason_check;
+ALTER TABLE suppressed_emails ADD CONSTRAINT suppressed_emails_reason_check
+CHECK (reason IN ('spam_complaint', 'hard_bounce', 'manual'));
+
+CREATE INDEX IF NOT EXISTS suppressed_emails_created_at_idx ON suppressed_emails (created_at);
+
+INSERT INTO suppressed_emails (email, reason, created_at)
+SELECT LOWER(email), 'hard_bounce', email_undeliverable_at
+FROM users
+WHERE email_undeliverable_at IS NOT NULL
+ON CONFLICT (email) DO NOTHING;
@@ -0,0 +1,20 @@
+-- Accounts renamed when user names became unique were not told, and can no longer log in with the
+-- name they knew. Each is sent its new name once. The renamed accounts are recognised by the
+-- suffix that migration appended, placeholder and imported accounts left out as they can't log in.
+CREATE TABLE user_name_change_notices (
+    user_id UUID PRIMARY KEY REFERENCES users (id) ON DELETE CASCADE,
+    previous_user_name TEXT NOT NULL,
+    user_name TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    sent_at TIMESTAMPTZ
+);
+
+INSERT INTO user_name_change_notices (user_id, previous_user_name, user_name)
+SELECT id, LEFT(user_name, LENGTH(user_name) - 9), user_name
+FROM users
+WHERE user_name LIKE '%-' || LEFT(id::TEXT, 8)
+  AND password_hash NOT LIKE '!%';
+
+CREATE INDEX user_name_change_notices_unsent_idx
+    ON user_name_change_notices (created_at)
+    WHERE sent_at IS NULL;
@@ -0,0 +1,8 @@
+-- Password reset and sign-in link emails are sent by the worker, so requesting one takes as long
+-- whether or not the address belongs to an account. A request still waiting is not queued again.
+CREATE TABLE account_email_requests (
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    purpose token_purpose NOT NULL,
+    requested_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, purpose)
+);
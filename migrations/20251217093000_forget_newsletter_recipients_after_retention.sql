@@ -0,0 +1,9 @@
+-- A week after its issue was sent, a delivery keeps nothing that names its recipient. Its outcome
+-- is counted on the issue instead, and the tracking token of an email that went out stays without
+-- the address, so opens and clicks keep counting and the links in the email keep redirecting.
+ALTER TABLE newsletter_issues
+    ADD COLUMN forgotten_sent BIGINT NOT NULL DEFAULT 0,
+    ADD COLUMN forgotten_failed BIGINT NOT NULL DEFAULT 0,
+    ADD COLUMN forgotten_skipped BIGINT NOT NULL DEFAULT 0;
+
+ALTER TABLE newsletter_tracking_tokens ALTER COLUMN user_email DROP NOT NULL;
@@ -0,0 +1,215 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, Mac};
+use secrecy::{ExposeSecret, Secret};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use uuid::Uuid;
+
+use crate::domain::Role;
+
+type HmacSha256 = Hmac<Sha256>;
+
+// Only HS256 tokens are issued, so only HS256 tokens are accepted. Trusting the `alg` a token
+// claims for itself would let a forged `none` token through.
+const ALGORITHM: &str = "HS256";
+
+#[derive(Serialize, Deserialize)]
+struct Header {
+    alg: String,
+    typ: String,
+}
+
+// Bearer tokens carry what a session would, so the handlers behind `reject_anonymous_users`
+// cannot tell the two apart
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct AccessClaims {
+    pub sub: Uuid,
+    pub role: Role,
+    // Seconds since the epoch
+    pub iat: i64,
+    pub exp: i64,
+}
+
+impl AccessClaims {
+    pub fn new(user_id: Uuid, role: Role, issued_at: DateTime<Utc>, ttl: Duration) -> Self {
+        Self {
+            sub: user_id,
+            role,
+            iat: issued_at.timestamp(),
+            exp: (issued_at + ttl).timestamp(),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum TokenError {
+    #[error("Malformed bearer token")]
+    Malformed,
+    #[error("Invalid bearer token signature")]
+    InvalidSignature,
+    #[error("Bearer token has expired")]
+    Expired,
+}
+
+pub fn encode_access_token(claims: &AccessClaims, signing_key: &Secret<String>) -> String {
+    let header = Header {
+        alg: ALGORITHM.to_string(),
+        typ: "JWT".to_string(),
+    };
+    // Serializing plain structs of strings, numbers and bools cannot fail
+    let header = serde_json::to_vec(&header).expect("JWT header should always serialize");
+    let claims = serde_json::to_vec(claims).expect("JWT claims should always serialize");
+
+    let signing_input = format!(
+        "{}.{}",
+        URL_SAFE_NO_PAD.encode(header),
+        URL_SAFE_NO_PAD.encode(claims)
+    );
+    let signature = mac(signing_key, &signing_input).finalize().into_bytes();
+
+    format!("{signing_input}.{}", URL_SAFE_NO_PAD.encode(signature))
+}
+
+// The signature is checked before anything inside the token is parsed
+pub fn decode_access_token(
+    token: &str,
+    signing_key: &Secret<String>,
+    now: DateTime<Utc>,
+) -> Result<AccessClaims, TokenError> {
+    let mut parts = token.split('.');
+    let (Some(header), Some(claims), Some(signature), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(TokenError::Malformed);
+    };
+
+    let signature = URL_SAFE_NO_PAD
+        .decode(signature)
+        .map_err(|_| TokenError::Malformed)?;
+    mac(signing_key, &format!("{header}.{claims}"))
+        .verify_slice(&signature)
+        .map_err(|_| TokenError::InvalidSignature)?;
+
+    let header: Header = decode_part(header)?;
+    if header.alg != ALGORITHM {
+        return Err(TokenError::Malformed);
+    }
+
+    let claims: AccessClaims = decode_part(claims)?;
+    if claims.exp <= now.timestamp() {
+        return Err(TokenError::Expired);
+    }
+
+    Ok(claims)
+}
+
+fn mac(signing_key: &Secret<String>, signing_input: &str) -> HmacSha256 {
+    // HMAC accepts keys of any length
+    let mut mac = HmacSha256::new_from_slice(signing_key.expose_secret().as_bytes())
+        .expect("HMAC should accept a key of any length");
+    mac.update(signing_input.as_bytes());
+    mac
+}
+
+fn decode_part<T: for<'de> Deserialize<'de>>(part: &str) -> Result<T, TokenError> {
+    let json = URL_SAFE_NO_PAD
+        .decode(part)
+        .map_err(|_| TokenError::Malformed)?;
+    serde_json::from_slice(&json).map_err(|_| TokenError::Malformed)
+}
+
+#[cfg(test)]
+mod tests {
+    use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+    use chrono::{Duration, Utc};
+    use claims::{assert_err_eq, assert_ok_eq};
+    use secrecy::Secret;
+    use uuid::Uuid;
+
+    use super::{AccessClaims, TokenError, decode_access_token, encode_access_token};
+    use crate::domain::Role;
+
+    fn key() -> Secret<String> {
+        Secret::new("signing-key".to_string())
+    }
+
+    fn claims() -> AccessClaims {
+        AccessClaims::new(
+            Uuid::new_v4(),
+            Role::User,
+            Utc::now(),
+            Duration::minutes(15),
+        )
+    }
+
+    #[test]
+    fn issued_tokens_decode_to_their_claims() {
+        let claims = claims();
+        let token = encode_access_token(&claims, &key());
+
+        assert_ok_eq!(decode_access_token(&token, &key(), Utc::now()), claims);
+    }
+
+    #[test]
+    fn tokens_signed_with_another_key_are_rejected() {
+        let token = encode_access_token(&claims(), &Secret::new("other-key".to_string()));
+
+        assert_err_eq!(
+            decode_access_token(&token, &key(), Utc::now()),
+            TokenError::InvalidSignature
+        );
+    }
+
+    #[test]
+    fn tampered_claims_are_rejected() {
+        let token = encode_access_token(&claims(), &key());
+        let mut parts: Vec<String> = token.split('.').map(str::to_string).collect();
+        let mut forged = claims();
+        forged.role = Role::Admin;
+        parts[1] = URL_SAFE_NO_PAD.encode(serde_json::to_vec(&forged).unwrap());
+
+        assert_err_eq!(
+            decode_access_token(&parts.join("."), &key(), Utc::now()),
+            TokenError::InvalidSignature
+        );
+    }
+
+    #[test]
+    fn unsigned_tokens_are_rejected() {
+        let header = URL_SAFE_NO_PAD.encode(r#"{"alg":"none","typ":"JWT"}"#);
+        let claims = URL_SAFE_NO_PAD.encode(serde_json::to_vec(&claims()).unwrap());
+
+        assert_err_eq!(
+            decode_access_token(&format!("{header}.{claims}."), &key(), Utc::now()),
+            TokenError::InvalidSignature
+        );
+    }
+
+    #[test]
+    fn expired_tokens_are_rejected() {
+        let claims = AccessClaims::new(
+            Uuid::new_v4(),
+            Role::User,
+            Utc::now() - Duration::minutes(20),
+            Duration::minutes(15),
+        );
+        let token = encode_access_token(&claims, &key());
+
+        assert_err_eq!(
+            decode_access_token(&token, &key(), Utc::now()),
+            TokenError::Expired
+        );
+    }
+
+    #[test]
+    fn malformed_tokens_are_rejected() {
+        for token in ["", "abc", "a.b", "a.b.c.d", "a.b.!!!"] {
+            assert_err_eq!(
+                decode_access_token(token, &key(), Utc::now()),
+                TokenError::Malformed,
+                "{token}"
+            );
+        }
+    }
+}
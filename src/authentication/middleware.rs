@@ -4,15 +4,35 @@ use std::{
 };
 
 use actix_web::{
-    FromRequest, HttpMessage,
+    FromRequest, HttpMessage, HttpResponse,
     body::MessageBody,
     dev::{ServiceRequest, ServiceResponse},
-    http::StatusCode,
+    error,
+    http::{
+        Method, StatusCode,
+        header::{self, HeaderName, HeaderValue},
+    },
     middleware::Next,
+    web::Data,
 };
+use chrono::{Duration, Utc};
+use sqlx::PgPool;
 use uuid::Uuid;
 
-use crate::{session_state::TypedSession, utils};
+use crate::{
+    authentication::decode_access_token,
+    configuration::{JwtSettings, SessionSettings},
+    domain::{PERSONAL_ACCESS_TOKEN_PREFIX, Permission, Role, Suspension, TokenScope},
+    repository,
+    session_state::{REMEMBER_ME_COOKIE, TypedSession},
+    utils::{self, ErrorResponse},
+};
+
+// How long a successful re-authentication unlocks sensitive operations for
+pub const SUDO_MODE_TTL: Duration = Duration::minutes(5);
+
+// Carries the admin's id on every response while they impersonate the logged in user
+pub const IMPERSONATED_BY_HEADER: HeaderName = HeaderName::from_static("x-impersonated-by");
 
 #[derive(Copy, Clone, Debug)]
 pub struct UserId(Uuid);
@@ -31,78 +51,274 @@ impl Deref for UserId {
     }
 }
 
-#[derive(Copy, Clone, Debug)]
-pub struct IsAdmin(bool);
-
-impl Display for IsAdmin {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        self.0.fmt(f)
-    }
-}
-
-impl Deref for IsAdmin {
-    type Target = bool;
-
-    fn deref(&self) -> &Self::Target {
-        &self.0
-    }
-}
-
-// Middleware that rejects requests from unauthenticated users
+// Middleware that rejects requests from unauthenticated users. Accepts a session cookie, or an
+// `Authorization: Bearer` JWT or personal access token. A request presenting a bad token is
+// rejected rather than checked against its session.
 pub async fn reject_anonymous_users(
     mut req: ServiceRequest,
     next: Next<impl MessageBody>,
 ) -> Result<ServiceResponse<impl MessageBody>, actix_web::Error> {
+    if let Some(token) = bearer_token(&req) {
+        let (user_id, role) = if token.starts_with(PERSONAL_ACCESS_TOKEN_PREFIX) {
+            personal_access_token_owner(&req, &token).await?
+        } else {
+            let jwt_settings = req.app_data::<Data<JwtSettings>>().ok_or_else(|| {
+                utils::app_error(StatusCode::INTERNAL_SERVER_ERROR, "Missing JWT settings")
+            })?;
+            let claims = decode_access_token(&token, &jwt_settings.signing_key, Utc::now())
+                .map_err(|e| utils::app_error(StatusCode::UNAUTHORIZED, e))?;
+            (claims.sub, claims.role)
+        };
+
+        reject_suspended_user(&req, user_id).await?;
+        req.extensions_mut().insert(UserId(user_id));
+        req.extensions_mut().insert(role);
+        return next.call(req).await;
+    }
+
     let session = {
         let (http_request, payload) = req.parts_mut();
         TypedSession::from_request(http_request, payload).await
     }?;
 
-    let user_id = session
+    let user_id = match session
         .get_user_id()
         .map_err(|e| utils::app_error(StatusCode::INTERNAL_SERVER_ERROR, e))?
-        .ok_or_else(|| utils::app_error(StatusCode::UNAUTHORIZED, "User has not logged in"))?;
+    {
+        Some(user_id) => user_id,
+        None => resume_remembered_session(&req, &session)
+            .await?
+            .ok_or_else(|| utils::app_error(StatusCode::UNAUTHORIZED, "User has not logged in"))?,
+    };
 
-    let is_admin = session
-        .get_is_admin()
+    let role = session
+        .get_role()
         .map_err(|e| utils::app_error(StatusCode::INTERNAL_SERVER_ERROR, e))?
         .ok_or_else(|| utils::app_error(StatusCode::UNAUTHORIZED, "User has not logged in"))?;
+    let impersonator_id = session
+        .get_impersonator_id()
+        .map_err(|e| utils::app_error(StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    // An impersonation runs in the admin's own session, so that is the one tracked. A suspended
+    // user can still be impersonated, otherwise the admin could not end it.
+    ensure_session_is_active(&req, session, impersonator_id.unwrap_or(user_id)).await?;
+    if impersonator_id.is_none() {
+        reject_suspended_user(&req, user_id).await?;
+    }
 
     req.extensions_mut().insert(UserId(user_id));
-    req.extensions_mut().insert(IsAdmin(is_admin));
-    next.call(req).await
+    req.extensions_mut().insert(role);
+    let Some(admin_id) = impersonator_id else {
+        return next.call(req).await;
+    };
+
+    let value = HeaderValue::from_str(&admin_id.to_string())
+        .map_err(|e| utils::app_error(StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    match next.call(req).await {
+        Ok(mut response) => {
+            response.headers_mut().insert(IMPERSONATED_BY_HEADER, value);
+            Ok(response)
+        }
+        // Errors only become responses outside the app, so build it here to attach the header
+        Err(e) => {
+            let mut response = e.error_response();
+            response.headers_mut().insert(IMPERSONATED_BY_HEADER, value);
+            Err(error::InternalError::from_response(e, response).into())
+        }
+    }
 }
 
-// Middleware that rejects requests from non-admin users
-pub async fn reject_non_admin_users(
-    mut req: ServiceRequest,
-    next: Next<impl MessageBody>,
-) -> Result<ServiceResponse<impl MessageBody>, actix_web::Error> {
-    let session = {
-        let (http_request, payload) = req.parts_mut();
-        TypedSession::from_request(http_request, payload).await
-    }?;
+// A browser that dropped its session cookie but kept the remember-me cookie is logged back into
+// the session it remembers, under a new session cookie
+async fn resume_remembered_session(
+    req: &ServiceRequest,
+    session: &TypedSession,
+) -> Result<Option<Uuid>, actix_web::Error> {
+    let Some(cookie) = req.cookie(REMEMBER_ME_COOKIE) else {
+        return Ok(None);
+    };
+    let pool = req.app_data::<Data<PgPool>>().ok_or_else(|| {
+        utils::app_error(StatusCode::INTERNAL_SERVER_ERROR, "Missing database pool")
+    })?;
+    let settings = req.app_data::<Data<SessionSettings>>().ok_or_else(|| {
+        utils::app_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Missing session settings",
+        )
+    })?;
 
-    let user_id = session
-        .get_user_id()
-        .map_err(|e| utils::app_error(StatusCode::INTERNAL_SERVER_ERROR, e))?
-        .ok_or_else(|| utils::app_error(StatusCode::UNAUTHORIZED, "User has not logged in"))?;
+    let Some(remembered) =
+        repository::resume_remembered_session(&utils::hash_token(cookie.value()), settings, pool)
+            .await
+            .map_err(|e| utils::app_error(StatusCode::INTERNAL_SERVER_ERROR, e))?
+    else {
+        return Ok(None);
+    };
+
+    session.renew();
+    session
+        .insert_user_id(remembered.user_id)
+        .and_then(|_| session.insert_role(remembered.role))
+        .and_then(|_| session.insert_session_id(remembered.session_id))
+        .map_err(|e| utils::app_error(StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    Ok(Some(remembered.user_id))
+}
+
+// Sessions revoked by their user or past a timeout in `SessionSettings` are logged out here, on
+// their next request. Every session
+// created at login has a tracking row, so one without is from before sessions were tracked and
+// has to log in again.
+async fn ensure_session_is_active(
+    req: &ServiceRequest,
+    session: TypedSession,
+    user_id: Uuid,
+) -> Result<(), actix_web::Error> {
+    let pool = req.app_data::<Data<PgPool>>().ok_or_else(|| {
+        utils::app_error(StatusCode::INTERNAL_SERVER_ERROR, "Missing database pool")
+    })?;
+    let settings = req.app_data::<Data<SessionSettings>>().ok_or_else(|| {
+        utils::app_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Missing session settings",
+        )
+    })?;
+    let session_id = session
+        .get_session_id()
+        .map_err(|e| utils::app_error(StatusCode::INTERNAL_SERVER_ERROR, e))?;
 
-    let is_admin = session
-        .get_is_admin()
+    let is_active = match session_id {
+        Some(session_id) => repository::touch_session(session_id, user_id, settings, pool)
+            .await
+            .map_err(|e| utils::app_error(StatusCode::INTERNAL_SERVER_ERROR, e))?,
+        None => false,
+    };
+    if !is_active {
+        session.log_out();
+        return Err(utils::app_error(
+            StatusCode::UNAUTHORIZED,
+            "Session has been revoked or has expired",
+        ));
+    }
+
+    Ok(())
+}
+
+// Banned users keep their sessions and tokens, which work again once the ban is over
+async fn reject_suspended_user(
+    req: &ServiceRequest,
+    user_id: Uuid,
+) -> Result<(), actix_web::Error> {
+    let pool = req.app_data::<Data<PgPool>>().ok_or_else(|| {
+        utils::app_error(StatusCode::INTERNAL_SERVER_ERROR, "Missing database pool")
+    })?;
+    let suspension = repository::get_suspension(user_id, pool)
+        .await
+        .map_err(|e| utils::app_error(StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    match suspension {
+        Some(suspension) => {
+            let message = suspension.to_string();
+            Err(
+                error::InternalError::from_response(message, suspended_response(&suspension))
+                    .into(),
+            )
+        }
+        None => Ok(()),
+    }
+}
+
+// Tells a banned user why and for how long, wherever they are turned away
+pub fn suspended_response(suspension: &Suspension) -> HttpResponse {
+    HttpResponse::Forbidden().json(ErrorResponse {
+        code: StatusCode::FORBIDDEN.as_u16(),
+        message: suspension.to_string(),
+        error: Some("account_suspended"),
+    })
+}
+
+// Read-only tokens are turned away from anything but GET and HEAD requests
+async fn personal_access_token_owner(
+    req: &ServiceRequest,
+    token: &str,
+) -> Result<(Uuid, Role), actix_web::Error> {
+    let pool = req.app_data::<Data<PgPool>>().ok_or_else(|| {
+        utils::app_error(StatusCode::INTERNAL_SERVER_ERROR, "Missing database pool")
+    })?;
+    let owner = repository::use_personal_access_token(&utils::hash_token(token), pool)
+        .await
         .map_err(|e| utils::app_error(StatusCode::INTERNAL_SERVER_ERROR, e))?
         .ok_or_else(|| {
-            utils::app_error(StatusCode::UNAUTHORIZED, "Missing admin flag in session")
+            utils::app_error(StatusCode::UNAUTHORIZED, "Invalid personal access token")
         })?;
 
-    if !is_admin {
+    let is_read = matches!(*req.method(), Method::GET | Method::HEAD);
+    if !is_read && !owner.scopes.contains(&TokenScope::Write) {
         return Err(utils::app_error(
             StatusCode::FORBIDDEN,
-            "Admin privileges required",
+            "Personal access token lacks the write scope",
+        ));
+    }
+
+    Ok((owner.user_id, owner.role))
+}
+
+fn bearer_token(req: &ServiceRequest) -> Option<String> {
+    let value = req.headers().get(header::AUTHORIZATION)?.to_str().ok()?;
+    let (scheme, token) = value.split_once(' ')?;
+    scheme
+        .eq_ignore_ascii_case("Bearer")
+        .then(|| token.trim().to_string())
+}
+
+// Middleware for routes restricted to roles with `permission`, must be nested inside
+// `reject_anonymous_users`. Wrap routes with `from_fn(move |req, next| require_permission(...))`.
+pub async fn require_permission(
+    permission: Permission,
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, actix_web::Error> {
+    let role = req
+        .extensions()
+        .get::<Role>()
+        .copied()
+        .ok_or_else(|| utils::app_error(StatusCode::UNAUTHORIZED, "User has not logged in"))?;
+
+    if !role.has(permission) {
+        return Err(utils::app_error(
+            StatusCode::FORBIDDEN,
+            "Insufficient permissions",
         ));
     }
 
-    req.extensions_mut().insert(UserId(user_id));
-    req.extensions_mut().insert(IsAdmin(is_admin));
     next.call(req).await
 }
+
+// Middleware for sensitive operations, must be nested inside `reject_anonymous_users`.
+// Rejects the request unless the user re-entered their password within `SUDO_MODE_TTL`.
+pub async fn require_sudo_mode(
+    mut req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, actix_web::Error> {
+    let session = {
+        let (http_request, payload) = req.parts_mut();
+        TypedSession::from_request(http_request, payload).await
+    }?;
+
+    let sudo_until = session
+        .get_sudo_until()
+        .map_err(|e| utils::app_error(StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    match sudo_until {
+        Some(sudo_until) if sudo_until > Utc::now() => next.call(req).await,
+        _ => {
+            let message = "Recent re-authentication required";
+            let response = HttpResponse::Unauthorized().json(ErrorResponse {
+                code: StatusCode::UNAUTHORIZED.as_u16(),
+                message: message.to_string(),
+                error: Some("reauthentication_required"),
+            });
+            Err(error::InternalError::from_response(message, response).into())
+        }
+    }
+}
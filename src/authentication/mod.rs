@@ -1,7 +1,13 @@
+mod jwt;
 mod middleware;
 mod password;
 
-pub use middleware::{IsAdmin, UserId, reject_anonymous_users, reject_non_admin_users};
+pub use jwt::{AccessClaims, TokenError, decode_access_token, encode_access_token};
+pub use middleware::{
+    IMPERSONATED_BY_HEADER, SUDO_MODE_TTL, UserId, reject_anonymous_users, require_permission,
+    require_sudo_mode, suspended_response,
+};
 pub use password::{
-    AuthError, Credentials, change_password, compute_password_hash, validate_credentials,
+    AuthError, Credentials, change_password, compute_password_hash, is_recently_used_password,
+    validate_credentials,
 };
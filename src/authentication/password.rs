@@ -4,10 +4,13 @@ use argon2::{
     password_hash::SaltString,
 };
 use secrecy::{ExposeSecret, Secret};
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, Transaction};
 use uuid::Uuid;
 
-use crate::{repository, telemetry};
+use crate::{
+    domain::{AuditAction, TokenPurpose},
+    repository, telemetry,
+};
 
 #[derive(thiserror::Error, Debug)]
 pub enum AuthError {
@@ -58,12 +61,15 @@ pub async fn validate_credentials(
         .map_err(AuthError::InvalidCredentials)
 }
 
+// A stored value that is not a PHC string, like the placeholder of imported users, matches no
+// password
 fn verify_password_hash(
     expected_password_hash: Secret<String>,
     password_candidate: Secret<String>,
 ) -> Result<(), AuthError> {
     let expected_password_hash = PasswordHash::new(expected_password_hash.expose_secret())
-        .context("Failed to parse hash in PHC string format.")?;
+        .context("Failed to parse hash in PHC string format.")
+        .map_err(AuthError::InvalidCredentials)?;
 
     Argon2::default()
         .verify_password(
@@ -74,19 +80,63 @@ fn verify_password_hash(
         .map_err(AuthError::InvalidCredentials)
 }
 
+// Whether `password` is the user's current one or among the `history_size - 1` they had
+// before it. A `history_size` of 0 allows any password.
 #[tracing::instrument(skip(password, pool))]
-pub async fn change_password(
+pub async fn is_recently_used_password(
     user_id: Uuid,
     password: Secret<String>,
+    history_size: u16,
     pool: &PgPool,
+) -> Result<bool, anyhow::Error> {
+    if history_size == 0 {
+        return Ok(false);
+    }
+    let hashes =
+        repository::get_recent_password_hashes(user_id, i64::from(history_size) - 1, pool).await?;
+
+    telemetry::spawn_blocking_with_tracing(move || {
+        hashes
+            .into_iter()
+            .any(|hash| verify_password_hash(hash, password.clone()).is_ok())
+    })
+    .await
+    .context("Failed to spawn blocking task.")
+}
+
+// Keeps the replaced hash so that `is_recently_used_password` can still recognise it. Whoever
+// knew the old password is signed out, every session but `keep_session` and every refresh token
+// is revoked. Runs in the caller's transaction, so a password reset consumes its token and sets
+// the password at once.
+#[tracing::instrument(skip(password, transaction))]
+pub async fn change_password(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    password: Secret<String>,
+    history_size: u16,
+    keep_session: Option<Uuid>,
 ) -> Result<(), anyhow::Error> {
     let password_hash =
         telemetry::spawn_blocking_with_tracing(move || compute_password_hash(password))
             .await?
             .context("Failed to hash password")?;
 
-    repository::update_password_hash(user_id, password_hash, pool).await
+    let keep = i64::from(history_size.saturating_sub(1));
+    repository::update_password_hash(transaction, user_id, password_hash, keep).await?;
+    repository::revoke_all_sessions(transaction, user_id, keep_session).await?;
+    repository::revoke_refresh_tokens_of_user(transaction, user_id).await?;
+    // A reset link sent before the change must not undo it
+    repository::delete_tokens_of_user(transaction, user_id, TokenPurpose::PasswordReset).await?;
+    repository::record_audit_event(
+        &mut **transaction,
+        AuditAction::PasswordChange,
+        Some(user_id),
+        Some(user_id),
+        serde_json::json!({}),
+    )
+    .await
 }
+
 pub fn compute_password_hash(password: Secret<String>) -> Result<Secret<String>, anyhow::Error> {
     let salt = SaltString::generate(&mut rand::thread_rng());
     let password_hash = Argon2::new(
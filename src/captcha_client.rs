@@ -0,0 +1,139 @@
+use std::time::Duration;
+
+use reqwest::{Client, Url};
+use secrecy::{ExposeSecret, Secret};
+
+// Verifies CAPTCHA tokens against a siteverify endpoint, the protocol shared by Turnstile,
+// hCaptcha and reCAPTCHA
+#[derive(Debug)]
+pub struct CaptchaClient {
+    http_client: Client,
+    verify_url: Url,
+    secret: Secret<String>,
+}
+
+#[derive(serde::Serialize)]
+struct VerifyRequest<'a> {
+    secret: &'a str,
+    response: &'a str,
+    remoteip: &'a str,
+}
+
+#[derive(serde::Deserialize)]
+struct VerifyResponse {
+    success: bool,
+}
+
+impl CaptchaClient {
+    pub fn new(verify_url: Url, secret: Secret<String>, timeout: Duration) -> Self {
+        let http_client = Client::builder()
+            .timeout(timeout)
+            .build()
+            // Safe to use `expect` as builder only fails on invalid TLS/config, not a simple timeout setup
+            .expect("Reqwest HTTP client with a simple timeout should always build successfully");
+
+        Self {
+            http_client,
+            verify_url,
+            secret,
+        }
+    }
+
+    // `Ok(false)` means the provider rejected the token, errors mean it could not be asked
+    pub async fn verify(&self, token: &str, remote_ip: &str) -> Result<bool, reqwest::Error> {
+        let request = VerifyRequest {
+            secret: self.secret.expose_secret(),
+            response: token,
+            remoteip: remote_ip,
+        };
+
+        let response: VerifyResponse = self
+            .http_client
+            .post(self.verify_url.clone())
+            .form(&request)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Ok(response.success)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use claims::{assert_err, assert_ok_eq};
+    use reqwest::Url;
+    use secrecy::Secret;
+    use wiremock::{Mock, MockServer, ResponseTemplate, matchers};
+
+    use crate::captcha_client::CaptchaClient;
+
+    fn captcha_client(server: &MockServer) -> CaptchaClient {
+        CaptchaClient::new(
+            Url::parse(&format!("{}/siteverify", server.uri())).unwrap(),
+            Secret::new("captcha-secret".to_string()),
+            Duration::from_millis(200),
+        )
+    }
+
+    #[tokio::test]
+    async fn verify_sends_the_secret_and_token_as_a_form() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/siteverify"))
+            .and(matchers::body_string_contains("secret=captcha-secret"))
+            .and(matchers::body_string_contains("response=token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let outcome = captcha_client(&mock_server)
+            .verify("token", "127.0.0.1")
+            .await;
+
+        assert_ok_eq!(outcome, true);
+    }
+
+    #[tokio::test]
+    async fn verify_returns_false_for_rejected_tokens() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(matchers::any())
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": false,
+                "error-codes": ["invalid-input-response"]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let outcome = captcha_client(&mock_server)
+            .verify("bad-token", "127.0.0.1")
+            .await;
+
+        assert_ok_eq!(outcome, false);
+    }
+
+    #[tokio::test]
+    async fn verify_fails_if_the_server_returns_500() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(matchers::any())
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&mock_server)
+            .await;
+
+        let outcome = captcha_client(&mock_server)
+            .verify("token", "127.0.0.1")
+            .await;
+
+        assert_err!(outcome);
+    }
+}
@@ -0,0 +1,765 @@
+use serde::Serialize;
+
+// Client facing API changes, newest release first. Add an entry to the current release
+// whenever an endpoint, parameter or response field is added, changed or deprecated.
+pub const CHANGELOG: &[Release] = &[Release {
+    version: "0.1.0",
+    changes: &[
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "POST /v1/user/me/sudo",
+            description: "Password checks count against the login throttle of the user and the client address. Responds 429 with `Retry-After` once it is exhausted, as does `DELETE /v1/user/me`.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "POST /v1/webhooks/postmark",
+            description: "Authenticated with the HTTP basic auth credentials of the webhook URL instead of an `X-Postmark-Signature` header, which Postmark never sends. Responds 401 with `WWW-Authenticate` for missing or wrong credentials.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "GET /v1/admin/me/newsletters/{id}/status",
+            description: "A week after sending, the recipients of an issue are removed from its status and failed deliveries can no longer be resent. Issues that reached anyone are kept with their delivery, open and click counts, so the tracked links in their emails keep redirecting.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "POST /v1/user/forgot-password",
+            description: "The reset link is emailed shortly after the 202 rather than before it. Asking again before it went out sends one email. Shares a budget of 10 requests per client address an hour with `POST /v1/user/login/magic-link`, responding 429 with `Retry-After` beyond it. The same applies to sign-in links.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "POST /v1/admin/me/subscribers/import",
+            description: "Imports subscribers from CSV, up to 10,000 rows with the email in the first column and an optional `email` header row. Unknown addresses get an account and are subscribed, or with `double_opt_in=true` are sent a confirmation link first. Addresses that already belong to an account are left as they are. Reports the `status` of every `row`: `subscribed`, `pending_confirmation`, `existing` or `invalid`, with an `error` for invalid rows and unsent confirmations.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "POST /v1/admin/me/suppressions",
+            description: "Puts an `email` on the suppression list, responding 201, 400 for invalid addresses and 409 for addresses already on it. Neither newsletters nor account emails are sent to suppressed addresses, newsletters count them as `skipped` in the issue status. `GET /v1/admin/me/suppressions` lists them with their `reason`, `spam_complaint`, `hard_bounce` or `manual`, newest first, paginated with `page` and `limit`. `DELETE /v1/admin/me/suppressions/{email}` removes an address, and its bounce, from the list, or responds 404.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "POST /v1/webhooks/postmark",
+            description: "Permanent bounces also put the address on the suppression list, account emails are no longer sent to it either.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "POST /v1/admin/me/newsletters/publish",
+            description: "Accepts optional `attachments`, each a `name`, `content_type` and base64 encoded `content`, sent along with every email of the issue, test sends included. Up to 5 PDF, PNG, JPEG, GIF, plain text or CSV files of at most 5 MB together. Files whose content does not match their `content_type` are refused with 400. Also applies to drafts.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "POST /v1/admin/me/newsletters/publish",
+            description: "Accepts an optional `alternative_title`, a second subject line that must differ from `title`. Recipients are split evenly between the two, and the status reports `subject_test` with the `sent`, `opened` and `open_rate` of each variant and the `winner`, null until both were sent or while tied. `subject_test` is null for issues published without one.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "GET /v1/newsletters",
+            description: "Public HTML archive of published newsletter issues, newest first, paginated with `page` and `limit`. `GET /v1/newsletters/{id}` renders one issue as subscribers received it, without anyone's name or unsubscribe link. Drafts, cancelled issues, issues sent to a segment only and scheduled issues not yet published are not listed.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "POST /v1/admin/me/newsletters/{id}/retry-failed",
+            description: "Queues the issue again for the recipients listed in its status `failures` only, each with a fresh set of retries. Responds with the number of `requeued_deliveries`, 404 for unknown issues and 409 for drafts and cancelled issues.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "GET /v1/admin/me/newsletters/{id}/status",
+            description: "`failures` lists the issue's dead-lettered deliveries: those to invalid addresses and those still failing after the configured number of retries, with the error of their last attempt and how many attempts were made.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "POST /v1/admin/me/newsletters/preview",
+            description: "Renders a `title` and `content`, as accepted by `POST /v1/admin/me/newsletters/publish`, into the `html` and `text` a subscriber would receive, placeholders filled in for the requesting admin. Nothing is stored. Responds 400 for content that could not be published.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "POST /v1/admin/me/newsletters/{id}/test-send",
+            description: "Sends the issue, or draft, to the requesting admin's own email only, with placeholders filled in for them. Nothing is queued or counted in the issue's status. Responds with the address it was `sent_to`.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "POST /v1/admin/me/newsletters/publish",
+            description: "`content` may contain the placeholders `{{user_name}}` and `{{unsubscribe_url}}`, replaced for every recipient. Content with any other placeholder is refused with 400. Also applies to drafts.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "POST /v1/admin/me/newsletters/segments",
+            description: "Creates a subscriber segment by signup date (`signed_up_after`, `signed_up_before`) and `active_within_days`, the subscribers who logged in that recently. Responds 201 with the `segment_id`, or 409 if the name is taken. `GET` lists the segments.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "POST /v1/admin/me/newsletters/publish",
+            description: "Accepts an optional `segment_id`, the issue is then sent only to the subscribers in that segment. The status reports the `segment_id`.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "POST /v1/webhooks/postmark",
+            description: "Spam complaints put the address on a suppression list. Neither newsletters nor account emails are sent to it anymore.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "POST /v1/webhooks/postmark",
+            description: "Receives Postmark events, signed with the base64 HMAC-SHA256 of the body in `X-Postmark-Signature`. Permanent bounces mark the address as undeliverable, newsletters are no longer sent to it. Responds 401 for a missing or invalid signature.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "GET /v1/admin/me/newsletters/{id}/status",
+            description: "Adds `clicks` on the issue's links, `clicked`, the recipients who clicked at least one, `click_rate`, their share of `sent`, and `links`, the clicks and `unique_clicks` of each link by `position`. Links in delivered issues now redirect through `/t/click/{token}`.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "GET /v1/admin/me/newsletters/{id}/status",
+            description: "Adds `opened`, the recipients who opened the issue, and `open_rate`, their share of the issue's `sent` deliveries. Opens are counted only when the mail client loads images.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "POST /v1/admin/me/newsletters/publish",
+            description: "`content` accepts a `markdown` body instead of `html` and `text`, both are then rendered from it. Also applies to drafts.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "DELETE /v1/admin/me/newsletters/{id}/queue",
+            description: "Cancels a published issue, removing the deliveries still queued and stopping its fan-out. Responds with the number of `removed_deliveries`, or 409 for drafts. The status reports `fan_out_status` `cancelled`.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "POST /v1/admin/me/newsletters/publish",
+            description: "Accepts an optional future `scheduled_at`. Delivery starts once it has passed. The issue list and status report `scheduled_at`.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "PUT /v1/admin/me/newsletters/{id}/schedule",
+            description: "Moves a scheduled issue to a new future `scheduled_at`. Responds 409 once the issue has started sending or was never scheduled.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "POST /v1/admin/me/newsletters",
+            description: "Saves a newsletter issue as a draft, with the same body as `POST /v1/admin/me/newsletters/publish`. Nothing is sent until it is published. Responds 201 with the `issue_id`.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "PUT /v1/admin/me/newsletters/{id}",
+            description: "Replaces the title and content of a draft. Responds 409 once the issue has been published.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "POST /v1/admin/me/newsletters/{id}/publish",
+            description: "Publishes a draft, which is then delivered like an issue sent with `POST /v1/admin/me/newsletters/publish`. Responds 409 if it was already published.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "GET /v1/admin/me/newsletters/{id}/status",
+            description: "Adds `sent`, `failed` and `skipped` delivery counts, `retrying_deliveries`, and `is_complete`, true once every delivery has left the queue. `failures` lists recipients that could not be reached, with the last error and the number of attempts.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "GET /v1/admin/me/newsletters",
+            description: "Lists newsletter issues newest first, drafts included, with `published_at` (null for drafts) and how many deliveries were enqueued, are pending and have completed. Paginate with `page` and `limit`.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "POST /v1/admin/me/users/{id}/impersonate",
+            description: "Switches the admin's session to the user, for support and debugging. Every response then carries `X-Impersonated-By` with the admin's id. Sudo mode is dropped. Admins cannot be impersonated. Start and end are recorded in the audit log.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "POST /v1/user/me/impersonation/end",
+            description: "Ends an impersonation and returns the session to the admin who started it. Responds 400 when not impersonating.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "POST /v1/user/register",
+            description: "Rate limited per client address. Responses carry `RateLimit-Limit`, `RateLimit-Remaining` and `RateLimit-Reset` headers, and 429 with `Retry-After` once the budget is spent.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "POST /v1/user/login",
+            description: "Also rate limited per client address, with the same `RateLimit-*` headers and 429 as `POST /v1/user/register`.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "GET /v1/user/me/request-subscription",
+            description: "Rate limited per user, with the same `RateLimit-*` headers and 429 as `POST /v1/user/register`.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "POST /v1/comment/me/create",
+            description: "Rate limited per user, with the same `RateLimit-*` headers and 429 as `POST /v1/user/register`.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "POST /v1/user/me/email/unsubscribe",
+            description: "Unsubscribes the caller from the newsletter. Issues already queued for them are not delivered.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "GET /v1/user/unsubscribe",
+            description: "Unsubscribes the owner of `token` from the newsletter without logging in. Every issue now ends with this link. The token never expires and can be used more than once.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "POST /v1/user/login/magic-link",
+            description: "Emails a sign-in link to the activated account using `email`. The link is valid for 15 minutes. Only the newest link works. Always answers 202, so it does not reveal who is registered.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "POST /v1/user/login/magic-link/verify",
+            description: "Logs in with the `token` from an emailed sign-in link and starts a session, like `POST /v1/user/login`. Each link works once. Suspended accounts get 403 with `error: \"account_suspended\"`.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "GET /v1/user/activation-reminders/opt-out",
+            description: "Stops activation reminder emails for the account behind `token`. Every reminder now carries this link alongside the activation link. The link keeps working for a week, so it can safely be followed twice.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "GET /v1/admin/me/audit-log",
+            description: "Lists security-relevant events newest first: logins, password changes, role changes, post hard deletes and newsletter publishes, each with its actor, target and details. Filter with `action`, `actor_id` and `target_id`, paginate with `page` and `limit`. Admins only.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "GET /v1/user/me/security/activity",
+            description: "Returns when the caller last logged in and their 50 most recent login attempts, failed ones included, with the IP address and user agent of each. Attempts are kept for 90 days.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "POST /v1/user/reset-password",
+            description: "Reset links stop working once the password is changed by any means. Links sent for activation, subscription, email changes and password resets are only accepted by their own endpoint.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "GET /v1/user/activate",
+            description: "Activation links expire 48 hours after they are sent. Expired links get a 410 rather than a 401, and each activation reminder sends a fresh link.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "GET /v1/user/subscribe",
+            description: "Subscription links expire 24 hours after they are sent, and expired links get a 410. Email change links also expire after 24 hours.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "POST /v1/admin/me/users/ban/{id}",
+            description: "Accepts an optional `reason` and `expires_at`. Banned users get a 403 with `error` `account_suspended` and a message giving the reason and expiry, both when logging in and on every authenticated request. `remove_content` together with `expires_at` is rejected with a 400, only permanent bans remove content. Bans no longer unsubscribe the user, they just get no newsletter issues while the ban lasts.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "POST /v1/admin/me/users/unban/{id}",
+            description: "Lifts a ban before it expires. Content removed by the ban stays removed.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "PUT /v1/admin/me/users/{id}/role",
+            description: "Sets a user's role to `user`, `moderator` or `admin`. The user is logged out everywhere and picks up the new role on their next login.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "GET /v1/admin/me/comments",
+            description: "Open to moderators as well as admins, as are removing comments, comment reports and reviewing submissions. Every admin route also accepts bearer tokens.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "POST /v1/user/me/logout-all",
+            description: "Logs the caller out of every session and revokes all their refresh tokens. Access tokens that were already issued stay valid until they expire.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "POST /v1/user/me/change-password",
+            description: "Logs out every other session and revokes all refresh tokens. Resetting a password does the same for every session.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "POST /v1/user/login",
+            description: "Accepts `remember_me`. When true, a long-lived `remember_me` cookie resumes the session after the browser drops the session cookie, for a configurable number of days from login.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "GET /v1/user/me/sessions",
+            description: "Lists the caller's signed-in sessions with when they were created and last used, their IP address and user agent. The session making the request is marked `current`.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "DELETE /v1/user/me/sessions/{id}",
+            description: "Revokes one of the caller's sessions, which is logged out on its next request.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "POST /v1/user/token",
+            description: "Also returns a `refresh_token` that can be exchanged for a new access token without the password.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "POST /v1/user/token/refresh",
+            description: "Exchanges a refresh token for a new access token and refresh token. Each refresh token works once. Reusing one ends the sign-in it belongs to.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "POST /v1/user/token/revoke",
+            description: "Revokes a refresh token and every token issued from the same sign-in.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "POST /v1/user/me/tokens",
+            description: "Creates a named personal access token with `read` and/or `write` scope, for CLI tools and integrations. Requires sudo mode. The token is only shown in this response. Send it as `Authorization: Bearer`. Read-only tokens are limited to GET and HEAD requests.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "GET /v1/user/me/tokens",
+            description: "Lists the caller's active personal access tokens, without the tokens themselves.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "DELETE /v1/user/me/tokens/{id}",
+            description: "Revokes a personal access token.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "POST /v1/user/token",
+            description: "Exchanges a user name and password for a short-lived bearer token, throttled like login. Routes for signed-in users accept it in `Authorization: Bearer`, apart from admin routes and those needing sudo mode.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "POST /v1/user/register",
+            description: "Accepts an optional `captcha_token`. Responds 403 when CAPTCHA verification is enabled and the token is rejected.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "POST /v1/user/login",
+            description: "Responds 429 with a `Retry-After` header once a client address or user name makes too many attempts in a short window.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "POST /v1/user/forgot-password",
+            description: "Emails a password reset link, valid for an hour and only the newest one works. Responds 202 whether or not the address belongs to an account.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "POST /v1/user/reset-password",
+            description: "Sets a new password from the token of a reset link. Each token works once.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "POST /v1/posts/me/suggest-metadata",
+            description: "Suggests titles and tags for a draft body, best first. Nothing is saved.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "GET /v1/user/search",
+            description: "Searches users by name for mention autocompletion and author discovery. Names starting with `q` come first, followed by similar names. Paginated and rate limited per client.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "GET /v1/admin/me/db/bloat",
+            description: "Reports live and dead rows per table, with an estimate of the space held by dead rows and whether each table is past the configured bloat thresholds.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "PUT /v1/user/me/blocks/{id}",
+            description: "Blocks a user: their comments are hidden from the caller, they can no longer comment on the caller's posts, and neither can message the other.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "DELETE /v1/user/me/blocks/{id}",
+            description: "Unblocks a user.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "GET /v1/user/me/blocks",
+            description: "Lists the users the caller blocked.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "GET /v1/comment/get/posts/{id}",
+            description: "Leaves out comments by users the signed-in caller blocked.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "POST /v1/comment/me/create",
+            description: "Returns 403 when the post's author blocked the caller.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "POST /v1/messages",
+            description: "Sends a direct message to `recipient_id`, rate limited per sender.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "GET /v1/user/me/messages",
+            description: "Lists the caller's conversations with their latest messages and unread counts.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "PATCH /v1/user/me/messages/{id}/read",
+            description: "Marks the other user's messages in a conversation as read.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "PUT /v1/user/me/following/{id}",
+            description: "Follows a user, following someone already followed is a no-op. Responds 403 when either user blocked the other.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "DELETE /v1/user/me/following/{id}",
+            description: "Unfollows a user.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "GET /v1/user/me/feed",
+            description: "Lists posts by followed authors, paginated and sorted like `GET /v1/posts/get/all`.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "GET /v1/user/me/export",
+            description: "Streams a JSON archive of the caller's account, posts, comments, likes and newsletter subscription status.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "POST /v1/admin/me/newsletters/publish",
+            description: "Retrying with an `Idempotency-Key` whose issue has since been removed returns 409 with error `idempotency_conflict` instead of replaying the old response.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "GET /v1/user/me/posts/export",
+            description: "Compresses the export with zstd and sets `Content-Encoding: zstd` when the request's `Accept-Encoding` allows it.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "DELETE /v1/user/me",
+            description: "Deletes the caller's account after confirming their `password`, removing or anonymously keeping their content depending on the server's policy.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "GET /v1/user/{id}",
+            description: "Returns 404 for deleted accounts.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "POST /v1/posts/me/{id}/proposals",
+            description: "Proposes an edit to a community post, stored as pending until its author reviews it.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "GET /v1/posts/me/{id}/proposals",
+            description: "Lists a post's pending edit proposals for its author, flagging those written against an older version.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "PATCH /v1/posts/me/proposals/{id}/approve",
+            description: "Applies an edit proposal to the post; 409 when the post changed after the proposal was written.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "PATCH /v1/posts/me/proposals/{id}/reject",
+            description: "Rejects a pending edit proposal.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "POST /v1/posts/me/create",
+            description: "Accepts `community: true` to let other users propose edits; posts include `is_community`.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "POST /v1/user/me/email",
+            description: "Starts an email change in sudo mode by sending a confirmation link to the new address; 409 when another account uses it.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "GET /v1/user/confirm-email",
+            description: "Confirms a pending email change with the emailed `token`, after which the new address replaces the old one.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "PUT /v1/user/me/username",
+            description: "Changes the caller's `user_name` and responds with the updated profile; 409 when another account has the name.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "POST /v1/user/register",
+            description: "User names are unique regardless of case; registering a taken name returns 409.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "POST /v1/posts/me/create",
+            description: "Accepts an optional `license`; posts without one get the site-wide default.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "PATCH /v1/posts/me/update/{id}",
+            description: "Accepts an optional `license`; the current license is kept when it is omitted.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "GET /v1/posts/get/all",
+            description: "Each post includes its `license`, and `?license=` narrows the listing to one license.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "GET /v1/posts/get/{id}",
+            description: "The post includes its `license`.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "PATCH /v1/user/me/profile",
+            description: "Updates the caller's `bio`, `website` and `location`; omitted fields are kept and empty strings clear them.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "GET /v1/user/{id}",
+            description: "The profile includes `bio`, `website` and `location`, null when not set.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "PUT /v1/user/me/avatar",
+            description: "Uploads a PNG, JPEG, GIF or WebP avatar of at most 256 KiB as the raw request body; `DELETE` removes it.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "GET /v1/user/{id}/avatar",
+            description: "Serves a user's avatar image.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "GET /v1/posts/get/all",
+            description: "Posts include `created_by_avatar_url`, null when the author has no avatar.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "GET /v1/posts/get/{id}",
+            description: "The post includes `created_by_avatar_url`, null when the author has no avatar.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "GET /v1/comment/get/posts/{id}",
+            description: "Comments include `created_by_avatar_url`, null when the commenter has no avatar.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "GET /v1/user/{id}",
+            description: "The profile includes `avatar_url`, null when no avatar is set.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "GET /v1/meta/time",
+            description: "Returns the server time so clients can detect clock skew; every response also carries an `X-Server-Time` header.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "POST /v1/user/me/sudo",
+            description: "Returns `sudo_until`, when sudo mode expires by the server clock.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "GET /v1/user/{id}",
+            description: "Returns a user's public profile with their join date, post count and comment count.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "GET /v1/admin/me/users/activation-reminders",
+            description: "Reports how many activation reminders were sent and how many led to an activation.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "POST /v1/comment/anonymous",
+            description: "Submits a comment without an account for moderation when enabled; requires a `captcha_token`.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "GET /v1/comment/get/posts/{id}",
+            description: "Comments include `author_display_name`, set on approved anonymous comments.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "GET /v1/user/{id}/comments",
+            description: "Lists a user's comments with the title of the post each was left on.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "GET /v1/admin/me/db/post-bodies",
+            description: "Reports storage saved by deduplicating large post bodies.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "GET /v1/comment/get/posts/{id}",
+            description: "Comments include `created_by_name`, the commenter's user name.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "GET /v1/embed/posts/{id}/comments",
+            description: "Lists a post's comments for embedding, callable from allow-listed origins.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "POST /v1/embed/posts/{id}/comments",
+            description: "Submits an anonymous comment for moderation when enabled; rate limited per IP.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "GET /v1/admin/me/submissions",
+            description: "Lists embed and anonymous comment submissions awaiting review.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "POST /v1/admin/me/submissions/{id}/approve",
+            description: "Publishes a comment submission, returning its `comment_id`. Embed submissions are published by a new account for their `author_email`, or under their `author_name` alone when the address already belongs to an account, since it was never verified.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "POST /v1/admin/me/submissions/{id}/reject",
+            description: "Discards a comment submission.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "POST /v1/admin/me/comments/import",
+            description: "Imports NDJSON comments with a per-line error report; supports `dry_run`.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "POST /v1/comment/me/report/{id}",
+            description: "Flags a comment for review with a `reason`.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "GET /v1/admin/me/reports/comments",
+            description: "Reported comments with post title, reporter and reason.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "GET /v1/admin/me/db/indexes",
+            description: "Index usage, unused indexes and sequential scan counts per table.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "GET /v1/user/me/mentions",
+            description: "Comments that mention the caller as `@username`, newest first.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "GET /v1/admin/me/comments",
+            description: "Recent comments across posts, filterable by `user_id`, `post_id`, `since` and `until`.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "POST /v1/admin/me/comments/remove",
+            description: "Removes up to 500 comments by `comment_ids`.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "GET /v1/comment/get/posts/{id}",
+            description: "Query parameter `sort` accepting `newest` (default) and `oldest`.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "GET /v1/admin/me/workers",
+            description: "Newsletter delivery worker instances with heartbeat-based liveness.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "GET /v1/posts/get/all",
+            description: "Response field `comment_count`; `sort` accepts `comments` and `-comments`.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "GET /v1/posts/get/{id}",
+            description: "Response field `comment_count`.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "GET /v1/meta/changelog",
+            description: "Machine-readable list of API changes per release.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "GET /v1/admin/me/logging",
+            description: "Returns the active tracing filter directives.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "PUT /v1/admin/me/logging",
+            description: "Replaces the tracing filter directives at runtime.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "GET /v1/admin/me/newsletters/{id}/status",
+            description: "Fan-out progress and pending deliveries of a newsletter issue.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "POST /v1/admin/me/newsletters/publish",
+            description: "Responds with `issue_id` before deliveries are enqueued.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "GET /v1/comment/get/posts/{id}",
+            description: "Paginated with `page` and `limit`; response includes `metadata`.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "POST /v1/user/me/sudo",
+            description: "Re-authenticates to unlock sensitive operations for 5 minutes.",
+        },
+        ApiChange {
+            kind: ChangeKind::Changed,
+            endpoint: "POST /v1/user/me/change-password",
+            description: "Requires sudo mode, otherwise 401 with `error: reauthentication_required`.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "POST /v1/admin/me/users/ban/{id}",
+            description: "Bans a user, optionally removing their content.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "GET /v1/user/me/posts/export",
+            description: "Streams the caller's posts as JSON or Markdown.",
+        },
+        ApiChange {
+            kind: ChangeKind::Added,
+            endpoint: "GET /v1/posts/get/{id}",
+            description: "Response field `excerpt`.",
+        },
+        ApiChange {
+            kind: ChangeKind::Removed,
+            endpoint: "GET /v1/posts/get/all",
+            description: "Response field `text`, replaced by `excerpt`.",
+        },
+    ],
+}];
+
+#[derive(Serialize, Debug)]
+pub struct Release {
+    pub version: &'static str,
+    pub changes: &'static [ApiChange],
+}
+
+#[derive(Serialize, Debug)]
+pub struct ApiChange {
+    pub kind: ChangeKind,
+    pub endpoint: &'static str,
+    pub description: &'static str,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Changed,
+    Deprecated,
+    Removed,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CHANGELOG;
+
+    #[test]
+    fn latest_release_matches_crate_version() {
+        assert_eq!(CHANGELOG[0].version, env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn every_change_names_method_and_path() {
+        for change in CHANGELOG.iter().flat_map(|r| r.changes) {
+            let (method, path) = change.endpoint.split_once(' ').unwrap();
+            assert!(["GET", "POST", "PUT", "PATCH", "DELETE"].contains(&method));
+            assert!(path.starts_with("/v1/"), "{}", change.endpoint);
+        }
+    }
+}
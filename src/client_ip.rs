@@ -0,0 +1,105 @@
+use std::net::IpAddr;
+
+use actix_web::{HttpRequest, http::header::HeaderMap, web::Data};
+
+const X_FORWARDED_FOR: &str = "x-forwarded-for";
+
+// Proxies in front of the app, from `application.trusted_proxies`. Only their forwarded headers
+// are believed, anyone else could put any address in them.
+#[derive(Clone, Debug, Default)]
+pub struct TrustedProxies(pub Vec<IpAddr>);
+
+impl TrustedProxies {
+    // Each proxy appends the address it received the request from to `X-Forwarded-For`, so the
+    // client is the rightmost entry not added by one of ours. Without trusted proxies the peer is
+    // the client, whatever the headers say.
+    fn client_ip(&self, peer: IpAddr, headers: &HeaderMap) -> IpAddr {
+        let mut client = peer;
+        let forwarded = headers
+            .get_all(X_FORWARDED_FOR)
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .collect::<Vec<_>>();
+
+        for entry in forwarded.into_iter().rev() {
+            if !self.0.contains(&client) {
+                break;
+            }
+            match entry.trim().parse() {
+                Ok(ip) => client = ip,
+                Err(_) => break,
+            }
+        }
+
+        client
+    }
+}
+
+// Address of the client that sent the request, for rate limits and login history. `None` only
+// for requests without a peer, e.g. in unit tests.
+pub fn client_ip(req: &HttpRequest) -> Option<IpAddr> {
+    let peer = req.peer_addr()?.ip();
+    let client_ip = match req.app_data::<Data<TrustedProxies>>() {
+        Some(trusted_proxies) => trusted_proxies.client_ip(peer, req.headers()),
+        None => peer,
+    };
+    Some(client_ip)
+}
+
+// `client_ip` as text, for rate limit keys and records of who did what
+pub fn client_address(req: &HttpRequest) -> String {
+    client_ip(req).map_or_else(|| "unknown".to_string(), |ip| ip.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+
+    use actix_web::http::header::{HeaderMap, HeaderName, HeaderValue};
+
+    use super::TrustedProxies;
+
+    fn forwarded_for(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("x-forwarded-for"),
+            HeaderValue::from_str(value).unwrap(),
+        );
+        headers
+    }
+
+    fn ip(value: &str) -> IpAddr {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn forwarded_headers_of_untrusted_peers_are_ignored() {
+        let trusted = TrustedProxies(vec![ip("10.0.0.1")]);
+
+        let client = trusted.client_ip(ip("203.0.113.7"), &forwarded_for("198.51.100.1"));
+
+        assert_eq!(client, ip("203.0.113.7"));
+    }
+
+    #[test]
+    fn the_address_a_trusted_proxy_received_the_request_from_is_the_client() {
+        let trusted = TrustedProxies(vec![ip("10.0.0.1"), ip("10.0.0.2")]);
+
+        // The client claims to be 198.51.100.1, the proxies saw 203.0.113.7
+        let client = trusted.client_ip(
+            ip("10.0.0.1"),
+            &forwarded_for("198.51.100.1, 203.0.113.7, 10.0.0.2"),
+        );
+
+        assert_eq!(client, ip("203.0.113.7"));
+    }
+
+    #[test]
+    fn unparseable_forwarded_addresses_are_not_trusted() {
+        let trusted = TrustedProxies(vec![ip("10.0.0.1")]);
+
+        let client = trusted.client_ip(ip("10.0.0.1"), &forwarded_for("not-an-ip"));
+
+        assert_eq!(client, ip("10.0.0.1"));
+    }
+}
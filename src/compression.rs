@@ -0,0 +1,156 @@
+use std::{
+    io::Write,
+    pin::Pin,
+    task::{Context, Poll, ready},
+};
+
+use actix_web::{HttpRequest, http::header, web::Bytes};
+use anyhow::Context as _;
+use futures_util::Stream;
+use zstd::stream::write::Encoder;
+
+// Whether the client listed `zstd` in Accept-Encoding without ruling it out with `q=0`
+pub fn accepts_zstd(req: &HttpRequest) -> bool {
+    req.headers()
+        .get_all(header::ACCEPT_ENCODING)
+        .filter_map(|value| value.to_str().ok())
+        .any(accept_encoding_allows_zstd)
+}
+
+fn accept_encoding_allows_zstd(value: &str) -> bool {
+    value.split(',').any(|coding| {
+        let mut params = coding.split(';');
+        let name = params.next().unwrap_or_default().trim();
+        let quality = params
+            .find_map(|param| param.trim().strip_prefix("q="))
+            .map_or(Some(1.0), |q| q.trim().parse::<f32>().ok());
+
+        name.eq_ignore_ascii_case("zstd") && quality.is_some_and(|q| q > 0.0)
+    })
+}
+
+// Compresses a streamed body chunk by chunk. Each chunk is flushed as its own zstd block so the
+// client keeps receiving data while the export is still being produced, instead of waiting for
+// the encoder to fill a window.
+pub struct ZstdStream<S> {
+    inner: Pin<Box<S>>,
+    encoder: Option<Encoder<'static, Vec<u8>>>,
+    bytes_in: u64,
+    bytes_out: u64,
+}
+
+impl<S> ZstdStream<S> {
+    pub fn new(inner: S, level: i32) -> Result<Self, anyhow::Error> {
+        let encoder = Encoder::new(Vec::new(), level).context("Failed to create zstd encoder")?;
+
+        Ok(Self {
+            inner: Box::pin(inner),
+            encoder: Some(encoder),
+            bytes_in: 0,
+            bytes_out: 0,
+        })
+    }
+}
+
+impl<S, E> Stream for ZstdStream<S>
+where
+    S: Stream<Item = Result<Bytes, E>>,
+    E: From<anyhow::Error>,
+{
+    type Item = Result<Bytes, E>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            let Some(encoder) = this.encoder.as_mut() else {
+                return Poll::Ready(None);
+            };
+
+            match ready!(this.inner.as_mut().poll_next(cx)) {
+                Some(Ok(chunk)) => {
+                    this.bytes_in += chunk.len() as u64;
+                    encoder
+                        .write_all(&chunk)
+                        .and_then(|_| encoder.flush())
+                        .context("Failed to compress response chunk")?;
+
+                    let compressed = std::mem::take(encoder.get_mut());
+                    if compressed.is_empty() {
+                        continue;
+                    }
+                    this.bytes_out += compressed.len() as u64;
+                    return Poll::Ready(Some(Ok(Bytes::from(compressed))));
+                }
+                Some(Err(e)) => {
+                    this.encoder = None;
+                    return Poll::Ready(Some(Err(e)));
+                }
+                None => {
+                    let encoder = this.encoder.take().expect("encoder is only taken once");
+                    let compressed = encoder.finish().context("Failed to finish zstd frame")?;
+                    this.bytes_out += compressed.len() as u64;
+
+                    tracing::info!(
+                        bytes_in = this.bytes_in,
+                        bytes_out = this.bytes_out,
+                        ratio = this.bytes_out as f64 / this.bytes_in.max(1) as f64,
+                        "Compressed streamed response with zstd"
+                    );
+
+                    return Poll::Ready(Some(Ok(Bytes::from(compressed))));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::web::Bytes;
+    use futures_util::{StreamExt, stream};
+
+    use super::{ZstdStream, accept_encoding_allows_zstd};
+
+    #[test]
+    fn zstd_is_accepted_when_listed() {
+        for value in ["zstd", "gzip, zstd", "br;q=1.0, ZSTD;q=0.5", "zstd ; q=1"] {
+            assert!(accept_encoding_allows_zstd(value), "{value}");
+        }
+    }
+
+    #[test]
+    fn zstd_is_refused_when_missing_or_ruled_out() {
+        for value in [
+            "",
+            "gzip, br",
+            "zstd;q=0",
+            "zstd;q=0.0",
+            "zstd;q=abc",
+            "zstdx",
+        ] {
+            assert!(!accept_encoding_allows_zstd(value), "{value}");
+        }
+    }
+
+    #[tokio::test]
+    async fn compressed_stream_decodes_to_the_original_chunks() {
+        let chunks = vec!["[", "{\"a\":1}", ",{\"b\":2}", "]"];
+        let inner = stream::iter(
+            chunks
+                .clone()
+                .into_iter()
+                .map(|c| Ok::<_, anyhow::Error>(Bytes::from(c))),
+        );
+
+        let compressed: Vec<u8> = ZstdStream::new(inner, 3)
+            .unwrap()
+            .map(|chunk| chunk.unwrap())
+            .collect::<Vec<_>>()
+            .await
+            .concat();
+
+        let decoded = zstd::decode_all(compressed.as_slice()).unwrap();
+        assert_eq!(decoded, chunks.concat().as_bytes());
+    }
+}
@@ -0,0 +1,135 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::Pagination;
+
+// Security-relevant actions recorded in the audit log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, sqlx::Type)]
+#[sqlx(type_name = "audit_action", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    Login,
+    PasswordChange,
+    RoleChange,
+    PostHardDelete,
+    NewsletterPublish,
+    NewsletterCancel,
+    ImpersonationStart,
+    ImpersonationEnd,
+}
+
+impl AuditAction {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s.trim().to_lowercase().as_str() {
+            "login" => Ok(AuditAction::Login),
+            "password_change" => Ok(AuditAction::PasswordChange),
+            "role_change" => Ok(AuditAction::RoleChange),
+            "post_hard_delete" => Ok(AuditAction::PostHardDelete),
+            "newsletter_publish" => Ok(AuditAction::NewsletterPublish),
+            "newsletter_cancel" => Ok(AuditAction::NewsletterCancel),
+            "impersonation_start" => Ok(AuditAction::ImpersonationStart),
+            "impersonation_end" => Ok(AuditAction::ImpersonationEnd),
+            _ => Err(
+                "Invalid action: must be login, password_change, role_change, post_hard_delete, newsletter_publish, newsletter_cancel, impersonation_start or impersonation_end."
+                    .to_string(),
+            ),
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct AuditEvent {
+    pub id: Uuid,
+    pub action: AuditAction,
+    // Who did it, absent for actions the service takes on its own
+    pub actor_id: Option<Uuid>,
+    // The user, post or newsletter issue acted on
+    pub target_id: Option<Uuid>,
+    // Details specific to the action, e.g. the old and new role of a role change
+    pub metadata: serde_json::Value,
+    pub occurred_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GetAuditLogQuery {
+    #[serde(default = "default_page")]
+    pub page: i32,
+    #[serde(default = "default_limit")]
+    pub limit: i32,
+    pub action: Option<String>,
+    pub actor_id: Option<Uuid>,
+    pub target_id: Option<Uuid>,
+}
+
+fn default_page() -> i32 {
+    1
+}
+
+fn default_limit() -> i32 {
+    20
+}
+
+// Filters left out match every entry
+#[derive(Debug)]
+pub struct AuditLogFilter {
+    pub pagination: Pagination,
+    pub action: Option<AuditAction>,
+    pub actor_id: Option<Uuid>,
+    pub target_id: Option<Uuid>,
+}
+
+impl TryFrom<GetAuditLogQuery> for AuditLogFilter {
+    type Error = String;
+
+    fn try_from(query: GetAuditLogQuery) -> Result<Self, Self::Error> {
+        Ok(Self {
+            pagination: Pagination::parse(query.page, query.limit)?,
+            action: query
+                .action
+                .as_deref()
+                .map(AuditAction::parse)
+                .transpose()?,
+            actor_id: query.actor_id,
+            target_id: query.target_id,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::{assert_err, assert_ok_eq};
+
+    use super::{AuditAction, AuditLogFilter, GetAuditLogQuery};
+
+    #[test]
+    fn actions_are_parsed_by_their_serialized_names() {
+        for action in [
+            AuditAction::Login,
+            AuditAction::PasswordChange,
+            AuditAction::RoleChange,
+            AuditAction::PostHardDelete,
+            AuditAction::NewsletterPublish,
+            AuditAction::NewsletterCancel,
+            AuditAction::ImpersonationStart,
+            AuditAction::ImpersonationEnd,
+        ] {
+            let name = serde_json::to_value(action).unwrap();
+            assert_ok_eq!(AuditAction::parse(name.as_str().unwrap()), action);
+        }
+        assert_err!(AuditAction::parse("logout"));
+    }
+
+    #[test]
+    fn unknown_action_filters_are_rejected() {
+        let query = GetAuditLogQuery {
+            page: 1,
+            limit: 20,
+            action: Some("everything".to_string()),
+            actor_id: None,
+            target_id: None,
+        };
+
+        assert_err!(AuditLogFilter::try_from(query));
+    }
+}
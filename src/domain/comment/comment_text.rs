@@ -21,8 +21,38 @@ impl CommentText {
 
         Ok(Self(trimmed.to_string()))
     }
+
+    /// Returns the distinct user names referenced as `@username`, in order of appearance.
+    /// Only tokens at the start of the text or after whitespace count, so emails don't match.
+    pub fn mentions(&self) -> Vec<&str> {
+        let mut mentions: Vec<&str> = Vec::new();
+
+        for word in self.0.split_whitespace() {
+            let Some(rest) = word.strip_prefix('@') else {
+                continue;
+            };
+
+            let end = rest
+                .find(|c: char| !(c.is_alphanumeric() || matches!(c, '_' | '-' | '.')))
+                .unwrap_or(rest.len());
+            // A trailing dot usually ends the sentence rather than the name
+            let name = rest[..end].trim_end_matches('.');
+
+            if !name.is_empty() && !mentions.contains(&name) {
+                mentions.push(name);
+            }
+            if mentions.len() == MAX_MENTIONS {
+                break;
+            }
+        }
+
+        mentions
+    }
 }
 
+// Caps the fan-out a single comment can cause
+const MAX_MENTIONS: usize = 10;
+
 impl AsRef<str> for CommentText {
     fn as_ref(&self) -> &str {
         &self.0
@@ -44,6 +74,28 @@ mod tests {
     use super::CommentText;
 
     // Example-based tests
+    #[test]
+    fn mentions_are_extracted_in_order_without_duplicates() {
+        let comment = CommentText::parse("@alice thanks, cc @bob_1 and @alice.".into()).unwrap();
+        assert_eq!(comment.mentions(), vec!["alice", "bob_1"]);
+    }
+
+    #[test]
+    fn emails_and_bare_at_signs_are_not_mentions() {
+        let comment = CommentText::parse("mail me@example.com or @ anyone".into()).unwrap();
+        assert!(comment.mentions().is_empty());
+    }
+
+    #[test]
+    fn mentions_are_capped() {
+        let text = (0..15)
+            .map(|i| format!("@user{i}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let comment = CommentText::parse(text).unwrap();
+        assert_eq!(comment.mentions().len(), 10);
+    }
+
     #[test]
     fn a_comment_with_200_chars_is_valid() {
         let comment = "a".repeat(200);
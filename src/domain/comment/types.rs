@@ -2,7 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::domain::Comment;
+use crate::domain::{Comment, Pagination, UserEmail, UserName, avatar_url};
 
 #[derive(sqlx::FromRow)]
 pub struct CommentRecord {
@@ -12,6 +12,11 @@ pub struct CommentRecord {
     pub created_at: DateTime<Utc>,
     pub created_by: Uuid,
     pub user_name: String,
+    pub avatar_updated_at: Option<DateTime<Utc>>,
+    pub author_display_name: Option<String>,
+    // Only selected by paginated queries
+    #[sqlx(default)]
+    pub total_count: i64,
 }
 
 // For creating comments - borrows data
@@ -32,6 +37,10 @@ pub struct CommentResponseBody {
     pub post_id: Uuid,
     pub created_at: DateTime<Utc>,
     pub created_by: Uuid,
+    pub created_by_name: String,
+    pub created_by_avatar_url: Option<String>,
+    // Set on anonymous comments, whose author is the placeholder user
+    pub author_display_name: Option<String>,
 }
 
 impl From<CommentRecord> for CommentResponseBody {
@@ -42,6 +51,9 @@ impl From<CommentRecord> for CommentResponseBody {
             post_id: record.post_id,
             created_at: record.created_at,
             created_by: record.created_by,
+            created_by_name: record.user_name,
+            created_by_avatar_url: avatar_url(record.created_by, record.avatar_updated_at),
+            author_display_name: record.author_display_name,
         }
     }
 }
@@ -59,3 +71,362 @@ impl TryFrom<CreateCommentPayload> for Comment {
         Comment::new(value.text, value.post_id)
     }
 }
+
+#[derive(Deserialize, Debug)]
+pub struct GetCommentsQuery {
+    #[serde(default = "default_page")]
+    pub page: i32,
+    #[serde(default = "default_limit")]
+    pub limit: i32,
+    #[serde(default = "default_sort")]
+    pub sort: String,
+}
+
+fn default_sort() -> String {
+    "newest".to_string()
+}
+
+fn default_page() -> i32 {
+    1
+}
+
+fn default_limit() -> i32 {
+    20
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CommentSort {
+    Newest,
+    Oldest,
+}
+
+impl CommentSort {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s {
+            "newest" => Ok(Self::Newest),
+            "oldest" => Ok(Self::Oldest),
+            _ => Err("invalid sort value".to_string()),
+        }
+    }
+
+    // id breaks ties between comments created within the same instant
+    pub fn to_sql(&self) -> &'static str {
+        match self {
+            CommentSort::Newest => "c.created_at DESC, c.id DESC",
+            CommentSort::Oldest => "c.created_at ASC, c.id ASC",
+        }
+    }
+}
+
+pub struct CommentsPage {
+    pub pagination: Pagination,
+    pub sort: CommentSort,
+}
+
+impl TryFrom<GetCommentsQuery> for CommentsPage {
+    type Error = String;
+
+    fn try_from(query: GetCommentsQuery) -> Result<Self, Self::Error> {
+        Ok(Self {
+            pagination: Pagination::parse(query.page, query.limit)?,
+            sort: CommentSort::parse(&query.sort)?,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+pub struct UserCommentRecord {
+    pub total_count: i64,
+    pub id: Uuid,
+    pub text: String,
+    pub post_id: Uuid,
+    pub post_title: String,
+    pub created_at: DateTime<Utc>,
+}
+
+// A comment listed on its author's profile, with the post it was left on
+#[derive(Serialize, Debug)]
+pub struct UserCommentResponseBody {
+    pub id: Uuid,
+    pub text: String,
+    pub post_id: Uuid,
+    pub post_title: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<UserCommentRecord> for UserCommentResponseBody {
+    fn from(record: UserCommentRecord) -> Self {
+        Self {
+            id: record.id,
+            text: record.text,
+            post_id: record.post_id,
+            post_title: record.post_title,
+            created_at: record.created_at,
+        }
+    }
+}
+
+// A comment in which the user was mentioned
+#[derive(Serialize, Debug)]
+pub struct MentionResponseBody {
+    pub comment_id: Uuid,
+    pub post_id: Uuid,
+    pub text: String,
+    pub mentioned_by: Uuid,
+    pub mentioned_by_name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+// Admin view includes the author's name so moderators can spot repeat offenders
+#[derive(Serialize, Debug)]
+pub struct ModerationCommentResponseBody {
+    pub id: Uuid,
+    pub text: String,
+    pub post_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub created_by: Uuid,
+    pub user_name: String,
+}
+
+impl From<CommentRecord> for ModerationCommentResponseBody {
+    fn from(record: CommentRecord) -> Self {
+        Self {
+            id: record.id,
+            text: record.text,
+            post_id: record.post_id,
+            created_at: record.created_at,
+            created_by: record.created_by,
+            user_name: record.user_name,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ModerationCommentsQuery {
+    pub user_id: Option<Uuid>,
+    pub post_id: Option<Uuid>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    #[serde(default = "default_page")]
+    pub page: i32,
+    #[serde(default = "default_limit")]
+    pub limit: i32,
+}
+
+#[derive(Debug)]
+pub struct ModerationFilters {
+    pub user_id: Option<Uuid>,
+    pub post_id: Option<Uuid>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub pagination: Pagination,
+}
+
+impl TryFrom<ModerationCommentsQuery> for ModerationFilters {
+    type Error = String;
+
+    fn try_from(query: ModerationCommentsQuery) -> Result<Self, Self::Error> {
+        if let (Some(since), Some(until)) = (query.since, query.until)
+            && since > until
+        {
+            return Err("since must not be after until".to_string());
+        }
+
+        Ok(Self {
+            user_id: query.user_id,
+            post_id: query.post_id,
+            since: query.since,
+            until: query.until,
+            pagination: Pagination::parse(query.page, query.limit)?,
+        })
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct RemoveCommentsPayload {
+    pub comment_ids: Vec<Uuid>,
+}
+
+#[derive(Debug)]
+pub struct CommentIdBatch(Vec<Uuid>);
+
+impl CommentIdBatch {
+    pub const MAX_LEN: usize = 500;
+
+    pub fn parse(mut ids: Vec<Uuid>) -> Result<Self, String> {
+        ids.sort_unstable();
+        ids.dedup();
+
+        if ids.is_empty() {
+            return Err("comment_ids must not be empty".to_string());
+        }
+        if ids.len() > Self::MAX_LEN {
+            return Err(format!(
+                "at most {} comments can be removed at once",
+                Self::MAX_LEN
+            ));
+        }
+
+        Ok(Self(ids))
+    }
+}
+
+impl AsRef<[Uuid]> for CommentIdBatch {
+    fn as_ref(&self) -> &[Uuid] {
+        &self.0
+    }
+}
+
+// One NDJSON line of a comment export from another platform
+#[derive(Deserialize, Debug)]
+pub struct ImportCommentLine {
+    pub post_id: String,
+    pub author_email: String,
+    pub author_name: String,
+    pub text: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+pub struct ImportedComment {
+    pub comment: Comment,
+    pub author_email: UserEmail,
+    pub author_name: UserName,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TryFrom<ImportCommentLine> for ImportedComment {
+    type Error = String;
+
+    fn try_from(line: ImportCommentLine) -> Result<Self, Self::Error> {
+        if line.created_at > Utc::now() {
+            return Err("Invalid created_at: cannot be in the future".to_string());
+        }
+
+        Ok(Self {
+            comment: Comment::new(line.text, line.post_id)?,
+            author_email: UserEmail::parse(line.author_email)?,
+            author_name: UserName::parse(line.author_name)?,
+            created_at: line.created_at,
+        })
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ImportCommentsQuery {
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ImportLineError {
+    // 1-based, matching what editors show for the uploaded file
+    pub line: usize,
+    pub error: String,
+}
+
+#[derive(Serialize, Debug, Default)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub imported: u64,
+    pub users_created: u64,
+    pub errors: Vec<ImportLineError>,
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use claims::{assert_err, assert_ok};
+    use uuid::Uuid;
+
+    use super::{
+        CommentIdBatch, CommentSort, ImportCommentLine, ImportedComment, ModerationCommentsQuery,
+        ModerationFilters,
+    };
+
+    #[test]
+    fn valid_comment_sorts_are_accepted() {
+        assert_ok!(CommentSort::parse("newest"));
+        assert_ok!(CommentSort::parse("oldest"));
+    }
+
+    #[test]
+    fn invalid_comment_sorts_are_rejected() {
+        for sort in ["", "-newest", "Oldest", "created_at"] {
+            assert_err!(CommentSort::parse(sort));
+        }
+    }
+
+    #[test]
+    fn comment_sort_to_sql() {
+        assert_eq!(CommentSort::Newest.to_sql(), "c.created_at DESC, c.id DESC");
+        assert_eq!(CommentSort::Oldest.to_sql(), "c.created_at ASC, c.id ASC");
+    }
+
+    #[test]
+    fn empty_comment_id_batch_is_rejected() {
+        assert_err!(CommentIdBatch::parse(vec![]));
+    }
+
+    #[test]
+    fn oversized_comment_id_batch_is_rejected() {
+        let ids = (0..=CommentIdBatch::MAX_LEN)
+            .map(|_| Uuid::new_v4())
+            .collect();
+        assert_err!(CommentIdBatch::parse(ids));
+    }
+
+    #[test]
+    fn duplicate_comment_ids_are_collapsed() {
+        let id = Uuid::new_v4();
+        let batch = CommentIdBatch::parse(vec![id, id]).unwrap();
+        assert_eq!(batch.as_ref(), &[id]);
+    }
+
+    #[test]
+    fn moderation_date_range_must_be_ordered() {
+        let now = Utc::now();
+        let query = ModerationCommentsQuery {
+            user_id: None,
+            post_id: None,
+            since: Some(now),
+            until: Some(now - Duration::days(1)),
+            page: 1,
+            limit: 20,
+        };
+        assert_err!(ModerationFilters::try_from(query));
+    }
+
+    fn import_line() -> ImportCommentLine {
+        ImportCommentLine {
+            post_id: Uuid::new_v4().to_string(),
+            author_email: "jane@example.com".to_string(),
+            author_name: "jane".to_string(),
+            text: "Great write-up".to_string(),
+            created_at: Utc::now() - Duration::days(365),
+        }
+    }
+
+    #[test]
+    fn valid_import_line_is_accepted() {
+        assert_ok!(ImportedComment::try_from(import_line()));
+    }
+
+    #[test]
+    fn import_line_with_invalid_email_is_rejected() {
+        let line = ImportCommentLine {
+            author_email: "not-an-email".to_string(),
+            ..import_line()
+        };
+        assert_err!(ImportedComment::try_from(line));
+    }
+
+    #[test]
+    fn import_line_from_the_future_is_rejected() {
+        let line = ImportCommentLine {
+            created_at: Utc::now() + Duration::days(1),
+            ..import_line()
+        };
+        assert_err!(ImportedComment::try_from(line));
+    }
+}
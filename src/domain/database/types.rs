@@ -0,0 +1,186 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+// Tables smaller than this are cheaper to scan than to index, so their seq scans are ignored
+pub const SEQ_SCAN_MIN_LIVE_ROWS: i64 = 10_000;
+
+#[derive(Serialize, Debug)]
+pub struct IndexUsage {
+    pub table: String,
+    pub index: String,
+    pub scans: i64,
+    pub size_bytes: i64,
+    // Unique and primary key indexes enforce constraints, so they are never reported as unused
+    pub is_unused: bool,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct TableScanStats {
+    pub table: String,
+    pub seq_scans: i64,
+    pub seq_rows_read: i64,
+    pub index_scans: i64,
+    pub live_rows: i64,
+}
+
+impl TableScanStats {
+    /// A table leans on sequential scans when it is large enough to benefit from an index
+    /// but is still scanned sequentially more often than through one.
+    pub fn is_seq_scan_heavy(&self) -> bool {
+        self.live_rows >= SEQ_SCAN_MIN_LIVE_ROWS && self.seq_scans > self.index_scans
+    }
+
+    /// Activity between an earlier snapshot and this one, as the underlying counters only grow.
+    pub fn since(&self, earlier: &TableScanStats) -> TableScanStats {
+        TableScanStats {
+            table: self.table.clone(),
+            seq_scans: (self.seq_scans - earlier.seq_scans).max(0),
+            seq_rows_read: (self.seq_rows_read - earlier.seq_rows_read).max(0),
+            index_scans: (self.index_scans - earlier.index_scans).max(0),
+            live_rows: self.live_rows,
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct TableScanReport {
+    #[serde(flatten)]
+    pub stats: TableScanStats,
+    pub is_seq_scan_heavy: bool,
+}
+
+impl From<TableScanStats> for TableScanReport {
+    fn from(stats: TableScanStats) -> Self {
+        Self {
+            is_seq_scan_heavy: stats.is_seq_scan_heavy(),
+            stats,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct TableBloatStats {
+    pub table: String,
+    pub live_rows: i64,
+    pub dead_rows: i64,
+    // Heap and TOAST, without indexes
+    pub size_bytes: i64,
+    // The later of the last manual vacuum and the last autovacuum
+    pub last_vacuumed_at: Option<DateTime<Utc>>,
+}
+
+impl TableBloatStats {
+    pub fn dead_row_ratio(&self) -> f64 {
+        let total = self.live_rows + self.dead_rows;
+        if total <= 0 {
+            return 0.0;
+        }
+        self.dead_rows as f64 / total as f64
+    }
+
+    /// Rough share of the table held by dead rows, assuming they are as wide as live ones.
+    pub fn estimated_bloat_bytes(&self) -> i64 {
+        (self.size_bytes as f64 * self.dead_row_ratio()) as i64
+    }
+
+    /// A handful of dead rows in a tiny table is noise, so both the ratio and the count must
+    /// reach their thresholds.
+    pub fn is_bloated(&self, max_dead_row_ratio: f64, min_dead_rows: i64) -> bool {
+        self.dead_rows >= min_dead_rows && self.dead_row_ratio() >= max_dead_row_ratio
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct TableBloatReport {
+    #[serde(flatten)]
+    pub stats: TableBloatStats,
+    pub dead_row_ratio: f64,
+    pub estimated_bloat_bytes: i64,
+    pub is_bloated: bool,
+}
+
+impl TableBloatReport {
+    pub fn new(stats: TableBloatStats, max_dead_row_ratio: f64, min_dead_rows: i64) -> Self {
+        Self {
+            dead_row_ratio: stats.dead_row_ratio(),
+            estimated_bloat_bytes: stats.estimated_bloat_bytes(),
+            is_bloated: stats.is_bloated(max_dead_row_ratio, min_dead_rows),
+            stats,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SEQ_SCAN_MIN_LIVE_ROWS, TableBloatStats, TableScanStats};
+
+    fn stats(seq_scans: i64, index_scans: i64, live_rows: i64) -> TableScanStats {
+        TableScanStats {
+            table: "posts".to_string(),
+            seq_scans,
+            seq_rows_read: seq_scans * live_rows,
+            index_scans,
+            live_rows,
+        }
+    }
+
+    #[test]
+    fn large_table_scanned_mostly_sequentially_is_heavy() {
+        assert!(stats(100, 10, SEQ_SCAN_MIN_LIVE_ROWS).is_seq_scan_heavy());
+    }
+
+    #[test]
+    fn small_tables_are_never_heavy() {
+        assert!(!stats(100, 0, SEQ_SCAN_MIN_LIVE_ROWS - 1).is_seq_scan_heavy());
+    }
+
+    #[test]
+    fn mostly_indexed_table_is_not_heavy() {
+        assert!(!stats(10, 100, SEQ_SCAN_MIN_LIVE_ROWS).is_seq_scan_heavy());
+    }
+
+    #[test]
+    fn since_subtracts_earlier_counters() {
+        let delta = stats(150, 40, 20_000).since(&stats(100, 30, 10_000));
+        assert_eq!(delta.seq_scans, 50);
+        assert_eq!(delta.index_scans, 10);
+        assert_eq!(delta.live_rows, 20_000);
+    }
+
+    #[test]
+    fn since_never_goes_negative_after_a_stats_reset() {
+        let delta = stats(5, 5, 20_000).since(&stats(100, 30, 10_000));
+        assert_eq!(delta.seq_scans, 0);
+        assert_eq!(delta.index_scans, 0);
+    }
+
+    fn bloat(live_rows: i64, dead_rows: i64) -> TableBloatStats {
+        TableBloatStats {
+            table: "idempotency".to_string(),
+            live_rows,
+            dead_rows,
+            size_bytes: 1_000_000,
+            last_vacuumed_at: None,
+        }
+    }
+
+    #[test]
+    fn dead_row_ratio_is_share_of_all_rows() {
+        assert_eq!(bloat(750, 250).dead_row_ratio(), 0.25);
+        assert_eq!(bloat(750, 250).estimated_bloat_bytes(), 250_000);
+    }
+
+    #[test]
+    fn empty_table_has_no_dead_row_ratio() {
+        assert_eq!(bloat(0, 0).dead_row_ratio(), 0.0);
+    }
+
+    #[test]
+    fn table_is_bloated_only_past_both_thresholds() {
+        assert!(bloat(5_000, 5_000).is_bloated(0.2, 1_000));
+        // High ratio, but too few dead rows to be worth a vacuum
+        assert!(!bloat(10, 90).is_bloated(0.2, 1_000));
+        // Many dead rows, but a small share of a large table
+        assert!(!bloat(100_000, 5_000).is_bloated(0.2, 1_000));
+    }
+}
@@ -0,0 +1,77 @@
+use std::fmt::{self, Display, Formatter};
+
+use unicode_segmentation::UnicodeSegmentation;
+
+// Validated like `CommentText`, with more room since messages are conversations
+#[derive(Debug)]
+pub struct MessageText(String);
+
+const MAX_MESSAGE_GRAPHEMES: usize = 1000;
+
+impl MessageText {
+    pub fn parse(s: String) -> Result<Self, String> {
+        let trimmed = s.trim();
+
+        if trimmed.is_empty() {
+            return Err("Invalid message: cannot be empty.".to_string());
+        }
+
+        if trimmed.graphemes(true).count() > MAX_MESSAGE_GRAPHEMES {
+            return Err(format!(
+                "Invalid message: cannot exceed {MAX_MESSAGE_GRAPHEMES} characters."
+            ));
+        }
+
+        Ok(Self(trimmed.to_string()))
+    }
+}
+
+impl AsRef<str> for MessageText {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for MessageText {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::{assert_err, assert_ok};
+    use proptest::prelude::*;
+
+    use super::MessageText;
+
+    #[test]
+    fn a_message_with_1000_chars_is_valid() {
+        assert_ok!(MessageText::parse("a".repeat(1000)));
+    }
+
+    #[test]
+    fn a_message_longer_than_1000_chars_is_rejected() {
+        assert_err!(MessageText::parse("a".repeat(1001)));
+    }
+
+    #[test]
+    fn surrounding_whitespace_is_trimmed() {
+        let message = MessageText::parse("  hello there \n".to_string()).unwrap();
+        assert_eq!(message.as_ref(), "hello there");
+    }
+
+    proptest! {
+        #[test]
+        fn whitespace_only_messages_are_rejected(message in r"\s{0,50}") {
+            prop_assert!(MessageText::parse(message).is_err());
+        }
+
+        #[test]
+        fn messages_with_valid_length_are_accepted(
+            message in r"[a-zA-Z0-9][a-zA-Z0-9 .!?]{0,999}",
+        ) {
+            prop_assert!(MessageText::parse(message).is_ok());
+        }
+    }
+}
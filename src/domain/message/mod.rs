@@ -0,0 +1,5 @@
+mod message_text;
+mod types;
+
+pub use message_text::MessageText;
+pub use types::*;
@@ -0,0 +1,91 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::MessageText;
+
+#[derive(Deserialize, Debug)]
+pub struct SendMessagePayload {
+    pub recipient_id: String,
+    pub text: String,
+}
+
+#[derive(Debug)]
+pub struct NewMessage {
+    pub recipient_id: Uuid,
+    pub text: MessageText,
+}
+
+impl TryFrom<SendMessagePayload> for NewMessage {
+    type Error = String;
+
+    fn try_from(payload: SendMessagePayload) -> Result<Self, Self::Error> {
+        let recipient_id = Uuid::parse_str(&payload.recipient_id)
+            .map_err(|_| "Invalid recipient_id: must be a valid UUID".to_string())?;
+
+        Ok(Self {
+            recipient_id,
+            text: MessageText::parse(payload.text)?,
+        })
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct SentMessage {
+    pub id: Uuid,
+    pub conversation_id: Uuid,
+}
+
+#[derive(Serialize, Debug)]
+pub struct MessageResponseBody {
+    pub id: Uuid,
+    pub sender_id: Uuid,
+    pub text: String,
+    pub created_at: DateTime<Utc>,
+    pub read_at: Option<DateTime<Utc>>,
+}
+
+// A thread with one other user, carrying its most recent messages oldest first
+#[derive(Serialize, Debug)]
+pub struct ConversationResponseBody {
+    pub id: Uuid,
+    pub other_user_id: Uuid,
+    pub other_user_name: String,
+    pub other_user_avatar_url: Option<String>,
+    // Messages from the other user the caller has not read yet
+    pub unread_count: i64,
+    pub last_message_at: DateTime<Utc>,
+    pub messages: Vec<MessageResponseBody>,
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::{assert_err, assert_ok};
+    use uuid::Uuid;
+
+    use super::{NewMessage, SendMessagePayload};
+
+    fn payload(recipient_id: &str, text: &str) -> SendMessagePayload {
+        SendMessagePayload {
+            recipient_id: recipient_id.to_string(),
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn valid_message_is_accepted() {
+        let recipient_id = Uuid::new_v4().to_string();
+        assert_ok!(NewMessage::try_from(payload(&recipient_id, "Hi there")));
+    }
+
+    #[test]
+    fn invalid_recipient_id_is_rejected() {
+        assert_err!(NewMessage::try_from(payload("not-a-uuid", "Hi there")));
+    }
+
+    #[test]
+    fn empty_text_is_rejected() {
+        let recipient_id = Uuid::new_v4().to_string();
+        assert_err!(NewMessage::try_from(payload(&recipient_id, "   ")));
+    }
+}
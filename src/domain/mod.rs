@@ -1,9 +1,21 @@
+mod audit;
 mod comment;
+mod database;
+mod message;
 mod newsletter;
+mod pagination;
 mod post;
+mod report;
+mod submission;
 mod user;
 
+pub use audit::*;
 pub use comment::*;
+pub use database::*;
+pub use message::*;
 pub use newsletter::*;
+pub use pagination::*;
 pub use post::*;
+pub use report::*;
+pub use submission::*;
 pub use user::*;
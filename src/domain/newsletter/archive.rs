@@ -0,0 +1,145 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::{RecipientPlaceholders, escape_html, expand_placeholders};
+
+// A published issue as shown on the public archive, see `GET /v1/newsletters`
+#[derive(Debug)]
+pub struct ArchivedNewsletter {
+    pub id: Uuid,
+    pub title: String,
+    pub html_content: String,
+    pub published_at: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+pub struct ArchivedNewsletterSummary {
+    pub id: Uuid,
+    pub title: String,
+    pub published_at: DateTime<Utc>,
+}
+
+// Nobody in particular reads the archive, and it must not link to anyone's unsubscribe page
+fn public_placeholders() -> RecipientPlaceholders {
+    RecipientPlaceholders {
+        user_name: "reader".to_string(),
+        unsubscribe_url: "#".to_string(),
+    }
+}
+
+// One page of the archive, newest issues first, with links to the neighbouring pages
+pub fn render_archive_page(
+    issues: &[ArchivedNewsletterSummary],
+    page: i32,
+    has_next_page: bool,
+) -> String {
+    let mut body = String::from("<h1>Newsletter archive</h1>\n");
+    if issues.is_empty() {
+        body.push_str("<p>No issues yet.</p>\n");
+    } else {
+        body.push_str("<ul>\n");
+        for issue in issues {
+            body.push_str(&format!(
+                "<li><a href=\"/v1/newsletters/{}\">{}</a> {}</li>\n",
+                issue.id,
+                escape_html(&issue.title),
+                published_on(issue.published_at)
+            ));
+        }
+        body.push_str("</ul>\n");
+    }
+
+    let mut links = Vec::new();
+    if page > 1 {
+        links.push(format!("<a href=\"?page={}\">Newer issues</a>", page - 1));
+    }
+    if has_next_page {
+        links.push(format!("<a href=\"?page={}\">Older issues</a>", page + 1));
+    }
+    if !links.is_empty() {
+        body.push_str(&format!("<nav>{}</nav>\n", links.join(" ")));
+    }
+
+    document("Newsletter archive", &body)
+}
+
+// The issue as subscribers received it, placeholders filled in for an anonymous reader
+pub fn render_archived_newsletter(issue: &ArchivedNewsletter) -> String {
+    let body = format!(
+        "<nav><a href=\"/v1/newsletters\">All issues</a></nav>\n<article>\n<h1>{}</h1>\n<p>{}</p>\n{}\n</article>\n",
+        escape_html(&issue.title),
+        published_on(issue.published_at),
+        expand_placeholders(&issue.html_content, &public_placeholders(), true)
+    );
+
+    document(&issue.title, &body)
+}
+
+fn published_on(published_at: DateTime<Utc>) -> String {
+    format!(
+        "<time datetime=\"{}\">{}</time>",
+        published_at.to_rfc3339(),
+        published_at.format("%B %-d, %Y")
+    )
+}
+
+fn document(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n<title>{}</title>\n</head>\n<body>\n{body}</body>\n</html>\n",
+        escape_html(title)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use uuid::Uuid;
+
+    use super::{
+        ArchivedNewsletter, ArchivedNewsletterSummary, render_archive_page,
+        render_archived_newsletter,
+    };
+
+    #[test]
+    fn issues_are_listed_with_links_to_neighbouring_pages() {
+        let issues = [ArchivedNewsletterSummary {
+            id: Uuid::nil(),
+            title: "Tips & <tricks>".to_string(),
+            published_at: Utc.with_ymd_and_hms(2025, 12, 1, 9, 0, 0).unwrap(),
+        }];
+
+        let html = render_archive_page(&issues, 2, true);
+
+        assert!(html.contains(&format!(
+            "<a href=\"/v1/newsletters/{}\">Tips &amp; &lt;tricks&gt;</a>",
+            Uuid::nil()
+        )));
+        assert!(html.contains(">December 1, 2025</time>"));
+        assert!(html.contains("<a href=\"?page=1\">Newer issues</a>"));
+        assert!(html.contains("<a href=\"?page=3\">Older issues</a>"));
+    }
+
+    #[test]
+    fn first_and_last_page_link_one_way_only() {
+        let html = render_archive_page(&[], 1, false);
+
+        assert!(html.contains("No issues yet."));
+        assert!(!html.contains("<nav>"));
+    }
+
+    #[test]
+    fn issues_are_rendered_without_anyones_details() {
+        let issue = ArchivedNewsletter {
+            id: Uuid::nil(),
+            title: "Weekly".to_string(),
+            html_content: "<p>Hi {{user_name}}</p><a href=\"{{unsubscribe_url}}\">leave</a>"
+                .to_string(),
+            published_at: Utc::now(),
+        };
+
+        let html = render_archived_newsletter(&issue);
+
+        assert!(html.contains("<title>Weekly</title>"));
+        assert!(html.contains("<p>Hi reader</p><a href=\"#\">leave</a>"));
+    }
+}
@@ -0,0 +1,248 @@
+use base64::{Engine, engine::general_purpose::STANDARD};
+use serde::Deserialize;
+
+// Postmark refuses messages over 10 MB, base64 encoded attachments included
+pub const MAX_ATTACHMENTS_BYTES: usize = 5 * 1024 * 1024;
+pub const MAX_ATTACHMENTS: usize = 5;
+// Request bodies carry the attachments base64 encoded, a third larger, next to the content
+pub const MAX_NEWSLETTER_PAYLOAD_BYTES: usize = 8 * 1024 * 1024;
+
+// Formats mail clients open without warnings. Binary ones are recognised by their leading bytes,
+// text ones only have to be valid UTF-8.
+const ATTACHMENT_SIGNATURES: [(&str, Option<&[u8]>); 6] = [
+    ("application/pdf", Some(b"%PDF-")),
+    ("image/png", Some(b"\x89PNG\r\n\x1a\n")),
+    ("image/jpeg", Some(b"\xff\xd8\xff")),
+    ("image/gif", Some(b"GIF8")),
+    ("text/plain", None),
+    ("text/csv", None),
+];
+
+// An attachment as sent by clients, its content base64 encoded
+#[derive(Deserialize, Debug)]
+pub struct AttachmentPayload {
+    pub name: String,
+    pub content_type: String,
+    pub content: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct EmailAttachment {
+    name: String,
+    content_type: String,
+    content: Vec<u8>,
+}
+
+impl EmailAttachment {
+    /// Returns an `EmailAttachment` if the declared type is an allowed format and the content is one.
+    pub fn parse(name: String, content_type: &str, content: Vec<u8>) -> Result<Self, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("Invalid attachment: name cannot be empty.".to_string());
+        }
+        if name.chars().count() > 255 {
+            return Err(
+                "Invalid attachment: name cannot be longer than 255 characters.".to_string(),
+            );
+        }
+        if name
+            .chars()
+            .any(|c| c.is_control() || c == '/' || c == '\\')
+        {
+            return Err(format!(
+                "Invalid attachment {name}: name contains forbidden characters."
+            ));
+        }
+
+        let Some((content_type, signature)) = ATTACHMENT_SIGNATURES
+            .iter()
+            .find(|(allowed, _)| content_type.eq_ignore_ascii_case(allowed))
+        else {
+            return Err(format!(
+                "Invalid attachment {name}: must be a PDF, PNG, JPEG, GIF, plain text or CSV file."
+            ));
+        };
+
+        if content.is_empty() {
+            return Err(format!("Invalid attachment {name}: cannot be empty."));
+        }
+        if content.len() > MAX_ATTACHMENTS_BYTES {
+            return Err(format!(
+                "Invalid attachment {name}: cannot be larger than {MAX_ATTACHMENTS_BYTES} bytes."
+            ));
+        }
+
+        // The declared type is only trusted once the content agrees with it
+        let matches_type = match signature {
+            Some(signature) => content.starts_with(signature),
+            None => std::str::from_utf8(&content).is_ok(),
+        };
+        if !matches_type {
+            return Err(format!(
+                "Invalid attachment {name}: content is not a valid {content_type} file."
+            ));
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            content_type: content_type.to_string(),
+            content,
+        })
+    }
+
+    // Attachments are stored once validated, so they are not checked again when read back
+    pub fn from_stored(name: String, content_type: String, content: Vec<u8>) -> Self {
+        Self {
+            name,
+            content_type,
+            content,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn content_type(&self) -> &str {
+        &self.content_type
+    }
+
+    pub fn content(&self) -> &[u8] {
+        &self.content
+    }
+}
+
+impl TryFrom<AttachmentPayload> for EmailAttachment {
+    type Error = String;
+
+    fn try_from(payload: AttachmentPayload) -> Result<Self, Self::Error> {
+        let content = STANDARD.decode(payload.content.trim()).map_err(|_| {
+            format!(
+                "Invalid attachment {}: content must be base64 encoded.",
+                payload.name
+            )
+        })?;
+        EmailAttachment::parse(payload.name, &payload.content_type, content)
+    }
+}
+
+// Checks the attachments of one email together, each of them having been checked on its own
+pub fn parse_attachments(payloads: Vec<AttachmentPayload>) -> Result<Vec<EmailAttachment>, String> {
+    if payloads.len() > MAX_ATTACHMENTS {
+        return Err(format!(
+            "Invalid attachments: cannot attach more than {MAX_ATTACHMENTS} files."
+        ));
+    }
+
+    let attachments = payloads
+        .into_iter()
+        .map(EmailAttachment::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let total_bytes: usize = attachments.iter().map(|a| a.content.len()).sum();
+    if total_bytes > MAX_ATTACHMENTS_BYTES {
+        return Err(format!(
+            "Invalid attachments: cannot be larger than {MAX_ATTACHMENTS_BYTES} bytes together."
+        ));
+    }
+
+    Ok(attachments)
+}
+
+#[cfg(test)]
+mod tests {
+    use base64::{Engine, engine::general_purpose::STANDARD};
+    use claims::{assert_err, assert_ok};
+
+    use super::{AttachmentPayload, EmailAttachment, MAX_ATTACHMENTS_BYTES, parse_attachments};
+
+    fn pdf() -> Vec<u8> {
+        b"%PDF-1.7\n%\xe2\xe3\xcf\xd3\n".to_vec()
+    }
+
+    fn payload(name: &str, content_type: &str, content: &[u8]) -> AttachmentPayload {
+        AttachmentPayload {
+            name: name.into(),
+            content_type: content_type.into(),
+            content: STANDARD.encode(content),
+        }
+    }
+
+    #[test]
+    fn pdf_attachment_is_accepted() {
+        let result = EmailAttachment::parse("digest.pdf".into(), "application/pdf", pdf());
+        assert_ok!(result);
+    }
+
+    #[test]
+    fn declared_type_is_normalised() {
+        let attachment =
+            EmailAttachment::parse("digest.pdf".into(), "Application/PDF", pdf()).unwrap();
+        assert_eq!(attachment.content_type(), "application/pdf");
+    }
+
+    #[test]
+    fn content_not_matching_the_declared_type_is_rejected() {
+        let result = EmailAttachment::parse("digest.pdf".into(), "application/pdf", b"MZ".to_vec());
+        assert_err!(result);
+    }
+
+    #[test]
+    fn text_attachment_must_be_utf8() {
+        let result = EmailAttachment::parse("notes.txt".into(), "text/plain", vec![0xff, 0xfe]);
+        assert_err!(result);
+    }
+
+    #[test]
+    fn unsupported_type_is_rejected() {
+        let result = EmailAttachment::parse(
+            "setup.exe".into(),
+            "application/octet-stream",
+            b"MZ".to_vec(),
+        );
+        assert_err!(result);
+    }
+
+    #[test]
+    fn empty_attachment_is_rejected() {
+        let result = EmailAttachment::parse("notes.txt".into(), "text/plain", Vec::new());
+        assert_err!(result);
+    }
+
+    #[test]
+    fn name_with_path_separators_is_rejected() {
+        let result = EmailAttachment::parse("../digest.pdf".into(), "application/pdf", pdf());
+        assert_err!(result);
+    }
+
+    #[test]
+    fn content_that_is_not_base64_is_rejected() {
+        let result = EmailAttachment::try_from(AttachmentPayload {
+            name: "digest.pdf".into(),
+            content_type: "application/pdf".into(),
+            content: "not base64!".into(),
+        });
+        assert_err!(result);
+    }
+
+    #[test]
+    fn attachments_too_large_together_are_rejected() {
+        let mut half = pdf();
+        half.resize(MAX_ATTACHMENTS_BYTES / 2 + 1, b' ');
+
+        let result = parse_attachments(vec![
+            payload("first.pdf", "application/pdf", &half),
+            payload("second.pdf", "application/pdf", &half),
+        ]);
+        assert_err!(result);
+    }
+
+    #[test]
+    fn too_many_attachments_are_rejected() {
+        let payloads = (0..6)
+            .map(|i| payload(&format!("notes-{i}.txt"), "text/plain", b"notes"))
+            .collect();
+
+        assert_err!(parse_attachments(payloads));
+    }
+}
@@ -0,0 +1,120 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::{Newsletter, NewsletterHtml, escape_html};
+
+// A post featured in the weekly digest, with its engagement at the time the digest is assembled
+#[derive(Debug)]
+pub struct DigestPost {
+    pub id: Uuid,
+    pub title: String,
+    pub excerpt: String,
+    pub author_name: String,
+    pub likes: i64,
+    pub comments: i64,
+}
+
+// Renders the digest of the week ending at `week_ending`, posts in the order given. Post titles
+// and excerpts are user content, so they are escaped and can't carry placeholders.
+pub fn weekly_digest(
+    week_ending: DateTime<Utc>,
+    posts: &[DigestPost],
+    base_url: &str,
+) -> Result<Newsletter, String> {
+    let mut html = String::from(
+        "<h1>Top posts of the week</h1>\n<p>The most liked and discussed posts of the past seven days.</p>\n",
+    );
+    for post in posts {
+        html.push_str(&format!(
+            "<h2><a href=\"{base_url}/posts/{}\">{}</a></h2>\n<p>by {} · {} · {}</p>\n",
+            post.id,
+            user_content(&post.title),
+            user_content(&post.author_name),
+            count(post.likes, "like"),
+            count(post.comments, "comment"),
+        ));
+        if !post.excerpt.trim().is_empty() {
+            html.push_str(&format!("<p>{}</p>\n", user_content(&post.excerpt)));
+        }
+    }
+
+    let title = format!(
+        "Top posts of the week ending {}",
+        week_ending.format("%B %-d, %Y")
+    );
+    let html = NewsletterHtml::parse(html)?;
+    let text = html.to_text();
+    Newsletter::new(title, html.as_ref().to_string(), text)
+}
+
+fn user_content(value: &str) -> String {
+    escape_html(&value.replace("{{", "{ {"))
+}
+
+fn count(n: i64, noun: &str) -> String {
+    match n {
+        1 => format!("1 {noun}"),
+        n => format!("{n} {noun}s"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use claims::assert_ok;
+    use uuid::Uuid;
+
+    use super::{DigestPost, weekly_digest};
+
+    fn post(title: &str, likes: i64, comments: i64) -> DigestPost {
+        DigestPost {
+            id: Uuid::nil(),
+            title: title.to_string(),
+            excerpt: "A look at async Rust".to_string(),
+            author_name: "ada".to_string(),
+            likes,
+            comments,
+        }
+    }
+
+    #[test]
+    fn posts_are_listed_with_their_engagement() {
+        let week_ending = Utc.with_ymd_and_hms(2025, 12, 8, 9, 0, 0).unwrap();
+
+        let digest = weekly_digest(
+            week_ending,
+            &[post("Async Rust", 3, 1), post("Pinning", 1, 0)],
+            "https://techhub.example.com",
+        )
+        .unwrap();
+
+        assert_eq!(
+            digest.title.as_ref(),
+            "Top posts of the week ending December 8, 2025"
+        );
+        let html = digest.content.html.as_ref();
+        assert!(html.contains(&format!(
+            "<a href=\"https://techhub.example.com/posts/{}\">Async Rust</a>",
+            Uuid::nil()
+        )));
+        assert!(html.contains("by ada · 3 likes · 1 comment"));
+        assert!(html.contains("1 like · 0 comments"));
+        let text = digest.content.text.as_ref();
+        assert!(text.contains("Async Rust"), "{text}");
+        assert!(text.contains("A look at async Rust"), "{text}");
+    }
+
+    #[test]
+    fn user_content_is_escaped_and_cannot_carry_placeholders() {
+        let digest = weekly_digest(
+            Utc::now(),
+            &[post("<script>alert(1)</script> for {{user_name}}", 1, 1)],
+            "https://techhub.example.com",
+        );
+
+        let digest = assert_ok!(digest);
+        let html = digest.content.html.as_ref();
+        assert!(!html.contains("<script>"), "{html}");
+        assert!(!html.contains("{{user_name}}"), "{html}");
+    }
+}
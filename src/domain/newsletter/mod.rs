@@ -1,19 +1,38 @@
+mod archive;
+mod attachment;
+mod digest;
 mod newsletter_content;
 mod newsletter_html;
+mod newsletter_links;
 mod newsletter_text;
 mod newsletter_title;
+mod placeholders;
+mod postmark_webhook;
+mod segment;
+mod subject_test;
+mod subscriber_import;
 mod types;
 
+pub use archive::*;
+pub use attachment::*;
+pub use digest::{DigestPost, weekly_digest};
 pub use newsletter_content::NewsletterContent;
 pub use newsletter_html::NewsletterHtml;
+pub use newsletter_links::{rewrite_links, tracked_links};
 pub use newsletter_text::NewsletterText;
 pub use newsletter_title::NewsletterTitle;
+pub use placeholders::*;
+pub use postmark_webhook::{PostmarkWebhookEvent, verify_webhook_credentials};
+pub use segment::*;
+pub use subject_test::*;
+pub use subscriber_import::*;
 pub use types::*;
 
 #[derive(Debug)]
 pub struct Newsletter {
     pub title: NewsletterTitle,
     pub content: NewsletterContent,
+    pub attachments: Vec<EmailAttachment>,
 }
 
 impl Newsletter {
@@ -21,6 +40,15 @@ impl Newsletter {
         Ok(Self {
             title: NewsletterTitle::parse(title)?,
             content: NewsletterContent::new(html, text)?,
+            attachments: Vec::new(),
+        })
+    }
+
+    pub(super) fn from_markdown(title: String, markdown: String) -> Result<Self, String> {
+        Ok(Self {
+            title: NewsletterTitle::parse(title)?,
+            content: NewsletterContent::from_markdown(markdown)?,
+            attachments: Vec::new(),
         })
     }
 }
@@ -1,4 +1,6 @@
-use super::{NewsletterHtml, NewsletterText};
+use pulldown_cmark::{Options, Parser, html};
+
+use super::{NewsletterHtml, NewsletterText, validate_placeholders};
 
 #[derive(Debug)]
 pub struct NewsletterContent {
@@ -8,9 +10,88 @@ pub struct NewsletterContent {
 
 impl NewsletterContent {
     pub fn new(html: String, text: String) -> Result<Self, String> {
+        validate_placeholders(&html)?;
+        validate_placeholders(&text)?;
         Ok(Self {
             html: NewsletterHtml::parse(html)?,
             text: NewsletterText::parse(text)?,
         })
     }
+
+    // Both bodies are derived from the markdown, so they never drift apart
+    pub fn from_markdown(markdown: String) -> Result<Self, String> {
+        if markdown.trim().is_empty() {
+            return Err("Invalid newsletter markdown: cannot be empty.".to_string());
+        }
+
+        let parser = Parser::new_ext(
+            &markdown,
+            Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH,
+        );
+        let mut rendered = String::new();
+        html::push_html(&mut rendered, parser);
+        // Link destinations are percent-encoded, which would hide `[leave]({{unsubscribe_url}})`
+        let rendered = rendered.replace("%7B%7B", "{{").replace("%7D%7D", "}}");
+        validate_placeholders(&rendered)?;
+
+        let html = NewsletterHtml::parse(rendered)?;
+        let text = NewsletterText::parse(html.to_text())?;
+        Ok(Self { html, text })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::assert_err;
+
+    use super::NewsletterContent;
+
+    #[test]
+    fn markdown_is_rendered_to_html_and_text() {
+        let content = NewsletterContent::from_markdown(
+            "# Weekly\n\nNew posts on [the blog](https://example.com):\n\n* First\n* Second\n"
+                .into(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            content.html.as_ref(),
+            "<h1>Weekly</h1>\n<p>New posts on <a href=\"https://example.com\">the blog</a>:</p>\n<ul>\n<li>First</li>\n<li>Second</li>\n</ul>"
+        );
+        assert_eq!(
+            content.text.as_ref(),
+            "Weekly\n\nNew posts on the blog (https://example.com):\n\n- First\n- Second"
+        );
+    }
+
+    #[test]
+    fn placeholders_survive_markdown_links() {
+        let content = NewsletterContent::from_markdown(
+            "Hi {{user_name}}, [leave]({{unsubscribe_url}})".into(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            content.html.as_ref(),
+            "<p>Hi {{user_name}}, <a href=\"{{unsubscribe_url}}\">leave</a></p>"
+        );
+    }
+
+    #[test]
+    fn unknown_placeholders_are_rejected() {
+        assert_err!(NewsletterContent::from_markdown("Hi {{nickname}}".into()));
+        assert_err!(NewsletterContent::new(
+            "<p>Hi {{nickname}}</p>".into(),
+            "Hi there".into()
+        ));
+        assert_err!(NewsletterContent::new(
+            "<p>Hi there</p>".into(),
+            "Hi {{nickname}}".into()
+        ));
+    }
+
+    #[test]
+    fn empty_markdown_is_rejected() {
+        assert_err!(NewsletterContent::from_markdown("  \n ".into()));
+    }
 }
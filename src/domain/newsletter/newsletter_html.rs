@@ -89,6 +89,98 @@ impl NewsletterHtml {
         }
         false
     }
+
+    // Plain text rendering, used as the text body of issues authored in markdown. Blocks are
+    // separated by blank lines, list items are bulleted and links keep their target.
+    pub fn to_text(&self) -> String {
+        let dom = driver::parse_document(RcDom::default(), Default::default()).one(self.0.as_str());
+        let mut text = String::new();
+        Self::push_text(&dom.document, &mut text);
+
+        let mut lines: Vec<&str> = Vec::new();
+        for line in text.lines().map(str::trim_end) {
+            // Collapse the blank lines left between nested blocks into one
+            if line.is_empty() && lines.last().is_none_or(|l| l.is_empty()) {
+                continue;
+            }
+            lines.push(line);
+        }
+        lines.join("\n").trim().to_string()
+    }
+
+    fn push_text(node: &Handle, out: &mut String) {
+        let NodeData::Element { name, attrs, .. } = &node.data else {
+            if let NodeData::Text { contents } = &node.data {
+                let contents = contents.borrow();
+                // Whitespace spanning lines only formats the markup between blocks
+                if !(contents.trim().is_empty() && contents.contains('\n')) {
+                    out.push_str(&contents);
+                }
+            }
+            for child in node.children.borrow().iter() {
+                Self::push_text(child, out);
+            }
+            return;
+        };
+
+        let tag = name.local.as_ref();
+        let is_block = matches!(
+            tag,
+            "p" | "div"
+                | "h1"
+                | "h2"
+                | "h3"
+                | "h4"
+                | "h5"
+                | "h6"
+                | "ul"
+                | "ol"
+                | "blockquote"
+                | "pre"
+                | "table"
+                | "hr"
+        );
+        match tag {
+            "head" | "script" | "style" => return,
+            "br" => out.push('\n'),
+            "li" | "tr" => out.push('\n'),
+            _ if is_block => out.push_str("\n\n"),
+            _ => {}
+        }
+        if tag == "li" {
+            out.push_str("- ");
+        }
+
+        let start = out.len();
+        for child in node.children.borrow().iter() {
+            Self::push_text(child, out);
+        }
+
+        let attr = |wanted: &str| {
+            attrs
+                .borrow()
+                .iter()
+                .find(|a| a.name.local.as_ref() == wanted)
+                .map(|a| a.value.to_string())
+        };
+        match tag {
+            "a" => {
+                if let Some(href) = attr("href")
+                    && out[start..].trim() != href
+                {
+                    out.push_str(&format!(" ({href})"));
+                }
+            }
+            "img" => {
+                if let Some(alt) = attr("alt") {
+                    out.push_str(&alt);
+                }
+            }
+            "td" | "th" => out.push(' '),
+            _ if is_block => out.push_str("\n\n"),
+            _ => {}
+        }
+    }
 }
 
 impl AsRef<str> for NewsletterHtml {
@@ -214,6 +306,31 @@ mod tests {
         assert_ok!(result);
     }
 
+    #[test]
+    fn html_is_rendered_as_text_block_by_block() {
+        let html = NewsletterHtml::parse(
+            "<h1>News</h1>\n<p>Hello <strong>there</strong></p>\n<ul>\n<li>One</li>\n<li>Two</li>\n</ul>\n"
+                .into(),
+        )
+        .unwrap();
+
+        assert_eq!(html.to_text(), "News\n\nHello there\n\n- One\n- Two");
+    }
+
+    #[test]
+    fn links_keep_their_target_in_text() {
+        let html = NewsletterHtml::parse(
+            r#"<p><a href="https://example.com/post">Read more</a> or <a href="https://example.com">https://example.com</a></p>"#
+                .into(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            html.to_text(),
+            "Read more (https://example.com/post) or https://example.com"
+        );
+    }
+
     // Property-based tests
     proptest! {
         #[test]
@@ -0,0 +1,113 @@
+use std::ops::Range;
+
+// Links of an issue, in order of appearance. Only web links are tracked, so `mailto:` and
+// in-page anchors keep working as written. Raw markup is scanned rather than parsed and
+// reserialized, so the issue is delivered exactly as authored apart from the rewritten links.
+pub fn tracked_links(html: &str) -> Vec<String> {
+    link_spans(html)
+        .into_iter()
+        .map(|span| html[span].replace("&amp;", "&"))
+        .collect()
+}
+
+// Replaces each tracked link with the URL `rewrite` returns for its position
+pub fn rewrite_links(html: &str, rewrite: impl Fn(usize) -> String) -> String {
+    let mut rewritten = String::with_capacity(html.len());
+    let mut copied_up_to = 0;
+    for (position, span) in link_spans(html).into_iter().enumerate() {
+        rewritten.push_str(&html[copied_up_to..span.start]);
+        rewritten.push_str(&rewrite(position));
+        copied_up_to = span.end;
+    }
+    rewritten.push_str(&html[copied_up_to..]);
+    rewritten
+}
+
+// Byte ranges of the `href` values of `<a>` tags pointing to http(s) URLs
+fn link_spans(html: &str) -> Vec<Range<usize>> {
+    // ASCII lowercasing keeps byte offsets, so they apply to `html` as well
+    let lower = html.to_ascii_lowercase();
+    let mut spans = Vec::new();
+    let mut from = 0;
+
+    while let Some(offset) = lower[from..].find("<a") {
+        let tag_start = from + offset;
+        let Some(tag_len) = lower[tag_start..].find('>') else {
+            break;
+        };
+        let tag_end = tag_start + tag_len;
+        from = tag_end;
+
+        // Skips `<abbr>`, `<aside>` and the like
+        if !lower[tag_start + 2..].starts_with(|c: char| c.is_ascii_whitespace()) {
+            continue;
+        }
+        if let Some(span) = href_span(&lower[tag_start..tag_end]) {
+            let span = tag_start + span.start..tag_start + span.end;
+            let href = &lower[span.clone()];
+            // Links containing placeholders only get their destination when sent, see
+            // `expand_placeholders`
+            if (href.starts_with("http://") || href.starts_with("https://")) && !href.contains("{{")
+            {
+                spans.push(span);
+            }
+        }
+    }
+
+    spans
+}
+
+fn href_span(tag: &str) -> Option<Range<usize>> {
+    let mut from = 0;
+    while let Some(offset) = tag[from..].find("href") {
+        let name_start = from + offset;
+        from = name_start + 4;
+        if !tag[..name_start].ends_with(|c: char| c.is_ascii_whitespace()) {
+            continue;
+        }
+
+        let rest = tag[from..].trim_start();
+        let Some(rest) = rest.strip_prefix('=') else {
+            continue;
+        };
+        let rest = rest.trim_start();
+        let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        let value_start = tag.len() - rest.len() + 1;
+        let value_len = tag[value_start..].find(quote)?;
+        return Some(value_start..value_start + value_len);
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{rewrite_links, tracked_links};
+
+    const HTML: &str = r##"<p>Read <a href="https://example.com/a?x=1&amp;y=2">this</a>, <A class="btn" HREF='http://example.com/b'>that</A>, <a href="mailto:hi@example.com">mail us</a> or <a href="#top">go up</a>.</p><abbr href="https://example.com/c">c</abbr>"##;
+
+    #[test]
+    fn only_web_links_of_anchor_tags_are_tracked() {
+        assert_eq!(
+            tracked_links(HTML),
+            vec!["https://example.com/a?x=1&y=2", "http://example.com/b"]
+        );
+    }
+
+    #[test]
+    fn tracked_links_are_rewritten_in_place() {
+        let rewritten = rewrite_links(HTML, |position| format!("https://t.example.com/{position}"));
+
+        assert_eq!(
+            rewritten,
+            r##"<p>Read <a href="https://t.example.com/0">this</a>, <A class="btn" HREF='https://t.example.com/1'>that</A>, <a href="mailto:hi@example.com">mail us</a> or <a href="#top">go up</a>.</p><abbr href="https://example.com/c">c</abbr>"##
+        );
+    }
+
+    #[test]
+    fn html_without_links_is_left_untouched() {
+        let html = "<p>No links here, <a name=\"anchor\">just an anchor</a></p>";
+
+        assert!(tracked_links(html).is_empty());
+        assert_eq!(rewrite_links(html, |_| unreachable!()), html);
+    }
+}
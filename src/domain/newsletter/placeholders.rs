@@ -0,0 +1,146 @@
+// Placeholders issues can contain, written as `{{user_name}}`, replaced for every recipient
+pub const KNOWN_PLACEHOLDERS: &[&str] = &["user_name", "unsubscribe_url"];
+
+// What the placeholders stand for in the issue sent to one recipient
+#[derive(Debug)]
+pub struct RecipientPlaceholders {
+    pub user_name: String,
+    pub unsubscribe_url: String,
+}
+
+impl RecipientPlaceholders {
+    fn value(&self, name: &str) -> Option<&str> {
+        match name {
+            "user_name" => Some(&self.user_name),
+            "unsubscribe_url" => Some(&self.unsubscribe_url),
+            _ => None,
+        }
+    }
+}
+
+// Unknown placeholders would reach every recipient as written, so they are refused up front
+pub fn validate_placeholders(content: &str) -> Result<(), String> {
+    let mut unknown: Vec<&str> = placeholders(content)
+        .into_iter()
+        .map(|(_, name)| name)
+        .filter(|name| !KNOWN_PLACEHOLDERS.contains(name))
+        .collect();
+    unknown.sort_unstable();
+    unknown.dedup();
+
+    if unknown.is_empty() {
+        return Ok(());
+    }
+    Err(format!(
+        "Invalid newsletter content: unknown placeholders {}, must be one of {}.",
+        unknown
+            .iter()
+            .map(|name| format!("{{{{{name}}}}}"))
+            .collect::<Vec<_>>()
+            .join(", "),
+        KNOWN_PLACEHOLDERS.join(", ")
+    ))
+}
+
+// Values are HTML escaped when expanded into `html` content
+pub fn expand_placeholders(content: &str, values: &RecipientPlaceholders, html: bool) -> String {
+    let mut expanded = String::with_capacity(content.len());
+    let mut copied_up_to = 0;
+    for (span, name) in placeholders(content) {
+        let Some(value) = values.value(name) else {
+            continue;
+        };
+        expanded.push_str(&content[copied_up_to..span.start]);
+        match html {
+            true => expanded.push_str(&escape_html(value)),
+            false => expanded.push_str(value),
+        }
+        copied_up_to = span.end;
+    }
+    expanded.push_str(&content[copied_up_to..]);
+    expanded
+}
+
+// Every `{{ name }}` with its byte range, names trimmed
+fn placeholders(content: &str) -> Vec<(std::ops::Range<usize>, &str)> {
+    let mut found = Vec::new();
+    let mut from = 0;
+    while let Some(offset) = content[from..].find("{{") {
+        let start = from + offset;
+        let Some(len) = content[start + 2..].find("}}") else {
+            break;
+        };
+        let end = start + 2 + len + 2;
+        found.push((start..end, content[start + 2..end - 2].trim()));
+        from = end;
+    }
+    found
+}
+
+pub(super) fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::{assert_err, assert_ok};
+
+    use super::{RecipientPlaceholders, expand_placeholders, validate_placeholders};
+
+    fn values() -> RecipientPlaceholders {
+        RecipientPlaceholders {
+            user_name: "ada<3".to_string(),
+            unsubscribe_url: "https://example.com/unsubscribe?token=abc&x=1".to_string(),
+        }
+    }
+
+    #[test]
+    fn known_placeholders_are_accepted() {
+        assert_ok!(validate_placeholders(
+            "Hi {{user_name}}, leave at {{ unsubscribe_url }}"
+        ));
+        assert_ok!(validate_placeholders("No placeholders, just { braces }"));
+    }
+
+    #[test]
+    fn unknown_placeholders_are_rejected_by_name() {
+        let error = validate_placeholders("Hi {{first_name}} and {{user_name}}").unwrap_err();
+
+        assert!(error.contains("{{first_name}}"), "{error}");
+        assert!(!error.contains("{{user_name}},"), "{error}");
+        assert_err!(validate_placeholders("{{}}"));
+    }
+
+    #[test]
+    fn placeholders_are_expanded_in_text_as_is() {
+        let expanded = expand_placeholders(
+            "Hi {{user_name}}, see {{ unsubscribe_url }}",
+            &values(),
+            false,
+        );
+
+        assert_eq!(
+            expanded,
+            "Hi ada<3, see https://example.com/unsubscribe?token=abc&x=1"
+        );
+    }
+
+    #[test]
+    fn placeholders_are_escaped_in_html() {
+        let expanded = expand_placeholders(
+            "<p>Hi {{user_name}}</p><a href=\"{{unsubscribe_url}}\">leave</a>",
+            &values(),
+            true,
+        );
+
+        assert_eq!(
+            expanded,
+            "<p>Hi ada&lt;3</p><a href=\"https://example.com/unsubscribe?token=abc&amp;x=1\">leave</a>"
+        );
+    }
+}
@@ -0,0 +1,137 @@
+use base64::{Engine, engine::general_purpose::STANDARD};
+use secrecy::{ExposeSecret, Secret};
+use serde::Deserialize;
+use subtle::ConstantTimeEq;
+
+// Bounce types after which Postmark will not deliver to the address again
+const PERMANENT_BOUNCE_TYPES: &[&str] = &["HardBounce", "BadEmailAddress", "ManuallyDeactivated"];
+
+// An event posted by Postmark. Only the fields needed to act on bounces are read, every other
+// record type is acknowledged and ignored.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+pub struct PostmarkWebhookEvent {
+    pub record_type: String,
+    #[serde(rename = "Type")]
+    pub bounce_type: Option<String>,
+    pub email: Option<String>,
+}
+
+impl PostmarkWebhookEvent {
+    // The address to stop sending to, None for soft bounces and other events
+    pub fn permanently_bounced_email(&self) -> Option<&str> {
+        if self.record_type != "Bounce" {
+            return None;
+        }
+        let bounce_type = self.bounce_type.as_deref()?;
+        if !PERMANENT_BOUNCE_TYPES.contains(&bounce_type) {
+            return None;
+        }
+        self.email.as_deref()
+    }
+
+    // The address of a recipient who marked an email as spam
+    pub fn spam_complaint_email(&self) -> Option<&str> {
+        if self.record_type != "SpamComplaint" {
+            return None;
+        }
+        self.email.as_deref()
+    }
+}
+
+// Postmark does not sign its webhooks, it authenticates with the basic auth credentials put in
+// the webhook URL. Checks an `Authorization` header against them, in constant time.
+pub fn verify_webhook_credentials(
+    username: &str,
+    password: &Secret<String>,
+    authorization: &str,
+) -> bool {
+    let Some(encoded) = authorization.strip_prefix("Basic ") else {
+        return false;
+    };
+    let Ok(decoded) = STANDARD.decode(encoded.trim()) else {
+        return false;
+    };
+    let Ok(decoded) = String::from_utf8(decoded) else {
+        return false;
+    };
+    let Some((given_username, given_password)) = decoded.split_once(':') else {
+        return false;
+    };
+
+    // Both are compared whatever the first one's outcome, so timing tells nothing of either
+    let username_matches = given_username.as_bytes().ct_eq(username.as_bytes());
+    let password_matches = given_password
+        .as_bytes()
+        .ct_eq(password.expose_secret().as_bytes());
+    (username_matches & password_matches).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use base64::{Engine, engine::general_purpose::STANDARD};
+    use secrecy::Secret;
+
+    use super::{PostmarkWebhookEvent, verify_webhook_credentials};
+
+    fn event(json: &str) -> PostmarkWebhookEvent {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn hard_bounces_are_permanent() {
+        let event = event(
+            r#"{"RecordType": "Bounce", "Type": "HardBounce", "TypeCode": 1, "Email": "gone@example.com"}"#,
+        );
+
+        assert_eq!(event.permanently_bounced_email(), Some("gone@example.com"));
+    }
+
+    #[test]
+    fn soft_bounces_and_other_events_are_not_permanent() {
+        for json in [
+            r#"{"RecordType": "Bounce", "Type": "SoftBounce", "Email": "full@example.com"}"#,
+            r#"{"RecordType": "Delivery", "Email": "fine@example.com"}"#,
+            r#"{"RecordType": "Open"}"#,
+        ] {
+            assert_eq!(event(json).permanently_bounced_email(), None, "{json}");
+        }
+    }
+
+    #[test]
+    fn spam_complaints_are_told_apart_from_bounces() {
+        let complaint = event(
+            r#"{"RecordType": "SpamComplaint", "Type": "SpamComplaint", "Email": "angry@example.com"}"#,
+        );
+        let bounce =
+            event(r#"{"RecordType": "Bounce", "Type": "HardBounce", "Email": "gone@example.com"}"#);
+
+        assert_eq!(complaint.spam_complaint_email(), Some("angry@example.com"));
+        assert_eq!(complaint.permanently_bounced_email(), None);
+        assert_eq!(bounce.spam_complaint_email(), None);
+    }
+
+    #[test]
+    fn credentials_must_match_both_username_and_password() {
+        let password = Secret::new("webhook-password".to_string());
+        let header = |credentials: &str| format!("Basic {}", STANDARD.encode(credentials));
+
+        assert!(verify_webhook_credentials(
+            "postmark",
+            &password,
+            &header("postmark:webhook-password")
+        ));
+        for authorization in [
+            header("postmark:other-password"),
+            header("someone:webhook-password"),
+            header("postmark"),
+            "Bearer webhook-password".to_string(),
+            "Basic not base64!".to_string(),
+        ] {
+            assert!(
+                !verify_webhook_credentials("postmark", &password, &authorization),
+                "{authorization}"
+            );
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use unicode_segmentation::UnicodeSegmentation;
+use uuid::Uuid;
+
+const MAX_NAME_LENGTH: usize = 100;
+// Logins are kept for a few years at most, longer windows would match everyone anyway
+const MAX_ACTIVE_WITHIN_DAYS: i32 = 3650;
+
+#[derive(Deserialize, Debug)]
+pub struct CreateSegmentPayload {
+    pub name: String,
+    pub signed_up_after: Option<DateTime<Utc>>,
+    pub signed_up_before: Option<DateTime<Utc>>,
+    // Subscribers who logged in within this many days
+    pub active_within_days: Option<i32>,
+}
+
+// Subscribers matching every criterion given, see `repository::fan_out_next_chunk`
+#[derive(Debug)]
+pub struct NewsletterSegment {
+    pub name: String,
+    pub signed_up_after: Option<DateTime<Utc>>,
+    pub signed_up_before: Option<DateTime<Utc>>,
+    pub active_within_days: Option<i32>,
+}
+
+impl TryFrom<CreateSegmentPayload> for NewsletterSegment {
+    type Error = String;
+
+    fn try_from(payload: CreateSegmentPayload) -> Result<Self, Self::Error> {
+        let name = payload.name.trim();
+        if name.is_empty() {
+            return Err("Invalid segment name: cannot be empty.".to_string());
+        }
+        if name.graphemes(true).count() > MAX_NAME_LENGTH {
+            return Err(format!(
+                "Invalid segment name: cannot be longer than {MAX_NAME_LENGTH} characters."
+            ));
+        }
+
+        if payload.signed_up_after.is_none()
+            && payload.signed_up_before.is_none()
+            && payload.active_within_days.is_none()
+        {
+            return Err(
+                "Invalid segment: give at least one of signed_up_after, signed_up_before or active_within_days."
+                    .to_string(),
+            );
+        }
+        if let (Some(after), Some(before)) = (payload.signed_up_after, payload.signed_up_before)
+            && after >= before
+        {
+            return Err(
+                "Invalid segment: signed_up_after must be before signed_up_before.".to_string(),
+            );
+        }
+        if let Some(days) = payload.active_within_days
+            && !(1..=MAX_ACTIVE_WITHIN_DAYS).contains(&days)
+        {
+            return Err(format!(
+                "Invalid segment: active_within_days must be between 1 and {MAX_ACTIVE_WITHIN_DAYS}."
+            ));
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            signed_up_after: payload.signed_up_after,
+            signed_up_before: payload.signed_up_before,
+            active_within_days: payload.active_within_days,
+        })
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct NewsletterSegmentSummary {
+    pub id: Uuid,
+    pub name: String,
+    pub signed_up_after: Option<DateTime<Utc>>,
+    pub signed_up_before: Option<DateTime<Utc>>,
+    pub active_within_days: Option<i32>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use claims::{assert_err, assert_ok};
+
+    use super::{CreateSegmentPayload, NewsletterSegment};
+
+    fn payload(name: &str) -> CreateSegmentPayload {
+        CreateSegmentPayload {
+            name: name.to_string(),
+            signed_up_after: None,
+            signed_up_before: None,
+            active_within_days: Some(30),
+        }
+    }
+
+    #[test]
+    fn segment_with_a_name_and_a_criterion_is_accepted() {
+        let segment = NewsletterSegment::try_from(payload("  Active readers ")).unwrap();
+
+        assert_eq!(segment.name, "Active readers");
+        assert_eq!(segment.active_within_days, Some(30));
+    }
+
+    #[test]
+    fn empty_or_overlong_names_are_rejected() {
+        assert_err!(NewsletterSegment::try_from(payload("   ")));
+        assert_err!(NewsletterSegment::try_from(payload(&"a".repeat(101))));
+        assert_ok!(NewsletterSegment::try_from(payload(&"a".repeat(100))));
+    }
+
+    #[test]
+    fn segment_without_criteria_is_rejected() {
+        let payload = CreateSegmentPayload {
+            active_within_days: None,
+            ..payload("Everyone")
+        };
+
+        assert_err!(NewsletterSegment::try_from(payload));
+    }
+
+    #[test]
+    fn signup_window_must_not_be_empty() {
+        let now = Utc::now();
+        let payload = CreateSegmentPayload {
+            signed_up_after: Some(now),
+            signed_up_before: Some(now - Duration::days(1)),
+            ..payload("Backwards")
+        };
+
+        assert_err!(NewsletterSegment::try_from(payload));
+    }
+
+    #[test]
+    fn activity_window_must_be_within_bounds() {
+        for days in [0, -5, 3651] {
+            let payload = CreateSegmentPayload {
+                active_within_days: Some(days),
+                ..payload("Out of bounds")
+            };
+            assert_err!(NewsletterSegment::try_from(payload));
+        }
+    }
+}
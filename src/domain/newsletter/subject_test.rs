@@ -0,0 +1,133 @@
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+// Which of the two subject lines of an issue a recipient is sent. Issues without an
+// `alternative_title` are only ever sent variant `A`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubjectVariant {
+    A,
+    B,
+}
+
+impl SubjectVariant {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SubjectVariant::A => "a",
+            SubjectVariant::B => "b",
+        }
+    }
+
+    // Splits recipients evenly, and always the same way for a given issue so a retried delivery
+    // is sent the subject line of its first attempt
+    pub fn for_recipient(issue_id: Uuid, email: &str) -> Self {
+        let hash = Sha256::new()
+            .chain_update(issue_id.as_bytes())
+            .chain_update(email.as_bytes())
+            .finalize();
+        match hash[0] % 2 {
+            0 => SubjectVariant::A,
+            _ => SubjectVariant::B,
+        }
+    }
+}
+
+// How one subject line of an A/B tested issue did
+#[derive(serde::Serialize, Debug)]
+pub struct SubjectVariantStats {
+    pub variant: &'static str,
+    pub title: String,
+    pub sent: i64,
+    pub opened: i64,
+    pub open_rate: f64,
+}
+
+impl SubjectVariantStats {
+    pub fn new(variant: SubjectVariant, title: String, sent: i64, opened: i64) -> Self {
+        Self {
+            variant: variant.as_str(),
+            title,
+            sent,
+            opened,
+            open_rate: match sent {
+                0 => 0.0,
+                sent => opened as f64 / sent as f64,
+            },
+        }
+    }
+}
+
+#[derive(serde::Serialize, Debug)]
+pub struct SubjectTest {
+    pub variants: Vec<SubjectVariantStats>,
+    // The variant with the higher open rate, None until both were sent or while they are tied
+    pub winner: Option<&'static str>,
+}
+
+impl SubjectTest {
+    pub fn new(a: SubjectVariantStats, b: SubjectVariantStats) -> Self {
+        let winner = if a.sent == 0 || b.sent == 0 || a.open_rate == b.open_rate {
+            None
+        } else if a.open_rate > b.open_rate {
+            Some(a.variant)
+        } else {
+            Some(b.variant)
+        };
+        Self {
+            variants: vec![a, b],
+            winner,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::{SubjectTest, SubjectVariant, SubjectVariantStats};
+
+    #[test]
+    fn recipients_are_split_between_both_variants_consistently() {
+        let issue_id = Uuid::new_v4();
+        let variants: Vec<_> = (0..200)
+            .map(|i| SubjectVariant::for_recipient(issue_id, &format!("user{i}@example.com")))
+            .collect();
+
+        let b = variants.iter().filter(|v| **v == SubjectVariant::B).count();
+        assert!(
+            (60..=140).contains(&b),
+            "{b} of 200 recipients got variant b"
+        );
+        for (i, variant) in variants.iter().enumerate() {
+            assert_eq!(
+                SubjectVariant::for_recipient(issue_id, &format!("user{i}@example.com")),
+                *variant
+            );
+        }
+    }
+
+    #[test]
+    fn the_variant_with_the_higher_open_rate_wins() {
+        let test = SubjectTest::new(
+            SubjectVariantStats::new(SubjectVariant::A, "A".into(), 10, 2),
+            SubjectVariantStats::new(SubjectVariant::B, "B".into(), 8, 4),
+        );
+
+        assert_eq!(test.winner, Some("b"));
+        assert_eq!(test.variants[1].open_rate, 0.5);
+    }
+
+    #[test]
+    fn there_is_no_winner_until_both_variants_were_sent_or_while_tied() {
+        let unsent = SubjectTest::new(
+            SubjectVariantStats::new(SubjectVariant::A, "A".into(), 3, 1),
+            SubjectVariantStats::new(SubjectVariant::B, "B".into(), 0, 0),
+        );
+        let tied = SubjectTest::new(
+            SubjectVariantStats::new(SubjectVariant::A, "A".into(), 4, 2),
+            SubjectVariantStats::new(SubjectVariant::B, "B".into(), 2, 1),
+        );
+
+        assert_eq!(unsent.winner, None);
+        assert_eq!(tied.winner, None);
+    }
+}
@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Debug)]
+pub struct ImportSubscribersQuery {
+    // Imported addresses are sent a confirmation link instead of being subscribed right away
+    #[serde(default)]
+    pub double_opt_in: bool,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SubscriberImportStatus {
+    Subscribed,
+    PendingConfirmation,
+    // The address already belongs to an account, whose subscription is left as it is
+    Existing,
+    Invalid,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SubscriberImportRow {
+    // 1-based, matching what spreadsheets show for the uploaded file
+    pub row: usize,
+    pub email: String,
+    pub status: SubscriberImportStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SubscriberImportReport {
+    pub double_opt_in: bool,
+    pub subscribed: u64,
+    pub pending_confirmation: u64,
+    pub existing: u64,
+    pub invalid: u64,
+    pub rows: Vec<SubscriberImportRow>,
+}
+
+impl SubscriberImportReport {
+    pub fn new(double_opt_in: bool, rows: Vec<SubscriberImportRow>) -> Self {
+        let count = |status| rows.iter().filter(|r| r.status == status).count() as u64;
+        Self {
+            double_opt_in,
+            subscribed: count(SubscriberImportStatus::Subscribed),
+            pending_confirmation: count(SubscriberImportStatus::PendingConfirmation),
+            existing: count(SubscriberImportStatus::Existing),
+            invalid: count(SubscriberImportStatus::Invalid),
+            rows,
+        }
+    }
+}
+
+// Reads the email from the first column of each row, skipping empty rows and a header row naming
+// the column `email`. Other columns, e.g. names exported along with the addresses, are ignored.
+pub fn parse_subscriber_csv(csv: &str) -> Vec<(usize, String)> {
+    let mut rows: Vec<(usize, String)> = csv
+        .trim_start_matches('\u{feff}')
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let first_column = line.split(',').next().unwrap_or_default();
+            (
+                i + 1,
+                first_column.trim().trim_matches('"').trim().to_string(),
+            )
+        })
+        .filter(|(_, email)| !email.is_empty())
+        .collect();
+
+    if rows
+        .first()
+        .is_some_and(|(_, email)| email.eq_ignore_ascii_case("email"))
+    {
+        rows.remove(0);
+    }
+
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_subscriber_csv;
+
+    #[test]
+    fn emails_are_read_from_the_first_column() {
+        let rows = parse_subscriber_csv("jane@example.com,Jane\n\"john@example.com\",John\n");
+        assert_eq!(
+            rows,
+            vec![
+                (1, "jane@example.com".to_string()),
+                (2, "john@example.com".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn header_and_empty_rows_are_skipped_keeping_row_numbers() {
+        let rows = parse_subscriber_csv("\u{feff}Email,Name\r\n\r\njane@example.com,Jane\r\n");
+        assert_eq!(rows, vec![(3, "jane@example.com".to_string())]);
+    }
+
+    #[test]
+    fn invalid_emails_are_kept_for_reporting() {
+        let rows = parse_subscriber_csv("email\nnot-an-email\n");
+        assert_eq!(rows, vec![(2, "not-an-email".to_string())]);
+    }
+}
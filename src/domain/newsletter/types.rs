@@ -1,39 +1,121 @@
+use chrono::{DateTime, Utc};
 use serde::Deserialize;
+use uuid::Uuid;
 
+use super::{
+    AttachmentPayload, EmailAttachment, NewsletterTitle, RecipientPlaceholders, SubjectTest,
+    SubjectVariant, expand_placeholders, parse_attachments, rewrite_links,
+};
 use crate::domain::Newsletter;
 
+// Either `markdown`, or both `html` and `text`
 #[derive(Deserialize, Debug)]
 pub struct NewsLetterContentPayload {
-    html: String,
-    text: String,
+    html: Option<String>,
+    text: Option<String>,
+    markdown: Option<String>,
 }
 
 #[derive(Deserialize, Debug)]
 pub struct NewsLetterData {
     title: String,
     content: NewsLetterContentPayload,
+    #[serde(default)]
+    attachments: Vec<AttachmentPayload>,
+}
+
+// Body of `publish_newsletter`, an issue sent right away unless `scheduled_at` is given, and to
+// every subscriber unless `segment_id` is. With an `alternative_title`, half of the recipients
+// get that subject line instead, see `SubjectVariant`.
+#[derive(Deserialize, Debug)]
+pub struct PublishNewsletterPayload {
+    #[serde(flatten)]
+    pub newsletter: NewsLetterData,
+    pub alternative_title: Option<String>,
+    pub scheduled_at: Option<DateTime<Utc>>,
+    pub segment_id: Option<Uuid>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ReschedulePayload {
+    pub scheduled_at: DateTime<Utc>,
+}
+
+// Issues can be scheduled for later, never for the past
+pub fn validate_scheduled_at(
+    scheduled_at: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Result<DateTime<Utc>, String> {
+    if scheduled_at <= now {
+        return Err("scheduled_at must be in the future.".to_string());
+    }
+    Ok(scheduled_at)
+}
+
+// A subject line to A/B test against the issue title, which it has to differ from
+pub fn validate_alternative_title(
+    alternative_title: String,
+    title: &NewsletterTitle,
+) -> Result<NewsletterTitle, String> {
+    let alternative_title = NewsletterTitle::parse(alternative_title)?;
+    if alternative_title.as_ref() == title.as_ref() {
+        return Err("alternative_title must differ from the title.".to_string());
+    }
+    Ok(alternative_title)
 }
 
 impl TryFrom<NewsLetterData> for Newsletter {
     type Error = String;
 
     fn try_from(payload: NewsLetterData) -> Result<Self, Self::Error> {
-        Newsletter::new(payload.title, payload.content.html, payload.content.text)
+        let NewsLetterContentPayload {
+            html,
+            text,
+            markdown,
+        } = payload.content;
+        let newsletter = match (markdown, html, text) {
+            (Some(markdown), None, None) => Newsletter::from_markdown(payload.title, markdown),
+            (None, Some(html), Some(text)) => Newsletter::new(payload.title, html, text),
+            _ => Err(
+                "Invalid newsletter content: provide either markdown, or both html and text."
+                    .to_string(),
+            ),
+        }?;
+        Ok(Newsletter {
+            attachments: parse_attachments(payload.attachments)?,
+            ..newsletter
+        })
     }
 }
 
 pub struct NewsletterIssue {
     title: String,
+    alternative_title: Option<String>,
     text_content: String,
     html_content: String,
+    attachments: Vec<EmailAttachment>,
 }
 
 impl NewsletterIssue {
     pub fn new(title: String, text_content: String, html_content: String) -> Self {
         Self {
             title,
+            alternative_title: None,
             text_content,
             html_content,
+            attachments: Vec::new(),
+        }
+    }
+    pub fn with_alternative_title(self, alternative_title: Option<String>) -> Self {
+        Self {
+            alternative_title,
+            ..self
+        }
+    }
+    pub fn with_attachments(self, attachments: Vec<EmailAttachment>) -> Self {
+        Self {
+            attachments,
+            ..self
         }
     }
     pub fn title(&self) -> &str {
@@ -45,4 +127,275 @@ impl NewsletterIssue {
     pub fn html_content(&self) -> &str {
         &self.html_content
     }
+    pub fn attachments(&self) -> &[EmailAttachment] {
+        &self.attachments
+    }
+
+    // Always `A` unless the issue is A/B testing its subject line
+    pub fn subject_variant_for(&self, issue_id: Uuid, email: &str) -> SubjectVariant {
+        match self.alternative_title {
+            Some(_) => SubjectVariant::for_recipient(issue_id, email),
+            None => SubjectVariant::A,
+        }
+    }
+
+    // The issue with the subject line of `variant`
+    pub fn with_subject_variant(self, variant: SubjectVariant) -> Self {
+        match (variant, self.alternative_title) {
+            (SubjectVariant::B, Some(alternative_title)) => Self {
+                title: alternative_title,
+                alternative_title: None,
+                ..self
+            },
+            (_, alternative_title) => Self {
+                alternative_title,
+                ..self
+            },
+        }
+    }
+
+    // The issue as sent to one subscriber, ending with their own unsubscribe link
+    pub fn with_unsubscribe_link(self, link: &str) -> Self {
+        Self {
+            text_content: format!(
+                "{}\n\nTo stop receiving this newsletter, visit {link}",
+                self.text_content
+            ),
+            html_content: format!(
+                "{}<br /><br /><a href=\"{link}\">Unsubscribe</a> from this newsletter.",
+                self.html_content
+            ),
+            ..self
+        }
+    }
+
+    // Loaded by the recipient's mail client when the issue is opened with images shown, so
+    // opens are undercounted rather than overcounted
+    pub fn with_open_tracking(self, pixel_url: &str) -> Self {
+        Self {
+            html_content: format!(
+                "{}<img src=\"{pixel_url}\" width=\"1\" height=\"1\" alt=\"\" />",
+                self.html_content
+            ),
+            ..self
+        }
+    }
+
+    // The issue as written for one recipient, applied once nothing else is added to it
+    pub fn with_placeholders(self, values: &RecipientPlaceholders) -> Self {
+        Self {
+            text_content: expand_placeholders(&self.text_content, values, false),
+            html_content: expand_placeholders(&self.html_content, values, true),
+            ..self
+        }
+    }
+
+    // Sends each web link through a redirect that records the click, `redirect_url` being given
+    // the position of the link in the issue
+    pub fn with_click_tracking(self, redirect_url: impl Fn(usize) -> String) -> Self {
+        Self {
+            html_content: rewrite_links(&self.html_content, redirect_url),
+            ..self
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct FailedDelivery {
+    pub newsletter_issue_id: Uuid,
+    pub user_email: String,
+    pub n_retries: i32,
+    pub last_error: String,
+    pub last_attempted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug)]
+pub struct PendingIssue {
+    pub title: String,
+    pub pending: i64,
+}
+
+#[derive(Debug)]
+pub struct DeliveryQueueStats {
+    pub total: i64,
+    pub ready: i64,
+    pub retrying: i64,
+    pub on_final_retry: i64,
+    // Tasks are moved to the dead-letter queue once retried this many times
+    pub max_retries: i32,
+    pub dead_lettered: i64,
+    // (n_retries, number of tasks) pairs ordered by n_retries
+    pub retries_distribution: Vec<(i32, i64)>,
+    pub pending_issues: Vec<PendingIssue>,
+    pub recent_errors: Vec<FailedDelivery>,
+    pub is_paused: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum FanOutOutcome {
+    NothingPending,
+    // The delivery queue is already deep, so enqueueing waits until workers catch up
+    Throttled,
+    ChunkEnqueued { enqueued: u64, finished: bool },
+}
+
+// How a delivery task left the queue
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryOutcome {
+    Sent,
+    // Undeliverable address, or still failing after the last retry. Also kept in the dead-letter
+    // queue, see `repository::dead_letter_delivery`
+    Failed,
+    // The recipient unsubscribed or deleted their account after the issue was enqueued
+    Skipped,
+}
+
+impl DeliveryOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeliveryOutcome::Sent => "sent",
+            DeliveryOutcome::Failed => "failed",
+            DeliveryOutcome::Skipped => "skipped",
+        }
+    }
+}
+
+// Why an address was put on the suppression list
+#[derive(Debug, Clone, Copy)]
+pub enum SuppressionReason {
+    SpamComplaint,
+    HardBounce,
+    // Added by an admin
+    Manual,
+}
+
+impl SuppressionReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SuppressionReason::SpamComplaint => "spam_complaint",
+            SuppressionReason::HardBounce => "hard_bounce",
+            SuppressionReason::Manual => "manual",
+        }
+    }
+}
+
+#[derive(serde::Serialize, Debug)]
+pub struct SuppressedEmail {
+    pub email: String,
+    pub reason: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SuppressEmailPayload {
+    pub email: String,
+}
+
+#[derive(serde::Serialize, Debug)]
+pub struct NewsletterIssueStatus {
+    pub id: Uuid,
+    pub title: String,
+    pub fan_out_status: String,
+    pub scheduled_at: Option<DateTime<Utc>>,
+    // None when sent to every subscriber
+    pub segment_id: Option<Uuid>,
+    pub enqueued: i64,
+    // Still queued, including those waiting for a retry
+    pub pending_deliveries: i64,
+    pub retrying_deliveries: i64,
+    pub sent: i64,
+    pub failed: i64,
+    pub skipped: i64,
+    // Recipients who opened the issue at least once, and their share of those it was sent to
+    pub opened: i64,
+    pub open_rate: f64,
+    // Every click on a link of the issue, and the recipients who clicked at least one
+    pub clicks: i64,
+    pub clicked: i64,
+    pub click_rate: f64,
+    pub links: Vec<LinkClicks>,
+    // Opens per subject line, None unless the issue was published with an `alternative_title`
+    pub subject_test: Option<SubjectTest>,
+    // Every subscriber has been enqueued and every delivery has left the queue
+    pub is_complete: bool,
+    pub failures: Vec<RecipientFailure>,
+    pub created_at: DateTime<Utc>,
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+#[derive(serde::Serialize, Debug)]
+pub struct LinkClicks {
+    pub position: i32,
+    pub url: String,
+    pub clicks: i64,
+    pub unique_clicks: i64,
+}
+
+#[derive(serde::Serialize, Debug)]
+pub struct RecipientFailure {
+    pub user_email: String,
+    pub error: String,
+    pub attempts: i32,
+    pub failed_at: DateTime<Utc>,
+}
+
+// An issue as listed for admins, with how far its delivery has got
+#[derive(serde::Serialize, Debug)]
+pub struct NewsletterIssueSummary {
+    pub id: Uuid,
+    pub title: String,
+    // None for drafts
+    pub published_at: Option<DateTime<Utc>>,
+    pub fan_out_status: String,
+    // Fan-out waits until then, None for issues sent right away
+    pub scheduled_at: Option<DateTime<Utc>>,
+    pub enqueued: i64,
+    pub pending_deliveries: i64,
+    // Enqueued deliveries no longer in the queue, whether sent, skipped or given up on
+    pub completed_deliveries: i64,
+}
+
+#[derive(serde::Serialize, Debug)]
+pub struct WorkerHeartbeat {
+    pub worker_id: Uuid,
+    pub hostname: String,
+    pub started_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    pub is_alive: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use claims::{assert_err, assert_ok, assert_ok_eq};
+
+    use super::{validate_alternative_title, validate_scheduled_at};
+    use crate::domain::NewsletterTitle;
+
+    #[test]
+    fn issues_can_only_be_scheduled_for_the_future() {
+        let now = Utc::now();
+
+        assert_ok_eq!(
+            validate_scheduled_at(now + Duration::minutes(1), now),
+            now + Duration::minutes(1)
+        );
+        assert_err!(validate_scheduled_at(now, now));
+        assert_err!(validate_scheduled_at(now - Duration::minutes(1), now));
+    }
+
+    #[test]
+    fn alternative_titles_must_be_valid_and_differ_from_the_title() {
+        let title = NewsletterTitle::parse("Weekly update".to_string()).unwrap();
+
+        assert_ok!(validate_alternative_title(
+            "This week in Rust".to_string(),
+            &title
+        ));
+        assert_err!(validate_alternative_title(
+            "Weekly update".to_string(),
+            &title
+        ));
+        assert_err!(validate_alternative_title("".to_string(), &title));
+    }
 }
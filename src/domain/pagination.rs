@@ -0,0 +1,70 @@
+use serde::Deserialize;
+
+use crate::domain::{Limit, Metadata, Page};
+
+// `?page=&limit=` of a listing that takes no other parameters
+#[derive(Deserialize, Debug)]
+pub struct PageQuery {
+    #[serde(default = "default_page")]
+    pub page: i32,
+    #[serde(default = "default_limit")]
+    pub limit: i32,
+}
+
+fn default_page() -> i32 {
+    1
+}
+
+fn default_limit() -> i32 {
+    20
+}
+
+#[derive(Debug)]
+pub struct Pagination {
+    pub page: Page,
+    pub limit: Limit,
+}
+
+impl Pagination {
+    pub fn parse(page: i32, limit: i32) -> Result<Self, String> {
+        Ok(Self {
+            page: Page::parse(page)?,
+            limit: Limit::parse(limit)?,
+        })
+    }
+
+    pub(crate) fn offset(&self) -> i32 {
+        (self.page.value() - 1) * self.limit.value()
+    }
+
+    pub(crate) fn metadata(&self, total_records: i64) -> Metadata {
+        Metadata::calculate(total_records, self.page.value(), self.limit.value())
+    }
+}
+
+impl TryFrom<PageQuery> for Pagination {
+    type Error = String;
+
+    fn try_from(query: PageQuery) -> Result<Self, Self::Error> {
+        Self::parse(query.page, query.limit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::assert_err;
+
+    use super::Pagination;
+
+    #[test]
+    fn offset_skips_the_previous_pages() {
+        let pagination = Pagination::parse(3, 20).unwrap();
+        assert_eq!(pagination.offset(), 40);
+    }
+
+    #[test]
+    fn invalid_page_or_limit_is_rejected() {
+        assert_err!(Pagination::parse(0, 20));
+        assert_err!(Pagination::parse(1, 101));
+    }
+}
@@ -0,0 +1,174 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, PartialEq)]
+pub enum ExportFormat {
+    Json,
+    Markdown,
+}
+
+impl ExportFormat {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s.trim().to_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "markdown" | "md" => Ok(Self::Markdown),
+            _ => Err("invalid export format: use either `json` or `markdown`".to_string()),
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "application/json",
+            ExportFormat::Markdown => "text/markdown; charset=utf-8",
+        }
+    }
+
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Markdown => "md",
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ExportPostsQuery {
+    #[serde(default = "default_format")]
+    pub format: String,
+    #[serde(default)]
+    pub include_comments: bool,
+}
+
+fn default_format() -> String {
+    "json".to_string()
+}
+
+#[derive(Serialize, Debug)]
+pub struct ExportedComment {
+    pub id: Uuid,
+    pub text: String,
+    pub created_at: DateTime<Utc>,
+    pub created_by_name: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ExportedPost {
+    pub id: Uuid,
+    pub title: String,
+    pub text: String,
+    pub img: String,
+    pub likes_count: usize,
+    pub created_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comments: Option<Vec<ExportedComment>>,
+}
+
+impl ExportedPost {
+    pub fn to_markdown(&self) -> String {
+        let mut md = format!(
+            "# {}\n\n![cover]({})\n\n*Published {} · {} likes*\n\n{}\n",
+            self.title,
+            self.img,
+            self.created_at.format("%Y-%m-%d %H:%M UTC"),
+            self.likes_count,
+            self.text,
+        );
+
+        if let Some(comments) = &self.comments {
+            md.push_str(&format!("\n## Comments ({})\n\n", comments.len()));
+            for comment in comments {
+                md.push_str(&format!(
+                    "- **{}** ({}): {}\n",
+                    comment.created_by_name,
+                    comment.created_at.format("%Y-%m-%d %H:%M UTC"),
+                    comment.text,
+                ));
+            }
+        }
+
+        md.push_str("\n---\n\n");
+        md
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::{assert_err, assert_ok};
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn post(comments: Option<Vec<ExportedComment>>) -> ExportedPost {
+        ExportedPost {
+            id: Uuid::new_v4(),
+            title: "My Post".into(),
+            text: "Body text".into(),
+            img: "https://example.com/img.jpg".into(),
+            likes_count: 3,
+            created_at: Utc::now(),
+            comments,
+        }
+    }
+
+    // `ExportFormat` tests
+    #[test]
+    fn json_format_is_accepted() {
+        assert_eq!(ExportFormat::parse("json"), Ok(ExportFormat::Json));
+    }
+
+    #[test]
+    fn markdown_format_and_alias_are_accepted() {
+        assert_eq!(ExportFormat::parse("markdown"), Ok(ExportFormat::Markdown));
+        assert_eq!(ExportFormat::parse("md"), Ok(ExportFormat::Markdown));
+    }
+
+    #[test]
+    fn format_is_case_insensitive() {
+        assert_ok!(ExportFormat::parse("JSON"));
+    }
+
+    #[test]
+    fn unknown_format_is_rejected() {
+        assert_err!(ExportFormat::parse("csv"));
+    }
+
+    // `ExportedPost` tests
+    #[test]
+    fn markdown_contains_title_and_body() {
+        let md = post(None).to_markdown();
+        assert!(md.starts_with("# My Post\n"));
+        assert!(md.contains("Body text"));
+        assert!(!md.contains("## Comments"));
+    }
+
+    #[test]
+    fn markdown_lists_comments_when_present() {
+        let comments = vec![ExportedComment {
+            id: Uuid::new_v4(),
+            text: "Nice!".into(),
+            created_at: Utc::now(),
+            created_by_name: "alice".into(),
+        }];
+        let md = post(Some(comments)).to_markdown();
+        assert!(md.contains("## Comments (1)"));
+        assert!(md.contains("**alice**"));
+    }
+
+    #[test]
+    fn json_omits_comments_when_not_requested() {
+        let json = serde_json::to_value(post(None)).unwrap();
+        assert!(json.get("comments").is_none());
+    }
+
+    // Property-based tests
+    proptest! {
+        #[test]
+        fn unknown_formats_are_rejected(
+            format in r"[a-z]{1,10}",
+        ) {
+            prop_assume!(!["json", "markdown", "md"].contains(&format.as_str()));
+            prop_assert!(ExportFormat::parse(&format).is_err());
+        }
+    }
+}
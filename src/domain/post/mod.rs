@@ -1,12 +1,20 @@
+mod export;
+mod post_excerpt;
 mod post_img;
+mod post_license;
 mod post_text;
 mod post_title;
+mod proposal;
 mod requests;
 mod types;
 
+pub use export::*;
+pub use post_excerpt::PostExcerpt;
 pub use post_img::PostImg;
+pub use post_license::PostLicense;
 pub use post_text::PostText;
 pub use post_title::PostTitle;
+pub use proposal::*;
 pub use requests::*;
 pub use types::*;
 
@@ -15,16 +23,29 @@ pub struct Post {
     pub title: PostTitle,
     pub text: PostText,
     pub img: PostImg,
+    pub excerpt: PostExcerpt,
+    // `None` when the author did not choose one
+    pub license: Option<PostLicense>,
 }
 
 impl Post {
     pub(super) fn new(title: String, text: String, img: String) -> Result<Self, String> {
+        let text = PostText::parse(text)?;
+        let excerpt = PostExcerpt::from_text(&text);
+
         Ok(Self {
             title: PostTitle::parse(title)?,
-            text: PostText::parse(text)?,
+            text,
             img: PostImg::parse(img)?,
+            excerpt,
+            license: None,
         })
     }
+
+    pub(super) fn with_license(mut self, license: Option<&str>) -> Result<Self, String> {
+        self.license = license.map(PostLicense::parse).transpose()?;
+        Ok(self)
+    }
 }
 
 #[cfg(test)]
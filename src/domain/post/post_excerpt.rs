@@ -0,0 +1,212 @@
+use std::fmt::{self, Display, Formatter};
+
+use super::PostText;
+
+#[derive(Debug)]
+pub struct PostExcerpt(String);
+
+impl PostExcerpt {
+    pub const MAX_WORDS: usize = 40;
+
+    // Markdown syntax that can surround a word, e.g. `**bold**`, `# heading`, `> quote`
+    const MARKDOWN_MARKERS: [char; 9] = ['#', '*', '_', '`', '~', '>', '-', '+', '|'];
+
+    /// Derives a plain-text excerpt from the post body: HTML tags and markdown syntax are
+    /// stripped and only the first `MAX_WORDS` words are kept.
+    pub fn from_text(text: &PostText) -> Self {
+        let plain = Self::plain_text(text.as_ref());
+        let mut words = plain.split_whitespace();
+
+        let mut excerpt = words
+            .by_ref()
+            .take(Self::MAX_WORDS)
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        if words.next().is_some() {
+            excerpt.push('…');
+        }
+
+        Self(excerpt)
+    }
+
+    /// `s` as plain words separated by single spaces, with HTML tags and markdown syntax stripped.
+    pub fn plain_text(s: &str) -> String {
+        Self::strip_markdown_links(&Self::strip_html(s))
+            .split_whitespace()
+            .map(|w| w.trim_matches(&Self::MARKDOWN_MARKERS[..]))
+            .filter(|w| !w.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn strip_html(s: &str) -> String {
+        let mut out = String::with_capacity(s.len());
+        let mut in_tag = false;
+        let mut chars = s.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                // Only `<` opening a tag, so `a < b` in plain text is kept
+                '<' if !in_tag
+                    && chars.peek().is_some_and(|&next| {
+                        next.is_ascii_alphabetic() || matches!(next, '/' | '!')
+                    }) =>
+                {
+                    in_tag = true
+                }
+                // Replace the tag with a space so `<p>a</p><p>b</p>` doesn't become `ab`
+                '>' if in_tag => {
+                    in_tag = false;
+                    out.push(' ');
+                }
+                _ if !in_tag => out.push(c),
+                _ => {}
+            }
+        }
+
+        out.replace("&nbsp;", " ")
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&#39;", "'")
+            .replace("&amp;", "&")
+    }
+
+    // `[text](url)` and `![alt](url)` become `text` and `alt`
+    fn strip_markdown_links(s: &str) -> String {
+        let mut out = String::with_capacity(s.len());
+        let mut chars = s.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '!' if chars.peek() == Some(&'[') => {}
+                '[' => {}
+                ']' if chars.peek() == Some(&'(') => {
+                    // Skip the link target up to and including the closing parenthesis
+                    for skipped in chars.by_ref() {
+                        if skipped == ')' {
+                            break;
+                        }
+                    }
+                }
+                ']' => {}
+                _ => out.push(c),
+            }
+        }
+
+        out
+    }
+}
+
+impl AsRef<str> for PostExcerpt {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for PostExcerpt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::{PostExcerpt, PostText};
+
+    fn excerpt(s: &str) -> String {
+        let text = PostText::parse(s.to_string()).unwrap();
+        PostExcerpt::from_text(&text).as_ref().to_string()
+    }
+
+    // Example-based tests
+    #[test]
+    fn short_plain_text_is_kept_as_is() {
+        assert_eq!(excerpt("A short post body."), "A short post body.");
+    }
+
+    #[test]
+    fn html_tags_are_stripped() {
+        assert_eq!(
+            excerpt("<p>Hello <strong>world</strong></p><p>again</p>"),
+            "Hello world again"
+        );
+    }
+
+    #[test]
+    fn less_than_signs_outside_tags_are_kept() {
+        assert_eq!(
+            excerpt("If a < b and b <= c then a<3, <br/>but <!-- not --> this"),
+            "If a < b and b <= c then a<3, but this"
+        );
+    }
+
+    #[test]
+    fn html_entities_are_decoded() {
+        assert_eq!(excerpt("Fish &amp; chips"), "Fish & chips");
+    }
+
+    #[test]
+    fn markdown_syntax_is_stripped() {
+        assert_eq!(
+            excerpt("# Heading\n\n- **bold** and _italic_ with `code`\n> quoted"),
+            "Heading bold and italic with code quoted"
+        );
+    }
+
+    #[test]
+    fn markdown_links_keep_only_their_text() {
+        assert_eq!(
+            excerpt("Read [the docs](https://docs.rs) and ![a cat](https://img.com/cat.png)"),
+            "Read the docs and a cat"
+        );
+    }
+
+    #[test]
+    fn long_text_is_truncated_to_max_words_with_ellipsis() {
+        let text = "word ".repeat(PostExcerpt::MAX_WORDS + 10);
+        let result = excerpt(&text);
+
+        assert!(result.ends_with('…'));
+        assert_eq!(
+            result.trim_end_matches('…').split_whitespace().count(),
+            PostExcerpt::MAX_WORDS
+        );
+    }
+
+    #[test]
+    fn text_with_exactly_max_words_has_no_ellipsis() {
+        let text = "word ".repeat(PostExcerpt::MAX_WORDS);
+        let result = excerpt(&text);
+
+        assert!(!result.ends_with('…'));
+        assert_eq!(result.split_whitespace().count(), PostExcerpt::MAX_WORDS);
+    }
+
+    // Property-based tests
+    proptest! {
+        #[test]
+        fn excerpt_never_exceeds_max_words(
+            text in r"[a-zA-Z0-9][a-zA-Z0-9 .!?#*<>\[\]()]{0,999}",
+        ) {
+            let result = excerpt(&text);
+            prop_assert!(result.trim_end_matches('…').split_whitespace().count() <= PostExcerpt::MAX_WORDS);
+        }
+
+        #[test]
+        fn excerpt_never_contains_html_tags(
+            words in prop::collection::vec(r"[a-z]{1,10}", 1..20),
+            tag in r"(p|div|span|strong|em)",
+        ) {
+            let text = words
+                .iter()
+                .map(|w| format!("<{tag}>{w}</{tag}>"))
+                .collect::<String>();
+            let result = excerpt(&text);
+            prop_assert!(!result.contains('<') && !result.contains('>'));
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use std::fmt::{self, Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+// Terms under which others may reuse a post. Stored and serialized by the lowercase name
+// readers see, e.g. `cc-by-sa`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PostLicense {
+    Cc0,
+    CcBy,
+    CcBySa,
+    CcByNc,
+    CcByNd,
+    AllRightsReserved,
+}
+
+impl PostLicense {
+    const ALL: [PostLicense; 6] = [
+        PostLicense::Cc0,
+        PostLicense::CcBy,
+        PostLicense::CcBySa,
+        PostLicense::CcByNc,
+        PostLicense::CcByNd,
+        PostLicense::AllRightsReserved,
+    ];
+
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let trimmed = s.trim();
+        Self::ALL
+            .into_iter()
+            .find(|license| license.as_str().eq_ignore_ascii_case(trimmed))
+            .ok_or_else(|| {
+                "Invalid license: must be one of cc0, cc-by, cc-by-sa, cc-by-nc, cc-by-nd or all-rights-reserved.".to_string()
+            })
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PostLicense::Cc0 => "cc0",
+            PostLicense::CcBy => "cc-by",
+            PostLicense::CcBySa => "cc-by-sa",
+            PostLicense::CcByNc => "cc-by-nc",
+            PostLicense::CcByNd => "cc-by-nd",
+            PostLicense::AllRightsReserved => "all-rights-reserved",
+        }
+    }
+}
+
+impl Display for PostLicense {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::{assert_err, assert_ok_eq};
+
+    use super::PostLicense;
+
+    #[test]
+    fn every_license_parses_from_its_own_name() {
+        for license in PostLicense::ALL {
+            assert_ok_eq!(PostLicense::parse(license.as_str()), license);
+        }
+    }
+
+    #[test]
+    fn names_are_matched_case_insensitively() {
+        assert_ok_eq!(PostLicense::parse(" CC-BY-SA "), PostLicense::CcBySa);
+    }
+
+    #[test]
+    fn serde_uses_the_same_names_as_parse() {
+        for license in PostLicense::ALL {
+            let json = serde_json::to_value(license).unwrap();
+            assert_eq!(json, license.as_str());
+        }
+    }
+
+    #[test]
+    fn unknown_licenses_are_rejected() {
+        for license in ["", "mit", "cc", "cc-by-nc-sa"] {
+            assert_err!(PostLicense::parse(license));
+        }
+    }
+}
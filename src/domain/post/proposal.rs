@@ -0,0 +1,68 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::Post;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProposalReview {
+    Approved,
+    Rejected,
+}
+
+impl ProposalReview {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProposalReview::Approved => "approved",
+            ProposalReview::Rejected => "rejected",
+        }
+    }
+}
+
+// A proposal replaces the whole post, like an update by the author, but never its license
+#[derive(Deserialize, Debug)]
+pub struct ProposeEditPayload {
+    pub title: String,
+    pub text: String,
+    pub img: String,
+}
+
+impl TryFrom<ProposeEditPayload> for Post {
+    type Error = String;
+
+    fn try_from(payload: ProposeEditPayload) -> Result<Self, Self::Error> {
+        Post::new(payload.title, payload.text, payload.img)
+    }
+}
+
+pub struct PendingEditProposal {
+    pub post_id: Uuid,
+    pub title: String,
+    pub post_text: String,
+    pub img: String,
+    pub base_version: i32,
+}
+
+impl TryFrom<PendingEditProposal> for Post {
+    type Error = String;
+
+    // Validated when proposed, so this only fails if the rules have since tightened
+    fn try_from(proposal: PendingEditProposal) -> Result<Self, Self::Error> {
+        Post::new(proposal.title, proposal.post_text, proposal.img)
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct EditProposalResponseBody {
+    pub id: Uuid,
+    pub post_id: Uuid,
+    pub proposed_by: Uuid,
+    pub proposed_by_name: String,
+    pub title: String,
+    pub text: String,
+    pub img: String,
+    pub base_version: i32,
+    // The post changed after the proposal was written, so approving it would fail
+    pub is_conflicting: bool,
+    pub created_at: DateTime<Utc>,
+}
@@ -2,9 +2,12 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::domain::PostLicense;
+
 pub struct PostQuery {
     pub title: Option<QueryTitle>,
     pub created_by_id: Option<CreatedBy>,
+    pub license: Option<PostLicense>,
     pub filters: Filters,
 }
 
@@ -19,6 +22,9 @@ impl TryFrom<GetAllPostsQuery> for PostQuery {
             created_by_id: (!query.id.is_empty())
                 .then(|| CreatedBy::parse(query.id))
                 .transpose()?,
+            license: (!query.license.is_empty())
+                .then(|| PostLicense::parse(&query.license))
+                .transpose()?,
             filters: Filters {
                 page: Page::parse(query.page)?,
                 limit: Limit::parse(query.limit)?,
@@ -28,6 +34,27 @@ impl TryFrom<GetAllPostsQuery> for PostQuery {
     }
 }
 
+impl PostQuery {
+    // Everything that shapes the result, normalized so equivalent listings share one key
+    pub fn flight_key(&self) -> String {
+        format!(
+            "title={}|created_by={}|license={}|sort={}|page={}|limit={}",
+            self.title
+                .as_ref()
+                .map(|t| t.as_ref().to_lowercase())
+                .unwrap_or_default(),
+            self.created_by_id
+                .as_ref()
+                .map(|c| c.as_ref().to_string())
+                .unwrap_or_default(),
+            self.license.map(|l| l.as_str()).unwrap_or_default(),
+            self.filters.sort.to_sql(),
+            self.filters.page.value(),
+            self.filters.limit.value()
+        )
+    }
+}
+
 #[derive(Debug)]
 pub struct QueryTitle(String);
 
@@ -111,6 +138,7 @@ impl Limit {
 pub enum SortField {
     Title,
     LikesCount,
+    CommentCount,
     CreatedAt,
 }
 
@@ -134,11 +162,13 @@ impl Sort {
             "title",
             "readtime",
             "likescount",
+            "comments",
             "created_at",
             "-id",
             "-title",
             "-readtime",
             "-likescount",
+            "-comments",
             "-created_at",
         ];
 
@@ -156,6 +186,7 @@ impl Sort {
             "title" => SortField::Title,
             "created_at" => SortField::CreatedAt,
             "likescount" => SortField::LikesCount,
+            "comments" => SortField::CommentCount,
             _ => return Err("invalid sort value".to_string()),
         };
 
@@ -167,6 +198,7 @@ impl Sort {
             SortField::Title => "title",
             SortField::CreatedAt => "created_at",
             SortField::LikesCount => "ARRAY_LENGTH(liked_by, 1)",
+            SortField::CommentCount => "comment_count",
         };
 
         let direction = match (&self.field, &self.direction) {
@@ -204,6 +236,31 @@ pub struct GetAllPostsQuery {
     pub limit: i32,
     #[serde(default)]
     pub id: String,
+    #[serde(default)]
+    pub license: String,
+}
+
+// The personalized feed pages and sorts like the public listing, without its search filters
+#[derive(Deserialize, Debug)]
+pub struct GetFeedQuery {
+    #[serde(default = "default_sort")]
+    pub sort: String,
+    #[serde(default = "default_page")]
+    pub page: i32,
+    #[serde(default = "default_limit")]
+    pub limit: i32,
+}
+
+impl TryFrom<GetFeedQuery> for Filters {
+    type Error = String;
+
+    fn try_from(query: GetFeedQuery) -> Result<Self, Self::Error> {
+        Ok(Filters {
+            page: Page::parse(query.page)?,
+            limit: Limit::parse(query.limit)?,
+            sort: Sort::parse(&query.sort)?,
+        })
+    }
 }
 
 fn default_sort() -> String {
@@ -411,6 +468,35 @@ mod tests {
         assert_eq!(limit.value(), 25);
     }
 
+    // `PostQuery` tests
+    fn post_query(title: &str, page: i32) -> PostQuery {
+        PostQuery::try_from(GetAllPostsQuery {
+            sort: "-created_at".to_string(),
+            title: title.to_string(),
+            page,
+            limit: 20,
+            id: String::new(),
+            license: String::new(),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn flight_key_ignores_title_case_and_padding() {
+        assert_eq!(
+            post_query("  Rust ", 1).flight_key(),
+            post_query("rust", 1).flight_key()
+        );
+    }
+
+    #[test]
+    fn flight_key_differs_per_page() {
+        assert_ne!(
+            post_query("rust", 1).flight_key(),
+            post_query("rust", 2).flight_key()
+        );
+    }
+
     // `Sort` tests
     #[test]
     fn valid_sort_title_is_accepted() {
@@ -430,6 +516,12 @@ mod tests {
         assert_ok!(result);
     }
 
+    #[test]
+    fn valid_sort_comments_is_accepted() {
+        let result = Sort::parse("comments");
+        assert_ok!(result);
+    }
+
     #[test]
     fn valid_desc_sort_title_is_accepted() {
         let result = Sort::parse("-title");
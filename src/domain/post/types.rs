@@ -2,7 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::domain::Post;
+use crate::domain::{Post, PostLicense, avatar_url};
 
 #[derive(sqlx::FromRow)]
 pub struct PostRecord {
@@ -10,12 +10,36 @@ pub struct PostRecord {
     pub id: Uuid,
     pub title: String,
     pub post_text: String,
+    pub excerpt: String,
     pub img: String,
     pub version: i32,
     pub liked_by: Option<Vec<Uuid>>,
+    pub comment_count: i64,
     pub created_by: Uuid,
     pub created_at: DateTime<Utc>,
     pub created_by_name: String,
+    pub created_by_avatar_updated_at: Option<DateTime<Utc>>,
+    pub license: String,
+    pub is_community: bool,
+}
+
+// List queries only select the excerpt so cards don't ship the full post body
+#[derive(sqlx::FromRow)]
+pub struct PostSummaryRecord {
+    pub total_count: i64,
+    pub id: Uuid,
+    pub title: String,
+    pub excerpt: String,
+    pub img: String,
+    pub version: i32,
+    pub liked_by: Option<Vec<Uuid>>,
+    pub comment_count: i64,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub created_by_name: String,
+    pub created_by_avatar_updated_at: Option<DateTime<Utc>>,
+    pub license: String,
+    pub is_community: bool,
 }
 
 #[derive(serde::Serialize)]
@@ -23,13 +47,18 @@ pub struct PostResponse {
     pub id: Uuid,
     pub title: String,
     pub text: String,
+    pub excerpt: String,
     pub img: String,
     pub version: i32,
     pub created_at: DateTime<Utc>,
     pub created_by: Uuid,
     created_by_name: String,
+    created_by_avatar_url: Option<String>,
+    pub license: String,
+    pub is_community: bool,
     #[serde(default)]
     pub liked_by: Vec<Uuid>,
+    pub comment_count: i64,
 }
 
 impl From<PostRecord> for PostResponse {
@@ -38,12 +67,61 @@ impl From<PostRecord> for PostResponse {
             id: record.id,
             title: record.title,
             text: record.post_text,
+            excerpt: record.excerpt,
             img: record.img,
             version: record.version,
             created_at: record.created_at,
             created_by: record.created_by,
             created_by_name: record.created_by_name,
+            created_by_avatar_url: avatar_url(
+                record.created_by,
+                record.created_by_avatar_updated_at,
+            ),
+            license: record.license,
+            is_community: record.is_community,
             liked_by: record.liked_by.unwrap_or_default(),
+            comment_count: record.comment_count,
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct PostSummaryResponse {
+    pub id: Uuid,
+    pub title: String,
+    pub excerpt: String,
+    pub img: String,
+    pub version: i32,
+    pub created_at: DateTime<Utc>,
+    pub created_by: Uuid,
+    created_by_name: String,
+    created_by_avatar_url: Option<String>,
+    pub license: String,
+    pub is_community: bool,
+    #[serde(default)]
+    pub liked_by: Vec<Uuid>,
+    pub comment_count: i64,
+}
+
+impl From<PostSummaryRecord> for PostSummaryResponse {
+    fn from(record: PostSummaryRecord) -> Self {
+        Self {
+            id: record.id,
+            title: record.title,
+            excerpt: record.excerpt,
+            img: record.img,
+            version: record.version,
+            created_at: record.created_at,
+            created_by: record.created_by,
+            created_by_name: record.created_by_name,
+            created_by_avatar_url: avatar_url(
+                record.created_by,
+                record.created_by_avatar_updated_at,
+            ),
+            license: record.license,
+            is_community: record.is_community,
+            liked_by: record.liked_by.unwrap_or_default(),
+            comment_count: record.comment_count,
         }
     }
 }
@@ -53,6 +131,23 @@ pub struct CreatePostPayload {
     title: String,
     text: String,
     img: String,
+    #[serde(default)]
+    license: Option<String>,
+    // Community posts accept edit proposals from other users
+    #[serde(default)]
+    pub community: bool,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SuggestMetadataPayload {
+    pub text: String,
+}
+
+#[derive(Serialize, Debug, Default, PartialEq)]
+pub struct MetadataSuggestions {
+    // Best first
+    pub titles: Vec<String>,
+    pub tags: Vec<String>,
 }
 
 #[derive(Serialize)]
@@ -60,7 +155,10 @@ pub struct CreatePostResponse<'a> {
     pub id: Uuid,
     pub title: &'a str,
     pub post_text: &'a str,
+    pub excerpt: &'a str,
     pub img: &'a str,
+    pub license: PostLicense,
+    pub is_community: bool,
     pub created_at: DateTime<Utc>,
     pub created_by: Uuid,
 }
@@ -69,8 +167,8 @@ impl TryFrom<CreatePostPayload> for Post {
     type Error = String;
 
     fn try_from(payload: CreatePostPayload) -> Result<Self, Self::Error> {
-        let post = Self::new(payload.title, payload.text, payload.img)?;
-        Ok(post)
+        Self::new(payload.title, payload.text, payload.img)?
+            .with_license(payload.license.as_deref())
     }
 }
 
@@ -79,12 +177,39 @@ pub struct UpdatePostPayload {
     pub title: String,
     pub text: String,
     pub img: String,
+    // Left unchanged when omitted
+    #[serde(default)]
+    pub license: Option<String>,
 }
 
 impl TryFrom<UpdatePostPayload> for Post {
     type Error = String;
 
     fn try_from(value: UpdatePostPayload) -> Result<Self, Self::Error> {
-        Post::new(value.title, value.text, value.img)
+        Post::new(value.title, value.text, value.img)?.with_license(value.license.as_deref())
     }
 }
+
+#[derive(Debug)]
+pub struct PostEngagement {
+    pub impressions: i64,
+    pub views: i64,
+}
+
+impl PostEngagement {
+    // Click-through rate used as ranking feedback, `None` until the post has been shown at all
+    pub fn ctr(&self) -> Option<f64> {
+        (self.impressions > 0).then(|| self.views as f64 / self.impressions as f64)
+    }
+}
+
+// How much storage moving large bodies into `post_bodies` saves through deduplication
+#[derive(Serialize, Debug)]
+pub struct PostBodyStats {
+    pub stored_bodies: i64,
+    pub stored_bytes: i64,
+    pub posts_using_stored_bodies: i64,
+    // What the same posts would take if every body was stored inline
+    pub logical_bytes: i64,
+    pub saved_bytes: i64,
+}
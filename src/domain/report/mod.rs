@@ -0,0 +1,5 @@
+mod report_reason;
+mod types;
+
+pub use report_reason::ReportReason;
+pub use types::*;
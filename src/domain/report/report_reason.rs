@@ -0,0 +1,54 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+#[derive(Debug)]
+pub struct ReportReason(String);
+
+impl ReportReason {
+    pub fn parse(s: String) -> Result<Self, String> {
+        let trimmed = s.trim();
+
+        if trimmed.is_empty() {
+            return Err("Invalid reason: cannot be empty.".to_string());
+        }
+
+        if trimmed.graphemes(true).count() > 500 {
+            return Err("Invalid reason: cannot exceed 500 characters.".to_string());
+        }
+
+        Ok(Self(trimmed.to_string()))
+    }
+}
+
+impl AsRef<str> for ReportReason {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::{assert_err, assert_ok};
+
+    use super::ReportReason;
+
+    #[test]
+    fn a_500_grapheme_reason_is_valid() {
+        assert_ok!(ReportReason::parse("ё".repeat(500)));
+    }
+
+    #[test]
+    fn a_reason_longer_than_500_graphemes_is_rejected() {
+        assert_err!(ReportReason::parse("a".repeat(501)));
+    }
+
+    #[test]
+    fn whitespace_only_reason_is_rejected() {
+        assert_err!(ReportReason::parse("   ".to_string()));
+    }
+
+    #[test]
+    fn reason_is_trimmed() {
+        let reason = ReportReason::parse("  spam  ".to_string()).unwrap();
+        assert_eq!(reason.as_ref(), "spam");
+    }
+}
@@ -0,0 +1,23 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Deserialize, Debug)]
+pub struct ReportPayload {
+    pub reason: String,
+}
+
+// A reported comment with enough context to review it without opening the post
+#[derive(Serialize, Debug)]
+pub struct CommentReportResponseBody {
+    pub id: Uuid,
+    pub comment_id: Uuid,
+    pub comment_text: String,
+    pub comment_author_id: Uuid,
+    pub post_id: Uuid,
+    pub post_title: String,
+    pub reporter_id: Uuid,
+    pub reporter_name: String,
+    pub reason: String,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,128 @@
+use chrono::{DateTime, Utc};
+use secrecy::Secret;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::{CommentText, UserEmail, UserName};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SubmissionSource {
+    Embed,
+    Anonymous,
+}
+
+impl SubmissionSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SubmissionSource::Embed => "embed",
+            SubmissionSource::Anonymous => "anonymous",
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct EmbedCommentPayload {
+    pub author_name: String,
+    pub author_email: String,
+    pub text: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AnonymousCommentPayload {
+    pub post_id: String,
+    pub text: String,
+    pub author_display_name: String,
+    pub captcha_token: Secret<String>,
+}
+
+// A comment from someone without an account, which only becomes a comment once approved
+#[derive(Debug)]
+pub struct CommentSubmission {
+    pub source: SubmissionSource,
+    pub post_id: Uuid,
+    pub author_name: UserName,
+    // Embed authors are matched to an account by email, anonymous ones never are
+    pub author_email: Option<UserEmail>,
+    pub text: CommentText,
+}
+
+impl CommentSubmission {
+    pub fn embed(post_id: Uuid, payload: EmbedCommentPayload) -> Result<Self, String> {
+        Ok(Self {
+            source: SubmissionSource::Embed,
+            post_id,
+            author_name: UserName::parse(payload.author_name)?,
+            author_email: Some(UserEmail::parse(payload.author_email)?),
+            text: CommentText::parse(payload.text)?,
+        })
+    }
+
+    pub fn anonymous(payload: AnonymousCommentPayload) -> Result<Self, String> {
+        let post_id = Uuid::parse_str(&payload.post_id)
+            .map_err(|_| "Invalid post_id: must be a valid UUID".to_string())?;
+
+        Ok(Self {
+            source: SubmissionSource::Anonymous,
+            post_id,
+            author_name: UserName::parse(payload.author_display_name)?,
+            author_email: None,
+            text: CommentText::parse(payload.text)?,
+        })
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct CommentSubmissionResponseBody {
+    pub id: Uuid,
+    pub source: String,
+    pub post_id: Uuid,
+    pub post_title: String,
+    pub author_name: String,
+    pub author_email: Option<String>,
+    pub text: String,
+    pub origin: Option<String>,
+    pub ip_address: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::{assert_err, assert_ok};
+    use secrecy::Secret;
+
+    use super::{AnonymousCommentPayload, CommentSubmission};
+
+    fn payload(post_id: &str, author_display_name: &str) -> AnonymousCommentPayload {
+        AnonymousCommentPayload {
+            post_id: post_id.to_string(),
+            text: "Nice post".to_string(),
+            author_display_name: author_display_name.to_string(),
+            captcha_token: Secret::new("token".to_string()),
+        }
+    }
+
+    #[test]
+    fn anonymous_submission_is_accepted() {
+        let post_id = uuid::Uuid::new_v4().to_string();
+        assert_ok!(CommentSubmission::anonymous(payload(
+            &post_id,
+            "Curious reader"
+        )));
+    }
+
+    #[test]
+    fn anonymous_submission_validates_display_name_like_user_name() {
+        let post_id = uuid::Uuid::new_v4().to_string();
+        for name in ["", "   ", "name{with}braces", &"a".repeat(257)] {
+            assert_err!(CommentSubmission::anonymous(payload(&post_id, name)));
+        }
+    }
+
+    #[test]
+    fn anonymous_submission_rejects_invalid_post_id() {
+        assert_err!(CommentSubmission::anonymous(payload(
+            "not-a-uuid",
+            "Reader"
+        )));
+    }
+}
@@ -0,0 +1,131 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+// Avatars are shown small, so anything bigger is an unresized photo
+pub const MAX_AVATAR_BYTES: usize = 256 * 1024;
+
+// Formats browsers render natively, each recognised by its leading bytes
+const AVATAR_SIGNATURES: [(&str, &[u8]); 4] = [
+    ("image/png", b"\x89PNG\r\n\x1a\n"),
+    ("image/jpeg", b"\xff\xd8\xff"),
+    ("image/gif", b"GIF8"),
+    ("image/webp", b"RIFF"),
+];
+
+#[derive(Debug)]
+pub struct Avatar {
+    content_type: &'static str,
+    image: Vec<u8>,
+}
+
+impl Avatar {
+    /// Returns an `Avatar` if the declared type is an allowed image format and the bytes are one.
+    pub fn parse(content_type: &str, image: Vec<u8>) -> Result<Self, String> {
+        let Some((content_type, signature)) = AVATAR_SIGNATURES
+            .iter()
+            .find(|(allowed, _)| content_type.eq_ignore_ascii_case(allowed))
+        else {
+            return Err("Invalid avatar: must be a PNG, JPEG, GIF or WebP image.".to_string());
+        };
+
+        if image.is_empty() {
+            return Err("Invalid avatar: image cannot be empty.".to_string());
+        }
+
+        if image.len() > MAX_AVATAR_BYTES {
+            return Err(format!(
+                "Invalid avatar: cannot be larger than {MAX_AVATAR_BYTES} bytes."
+            ));
+        }
+
+        // The declared type is only trusted once the content agrees with it, so nothing else
+        // can be served back under an image type
+        let matches_signature = image.starts_with(signature)
+            && (*content_type != "image/webp" || image.get(8..12) == Some(b"WEBP"));
+        if !matches_signature {
+            return Err(format!(
+                "Invalid avatar: content is not a valid {content_type} image."
+            ));
+        }
+
+        Ok(Self {
+            content_type,
+            image,
+        })
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        self.content_type
+    }
+
+    pub fn image(&self) -> &[u8] {
+        &self.image
+    }
+}
+
+// An avatar as stored, to be served back as is
+#[derive(Debug)]
+pub struct StoredAvatar {
+    pub content_type: String,
+    pub image: Vec<u8>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// Versioned by upload time so the image can be cached indefinitely under its URL
+pub fn avatar_url(user_id: Uuid, avatar_updated_at: Option<DateTime<Utc>>) -> Option<String> {
+    avatar_updated_at.map(|updated_at| {
+        format!(
+            "/v1/user/{user_id}/avatar?v={}",
+            updated_at.timestamp_millis()
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::{assert_err, assert_ok};
+
+    use super::{Avatar, MAX_AVATAR_BYTES};
+
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+
+    #[test]
+    fn a_png_declared_as_png_is_accepted() {
+        let avatar = Avatar::parse("image/png", PNG.to_vec()).unwrap();
+        assert_eq!(avatar.content_type(), "image/png");
+    }
+
+    #[test]
+    fn a_webp_needs_the_webp_marker_after_the_riff_header() {
+        assert_ok!(Avatar::parse(
+            "image/webp",
+            b"RIFF\x24\0\0\0WEBPVP8 ".to_vec()
+        ));
+        assert_err!(Avatar::parse(
+            "image/webp",
+            b"RIFF\x24\0\0\0WAVEfmt ".to_vec()
+        ));
+    }
+
+    #[test]
+    fn content_not_matching_the_declared_type_is_rejected() {
+        assert_err!(Avatar::parse("image/jpeg", PNG.to_vec()));
+        assert_err!(Avatar::parse("image/png", b"<svg></svg>".to_vec()));
+    }
+
+    #[test]
+    fn other_content_types_are_rejected() {
+        for content_type in ["image/svg+xml", "text/html", "application/octet-stream", ""] {
+            assert_err!(Avatar::parse(content_type, PNG.to_vec()));
+        }
+    }
+
+    #[test]
+    fn empty_and_oversized_images_are_rejected() {
+        assert_err!(Avatar::parse("image/png", Vec::new()));
+
+        let mut oversized = PNG.to_vec();
+        oversized.resize(MAX_AVATAR_BYTES + 1, 0);
+        assert_err!(Avatar::parse("image/png", oversized));
+    }
+}
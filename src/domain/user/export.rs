@@ -0,0 +1,24 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+// The account fields included in a personal data export, unlike `UserProfile` this is only
+// ever shown to its owner
+#[derive(Serialize, Debug)]
+pub struct ExportedAccount {
+    pub id: Uuid,
+    pub user_name: String,
+    pub email: String,
+    pub bio: Option<String>,
+    pub website: Option<String>,
+    pub location: Option<String>,
+    pub avatar_url: Option<String>,
+    pub is_subscribed: bool,
+    pub joined_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ExportedLike {
+    pub post_id: Uuid,
+    pub post_title: String,
+}
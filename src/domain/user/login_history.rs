@@ -0,0 +1,18 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+// A password check against the user's account, whether or not it let them in
+#[derive(Serialize, Debug)]
+pub struct LoginEvent {
+    pub occurred_at: DateTime<Utc>,
+    pub succeeded: bool,
+    pub ip_address: String,
+    pub user_agent: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SecurityActivity {
+    pub last_login_at: Option<DateTime<Utc>>,
+    // Newest first
+    pub recent_logins: Vec<LoginEvent>,
+}
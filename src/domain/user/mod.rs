@@ -1,13 +1,45 @@
+mod avatar;
+mod export;
+mod login_history;
+mod password_strength;
+mod personal_access_token;
+mod refresh_token;
+mod role;
+mod search;
+mod session;
+mod suspension;
+mod token_purpose;
 mod types;
+mod user_bio;
 mod user_email;
+mod user_location;
 mod user_name;
 mod user_password;
+mod user_website;
 
+pub use avatar::*;
+pub use export::*;
+pub use login_history::*;
+pub use password_strength::*;
+pub use personal_access_token::*;
+pub use refresh_token::*;
+pub use role::*;
+pub use search::*;
 use secrecy::{ExposeSecret, Secret};
+pub use session::*;
+pub use suspension::*;
+pub use token_purpose::*;
 pub use types::*;
+pub use user_bio::UserBio;
 pub use user_email::UserEmail;
+pub use user_location::UserLocation;
 pub use user_name::UserName;
 pub use user_password::UserPassword;
+pub use user_website::UserWebsite;
+use uuid::Uuid;
+
+// Placeholder author of approved anonymous comments, seeded by a migration and unable to log in
+pub const ANONYMOUS_USER_ID: Uuid = Uuid::from_u128(0x00000000_0000_4000_8000_000000000001);
 
 pub struct NewUser {
     pub email: UserEmail,
@@ -42,6 +74,19 @@ impl TryFrom<ChangePasswordData> for (UserPassword, UserPassword) {
     }
 }
 
+#[derive(serde::Deserialize)]
+pub struct SudoData {
+    password: Secret<String>,
+}
+
+impl TryFrom<SudoData> for UserPassword {
+    type Error = String;
+
+    fn try_from(payload: SudoData) -> Result<Self, Self::Error> {
+        UserPassword::parse(payload.password.expose_secret().to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use claims::assert_ok;
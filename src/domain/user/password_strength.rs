@@ -0,0 +1,331 @@
+// Estimates how long an attacker would take to guess a password, in the spirit of zxcvbn: known
+// fragments such as common passwords, keyboard walks and the user's own details are each worth a
+// single guess from a small dictionary, repeats and sequences are nearly free, and only what is
+// left is priced by its character set.
+
+pub const MAX_PASSWORD_SCORE: u8 = 4;
+
+// Bits of guessing work needed to reach each score above 0
+const SCORE_THRESHOLDS_BITS: [f64; 4] = [20.0, 30.0, 40.0, 50.0];
+// A fragment found in a dictionary of roughly a thousand entries
+const FRAGMENT_BITS: f64 = 10.0;
+// Repeating the previous character, or stepping one past it as in `abc` or `321`
+const PATTERN_BITS: f64 = 1.0;
+// Shorter user details would match inside too many unrelated passwords
+const MIN_USER_INPUT_LENGTH: usize = 4;
+
+// Passwords and words at the top of every cracking list, spelled without l33t substitutions
+const COMMON_FRAGMENTS: [&str; 60] = [
+    "password",
+    "iloveyou",
+    "princess",
+    "sunshine",
+    "football",
+    "baseball",
+    "welcome",
+    "letmein",
+    "trustnoi",
+    "whatever",
+    "starwars",
+    "superman",
+    "computer",
+    "michelle",
+    "jennifer",
+    "corvette",
+    "mercedes",
+    "mustang",
+    "master",
+    "dragon",
+    "monkey",
+    "shadow",
+    "soccer",
+    "hockey",
+    "killer",
+    "secret",
+    "freedom",
+    "batman",
+    "charlie",
+    "jordan",
+    "hunter",
+    "ranger",
+    "buster",
+    "thomas",
+    "tigger",
+    "summer",
+    "winter",
+    "spring",
+    "autumn",
+    "ginger",
+    "cookie",
+    "flower",
+    "hello",
+    "admin",
+    "login",
+    "access",
+    "change",
+    "chelsea",
+    "arsenal",
+    "liverpool",
+    "pokemon",
+    "naruto",
+    "matrix",
+    "love",
+    "pass",
+    "test",
+    "guest",
+    "abcd",
+    "qwer",
+    "root",
+];
+
+// Runs of adjacent keys, matched before l33t substitution so digits stay digits
+const KEYBOARD_FRAGMENTS: [&str; 10] = [
+    "qwertyuiop",
+    "asdfghjkl",
+    "zxcvbnm",
+    "1qaz2wsx",
+    "qazwsx",
+    "qwerty",
+    "asdf",
+    "zxcv",
+    "poiuy",
+    "lkjh",
+];
+
+#[derive(Debug, PartialEq, Clone, Copy, PartialOrd, Ord, Eq)]
+enum Weakness {
+    // Declared from least to most telling, the most telling one found is reported
+    TooShort,
+    Patterns,
+    KeyboardWalk,
+    CommonPassword,
+    PersonalDetails,
+}
+
+impl Weakness {
+    fn feedback(&self) -> &'static str {
+        match self {
+            Weakness::TooShort => "Use a longer password, a few unrelated words work well.",
+            Weakness::Patterns => "Avoid repeated characters and sequences like abc or 123.",
+            Weakness::KeyboardWalk => "Avoid runs of neighbouring keys like qwerty.",
+            Weakness::CommonPassword => {
+                "Avoid common passwords and words, swapping letters for symbols does not help."
+            }
+            Weakness::PersonalDetails => "Avoid your user name and email address.",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct PasswordEstimate {
+    // From 0, guessed almost immediately, to `MAX_PASSWORD_SCORE`
+    pub score: u8,
+    // What would help most, for passwords that are too weak
+    pub feedback: &'static str,
+}
+
+/// `user_inputs` are details of the account, e.g. its user name, which an attacker targeting the
+/// user tries first.
+pub fn estimate_password_strength(password: &str, user_inputs: &[&str]) -> PasswordEstimate {
+    let original: Vec<char> = password.chars().collect();
+    let lower: Vec<char> = original
+        .iter()
+        .map(|c| c.to_lowercase().next().unwrap_or(*c))
+        .collect();
+    let unleet: Vec<char> = lower.iter().map(|c| unleet(*c)).collect();
+
+    let personal: Vec<Vec<char>> = user_inputs
+        .iter()
+        .flat_map(|input| input.split(['@', ' ', '.', '_', '-']))
+        .chain(user_inputs.iter().copied())
+        .filter(|input| input.chars().count() >= MIN_USER_INPUT_LENGTH)
+        .map(|input| input.to_lowercase().chars().collect())
+        .collect();
+
+    let mut fragments: Vec<(Vec<char>, Weakness)> = personal
+        .into_iter()
+        .map(|f| (f, Weakness::PersonalDetails))
+        .chain(
+            COMMON_FRAGMENTS
+                .iter()
+                .map(|f| (f.chars().collect(), Weakness::CommonPassword)),
+        )
+        .chain(
+            KEYBOARD_FRAGMENTS
+                .iter()
+                .map(|f| (f.chars().collect(), Weakness::KeyboardWalk)),
+        )
+        .collect();
+    // Longest first, so `password` is matched whole rather than as `pass`
+    fragments.sort_by_key(|(f, _)| std::cmp::Reverse(f.len()));
+
+    let mut covered = vec![false; original.len()];
+    let mut bits = 0.0;
+    let mut weakness = Weakness::TooShort;
+
+    for (fragment, kind) in &fragments {
+        for haystack in [&lower, &unleet] {
+            while let Some(start) = find_uncovered(haystack, fragment, &covered) {
+                covered[start..start + fragment.len()].fill(true);
+                bits += FRAGMENT_BITS;
+                weakness = weakness.max(*kind);
+            }
+        }
+    }
+
+    let charset_bits = (charset_size(&original) as f64).log2();
+    let mut previous: Option<char> = None;
+    for (c, is_covered) in lower.iter().zip(&covered) {
+        if *is_covered {
+            previous = None;
+            continue;
+        }
+        match previous {
+            Some(p) if p == *c || (*c as i64 - p as i64).abs() == 1 => {
+                bits += PATTERN_BITS;
+                weakness = weakness.max(Weakness::Patterns);
+            }
+            _ => bits += charset_bits,
+        }
+        previous = Some(*c);
+    }
+
+    let score = SCORE_THRESHOLDS_BITS
+        .iter()
+        .take_while(|threshold| bits >= **threshold)
+        .count() as u8;
+
+    PasswordEstimate {
+        score,
+        feedback: weakness.feedback(),
+    }
+}
+
+fn unleet(c: char) -> char {
+    match c {
+        '@' | '4' => 'a',
+        '0' => 'o',
+        '1' | '!' | '|' => 'i',
+        '3' => 'e',
+        '$' | '5' => 's',
+        '7' | '+' => 't',
+        '8' => 'b',
+        '9' => 'g',
+        _ => c,
+    }
+}
+
+fn find_uncovered(haystack: &[char], needle: &[char], covered: &[bool]) -> Option<usize> {
+    if needle.is_empty() || needle.len() > haystack.len() {
+        return None;
+    }
+    (0..=haystack.len() - needle.len()).find(|&start| {
+        haystack[start..start + needle.len()] == *needle
+            && !covered[start..start + needle.len()].contains(&true)
+    })
+}
+
+// Characters an attacker brute-forcing this password would have to try at each position
+fn charset_size(password: &[char]) -> u32 {
+    let mut size = 0;
+    if password.iter().any(|c| c.is_ascii_lowercase()) {
+        size += 26;
+    }
+    if password.iter().any(|c| c.is_ascii_uppercase()) {
+        size += 26;
+    }
+    if password.iter().any(|c| c.is_ascii_digit()) {
+        size += 10;
+    }
+    if password
+        .iter()
+        .any(|c| c.is_ascii_punctuation() || *c == ' ')
+    {
+        size += 33;
+    }
+    if password.iter().any(|c| !c.is_ascii()) {
+        size += 100;
+    }
+    size.max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MAX_PASSWORD_SCORE, estimate_password_strength};
+
+    fn score(password: &str) -> u8 {
+        estimate_password_strength(password, &[]).score
+    }
+
+    #[test]
+    fn common_passwords_score_low_even_with_substitutions() {
+        for password in [
+            "password",
+            "Password1",
+            "P@ssw0rd!",
+            "iloveyou",
+            "letmein123",
+        ] {
+            assert!(
+                score(password) <= 1,
+                "{password} scored {}",
+                score(password)
+            );
+        }
+    }
+
+    #[test]
+    fn repeats_sequences_and_keyboard_walks_score_low() {
+        for password in [
+            "aaaaaaaaaa",
+            "12345678",
+            "abcdefghij",
+            "qwertyuiop",
+            "1qaz2wsx",
+        ] {
+            assert!(
+                score(password) <= 1,
+                "{password} scored {}",
+                score(password)
+            );
+        }
+    }
+
+    #[test]
+    fn long_or_random_passwords_score_high() {
+        for password in [
+            "correct horse battery staple",
+            "Xk9#mQ2$vL7p",
+            "d8f3a1c2-77b4-4e9a-a3c1-0f2e6b5d9c84",
+        ] {
+            assert_eq!(score(password), MAX_PASSWORD_SCORE, "{password}");
+        }
+    }
+
+    #[test]
+    fn short_random_lowercase_is_only_fair() {
+        assert_eq!(score("kxvmwpzq"), 2);
+    }
+
+    #[test]
+    fn user_details_count_as_a_single_guess() {
+        let inputs = ["jonathan.smith", "jonathan.smith@example.com"];
+        let estimate = estimate_password_strength("jonathansmith2", &inputs);
+
+        assert!(estimate.score <= 1);
+        assert_eq!(estimate.feedback, "Avoid your user name and email address.");
+        assert_eq!(score("jonathansmith2"), MAX_PASSWORD_SCORE);
+    }
+
+    #[test]
+    fn feedback_names_the_most_telling_weakness() {
+        assert_eq!(
+            estimate_password_strength("P@ssw0rd", &[]).feedback,
+            "Avoid common passwords and words, swapping letters for symbols does not help."
+        );
+        assert_eq!(
+            estimate_password_strength("zzzzzzzz", &[]).feedback,
+            "Avoid repeated characters and sequences like abc or 123."
+        );
+    }
+}
@@ -0,0 +1,150 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use unicode_segmentation::UnicodeSegmentation;
+use uuid::Uuid;
+
+use crate::domain::Role;
+
+// Marks the bearer tokens that are personal access tokens rather than JWTs, and makes leaked
+// ones easy to spot in logs and secret scanners
+pub const PERSONAL_ACCESS_TOKEN_PREFIX: &str = "thpat_";
+// Names are labels for the user, e.g. the machine or integration a token was made for
+const MAX_TOKEN_NAME_GRAPHEMES: usize = 64;
+
+// What a personal access token may do. `Read` tokens are limited to GET and HEAD requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenScope {
+    Read,
+    Write,
+}
+
+impl TokenScope {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s.trim().to_lowercase().as_str() {
+            "read" => Ok(TokenScope::Read),
+            "write" => Ok(TokenScope::Write),
+            _ => Err("Invalid scope: must be read or write.".to_string()),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TokenScope::Read => "read",
+            TokenScope::Write => "write",
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CreatePersonalAccessTokenPayload {
+    pub name: String,
+    pub scopes: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct NewPersonalAccessToken {
+    pub name: String,
+    // Deduplicated, in the order given
+    pub scopes: Vec<TokenScope>,
+}
+
+impl TryFrom<CreatePersonalAccessTokenPayload> for NewPersonalAccessToken {
+    type Error = String;
+
+    fn try_from(payload: CreatePersonalAccessTokenPayload) -> Result<Self, Self::Error> {
+        let name = payload.name.trim();
+        if name.is_empty() {
+            return Err("Invalid token name: must not be empty.".to_string());
+        }
+        if name.graphemes(true).count() > MAX_TOKEN_NAME_GRAPHEMES {
+            return Err(format!(
+                "Invalid token name: must be at most {MAX_TOKEN_NAME_GRAPHEMES} characters."
+            ));
+        }
+
+        let mut scopes = Vec::new();
+        for scope in &payload.scopes {
+            let scope = TokenScope::parse(scope)?;
+            if !scopes.contains(&scope) {
+                scopes.push(scope);
+            }
+        }
+        if scopes.is_empty() {
+            return Err("Invalid scopes: at least one is required.".to_string());
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            scopes,
+        })
+    }
+}
+
+// A token as listed to its owner, the token itself is never shown again after creation
+#[derive(Serialize, Debug)]
+pub struct PersonalAccessTokenSummary {
+    pub id: Uuid,
+    pub name: String,
+    pub scopes: Vec<TokenScope>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+// Returned once, when the token is created
+#[derive(Serialize, Debug)]
+pub struct CreatedPersonalAccessToken {
+    pub token: String,
+    #[serde(flatten)]
+    pub summary: PersonalAccessTokenSummary,
+}
+
+// The account a presented token acts for
+#[derive(Debug)]
+pub struct PersonalAccessTokenOwner {
+    pub user_id: Uuid,
+    pub role: Role,
+    pub scopes: Vec<TokenScope>,
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::{assert_err, assert_ok};
+
+    use super::{CreatePersonalAccessTokenPayload, NewPersonalAccessToken, TokenScope};
+
+    fn payload(name: &str, scopes: &[&str]) -> CreatePersonalAccessTokenPayload {
+        CreatePersonalAccessTokenPayload {
+            name: name.to_string(),
+            scopes: scopes.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn scopes_are_parsed_and_deduplicated() {
+        let token =
+            NewPersonalAccessToken::try_from(payload(" ci ", &["Write", "read", "write"])).unwrap();
+
+        assert_eq!(token.name, "ci");
+        assert_eq!(token.scopes, [TokenScope::Write, TokenScope::Read]);
+    }
+
+    #[test]
+    fn unknown_or_missing_scopes_are_rejected() {
+        assert_err!(NewPersonalAccessToken::try_from(payload("ci", &["admin"])));
+        assert_err!(NewPersonalAccessToken::try_from(payload("ci", &[])));
+    }
+
+    #[test]
+    fn names_must_be_present_and_short() {
+        assert_err!(NewPersonalAccessToken::try_from(payload("  ", &["read"])));
+        assert_err!(NewPersonalAccessToken::try_from(payload(
+            &"a".repeat(65),
+            &["read"]
+        )));
+        assert_ok!(NewPersonalAccessToken::try_from(payload(
+            &"a".repeat(64),
+            &["read"]
+        )));
+    }
+}
@@ -0,0 +1,33 @@
+use chrono::{DateTime, Utc};
+use secrecy::Secret;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::domain::Role;
+
+// Sets refresh tokens apart from the bearer tokens the API accepts on requests
+pub const REFRESH_TOKEN_PREFIX: &str = "thrt_";
+
+#[derive(Deserialize)]
+pub struct RefreshTokenPayload {
+    pub refresh_token: Secret<String>,
+}
+
+// Where a sign-in came from, kept with its refresh tokens so the user can tell them apart
+#[derive(Debug, Clone)]
+pub struct DeviceMetadata {
+    pub user_agent: Option<String>,
+    pub ip_address: String,
+}
+
+// A refresh token as found when presented, its account still in good standing
+#[derive(Debug)]
+pub struct StoredRefreshToken {
+    pub id: Uuid,
+    pub family_id: Uuid,
+    pub user_id: Uuid,
+    pub role: Role,
+    pub expires_at: DateTime<Utc>,
+    pub rotated_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,112 @@
+use serde::{Deserialize, Serialize};
+
+// What a user may do beyond managing their own account and content
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "user_role", rename_all = "lowercase")]
+pub enum Role {
+    User,
+    Moderator,
+    Admin,
+}
+
+// Actions on content or accounts that are not the user's own, or on the service itself
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Permission {
+    // Reviewing, removing and deleting other users' comments
+    ModerateComments,
+    // Editing and deleting other users' posts, reviewing proposals on them
+    ManagePosts,
+    // Banning users and assigning roles
+    ManageUsers,
+    PublishNewsletters,
+    // Database, worker and logging diagnostics, bulk imports
+    OperateService,
+    // Reading the security audit log
+    ReviewAuditLog,
+    // Acting as another user to reproduce what they see
+    ImpersonateUsers,
+}
+
+impl Role {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s.trim().to_lowercase().as_str() {
+            "user" => Ok(Role::User),
+            "moderator" => Ok(Role::Moderator),
+            "admin" => Ok(Role::Admin),
+            _ => Err("Invalid role: must be user, moderator or admin.".to_string()),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::User => "user",
+            Role::Moderator => "moderator",
+            Role::Admin => "admin",
+        }
+    }
+
+    pub fn permissions(&self) -> &'static [Permission] {
+        match self {
+            Role::User => &[],
+            Role::Moderator => &[Permission::ModerateComments],
+            Role::Admin => &[
+                Permission::ModerateComments,
+                Permission::ManagePosts,
+                Permission::ManageUsers,
+                Permission::PublishNewsletters,
+                Permission::OperateService,
+                Permission::ReviewAuditLog,
+                Permission::ImpersonateUsers,
+            ],
+        }
+    }
+
+    pub fn has(&self, permission: Permission) -> bool {
+        self.permissions().contains(&permission)
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SetRolePayload {
+    pub role: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::{assert_err, assert_ok_eq};
+
+    use super::{Permission, Role};
+
+    #[test]
+    fn roles_round_trip_through_their_names() {
+        for role in [Role::User, Role::Moderator, Role::Admin] {
+            assert_ok_eq!(Role::parse(role.as_str()), role);
+        }
+        assert_ok_eq!(Role::parse(" Moderator "), Role::Moderator);
+        assert_err!(Role::parse("owner"));
+    }
+
+    #[test]
+    fn moderators_may_only_moderate_comments() {
+        assert!(Role::Moderator.has(Permission::ModerateComments));
+        assert!(!Role::Moderator.has(Permission::ManageUsers));
+        assert!(!Role::Moderator.has(Permission::ManagePosts));
+        assert!(Role::User.permissions().is_empty());
+    }
+
+    #[test]
+    fn admins_have_every_permission() {
+        for permission in [
+            Permission::ModerateComments,
+            Permission::ManagePosts,
+            Permission::ManageUsers,
+            Permission::PublishNewsletters,
+            Permission::OperateService,
+            Permission::ReviewAuditLog,
+            Permission::ImpersonateUsers,
+        ] {
+            assert!(Role::Admin.has(permission), "{permission:?}");
+        }
+    }
+}
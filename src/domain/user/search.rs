@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+use unicode_segmentation::UnicodeSegmentation;
+use uuid::Uuid;
+
+use crate::domain::Pagination;
+
+// Longest term worth comparing, user names themselves are capped at 256 characters
+const MAX_SEARCH_TERM_LENGTH: usize = 64;
+
+#[derive(Debug)]
+pub struct UserSearchTerm(String);
+
+impl UserSearchTerm {
+    /// A leading `@` is dropped so mention autocompletion can pass what the user typed.
+    pub fn parse(s: String) -> Result<Self, String> {
+        let trimmed = s.trim();
+        let trimmed = trimmed.strip_prefix('@').unwrap_or(trimmed).trim_start();
+
+        if trimmed.is_empty() {
+            return Err("Invalid search term: cannot be empty or whitespace.".to_string());
+        }
+
+        if trimmed.graphemes(true).count() > MAX_SEARCH_TERM_LENGTH {
+            return Err(format!(
+                "Invalid search term: cannot be longer than {MAX_SEARCH_TERM_LENGTH} characters."
+            ));
+        }
+
+        Ok(Self(trimmed.to_string()))
+    }
+
+    // LIKE pattern matching names that start with the term, wildcards in the term match literally
+    pub fn prefix_pattern(&self) -> String {
+        let escaped = self
+            .0
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+        format!("{escaped}%")
+    }
+}
+
+impl AsRef<str> for UserSearchTerm {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SearchUsersQuery {
+    pub q: String,
+    #[serde(default = "default_page")]
+    pub page: i32,
+    #[serde(default = "default_limit")]
+    pub limit: i32,
+}
+
+fn default_page() -> i32 {
+    1
+}
+
+fn default_limit() -> i32 {
+    10
+}
+
+pub struct UserSearch {
+    pub term: UserSearchTerm,
+    pub pagination: Pagination,
+}
+
+impl TryFrom<SearchUsersQuery> for UserSearch {
+    type Error = String;
+
+    fn try_from(query: SearchUsersQuery) -> Result<Self, Self::Error> {
+        Ok(Self {
+            term: UserSearchTerm::parse(query.q)?,
+            pagination: Pagination::parse(query.page, query.limit)?,
+        })
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct UserSearchResult {
+    pub id: Uuid,
+    pub user_name: String,
+    pub avatar_url: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::{assert_err, assert_ok};
+
+    use super::UserSearchTerm;
+
+    #[test]
+    fn term_is_trimmed_and_loses_a_leading_at_sign() {
+        let term = UserSearchTerm::parse("  @ali ".to_string()).unwrap();
+        assert_eq!(term.as_ref(), "ali");
+    }
+
+    #[test]
+    fn empty_or_overlong_terms_are_rejected() {
+        for term in ["", "   ", "@", &"a".repeat(65)] {
+            assert_err!(UserSearchTerm::parse(term.to_string()));
+        }
+        assert_ok!(UserSearchTerm::parse("a".repeat(64)));
+    }
+
+    #[test]
+    fn prefix_pattern_escapes_like_wildcards() {
+        let term = UserSearchTerm::parse(r"50%_off\".to_string()).unwrap();
+        assert_eq!(term.prefix_pattern(), r"50\%\_off\\%");
+    }
+}
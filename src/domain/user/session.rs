@@ -0,0 +1,27 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::domain::Role;
+
+// A signed-in browser as listed to its user
+#[derive(Serialize, Debug)]
+pub struct SessionSummary {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    pub ip_address: String,
+    pub user_agent: Option<String>,
+    // Logged in with "remember me", so it outlives the browser session
+    pub remember_me: bool,
+    // Whether this is the session making the request
+    pub current: bool,
+}
+
+// A session resumed from its remember-me cookie
+#[derive(Debug)]
+pub struct RememberedSession {
+    pub session_id: Uuid,
+    pub user_id: Uuid,
+    pub role: Role,
+}
@@ -0,0 +1,111 @@
+use std::fmt::{self, Display, Formatter};
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use unicode_segmentation::UnicodeSegmentation;
+
+const MAX_REASON_GRAPHEMES: usize = 500;
+
+#[derive(Deserialize, Debug)]
+pub struct BanUserPayload {
+    #[serde(default)]
+    pub remove_content: bool,
+    // Shown to the user whenever they are turned away
+    pub reason: Option<String>,
+    // The ban is lifted automatically from then on, without it the ban is permanent
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+// A ban in force, as explained to the banned user
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suspension {
+    pub reason: Option<String>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl Suspension {
+    pub fn parse(
+        reason: Option<String>,
+        until: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> Result<Self, String> {
+        let reason = reason
+            .map(|r| r.trim().to_string())
+            .filter(|r| !r.is_empty());
+        if reason
+            .as_ref()
+            .is_some_and(|r| r.graphemes(true).count() > MAX_REASON_GRAPHEMES)
+        {
+            return Err(format!(
+                "Invalid reason: must be at most {MAX_REASON_GRAPHEMES} characters."
+            ));
+        }
+        if until.is_some_and(|until| until <= now) {
+            return Err("Invalid expiry: must be in the future.".to_string());
+        }
+
+        Ok(Self { reason, until })
+    }
+}
+
+impl Display for Suspension {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.until {
+            Some(until) => write!(
+                f,
+                "Your account is suspended until {}",
+                until.format("%Y-%m-%d %H:%M UTC")
+            )?,
+            None => write!(f, "Your account is suspended")?,
+        }
+        match &self.reason {
+            Some(reason) => write!(f, ": {reason}"),
+            None => write!(f, "."),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+    use claims::{assert_err, assert_ok};
+
+    use super::Suspension;
+
+    #[test]
+    fn blank_reasons_are_dropped() {
+        let suspension = Suspension::parse(Some("  ".to_string()), None, Utc::now()).unwrap();
+        assert_eq!(suspension.reason, None);
+    }
+
+    #[test]
+    fn long_reasons_are_rejected() {
+        assert_err!(Suspension::parse(Some("a".repeat(501)), None, Utc::now()));
+        assert_ok!(Suspension::parse(Some("a".repeat(500)), None, Utc::now()));
+    }
+
+    #[test]
+    fn expiry_must_be_in_the_future() {
+        let now = Utc::now();
+        assert_err!(Suspension::parse(None, Some(now), now));
+        assert_ok!(Suspension::parse(None, Some(now + Duration::hours(1)), now));
+    }
+
+    #[test]
+    fn message_includes_expiry_and_reason() {
+        let suspension = Suspension {
+            reason: Some("Spamming links".to_string()),
+            until: Some(Utc.with_ymd_and_hms(2030, 1, 2, 3, 4, 5).unwrap()),
+        };
+        assert_eq!(
+            suspension.to_string(),
+            "Your account is suspended until 2030-01-02 03:04 UTC: Spamming links"
+        );
+
+        let permanent = Suspension {
+            reason: None,
+            until: None,
+        };
+        assert_eq!(permanent.to_string(), "Your account is suspended.");
+    }
+}
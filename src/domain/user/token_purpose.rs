@@ -0,0 +1,12 @@
+// What an emailed link was sent for. A token is only accepted for its own purpose.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "token_purpose", rename_all = "snake_case")]
+pub enum TokenPurpose {
+    Activation,
+    Subscription,
+    EmailChange,
+    PasswordReset,
+    ReminderOptOut,
+    MagicLink,
+    Unsubscribe,
+}
@@ -3,13 +3,16 @@ use serde::Deserialize;
 
 use crate::{
     authentication::Credentials,
-    domain::{NewUser, UserName, UserPassword},
+    domain::{NewUser, TokenPurpose, UserBio, UserLocation, UserName, UserPassword, UserWebsite},
 };
 
 #[derive(serde::Deserialize)]
 pub struct LoginData {
     user_name: String,
     password: Secret<String>,
+    // Keep the session after the browser is closed, see `SessionSettings::remember_me_days`
+    #[serde(default)]
+    pub remember_me: bool,
 }
 
 impl TryFrom<LoginData> for Credentials {
@@ -26,11 +29,33 @@ impl TryFrom<LoginData> for Credentials {
     }
 }
 
+#[derive(serde::Serialize, Debug)]
+pub struct AccessTokenResponse {
+    pub access_token: String,
+    pub token_type: &'static str,
+    // Seconds until the token stops being accepted
+    pub expires_in: i64,
+    // Exchanged at `POST /v1/user/token/refresh` for a new pair, once
+    pub refresh_token: String,
+}
+
+// A client address or user name's login attempts in the current throttling window
+#[derive(Debug)]
+pub struct LoginAttempts {
+    // Including the attempt being made
+    pub count: i32,
+    // Until the window ends and the count starts again from zero
+    pub retry_after_secs: i32,
+}
+
 #[derive(Deserialize)]
 pub struct UserData {
     email: String,
     user_name: String,
     password: Secret<String>,
+    // Only checked when registration requires a CAPTCHA
+    #[serde(default)]
+    pub captcha_token: Option<Secret<String>>,
 }
 
 // This is like saying - I know how to build myself `NewUser` from something else `UserData`
@@ -46,3 +71,209 @@ impl TryFrom<UserData> for NewUser {
         )
     }
 }
+
+#[derive(serde::Serialize, Debug, Default)]
+pub struct BanSummary {
+    pub posts_removed: u64,
+    pub comments_removed: u64,
+    pub likes_withdrawn: u64,
+    pub queued_deliveries_purged: u64,
+}
+
+// What happens to a deleted account's posts and comments
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum AccountDeletionPolicy {
+    // Soft deleted, like the content of a banned user
+    RemoveContent,
+    // Kept and attributed to the placeholder author of anonymous comments
+    ReassignContent,
+}
+
+#[derive(serde::Serialize, Debug, Default)]
+pub struct AccountDeletionSummary {
+    pub posts_removed: u64,
+    pub comments_removed: u64,
+    pub posts_reassigned: u64,
+    pub comments_reassigned: u64,
+    pub likes_withdrawn: u64,
+    pub queued_deliveries_purged: u64,
+}
+
+// An unactivated account owed its next activation reminder
+#[derive(Debug)]
+pub struct DueActivationReminder {
+    pub user_id: uuid::Uuid,
+    pub email: String,
+    pub reminder_number: i16,
+}
+
+// A password reset or sign-in link asked for, to be emailed by the worker
+#[derive(Debug)]
+pub struct AccountEmailRequest {
+    pub user_id: uuid::Uuid,
+    pub email: String,
+    pub purpose: TokenPurpose,
+}
+
+// An account renamed when user names became unique, still to be told its new name
+#[derive(Debug)]
+pub struct UserNameChangeNotice {
+    pub user_id: uuid::Uuid,
+    pub email: String,
+    pub previous_user_name: String,
+    pub user_name: String,
+}
+
+// A reminder converts when the user activates before any later reminder is sent
+#[derive(serde::Serialize, Debug)]
+pub struct ActivationReminderStats {
+    pub reminder_number: i16,
+    pub sent: i64,
+    pub converted: i64,
+}
+
+impl ActivationReminderStats {
+    // `None` until the reminder has been sent at all
+    pub fn conversion_rate(&self) -> Option<f64> {
+        (self.sent > 0).then(|| self.converted as f64 / self.sent as f64)
+    }
+}
+
+// What anyone can see about a user on their author page
+#[derive(serde::Serialize, Debug)]
+pub struct UserProfile {
+    pub id: uuid::Uuid,
+    pub user_name: String,
+    pub avatar_url: Option<String>,
+    pub bio: Option<String>,
+    pub website: Option<String>,
+    pub location: Option<String>,
+    pub joined_at: chrono::DateTime<chrono::Utc>,
+    pub post_count: i64,
+    pub comment_count: i64,
+}
+
+#[derive(serde::Serialize, Debug)]
+pub struct BlockedUserResponseBody {
+    pub id: uuid::Uuid,
+    pub user_name: String,
+    pub blocked_at: chrono::DateTime<chrono::Utc>,
+}
+
+// Omitted fields are left as they are, an empty string clears the field
+#[derive(Deserialize, Debug)]
+pub struct UpdateProfilePayload {
+    pub bio: Option<String>,
+    pub website: Option<String>,
+    pub location: Option<String>,
+}
+
+// `None` leaves a field unchanged, `Some(None)` clears it
+#[derive(Debug)]
+pub struct ProfileUpdate {
+    pub bio: Option<Option<UserBio>>,
+    pub website: Option<Option<UserWebsite>>,
+    pub location: Option<Option<UserLocation>>,
+}
+
+fn parse_profile_field<T>(
+    value: Option<String>,
+    parse: fn(String) -> Result<T, String>,
+) -> Result<Option<Option<T>>, String> {
+    value
+        .map(|v| match v.trim() {
+            "" => Ok(None),
+            _ => parse(v).map(Some),
+        })
+        .transpose()
+}
+
+impl TryFrom<UpdateProfilePayload> for ProfileUpdate {
+    type Error = String;
+
+    fn try_from(payload: UpdateProfilePayload) -> Result<Self, Self::Error> {
+        let update = Self {
+            bio: parse_profile_field(payload.bio, UserBio::parse)?,
+            website: parse_profile_field(payload.website, UserWebsite::parse)?,
+            location: parse_profile_field(payload.location, UserLocation::parse)?,
+        };
+
+        if update.bio.is_none() && update.website.is_none() && update.location.is_none() {
+            return Err("Invalid profile: at least one field must be provided.".to_string());
+        }
+
+        Ok(update)
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ChangeUserNamePayload {
+    pub user_name: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ChangeEmailPayload {
+    pub email: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ForgotPasswordPayload {
+    pub email: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct MagicLinkPayload {
+    pub email: String,
+}
+
+#[derive(Deserialize)]
+pub struct MagicLinkLoginPayload {
+    pub token: String,
+}
+
+#[derive(Deserialize)]
+pub struct ResetPasswordPayload {
+    pub token: String,
+    pub new_password: Secret<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::{assert_err, assert_ok};
+
+    use super::{ProfileUpdate, UpdateProfilePayload};
+
+    fn payload(bio: Option<&str>, website: Option<&str>) -> UpdateProfilePayload {
+        UpdateProfilePayload {
+            bio: bio.map(str::to_string),
+            website: website.map(str::to_string),
+            location: None,
+        }
+    }
+
+    #[test]
+    fn omitted_fields_are_left_unchanged_and_empty_ones_cleared() {
+        let update = ProfileUpdate::try_from(payload(Some("  "), None)).unwrap();
+        assert!(matches!(update.bio, Some(None)));
+        assert!(update.website.is_none());
+        assert!(update.location.is_none());
+    }
+
+    #[test]
+    fn provided_fields_are_validated() {
+        assert_ok!(ProfileUpdate::try_from(payload(
+            Some("Rustacean"),
+            Some("https://athfan.dev")
+        )));
+        assert_err!(ProfileUpdate::try_from(payload(
+            None,
+            Some("javascript:alert(1)")
+        )));
+    }
+
+    #[test]
+    fn an_update_without_any_field_is_rejected() {
+        assert_err!(ProfileUpdate::try_from(payload(None, None)));
+    }
+}
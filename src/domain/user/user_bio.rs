@@ -0,0 +1,79 @@
+use std::fmt::{self, Display, Formatter};
+
+use unicode_segmentation::UnicodeSegmentation;
+
+#[derive(Debug)]
+pub struct UserBio(String);
+
+impl UserBio {
+    /// Returns an instance of `UserBio` if all conditions are met.
+    pub fn parse(s: String) -> Result<Self, String> {
+        let trimmed = s.trim();
+
+        if trimmed.is_empty() {
+            return Err("Invalid bio: cannot be empty or whitespace.".to_string());
+        }
+
+        if trimmed.graphemes(true).count() > 500 {
+            return Err("Invalid bio: cannot be longer than 500 characters.".to_string());
+        }
+
+        // Line breaks are kept so bios can have paragraphs
+        if trimmed.chars().any(|c| c.is_control() && c != '\n') {
+            return Err("Invalid bio: contains control characters.".to_string());
+        }
+
+        Ok(Self(trimmed.to_string()))
+    }
+}
+
+impl AsRef<str> for UserBio {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for UserBio {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::{assert_err, assert_ok};
+    use proptest::prelude::*;
+
+    use super::UserBio;
+
+    #[test]
+    fn a_500_grapheme_long_bio_is_valid() {
+        assert_ok!(UserBio::parse("ё".repeat(500)));
+    }
+
+    #[test]
+    fn a_bio_longer_than_500_graphemes_is_rejected() {
+        assert_err!(UserBio::parse("a".repeat(501)));
+    }
+
+    #[test]
+    fn a_bio_with_paragraphs_is_accepted() {
+        assert_ok!(UserBio::parse(
+            "Rustacean.\n\nWrites about databases.".into()
+        ));
+    }
+
+    #[test]
+    fn control_characters_other_than_newlines_are_rejected() {
+        for c in ['\0', '\r', '\t', '\u{1b}'] {
+            assert_err!(UserBio::parse(format!("Hello{c}there")));
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn whitespace_only_bios_are_rejected(bio in r"\s{0,50}") {
+            prop_assert!(UserBio::parse(bio).is_err());
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use std::fmt::{self, Display, Formatter};
+
+use unicode_segmentation::UnicodeSegmentation;
+
+#[derive(Debug)]
+pub struct UserLocation(String);
+
+impl UserLocation {
+    /// Returns an instance of `UserLocation` if all conditions are met.
+    pub fn parse(s: String) -> Result<Self, String> {
+        let trimmed = s.trim();
+
+        if trimmed.is_empty() {
+            return Err("Invalid location: cannot be empty or whitespace.".to_string());
+        }
+
+        if trimmed.graphemes(true).count() > 100 {
+            return Err("Invalid location: cannot be longer than 100 characters.".to_string());
+        }
+
+        if trimmed.chars().any(char::is_control) {
+            return Err("Invalid location: contains control characters.".to_string());
+        }
+
+        Ok(Self(trimmed.to_string()))
+    }
+}
+
+impl AsRef<str> for UserLocation {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for UserLocation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::{assert_err, assert_ok};
+
+    use super::UserLocation;
+
+    #[test]
+    fn a_city_and_country_is_accepted() {
+        assert_ok!(UserLocation::parse("Colombo, Sri Lanka".into()));
+    }
+
+    #[test]
+    fn a_location_longer_than_100_graphemes_is_rejected() {
+        assert_err!(UserLocation::parse("a".repeat(101)));
+    }
+
+    #[test]
+    fn a_multi_line_location_is_rejected() {
+        assert_err!(UserLocation::parse("Colombo\nSri Lanka".into()));
+    }
+}
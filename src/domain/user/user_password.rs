@@ -1,6 +1,8 @@
-use secrecy::Secret;
+use secrecy::{ExposeSecret, Secret};
 use unicode_segmentation::UnicodeSegmentation;
 
+use super::estimate_password_strength;
+
 #[derive(Debug)]
 pub struct UserPassword(Secret<String>);
 
@@ -27,6 +29,19 @@ impl UserPassword {
         Ok(Self(Secret::new(trimmed.to_string())))
     }
 
+    /// Only applied to passwords being set, an existing password is checked against its hash
+    /// whatever its strength. `user_inputs` are the account's own details, e.g. its user name.
+    pub fn ensure_strong(&self, min_score: u8, user_inputs: &[&str]) -> Result<(), String> {
+        let estimate = estimate_password_strength(self.0.expose_secret(), user_inputs);
+        if estimate.score < min_score {
+            return Err(format!(
+                "Invalid user password: too easy to guess. {}",
+                estimate.feedback
+            ));
+        }
+        Ok(())
+    }
+
     pub fn into_secret(self) -> Secret<String> {
         self.0
     }
@@ -0,0 +1,76 @@
+use std::fmt::{self, Display, Formatter};
+
+use url::Url;
+
+#[derive(Debug)]
+pub struct UserWebsite(String);
+
+impl UserWebsite {
+    /// Returns an instance of `UserWebsite` if all conditions are met.
+    pub fn parse(s: String) -> Result<Self, String> {
+        let trimmed = s.trim();
+
+        if trimmed.len() > 2048 {
+            return Err("Invalid website: cannot be longer than 2048 characters.".to_string());
+        }
+
+        let url =
+            Url::parse(trimmed).map_err(|_| "Invalid website: must be a valid URL.".to_string())?;
+
+        // Rendered as a link on the profile, so schemes like `javascript:` must never get through
+        if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() {
+            return Err("Invalid website: must be an HTTP or HTTPS URL.".to_string());
+        }
+
+        Ok(Self(trimmed.to_string()))
+    }
+}
+
+impl AsRef<str> for UserWebsite {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for UserWebsite {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::{assert_err, assert_ok};
+
+    use super::UserWebsite;
+
+    #[test]
+    fn http_and_https_urls_are_accepted() {
+        assert_ok!(UserWebsite::parse("https://athfan.dev".into()));
+        assert_ok!(UserWebsite::parse("http://example.com/about".into()));
+    }
+
+    #[test]
+    fn other_schemes_are_rejected() {
+        for website in [
+            "javascript:alert(1)",
+            "data:text/html,hi",
+            "ftp://example.com",
+            "mailto:me@example.com",
+        ] {
+            assert_err!(UserWebsite::parse(website.into()));
+        }
+    }
+
+    #[test]
+    fn text_that_is_not_a_url_is_rejected() {
+        assert_err!(UserWebsite::parse("my website".into()));
+        assert_err!(UserWebsite::parse("".into()));
+    }
+
+    #[test]
+    fn overly_long_urls_are_rejected() {
+        let website = format!("https://example.com/{}", "a".repeat(2048));
+        assert_err!(UserWebsite::parse(website));
+    }
+}
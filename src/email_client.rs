@@ -1,9 +1,14 @@
 use std::time::Duration;
 
+use base64::{Engine, engine::general_purpose::STANDARD};
 use reqwest::{Client, Url};
 use secrecy::{ExposeSecret, Secret};
+use sqlx::PgPool;
 
-use crate::domain::UserEmail;
+use crate::{
+    domain::{EmailAttachment, UserEmail},
+    repository,
+};
 
 #[derive(Debug)]
 pub struct EmailClient {
@@ -11,8 +16,13 @@ pub struct EmailClient {
     base_url: Url,
     sender: UserEmail,
     authorization_token: Secret<String>,
+    // Recipients on it are skipped, None when sending to every address
+    suppression_list: Option<PgPool>,
 }
 
+// Most messages Postmark accepts in one batch request
+pub const MAX_BATCH_SIZE: usize = 500;
+
 #[derive(serde::Serialize)]
 #[serde(rename_all = "PascalCase")]
 struct SendEmailRequest<'a> {
@@ -21,6 +31,50 @@ struct SendEmailRequest<'a> {
     subject: &'a str,
     html_body: &'a str,
     text_body: &'a str,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    attachments: Vec<AttachmentRequest<'a>>,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct AttachmentRequest<'a> {
+    name: &'a str,
+    // base64 encoded
+    content: String,
+    content_type: &'a str,
+}
+
+impl<'a> From<&'a EmailAttachment> for AttachmentRequest<'a> {
+    fn from(attachment: &'a EmailAttachment) -> Self {
+        Self {
+            name: attachment.name(),
+            content: STANDARD.encode(attachment.content()),
+            content_type: attachment.content_type(),
+        }
+    }
+}
+
+// Postmark's verdict on one message of a batch, in the order they were sent
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct BatchEmailResponse {
+    error_code: i64,
+    message: String,
+}
+
+pub struct BatchEmail<'a> {
+    pub recipient: &'a UserEmail,
+    pub subject: &'a str,
+    pub html_content: &'a str,
+    pub text_content: &'a str,
+}
+
+// What became of an email that did not fail
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SendOutcome {
+    Sent,
+    // The recipient is on the suppression list, nothing was sent
+    Suppressed,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -30,6 +84,9 @@ pub enum EmailError {
 
     #[error(transparent)]
     Url(#[from] url::ParseError),
+
+    #[error(transparent)]
+    Suppression(#[from] anyhow::Error),
 }
 
 impl EmailClient {
@@ -50,15 +107,47 @@ impl EmailClient {
             base_url,
             sender,
             authorization_token,
+            suppression_list: None,
+        }
+    }
+
+    // Emails to suppressed addresses are dropped and reported as `SendOutcome::Suppressed`, which
+    // flows such as a password reset treat like a sent email so they behave the same whether or
+    // not the recipient is suppressed
+    pub fn with_suppression_list(self, pool: PgPool) -> Self {
+        Self {
+            suppression_list: Some(pool),
+            ..self
         }
     }
+
     pub async fn send_email(
         &self,
         recipient: &UserEmail,
         subject: &str,
         html_content: &str,
         text_content: &str,
-    ) -> Result<(), EmailError> {
+    ) -> Result<SendOutcome, EmailError> {
+        self.send_email_with_attachments(recipient, subject, html_content, text_content, &[])
+            .await
+    }
+
+    // Attachments are expected to have been validated, see `EmailAttachment::parse`
+    pub async fn send_email_with_attachments(
+        &self,
+        recipient: &UserEmail,
+        subject: &str,
+        html_content: &str,
+        text_content: &str,
+        attachments: &[EmailAttachment],
+    ) -> Result<SendOutcome, EmailError> {
+        if let Some(pool) = &self.suppression_list
+            && repository::is_email_suppressed(recipient.as_ref(), pool).await?
+        {
+            tracing::info!("Recipient is suppressed, email not sent");
+            return Ok(SendOutcome::Suppressed);
+        }
+
         let url = self.base_url.join("/email")?;
 
         let request_body = SendEmailRequest {
@@ -67,6 +156,7 @@ impl EmailClient {
             subject,
             html_body: html_content,
             text_body: text_content,
+            attachments: attachments.iter().map(AttachmentRequest::from).collect(),
         };
 
         self.http_client
@@ -80,7 +170,78 @@ impl EmailClient {
             .await?
             .error_for_status()?;
 
-        Ok(())
+        Ok(SendOutcome::Sent)
+    }
+
+    // Sends up to `MAX_BATCH_SIZE` emails in a single request. Postmark accepts or rejects each
+    // of them on its own, so the outcome of every email is returned in the order they were given,
+    // with Postmark's error message for the rejected ones. Suppressed recipients are skipped, like
+    // with `send_email`.
+    pub async fn send_batch(
+        &self,
+        emails: &[BatchEmail<'_>],
+    ) -> Result<Vec<Result<SendOutcome, String>>, EmailError> {
+        let mut outcomes = vec![Ok(SendOutcome::Suppressed); emails.len()];
+
+        let mut to_send = Vec::with_capacity(emails.len());
+        for (position, email) in emails.iter().enumerate() {
+            if let Some(pool) = &self.suppression_list
+                && repository::is_email_suppressed(email.recipient.as_ref(), pool).await?
+            {
+                tracing::info!("Recipient is suppressed, email not sent");
+                continue;
+            }
+            to_send.push(position);
+        }
+
+        if to_send.is_empty() {
+            return Ok(outcomes);
+        }
+
+        let url = self.base_url.join("/email/batch")?;
+
+        let request_body: Vec<SendEmailRequest> = to_send
+            .iter()
+            .map(|&position| {
+                let email = &emails[position];
+                SendEmailRequest {
+                    from: self.sender.as_ref(),
+                    to: email.recipient.as_ref(),
+                    subject: email.subject,
+                    html_body: email.html_content,
+                    text_body: email.text_content,
+                    attachments: Vec::new(),
+                }
+            })
+            .collect();
+
+        let responses: Vec<BatchEmailResponse> = self
+            .http_client
+            .post(url)
+            .header(
+                "X-Postmark-Server-Token",
+                self.authorization_token.expose_secret(),
+            )
+            .json(&request_body)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let mut responses = responses.into_iter();
+        for position in to_send {
+            outcomes[position] = match responses.next() {
+                Some(response) if response.error_code == 0 => Ok(SendOutcome::Sent),
+                Some(response) => Err(format!(
+                    "Postmark error {}: {}",
+                    response.error_code, response.message
+                )),
+                None => Err("Missing from Postmark's batch response".to_string()),
+            };
+        }
+
+        Ok(outcomes)
     }
 }
 
@@ -98,7 +259,10 @@ mod tests {
     use serde_json::Value;
     use wiremock::{Match, Mock, MockServer, Request, ResponseTemplate, matchers};
 
-    use crate::{domain::UserEmail, email_client::EmailClient};
+    use crate::{
+        domain::{EmailAttachment, UserEmail},
+        email_client::{BatchEmail, EmailClient},
+    };
 
     struct SendEmailBodyMatcher;
 
@@ -195,6 +359,157 @@ mod tests {
         assert_err!(outcome);
     }
 
+    #[tokio::test]
+    async fn send_email_with_attachments_sends_them_base64_encoded() {
+        let mock_server = MockServer::start().await;
+        let email_client = email_client(mock_server.uri());
+        let attachment =
+            EmailAttachment::parse("digest.pdf".into(), "application/pdf", b"%PDF-1.7".to_vec())
+                .unwrap();
+
+        Mock::given(matchers::path("/email"))
+            .and(SendEmailBodyMatcher)
+            .and(matchers::body_partial_json(serde_json::json!({
+                "Attachments": [{
+                    "Name": "digest.pdf",
+                    "Content": "JVBERi0xLjc=",
+                    "ContentType": "application/pdf",
+                }]
+            })))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let outcome = email_client
+            .send_email_with_attachments(
+                &email(),
+                &subject(),
+                &content(),
+                &content(),
+                &[attachment],
+            )
+            .await;
+
+        assert_ok!(outcome);
+    }
+
+    #[tokio::test]
+    async fn send_email_without_attachments_leaves_them_out() {
+        let mock_server = MockServer::start().await;
+        let email_client = email_client(mock_server.uri());
+
+        Mock::given(matchers::any())
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        email_client
+            .send_email(&email(), &subject(), &content(), &content())
+            .await
+            .unwrap();
+
+        let requests = mock_server.received_requests().await.unwrap();
+        let body: Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert!(body.get("Attachments").is_none());
+    }
+
+    #[tokio::test]
+    async fn send_batch_sends_all_emails_in_one_request() {
+        let mock_server = MockServer::start().await;
+        let email_client = email_client(mock_server.uri());
+        let recipients = [email(), email(), email()];
+
+        Mock::given(matchers::header_exists("X-Postmark-Server-Token"))
+            .and(matchers::path("/email/batch"))
+            .and(matchers::method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                { "ErrorCode": 0, "Message": "OK" },
+                { "ErrorCode": 0, "Message": "OK" },
+                { "ErrorCode": 0, "Message": "OK" },
+            ])))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let (subject, content) = (subject(), content());
+        let outcomes = email_client
+            .send_batch(&batch(&recipients, &subject, &content))
+            .await
+            .unwrap();
+
+        let requests = mock_server.received_requests().await.unwrap();
+        let body: Vec<Value> = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(body.len(), 3);
+        assert!(body.iter().all(|email| email.get("To").is_some()));
+        assert!(outcomes.iter().all(Result::is_ok));
+    }
+
+    #[tokio::test]
+    async fn send_batch_reports_the_emails_postmark_rejected() {
+        let mock_server = MockServer::start().await;
+        let email_client = email_client(mock_server.uri());
+        let recipients = [email(), email()];
+
+        Mock::given(matchers::path("/email/batch"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                { "ErrorCode": 0, "Message": "OK" },
+                { "ErrorCode": 406, "Message": "Inactive recipient" },
+            ])))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let (subject, content) = (subject(), content());
+        let outcomes = email_client
+            .send_batch(&batch(&recipients, &subject, &content))
+            .await
+            .unwrap();
+
+        assert_ok!(&outcomes[0]);
+        assert_eq!(
+            outcomes[1],
+            Err("Postmark error 406: Inactive recipient".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn send_batch_fails_if_the_server_returns_500() {
+        let mock_server = MockServer::start().await;
+        let email_client = email_client(mock_server.uri());
+        let recipients = [email()];
+
+        Mock::given(matchers::any())
+            .respond_with(ResponseTemplate::new(500))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let (subject, content) = (subject(), content());
+        let outcome = email_client
+            .send_batch(&batch(&recipients, &subject, &content))
+            .await;
+
+        assert_err!(outcome);
+    }
+
+    fn batch<'a>(
+        recipients: &'a [UserEmail],
+        subject: &'a str,
+        content: &'a str,
+    ) -> Vec<BatchEmail<'a>> {
+        recipients
+            .iter()
+            .map(|recipient| BatchEmail {
+                recipient,
+                subject,
+                html_content: content,
+                text_content: content,
+            })
+            .collect()
+    }
+
     // Generate a random email subject
     fn subject() -> String {
         lorem::en::Sentence(1..2).fake()
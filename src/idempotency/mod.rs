@@ -1,4 +1,4 @@
 mod key;
 mod persistence;
 pub use key::IdempotencyKey;
-pub use persistence::{NextAction, save_response, try_processing};
+pub use persistence::{NextAction, SavedResponse, save_response, try_processing};
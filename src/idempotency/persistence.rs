@@ -13,20 +13,27 @@ struct HeaderPairRecord {
 
 pub enum NextAction {
     StartProcessing(Transaction<'static, Postgres>),
-    ReturnSavedResponse(HttpResponse),
+    ReturnSavedResponse(SavedResponse),
+}
+
+pub struct SavedResponse {
+    pub response: HttpResponse,
+    // What the response refers to, callers check it still exists before replaying
+    pub resource_id: Option<Uuid>,
 }
 
 pub async fn get_saved_response(
     pool: &PgPool,
     idempotency_key: &IdempotencyKey,
     user_id: Uuid,
-) -> Result<Option<HttpResponse>, anyhow::Error> {
+) -> Result<Option<SavedResponse>, anyhow::Error> {
     let saved_response = sqlx::query!(
         r#"
         SELECT 
             response_status_code as "response_status_code!", 
             response_headers as "response_headers!: Vec<HeaderPairRecord>",
-            response_body as "response_body!"
+            response_body as "response_body!",
+            resource_id
         FROM idempotency
         WHERE 
           user_id = $1 AND
@@ -43,7 +50,10 @@ pub async fn get_saved_response(
         for HeaderPairRecord { name, value } in r.response_headers {
             response.append_header((name, value));
         }
-        Ok(Some(response.body(r.response_body)))
+        Ok(Some(SavedResponse {
+            response: response.body(r.response_body),
+            resource_id: r.resource_id,
+        }))
     } else {
         Ok(None)
     }
@@ -53,6 +63,7 @@ pub async fn save_response(
     mut transaction: Transaction<'static, Postgres>,
     idempotency_key: &IdempotencyKey,
     user_id: Uuid,
+    resource_id: Option<Uuid>,
     http_response: HttpResponse,
 ) -> Result<HttpResponse, anyhow::Error> {
     let (response_head, body) = http_response.into_parts();
@@ -78,7 +89,8 @@ pub async fn save_response(
         SET
         response_status_code = $3,
         response_headers = $4,
-        response_body = $5
+        response_body = $5,
+        resource_id = $6
         WHERE
         user_id = $1 AND
         idempotency_key = $2
@@ -88,7 +100,8 @@ pub async fn save_response(
             status_code,
             headers,
             // body.as_ref() returns a &[u8] — a byte slice which sqlx knows how to store in a BYTEA column
-            body.as_ref()
+            body.as_ref(),
+            resource_id
         ))
         .await?;
 
@@ -1,13 +1,22 @@
 #![cfg_attr(test, allow(clippy::unwrap_used))]
 pub mod authentication;
+pub mod captcha_client;
+pub mod changelog;
+pub mod client_ip;
+pub mod compression;
 pub mod configuration;
 pub mod domain;
 pub mod email_client;
 pub mod idempotency;
 pub mod newsletter_delivery_worker;
+pub mod post_views;
+pub mod queue_dashboard;
+pub mod rate_limiter;
 pub mod repository;
 pub mod routes;
 pub mod session_state;
+pub mod single_flight;
 pub mod startup;
+pub mod suggester;
 pub mod telemetry;
 pub mod utils;
@@ -1,6 +1,12 @@
-use std::fmt::{Debug, Display};
+use std::{
+    env,
+    fmt::{Debug, Display},
+    io,
+};
 
-use techhub::{configuration, newsletter_delivery_worker, startup::Application, telemetry};
+use techhub::{
+    configuration, newsletter_delivery_worker, queue_dashboard, startup::Application, telemetry,
+};
 use tokio::task::JoinError;
 
 #[tokio::main]
@@ -11,9 +17,37 @@ async fn main() {
 }
 
 async fn try_main() -> anyhow::Result<()> {
-    let subscriber = telemetry::get_subscriber("techhub".into(), "info".into(), std::io::stdout);
+    match env::args().nth(1).as_deref() {
+        None => run_server().await,
+        Some("queue") => run_queue_dashboard().await,
+        Some(other) => anyhow::bail!(
+            "Unknown command: {other}. Run `techhub` to start the server or `techhub queue` to inspect the delivery queue"
+        ),
+    }
+}
+
+async fn run_queue_dashboard() -> anyhow::Result<()> {
+    // Logging to stdout would draw over the terminal UI
+    let config = configuration::get_config().expect("Failed to read config");
+    let subscriber = telemetry::get_subscriber(
+        "techhub".into(),
+        config.application.log_filter.clone(),
+        io::sink,
+    );
     telemetry::init_subscriber(subscriber);
+
+    queue_dashboard::run_queue_dashboard(config).await
+}
+
+async fn run_server() -> anyhow::Result<()> {
     let config = configuration::get_config().expect("Failed to read config");
+    // RUST_LOG takes precedence over the configured directives
+    let subscriber = telemetry::get_subscriber(
+        "techhub".into(),
+        config.application.log_filter.clone(),
+        io::stdout,
+    );
+    telemetry::init_subscriber(subscriber);
     let application = Application::build(config.clone()).await?;
 
     let application_task = tokio::spawn(application.run_until_stopped());
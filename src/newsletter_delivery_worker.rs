@@ -1,6 +1,7 @@
-use std::ops::DerefMut;
+use std::{env, ops::DerefMut};
 
 use anyhow::Context;
+use chrono::{DateTime, Utc};
 use rand::{Rng, SeedableRng, rngs::StdRng};
 use sqlx::{Executor, PgPool};
 use tokio::{time, time::Duration};
@@ -8,9 +9,59 @@ use tracing::{Span, field};
 use uuid::Uuid;
 
 use crate::{
-    configuration::Configuration, domain::UserEmail, email_client::EmailClient, repository, startup,
+    configuration::{
+        Configuration, DatabaseMaintenanceSettings, NewsletterDeliverySettings,
+        NewsletterDigestSettings, SessionSettings,
+    },
+    domain::{
+        AccountEmailRequest, AuditAction, DeliveryOutcome, DueActivationReminder, NewsletterIssue,
+        RecipientPlaceholders, TableBloatStats, TableScanStats, TokenPurpose, UserEmail,
+        UserNameChangeNotice, weekly_digest,
+    },
+    email_client::{BatchEmail, EmailClient, MAX_BATCH_SIZE, SendOutcome},
+    repository, routes, startup, utils,
 };
 
+// Subscribers enqueued per fan-out step of a freshly published issue
+pub const FAN_OUT_CHUNK_SIZE: i64 = 1000;
+// Fan-out pauses while this many tasks are already waiting for delivery
+pub const MAX_QUEUE_DEPTH: i64 = 10_000;
+// Each instance reports itself alive this often
+pub const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+// Instances that missed a few heartbeats in a row are reported as dead
+pub const WORKER_LIVENESS_TIMEOUT: Duration = Duration::from_secs(90);
+// Instances started together by a deployment spread their first dequeue over this window
+const STARTUP_JITTER_SECS: u64 = 30;
+// Window over which instances spread their first cleanup run
+const CLEANUP_SKEW_SECS: u128 = 3600;
+// Tables whose feed and comment queries are expected to be served by indexes
+const SEQ_SCAN_WATCHED_TABLES: [&str; 2] = ["posts", "comments"];
+const SEQ_SCAN_CHECK_INTERVAL: Duration = Duration::from_secs(3600);
+// Tables whose rows are written once and deleted or rewritten soon after, so dead rows pile up
+// between autovacuum runs. Kept in alphabetical order, the order the stats are reported in.
+pub const BLOAT_WATCHED_TABLES: [&str; 6] = [
+    "account_email_requests",
+    "idempotency",
+    "issue_delivery_queue",
+    "login_throttles",
+    "refresh_tokens",
+    "user_sessions",
+];
+const BLOAT_CHECK_INTERVAL: Duration = Duration::from_secs(3600);
+// Hours after registration at which an unactivated account is reminded, one reminder each
+pub const ACTIVATION_REMINDER_DELAYS_HOURS: [i32; 2] = [24, 72];
+// Accounts registered longer ago than this are no longer reminded
+const ACTIVATION_REMINDER_WINDOW_HOURS: i32 = 7 * 24;
+const ACTIVATION_REMINDER_BATCH_SIZE: i64 = 100;
+const ACTIVATION_REMINDER_INTERVAL: Duration = Duration::from_secs(3600);
+const USER_NAME_CHANGE_NOTICE_BATCH_SIZE: i64 = 100;
+const USER_NAME_CHANGE_NOTICE_INTERVAL: Duration = Duration::from_secs(3600);
+// Password reset and sign-in links are waited for, so their queue is polled often
+const ACCOUNT_EMAIL_BATCH_SIZE: i64 = 100;
+const ACCOUNT_EMAIL_INTERVAL: Duration = Duration::from_secs(5);
+// Posts published over this many days before a digest run are featured in it
+const DIGEST_PERIOD_DAYS: i64 = 7;
+
 pub enum ExecutionOutcome {
     TaskCompleted,
     EmptyQueue,
@@ -18,17 +69,105 @@ pub enum ExecutionOutcome {
 
 pub async fn run_worker_until_stopped(config: Configuration) -> Result<(), anyhow::Error> {
     let connection_pool = startup::get_connection_pool(&config.database);
-    let email_client = config.email_client.client();
-    worker_loop(connection_pool, email_client).await
+    let email_client = config
+        .email_client
+        .clone()
+        .client()
+        .with_suppression_list(connection_pool.clone());
+
+    tokio::spawn(remind_unactivated_users(
+        connection_pool.clone(),
+        config
+            .email_client
+            .clone()
+            .client()
+            .with_suppression_list(connection_pool.clone()),
+        config.application.base_url.clone(),
+    ));
+    tokio::spawn(send_requested_account_emails(
+        connection_pool.clone(),
+        config
+            .email_client
+            .clone()
+            .client()
+            .with_suppression_list(connection_pool.clone()),
+        config.application.base_url.clone(),
+    ));
+    tokio::spawn(notify_renamed_users(
+        connection_pool.clone(),
+        config
+            .email_client
+            .client()
+            .with_suppression_list(connection_pool.clone()),
+    ));
+    tokio::spawn(watch_table_bloat(
+        connection_pool.clone(),
+        config.database_maintenance,
+    ));
+    if config.newsletter_digest.enabled {
+        tokio::spawn(send_weekly_digests(
+            connection_pool.clone(),
+            config.newsletter_digest,
+            config.application.base_url.clone(),
+        ));
+    }
+
+    worker_loop(
+        connection_pool,
+        email_client,
+        config.session,
+        config.newsletter_delivery,
+        config.application.base_url,
+    )
+    .await
 }
 
-async fn worker_loop(pool: PgPool, email_client: EmailClient) -> Result<(), anyhow::Error> {
+async fn worker_loop(
+    pool: PgPool,
+    email_client: EmailClient,
+    session_settings: SessionSettings,
+    delivery_settings: NewsletterDeliverySettings,
+    base_url: String,
+) -> Result<(), anyhow::Error> {
+    let worker_id = Uuid::new_v4();
+    let hostname = env::var("HOSTNAME").unwrap_or_else(|_| "unknown".to_string());
+    let started_at = Utc::now();
+    tracing::info!(%worker_id, %hostname, "Newsletter delivery worker starting");
+
+    // spawn heartbeat loop independently so liveness is reported even while a task is in flight
+    let pool_for_heartbeat = pool.clone();
+
+    tokio::spawn(async move {
+        let mut interval = time::interval(HEARTBEAT_INTERVAL);
+
+        loop {
+            interval.tick().await;
+            if let Err(e) = repository::record_worker_heartbeat(
+                &pool_for_heartbeat,
+                worker_id,
+                &hostname,
+                started_at,
+            )
+            .await
+            {
+                tracing::error!(error.cause_chain = ?e, "Worker heartbeat failed");
+            }
+        }
+    });
+
+    tokio::spawn(watch_seq_scans(pool.clone()));
+
     // spawn cleanup loops independently
     let pool_for_cleanup = pool.clone();
 
     tokio::spawn(async move {
         let mut rng = StdRng::from_entropy();
 
+        // Derived from the instance id so instances started by the same deployment don't all
+        // hit the db with their first cleanup at once
+        let skew_secs = (worker_id.as_u128() % CLEANUP_SKEW_SECS) as u64;
+        time::sleep(Duration::from_secs(skew_secs)).await;
+
         loop {
             if let Err(e) = repository::cleanup_old_idempotency_records(&pool_for_cleanup).await {
                 tracing::error!(error.cause_chain = ?e, "Idempotency cleanup failed");
@@ -36,6 +175,29 @@ async fn worker_loop(pool: PgPool, email_client: EmailClient) -> Result<(), anyh
             if let Err(e) = repository::cleanup_old_newsletter_issues(&pool_for_cleanup).await {
                 tracing::error!(error.cause_chain = ?e, "Old newsletter cleanup failed");
             }
+            if let Err(e) = repository::aggregate_expired_impressions(&pool_for_cleanup).await {
+                tracing::error!(error.cause_chain = ?e, "Impressions aggregation failed");
+            }
+            if let Err(e) = repository::cleanup_stale_worker_heartbeats(&pool_for_cleanup).await {
+                tracing::error!(error.cause_chain = ?e, "Worker heartbeat cleanup failed");
+            }
+            if let Err(e) = repository::cleanup_orphaned_post_bodies(&pool_for_cleanup).await {
+                tracing::error!(error.cause_chain = ?e, "Orphaned post body cleanup failed");
+            }
+            if let Err(e) = repository::cleanup_expired_login_throttles(&pool_for_cleanup).await {
+                tracing::error!(error.cause_chain = ?e, "Login throttle cleanup failed");
+            }
+            if let Err(e) = repository::cleanup_expired_tokens(&pool_for_cleanup).await {
+                tracing::error!(error.cause_chain = ?e, "Expired token cleanup failed");
+            }
+            if let Err(e) = repository::cleanup_old_login_history(&pool_for_cleanup).await {
+                tracing::error!(error.cause_chain = ?e, "Login history cleanup failed");
+            }
+            if let Err(e) =
+                repository::cleanup_expired_sessions(&session_settings, &pool_for_cleanup).await
+            {
+                tracing::error!(error.cause_chain = ?e, "Session cleanup failed");
+            }
 
             // This random jitter will ensure multiple instances of app won't clean db at same time
             // Nonetheless a delete statement is concurrency safe in db
@@ -48,9 +210,25 @@ async fn worker_loop(pool: PgPool, email_client: EmailClient) -> Result<(), anyh
     // start with 1s base delay, max 1 minute
     let mut backoff_secs = 1_u64;
 
+    // Avoid every instance of a fresh deployment polling the queue in lockstep
+    let startup_jitter = rng.gen_range(0..=STARTUP_JITTER_SECS);
+    time::sleep(Duration::from_secs(startup_jitter)).await;
+
     // newsletter dispatch worker loop
     loop {
-        match try_execute_task(&pool, &email_client).await {
+        // Operators can pause delivery (e.g. via `techhub queue`) without stopping the app
+        match repository::is_delivery_paused(&pool).await {
+            Ok(true) => {
+                time::sleep(Duration::from_secs(10)).await;
+                continue;
+            }
+            Ok(false) => {}
+            Err(e) => {
+                tracing::error!(error.cause_chain = ?e, "Failed to read delivery pause flag");
+            }
+        }
+
+        match try_execute_task(&pool, &email_client, &base_url, &delivery_settings).await {
             Ok(ExecutionOutcome::EmptyQueue) => {
                 // Zero pending tasks hence sleep longer, reset backoff
                 backoff_secs = 1;
@@ -81,6 +259,466 @@ async fn worker_loop(pool: PgPool, email_client: EmailClient) -> Result<(), anyh
     }
 }
 
+// Warns when the watched tables were mostly read sequentially since the previous check, which
+// usually means a new filter or sort is missing an index
+async fn watch_seq_scans(pool: PgPool) {
+    let mut interval = time::interval(SEQ_SCAN_CHECK_INTERVAL);
+    let mut previous: Vec<TableScanStats> = Vec::new();
+
+    loop {
+        interval.tick().await;
+
+        let current = match repository::get_table_scan_stats(&pool, &SEQ_SCAN_WATCHED_TABLES).await
+        {
+            Ok(current) => current,
+            Err(e) => {
+                tracing::error!(error.cause_chain = ?e, "Failed to read table scan statistics");
+                continue;
+            }
+        };
+
+        for stats in &current {
+            let Some(earlier) = previous.iter().find(|p| p.table == stats.table) else {
+                continue;
+            };
+            let delta = stats.since(earlier);
+            if delta.is_seq_scan_heavy() {
+                tracing::warn!(
+                    table = %delta.table,
+                    seq_scans = delta.seq_scans,
+                    seq_rows_read = delta.seq_rows_read,
+                    index_scans = delta.index_scans,
+                    live_rows = delta.live_rows,
+                    "Queries are falling back to sequential scans"
+                );
+            }
+        }
+
+        previous = current;
+    }
+}
+
+async fn watch_table_bloat(pool: PgPool, settings: DatabaseMaintenanceSettings) {
+    let mut interval = time::interval(BLOAT_CHECK_INTERVAL);
+
+    loop {
+        interval.tick().await;
+        if let Err(e) = check_table_bloat(&pool, &settings).await {
+            tracing::error!(error.cause_chain = ?e, "Table bloat check failed");
+        }
+    }
+}
+
+// Samples dead rows in the watched tables and returns the bloated ones. Those are vacuumed when
+// configured to, otherwise only reported so an operator can decide.
+#[tracing::instrument(skip_all)]
+pub async fn check_table_bloat(
+    pool: &PgPool,
+    settings: &DatabaseMaintenanceSettings,
+) -> Result<Vec<TableBloatStats>, anyhow::Error> {
+    let stats = repository::get_table_bloat_stats(pool, &BLOAT_WATCHED_TABLES).await?;
+
+    let mut bloated = Vec::new();
+    for table in stats {
+        tracing::info!(
+            table = %table.table,
+            live_rows = table.live_rows,
+            dead_rows = table.dead_rows,
+            dead_row_ratio = table.dead_row_ratio(),
+            estimated_bloat_bytes = table.estimated_bloat_bytes(),
+            "Sampled table bloat"
+        );
+
+        if !table.is_bloated(settings.max_dead_row_ratio, settings.min_dead_rows) {
+            continue;
+        }
+
+        if settings.vacuum_bloated_tables {
+            tracing::warn!(table = %table.table, "Table is bloated, running VACUUM ANALYZE");
+            repository::vacuum_analyze_table(pool, &table.table).await?;
+        } else {
+            tracing::warn!(
+                table = %table.table,
+                dead_rows = table.dead_rows,
+                dead_row_ratio = table.dead_row_ratio(),
+                last_vacuumed_at = ?table.last_vacuumed_at,
+                "Table is bloated and may need a VACUUM ANALYZE"
+            );
+        }
+        bloated.push(table);
+    }
+
+    Ok(bloated)
+}
+
+// Runs missed while no worker instance was up are skipped, not caught up on
+async fn send_weekly_digests(pool: PgPool, settings: NewsletterDigestSettings, base_url: String) {
+    let schedule = settings
+        .schedule()
+        .expect("Invalid newsletter digest schedule");
+
+    for scheduled_for in schedule.upcoming(Utc) {
+        let wait = (scheduled_for - Utc::now()).to_std().unwrap_or_default();
+        time::sleep(wait).await;
+
+        if let Err(e) = send_weekly_digest(&pool, &settings, &base_url, scheduled_for).await {
+            tracing::error!(error.cause_chain = ?e, %scheduled_for, "Newsletter digest failed");
+        }
+    }
+}
+
+// Creates the digest issue of the run scheduled at `scheduled_for`, which the worker then fans
+// out and delivers like any published issue. Returns None when another instance already handled
+// the run, or when no post of the week is worth sending.
+#[tracing::instrument(skip(pool, settings, base_url))]
+pub async fn send_weekly_digest(
+    pool: &PgPool,
+    settings: &NewsletterDigestSettings,
+    base_url: &str,
+    scheduled_for: DateTime<Utc>,
+) -> Result<Option<Uuid>, anyhow::Error> {
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to start a transaction")?;
+
+    if !repository::claim_newsletter_digest(&mut transaction, scheduled_for).await? {
+        return Ok(None);
+    }
+
+    let since = scheduled_for - chrono::Duration::days(DIGEST_PERIOD_DAYS);
+    let posts =
+        repository::get_top_posts_for_digest(since, scheduled_for, settings.max_posts, pool)
+            .await?;
+    if posts.is_empty() {
+        tracing::info!("No posts to feature, skipping this week's newsletter digest");
+        transaction
+            .commit()
+            .await
+            .context("Failed to commit newsletter digest run")?;
+        return Ok(None);
+    }
+
+    let digest = weekly_digest(scheduled_for, &posts, base_url)
+        .map_err(|e| anyhow::anyhow!(e))
+        .context("Failed to render newsletter digest")?;
+    let issue_id = repository::insert_newsletter_issue(
+        &mut transaction,
+        digest.title.as_ref(),
+        None,
+        digest.content.text.as_ref(),
+        digest.content.html.as_ref(),
+        None,
+        None,
+    )
+    .await?;
+    repository::set_newsletter_digest_issue(&mut transaction, scheduled_for, issue_id).await?;
+    repository::record_audit_event(
+        &mut *transaction,
+        AuditAction::NewsletterPublish,
+        None,
+        Some(issue_id),
+        serde_json::json!({
+            "title": digest.title.as_ref(),
+            "digest_scheduled_for": scheduled_for,
+        }),
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit newsletter digest")?;
+
+    tracing::info!(%issue_id, posts = posts.len(), "Newsletter digest created");
+    Ok(Some(issue_id))
+}
+
+#[tracing::instrument(skip_all)]
+async fn remind_unactivated_users(pool: PgPool, email_client: EmailClient, base_url: String) {
+    let mut interval = time::interval(ACTIVATION_REMINDER_INTERVAL);
+
+    loop {
+        interval.tick().await;
+        if let Err(e) = send_activation_reminders(&pool, &email_client, &base_url).await {
+            tracing::error!(error.cause_chain = ?e, "Activation reminders failed");
+        }
+    }
+}
+
+// Sends every reminder currently due, returning how many went out. A reminder that fails to send
+// is not recorded, so it is retried on the next run.
+#[tracing::instrument(skip_all)]
+pub async fn send_activation_reminders(
+    pool: &PgPool,
+    email_client: &EmailClient,
+    base_url: &str,
+) -> Result<usize, anyhow::Error> {
+    let due = repository::get_due_activation_reminders(
+        pool,
+        &ACTIVATION_REMINDER_DELAYS_HOURS,
+        ACTIVATION_REMINDER_WINDOW_HOURS,
+        ACTIVATION_REMINDER_BATCH_SIZE,
+    )
+    .await?;
+
+    let mut sent = 0;
+    for reminder in due {
+        match send_activation_reminder(pool, email_client, base_url, &reminder).await {
+            Ok(true) => sent += 1,
+            Ok(false) => {}
+            Err(e) => {
+                tracing::error!(
+                    error.cause_chain = ?e,
+                    user_id = %reminder.user_id,
+                    reminder_number = reminder.reminder_number,
+                    "Failed to send activation reminder"
+                );
+            }
+        }
+    }
+
+    Ok(sent)
+}
+
+async fn send_activation_reminder(
+    pool: &PgPool,
+    email_client: &EmailClient,
+    base_url: &str,
+    reminder: &DueActivationReminder,
+) -> Result<bool, anyhow::Error> {
+    let email = match UserEmail::parse(reminder.email.clone()) {
+        Ok(email) => email,
+        Err(e) => {
+            tracing::warn!(error.message = %e, user_id = %reminder.user_id, "Skipping activation reminder to invalid email");
+            return Ok(false);
+        }
+    };
+
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to start a transaction")?;
+
+    if !repository::record_activation_reminder(
+        &mut transaction,
+        reminder.user_id,
+        reminder.reminder_number,
+    )
+    .await?
+    {
+        return Ok(false);
+    }
+
+    let token = utils::generate_token();
+    repository::replace_activation_token(&mut transaction, reminder.user_id, &token).await?;
+    let opt_out_token = utils::generate_token();
+    repository::replace_reminder_opt_out_token(&mut transaction, reminder.user_id, &opt_out_token)
+        .await?;
+
+    // Sent before committing so a failed send leaves the reminder due and the old links working
+    routes::send_activation_reminder_email(email_client, email, base_url, &token, &opt_out_token)
+        .await
+        .context("Failed to send activation reminder email")?;
+
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit activation reminder")?;
+
+    Ok(true)
+}
+
+#[tracing::instrument(skip_all)]
+async fn send_requested_account_emails(pool: PgPool, email_client: EmailClient, base_url: String) {
+    let mut interval = time::interval(ACCOUNT_EMAIL_INTERVAL);
+
+    loop {
+        interval.tick().await;
+        if let Err(e) = send_account_emails(&pool, &email_client, &base_url).await {
+            tracing::error!(error.cause_chain = ?e, "Account emails failed");
+        }
+    }
+}
+
+// Sends the password reset and sign-in links asked for, returning how many went out. A request
+// that fails to send stays queued, so it is retried on the next run.
+#[tracing::instrument(skip_all)]
+pub async fn send_account_emails(
+    pool: &PgPool,
+    email_client: &EmailClient,
+    base_url: &str,
+) -> Result<usize, anyhow::Error> {
+    let requests = repository::get_account_email_requests(pool, ACCOUNT_EMAIL_BATCH_SIZE).await?;
+
+    let mut sent = 0;
+    for request in requests {
+        match send_account_email(pool, email_client, base_url, &request).await {
+            Ok(true) => sent += 1,
+            Ok(false) => {}
+            Err(e) => {
+                tracing::error!(
+                    error.cause_chain = ?e,
+                    user_id = %request.user_id,
+                    purpose = ?request.purpose,
+                    "Failed to send account email"
+                );
+            }
+        }
+    }
+
+    Ok(sent)
+}
+
+async fn send_account_email(
+    pool: &PgPool,
+    email_client: &EmailClient,
+    base_url: &str,
+    request: &AccountEmailRequest,
+) -> Result<bool, anyhow::Error> {
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to start a transaction")?;
+
+    let owed =
+        repository::take_account_email_request(&mut transaction, request.user_id, request.purpose)
+            .await?;
+    let email = match UserEmail::parse(request.email.clone()) {
+        Ok(email) if owed => email,
+        result => {
+            if let Err(e) = result {
+                tracing::warn!(error.message = %e, user_id = %request.user_id, "Dropping account email to invalid email");
+            }
+            // Nothing would change on the next run, so the request stays removed
+            transaction
+                .commit()
+                .await
+                .context("Failed to drop account email request")?;
+            return Ok(false);
+        }
+    };
+
+    // Sent before committing so a failed send leaves the request queued and the old link working
+    let token = utils::generate_token();
+    match request.purpose {
+        TokenPurpose::PasswordReset => {
+            repository::replace_short_lived_token(
+                &mut transaction,
+                request.user_id,
+                &token,
+                TokenPurpose::PasswordReset,
+                routes::PASSWORD_RESET_TOKEN_TTL_MINUTES,
+            )
+            .await?;
+            routes::send_password_reset_email(email_client, email, base_url, &token)
+                .await
+                .context("Failed to send a password reset email")?;
+        }
+        TokenPurpose::MagicLink => {
+            repository::replace_short_lived_token(
+                &mut transaction,
+                request.user_id,
+                &token,
+                TokenPurpose::MagicLink,
+                routes::MAGIC_LINK_TOKEN_TTL_MINUTES,
+            )
+            .await?;
+            routes::send_magic_link_email(email_client, email, base_url, &token)
+                .await
+                .context("Failed to send a sign-in email")?;
+        }
+        purpose => anyhow::bail!("No account email is sent for {purpose:?} tokens"),
+    }
+
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit account email")?;
+
+    Ok(true)
+}
+
+#[tracing::instrument(skip_all)]
+async fn notify_renamed_users(pool: PgPool, email_client: EmailClient) {
+    let mut interval = time::interval(USER_NAME_CHANGE_NOTICE_INTERVAL);
+
+    loop {
+        interval.tick().await;
+        if let Err(e) = send_user_name_change_notices(&pool, &email_client).await {
+            tracing::error!(error.cause_chain = ?e, "User name change notices failed");
+        }
+    }
+}
+
+// Sends the notices still owed, returning how many went out. A notice that fails to send stays
+// unsent, so it is retried on the next run.
+#[tracing::instrument(skip_all)]
+pub async fn send_user_name_change_notices(
+    pool: &PgPool,
+    email_client: &EmailClient,
+) -> Result<usize, anyhow::Error> {
+    let notices =
+        repository::get_unsent_user_name_change_notices(pool, USER_NAME_CHANGE_NOTICE_BATCH_SIZE)
+            .await?;
+
+    let mut sent = 0;
+    for notice in notices {
+        match send_user_name_change_notice(pool, email_client, &notice).await {
+            Ok(true) => sent += 1,
+            Ok(false) => {}
+            Err(e) => {
+                tracing::error!(
+                    error.cause_chain = ?e,
+                    user_id = %notice.user_id,
+                    "Failed to send user name change notice"
+                );
+            }
+        }
+    }
+
+    Ok(sent)
+}
+
+async fn send_user_name_change_notice(
+    pool: &PgPool,
+    email_client: &EmailClient,
+    notice: &UserNameChangeNotice,
+) -> Result<bool, anyhow::Error> {
+    let email = match UserEmail::parse(notice.email.clone()) {
+        Ok(email) => email,
+        Err(e) => {
+            tracing::warn!(error.message = %e, user_id = %notice.user_id, "Skipping user name change notice to invalid email");
+            return Ok(false);
+        }
+    };
+
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to start a transaction")?;
+
+    if !repository::mark_user_name_change_notice_sent(&mut transaction, notice.user_id).await? {
+        return Ok(false);
+    }
+
+    // Sent before committing so a failed send leaves the notice unsent
+    routes::send_user_name_change_email(
+        email_client,
+        &email,
+        &notice.previous_user_name,
+        &notice.user_name,
+    )
+    .await
+    .context("Failed to send user name change email")?;
+
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit user name change notice")?;
+
+    Ok(true)
+}
+
 #[tracing::instrument(
     skip_all,
     fields(
@@ -91,7 +729,16 @@ async fn worker_loop(pool: PgPool, email_client: EmailClient) -> Result<(), anyh
 pub async fn try_execute_task(
     pool: &PgPool,
     email_client: &EmailClient,
+    base_url: &str,
+    settings: &NewsletterDeliverySettings,
 ) -> Result<ExecutionOutcome, anyhow::Error> {
+    // Keep feeding the queue with subscribers of newly published issues
+    repository::fan_out_next_chunk(pool, FAN_OUT_CHUNK_SIZE, MAX_QUEUE_DEPTH).await?;
+
+    if settings.batch_size > 1 {
+        return try_execute_batch(pool, email_client, base_url, settings).await;
+    }
+
     // Fetch a pending delivery task (row locked until commit/rollback)
     let maybe_task = dequeue_task(pool).await?;
     if maybe_task.is_none() {
@@ -107,9 +754,48 @@ pub async fn try_execute_task(
         .record("subscriber_email", field::display(&email));
 
     // Process the task within the same transaction
+    let result = process_delivery_task(
+        &mut transaction,
+        issue_id,
+        &email,
+        n_retries,
+        email_client,
+        base_url,
+        settings,
+    )
+    .await;
+
+    commit_or_rollback(transaction, result).await?;
+
+    Ok(ExecutionOutcome::TaskCompleted)
+}
+
+// Delivers up to `batch_size` tasks with a single Postmark request, all of them locked and
+// settled in one transaction
+async fn try_execute_batch(
+    pool: &PgPool,
+    email_client: &EmailClient,
+    base_url: &str,
+    settings: &NewsletterDeliverySettings,
+) -> Result<ExecutionOutcome, anyhow::Error> {
+    let limit = settings.batch_size.min(MAX_BATCH_SIZE) as i64;
+    let (mut transaction, tasks) = dequeue_tasks(pool, limit).await?;
+    if tasks.is_empty() {
+        return Ok(ExecutionOutcome::EmptyQueue);
+    }
+
     let result =
-        process_delivery_task(&mut transaction, issue_id, &email, n_retries, email_client).await;
+        process_delivery_batch(&mut transaction, &tasks, email_client, base_url, settings).await;
+
+    commit_or_rollback(transaction, result).await?;
+
+    Ok(ExecutionOutcome::TaskCompleted)
+}
 
+async fn commit_or_rollback(
+    transaction: repository::PgTransaction,
+    result: Result<(), anyhow::Error>,
+) -> Result<(), anyhow::Error> {
     match result {
         Ok(_) => {
             transaction
@@ -136,7 +822,7 @@ pub async fn try_execute_task(
         }
     }
 
-    Ok(ExecutionOutcome::TaskCompleted)
+    Ok(())
 }
 
 #[tracing::instrument(
@@ -146,52 +832,283 @@ pub async fn try_execute_task(
         email = %email
     ),
 )]
+#[allow(clippy::needless_borrow)]
 async fn process_delivery_task(
     transaction: &mut repository::PgTransaction,
     issue_id: Uuid,
     email: &str,
     n_retries: i32,
     email_client: &EmailClient,
+    base_url: &str,
+    settings: &NewsletterDeliverySettings,
 ) -> Result<(), anyhow::Error> {
-    let Ok(valid_email) = UserEmail::parse(email.to_string()) else {
-        tracing::error!(
-            %email,
-            "Invalid subscriber email — deleting newsletter issue task permanently"
-        );
-        delete_task(transaction, issue_id, email).await?;
+    let Some((valid_email, issue)) =
+        prepare_delivery(transaction, issue_id, email, n_retries, base_url).await?
+    else {
         return Ok(());
     };
 
-    // Fetch issue content
-    let issue = repository::get_newsletter_issue(transaction, issue_id).await?;
-
     // Try sending the email
-    match email_client
-        .send_email(
+    let outcome = email_client
+        .send_email_with_attachments(
             &valid_email,
             &issue.title(),
             &issue.html_content(),
             &issue.text_content(),
+            issue.attachments(),
         )
         .await
-    {
-        Ok(_) => {
-            // success, remove from queue
-            delete_task(transaction, issue_id, email).await?;
+        .map_err(|e| {
+            tracing::error!(
+                error.cause_chain = ?e,
+                error.message = %e,
+                "Failed to deliver newsletter, will retry later."
+            );
+            e.to_string()
+        });
+
+    settle_task(transaction, issue_id, email, n_retries, outcome, settings).await
+}
+
+#[tracing::instrument(skip_all, fields(tasks = tasks.len()))]
+async fn process_delivery_batch(
+    transaction: &mut repository::PgTransaction,
+    tasks: &[DeliveryTask],
+    email_client: &EmailClient,
+    base_url: &str,
+    settings: &NewsletterDeliverySettings,
+) -> Result<(), anyhow::Error> {
+    let mut prepared = Vec::with_capacity(tasks.len());
+    for task in tasks {
+        let Some((valid_email, issue)) = prepare_delivery(
+            transaction,
+            task.issue_id,
+            &task.email,
+            task.n_retries,
+            base_url,
+        )
+        .await?
+        else {
+            continue;
+        };
+
+        // Postmark caps a batch request at 50 MB, which a handful of copies of an issue with
+        // attachments would already exceed
+        if !issue.attachments().is_empty() {
+            let outcome = email_client
+                .send_email_with_attachments(
+                    &valid_email,
+                    issue.title(),
+                    issue.html_content(),
+                    issue.text_content(),
+                    issue.attachments(),
+                )
+                .await
+                .map_err(|e| e.to_string());
+            if let Err(e) = &outcome {
+                tracing::error!(
+                    newsletter_issue_id = %task.issue_id,
+                    email = %task.email,
+                    error.message = %e,
+                    "Failed to deliver newsletter, will retry later."
+                );
+            }
+            settle_task(
+                transaction,
+                task.issue_id,
+                &task.email,
+                task.n_retries,
+                outcome,
+                settings,
+            )
+            .await?;
+            continue;
         }
+
+        prepared.push((task, valid_email, issue));
+    }
+
+    if prepared.is_empty() {
+        return Ok(());
+    }
+
+    let emails: Vec<BatchEmail> = prepared
+        .iter()
+        .map(|(_, valid_email, issue)| BatchEmail {
+            recipient: valid_email,
+            subject: issue.title(),
+            html_content: issue.html_content(),
+            text_content: issue.text_content(),
+        })
+        .collect();
+
+    // A failed request leaves every email of the batch unsent, so all of them are retried
+    let outcomes = match email_client.send_batch(&emails).await {
+        Ok(outcomes) => outcomes,
         Err(e) => {
             tracing::error!(
                 error.cause_chain = ?e,
                 error.message = %e,
+                "Failed to deliver newsletter batch, will retry later."
+            );
+            vec![Err(e.to_string()); emails.len()]
+        }
+    };
+
+    for ((task, _, _), outcome) in prepared.iter().zip(outcomes) {
+        if let Err(e) = &outcome {
+            tracing::error!(
+                newsletter_issue_id = %task.issue_id,
+                email = %task.email,
+                error.message = %e,
                 "Failed to deliver newsletter, will retry later."
             );
-            retry_task(transaction, issue_id, email, n_retries).await?;
         }
+        settle_task(
+            transaction,
+            task.issue_id,
+            &task.email,
+            task.n_retries,
+            outcome,
+            settings,
+        )
+        .await?;
     }
 
     Ok(())
 }
 
+// Returns the recipient and their personalised copy of the issue, or None when the task was
+// already settled without sending anything
+#[tracing::instrument(
+    skip_all,
+    fields(
+        newsletter_issue_id = %issue_id,
+        email = %email
+    ),
+)]
+async fn prepare_delivery(
+    transaction: &mut repository::PgTransaction,
+    issue_id: Uuid,
+    email: &str,
+    n_retries: i32,
+    base_url: &str,
+) -> Result<Option<(UserEmail, NewsletterIssue)>, anyhow::Error> {
+    // Only tasks that were being sent while the issue was cancelled, and were then retried, are
+    // still queued
+    if repository::is_newsletter_issue_cancelled(transaction, issue_id).await? {
+        tracing::info!("Newsletter issue was cancelled, skipping delivery");
+        finish_task(
+            transaction,
+            issue_id,
+            email,
+            DeliveryOutcome::Skipped,
+            None,
+            n_retries,
+        )
+        .await?;
+        return Ok(None);
+    }
+
+    let Ok(valid_email) = UserEmail::parse(email.to_string()) else {
+        tracing::error!(
+            %email,
+            "Invalid subscriber email — deleting newsletter issue task permanently"
+        );
+        dead_letter_task(
+            transaction,
+            issue_id,
+            email,
+            "Invalid subscriber email",
+            n_retries,
+        )
+        .await?;
+        return Ok(None);
+    };
+
+    let Some(unsubscribe_token) =
+        repository::get_or_create_unsubscribe_token(transaction, email, &utils::generate_token())
+            .await?
+    else {
+        tracing::info!("Subscriber is gone, skipping newsletter issue");
+        finish_task(
+            transaction,
+            issue_id,
+            email,
+            DeliveryOutcome::Skipped,
+            None,
+            n_retries,
+        )
+        .await?;
+        return Ok(None);
+    };
+
+    let issue = repository::get_newsletter_issue(transaction, issue_id).await?;
+    let subject_variant = issue.subject_variant_for(issue_id, email);
+    let tracking_token = repository::get_or_create_tracking_token(
+        transaction,
+        issue_id,
+        email,
+        &utils::generate_token(),
+        subject_variant,
+    )
+    .await?;
+
+    let unsubscribe_url = format!("{base_url}/v1/user/unsubscribe?token={unsubscribe_token}");
+    let placeholders = RecipientPlaceholders {
+        user_name: repository::get_subscriber_name(transaction, email).await?,
+        unsubscribe_url: unsubscribe_url.clone(),
+    };
+
+    let issue = issue
+        .with_subject_variant(subject_variant)
+        .with_click_tracking(|position| format!("{base_url}/t/click/{tracking_token}.{position}"))
+        .with_open_tracking(&format!("{base_url}/t/open/{tracking_token}"))
+        .with_unsubscribe_link(&unsubscribe_url)
+        .with_placeholders(&placeholders);
+
+    Ok(Some((valid_email, issue)))
+}
+
+// Removes a sent or suppressed task from the queue, or schedules a failed one for a retry
+async fn settle_task(
+    transaction: &mut repository::PgTransaction,
+    issue_id: Uuid,
+    email: &str,
+    n_retries: i32,
+    outcome: Result<SendOutcome, String>,
+    settings: &NewsletterDeliverySettings,
+) -> Result<(), anyhow::Error> {
+    match outcome {
+        Ok(SendOutcome::Sent) => {
+            // success, remove from queue
+            finish_task(
+                transaction,
+                issue_id,
+                email,
+                DeliveryOutcome::Sent,
+                None,
+                n_retries + 1,
+            )
+            .await
+        }
+        // Nothing went out, so nothing is left to track either
+        Ok(SendOutcome::Suppressed) => {
+            repository::delete_tracking_token(transaction, issue_id, email).await?;
+            finish_task(
+                transaction,
+                issue_id,
+                email,
+                DeliveryOutcome::Skipped,
+                None,
+                n_retries,
+            )
+            .await
+        }
+        Err(e) => retry_task(transaction, issue_id, email, n_retries, &e, settings).await,
+    }
+}
+
 async fn dequeue_task(
     pool: &PgPool,
 ) -> Result<Option<(repository::PgTransaction, Uuid, String, i32)>, anyhow::Error> {
@@ -226,6 +1143,46 @@ async fn dequeue_task(
     }
 }
 
+struct DeliveryTask {
+    issue_id: Uuid,
+    email: String,
+    n_retries: i32,
+}
+
+async fn dequeue_tasks(
+    pool: &PgPool,
+    limit: i64,
+) -> Result<(repository::PgTransaction, Vec<DeliveryTask>), anyhow::Error> {
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to start a transaction")?;
+
+    let tasks = sqlx::query!(
+        r#"
+        SELECT newsletter_issue_id, user_email, n_retries
+        FROM issue_delivery_queue
+        WHERE execute_after <= NOW()
+        FOR UPDATE
+        SKIP LOCKED
+        LIMIT $1
+        "#,
+        limit
+    )
+    .fetch_all(transaction.deref_mut())
+    .await
+    .context("Failed dequeue newsletter issue tasks from db")?
+    .into_iter()
+    .map(|r| DeliveryTask {
+        issue_id: r.newsletter_issue_id,
+        email: r.user_email,
+        n_retries: r.n_retries,
+    })
+    .collect();
+
+    Ok((transaction, tasks))
+}
+
 #[tracing::instrument(
     skip_all,
     fields(
@@ -238,32 +1195,35 @@ async fn retry_task(
     issue_id: Uuid,
     email: &str,
     current_retry: i32,
+    error_message: &str,
+    settings: &NewsletterDeliverySettings,
 ) -> Result<(), anyhow::Error> {
     let next_retry = current_retry + 1;
 
-    // give up after 5 attempts
-    if next_retry > 5 {
-        tracing::error!(%issue_id, "Max retries reached, dropping newsletter issue task permanently");
-        delete_task(transaction, issue_id, email).await?;
+    if next_retry > settings.max_retries {
+        tracing::error!(%issue_id, "Max retries reached, moving newsletter issue task to the dead-letter queue");
+        dead_letter_task(transaction, issue_id, email, error_message, next_retry).await?;
         return Ok(());
     }
 
-    // Exponential backoff: 1m, 2m, 4m, 8m, 16m, 32m, 60m
-    let base_delay_secs = 60 * (1 << (next_retry - 1)).min(60);
-    let jitter_secs: i64 = rand::thread_rng().gen_range(0..=30);
-    let total_delay_secs = (base_delay_secs + jitter_secs) as f64;
+    let jitter_secs = rand::thread_rng().gen_range(0..=settings.retry_jitter_seconds);
+    let total_delay_secs =
+        (settings.retry_delay(next_retry) + Duration::from_secs(jitter_secs)).as_secs_f64();
 
     let query = sqlx::query!(
         r#"
         UPDATE issue_delivery_queue
         SET n_retries = $3,
-            execute_after = NOW() + ($4 * INTERVAL '1 second')
+            execute_after = NOW() + ($4 * INTERVAL '1 second'),
+            last_error = $5,
+            last_attempted_at = NOW()
         WHERE newsletter_issue_id = $1 AND user_email = $2
         "#,
         issue_id,
         email,
         next_retry,
-        total_delay_secs
+        total_delay_secs,
+        error_message
     );
     transaction
         .execute(query)
@@ -273,10 +1233,14 @@ async fn retry_task(
     Ok(())
 }
 
-async fn delete_task(
+// Removes the task from the queue and logs how it went, for the status of the issue
+async fn finish_task(
     transaction: &mut repository::PgTransaction,
     issue_id: Uuid,
     email: &str,
+    outcome: DeliveryOutcome,
+    error: Option<&str>,
+    attempts: i32,
 ) -> Result<(), anyhow::Error> {
     let query = sqlx::query!(
         r#"
@@ -293,5 +1257,26 @@ async fn delete_task(
         .await
         .context("Failed delete a newsletter issue task from db")?;
 
-    Ok(())
+    repository::record_delivery_outcome(transaction, issue_id, email, outcome, error, attempts)
+        .await
+}
+
+// Gives up on the task for good, keeping it in the dead-letter queue with the error that ended it
+async fn dead_letter_task(
+    transaction: &mut repository::PgTransaction,
+    issue_id: Uuid,
+    email: &str,
+    error: &str,
+    attempts: i32,
+) -> Result<(), anyhow::Error> {
+    finish_task(
+        transaction,
+        issue_id,
+        email,
+        DeliveryOutcome::Failed,
+        Some(error),
+        attempts,
+    )
+    .await?;
+    repository::dead_letter_delivery(transaction, issue_id, email, attempts, error).await
 }
@@ -0,0 +1,104 @@
+use std::{
+    collections::HashMap,
+    mem,
+    sync::{Arc, Mutex},
+};
+
+use sqlx::PgPool;
+use tokio::time::{self, Duration};
+use uuid::Uuid;
+
+use crate::repository;
+
+// Views are written at most this often, a crash loses at most this much of them
+pub const POST_VIEW_FLUSH_INTERVAL: Duration = Duration::from_secs(10);
+
+// Counts post views in memory so opening a post does not write to the db. The counts are added to
+// the daily rollup in one statement per flush.
+#[derive(Clone, Default, Debug)]
+pub struct PostViewCounter {
+    pending: Arc<Mutex<HashMap<Uuid, i64>>>,
+}
+
+impl PostViewCounter {
+    pub fn record(&self, post_id: Uuid) {
+        let mut pending = self.pending.lock().expect("post view lock poisoned");
+        *pending.entry(post_id).or_default() += 1;
+    }
+
+    fn take(&self) -> HashMap<Uuid, i64> {
+        mem::take(&mut *self.pending.lock().expect("post view lock poisoned"))
+    }
+
+    // Views of a failed flush are counted again, to be written by the next one
+    fn restore(&self, views: HashMap<Uuid, i64>) {
+        let mut pending = self.pending.lock().expect("post view lock poisoned");
+        for (post_id, count) in views {
+            *pending.entry(post_id).or_default() += count;
+        }
+    }
+
+    // Views are counted on the day they are flushed, which is only off for those just before
+    // midnight
+    pub async fn flush(&self, pool: &PgPool) -> Result<(), anyhow::Error> {
+        let views = self.take();
+        if views.is_empty() {
+            return Ok(());
+        }
+
+        let (post_ids, counts): (Vec<Uuid>, Vec<i64>) =
+            views.iter().map(|(id, count)| (*id, *count)).unzip();
+        if let Err(e) = repository::add_post_views(&post_ids, &counts, pool).await {
+            self.restore(views);
+            return Err(e);
+        }
+
+        Ok(())
+    }
+}
+
+pub async fn run_flush_loop(counter: PostViewCounter, pool: PgPool) {
+    let mut interval = time::interval(POST_VIEW_FLUSH_INTERVAL);
+    loop {
+        interval.tick().await;
+        if let Err(e) = counter.flush(&pool).await {
+            tracing::warn!(error.cause_chain = ?e, "Failed to flush post views");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::PostViewCounter;
+
+    #[test]
+    fn views_are_counted_per_post_until_taken() {
+        let counter = PostViewCounter::default();
+        let first = Uuid::new_v4();
+        let second = Uuid::new_v4();
+
+        counter.record(first);
+        counter.record(first);
+        counter.record(second);
+
+        let views = counter.take();
+        assert_eq!(views[&first], 2);
+        assert_eq!(views[&second], 1);
+        assert!(counter.take().is_empty());
+    }
+
+    #[test]
+    fn restored_views_are_added_to_new_ones() {
+        let counter = PostViewCounter::default();
+        let post_id = Uuid::new_v4();
+
+        counter.record(post_id);
+        let views = counter.take();
+        counter.record(post_id);
+        counter.restore(views);
+
+        assert_eq!(counter.take()[&post_id], 2);
+    }
+}
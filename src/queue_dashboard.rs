@@ -0,0 +1,209 @@
+use std::io;
+
+use anyhow::Context;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::{
+    DefaultTerminal, Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Bar, BarChart, BarGroup, Block, Paragraph, Row, Table},
+};
+use sqlx::PgPool;
+use tokio::{
+    task,
+    time::{Duration, Instant},
+};
+
+use crate::{configuration::Configuration, domain::DeliveryQueueStats, repository, startup};
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+const INPUT_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+// Terminal dashboard for operators without access to the web admin: `techhub queue`
+pub async fn run_queue_dashboard(config: Configuration) -> Result<(), anyhow::Error> {
+    let pool = startup::get_connection_pool(&config.database);
+    let max_retries = config.newsletter_delivery.max_retries;
+    let stats = repository::get_delivery_queue_stats(&pool, max_retries)
+        .await
+        .context("Failed to load delivery queue stats")?;
+
+    let mut terminal = ratatui::init();
+    let result = dashboard_loop(&mut terminal, &pool, stats).await;
+    // Always give the terminal back, even if the loop failed
+    ratatui::restore();
+    result
+}
+
+async fn dashboard_loop(
+    terminal: &mut DefaultTerminal,
+    pool: &PgPool,
+    mut stats: DeliveryQueueStats,
+) -> Result<(), anyhow::Error> {
+    let mut last_refresh = Instant::now();
+    let mut status = String::new();
+
+    loop {
+        terminal
+            .draw(|frame| render(frame, &stats, &status))
+            .context("Failed to draw queue dashboard")?;
+
+        let mut force_refresh = false;
+        match read_key()? {
+            Some(KeyCode::Char('q')) | Some(KeyCode::Esc) => return Ok(()),
+            Some(KeyCode::Char('p')) => {
+                status = toggle_pause(pool, true).await;
+                force_refresh = true;
+            }
+            Some(KeyCode::Char('r')) => {
+                status = toggle_pause(pool, false).await;
+                force_refresh = true;
+            }
+            _ => {}
+        }
+
+        if force_refresh || last_refresh.elapsed() >= REFRESH_INTERVAL {
+            // Keep showing the last known stats if the database is temporarily unreachable
+            match repository::get_delivery_queue_stats(pool, stats.max_retries).await {
+                Ok(s) => stats = s,
+                Err(e) => status = format!("Refresh failed: {e}"),
+            }
+            last_refresh = Instant::now();
+        }
+    }
+}
+
+fn read_key() -> Result<Option<KeyCode>, anyhow::Error> {
+    // crossterm polling is blocking, so let the runtime know this thread is busy
+    task::block_in_place(|| -> io::Result<Option<KeyCode>> {
+        if event::poll(INPUT_POLL_INTERVAL)?
+            && let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            return Ok(Some(key.code));
+        }
+        Ok(None)
+    })
+    .context("Failed to read terminal input")
+}
+
+async fn toggle_pause(pool: &PgPool, is_paused: bool) -> String {
+    match repository::set_delivery_paused(pool, is_paused).await {
+        Ok(()) if is_paused => "Delivery paused".to_string(),
+        Ok(()) => "Delivery resumed".to_string(),
+        Err(e) => format!("Failed to update pause flag: {e}"),
+    }
+}
+
+fn render(frame: &mut Frame, stats: &DeliveryQueueStats, status: &str) {
+    let [header, middle, errors, footer] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Length(12),
+        Constraint::Min(5),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    render_header(frame, header, stats);
+
+    let [summary, chart] =
+        Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(middle);
+    render_summary(frame, summary, stats);
+    render_retries_chart(frame, chart, stats);
+
+    render_errors(frame, errors, stats);
+
+    let help = format!("q quit · p pause · r resume · refreshes every 2s   {status}");
+    frame.render_widget(Paragraph::new(help).dark_gray(), footer);
+}
+
+fn render_header(frame: &mut Frame, area: Rect, stats: &DeliveryQueueStats) {
+    let state = if stats.is_paused {
+        "PAUSED".bold().fg(Color::Yellow)
+    } else {
+        "RUNNING".bold().fg(Color::Green)
+    };
+
+    let line = Line::from(vec!["Newsletter delivery worker: ".into(), state]);
+    frame.render_widget(
+        Paragraph::new(line).block(Block::bordered().title(" TechHub queue ")),
+        area,
+    );
+}
+
+fn render_summary(frame: &mut Frame, area: Rect, stats: &DeliveryQueueStats) {
+    let [totals, issues] =
+        Layout::vertical([Constraint::Length(7), Constraint::Min(3)]).areas(area);
+
+    let lines = vec![
+        Line::from(format!("Queue depth:     {}", stats.total)),
+        Line::from(format!("Ready now:       {}", stats.ready)),
+        Line::from(format!("Retrying:        {}", stats.retrying)),
+        Line::from(format!(
+            "On final retry:  {} (dead-lettered after {} retries)",
+            stats.on_final_retry, stats.max_retries
+        )),
+        Line::from(format!("Dead-lettered:   {}", stats.dead_lettered)),
+    ];
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::bordered().title(" Depth ")),
+        totals,
+    );
+
+    let rows = stats
+        .pending_issues
+        .iter()
+        .map(|issue| Row::new(vec![issue.title.clone(), issue.pending.to_string()]));
+    let table = Table::new(rows, [Constraint::Min(10), Constraint::Length(10)])
+        .header(Row::new(vec!["Issue", "Pending"]).bold())
+        .block(Block::bordered().title(" Pending issues "));
+    frame.render_widget(table, issues);
+}
+
+fn render_retries_chart(frame: &mut Frame, area: Rect, stats: &DeliveryQueueStats) {
+    let bars: Vec<Bar> = stats
+        .retries_distribution
+        .iter()
+        .map(|(n_retries, count)| {
+            Bar::default()
+                .value((*count).max(0) as u64)
+                .label(Line::from(n_retries.to_string()))
+        })
+        .collect();
+
+    let chart = BarChart::default()
+        .block(Block::bordered().title(" Tasks by retry count "))
+        .data(BarGroup::default().bars(&bars))
+        .bar_width(5)
+        .bar_gap(1)
+        .bar_style(Style::default().fg(Color::Cyan));
+    frame.render_widget(chart, area);
+}
+
+fn render_errors(frame: &mut Frame, area: Rect, stats: &DeliveryQueueStats) {
+    let rows = stats.recent_errors.iter().map(|e| {
+        let attempted_at = e
+            .last_attempted_at
+            .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_default();
+        Row::new(vec![
+            attempted_at,
+            e.user_email.clone(),
+            e.n_retries.to_string(),
+            e.last_error.clone(),
+        ])
+    });
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(19),
+            Constraint::Length(30),
+            Constraint::Length(7),
+            Constraint::Min(20),
+        ],
+    )
+    .header(Row::new(vec!["Last attempt", "Recipient", "Retries", "Error"]).bold())
+    .block(Block::bordered().title(" Recent worker errors "));
+    frame.render_widget(table, area);
+}
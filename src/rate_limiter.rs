@@ -0,0 +1,256 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use actix_web::{
+    HttpMessage,
+    body::MessageBody,
+    dev::{ServiceRequest, ServiceResponse},
+    error,
+    http::{
+        StatusCode,
+        header::{self, HeaderMap, HeaderName},
+    },
+    middleware::Next,
+    web::Data,
+};
+
+use crate::{authentication::UserId, client_ip::client_address, utils};
+
+// Fixed-window request counter kept in memory. Each instance counts on its own, which is enough
+// to stop a single client from hammering a cheap read endpoint. Limits that must hold across
+// instances are counted in the db instead.
+pub struct RateLimiter {
+    max_requests: u32,
+    window: Duration,
+    windows: Mutex<Windows>,
+}
+
+struct Windows {
+    by_key: HashMap<String, (Instant, u32)>,
+    swept_at: Instant,
+}
+
+impl RateLimiter {
+    pub fn new(max_requests: u32, window: Duration) -> Self {
+        Self {
+            max_requests,
+            window,
+            windows: Mutex::new(Windows {
+                by_key: HashMap::new(),
+                swept_at: Instant::now(),
+            }),
+        }
+    }
+
+    // Counts the request and returns whether it is within the limit
+    pub fn try_acquire(&self, key: &str) -> bool {
+        self.acquire(key).allowed
+    }
+
+    // Counts the request and returns the state of the key's window, for clients to pace themselves
+    pub fn acquire(&self, key: &str) -> RateLimitStatus {
+        self.acquire_at(key, Instant::now())
+    }
+
+    fn acquire_at(&self, key: &str, now: Instant) -> RateLimitStatus {
+        let mut windows = self.windows.lock().expect("rate limiter lock poisoned");
+
+        // Expired windows are dropped so one-off clients don't accumulate forever. Sweeping at
+        // most once per window keeps the full scan off all but one request in that time.
+        if now.duration_since(windows.swept_at) >= self.window {
+            windows
+                .by_key
+                .retain(|_, (started_at, _)| now.duration_since(*started_at) < self.window);
+            windows.swept_at = now;
+        }
+
+        let (started_at, count) = windows.by_key.entry(key.to_string()).or_insert((now, 0));
+        if now.duration_since(*started_at) >= self.window {
+            *started_at = now;
+            *count = 0;
+        }
+
+        let allowed = *count < self.max_requests;
+        if allowed {
+            *count += 1;
+        }
+
+        RateLimitStatus {
+            allowed,
+            limit: self.max_requests,
+            remaining: self.max_requests - *count,
+            reset_after: self.window - now.duration_since(*started_at),
+        }
+    }
+}
+
+pub struct RateLimitStatus {
+    pub allowed: bool,
+    pub limit: u32,
+    // Requests left in the current window
+    pub remaining: u32,
+    // Until the current window ends and the count starts again
+    pub reset_after: Duration,
+}
+
+// Route groups sharing a request budget, each configured under `rate_limits`
+#[derive(Copy, Clone, Debug)]
+pub enum RateLimitedRoute {
+    Registration,
+    Login,
+    SubscriptionEmail,
+    AccountEmail,
+    CommentCreation,
+}
+
+pub struct RouteRateLimiters {
+    pub registration: RateLimiter,
+    pub login: RateLimiter,
+    pub subscription_email: RateLimiter,
+    pub account_email: RateLimiter,
+    pub comment_creation: RateLimiter,
+}
+
+impl RouteRateLimiters {
+    fn get(&self, route: RateLimitedRoute) -> &RateLimiter {
+        match route {
+            RateLimitedRoute::Registration => &self.registration,
+            RateLimitedRoute::Login => &self.login,
+            RateLimitedRoute::SubscriptionEmail => &self.subscription_email,
+            RateLimitedRoute::AccountEmail => &self.account_email,
+            RateLimitedRoute::CommentCreation => &self.comment_creation,
+        }
+    }
+}
+
+const RATE_LIMIT_LIMIT: HeaderName = HeaderName::from_static("ratelimit-limit");
+const RATE_LIMIT_REMAINING: HeaderName = HeaderName::from_static("ratelimit-remaining");
+const RATE_LIMIT_RESET: HeaderName = HeaderName::from_static("ratelimit-reset");
+
+// Middleware that counts requests against the budget of `route`, per user when nested inside
+// `reject_anonymous_users` and per client IP otherwise, see `client_ip::client_ip`. Responses carry the `RateLimit-*`
+// headers, requests over the budget get 429 with `Retry-After`. Wrap routes with
+// `from_fn(move |req, next| rate_limit(...))`.
+pub async fn rate_limit(
+    route: RateLimitedRoute,
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, actix_web::Error> {
+    let limiters = req.app_data::<Data<RouteRateLimiters>>().ok_or_else(|| {
+        utils::app_error(StatusCode::INTERNAL_SERVER_ERROR, "Missing rate limiters")
+    })?;
+
+    let user_id = req.extensions().get::<UserId>().copied();
+    let key = match user_id {
+        Some(user_id) => format!("user:{user_id}"),
+        None => format!("ip:{}", client_address(req.request())),
+    };
+    let status = limiters.get(route).acquire(&key);
+
+    if !status.allowed {
+        tracing::warn!(?route, key, "Rate limit reached");
+        let message = "Too many requests, try again later";
+        let mut response =
+            utils::build_error_response(StatusCode::TOO_MANY_REQUESTS, message.to_string());
+        stamp_rate_limit(response.headers_mut(), &status);
+        response
+            .headers_mut()
+            .insert(header::RETRY_AFTER, reset_secs(&status).into());
+        return Err(error::InternalError::from_response(message, response).into());
+    }
+
+    match next.call(req).await {
+        Ok(mut response) => {
+            stamp_rate_limit(response.headers_mut(), &status);
+            Ok(response)
+        }
+        // Errors only become responses outside the app, so build it here to attach the headers
+        Err(e) => {
+            let mut response = e.error_response();
+            stamp_rate_limit(response.headers_mut(), &status);
+            Err(error::InternalError::from_response(e, response).into())
+        }
+    }
+}
+
+fn stamp_rate_limit(headers: &mut HeaderMap, status: &RateLimitStatus) {
+    headers.insert(RATE_LIMIT_LIMIT, status.limit.into());
+    headers.insert(RATE_LIMIT_REMAINING, status.remaining.into());
+    headers.insert(RATE_LIMIT_RESET, reset_secs(status).into());
+}
+
+// Rounded up, so a client waiting this long always finds a fresh window
+fn reset_secs(status: &RateLimitStatus) -> u64 {
+    status.reset_after.as_millis().div_ceil(1000) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::RateLimiter;
+
+    #[test]
+    fn requests_over_the_limit_are_refused_until_the_window_ends() {
+        let limiter = RateLimiter::new(2, Duration::from_secs(60));
+        let start = Instant::now();
+
+        assert!(limiter.acquire_at("1.2.3.4", start).allowed);
+        assert!(limiter.acquire_at("1.2.3.4", start).allowed);
+        assert!(
+            !limiter
+                .acquire_at("1.2.3.4", start + Duration::from_secs(59))
+                .allowed
+        );
+        assert!(
+            limiter
+                .acquire_at("1.2.3.4", start + Duration::from_secs(60))
+                .allowed
+        );
+    }
+
+    #[test]
+    fn keys_are_counted_separately() {
+        let limiter = RateLimiter::new(1, Duration::from_secs(60));
+        let now = Instant::now();
+
+        assert!(limiter.acquire_at("1.2.3.4", now).allowed);
+        assert!(limiter.acquire_at("5.6.7.8", now).allowed);
+        assert!(!limiter.acquire_at("1.2.3.4", now).allowed);
+    }
+
+    #[test]
+    fn status_reports_the_remaining_requests_and_time_until_reset() {
+        let limiter = RateLimiter::new(2, Duration::from_secs(60));
+        let start = Instant::now();
+
+        let status = limiter.acquire_at("1.2.3.4", start);
+        assert_eq!((status.limit, status.remaining), (2, 1));
+        assert_eq!(status.reset_after, Duration::from_secs(60));
+
+        limiter.acquire_at("1.2.3.4", start);
+        let status = limiter.acquire_at("1.2.3.4", start + Duration::from_secs(45));
+        assert!(!status.allowed);
+        assert_eq!(status.remaining, 0);
+        assert_eq!(status.reset_after, Duration::from_secs(15));
+    }
+
+    #[test]
+    fn expired_windows_are_swept_once_a_window_has_passed() {
+        let limiter = RateLimiter::new(1, Duration::from_secs(60));
+        let start = Instant::now();
+
+        limiter.acquire_at("1.2.3.4", start);
+        limiter.acquire_at("5.6.7.8", start + Duration::from_secs(30));
+        limiter.acquire_at("9.9.9.9", start + Duration::from_secs(75));
+
+        // The first window ended and was dropped, the second is still running
+        let windows = limiter.windows.lock().unwrap();
+        let mut keys: Vec<&str> = windows.by_key.keys().map(String::as_str).collect();
+        keys.sort();
+        assert_eq!(keys, ["5.6.7.8", "9.9.9.9"]);
+    }
+}
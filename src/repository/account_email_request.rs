@@ -0,0 +1,90 @@
+use std::ops::DerefMut;
+
+use anyhow::Context;
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::domain::{AccountEmailRequest, TokenPurpose, UserEmail};
+
+// Queues the email for the account of the address, if it is activated and not deleted, the only
+// accounts that can log in. Both cases run the same single statement, so the response time does
+// not tell them apart.
+#[tracing::instrument(skip(email, pool))]
+pub async fn queue_account_email(
+    email: &UserEmail,
+    purpose: TokenPurpose,
+    pool: &PgPool,
+) -> Result<(), anyhow::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO account_email_requests (user_id, purpose)
+        SELECT id, $2 FROM users
+        WHERE LOWER(email) = LOWER($1) AND is_activated AND deleted_at IS NULL
+        ON CONFLICT DO NOTHING
+        "#,
+        email.as_ref(),
+        purpose as TokenPurpose
+    )
+    .execute(pool)
+    .await
+    .context("Failed to queue an account email")?;
+    Ok(())
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn get_account_email_requests(
+    pool: &PgPool,
+    limit: i64,
+) -> Result<Vec<AccountEmailRequest>, anyhow::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT r.user_id, u.email, r.purpose AS "purpose: TokenPurpose"
+        FROM account_email_requests r
+        INNER JOIN users u ON u.id = r.user_id
+        ORDER BY r.requested_at ASC
+        LIMIT $1
+        "#,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch account email requests")?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| AccountEmailRequest {
+            user_id: r.user_id,
+            email: r.email,
+            purpose: r.purpose,
+        })
+        .collect())
+}
+
+// Removes the request and returns whether the email is still owed, `false` when another worker
+// instance took it already or the account was deleted since
+#[tracing::instrument(skip(transaction))]
+pub async fn take_account_email_request(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    purpose: TokenPurpose,
+) -> Result<bool, anyhow::Error> {
+    sqlx::query_scalar!(
+        r#"
+        WITH taken AS (
+            DELETE FROM account_email_requests
+            WHERE user_id = $1 AND purpose = $2
+            RETURNING user_id
+        )
+        SELECT EXISTS(
+            SELECT 1 FROM taken
+            INNER JOIN users u ON u.id = taken.user_id
+            WHERE u.deleted_at IS NULL
+        ) AS "owed!"
+        "#,
+        user_id,
+        purpose as TokenPurpose
+    )
+    .fetch_one(transaction.deref_mut())
+    .await
+    .context("Failed to take an account email request")
+}
@@ -0,0 +1,139 @@
+use std::ops::DerefMut;
+
+use anyhow::Context;
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::domain::{ActivationReminderStats, DueActivationReminder};
+
+// Reminder n is due `delays_hours[n - 1]` hours after registration, and never sooner after the
+// previous reminder than the schedule spaces them, so accounts that are already late when first
+// picked up don't get every reminder at once. Accounts older than `window_hours` are left alone,
+// as are placeholder and imported accounts, which can't log in, and users who opted out.
+#[tracing::instrument(skip(pool))]
+pub async fn get_due_activation_reminders(
+    pool: &PgPool,
+    delays_hours: &[i32],
+    window_hours: i32,
+    limit: i64,
+) -> Result<Vec<DueActivationReminder>, anyhow::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT u.id, u.email, (COUNT(r.user_id) + 1)::SMALLINT AS "reminder_number!"
+        FROM users u
+        LEFT JOIN activation_reminders r ON r.user_id = u.id
+        WHERE u.is_activated = false
+          AND (u.banned_at IS NULL OR u.banned_until <= NOW())
+          AND u.password_hash NOT LIKE '!%'
+          AND u.activation_reminders_opted_out_at IS NULL
+          AND u.created_at > NOW() - make_interval(hours => $2)
+        GROUP BY u.id
+        HAVING COUNT(r.user_id) < cardinality($1::INT[])
+           AND u.created_at <= NOW() - make_interval(hours => ($1::INT[])[COUNT(r.user_id)::INT + 1])
+           AND (
+               MAX(r.sent_at) IS NULL
+               OR MAX(r.sent_at) <= NOW() - make_interval(
+                   hours => ($1::INT[])[COUNT(r.user_id)::INT + 1] - ($1::INT[])[COUNT(r.user_id)::INT]
+               )
+           )
+        ORDER BY u.created_at ASC
+        LIMIT $3
+        "#,
+        delays_hours,
+        window_hours,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch users due an activation reminder")?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| DueActivationReminder {
+            user_id: r.id,
+            email: r.email,
+            reminder_number: r.reminder_number,
+        })
+        .collect())
+}
+
+// Returns `false` when the reminder was already recorded, e.g. by another worker instance
+#[tracing::instrument(skip(transaction))]
+pub async fn record_activation_reminder(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    reminder_number: i16,
+) -> Result<bool, anyhow::Error> {
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO activation_reminders (user_id, reminder_number)
+        VALUES ($1, $2)
+        ON CONFLICT DO NOTHING
+        "#,
+        user_id,
+        reminder_number
+    )
+    .execute(transaction.deref_mut())
+    .await
+    .context("Failed to record activation reminder")?;
+
+    Ok(result.rows_affected() == 1)
+}
+
+// Opting out again keeps the time of the first opt-out
+#[tracing::instrument(skip(pool))]
+pub async fn opt_out_of_activation_reminders(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<(), anyhow::Error> {
+    sqlx::query!(
+        r#"
+        UPDATE users
+        SET activation_reminders_opted_out_at = COALESCE(activation_reminders_opted_out_at, NOW())
+        WHERE id = $1
+        "#,
+        user_id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to opt out of activation reminders")?;
+
+    Ok(())
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn get_activation_reminder_stats(
+    pool: &PgPool,
+) -> Result<Vec<ActivationReminderStats>, anyhow::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT r.reminder_number,
+               COUNT(*) AS "sent!",
+               COUNT(*) FILTER (
+                   WHERE u.activated_at >= r.sent_at
+                     AND NOT EXISTS (
+                         SELECT 1 FROM activation_reminders later
+                         WHERE later.user_id = r.user_id
+                           AND later.reminder_number > r.reminder_number
+                           AND later.sent_at <= u.activated_at
+                     )
+               ) AS "converted!"
+        FROM activation_reminders r
+        INNER JOIN users u ON u.id = r.user_id
+        GROUP BY r.reminder_number
+        ORDER BY r.reminder_number ASC
+        "#
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch activation reminder statistics")?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| ActivationReminderStats {
+            reminder_number: r.reminder_number,
+            sent: r.sent,
+            converted: r.converted,
+        })
+        .collect())
+}
@@ -0,0 +1,74 @@
+use anyhow::Context;
+use sqlx::{PgExecutor, PgPool};
+use uuid::Uuid;
+
+use crate::domain::{AuditAction, AuditEvent, AuditLogFilter};
+
+// Takes any executor so the entry can be written in the same transaction as the action it records
+#[tracing::instrument(skip(executor, metadata))]
+pub async fn record_audit_event(
+    executor: impl PgExecutor<'_>,
+    action: AuditAction,
+    actor_id: Option<Uuid>,
+    target_id: Option<Uuid>,
+    metadata: serde_json::Value,
+) -> Result<(), anyhow::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO audit_log (action, actor_id, target_id, metadata)
+        VALUES ($1, $2, $3, $4)
+        "#,
+        action as AuditAction,
+        actor_id,
+        target_id,
+        metadata
+    )
+    .execute(executor)
+    .await
+    .context("Failed to record an audit event")?;
+
+    Ok(())
+}
+
+// Newest first
+#[tracing::instrument(skip(filter, pool))]
+pub async fn get_audit_log(
+    filter: &AuditLogFilter,
+    pool: &PgPool,
+) -> Result<(Vec<AuditEvent>, i64), anyhow::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT COUNT(*) OVER() AS "total_count!",
+               id, action AS "action: AuditAction", actor_id, target_id, metadata, occurred_at
+        FROM audit_log
+        WHERE ($1::audit_action IS NULL OR action = $1)
+          AND ($2::uuid IS NULL OR actor_id = $2)
+          AND ($3::uuid IS NULL OR target_id = $3)
+        ORDER BY occurred_at DESC, id DESC
+        LIMIT $4 OFFSET $5
+        "#,
+        filter.action as Option<AuditAction>,
+        filter.actor_id,
+        filter.target_id,
+        filter.pagination.limit.value() as i64,
+        filter.pagination.offset() as i64
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to load the audit log")?;
+
+    let total_count = rows.first().map(|r| r.total_count).unwrap_or(0);
+    let events = rows
+        .into_iter()
+        .map(|r| AuditEvent {
+            id: r.id,
+            action: r.action,
+            actor_id: r.actor_id,
+            target_id: r.target_id,
+            metadata: r.metadata,
+            occurred_at: r.occurred_at,
+        })
+        .collect();
+
+    Ok((events, total_count))
+}
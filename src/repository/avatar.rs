@@ -0,0 +1,97 @@
+use std::ops::DerefMut;
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::{Avatar, StoredAvatar};
+
+// Replaces any previous avatar, returning the time that versions its URL
+#[tracing::instrument(skip(avatar, pool), fields(bytes = avatar.image().len()))]
+pub async fn store_avatar(
+    user_id: Uuid,
+    avatar: &Avatar,
+    pool: &PgPool,
+) -> Result<DateTime<Utc>, anyhow::Error> {
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to start a transaction")?;
+
+    let updated_at = sqlx::query_scalar!(
+        r#"
+        INSERT INTO user_avatars (user_id, content_type, image)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (user_id) DO UPDATE
+        SET content_type = EXCLUDED.content_type, image = EXCLUDED.image, updated_at = NOW()
+        RETURNING updated_at
+        "#,
+        user_id,
+        avatar.content_type(),
+        avatar.image()
+    )
+    .fetch_one(transaction.deref_mut())
+    .await
+    .context("Failed to store avatar")?;
+
+    sqlx::query!(
+        "UPDATE users SET avatar_updated_at = $2 WHERE id = $1",
+        user_id,
+        updated_at
+    )
+    .execute(transaction.deref_mut())
+    .await
+    .context("Failed to record avatar update on user")?;
+
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit avatar upload")?;
+
+    Ok(updated_at)
+}
+
+// Returns `false` when the user had no avatar
+#[tracing::instrument(skip(pool))]
+pub async fn delete_avatar(user_id: Uuid, pool: &PgPool) -> Result<bool, anyhow::Error> {
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to start a transaction")?;
+
+    let result = sqlx::query!("DELETE FROM user_avatars WHERE user_id = $1", user_id)
+        .execute(transaction.deref_mut())
+        .await
+        .context("Failed to delete avatar")?;
+
+    sqlx::query!(
+        "UPDATE users SET avatar_updated_at = NULL WHERE id = $1",
+        user_id
+    )
+    .execute(transaction.deref_mut())
+    .await
+    .context("Failed to clear avatar update on user")?;
+
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit avatar removal")?;
+
+    Ok(result.rows_affected() == 1)
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn get_avatar(
+    user_id: Uuid,
+    pool: &PgPool,
+) -> Result<Option<StoredAvatar>, anyhow::Error> {
+    sqlx::query_as!(
+        StoredAvatar,
+        "SELECT content_type, image, updated_at FROM user_avatars WHERE user_id = $1",
+        user_id
+    )
+    .fetch_optional(pool)
+    .await
+    .context("Failed to load avatar")
+}
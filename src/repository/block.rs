@@ -0,0 +1,127 @@
+use std::ops::DerefMut;
+
+use anyhow::Context;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::BlockedUserResponseBody;
+
+// Blocking someone already blocked is a no-op. Follows between the two are dropped in both
+// directions. Returns `false` when the user to block does not exist or has deleted their account.
+#[tracing::instrument(skip(pool))]
+pub async fn block_user(
+    blocker_id: Uuid,
+    blocked_id: Uuid,
+    pool: &PgPool,
+) -> Result<bool, anyhow::Error> {
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to start a transaction")?;
+
+    let exists = sqlx::query_scalar!(
+        r#"
+        WITH target AS (
+            SELECT id FROM users WHERE id = $2 AND deleted_at IS NULL
+        ), inserted AS (
+            INSERT INTO user_blocks (blocker_id, blocked_id)
+            SELECT $1, id FROM target
+            ON CONFLICT DO NOTHING
+        )
+        SELECT EXISTS(SELECT 1 FROM target) AS "exists!"
+        "#,
+        blocker_id,
+        blocked_id
+    )
+    .fetch_one(transaction.deref_mut())
+    .await
+    .context("Failed to block user")?;
+
+    if exists {
+        sqlx::query!(
+            r#"
+            DELETE FROM follows
+            WHERE (follower_id = $1 AND followed_id = $2)
+            OR (follower_id = $2 AND followed_id = $1)
+            "#,
+            blocker_id,
+            blocked_id
+        )
+        .execute(transaction.deref_mut())
+        .await
+        .context("Failed to remove follows between blocked users")?;
+    }
+
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit user block")?;
+
+    Ok(exists)
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn unblock_user(
+    blocker_id: Uuid,
+    blocked_id: Uuid,
+    pool: &PgPool,
+) -> Result<(), anyhow::Error> {
+    sqlx::query!(
+        r#"
+        DELETE FROM user_blocks
+        WHERE blocker_id = $1 AND blocked_id = $2
+        "#,
+        blocker_id,
+        blocked_id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to unblock user")?;
+
+    Ok(())
+}
+
+// Most recently blocked first
+#[tracing::instrument(skip(pool))]
+pub async fn get_blocked_users(
+    blocker_id: Uuid,
+    pool: &PgPool,
+) -> Result<Vec<BlockedUserResponseBody>, anyhow::Error> {
+    sqlx::query_as!(
+        BlockedUserResponseBody,
+        r#"
+        SELECT u.id, u.user_name, b.created_at AS blocked_at
+        FROM user_blocks b
+        INNER JOIN users u ON u.id = b.blocked_id
+        WHERE b.blocker_id = $1
+        ORDER BY b.created_at DESC
+        "#,
+        blocker_id
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to load blocked users")
+}
+
+// Whether either user blocked the other
+#[tracing::instrument(skip(pool))]
+pub async fn is_block_between(
+    user_id: Uuid,
+    other_user_id: Uuid,
+    pool: &PgPool,
+) -> Result<bool, anyhow::Error> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM user_blocks
+            WHERE (blocker_id = $1 AND blocked_id = $2)
+            OR (blocker_id = $2 AND blocked_id = $1)
+        ) AS "exists!"
+        "#,
+        user_id,
+        other_user_id
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to check for a block between users")
+}
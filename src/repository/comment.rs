@@ -1,35 +1,154 @@
+use std::{
+    collections::{HashMap, HashSet},
+    ops::DerefMut,
+};
+
 use anyhow::Context;
 use chrono::{DateTime, Utc};
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, Transaction};
 use uuid::Uuid;
 
 use crate::{
-    domain::{Comment, CommentRecord, CommentResponseBody},
+    domain::{
+        Comment, CommentIdBatch, CommentRecord, CommentResponseBody, CommentsPage, ImportLineError,
+        ImportReport, ImportedComment, MentionResponseBody, ModerationCommentResponseBody,
+        ModerationFilters, Pagination, UserCommentRecord, UserCommentResponseBody, UserName,
+    },
     routes::CommentError,
 };
 
-#[tracing::instrument(skip(pool), fields(post_id=%post_id))]
+// Comments by users the viewer blocked are left out
+#[tracing::instrument(skip(pool, page), fields(post_id=%post_id))]
 pub async fn get_comments_for_post(
     post_id: Uuid,
+    viewer_id: Option<Uuid>,
+    page: &CommentsPage,
+    pool: &PgPool,
+) -> Result<(Vec<CommentResponseBody>, i64), anyhow::Error> {
+    let query = format!(
+        r#"
+        SELECT COUNT(*) OVER()::BIGINT AS total_count,
+               c.id, c.text, c.created_by, c.post_id, u.user_name AS user_name, u.avatar_updated_at, c.author_display_name,
+               c.created_at
+        FROM comments c
+        INNER JOIN users u ON c.created_by = u.id
+        WHERE post_id = $1 AND c.deleted_at IS NULL
+        AND NOT EXISTS (
+            SELECT 1 FROM user_blocks b
+            WHERE b.blocker_id = $4 AND b.blocked_id = c.created_by
+        )
+        ORDER BY {}
+        LIMIT $2 OFFSET $3
+        "#,
+        page.sort.to_sql()
+    );
+
+    let rows = sqlx::query_as::<_, CommentRecord>(&query)
+        .bind(post_id)
+        .bind(page.pagination.limit.value() as i64)
+        .bind(page.pagination.offset() as i64)
+        .bind(viewer_id)
+        .fetch_all(pool)
+        .await
+        .context("Failed to load comments for posts")?;
+
+    let total_count = rows.first().map(|r| r.total_count).unwrap_or(0);
+    let comments = rows.into_iter().map(CommentResponseBody::from).collect();
+
+    Ok((comments, total_count))
+}
+
+// Comments on posts that have since been deleted are left out, same as on the posts themselves
+#[tracing::instrument(skip(pool, page))]
+pub async fn get_comments_by_user(
+    user_id: Uuid,
+    page: &CommentsPage,
+    pool: &PgPool,
+) -> Result<(Vec<UserCommentResponseBody>, i64), anyhow::Error> {
+    let query = format!(
+        r#"
+        SELECT COUNT(*) OVER()::BIGINT AS total_count,
+               c.id, c.text, c.post_id, p.title AS post_title, c.created_at
+        FROM comments c
+        INNER JOIN posts p ON c.post_id = p.id
+        WHERE c.created_by = $1 AND c.deleted_at IS NULL AND p.deleted_at IS NULL
+        ORDER BY {}
+        LIMIT $2 OFFSET $3
+        "#,
+        page.sort.to_sql()
+    );
+
+    let rows = sqlx::query_as::<_, UserCommentRecord>(&query)
+        .bind(user_id)
+        .bind(page.pagination.limit.value() as i64)
+        .bind(page.pagination.offset() as i64)
+        .fetch_all(pool)
+        .await
+        .context("Failed to load comments by user")?;
+
+    let total_count = rows.first().map(|r| r.total_count).unwrap_or(0);
+    let comments = rows
+        .into_iter()
+        .map(UserCommentResponseBody::from)
+        .collect();
+
+    Ok((comments, total_count))
+}
+
+// Keyset paginated like the post export, with the same filters as `get_comments_by_user`
+#[tracing::instrument(skip(pool))]
+pub async fn get_user_comments_for_export(
+    user_id: Uuid,
+    after: Option<(DateTime<Utc>, Uuid)>,
+    limit: i64,
     pool: &PgPool,
-) -> Result<Vec<CommentResponseBody>, anyhow::Error> {
+) -> Result<Vec<UserCommentResponseBody>, anyhow::Error> {
+    let (after_created_at, after_id) = after.unzip();
+
+    let comments = sqlx::query_as!(
+        UserCommentResponseBody,
+        r#"
+        SELECT c.id, c.text, c.post_id, p.title AS post_title, c.created_at
+        FROM comments c
+        INNER JOIN posts p ON c.post_id = p.id
+        WHERE c.created_by = $1 AND c.deleted_at IS NULL AND p.deleted_at IS NULL
+        AND ($2::TIMESTAMPTZ IS NULL OR (c.created_at, c.id) > ($2, $3::UUID))
+        ORDER BY c.created_at, c.id
+        LIMIT $4
+        "#,
+        user_id,
+        after_created_at,
+        after_id,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch comments for export")?;
+
+    Ok(comments)
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn get_comments_for_posts(
+    post_ids: &[Uuid],
+    pool: &PgPool,
+) -> Result<Vec<CommentRecord>, anyhow::Error> {
     let rows = sqlx::query_as::<_, CommentRecord>(
         r#"
-        SELECT c.id, c.text, c.created_by, c.post_id, u.user_name AS user_name, c.created_at
+        SELECT c.id, c.text, c.created_by, c.post_id, u.user_name AS user_name, u.avatar_updated_at, c.author_display_name,
+               c.created_at
         FROM comments c
         INNER JOIN users u ON c.created_by = u.id
-        WHERE post_id = $1
-        ORDER BY c.id DESC
+        WHERE post_id = ANY($1) AND c.deleted_at IS NULL
+        ORDER BY c.created_at
         "#,
     )
-    .bind(post_id)
+    .bind(post_ids)
     .fetch_all(pool)
     .await
     .context("Failed to load comments for posts")?;
 
-    let comments = rows.into_iter().map(CommentResponseBody::from).collect();
-
-    Ok(comments)
+    Ok(rows)
 }
 
 #[tracing::instrument(skip(pool), fields(post_id=%comment.post_id))]
@@ -100,3 +219,282 @@ pub async fn did_user_create_the_comment(
 
     Ok(result)
 }
+
+// Recent comments across all posts, newest first, for the admin moderation queue
+#[tracing::instrument(skip_all)]
+pub async fn get_comments_for_moderation(
+    filters: &ModerationFilters,
+    pool: &PgPool,
+) -> Result<(Vec<ModerationCommentResponseBody>, i64), anyhow::Error> {
+    let rows = sqlx::query_as::<_, CommentRecord>(
+        r#"
+        SELECT COUNT(*) OVER()::BIGINT AS total_count,
+               c.id, c.text, c.created_by, c.post_id, u.user_name AS user_name, u.avatar_updated_at, c.author_display_name,
+               c.created_at
+        FROM comments c
+        INNER JOIN users u ON c.created_by = u.id
+        WHERE c.deleted_at IS NULL
+        AND ($1::UUID IS NULL OR c.created_by = $1)
+        AND ($2::UUID IS NULL OR c.post_id = $2)
+        AND ($3::TIMESTAMPTZ IS NULL OR c.created_at >= $3)
+        AND ($4::TIMESTAMPTZ IS NULL OR c.created_at <= $4)
+        ORDER BY c.created_at DESC, c.id DESC
+        LIMIT $5 OFFSET $6
+        "#,
+    )
+    .bind(filters.user_id)
+    .bind(filters.post_id)
+    .bind(filters.since)
+    .bind(filters.until)
+    .bind(filters.pagination.limit.value() as i64)
+    .bind(filters.pagination.offset() as i64)
+    .fetch_all(pool)
+    .await
+    .context("Failed to load comments for moderation")?;
+
+    let total_count = rows.first().map(|r| r.total_count).unwrap_or(0);
+    let comments = rows
+        .into_iter()
+        .map(ModerationCommentResponseBody::from)
+        .collect();
+
+    Ok((comments, total_count))
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn remove_comments(ids: &CommentIdBatch, pool: &PgPool) -> Result<u64, anyhow::Error> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE comments
+        SET deleted_at = NOW()
+        WHERE id = ANY($1) AND deleted_at IS NULL
+        "#,
+        ids.as_ref()
+    )
+    .execute(pool)
+    .await
+    .context("Failed to remove comments")?;
+
+    Ok(result.rows_affected())
+}
+
+// Self mentions and banned or inactive users are skipped
+#[tracing::instrument(skip(pool))]
+pub async fn record_comment_mentions(
+    comment_id: Uuid,
+    author_id: Uuid,
+    user_names: &[&str],
+    pool: &PgPool,
+) -> Result<u64, anyhow::Error> {
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO comment_mentions (comment_id, mentioned_user_id)
+        SELECT $1, u.id
+        FROM users u
+        WHERE u.user_name = ANY($2)
+        AND u.id <> $3
+        AND u.is_activated
+        AND (u.banned_at IS NULL OR u.banned_until <= NOW())
+        AND NOT EXISTS (
+            SELECT 1 FROM user_blocks b WHERE b.blocker_id = u.id AND b.blocked_id = $3
+        )
+        ON CONFLICT DO NOTHING
+        "#,
+        comment_id,
+        user_names as &[&str],
+        author_id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to record comment mentions")?;
+
+    Ok(result.rows_affected())
+}
+
+#[tracing::instrument(skip(pool, page))]
+pub async fn get_mentions_for_user(
+    user_id: Uuid,
+    page: &Pagination,
+    pool: &PgPool,
+) -> Result<(Vec<MentionResponseBody>, i64), anyhow::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT COUNT(*) OVER() AS "total_count!",
+               c.id, c.post_id, c.text, c.created_by, u.user_name, m.created_at
+        FROM comment_mentions m
+        INNER JOIN comments c ON m.comment_id = c.id
+        INNER JOIN users u ON c.created_by = u.id
+        WHERE m.mentioned_user_id = $1 AND c.deleted_at IS NULL
+        ORDER BY m.created_at DESC, c.id DESC
+        LIMIT $2 OFFSET $3
+        "#,
+        user_id,
+        page.limit.value() as i64,
+        page.offset() as i64
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to load mentions")?;
+
+    let total_count = rows.first().map(|r| r.total_count).unwrap_or(0);
+    let mentions = rows
+        .into_iter()
+        .map(|r| MentionResponseBody {
+            comment_id: r.id,
+            post_id: r.post_id,
+            text: r.text,
+            mentioned_by: r.created_by,
+            mentioned_by_name: r.user_name,
+            created_at: r.created_at,
+        })
+        .collect();
+
+    Ok((mentions, total_count))
+}
+
+// Shell users can never log in: they are not activated and this is not a valid password hash
+pub(super) const SHELL_USER_PASSWORD_HASH: &str = "!imported";
+
+// Looks up a user by (lowercased) email, creating a shell user when nobody has that address yet.
+// Only for addresses vouched for by whoever supplies them, e.g. an admin importing comments.
+#[tracing::instrument(skip(user_name, transaction))]
+pub(crate) async fn find_or_create_shell_user(
+    email: &str,
+    user_name: &UserName,
+    transaction: &mut Transaction<'_, Postgres>,
+) -> Result<(Uuid, bool), anyhow::Error> {
+    if let Some(user_id) = find_user_id_by_email(email, transaction).await? {
+        return Ok((user_id, false));
+    }
+
+    let user_id = create_shell_user(email, user_name, transaction).await?;
+    Ok((user_id, true))
+}
+
+#[tracing::instrument(skip(transaction))]
+pub(crate) async fn find_user_id_by_email(
+    email: &str,
+    transaction: &mut Transaction<'_, Postgres>,
+) -> Result<Option<Uuid>, anyhow::Error> {
+    sqlx::query_scalar!(r#"SELECT id FROM users WHERE LOWER(email) = $1"#, email)
+        .fetch_optional(transaction.deref_mut())
+        .await
+        .context("Failed to look up comment author")
+}
+
+// A name already held by another account gets the new id appended, like the duplicates renamed
+// when names became unique
+#[tracing::instrument(skip(user_name, transaction))]
+pub(crate) async fn create_shell_user(
+    email: &str,
+    user_name: &UserName,
+    transaction: &mut Transaction<'_, Postgres>,
+) -> Result<Uuid, anyhow::Error> {
+    let user_id = Uuid::new_v4();
+    sqlx::query!(
+        r#"
+        INSERT INTO users (id, user_name, email, password_hash)
+        SELECT $1::UUID,
+               CASE WHEN EXISTS (SELECT 1 FROM users WHERE LOWER(user_name) = LOWER($2::TEXT))
+                    THEN $2::TEXT || '-' || LEFT($1::UUID::TEXT, 8)
+                    ELSE $2::TEXT
+               END,
+               $3::TEXT, $4::TEXT
+        "#,
+        user_id,
+        user_name.as_ref(),
+        email,
+        SHELL_USER_PASSWORD_HASH
+    )
+    .execute(transaction.deref_mut())
+    .await
+    .context("Failed to create shell user for comment author")?;
+
+    Ok(user_id)
+}
+
+// Imports all valid lines in one transaction, matching authors by email and creating shell users
+// for unknown ones. A dry run performs every step and then rolls back.
+#[tracing::instrument(skip_all, fields(lines = comments.len(), dry_run))]
+pub async fn import_comments(
+    comments: Vec<(usize, ImportedComment)>,
+    dry_run: bool,
+    pool: &PgPool,
+) -> Result<ImportReport, anyhow::Error> {
+    let mut report = ImportReport {
+        dry_run,
+        ..ImportReport::default()
+    };
+
+    let post_ids: Vec<Uuid> = comments.iter().map(|(_, c)| c.comment.post_id).collect();
+    let existing_posts: HashSet<Uuid> = sqlx::query_scalar!(
+        r#"SELECT id FROM posts WHERE id = ANY($1) AND deleted_at IS NULL"#,
+        &post_ids
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to check posts for imported comments")?
+    .into_iter()
+    .collect();
+
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to start a transaction")?;
+    let mut authors: HashMap<String, Uuid> = HashMap::new();
+
+    for (line, imported) in comments {
+        if !existing_posts.contains(&imported.comment.post_id) {
+            report.errors.push(ImportLineError {
+                line,
+                error: "post not found".to_string(),
+            });
+            continue;
+        }
+
+        let email = imported.author_email.as_ref().to_lowercase();
+        let author_id = match authors.get(&email) {
+            Some(author_id) => *author_id,
+            None => {
+                let (author_id, created) =
+                    find_or_create_shell_user(&email, &imported.author_name, &mut transaction)
+                        .await?;
+                if created {
+                    report.users_created += 1;
+                }
+                authors.insert(email, author_id);
+                author_id
+            }
+        };
+
+        sqlx::query!(
+            r#"
+            INSERT INTO comments (id, text, post_id, created_by, created_at)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+            Uuid::new_v4(),
+            imported.comment.text.as_ref(),
+            imported.comment.post_id,
+            author_id,
+            imported.created_at
+        )
+        .execute(transaction.deref_mut())
+        .await
+        .context("Failed to insert imported comment")?;
+        report.imported += 1;
+    }
+
+    if dry_run {
+        transaction
+            .rollback()
+            .await
+            .context("Failed to roll back dry run import")?;
+    } else {
+        transaction
+            .commit()
+            .await
+            .context("Failed to commit comment import")?;
+    }
+
+    Ok(report)
+}
@@ -0,0 +1,92 @@
+use anyhow::Context;
+use sqlx::{Executor, PgPool};
+
+use crate::domain::{IndexUsage, TableBloatStats, TableScanStats};
+
+#[tracing::instrument(skip(pool))]
+pub async fn get_index_usage(pool: &PgPool) -> Result<Vec<IndexUsage>, anyhow::Error> {
+    let indexes = sqlx::query_as!(
+        IndexUsage,
+        r#"
+        SELECT s.relname::TEXT AS "table!",
+               s.indexrelname::TEXT AS "index!",
+               s.idx_scan AS "scans!",
+               pg_relation_size(s.indexrelid) AS "size_bytes!",
+               (s.idx_scan = 0 AND NOT i.indisunique AND NOT i.indisprimary) AS "is_unused!"
+        FROM pg_stat_user_indexes s
+        INNER JOIN pg_index i ON i.indexrelid = s.indexrelid
+        ORDER BY s.relname, s.indexrelname
+        "#
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch index usage statistics")?;
+
+    Ok(indexes)
+}
+
+// Pass no table names to get every user table
+#[tracing::instrument(skip(pool))]
+pub async fn get_table_scan_stats(
+    pool: &PgPool,
+    tables: &[&str],
+) -> Result<Vec<TableScanStats>, anyhow::Error> {
+    let stats = sqlx::query_as!(
+        TableScanStats,
+        r#"
+        SELECT relname::TEXT AS "table!",
+               COALESCE(seq_scan, 0) AS "seq_scans!",
+               COALESCE(seq_tup_read, 0) AS "seq_rows_read!",
+               COALESCE(idx_scan, 0) AS "index_scans!",
+               COALESCE(n_live_tup, 0) AS "live_rows!"
+        FROM pg_stat_user_tables
+        WHERE cardinality($1::TEXT[]) = 0 OR relname = ANY($1)
+        ORDER BY relname
+        "#,
+        tables as &[&str]
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch table scan statistics")?;
+
+    Ok(stats)
+}
+
+// Pass no table names to get every user table
+#[tracing::instrument(skip(pool))]
+pub async fn get_table_bloat_stats(
+    pool: &PgPool,
+    tables: &[&str],
+) -> Result<Vec<TableBloatStats>, anyhow::Error> {
+    let stats = sqlx::query_as!(
+        TableBloatStats,
+        r#"
+        SELECT relname::TEXT AS "table!",
+               COALESCE(n_live_tup, 0) AS "live_rows!",
+               COALESCE(n_dead_tup, 0) AS "dead_rows!",
+               pg_table_size(relid) AS "size_bytes!",
+               GREATEST(last_vacuum, last_autovacuum) AS last_vacuumed_at
+        FROM pg_stat_user_tables
+        WHERE cardinality($1::TEXT[]) = 0 OR relname = ANY($1)
+        ORDER BY relname
+        "#,
+        tables as &[&str]
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch table bloat statistics")?;
+
+    Ok(stats)
+}
+
+// VACUUM cannot be prepared or run in a transaction, so it goes over the simple query protocol.
+// The table name is interpolated and must never come from user input.
+#[tracing::instrument(skip(pool))]
+pub async fn vacuum_analyze_table(pool: &PgPool, table: &str) -> Result<(), anyhow::Error> {
+    let statement = format!("VACUUM ANALYZE \"{}\"", table.replace('"', "\"\""));
+    pool.execute(statement.as_str())
+        .await
+        .with_context(|| format!("Failed to vacuum table {table}"))?;
+
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use anyhow::Context;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::{Filters, PostSummaryRecord, PostSummaryResponse, SortDirection};
+
+// Following someone already followed is a no-op. Returns `false` when the user to follow does
+// not exist or has deleted their account.
+#[tracing::instrument(skip(pool))]
+pub async fn follow_user(
+    follower_id: Uuid,
+    followed_id: Uuid,
+    pool: &PgPool,
+) -> Result<bool, anyhow::Error> {
+    sqlx::query_scalar!(
+        r#"
+        WITH target AS (
+            SELECT id FROM users WHERE id = $2 AND deleted_at IS NULL
+        ), inserted AS (
+            INSERT INTO follows (follower_id, followed_id)
+            SELECT $1, id FROM target
+            WHERE NOT EXISTS (
+                SELECT 1 FROM user_blocks
+                WHERE (blocker_id = $1 AND blocked_id = $2)
+                OR (blocker_id = $2 AND blocked_id = $1)
+            )
+            ON CONFLICT DO NOTHING
+        )
+        SELECT EXISTS(SELECT 1 FROM target) AS "exists!"
+        "#,
+        follower_id,
+        followed_id
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to follow user")
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn unfollow_user(
+    follower_id: Uuid,
+    followed_id: Uuid,
+    pool: &PgPool,
+) -> Result<(), anyhow::Error> {
+    sqlx::query!(
+        r#"
+        DELETE FROM follows
+        WHERE follower_id = $1 AND followed_id = $2
+        "#,
+        follower_id,
+        followed_id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to unfollow user")?;
+
+    Ok(())
+}
+
+// Same cards and ordering as the public listing, restricted to authors the user follows
+#[tracing::instrument(skip(filters, pool))]
+pub async fn get_feed_posts(
+    user_id: Uuid,
+    filters: &Filters,
+    pool: &PgPool,
+) -> Result<(Vec<PostSummaryResponse>, i64), anyhow::Error> {
+    let query = format!(
+        r#"
+        SELECT COUNT(*) OVER()::BIGINT AS total_count,
+               p.id, p.title, p.excerpt, p.img, p.version,
+               p.liked_by, cc.comment_count, p.created_by, p.created_at, p.license, p.is_community,
+               u.user_name as created_by_name, u.avatar_updated_at AS created_by_avatar_updated_at
+        FROM posts p
+        INNER JOIN follows f ON f.followed_id = p.created_by AND f.follower_id = $1
+        INNER JOIN users u ON p.created_by = u.id
+        LEFT JOIN LATERAL (
+            SELECT COUNT(*) AS comment_count
+            FROM comments c
+            WHERE c.post_id = p.id AND c.deleted_at IS NULL
+        ) cc ON TRUE
+        WHERE p.deleted_at IS NULL
+        ORDER BY {}, p.created_at {}
+        LIMIT $2 OFFSET $3
+        "#,
+        filters.sort.to_sql(),
+        match filters.sort.direction {
+            SortDirection::Desc => "DESC",
+            SortDirection::Asc => "ASC",
+        },
+    );
+
+    let records = sqlx::query_as::<_, PostSummaryRecord>(&query)
+        .bind(user_id)
+        .bind(filters.limit.value() as i64)
+        .bind(filters.offset() as i64)
+        .fetch_all(pool)
+        .await
+        .context("Failed to fetch feed posts")?;
+
+    let total_count = records.first().map(|r| r.total_count).unwrap_or(0);
+    let posts = records.into_iter().map(PostSummaryResponse::from).collect();
+
+    Ok((posts, total_count))
+}
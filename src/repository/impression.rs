@@ -0,0 +1,123 @@
+use anyhow::Context;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::PostEngagement;
+
+// Raw impressions identify who saw what, so they are only kept for this long
+// before being rolled up into anonymous daily counts
+const IMPRESSION_RETENTION_DAYS: i32 = 30;
+
+#[tracing::instrument(skip(pool, post_ids), fields(n_posts = post_ids.len()))]
+pub async fn insert_impressions(
+    user_id: Option<Uuid>,
+    post_ids: &[Uuid],
+    pool: &PgPool,
+) -> Result<(), anyhow::Error> {
+    if post_ids.is_empty() {
+        return Ok(());
+    }
+
+    let positions: Vec<i32> = (1..=post_ids.len() as i32).collect();
+
+    // A single statement for the whole page instead of one insert per post
+    sqlx::query!(
+        r#"
+        INSERT INTO impressions (post_id, user_id, position)
+        SELECT post_id, $1, position
+        FROM UNNEST($2::UUID[], $3::INT[]) AS t(post_id, position)
+        "#,
+        user_id,
+        post_ids,
+        &positions
+    )
+    .execute(pool)
+    .await
+    .context("Failed to insert impressions")?;
+
+    Ok(())
+}
+
+// Adds the views counted in memory since the last flush, one count per post
+#[tracing::instrument(skip_all, fields(n_posts = post_ids.len()))]
+pub async fn add_post_views(
+    post_ids: &[Uuid],
+    views: &[i64],
+    pool: &PgPool,
+) -> Result<(), anyhow::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO post_engagement_daily (post_id, day, views)
+        SELECT post_id, CURRENT_DATE, views
+        FROM UNNEST($1::UUID[], $2::BIGINT[]) AS t(post_id, views)
+        -- Posts deleted since they were viewed are skipped
+        WHERE EXISTS (SELECT 1 FROM posts WHERE id = t.post_id)
+        ON CONFLICT (post_id, day) DO UPDATE
+        SET views = post_engagement_daily.views + EXCLUDED.views
+        "#,
+        post_ids,
+        views
+    )
+    .execute(pool)
+    .await
+    .context("Failed to record post views")?;
+
+    Ok(())
+}
+
+// Moves expired raw impressions into the daily rollup in one statement, so a
+// crash can never count an impression twice or lose it
+#[tracing::instrument(skip(pool))]
+pub async fn aggregate_expired_impressions(pool: &PgPool) -> Result<(), anyhow::Error> {
+    let aggregated = sqlx::query!(
+        r#"
+        WITH expired AS (
+            DELETE FROM impressions
+            WHERE shown_at < NOW() - make_interval(days => $1)
+            RETURNING post_id, shown_at
+        )
+        INSERT INTO post_engagement_daily (post_id, day, impressions)
+        SELECT post_id, shown_at::DATE, COUNT(*)
+        FROM expired
+        GROUP BY post_id, shown_at::DATE
+        ON CONFLICT (post_id, day) DO UPDATE
+        SET impressions = post_engagement_daily.impressions + EXCLUDED.impressions
+        "#,
+        IMPRESSION_RETENTION_DAYS
+    )
+    .execute(pool)
+    .await
+    .context("Failed to aggregate expired impressions")?
+    .rows_affected();
+
+    tracing::info!(aggregated, "Impressions aggregation completed");
+    Ok(())
+}
+
+// Views reach the daily rollup as they are flushed but impressions only once they expire, so the
+// impressions of the last `IMPRESSION_RETENTION_DAYS` are still counted from the raw table
+#[tracing::instrument(skip(pool))]
+pub async fn get_post_engagement(
+    post_id: Uuid,
+    pool: &PgPool,
+) -> Result<PostEngagement, anyhow::Error> {
+    let record = sqlx::query!(
+        r#"
+        SELECT
+            (SELECT COUNT(*) FROM impressions WHERE post_id = $1)
+                + COALESCE((SELECT SUM(impressions) FROM post_engagement_daily WHERE post_id = $1), 0)::BIGINT
+                AS "impressions!",
+            COALESCE((SELECT SUM(views) FROM post_engagement_daily WHERE post_id = $1), 0)::BIGINT
+                AS "views!"
+        "#,
+        post_id
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to fetch post engagement")?;
+
+    Ok(PostEngagement {
+        impressions: record.impressions,
+        views: record.views,
+    })
+}
@@ -0,0 +1,106 @@
+use anyhow::Context;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::{DeviceMetadata, LoginEvent, SecurityActivity};
+
+// Long enough to look back on a suspicious month
+const LOGIN_HISTORY_RETENTION_DAYS: i32 = 90;
+
+#[tracing::instrument(skip(pool))]
+pub async fn record_successful_login(
+    user_id: Uuid,
+    device: &DeviceMetadata,
+    pool: &PgPool,
+) -> Result<(), anyhow::Error> {
+    sqlx::query!(
+        r#"
+        WITH update_last_login AS (
+            UPDATE users SET last_login_at = NOW() WHERE id = $1
+        )
+        INSERT INTO login_history (user_id, succeeded, ip_address, user_agent)
+        VALUES ($1, true, $2, $3)
+        "#,
+        user_id,
+        device.ip_address,
+        device.user_agent
+    )
+    .execute(pool)
+    .await
+    .context("Failed to record a successful login")?;
+
+    Ok(())
+}
+
+// Attempts with a user name that matches no account are not recorded, there is no one to show
+// them to
+#[tracing::instrument(skip(pool))]
+pub async fn record_failed_login(
+    user_name: &str,
+    device: &DeviceMetadata,
+    pool: &PgPool,
+) -> Result<(), anyhow::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO login_history (user_id, succeeded, ip_address, user_agent)
+        SELECT id, false, $2, $3
+        FROM users
+        WHERE user_name = $1
+        "#,
+        user_name,
+        device.ip_address,
+        device.user_agent
+    )
+    .execute(pool)
+    .await
+    .context("Failed to record a failed login")?;
+
+    Ok(())
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn get_security_activity(
+    user_id: Uuid,
+    limit: i64,
+    pool: &PgPool,
+) -> Result<SecurityActivity, anyhow::Error> {
+    let last_login_at =
+        sqlx::query_scalar!(r#"SELECT last_login_at FROM users WHERE id = $1"#, user_id)
+            .fetch_one(pool)
+            .await
+            .context("Failed to fetch last login of user")?;
+
+    let recent_logins = sqlx::query_as!(
+        LoginEvent,
+        r#"
+        SELECT occurred_at, succeeded, ip_address, user_agent
+        FROM login_history
+        WHERE user_id = $1
+        ORDER BY occurred_at DESC
+        LIMIT $2
+        "#,
+        user_id,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch login history of user")?;
+
+    Ok(SecurityActivity {
+        last_login_at,
+        recent_logins,
+    })
+}
+
+pub async fn cleanup_old_login_history(pool: &PgPool) -> Result<(), anyhow::Error> {
+    let deleted = sqlx::query!(
+        r#"DELETE FROM login_history WHERE occurred_at < NOW() - make_interval(days => $1)"#,
+        LOGIN_HISTORY_RETENTION_DAYS
+    )
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    tracing::info!(deleted, "Login history cleanup completed");
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use anyhow::Context;
+use sqlx::PgPool;
+
+use crate::domain::LoginAttempts;
+
+// Counts an attempt against `key`, starting a new window when the previous one has ended. A
+// single upsert, so concurrent attempts on different instances are all counted.
+#[tracing::instrument(skip(pool))]
+pub async fn record_login_attempt(
+    key: &str,
+    window_secs: i32,
+    pool: &PgPool,
+) -> Result<LoginAttempts, anyhow::Error> {
+    let row = sqlx::query!(
+        r#"
+        INSERT INTO login_throttles (key, window_started_at, attempts)
+        VALUES ($1, NOW(), 1)
+        ON CONFLICT (key) DO UPDATE SET
+            window_started_at = CASE
+                WHEN login_throttles.window_started_at <= NOW() - make_interval(secs => $2)
+                THEN NOW()
+                ELSE login_throttles.window_started_at
+            END,
+            attempts = CASE
+                WHEN login_throttles.window_started_at <= NOW() - make_interval(secs => $2)
+                THEN 1
+                ELSE login_throttles.attempts + 1
+            END
+        RETURNING
+            attempts,
+            CEIL(EXTRACT(EPOCH FROM
+                window_started_at + make_interval(secs => $2) - NOW()
+            ))::INTEGER AS "retry_after_secs!"
+        "#,
+        key,
+        f64::from(window_secs)
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to record a login attempt")?;
+
+    Ok(LoginAttempts {
+        count: row.attempts,
+        retry_after_secs: row.retry_after_secs.max(1),
+    })
+}
+
+// Windows are at most minutes long, so day-old entries only hold finished counts
+pub async fn cleanup_expired_login_throttles(pool: &PgPool) -> Result<(), anyhow::Error> {
+    let deleted = sqlx::query!(
+        r#"DELETE FROM login_throttles WHERE window_started_at < NOW() - INTERVAL '1 day'"#
+    )
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    tracing::info!(deleted, "Login throttle cleanup completed");
+    Ok(())
+}
@@ -0,0 +1,224 @@
+use std::{collections::HashMap, ops::DerefMut};
+
+use anyhow::Context;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::{
+    ConversationResponseBody, MessageResponseBody, NewMessage, Pagination, SentMessage, avatar_url,
+};
+
+// Only the latest messages of each thread are listed in the inbox
+const MESSAGES_PER_CONVERSATION: i64 = 50;
+
+#[tracing::instrument(skip(pool))]
+pub async fn count_recent_messages(sender_id: Uuid, pool: &PgPool) -> Result<i64, anyhow::Error> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT COUNT(*) AS "count!"
+        FROM messages
+        WHERE sender_id = $1 AND created_at > NOW() - INTERVAL '1 hour'
+        "#,
+        sender_id
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to count recent messages")
+}
+
+// Starts the conversation on the first message. Returns `None` when the recipient does not
+// exist or has deleted their account.
+#[tracing::instrument(skip(message, pool), fields(recipient_id=%message.recipient_id))]
+pub async fn insert_message(
+    sender_id: Uuid,
+    message: &NewMessage,
+    pool: &PgPool,
+) -> Result<Option<SentMessage>, anyhow::Error> {
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to start a transaction")?;
+
+    let conversation_id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO conversations (id, user_a, user_b)
+        SELECT $1, LEAST($2::UUID, u.id), GREATEST($2::UUID, u.id)
+        FROM users u
+        WHERE u.id = $3 AND u.deleted_at IS NULL
+        ON CONFLICT (user_a, user_b) DO UPDATE SET last_message_at = NOW()
+        RETURNING id
+        "#,
+        Uuid::new_v4(),
+        sender_id,
+        message.recipient_id
+    )
+    .fetch_optional(transaction.deref_mut())
+    .await
+    .context("Failed to upsert conversation")?;
+
+    let Some(conversation_id) = conversation_id else {
+        return Ok(None);
+    };
+
+    let id = Uuid::new_v4();
+    sqlx::query!(
+        r#"
+        INSERT INTO messages (id, conversation_id, sender_id, text)
+        VALUES ($1, $2, $3, $4)
+        "#,
+        id,
+        conversation_id,
+        sender_id,
+        message.text.as_ref()
+    )
+    .execute(transaction.deref_mut())
+    .await
+    .context("Failed to insert message")?;
+
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit message")?;
+
+    Ok(Some(SentMessage {
+        id,
+        conversation_id,
+    }))
+}
+
+// Most recently active conversations first
+#[tracing::instrument(skip(page, pool))]
+pub async fn get_conversations(
+    user_id: Uuid,
+    page: &Pagination,
+    pool: &PgPool,
+) -> Result<(Vec<ConversationResponseBody>, i64), anyhow::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT COUNT(*) OVER() AS "total_count!",
+               c.id, c.last_message_at, u.id AS other_user_id, u.user_name AS other_user_name,
+               u.avatar_updated_at AS other_user_avatar_updated_at,
+               (
+                   SELECT COUNT(*) FROM messages m
+                   WHERE m.conversation_id = c.id AND m.sender_id <> $1 AND m.read_at IS NULL
+               ) AS "unread_count!"
+        FROM conversations c
+        INNER JOIN users u ON u.id = CASE WHEN c.user_a = $1 THEN c.user_b ELSE c.user_a END
+        WHERE c.user_a = $1 OR c.user_b = $1
+        ORDER BY c.last_message_at DESC, c.id
+        LIMIT $2 OFFSET $3
+        "#,
+        user_id,
+        page.limit.value() as i64,
+        page.offset() as i64
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to load conversations")?;
+
+    let total_count = rows.first().map(|r| r.total_count).unwrap_or(0);
+    let conversation_ids: Vec<Uuid> = rows.iter().map(|r| r.id).collect();
+    let mut messages = get_latest_messages(&conversation_ids, pool).await?;
+
+    let conversations = rows
+        .into_iter()
+        .map(|r| ConversationResponseBody {
+            id: r.id,
+            other_user_id: r.other_user_id,
+            other_user_name: r.other_user_name,
+            other_user_avatar_url: avatar_url(r.other_user_id, r.other_user_avatar_updated_at),
+            unread_count: r.unread_count,
+            last_message_at: r.last_message_at,
+            messages: messages.remove(&r.id).unwrap_or_default(),
+        })
+        .collect();
+
+    Ok((conversations, total_count))
+}
+
+async fn get_latest_messages(
+    conversation_ids: &[Uuid],
+    pool: &PgPool,
+) -> Result<HashMap<Uuid, Vec<MessageResponseBody>>, anyhow::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT conversation_id AS "conversation_id!", id AS "id!", sender_id AS "sender_id!",
+               text AS "text!", created_at AS "created_at!", read_at
+        FROM (
+            SELECT m.*, ROW_NUMBER() OVER (
+                PARTITION BY m.conversation_id ORDER BY m.created_at DESC, m.id DESC
+            ) AS position
+            FROM messages m
+            WHERE m.conversation_id = ANY($1)
+        ) latest
+        WHERE position <= $2
+        ORDER BY created_at, id
+        "#,
+        conversation_ids,
+        MESSAGES_PER_CONVERSATION
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to load messages")?;
+
+    let mut by_conversation: HashMap<Uuid, Vec<MessageResponseBody>> = HashMap::new();
+    for r in rows {
+        by_conversation
+            .entry(r.conversation_id)
+            .or_default()
+            .push(MessageResponseBody {
+                id: r.id,
+                sender_id: r.sender_id,
+                text: r.text,
+                created_at: r.created_at,
+                read_at: r.read_at,
+            });
+    }
+
+    Ok(by_conversation)
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn get_unread_message_count(user_id: Uuid, pool: &PgPool) -> Result<i64, anyhow::Error> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT COUNT(*) AS "count!"
+        FROM messages m
+        INNER JOIN conversations c ON c.id = m.conversation_id
+        WHERE (c.user_a = $1 OR c.user_b = $1) AND m.sender_id <> $1 AND m.read_at IS NULL
+        "#,
+        user_id
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to count unread messages")
+}
+
+// Marks the other user's messages as read. Returns `false` when the conversation is not one of
+// the user's.
+#[tracing::instrument(skip(pool))]
+pub async fn mark_conversation_read(
+    user_id: Uuid,
+    conversation_id: Uuid,
+    pool: &PgPool,
+) -> Result<bool, anyhow::Error> {
+    sqlx::query_scalar!(
+        r#"
+        WITH conversation AS (
+            SELECT id FROM conversations
+            WHERE id = $2 AND (user_a = $1 OR user_b = $1)
+        ), marked AS (
+            UPDATE messages
+            SET read_at = NOW()
+            WHERE conversation_id IN (SELECT id FROM conversation)
+            AND sender_id <> $1 AND read_at IS NULL
+        )
+        SELECT EXISTS(SELECT 1 FROM conversation) AS "exists!"
+        "#,
+        user_id,
+        conversation_id
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to mark conversation as read")
+}
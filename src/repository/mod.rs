@@ -1,16 +1,60 @@
+mod account_email_request;
+mod activation_reminder;
+mod audit_log;
+mod avatar;
+mod block;
 mod comment;
+mod database;
+mod follow;
 mod idempotency;
+mod impression;
+mod login_history;
+mod login_throttle;
+mod message;
 mod newsletter;
+mod personal_access_token;
 pub mod post;
+mod proposal;
+mod refresh_token;
+mod report;
+mod segment;
+mod session;
+mod submission;
+mod suppression;
 mod token;
+mod tracking;
 mod user;
+mod user_name_change_notice;
+mod worker;
 
+pub use account_email_request::*;
+pub use activation_reminder::*;
+pub use audit_log::*;
+pub use avatar::*;
+pub use block::*;
 pub use comment::*;
+pub use database::*;
+pub use follow::*;
 pub use idempotency::*;
+pub use impression::*;
+pub use login_history::*;
+pub use login_throttle::*;
+pub use message::*;
 pub use newsletter::*;
+pub use personal_access_token::*;
 pub use post::*;
+pub use proposal::*;
+pub use refresh_token::*;
+pub use report::*;
+pub use segment::*;
+pub use session::*;
 use sqlx::{Postgres, Transaction};
+pub use submission::*;
+pub use suppression::*;
 pub use token::*;
+pub use tracking::*;
 pub use user::*;
+pub use user_name_change_notice::*;
+pub use worker::*;
 
 pub type PgTransaction = Transaction<'static, Postgres>;
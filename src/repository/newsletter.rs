@@ -1,16 +1,25 @@
 use anyhow::Context;
-use sqlx::{Executor, PgPool, Postgres, Transaction};
+use chrono::{DateTime, Utc};
+use sqlx::{Executor, PgExecutor, PgPool, Postgres, Transaction};
 use uuid::Uuid;
 
 use super::PgTransaction;
-use crate::domain::NewsletterIssue;
+use crate::domain::{
+    ArchivedNewsletter, ArchivedNewsletterSummary, DeliveryOutcome, DeliveryQueueStats,
+    EmailAttachment, FailedDelivery, FanOutOutcome, LinkClicks, NewsletterIssue,
+    NewsletterIssueStatus, NewsletterIssueSummary, Pagination, PendingIssue, RecipientFailure,
+    SubjectTest, SubjectVariant, SubjectVariantStats,
+};
 
 #[tracing::instrument(skip_all)]
 pub async fn insert_newsletter_issue(
     transaction: &mut Transaction<'_, Postgres>,
     title: &str,
+    alternative_title: Option<&str>,
     text_content: &str,
     html_content: &str,
+    scheduled_at: Option<DateTime<Utc>>,
+    segment_id: Option<Uuid>,
 ) -> Result<Uuid, anyhow::Error> {
     let newsletter_issue_id = Uuid::new_v4();
     let query = sqlx::query!(
@@ -18,15 +27,23 @@ pub async fn insert_newsletter_issue(
         INSERT INTO newsletter_issues (
         id,
         title,
+        alternative_title,
         text_content,
-        html_content
+        html_content,
+        fan_out_status,
+        scheduled_at,
+        published_at,
+        segment_id
         )
-        VALUES ($1, $2, $3, $4)
+        VALUES ($1, $2, $3, $4, $5, 'pending', $6, COALESCE($6, NOW()), $7)
         "#,
         newsletter_issue_id,
         title,
+        alternative_title,
         text_content,
-        html_content
+        html_content,
+        scheduled_at,
+        segment_id
     );
     transaction
         .execute(query)
@@ -35,37 +52,656 @@ pub async fn insert_newsletter_issue(
     Ok(newsletter_issue_id)
 }
 
-#[tracing::instrument(skip(transaction))]
-pub async fn enqueue_delivery_tasks(
-    transaction: &mut Transaction<'_, Postgres>,
-    newsletter_issue_id: Uuid,
-) -> Result<(), anyhow::Error> {
-    let query = sqlx::query!(
+#[tracing::instrument(skip_all)]
+pub async fn insert_newsletter_draft(
+    transaction: &mut PgTransaction,
+    title: &str,
+    text_content: &str,
+    html_content: &str,
+) -> Result<Uuid, anyhow::Error> {
+    let newsletter_issue_id = Uuid::new_v4();
+    sqlx::query!(
         r#"
-        INSERT INTO issue_delivery_queue (
+        INSERT INTO newsletter_issues (id, title, text_content, html_content, fan_out_status)
+        VALUES ($1, $2, $3, $4, 'draft')
+        "#,
         newsletter_issue_id,
-        user_email
+        title,
+        text_content,
+        html_content
+    )
+    .execute(&mut **transaction)
+    .await
+    .context("Failed to store newsletter draft")?;
+
+    Ok(newsletter_issue_id)
+}
+
+// False unless the issue is still a draft
+#[tracing::instrument(skip(transaction, title, text_content, html_content))]
+pub async fn update_newsletter_draft(
+    transaction: &mut PgTransaction,
+    issue_id: Uuid,
+    title: &str,
+    text_content: &str,
+    html_content: &str,
+) -> Result<bool, anyhow::Error> {
+    let updated = sqlx::query!(
+        r#"
+        UPDATE newsletter_issues
+        SET title = $2, text_content = $3, html_content = $4
+        WHERE id = $1 AND fan_out_status = 'draft'
+        "#,
+        issue_id,
+        title,
+        text_content,
+        html_content
+    )
+    .execute(&mut **transaction)
+    .await
+    .context("Failed to update newsletter draft")?
+    .rows_affected();
+
+    Ok(updated > 0)
+}
+
+// Replaces every attachment of the issue, keeping the order they are given in
+#[tracing::instrument(skip(transaction, attachments), fields(attachments = attachments.len()))]
+pub async fn set_newsletter_attachments(
+    transaction: &mut PgTransaction,
+    issue_id: Uuid,
+    attachments: &[EmailAttachment],
+) -> Result<(), anyhow::Error> {
+    sqlx::query!(
+        r#"DELETE FROM newsletter_issue_attachments WHERE newsletter_issue_id = $1"#,
+        issue_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .context("Failed to remove newsletter attachments")?;
+
+    for (position, attachment) in attachments.iter().enumerate() {
+        sqlx::query!(
+            r#"
+            INSERT INTO newsletter_issue_attachments
+            (newsletter_issue_id, position, name, content_type, content)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+            issue_id,
+            position as i32,
+            attachment.name(),
+            attachment.content_type(),
+            attachment.content()
+        )
+        .execute(&mut **transaction)
+        .await
+        .context("Failed to store newsletter attachment")?;
+    }
+
+    Ok(())
+}
+
+pub async fn get_newsletter_attachments(
+    executor: impl PgExecutor<'_>,
+    issue_id: Uuid,
+) -> Result<Vec<EmailAttachment>, anyhow::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT name, content_type, content
+        FROM newsletter_issue_attachments
+        WHERE newsletter_issue_id = $1
+        ORDER BY position
+        "#,
+        issue_id
+    )
+    .fetch_all(executor)
+    .await
+    .context("Failed to get newsletter attachments")?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| EmailAttachment::from_stored(row.name, row.content_type, row.content))
+        .collect())
+}
+
+// Hands the draft to the fan-out, returning its title. None unless the issue is still a draft.
+#[tracing::instrument(skip(transaction))]
+pub async fn publish_newsletter_draft(
+    transaction: &mut PgTransaction,
+    issue_id: Uuid,
+) -> Result<Option<String>, anyhow::Error> {
+    let title = sqlx::query_scalar!(
+        r#"
+        UPDATE newsletter_issues
+        SET fan_out_status = 'pending', published_at = NOW()
+        WHERE id = $1 AND fan_out_status = 'draft'
+        RETURNING title
+        "#,
+        issue_id
+    )
+    .fetch_optional(&mut **transaction)
+    .await
+    .context("Failed to publish newsletter draft")?;
+
+    Ok(title)
+}
+
+// Moves an issue whose fan-out has not started yet. False once it has, or for drafts.
+#[tracing::instrument(skip(pool))]
+pub async fn reschedule_newsletter_issue(
+    pool: &PgPool,
+    issue_id: Uuid,
+    scheduled_at: DateTime<Utc>,
+) -> Result<bool, anyhow::Error> {
+    let updated = sqlx::query!(
+        r#"
+        UPDATE newsletter_issues
+        SET scheduled_at = $2, published_at = $2
+        WHERE id = $1 AND fan_out_status = 'pending' AND fan_out_cursor IS NULL
+          AND scheduled_at > NOW()
+        "#,
+        issue_id,
+        scheduled_at
+    )
+    .execute(pool)
+    .await
+    .context("Failed to reschedule newsletter issue")?
+    .rows_affected();
+
+    Ok(updated > 0)
+}
+
+// Stops the fan-out of a published issue and removes its queued deliveries, returning the title
+// and how many were removed. None for drafts and unknown issues. Deliveries being sent right
+// now are locked by the worker, so they are left to finish, see `is_newsletter_issue_cancelled`.
+#[tracing::instrument(skip(transaction))]
+pub async fn cancel_newsletter_issue(
+    transaction: &mut PgTransaction,
+    issue_id: Uuid,
+) -> Result<Option<(String, u64)>, anyhow::Error> {
+    let Some(title) = sqlx::query_scalar!(
+        r#"
+        UPDATE newsletter_issues
+        SET fan_out_status = 'cancelled'
+        WHERE id = $1 AND fan_out_status <> 'draft'
+        RETURNING title
+        "#,
+        issue_id
+    )
+    .fetch_optional(&mut **transaction)
+    .await
+    .context("Failed to cancel newsletter issue")?
+    else {
+        return Ok(None);
+    };
+
+    let removed = sqlx::query!(
+        r#"
+        DELETE FROM issue_delivery_queue
+        WHERE (newsletter_issue_id, user_email) IN (
+            SELECT newsletter_issue_id, user_email
+            FROM issue_delivery_queue
+            WHERE newsletter_issue_id = $1
+            FOR UPDATE SKIP LOCKED
+        )
+        "#,
+        issue_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .context("Failed to remove queued deliveries of a cancelled newsletter issue")?
+    .rows_affected();
+
+    Ok(Some((title, removed)))
+}
+
+// Moves the issue's dead-lettered deliveries back to the queue with a fresh retry budget. None when
+// the issue is a draft or was cancelled, as nothing of it is delivered anymore.
+#[tracing::instrument(skip(transaction))]
+pub async fn requeue_failed_deliveries(
+    transaction: &mut PgTransaction,
+    issue_id: Uuid,
+) -> Result<Option<u64>, anyhow::Error> {
+    let deliverable = sqlx::query_scalar!(
+        r#"
+        SELECT id
+        FROM newsletter_issues
+        WHERE id = $1 AND fan_out_status NOT IN ('draft', 'cancelled')
+        FOR UPDATE
+        "#,
+        issue_id
+    )
+    .fetch_optional(&mut **transaction)
+    .await
+    .context("Failed to lock newsletter issue")?;
+    if deliverable.is_none() {
+        return Ok(None);
+    }
+
+    let requeued = sqlx::query!(
+        r#"
+        WITH failed AS (
+            DELETE FROM issue_delivery_failures
+            WHERE newsletter_issue_id = $1
+            RETURNING user_email
         )
-        SELECT $1, email
+        INSERT INTO issue_delivery_queue (newsletter_issue_id, user_email)
+        SELECT $1, user_email FROM failed
+        ON CONFLICT DO NOTHING
+        "#,
+        issue_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .context("Failed to requeue failed newsletter deliveries")?
+    .rows_affected();
+
+    // Counted as pending again in the status of the issue until they are retried
+    sqlx::query!(
+        r#"
+        DELETE FROM newsletter_delivery_log
+        WHERE newsletter_issue_id = $1 AND outcome = 'failed'
+        "#,
+        issue_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .context("Failed to clear failed newsletter deliveries from the delivery log")?;
+
+    Ok(Some(requeued))
+}
+
+pub async fn is_newsletter_issue_cancelled(
+    transaction: &mut PgTransaction,
+    issue_id: Uuid,
+) -> Result<bool, anyhow::Error> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM newsletter_issues WHERE id = $1 AND fan_out_status = 'cancelled'
+        ) AS "cancelled!"
+        "#,
+        issue_id
+    )
+    .fetch_one(&mut **transaction)
+    .await
+    .context("Failed to check whether newsletter issue was cancelled")
+}
+
+// Enqueues delivery tasks for the next chunk of subscribers of the oldest issue still
+// being fanned out, once its scheduled time has passed. Each chunk commits on its own, so
+// publishing to a huge audience never holds a long transaction and progress survives restarts.
+// Issues targeted at a segment only go to the subscribers in it.
+#[tracing::instrument(skip(pool))]
+pub async fn fan_out_next_chunk(
+    pool: &PgPool,
+    chunk_size: i64,
+    max_queue_depth: i64,
+) -> Result<FanOutOutcome, anyhow::Error> {
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+
+    let Some(issue) = sqlx::query!(
+        r#"
+        SELECT id, fan_out_cursor, segment_id
+        FROM newsletter_issues
+        WHERE fan_out_status = 'pending' AND (scheduled_at IS NULL OR scheduled_at <= NOW())
+        ORDER BY created_at
+        LIMIT 1
+        FOR UPDATE SKIP LOCKED
+        "#
+    )
+    .fetch_optional(&mut *transaction)
+    .await
+    .context("Failed to fetch newsletter issue pending fan-out")?
+    else {
+        return Ok(FanOutOutcome::NothingPending);
+    };
+
+    // Bounded count, so checking the depth stays cheap however large the queue is
+    let queue_depth = sqlx::query_scalar!(
+        r#"
+        SELECT COUNT(*) AS "count!"
+        FROM (SELECT 1 FROM issue_delivery_queue LIMIT $1) AS q
+        "#,
+        max_queue_depth
+    )
+    .fetch_one(&mut *transaction)
+    .await
+    .context("Failed to check delivery queue depth")?;
+
+    if queue_depth >= max_queue_depth {
+        return Ok(FanOutOutcome::Throttled);
+    }
+
+    let subscribers = sqlx::query!(
+        r#"
+        SELECT id, email
         FROM users
         WHERE is_activated = true and is_subscribed = true
+        AND (banned_at IS NULL OR banned_until <= NOW())
+        AND email_undeliverable_at IS NULL
+        AND NOT EXISTS (SELECT 1 FROM suppressed_emails s WHERE s.email = LOWER(users.email))
+        AND ($1::UUID IS NULL OR id > $1)
+        AND ($3::UUID IS NULL OR EXISTS (
+            SELECT 1 FROM newsletter_segments s
+            WHERE s.id = $3
+            AND (s.signed_up_after IS NULL OR users.created_at >= s.signed_up_after)
+            AND (s.signed_up_before IS NULL OR users.created_at < s.signed_up_before)
+            AND (s.active_within_days IS NULL
+                 OR users.last_login_at >= NOW() - make_interval(days => s.active_within_days))
+        ))
+        ORDER BY id
+        LIMIT $2
         "#,
-        newsletter_issue_id,
-    );
+        issue.fan_out_cursor,
+        chunk_size,
+        issue.segment_id
+    )
+    .fetch_all(&mut *transaction)
+    .await
+    .context("Failed to fetch next chunk of subscribers")?;
+
+    let emails: Vec<String> = subscribers.iter().map(|s| s.email.clone()).collect();
+    let enqueued = sqlx::query!(
+        r#"
+        INSERT INTO issue_delivery_queue (newsletter_issue_id, user_email)
+        SELECT $1, email FROM UNNEST($2::TEXT[]) AS email
+        ON CONFLICT DO NOTHING
+        "#,
+        issue.id,
+        &emails
+    )
+    .execute(&mut *transaction)
+    .await
+    .context("Failed to enqueue delivery tasks")?
+    .rows_affected();
+
+    let finished = (subscribers.len() as i64) < chunk_size;
+    let cursor = subscribers.last().map(|s| s.id).or(issue.fan_out_cursor);
+    sqlx::query!(
+        r#"
+        UPDATE newsletter_issues
+        SET fan_out_cursor = $2,
+            enqueued_count = enqueued_count + $3,
+            fan_out_status = CASE WHEN $4 THEN 'done' ELSE 'pending' END
+        WHERE id = $1
+        "#,
+        issue.id,
+        cursor,
+        enqueued as i64,
+        finished
+    )
+    .execute(&mut *transaction)
+    .await
+    .context("Failed to record newsletter fan-out progress")?;
+
     transaction
-        .execute(query)
+        .commit()
         .await
-        .context("Failed to enqueue delivery tasks")?;
+        .context("Failed to commit newsletter fan-out chunk")?;
+
+    tracing::info!(issue_id = %issue.id, enqueued, finished, "Newsletter fan-out chunk enqueued");
+    Ok(FanOutOutcome::ChunkEnqueued { enqueued, finished })
+}
+
+// Failures listed with the status, the most recent first
+const MAX_LISTED_FAILURES: i64 = 100;
+
+pub async fn get_newsletter_issue_status(
+    pool: &PgPool,
+    issue_id: Uuid,
+) -> Result<Option<NewsletterIssueStatus>, anyhow::Error> {
+    let Some(row) = sqlx::query!(
+        r#"
+        SELECT n.id, n.title, n.alternative_title, n.fan_out_status, n.scheduled_at, n.segment_id,
+               n.enqueued_count, n.created_at, n.published_at,
+               (SELECT COUNT(*) FROM issue_delivery_queue q WHERE q.newsletter_issue_id = n.id) AS "pending!",
+               (SELECT COUNT(*) FROM issue_delivery_queue q WHERE q.newsletter_issue_id = n.id AND q.n_retries > 0) AS "retrying!",
+               n.forgotten_sent + (SELECT COUNT(*) FROM newsletter_delivery_log l WHERE l.newsletter_issue_id = n.id AND l.outcome = 'sent') AS "sent!",
+               n.forgotten_failed + (SELECT COUNT(*) FROM newsletter_delivery_log l WHERE l.newsletter_issue_id = n.id AND l.outcome = 'failed') AS "failed!",
+               n.forgotten_skipped + (SELECT COUNT(*) FROM newsletter_delivery_log l WHERE l.newsletter_issue_id = n.id AND l.outcome = 'skipped') AS "skipped!",
+               (SELECT COUNT(*) FROM newsletter_tracking_tokens t WHERE t.newsletter_issue_id = n.id AND t.first_opened_at IS NOT NULL) AS "opened!",
+               (SELECT COUNT(*) FROM newsletter_link_clicks c JOIN newsletter_tracking_tokens t ON t.token = c.token WHERE t.newsletter_issue_id = n.id) AS "clicks!",
+               (SELECT COUNT(DISTINCT c.token) FROM newsletter_link_clicks c JOIN newsletter_tracking_tokens t ON t.token = c.token WHERE t.newsletter_issue_id = n.id) AS "clicked!"
+        FROM newsletter_issues n
+        WHERE n.id = $1
+        "#,
+        issue_id
+    )
+    .fetch_optional(pool)
+    .await
+    .context("Failed to fetch newsletter issue status")?
+    else {
+        return Ok(None);
+    };
+
+    let failures = sqlx::query_as!(
+        RecipientFailure,
+        r#"
+        SELECT user_email, last_error AS error, n_retries AS attempts, failed_at
+        FROM issue_delivery_failures
+        WHERE newsletter_issue_id = $1
+        ORDER BY failed_at DESC
+        LIMIT $2
+        "#,
+        issue_id,
+        MAX_LISTED_FAILURES
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch failed newsletter deliveries")?;
+
+    let links = sqlx::query_as!(
+        LinkClicks,
+        r#"
+        SELECT c.link_position AS position, c.url, COUNT(*) AS "clicks!",
+               COUNT(DISTINCT c.token) AS "unique_clicks!"
+        FROM newsletter_link_clicks c
+        JOIN newsletter_tracking_tokens t ON t.token = c.token
+        WHERE t.newsletter_issue_id = $1
+        GROUP BY c.link_position, c.url
+        ORDER BY c.link_position
+        "#,
+        issue_id
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch newsletter link clicks")?;
+
+    let subject_test = match row.alternative_title {
+        Some(alternative_title) => {
+            let variants = sqlx::query!(
+                r#"
+                SELECT COUNT(*) FILTER (WHERE t.subject_variant = 'a') AS "sent_a!",
+                       COUNT(*) FILTER (WHERE t.subject_variant = 'a' AND t.first_opened_at IS NOT NULL) AS "opened_a!",
+                       COUNT(*) FILTER (WHERE t.subject_variant = 'b') AS "sent_b!",
+                       COUNT(*) FILTER (WHERE t.subject_variant = 'b' AND t.first_opened_at IS NOT NULL) AS "opened_b!"
+                FROM newsletter_tracking_tokens t
+                LEFT JOIN newsletter_delivery_log l
+                  ON l.newsletter_issue_id = t.newsletter_issue_id AND l.user_email = t.user_email
+                -- Tokens without an address are left only for emails that went out
+                WHERE t.newsletter_issue_id = $1 AND (l.outcome = 'sent' OR t.user_email IS NULL)
+                "#,
+                issue_id
+            )
+            .fetch_one(pool)
+            .await
+            .context("Failed to fetch newsletter subject line opens")?;
+            Some(SubjectTest::new(
+                SubjectVariantStats::new(
+                    SubjectVariant::A,
+                    row.title.clone(),
+                    variants.sent_a,
+                    variants.opened_a,
+                ),
+                SubjectVariantStats::new(
+                    SubjectVariant::B,
+                    alternative_title,
+                    variants.sent_b,
+                    variants.opened_b,
+                ),
+            ))
+        }
+        None => None,
+    };
+
+    Ok(Some(NewsletterIssueStatus {
+        id: row.id,
+        title: row.title,
+        is_complete: row.fan_out_status == "done" && row.pending == 0,
+        fan_out_status: row.fan_out_status,
+        scheduled_at: row.scheduled_at,
+        segment_id: row.segment_id,
+        enqueued: row.enqueued_count,
+        pending_deliveries: row.pending,
+        retrying_deliveries: row.retrying,
+        sent: row.sent,
+        failed: row.failed,
+        skipped: row.skipped,
+        opened: row.opened,
+        open_rate: match row.sent {
+            0 => 0.0,
+            sent => row.opened as f64 / sent as f64,
+        },
+        clicks: row.clicks,
+        clicked: row.clicked,
+        click_rate: match row.sent {
+            0 => 0.0,
+            sent => row.clicked as f64 / sent as f64,
+        },
+        links,
+        subject_test,
+        failures,
+        created_at: row.created_at,
+        published_at: row.published_at,
+    }))
+}
+
+// Recorded in the transaction that removes the task from the queue
+#[tracing::instrument(skip(transaction, error))]
+pub async fn record_delivery_outcome(
+    transaction: &mut PgTransaction,
+    issue_id: Uuid,
+    email: &str,
+    outcome: DeliveryOutcome,
+    error: Option<&str>,
+    attempts: i32,
+) -> Result<(), anyhow::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO newsletter_delivery_log (newsletter_issue_id, user_email, outcome, error, attempts)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (newsletter_issue_id, user_email) DO UPDATE
+        SET outcome = EXCLUDED.outcome, error = EXCLUDED.error, attempts = EXCLUDED.attempts,
+            recorded_at = NOW()
+        "#,
+        issue_id,
+        email,
+        outcome.as_str(),
+        error,
+        attempts
+    )
+    .execute(&mut **transaction)
+    .await
+    .context("Failed to record the outcome of a newsletter delivery")?;
+
+    Ok(())
+}
+
+// Moves a task that failed for good to the dead-letter queue, in the transaction that removes it
+// from the delivery queue
+#[tracing::instrument(skip(transaction, last_error))]
+pub async fn dead_letter_delivery(
+    transaction: &mut PgTransaction,
+    issue_id: Uuid,
+    email: &str,
+    n_retries: i32,
+    last_error: &str,
+) -> Result<(), anyhow::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO issue_delivery_failures (newsletter_issue_id, user_email, n_retries, last_error)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (newsletter_issue_id, user_email) DO UPDATE
+        SET n_retries = EXCLUDED.n_retries, last_error = EXCLUDED.last_error, failed_at = NOW()
+        "#,
+        issue_id,
+        email,
+        n_retries,
+        last_error
+    )
+    .execute(&mut **transaction)
+    .await
+    .context("Failed to move a newsletter delivery to the dead-letter queue")?;
+
     Ok(())
 }
 
+// Newest first
+#[tracing::instrument(skip(page, pool))]
+pub async fn get_newsletter_issues(
+    page: &Pagination,
+    pool: &PgPool,
+) -> Result<(Vec<NewsletterIssueSummary>, i64), anyhow::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT COUNT(*) OVER() AS "total_count!",
+               n.id, n.title, n.published_at, n.fan_out_status, n.scheduled_at, n.enqueued_count,
+               COALESCE(q.pending, 0) AS "pending!"
+        FROM newsletter_issues n
+        LEFT JOIN (
+            SELECT newsletter_issue_id, COUNT(*) AS pending
+            FROM issue_delivery_queue
+            GROUP BY newsletter_issue_id
+        ) q ON q.newsletter_issue_id = n.id
+        ORDER BY n.created_at DESC, n.id DESC
+        LIMIT $1 OFFSET $2
+        "#,
+        page.limit.value() as i64,
+        page.offset() as i64
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to load newsletter issues")?;
+
+    let total_count = rows.first().map(|r| r.total_count).unwrap_or(0);
+    let issues = rows
+        .into_iter()
+        .map(|r| NewsletterIssueSummary {
+            id: r.id,
+            title: r.title,
+            published_at: r.published_at,
+            fan_out_status: r.fan_out_status,
+            scheduled_at: r.scheduled_at,
+            enqueued: r.enqueued_count,
+            pending_deliveries: r.pending,
+            completed_deliveries: r.enqueued_count - r.pending,
+        })
+        .collect();
+
+    Ok((issues, total_count))
+}
+
+pub async fn newsletter_issue_exists(pool: &PgPool, issue_id: Uuid) -> Result<bool, anyhow::Error> {
+    sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM newsletter_issues WHERE id = $1) AS "exists!""#,
+        issue_id
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to check whether newsletter issue exists")
+}
+
 pub async fn get_newsletter_issue(
     transaction: &mut PgTransaction,
     issue_id: Uuid,
 ) -> Result<NewsletterIssue, anyhow::Error> {
     let row = sqlx::query!(
         r#"
-    SELECT title, text_content, html_content
+    SELECT title, alternative_title, text_content, html_content
     FROM newsletter_issues
     WHERE id = $1
     "#,
@@ -74,27 +710,408 @@ pub async fn get_newsletter_issue(
     .fetch_one(&mut **transaction)
     .await
     .context("Failed to get newsletter issue details")?;
+    let attachments = get_newsletter_attachments(&mut **transaction, issue_id).await?;
+
+    Ok(
+        NewsletterIssue::new(row.title, row.text_content, row.html_content)
+            .with_alternative_title(row.alternative_title)
+            .with_attachments(attachments),
+    )
+}
+
+// Drafts included, None for unknown issues
+#[tracing::instrument(skip(pool))]
+pub async fn find_newsletter_issue(
+    pool: &PgPool,
+    issue_id: Uuid,
+) -> Result<Option<NewsletterIssue>, anyhow::Error> {
+    let row = sqlx::query!(
+        r#"
+        SELECT title, text_content, html_content
+        FROM newsletter_issues
+        WHERE id = $1
+        "#,
+        issue_id
+    )
+    .fetch_optional(pool)
+    .await
+    .context("Failed to find newsletter issue")?;
+    let Some(row) = row else {
+        return Ok(None);
+    };
+    let attachments = get_newsletter_attachments(pool, issue_id).await?;
 
-    Ok(NewsletterIssue::new(
-        row.title,
-        row.text_content,
-        row.html_content,
+    Ok(Some(
+        NewsletterIssue::new(row.title, row.text_content, row.html_content)
+            .with_attachments(attachments),
     ))
 }
 
-// Moving to an archive table rather than deleting would be preferable if you want to record keep
+// For the `{{user_name}}` placeholder of the issue sent to the subscriber
+pub async fn get_subscriber_name(
+    transaction: &mut PgTransaction,
+    email: &str,
+) -> Result<String, anyhow::Error> {
+    sqlx::query_scalar!(r#"SELECT user_name FROM users WHERE email = $1"#, email)
+        .fetch_one(&mut **transaction)
+        .await
+        .context("Failed to get the name of a subscriber")
+}
+
+// Claims the digest run scheduled at `scheduled_for`, false when another worker instance got to
+// it first
+pub async fn claim_newsletter_digest(
+    transaction: &mut PgTransaction,
+    scheduled_for: DateTime<Utc>,
+) -> Result<bool, anyhow::Error> {
+    let claimed = sqlx::query!(
+        r#"
+        INSERT INTO newsletter_digests (scheduled_for)
+        VALUES ($1)
+        ON CONFLICT (scheduled_for) DO NOTHING
+        "#,
+        scheduled_for
+    )
+    .execute(&mut **transaction)
+    .await
+    .context("Failed to claim a newsletter digest run")?
+    .rows_affected()
+        == 1;
+
+    Ok(claimed)
+}
+
+pub async fn set_newsletter_digest_issue(
+    transaction: &mut PgTransaction,
+    scheduled_for: DateTime<Utc>,
+    issue_id: Uuid,
+) -> Result<(), anyhow::Error> {
+    sqlx::query!(
+        r#"
+        UPDATE newsletter_digests
+        SET newsletter_issue_id = $2
+        WHERE scheduled_for = $1
+        "#,
+        scheduled_for,
+        issue_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .context("Failed to link a newsletter digest to its issue")?;
+
+    Ok(())
+}
+
+// Scheduled issues are kept for a week after their scheduled time, drafts until published and
+// issues still being sent until their fan-out and queue are done. Past that week the recipients of
+// an issue are forgotten, see `forget_newsletter_recipients`, and issues no email went out for are
+// deleted. Issues that reached anyone stay, without recipients, for their stats and the links in
+// their emails. Public issues are moved to `newsletter_archive` as they are deleted, with only what
+// the archive shows.
 #[tracing::instrument(skip(pool))]
 pub async fn cleanup_old_newsletter_issues(pool: &PgPool) -> Result<(), anyhow::Error> {
-    let deleted = sqlx::query!(
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to start newsletter cleanup transaction")?;
+
+    let forgotten = forget_newsletter_recipients(&mut transaction).await?;
+
+    let record = sqlx::query!(
+        r#"
+        WITH deleted AS (
+            DELETE FROM newsletter_issues n
+            WHERE GREATEST(n.created_at, n.scheduled_at) < NOW() - INTERVAL '7 days'
+              AND n.fan_out_status IN ('done', 'cancelled')
+              AND NOT EXISTS (
+                  SELECT 1 FROM issue_delivery_queue q WHERE q.newsletter_issue_id = n.id
+              )
+              AND NOT EXISTS (
+                  SELECT 1 FROM newsletter_tracking_tokens t WHERE t.newsletter_issue_id = n.id
+              )
+            RETURNING n.id, n.title, n.html_content, n.published_at, n.fan_out_status, n.segment_id
+        ), archived AS (
+            INSERT INTO newsletter_archive (id, title, html_content, published_at)
+            SELECT id, title, html_content, published_at
+            FROM deleted
+            WHERE fan_out_status = 'done' AND segment_id IS NULL AND published_at IS NOT NULL
+            ON CONFLICT (id) DO NOTHING
+            RETURNING id
+        )
+        SELECT
+            (SELECT COUNT(*) FROM deleted) AS "deleted!",
+            (SELECT COUNT(*) FROM archived) AS "archived!"
+        "#,
+    )
+    .fetch_one(&mut *transaction)
+    .await
+    .context("Failed to clean up old newsletter issues")?;
+
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit newsletter cleanup transaction")?;
+
+    tracing::info!(
+        archived = record.archived,
+        deleted = record.deleted,
+        forgotten,
+        "Old newsletter issues cleanup completed"
+    );
+    Ok(())
+}
+
+// Removes the deliveries and failures of issues past their week, counting their outcomes on the
+// issue. Tracking tokens of emails that went out lose the address, the others are removed with
+// their clicks. Returns the number of deliveries forgotten.
+async fn forget_newsletter_recipients(
+    transaction: &mut PgTransaction,
+) -> Result<i64, anyhow::Error> {
+    sqlx::query_scalar!(
         r#"
-        DELETE FROM newsletter_issues
-        WHERE created_at < NOW() - INTERVAL '7 days'
+        WITH expired AS (
+            SELECT n.id
+            FROM newsletter_issues n
+            WHERE GREATEST(n.created_at, n.scheduled_at) < NOW() - INTERVAL '7 days'
+              AND n.fan_out_status IN ('done', 'cancelled')
+              AND NOT EXISTS (
+                  SELECT 1 FROM issue_delivery_queue q WHERE q.newsletter_issue_id = n.id
+              )
+        ), forgotten AS (
+            DELETE FROM newsletter_delivery_log l
+            USING expired e
+            WHERE l.newsletter_issue_id = e.id
+            RETURNING l.newsletter_issue_id, l.user_email, l.outcome
+        ), counted AS (
+            UPDATE newsletter_issues n
+            SET forgotten_sent = n.forgotten_sent + c.sent,
+                forgotten_failed = n.forgotten_failed + c.failed,
+                forgotten_skipped = n.forgotten_skipped + c.skipped
+            FROM (
+                SELECT newsletter_issue_id,
+                       COUNT(*) FILTER (WHERE outcome = 'sent') AS sent,
+                       COUNT(*) FILTER (WHERE outcome = 'failed') AS failed,
+                       COUNT(*) FILTER (WHERE outcome = 'skipped') AS skipped
+                FROM forgotten
+                GROUP BY newsletter_issue_id
+            ) c
+            WHERE n.id = c.newsletter_issue_id
+        ), deleted_failures AS (
+            DELETE FROM issue_delivery_failures f
+            USING expired e
+            WHERE f.newsletter_issue_id = e.id
+        ), deleted_tokens AS (
+            DELETE FROM newsletter_tracking_tokens t
+            USING expired e
+            WHERE t.newsletter_issue_id = e.id
+              AND t.user_email IS NOT NULL
+              AND NOT EXISTS (
+                  SELECT 1 FROM forgotten f
+                  WHERE f.newsletter_issue_id = t.newsletter_issue_id
+                    AND f.user_email = t.user_email
+                    AND f.outcome = 'sent'
+              )
+        ), anonymized_tokens AS (
+            UPDATE newsletter_tracking_tokens t
+            SET user_email = NULL
+            FROM forgotten f
+            WHERE f.newsletter_issue_id = t.newsletter_issue_id
+              AND f.user_email = t.user_email
+              AND f.outcome = 'sent'
+        )
+        SELECT COUNT(*) AS "forgotten!" FROM forgotten
         "#,
     )
+    .fetch_one(&mut **transaction)
+    .await
+    .context("Failed to forget the recipients of old newsletter issues")
+}
+
+// Newest first
+#[tracing::instrument(skip(page, pool))]
+pub async fn get_public_newsletters(
+    page: &Pagination,
+    pool: &PgPool,
+) -> Result<(Vec<ArchivedNewsletterSummary>, i64), anyhow::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT COUNT(*) OVER() AS "total_count!", id AS "id!", title AS "title!",
+               published_at AS "published_at!"
+        FROM public_newsletters
+        WHERE published_at <= NOW()
+        ORDER BY published_at DESC, id
+        LIMIT $1 OFFSET $2
+        "#,
+        page.limit.value() as i64,
+        page.offset() as i64
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch public newsletter issues")?;
+
+    let total_count = rows.first().map(|r| r.total_count).unwrap_or(0);
+    let issues = rows
+        .into_iter()
+        .map(|r| ArchivedNewsletterSummary {
+            id: r.id,
+            title: r.title,
+            published_at: r.published_at,
+        })
+        .collect();
+
+    Ok((issues, total_count))
+}
+
+pub async fn get_public_newsletter(
+    pool: &PgPool,
+    issue_id: Uuid,
+) -> Result<Option<ArchivedNewsletter>, anyhow::Error> {
+    sqlx::query_as!(
+        ArchivedNewsletter,
+        r#"
+        SELECT id AS "id!", title AS "title!", html_content AS "html_content!",
+               published_at AS "published_at!"
+        FROM public_newsletters
+        WHERE id = $1 AND published_at <= NOW()
+        "#,
+        issue_id
+    )
+    .fetch_optional(pool)
+    .await
+    .context("Failed to fetch public newsletter issue")
+}
+
+const DELIVERY_WORKER: &str = "newsletter_delivery";
+
+pub async fn is_delivery_paused(pool: &PgPool) -> Result<bool, anyhow::Error> {
+    let is_paused = sqlx::query_scalar!(
+        r#"
+        SELECT is_paused
+        FROM worker_controls
+        WHERE name = $1
+        "#,
+        DELIVERY_WORKER
+    )
+    .fetch_optional(pool)
+    .await
+    .context("Failed to read newsletter delivery pause flag")?
+    .unwrap_or(false);
+
+    Ok(is_paused)
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn set_delivery_paused(pool: &PgPool, is_paused: bool) -> Result<(), anyhow::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO worker_controls (name, is_paused, updated_at)
+        VALUES ($1, $2, NOW())
+        ON CONFLICT (name) DO UPDATE
+        SET is_paused = EXCLUDED.is_paused, updated_at = EXCLUDED.updated_at
+        "#,
+        DELIVERY_WORKER,
+        is_paused
+    )
     .execute(pool)
-    .await?
-    .rows_affected();
+    .await
+    .context("Failed to update newsletter delivery pause flag")?;
 
-    tracing::info!(deleted, "Old newsletter issues cleanup completed");
     Ok(())
 }
+
+#[tracing::instrument(skip(pool))]
+pub async fn get_delivery_queue_stats(
+    pool: &PgPool,
+    max_retries: i32,
+) -> Result<DeliveryQueueStats, anyhow::Error> {
+    let totals = sqlx::query!(
+        r#"
+        SELECT
+            COUNT(*) AS "total!",
+            COUNT(*) FILTER (WHERE execute_after <= NOW()) AS "ready!",
+            COUNT(*) FILTER (WHERE n_retries > 0) AS "retrying!",
+            COUNT(*) FILTER (WHERE n_retries >= $1) AS "on_final_retry!"
+        FROM issue_delivery_queue
+        "#,
+        max_retries
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to count delivery queue tasks")?;
+
+    let dead_lettered =
+        sqlx::query_scalar!(r#"SELECT COUNT(*) AS "count!" FROM issue_delivery_failures"#)
+            .fetch_one(pool)
+            .await
+            .context("Failed to count dead-lettered deliveries")?;
+
+    let retries_distribution = sqlx::query!(
+        r#"
+        SELECT n_retries, COUNT(*) AS "count!"
+        FROM issue_delivery_queue
+        GROUP BY n_retries
+        ORDER BY n_retries
+        "#
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to compute delivery retries distribution")?
+    .into_iter()
+    .map(|r| (r.n_retries, r.count))
+    .collect();
+
+    let pending_issues = sqlx::query!(
+        r#"
+        SELECT n.title, COUNT(*) AS "pending!"
+        FROM issue_delivery_queue q
+        INNER JOIN newsletter_issues n ON q.newsletter_issue_id = n.id
+        GROUP BY n.id, n.title
+        ORDER BY MIN(n.created_at)
+        LIMIT 10
+        "#
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to count pending tasks per newsletter issue")?
+    .into_iter()
+    .map(|r| PendingIssue {
+        title: r.title,
+        pending: r.pending,
+    })
+    .collect();
+
+    let recent_errors = sqlx::query!(
+        r#"
+        SELECT newsletter_issue_id, user_email, n_retries, last_error AS "last_error!", last_attempted_at
+        FROM issue_delivery_queue
+        WHERE last_error IS NOT NULL
+        ORDER BY last_attempted_at DESC NULLS LAST
+        LIMIT 20
+        "#
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch recent delivery errors")?
+    .into_iter()
+    .map(|r| FailedDelivery {
+        newsletter_issue_id: r.newsletter_issue_id,
+        user_email: r.user_email,
+        n_retries: r.n_retries,
+        last_error: r.last_error,
+        last_attempted_at: r.last_attempted_at,
+    })
+    .collect();
+
+    Ok(DeliveryQueueStats {
+        total: totals.total,
+        ready: totals.ready,
+        retrying: totals.retrying,
+        on_final_retry: totals.on_final_retry,
+        max_retries,
+        dead_lettered,
+        retries_distribution,
+        pending_issues,
+        recent_errors,
+        is_paused: is_delivery_paused(pool).await?,
+    })
+}
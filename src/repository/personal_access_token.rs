@@ -0,0 +1,160 @@
+use std::ops::DerefMut;
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::domain::{
+    NewPersonalAccessToken, PersonalAccessTokenOwner, PersonalAccessTokenSummary, Role, TokenScope,
+};
+
+fn parse_scopes(scopes: Vec<String>) -> Result<Vec<TokenScope>, anyhow::Error> {
+    scopes
+        .iter()
+        .map(|scope| TokenScope::parse(scope).map_err(anyhow::Error::msg))
+        .collect::<Result<_, _>>()
+        .context("Stored token scopes are invalid")
+}
+
+#[tracing::instrument(skip(token, token_hash, pool))]
+pub async fn insert_personal_access_token(
+    user_id: Uuid,
+    token: &NewPersonalAccessToken,
+    token_hash: &str,
+    pool: &PgPool,
+) -> Result<(Uuid, DateTime<Utc>), anyhow::Error> {
+    let scopes: Vec<String> = token
+        .scopes
+        .iter()
+        .map(|s| s.as_str().to_string())
+        .collect();
+    let row = sqlx::query!(
+        r#"
+        INSERT INTO personal_access_tokens (id, user_id, name, token_hash, scopes)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id, created_at
+        "#,
+        Uuid::new_v4(),
+        user_id,
+        token.name,
+        token_hash,
+        &scopes
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to store a personal access token")?;
+
+    Ok((row.id, row.created_at))
+}
+
+// Tokens that have not been revoked, newest first
+#[tracing::instrument(skip(pool))]
+pub async fn get_personal_access_tokens(
+    user_id: Uuid,
+    pool: &PgPool,
+) -> Result<Vec<PersonalAccessTokenSummary>, anyhow::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT id, name, scopes, created_at, last_used_at
+        FROM personal_access_tokens
+        WHERE user_id = $1 AND revoked_at IS NULL
+        ORDER BY created_at DESC, id
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch personal access tokens")?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(PersonalAccessTokenSummary {
+                id: row.id,
+                name: row.name,
+                scopes: parse_scopes(row.scopes)?,
+                created_at: row.created_at,
+                last_used_at: row.last_used_at,
+            })
+        })
+        .collect()
+}
+
+// Returns false when the user has no such active token
+#[tracing::instrument(skip(pool))]
+pub async fn revoke_personal_access_token(
+    user_id: Uuid,
+    token_id: Uuid,
+    pool: &PgPool,
+) -> Result<bool, anyhow::Error> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE personal_access_tokens
+        SET revoked_at = NOW()
+        WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL
+        "#,
+        token_id,
+        user_id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to revoke a personal access token")?;
+
+    Ok(result.rows_affected() == 1)
+}
+
+#[tracing::instrument(skip(transaction))]
+pub async fn revoke_personal_access_tokens_of_user(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+) -> Result<u64, anyhow::Error> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE personal_access_tokens
+        SET revoked_at = NOW()
+        WHERE user_id = $1 AND revoked_at IS NULL
+        "#,
+        user_id
+    )
+    .execute(transaction.deref_mut())
+    .await
+    .context("Failed to revoke the personal access tokens of a user")?;
+
+    Ok(result.rows_affected())
+}
+
+// Resolves a presented token to its owner and records the use. Tokens of banned, deleted or
+// deactivated accounts resolve to nothing, as their sessions would.
+#[tracing::instrument(skip_all)]
+pub async fn use_personal_access_token(
+    token_hash: &str,
+    pool: &PgPool,
+) -> Result<Option<PersonalAccessTokenOwner>, anyhow::Error> {
+    let row = sqlx::query!(
+        r#"
+        UPDATE personal_access_tokens t
+        SET last_used_at = NOW()
+        FROM users u
+        WHERE t.token_hash = $1
+          AND t.revoked_at IS NULL
+          AND u.id = t.user_id
+          AND u.is_activated = true
+          AND (u.banned_at IS NULL OR u.banned_until <= NOW())
+          AND u.deleted_at IS NULL
+        RETURNING t.user_id, t.scopes, u.role AS "role: Role"
+        "#,
+        token_hash
+    )
+    .fetch_optional(pool)
+    .await
+    .context("Failed to look up a personal access token")?;
+
+    row.map(|row| {
+        Ok(PersonalAccessTokenOwner {
+            user_id: row.user_id,
+            role: row.role,
+            scopes: parse_scopes(row.scopes)?,
+        })
+    })
+    .transpose()
+}
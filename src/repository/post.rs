@@ -1,5 +1,6 @@
 use anyhow::Context;
 use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
 use sqlx::PgPool;
 use tracing::Span;
 use uuid::Uuid;
@@ -7,8 +8,9 @@ use uuid::Uuid;
 use crate::{
     authentication::UserId,
     domain::{
-        CreatedBy, Filters, PostImg, PostRecord, PostResponse, PostText, PostTitle, QueryTitle,
-        SortDirection,
+        CreatedBy, DigestPost, ExportedLike, ExportedPost, Filters, PostBodyStats, PostExcerpt,
+        PostImg, PostLicense, PostRecord, PostResponse, PostSummaryRecord, PostSummaryResponse,
+        PostText, PostTitle, QueryTitle, SortDirection,
     },
     routes::PostError,
 };
@@ -17,9 +19,10 @@ use crate::{
 pub async fn get_all_posts(
     title: Option<&QueryTitle>,
     created_by_id: Option<&CreatedBy>,
+    license: Option<PostLicense>,
     filters: &Filters,
     pool: &PgPool,
-) -> Result<(Vec<PostResponse>, i64), PostError> {
+) -> Result<(Vec<PostSummaryResponse>, i64), PostError> {
     let title_search = title.map(|t| t.as_ref().to_string()).unwrap_or_default();
     let offset = filters.offset() as i64;
     let limit = filters.limit.value() as i64;
@@ -29,25 +32,33 @@ pub async fn get_all_posts(
     let (where_clause, params_count) = if created_by_id.is_some() {
         (
             "WHERE (to_tsvector('english', title) @@ plainto_tsquery('english', $1) OR $1 = '')
-        AND p.created_by = $2
+        AND ($2::TEXT IS NULL OR p.license = $2)
+        AND p.created_by = $3
         AND p.deleted_at IS NULL",
-            2,
+            3,
         )
     } else {
         (
             "WHERE (to_tsvector('english', title) @@ plainto_tsquery('english', $1) OR $1 = '')
+        AND ($2::TEXT IS NULL OR p.license = $2)
         AND p.deleted_at IS NULL",
-            1,
+            2,
         )
     };
 
     let query = format!(
         r#"
         SELECT COUNT(*) OVER()::BIGINT AS total_count,
-               p.id, p.title, p.post_text, p.img, p.version,
-               p.liked_by, p.created_by, p.created_at, u.user_name as created_by_name
+               p.id, p.title, p.excerpt, p.img, p.version,
+               p.liked_by, cc.comment_count, p.created_by, p.created_at, p.license, p.is_community,
+               u.user_name as created_by_name, u.avatar_updated_at AS created_by_avatar_updated_at
         FROM posts p
         INNER JOIN users u ON p.created_by = u.id
+        LEFT JOIN LATERAL (
+            SELECT COUNT(*) AS comment_count
+            FROM comments c
+            WHERE c.post_id = p.id AND c.deleted_at IS NULL
+        ) cc ON TRUE
         {}
         ORDER BY {}, p.created_at {}
         LIMIT ${} OFFSET ${}
@@ -62,7 +73,9 @@ pub async fn get_all_posts(
         params_count + 2
     );
 
-    let mut query_builder = sqlx::query_as::<_, PostRecord>(&query).bind(&title_search);
+    let mut query_builder = sqlx::query_as::<_, PostSummaryRecord>(&query)
+        .bind(&title_search)
+        .bind(license.map(|l| l.as_str()));
 
     if let Some(creator_id) = created_by_id {
         query_builder = query_builder.bind(creator_id.as_ref());
@@ -77,7 +90,7 @@ pub async fn get_all_posts(
 
     let total_count = records.first().map(|r| r.total_count).unwrap_or(0);
 
-    let posts = records.into_iter().map(PostResponse::from).collect();
+    let posts = records.into_iter().map(PostSummaryResponse::from).collect();
 
     Ok((posts, total_count))
 }
@@ -85,10 +98,16 @@ pub async fn get_all_posts(
 pub async fn get_post(id: Uuid, pool: &PgPool) -> Result<PostResponse, PostError> {
     let record = sqlx::query_as::<_, PostRecord>(
         r#"
-        SELECT 0::BIGINT as total_count, p.id, p.title, p.post_text, p.img, p.version, p.liked_by, p.created_by, p.created_at, u.user_name as created_by_name
+        SELECT 0::BIGINT as total_count, p.id, p.title, COALESCE(b.body, p.post_text) AS post_text, p.excerpt, p.img, p.version, p.liked_by, cc.comment_count, p.created_by, p.created_at, p.license, p.is_community, u.user_name as created_by_name, u.avatar_updated_at AS created_by_avatar_updated_at
         FROM posts p
         INNER JOIN users u ON p.created_by = u.id
-        WHERE p.id = $1 AND deleted_at IS NULL
+        LEFT JOIN post_bodies b ON b.hash = p.body_hash
+        LEFT JOIN LATERAL (
+            SELECT COUNT(*) AS comment_count
+            FROM comments c
+            WHERE c.post_id = p.id AND c.deleted_at IS NULL
+        ) cc ON TRUE
+        WHERE p.id = $1 AND p.deleted_at IS NULL
         "#,
     )
         .bind(id)
@@ -102,6 +121,84 @@ pub async fn get_post(id: Uuid, pool: &PgPool) -> Result<PostResponse, PostError
     }
 }
 
+// Bodies at least this large are stored once in `post_bodies` and shared by identical posts
+pub const LARGE_POST_BODY_BYTES: usize = 4 * 1024;
+
+// Returns what to keep in `posts.post_text` along with the hash of the shared body, if any.
+// Large bodies are left inline as an empty string and read back through `post_bodies`.
+async fn store_post_body<'a>(
+    text: &'a PostText,
+    pool: &PgPool,
+) -> Result<(&'a str, Option<String>), anyhow::Error> {
+    let body = text.as_ref();
+    if body.len() < LARGE_POST_BODY_BYTES {
+        return Ok((body, None));
+    }
+
+    let hash = format!("{:x}", Sha256::digest(body.as_bytes()));
+    sqlx::query!(
+        r#"
+        INSERT INTO post_bodies (hash, body)
+        VALUES ($1, $2)
+        ON CONFLICT (hash) DO UPDATE SET touched_at = NOW()
+        "#,
+        hash,
+        body
+    )
+    .execute(pool)
+    .await
+    .context("Failed to store post body")?;
+
+    Ok(("", Some(hash)))
+}
+
+// Bodies no post points at any more, left behind by edits and hard deletes. The grace period
+// keeps a body that is being reused by an in-flight insert or update from being pruned under it.
+#[tracing::instrument(skip(pool))]
+pub async fn cleanup_orphaned_post_bodies(pool: &PgPool) -> Result<(), anyhow::Error> {
+    let deleted = sqlx::query!(
+        r#"
+        DELETE FROM post_bodies b
+        WHERE b.touched_at < NOW() - INTERVAL '1 hour'
+        AND NOT EXISTS (SELECT 1 FROM posts p WHERE p.body_hash = b.hash)
+        "#
+    )
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    tracing::info!(deleted, "Orphaned post body cleanup completed");
+    Ok(())
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn get_post_body_stats(pool: &PgPool) -> Result<PostBodyStats, anyhow::Error> {
+    let row = sqlx::query!(
+        r#"
+        SELECT
+            (SELECT COUNT(*) FROM post_bodies) AS "stored_bodies!",
+            (SELECT COALESCE(SUM(octet_length(body)), 0) FROM post_bodies)::BIGINT AS "stored_bytes!",
+            COUNT(*) AS "posts_using_stored_bodies!",
+            COALESCE(SUM(octet_length(b.body)), 0)::BIGINT AS "logical_bytes!"
+        FROM posts p
+        INNER JOIN post_bodies b ON b.hash = p.body_hash
+        "#
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to fetch post body statistics")?;
+
+    Ok(PostBodyStats {
+        stored_bodies: row.stored_bodies,
+        stored_bytes: row.stored_bytes,
+        posts_using_stored_bodies: row.posts_using_stored_bodies,
+        logical_bytes: row.logical_bytes,
+        // Orphans awaiting cleanup can briefly make storage exceed what posts reference
+        saved_bytes: (row.logical_bytes - row.stored_bytes).max(0),
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
 #[tracing::instrument(
     skip_all,
     fields(post_id=tracing::field::Empty)
@@ -109,20 +206,28 @@ pub async fn get_post(id: Uuid, pool: &PgPool) -> Result<PostResponse, PostError
 pub async fn insert_post(
     title: &PostTitle,
     text: &PostText,
+    excerpt: &PostExcerpt,
     img: &PostImg,
+    license: PostLicense,
+    is_community: bool,
     created_by: UserId,
     pool: &PgPool,
 ) -> Result<(Uuid, DateTime<Utc>), anyhow::Error> {
+    let (post_text, body_hash) = store_post_body(text, pool).await?;
     let record = sqlx::query!(
         r#"
-        INSERT INTO posts (id, title, post_text, img, created_by)
-        VALUES ($1, $2, $3, $4, $5)
+        INSERT INTO posts (id, title, post_text, body_hash, excerpt, img, license, is_community, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
         RETURNING id, created_at
         "#,
         Uuid::new_v4(),
         title.as_ref(),
-        text.as_ref(),
+        post_text,
+        body_hash,
+        excerpt.as_ref(),
         img.as_ref(),
+        license.as_str(),
+        is_community,
         *created_by,
     )
     .fetch_one(pool)
@@ -132,24 +237,32 @@ pub async fn insert_post(
     Ok((record.id, record.created_at))
 }
 
+#[allow(clippy::too_many_arguments)]
 #[tracing::instrument(skip_all, fields(post_id=%id))]
 pub async fn update_post(
     id: Uuid,
     title: &PostTitle,
     text: &PostText,
+    excerpt: &PostExcerpt,
     img: &PostImg,
+    license: Option<PostLicense>,
     version: i32,
     pool: &PgPool,
 ) -> Result<(), PostError> {
+    let (post_text, body_hash) = store_post_body(text, pool).await?;
     let result = sqlx::query!(
         r#"
         UPDATE posts
-        SET title = $1, post_text = $2, img = $3, version = version + 1
-        WHERE id = $4 AND version = $5
+        SET title = $1, post_text = $2, body_hash = $3, excerpt = $4, img = $5,
+            license = COALESCE($6, license), version = version + 1
+        WHERE id = $7 AND version = $8
         "#,
         title.as_ref(),
-        text.as_ref(),
+        post_text,
+        body_hash,
+        excerpt.as_ref(),
         img.as_ref(),
+        license.map(|l| l.as_str()),
         id,
         version
     )
@@ -183,13 +296,26 @@ pub async fn soft_delete_post(post_id: Uuid, pool: &PgPool) -> Result<bool, anyh
 }
 
 #[tracing::instrument(skip(pool))]
-pub async fn hard_delete_post(post_id: Uuid, pool: &PgPool) -> Result<bool, anyhow::Error> {
+// Recorded in the audit log along with the post's title and author, which are gone afterwards
+pub async fn hard_delete_post(
+    post_id: Uuid,
+    admin_id: Uuid,
+    pool: &PgPool,
+) -> Result<bool, anyhow::Error> {
     let result = sqlx::query!(
         r#"
-        DELETE FROM posts
-        WHERE id = $1
+        WITH deleted AS (
+            DELETE FROM posts
+            WHERE id = $1
+            RETURNING id, title, created_by
+        )
+        INSERT INTO audit_log (action, actor_id, target_id, metadata)
+        SELECT 'post_hard_delete', $2, id,
+               jsonb_build_object('title', title, 'author_id', created_by)
+        FROM deleted
         "#,
-        post_id
+        post_id,
+        admin_id
     )
     .execute(pool)
     .await
@@ -281,3 +407,143 @@ pub async fn did_user_create_the_post(
 
     Ok(result)
 }
+
+#[tracing::instrument(skip(pool))]
+pub async fn is_blocked_by_post_author(
+    post_id: Uuid,
+    user_id: Uuid,
+    pool: &PgPool,
+) -> Result<bool, anyhow::Error> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS(
+            SELECT 1
+            FROM posts p
+            INNER JOIN user_blocks b ON b.blocker_id = p.created_by
+            WHERE p.id = $1 AND b.blocked_id = $2
+        ) AS "exists!"
+        "#,
+        post_id,
+        user_id
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to check if the post author blocked the user")
+}
+
+// Keyset pagination on (created_at, id) keeps each batch cheap regardless of how far into the export we are
+#[tracing::instrument(skip(pool))]
+pub async fn get_user_posts_for_export(
+    user_id: Uuid,
+    after: Option<(DateTime<Utc>, Uuid)>,
+    limit: i64,
+    pool: &PgPool,
+) -> Result<Vec<ExportedPost>, anyhow::Error> {
+    let (after_created_at, after_id) = after.unzip();
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT p.id, p.title, COALESCE(b.body, p.post_text) AS "post_text!", p.img, p.created_at,
+               COALESCE(ARRAY_LENGTH(p.liked_by, 1), 0) AS "likes_count!"
+        FROM posts p
+        LEFT JOIN post_bodies b ON b.hash = p.body_hash
+        WHERE p.created_by = $1
+        AND p.deleted_at IS NULL
+        AND ($2::TIMESTAMPTZ IS NULL OR (p.created_at, p.id) > ($2, $3::UUID))
+        ORDER BY p.created_at, p.id
+        LIMIT $4
+        "#,
+        user_id,
+        after_created_at,
+        after_id,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch posts for export")?;
+
+    let posts = rows
+        .into_iter()
+        .map(|r| ExportedPost {
+            id: r.id,
+            title: r.title,
+            text: r.post_text,
+            img: r.img,
+            likes_count: r.likes_count as usize,
+            created_at: r.created_at,
+            comments: None,
+        })
+        .collect();
+
+    Ok(posts)
+}
+
+// Posts the user liked, ordered by id since likes carry no timestamp of their own
+#[tracing::instrument(skip(pool))]
+pub async fn get_liked_posts_for_export(
+    user_id: Uuid,
+    after: Option<Uuid>,
+    limit: i64,
+    pool: &PgPool,
+) -> Result<Vec<ExportedLike>, anyhow::Error> {
+    let likes = sqlx::query_as!(
+        ExportedLike,
+        r#"
+        SELECT id AS post_id, title AS post_title
+        FROM posts
+        WHERE $1 = ANY(liked_by)
+        AND deleted_at IS NULL
+        AND ($2::UUID IS NULL OR id > $2)
+        ORDER BY id
+        LIMIT $3
+        "#,
+        user_id,
+        after,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch liked posts for export")?;
+
+    Ok(likes)
+}
+
+// Posts published in the window, ranked by likes and comments together. Likes carry no
+// timestamp, so a post's engagement counts in full whenever it came in. Posts nobody engaged
+// with are left out.
+#[tracing::instrument(skip(pool))]
+pub async fn get_top_posts_for_digest(
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+    limit: i64,
+    pool: &PgPool,
+) -> Result<Vec<DigestPost>, anyhow::Error> {
+    let posts = sqlx::query_as!(
+        DigestPost,
+        r#"
+        SELECT p.id, p.title, p.excerpt, u.user_name AS author_name,
+               COALESCE(CARDINALITY(p.liked_by), 0)::BIGINT AS "likes!",
+               cc.comment_count AS "comments!"
+        FROM posts p
+        INNER JOIN users u ON p.created_by = u.id
+        CROSS JOIN LATERAL (
+            SELECT COUNT(*) AS comment_count
+            FROM comments c
+            WHERE c.post_id = p.id AND c.deleted_at IS NULL
+        ) cc
+        WHERE p.created_at >= $1 AND p.created_at < $2
+        AND p.deleted_at IS NULL
+        AND (CARDINALITY(p.liked_by) > 0 OR cc.comment_count > 0)
+        ORDER BY CARDINALITY(p.liked_by) + cc.comment_count DESC, p.created_at DESC
+        LIMIT $3
+        "#,
+        since,
+        until,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch top posts for the newsletter digest")?;
+
+    Ok(posts)
+}
@@ -0,0 +1,102 @@
+use anyhow::Context;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::{EditProposalResponseBody, PendingEditProposal, Post, ProposalReview};
+
+#[tracing::instrument(skip(proposal, pool))]
+pub async fn insert_edit_proposal(
+    post_id: Uuid,
+    proposed_by: Uuid,
+    proposal: &Post,
+    base_version: i32,
+    pool: &PgPool,
+) -> Result<Uuid, anyhow::Error> {
+    sqlx::query_scalar!(
+        r#"
+        INSERT INTO post_edit_proposals (id, post_id, proposed_by, title, post_text, img, base_version)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING id
+        "#,
+        Uuid::new_v4(),
+        post_id,
+        proposed_by,
+        proposal.title.as_ref(),
+        proposal.text.as_ref(),
+        proposal.img.as_ref(),
+        base_version
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to insert edit proposal")
+}
+
+// Oldest first, so proposals are reviewed in the order they were written
+#[tracing::instrument(skip(pool))]
+pub async fn get_pending_edit_proposals(
+    post_id: Uuid,
+    pool: &PgPool,
+) -> Result<Vec<EditProposalResponseBody>, anyhow::Error> {
+    let proposals = sqlx::query_as!(
+        EditProposalResponseBody,
+        r#"
+        SELECT ep.id, ep.post_id, ep.proposed_by, u.user_name AS proposed_by_name, ep.title,
+               ep.post_text AS text, ep.img, ep.base_version,
+               ep.base_version <> p.version AS "is_conflicting!", ep.created_at
+        FROM post_edit_proposals ep
+        INNER JOIN posts p ON ep.post_id = p.id
+        INNER JOIN users u ON ep.proposed_by = u.id
+        WHERE ep.post_id = $1 AND ep.status = 'pending'
+        ORDER BY ep.created_at ASC, ep.id ASC
+        "#,
+        post_id
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to load pending edit proposals")?;
+
+    Ok(proposals)
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn get_pending_edit_proposal(
+    proposal_id: Uuid,
+    pool: &PgPool,
+) -> Result<Option<PendingEditProposal>, anyhow::Error> {
+    sqlx::query_as!(
+        PendingEditProposal,
+        r#"
+        SELECT ep.post_id, ep.title, ep.post_text, ep.img, ep.base_version
+        FROM post_edit_proposals ep
+        INNER JOIN posts p ON ep.post_id = p.id
+        WHERE ep.id = $1 AND ep.status = 'pending' AND p.deleted_at IS NULL
+        "#,
+        proposal_id
+    )
+    .fetch_optional(pool)
+    .await
+    .context("Failed to load edit proposal")
+}
+
+// Returns false when the proposal was already reviewed
+#[tracing::instrument(skip(pool))]
+pub async fn review_edit_proposal(
+    proposal_id: Uuid,
+    review: ProposalReview,
+    pool: &PgPool,
+) -> Result<bool, anyhow::Error> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE post_edit_proposals
+        SET status = $2, reviewed_at = NOW()
+        WHERE id = $1 AND status = 'pending'
+        "#,
+        proposal_id,
+        review.as_str()
+    )
+    .execute(pool)
+    .await
+    .context("Failed to review edit proposal")?;
+
+    Ok(result.rows_affected() > 0)
+}
@@ -0,0 +1,131 @@
+use std::ops::DerefMut;
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::domain::{DeviceMetadata, Role, StoredRefreshToken};
+
+#[tracing::instrument(skip(transaction, token_hash))]
+pub async fn insert_refresh_token(
+    transaction: &mut Transaction<'_, Postgres>,
+    family_id: Uuid,
+    user_id: Uuid,
+    token_hash: &str,
+    device: &DeviceMetadata,
+    expires_at: DateTime<Utc>,
+) -> Result<(), anyhow::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO refresh_tokens
+            (id, family_id, user_id, token_hash, user_agent, ip_address, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+        Uuid::new_v4(),
+        family_id,
+        user_id,
+        token_hash,
+        device.user_agent,
+        device.ip_address,
+        expires_at
+    )
+    .execute(transaction.deref_mut())
+    .await
+    .context("Failed to store a refresh token")?;
+
+    Ok(())
+}
+
+// Locks the token until the transaction ends, so two refreshes with the same token cannot both
+// rotate it. Tokens of banned, deleted or deactivated accounts are not found.
+#[tracing::instrument(skip_all)]
+pub async fn get_refresh_token_for_update(
+    transaction: &mut Transaction<'_, Postgres>,
+    token_hash: &str,
+) -> Result<Option<StoredRefreshToken>, anyhow::Error> {
+    let token = sqlx::query_as!(
+        StoredRefreshToken,
+        r#"
+        SELECT
+            t.id,
+            t.family_id,
+            t.user_id,
+            u.role AS "role: Role",
+            t.expires_at,
+            t.rotated_at,
+            t.revoked_at
+        FROM refresh_tokens t
+        JOIN users u ON u.id = t.user_id
+        WHERE t.token_hash = $1
+          AND u.is_activated = true
+          AND (u.banned_at IS NULL OR u.banned_until <= NOW())
+          AND u.deleted_at IS NULL
+        FOR UPDATE OF t
+        "#,
+        token_hash
+    )
+    .fetch_optional(transaction.deref_mut())
+    .await
+    .context("Failed to look up a refresh token")?;
+
+    Ok(token)
+}
+
+#[tracing::instrument(skip(transaction))]
+pub async fn mark_refresh_token_rotated(
+    transaction: &mut Transaction<'_, Postgres>,
+    token_id: Uuid,
+) -> Result<(), anyhow::Error> {
+    sqlx::query!(
+        r#"UPDATE refresh_tokens SET rotated_at = NOW() WHERE id = $1"#,
+        token_id
+    )
+    .execute(transaction.deref_mut())
+    .await
+    .context("Failed to mark a refresh token as rotated")?;
+
+    Ok(())
+}
+
+// Ends the sign-in the family belongs to, returning how many tokens were still live
+#[tracing::instrument(skip(transaction))]
+pub async fn revoke_refresh_token_family(
+    transaction: &mut Transaction<'_, Postgres>,
+    family_id: Uuid,
+) -> Result<u64, anyhow::Error> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE refresh_tokens
+        SET revoked_at = NOW()
+        WHERE family_id = $1 AND revoked_at IS NULL
+        "#,
+        family_id
+    )
+    .execute(transaction.deref_mut())
+    .await
+    .context("Failed to revoke a refresh token family")?;
+
+    Ok(result.rows_affected())
+}
+
+// Ends every sign-in of a bearer-token client of the user
+#[tracing::instrument(skip(transaction))]
+pub async fn revoke_refresh_tokens_of_user(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+) -> Result<u64, anyhow::Error> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE refresh_tokens
+        SET revoked_at = NOW()
+        WHERE user_id = $1 AND revoked_at IS NULL
+        "#,
+        user_id
+    )
+    .execute(transaction.deref_mut())
+    .await
+    .context("Failed to revoke the refresh tokens of a user")?;
+
+    Ok(result.rows_affected())
+}
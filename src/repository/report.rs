@@ -0,0 +1,104 @@
+use anyhow::Context;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    domain::{CommentReportResponseBody, Pagination, ReportReason},
+    routes::ReportError,
+};
+
+#[tracing::instrument(skip(pool, reason))]
+pub async fn insert_comment_report(
+    comment_id: Uuid,
+    reporter_id: Uuid,
+    reason: &ReportReason,
+    pool: &PgPool,
+) -> Result<Uuid, ReportError> {
+    let report_id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO reports (id, comment_id, reporter_id, reason)
+        SELECT $1, c.id, $3, $4
+        FROM comments c
+        WHERE c.id = $2 AND c.deleted_at IS NULL
+        ON CONFLICT (comment_id, reporter_id) WHERE comment_id IS NOT NULL DO NOTHING
+        RETURNING id
+        "#,
+        Uuid::new_v4(),
+        comment_id,
+        reporter_id,
+        reason.as_ref()
+    )
+    .fetch_optional(pool)
+    .await
+    .context("Failed to insert comment report")?;
+
+    if let Some(report_id) = report_id {
+        return Ok(report_id);
+    }
+
+    // Nothing was inserted, either because the comment is gone or it was already reported
+    let comment_exists = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM comments WHERE id = $1 AND deleted_at IS NULL
+        ) AS "exists!"
+        "#,
+        comment_id
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to check if comment exists")?;
+
+    if comment_exists {
+        Err(ReportError::AlreadyReported)
+    } else {
+        Err(ReportError::NotFound)
+    }
+}
+
+// Reports of comments that are still visible, newest first
+#[tracing::instrument(skip(pool, page))]
+pub async fn get_comment_reports(
+    page: &Pagination,
+    pool: &PgPool,
+) -> Result<(Vec<CommentReportResponseBody>, i64), anyhow::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT COUNT(*) OVER() AS "total_count!",
+               r.id, r.reason, r.created_at, r.reporter_id, reporter.user_name AS reporter_name,
+               c.id AS comment_id, c.text AS comment_text, c.created_by AS comment_author_id,
+               p.id AS post_id, p.title AS post_title
+        FROM reports r
+        INNER JOIN comments c ON r.comment_id = c.id
+        INNER JOIN posts p ON c.post_id = p.id
+        INNER JOIN users reporter ON r.reporter_id = reporter.id
+        WHERE c.deleted_at IS NULL
+        ORDER BY r.created_at DESC, r.id DESC
+        LIMIT $1 OFFSET $2
+        "#,
+        page.limit.value() as i64,
+        page.offset() as i64
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to load comment reports")?;
+
+    let total_count = rows.first().map(|r| r.total_count).unwrap_or(0);
+    let reports = rows
+        .into_iter()
+        .map(|r| CommentReportResponseBody {
+            id: r.id,
+            comment_id: r.comment_id,
+            comment_text: r.comment_text,
+            comment_author_id: r.comment_author_id,
+            post_id: r.post_id,
+            post_title: r.post_title,
+            reporter_id: r.reporter_id,
+            reporter_name: r.reporter_name,
+            reason: r.reason,
+            created_at: r.created_at,
+        })
+        .collect();
+
+    Ok((reports, total_count))
+}
@@ -0,0 +1,74 @@
+use anyhow::Context;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::{NewsletterSegment, NewsletterSegmentSummary};
+
+const SEGMENT_NAME_UNIQUE_CONSTRAINT: &str = "newsletter_segments_name_key";
+
+// None when another segment already has the name
+#[tracing::instrument(skip(pool))]
+pub async fn insert_newsletter_segment(
+    pool: &PgPool,
+    segment: &NewsletterSegment,
+) -> Result<Option<Uuid>, anyhow::Error> {
+    let segment_id = Uuid::new_v4();
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO newsletter_segments (
+        id,
+        name,
+        signed_up_after,
+        signed_up_before,
+        active_within_days
+        )
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+        segment_id,
+        segment.name,
+        segment.signed_up_after,
+        segment.signed_up_before,
+        segment.active_within_days
+    )
+    .execute(pool)
+    .await;
+
+    match result {
+        Ok(_) => Ok(Some(segment_id)),
+        Err(sqlx::Error::Database(e)) if e.constraint() == Some(SEGMENT_NAME_UNIQUE_CONSTRAINT) => {
+            Ok(None)
+        }
+        Err(e) => Err(e).context("Failed to store newsletter segment"),
+    }
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn list_newsletter_segments(
+    pool: &PgPool,
+) -> Result<Vec<NewsletterSegmentSummary>, anyhow::Error> {
+    sqlx::query_as!(
+        NewsletterSegmentSummary,
+        r#"
+        SELECT id, name, signed_up_after, signed_up_before, active_within_days, created_at
+        FROM newsletter_segments
+        ORDER BY name
+        "#
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to list newsletter segments")
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn newsletter_segment_exists(
+    pool: &PgPool,
+    segment_id: Uuid,
+) -> Result<bool, anyhow::Error> {
+    sqlx::query_scalar!(
+        r#"SELECT EXISTS (SELECT 1 FROM newsletter_segments WHERE id = $1) AS "exists!""#,
+        segment_id
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to check whether newsletter segment exists")
+}
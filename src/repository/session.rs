@@ -0,0 +1,235 @@
+use std::ops::DerefMut;
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::{
+    configuration::SessionSettings,
+    domain::{DeviceMetadata, RememberedSession, Role, SessionSummary},
+};
+
+// Remembered sessions last a fixed time from login, others until idle or absolute timeout
+struct Cutoffs {
+    last_seen: DateTime<Utc>,
+    created: DateTime<Utc>,
+    remembered_created: DateTime<Utc>,
+}
+
+impl Cutoffs {
+    fn new(settings: &SessionSettings) -> Self {
+        let now = Utc::now();
+        Self {
+            last_seen: now - settings.idle_timeout(),
+            created: now - settings.absolute_timeout(),
+            remembered_created: now - settings.remember_me_lifetime(),
+        }
+    }
+}
+
+#[tracing::instrument(skip(remember_token_hash, pool))]
+pub async fn insert_session(
+    user_id: Uuid,
+    device: &DeviceMetadata,
+    remember_token_hash: Option<&str>,
+    pool: &PgPool,
+) -> Result<Uuid, anyhow::Error> {
+    let session_id = Uuid::new_v4();
+    sqlx::query!(
+        r#"
+        INSERT INTO user_sessions (id, user_id, user_agent, ip_address, remember_token_hash)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+        session_id,
+        user_id,
+        device.user_agent,
+        device.ip_address,
+        remember_token_hash
+    )
+    .execute(pool)
+    .await
+    .context("Failed to store a session")?;
+
+    Ok(session_id)
+}
+
+// Records a request made with the session, restarting its idle timeout. False if the session was
+// revoked, has timed out or its account was deleted.
+#[tracing::instrument(skip(settings, pool))]
+pub async fn touch_session(
+    session_id: Uuid,
+    user_id: Uuid,
+    settings: &SessionSettings,
+    pool: &PgPool,
+) -> Result<bool, anyhow::Error> {
+    let cutoffs = Cutoffs::new(settings);
+    let result = sqlx::query!(
+        r#"
+        UPDATE user_sessions
+        SET last_seen_at = NOW()
+        WHERE id = $1
+          AND user_id = $2
+          AND revoked_at IS NULL
+          AND CASE
+              WHEN remember_token_hash IS NULL THEN last_seen_at > $3 AND created_at > $4
+              ELSE created_at > $5
+          END
+          AND EXISTS (SELECT 1 FROM users WHERE id = $2 AND deleted_at IS NULL)
+        "#,
+        session_id,
+        user_id,
+        cutoffs.last_seen,
+        cutoffs.created,
+        cutoffs.remembered_created
+    )
+    .execute(pool)
+    .await
+    .context("Failed to record session activity")?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+// The session a remember-me token belongs to, if it is still active and its account in good
+// standing. Records the request like `touch_session`.
+#[tracing::instrument(skip_all)]
+pub async fn resume_remembered_session(
+    remember_token_hash: &str,
+    settings: &SessionSettings,
+    pool: &PgPool,
+) -> Result<Option<RememberedSession>, anyhow::Error> {
+    sqlx::query_as!(
+        RememberedSession,
+        r#"
+        UPDATE user_sessions s
+        SET last_seen_at = NOW()
+        FROM users u
+        WHERE s.remember_token_hash = $1
+          AND s.revoked_at IS NULL
+          AND s.created_at > $2
+          AND u.id = s.user_id
+          AND u.is_activated = true
+          AND (u.banned_at IS NULL OR u.banned_until <= NOW())
+          AND u.deleted_at IS NULL
+        RETURNING s.id AS session_id, s.user_id, u.role AS "role: Role"
+        "#,
+        remember_token_hash,
+        Cutoffs::new(settings).remembered_created
+    )
+    .fetch_optional(pool)
+    .await
+    .context("Failed to look up a remembered session")
+}
+
+// Sessions that are neither revoked nor timed out, most recently used first
+#[tracing::instrument(skip(settings, pool))]
+pub async fn get_active_sessions(
+    user_id: Uuid,
+    current_session_id: Option<Uuid>,
+    settings: &SessionSettings,
+    pool: &PgPool,
+) -> Result<Vec<SessionSummary>, anyhow::Error> {
+    let cutoffs = Cutoffs::new(settings);
+    sqlx::query_as!(
+        SessionSummary,
+        r#"
+        SELECT
+            id,
+            created_at,
+            last_seen_at,
+            ip_address,
+            user_agent,
+            remember_token_hash IS NOT NULL AS "remember_me!",
+            (id = $2) IS TRUE AS "current!"
+        FROM user_sessions
+        WHERE user_id = $1
+          AND revoked_at IS NULL
+          AND CASE
+              WHEN remember_token_hash IS NULL THEN last_seen_at > $3 AND created_at > $4
+              ELSE created_at > $5
+          END
+        ORDER BY last_seen_at DESC, id
+        "#,
+        user_id,
+        current_session_id,
+        cutoffs.last_seen,
+        cutoffs.created,
+        cutoffs.remembered_created
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch sessions")
+}
+
+// False if the user has no such active session
+#[tracing::instrument(skip(pool))]
+pub async fn revoke_session(
+    user_id: Uuid,
+    session_id: Uuid,
+    pool: &PgPool,
+) -> Result<bool, anyhow::Error> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE user_sessions
+        SET revoked_at = NOW()
+        WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL
+        "#,
+        session_id,
+        user_id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to revoke a session")?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+// Ends every session of the user but `except`, returning how many were still active
+#[tracing::instrument(skip(transaction))]
+pub async fn revoke_all_sessions(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    except: Option<Uuid>,
+) -> Result<u64, anyhow::Error> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE user_sessions
+        SET revoked_at = NOW()
+        WHERE user_id = $1 AND id IS DISTINCT FROM $2 AND revoked_at IS NULL
+        "#,
+        user_id,
+        except
+    )
+    .execute(transaction.deref_mut())
+    .await
+    .context("Failed to revoke the sessions of a user")?;
+
+    Ok(result.rows_affected())
+}
+
+// Revoked and timed out sessions are of no further use
+#[tracing::instrument(skip_all)]
+pub async fn cleanup_expired_sessions(
+    settings: &SessionSettings,
+    pool: &PgPool,
+) -> Result<(), anyhow::Error> {
+    let cutoffs = Cutoffs::new(settings);
+    sqlx::query!(
+        r#"
+        DELETE FROM user_sessions
+        WHERE revoked_at IS NOT NULL
+           OR CASE
+              WHEN remember_token_hash IS NULL THEN last_seen_at <= $1 OR created_at <= $2
+              ELSE created_at <= $3
+          END
+        "#,
+        cutoffs.last_seen,
+        cutoffs.created,
+        cutoffs.remembered_created
+    )
+    .execute(pool)
+    .await
+    .context("Failed to delete expired sessions")?;
+
+    Ok(())
+}
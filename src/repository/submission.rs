@@ -0,0 +1,220 @@
+use std::ops::DerefMut;
+
+use anyhow::Context;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        ANONYMOUS_USER_ID, CommentSubmission, CommentSubmissionResponseBody, Pagination,
+        SubmissionSource, UserName,
+    },
+    repository::{create_shell_user, find_user_id_by_email},
+    routes::SubmissionReviewError,
+};
+
+// Returns `None` when the post does not exist or has been deleted
+#[tracing::instrument(skip(submission, pool), fields(post_id=%submission.post_id))]
+pub async fn insert_comment_submission(
+    submission: &CommentSubmission,
+    origin: Option<&str>,
+    ip_address: &str,
+    pool: &PgPool,
+) -> Result<Option<Uuid>, anyhow::Error> {
+    sqlx::query_scalar!(
+        r#"
+        INSERT INTO comment_submissions (id, source, post_id, author_name, author_email, text, origin, ip_address)
+        SELECT $1, $2, p.id, $4, $5, $6, $7, $8
+        FROM posts p
+        WHERE p.id = $3 AND p.deleted_at IS NULL
+        RETURNING id
+        "#,
+        Uuid::new_v4(),
+        submission.source.as_str(),
+        submission.post_id,
+        submission.author_name.as_ref(),
+        submission.author_email.as_ref().map(|e| e.as_ref()),
+        submission.text.as_ref(),
+        origin,
+        ip_address
+    )
+    .fetch_optional(pool)
+    .await
+    .context("Failed to insert comment submission")
+}
+
+// Counts everything submitted from this address through one source in the last hour, whatever
+// its review outcome, so each source keeps its own limit
+#[tracing::instrument(skip(pool))]
+pub async fn count_recent_submissions(
+    source: SubmissionSource,
+    ip_address: &str,
+    pool: &PgPool,
+) -> Result<i64, anyhow::Error> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT COUNT(*) AS "count!"
+        FROM comment_submissions
+        WHERE source = $1 AND ip_address = $2 AND created_at > NOW() - INTERVAL '1 hour'
+        "#,
+        source.as_str(),
+        ip_address
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to count recent comment submissions")
+}
+
+// Submissions awaiting review, oldest first so the queue is worked through in order
+#[tracing::instrument(skip(pool, page))]
+pub async fn get_pending_submissions(
+    page: &Pagination,
+    pool: &PgPool,
+) -> Result<(Vec<CommentSubmissionResponseBody>, i64), anyhow::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT COUNT(*) OVER() AS "total_count!",
+               s.id, s.source, s.post_id, p.title AS post_title, s.author_name, s.author_email,
+               s.text, s.origin, s.ip_address, s.created_at
+        FROM comment_submissions s
+        INNER JOIN posts p ON s.post_id = p.id
+        WHERE s.status = 'pending' AND p.deleted_at IS NULL
+        ORDER BY s.created_at ASC, s.id ASC
+        LIMIT $1 OFFSET $2
+        "#,
+        page.limit.value() as i64,
+        page.offset() as i64
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to load pending comment submissions")?;
+
+    let total_count = rows.first().map(|r| r.total_count).unwrap_or(0);
+    let submissions = rows
+        .into_iter()
+        .map(|r| CommentSubmissionResponseBody {
+            id: r.id,
+            source: r.source,
+            post_id: r.post_id,
+            post_title: r.post_title,
+            author_name: r.author_name,
+            author_email: r.author_email,
+            text: r.text,
+            origin: r.origin,
+            ip_address: r.ip_address,
+            created_at: r.created_at,
+        })
+        .collect();
+
+    Ok((submissions, total_count))
+}
+
+// Publishes a pending submission as a comment. Authors with an email get an account the same
+// way comment imports do, anonymous ones are attributed to the placeholder author under their
+// display name.
+#[tracing::instrument(skip(pool))]
+pub async fn approve_submission(
+    submission_id: Uuid,
+    pool: &PgPool,
+) -> Result<Uuid, SubmissionReviewError> {
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to start a transaction")?;
+
+    let submission = sqlx::query!(
+        r#"
+        SELECT post_id, author_name, author_email, text, created_at
+        FROM comment_submissions
+        WHERE id = $1 AND status = 'pending'
+        FOR UPDATE
+        "#,
+        submission_id
+    )
+    .fetch_optional(transaction.deref_mut())
+    .await
+    .context("Failed to load comment submission")?
+    .ok_or(SubmissionReviewError::NotFound)?;
+
+    // Nobody verified the submitted email, so it must not attribute the comment to the account
+    // that owns the address. Such comments are shown under the submitted name, like anonymous ones.
+    let (author_id, author_display_name) = match submission.author_email {
+        Some(email) => {
+            let email = email.to_lowercase();
+            if find_user_id_by_email(&email, &mut transaction)
+                .await?
+                .is_some()
+            {
+                (ANONYMOUS_USER_ID, Some(submission.author_name))
+            } else {
+                // Validated on submission, so this only fails if the rules have since tightened
+                let author_name = UserName::parse(submission.author_name)
+                    .map_err(SubmissionReviewError::ValidationError)?;
+                let author_id = create_shell_user(&email, &author_name, &mut transaction).await?;
+                (author_id, None)
+            }
+        }
+        None => (ANONYMOUS_USER_ID, Some(submission.author_name)),
+    };
+
+    let comment_id = Uuid::new_v4();
+    sqlx::query!(
+        r#"
+        INSERT INTO comments (id, text, post_id, created_by, author_display_name, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+        comment_id,
+        submission.text,
+        submission.post_id,
+        author_id,
+        author_display_name,
+        submission.created_at
+    )
+    .execute(transaction.deref_mut())
+    .await
+    .context("Failed to insert approved comment")?;
+
+    sqlx::query!(
+        r#"
+        UPDATE comment_submissions
+        SET status = 'approved', comment_id = $2, reviewed_at = NOW()
+        WHERE id = $1
+        "#,
+        submission_id,
+        comment_id
+    )
+    .execute(transaction.deref_mut())
+    .await
+    .context("Failed to mark comment submission as approved")?;
+
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit comment submission approval")?;
+
+    Ok(comment_id)
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn reject_submission(
+    submission_id: Uuid,
+    pool: &PgPool,
+) -> Result<(), SubmissionReviewError> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE comment_submissions
+        SET status = 'rejected', reviewed_at = NOW()
+        WHERE id = $1 AND status = 'pending'
+        "#,
+        submission_id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to reject comment submission")?;
+
+    if result.rows_affected() == 0 {
+        return Err(SubmissionReviewError::NotFound);
+    }
+
+    Ok(())
+}
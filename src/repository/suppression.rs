@@ -0,0 +1,113 @@
+use anyhow::Context;
+use sqlx::PgPool;
+
+use crate::domain::{Pagination, SuppressedEmail, SuppressionReason};
+
+// Suppressing an address twice keeps the first reason. False when it already was suppressed.
+#[tracing::instrument(skip(pool))]
+pub async fn suppress_email(
+    email: &str,
+    reason: SuppressionReason,
+    pool: &PgPool,
+) -> Result<bool, anyhow::Error> {
+    let inserted = sqlx::query!(
+        r#"
+        INSERT INTO suppressed_emails (email, reason)
+        VALUES (LOWER($1), $2)
+        ON CONFLICT (email) DO NOTHING
+        "#,
+        email,
+        reason.as_str()
+    )
+    .execute(pool)
+    .await
+    .context("Failed to suppress email")?
+    .rows_affected();
+
+    Ok(inserted > 0)
+}
+
+// Also clears the bounce recorded on the user with the address, if any, so newsletters are
+// enqueued for them again. False when the address was not suppressed.
+#[tracing::instrument(skip(pool))]
+pub async fn unsuppress_email(email: &str, pool: &PgPool) -> Result<bool, anyhow::Error> {
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to start a transaction")?;
+
+    let deleted = sqlx::query!(
+        r#"DELETE FROM suppressed_emails WHERE email = LOWER($1)"#,
+        email
+    )
+    .execute(&mut *transaction)
+    .await
+    .context("Failed to remove email from the suppression list")?
+    .rows_affected();
+    if deleted == 0 {
+        return Ok(false);
+    }
+
+    sqlx::query!(
+        r#"
+        UPDATE users
+        SET email_undeliverable_at = NULL
+        WHERE LOWER(email) = LOWER($1)
+        "#,
+        email
+    )
+    .execute(&mut *transaction)
+    .await
+    .context("Failed to clear undeliverable mark of user email")?;
+
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit removal from the suppression list")?;
+
+    Ok(true)
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn is_email_suppressed(email: &str, pool: &PgPool) -> Result<bool, anyhow::Error> {
+    sqlx::query_scalar!(
+        r#"SELECT EXISTS (SELECT 1 FROM suppressed_emails WHERE email = LOWER($1)) AS "suppressed!""#,
+        email
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to check whether email is suppressed")
+}
+
+// Most recently suppressed first
+#[tracing::instrument(skip(pool))]
+pub async fn get_suppressed_emails(
+    page: &Pagination,
+    pool: &PgPool,
+) -> Result<(Vec<SuppressedEmail>, i64), anyhow::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT COUNT(*) OVER() AS "total_count!", email, reason, created_at
+        FROM suppressed_emails
+        ORDER BY created_at DESC, email
+        LIMIT $1 OFFSET $2
+        "#,
+        page.limit.value() as i64,
+        page.offset() as i64
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to load suppressed emails")?;
+
+    let total_count = rows.first().map(|r| r.total_count).unwrap_or(0);
+    let emails = rows
+        .into_iter()
+        .map(|r| SuppressedEmail {
+            email: r.email,
+            reason: r.reason,
+            created_at: r.created_at,
+        })
+        .collect();
+
+    Ok((emails, total_count))
+}
@@ -1,21 +1,45 @@
+use std::ops::DerefMut;
+
 use anyhow::Context;
-use sqlx::{Executor, PgPool, Postgres, Transaction};
+use sqlx::{Executor, PgExecutor, PgPool, Postgres, Transaction};
 use uuid::Uuid;
 
-#[tracing::instrument(skip(token, pool))]
+use crate::domain::{TokenPurpose, UserEmail};
+
+// How long emailed links stay valid. Activation links last past the first activation reminder,
+// and each reminder sends a fresh one.
+pub const ACTIVATION_TOKEN_TTL_HOURS: i32 = 48;
+pub const SUBSCRIPTION_TOKEN_TTL_HOURS: i32 = 24;
+pub const EMAIL_CHANGE_TOKEN_TTL_HOURS: i32 = 24;
+// As long as reminders can still arrive, they stop a week after registration
+pub const REMINDER_OPT_OUT_TOKEN_TTL_HOURS: i32 = 7 * 24;
+
+#[derive(thiserror::Error, Debug)]
+pub enum TokenLookupError {
+    #[error("Unknown token")]
+    Unknown,
+    #[error("Expired token")]
+    Expired,
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+// Takes any executor so imported subscribers get their token in the same transaction
+#[tracing::instrument(skip(token, executor))]
 pub async fn store_subscription_token(
-    pool: &PgPool,
+    executor: impl PgExecutor<'_>,
     user_id: Uuid,
     token: &str,
 ) -> Result<(), anyhow::Error> {
     sqlx::query!(
-        r#"INSERT INTO tokens (token, user_id, is_subscription)
-            VALUES ($1, $2, $3)"#,
+        r#"INSERT INTO tokens (token, user_id, purpose, expires_at)
+            VALUES ($1, $2, $3, NOW() + make_interval(hours => $4))"#,
         token,
         user_id,
-        true,
+        TokenPurpose::Subscription as TokenPurpose,
+        SUBSCRIPTION_TOKEN_TTL_HOURS,
     )
-    .execute(pool)
+    .execute(executor)
     .await
     .context("Failed to store the user subscription token")?;
 
@@ -29,11 +53,12 @@ pub async fn store_activation_token(
     token: &str,
 ) -> Result<(), anyhow::Error> {
     let query = sqlx::query!(
-        r#"INSERT INTO tokens (token, user_id, is_activation)
-            VALUES ($1, $2, $3)"#,
+        r#"INSERT INTO tokens (token, user_id, purpose, expires_at)
+            VALUES ($1, $2, $3, NOW() + make_interval(hours => $4))"#,
         token,
         user_id,
-        true,
+        TokenPurpose::Activation as TokenPurpose,
+        ACTIVATION_TOKEN_TTL_HOURS,
     );
 
     transaction
@@ -43,17 +68,215 @@ pub async fn store_activation_token(
     Ok(())
 }
 
+// Invalidates every link of the user sent for `purpose`
+#[tracing::instrument(skip(transaction))]
+pub async fn delete_tokens_of_user(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    purpose: TokenPurpose,
+) -> Result<(), anyhow::Error> {
+    let query = sqlx::query!(
+        r#"DELETE FROM tokens WHERE user_id = $1 AND purpose = $2"#,
+        user_id,
+        purpose as TokenPurpose,
+    );
+
+    transaction
+        .execute(query)
+        .await
+        .context("Failed to delete tokens of user")?;
+    Ok(())
+}
+
+// Invalidates any activation link sent earlier, so only the newest one works
+#[tracing::instrument(skip(token, transaction))]
+pub async fn replace_activation_token(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    token: &str,
+) -> Result<(), anyhow::Error> {
+    delete_tokens_of_user(transaction, user_id, TokenPurpose::Activation).await?;
+    store_activation_token(transaction, user_id, token).await
+}
+
+// Each reminder carries its own opt-out link, earlier ones stop working
+#[tracing::instrument(skip(token, transaction))]
+pub async fn replace_reminder_opt_out_token(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    token: &str,
+) -> Result<(), anyhow::Error> {
+    delete_tokens_of_user(transaction, user_id, TokenPurpose::ReminderOptOut).await?;
+
+    let query = sqlx::query!(
+        r#"INSERT INTO tokens (token, user_id, purpose, expires_at)
+            VALUES ($1, $2, $3, NOW() + make_interval(hours => $4))"#,
+        token,
+        user_id,
+        TokenPurpose::ReminderOptOut as TokenPurpose,
+        REMINDER_OPT_OUT_TOKEN_TTL_HOURS,
+    );
+
+    transaction
+        .execute(query)
+        .await
+        .context("Failed to store the reminder opt-out token")?;
+    Ok(())
+}
+
+// The unsubscribe token of the subscriber using `email`, created with `new_token` on their first
+// issue. `None` when no subscriber uses the address anymore, e.g. they unsubscribed after the
+// issue was queued.
+#[tracing::instrument(skip(new_token, transaction))]
+pub async fn get_or_create_unsubscribe_token(
+    transaction: &mut Transaction<'_, Postgres>,
+    email: &str,
+    new_token: &str,
+) -> Result<Option<String>, anyhow::Error> {
+    let token = sqlx::query_scalar!(
+        r#"
+        WITH subscriber AS (
+            SELECT id FROM users
+            WHERE email = $1 AND is_activated = true AND is_subscribed = true
+        ), existing AS (
+            SELECT t.token FROM tokens t
+            INNER JOIN subscriber s ON t.user_id = s.id
+            WHERE t.purpose = $3
+            LIMIT 1
+        ), inserted AS (
+            INSERT INTO tokens (token, user_id, purpose, expires_at)
+            SELECT $2, id, $3, 'infinity' FROM subscriber
+            WHERE NOT EXISTS (SELECT 1 FROM existing)
+            RETURNING token
+        )
+        SELECT token AS "token!" FROM existing
+        UNION ALL
+        SELECT token FROM inserted
+        "#,
+        email,
+        new_token,
+        TokenPurpose::Unsubscribe as TokenPurpose,
+    )
+    .fetch_optional(transaction.deref_mut())
+    .await
+    .context("Failed to get the unsubscribe token of a subscriber")?;
+
+    Ok(token)
+}
+
+// Only the newest email change link works, so a user who retypes a mistyped address cannot
+// confirm the old one by accident
+#[tracing::instrument(skip(token, transaction))]
+pub async fn replace_email_change_token(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    token: &str,
+    pending_email: &UserEmail,
+) -> Result<(), anyhow::Error> {
+    delete_tokens_of_user(transaction, user_id, TokenPurpose::EmailChange).await?;
+
+    let query = sqlx::query!(
+        r#"INSERT INTO tokens (token, user_id, purpose, pending_email, expires_at)
+            VALUES ($1, $2, $3, $4, NOW() + make_interval(hours => $5))"#,
+        token,
+        user_id,
+        TokenPurpose::EmailChange as TokenPurpose,
+        pending_email.as_ref(),
+        EMAIL_CHANGE_TOKEN_TTL_HOURS,
+    );
+
+    transaction
+        .execute(query)
+        .await
+        .context("Failed to store the email change token")?;
+    Ok(())
+}
+
+// Expired tokens are told apart from unknown ones until the worker purges them, so the user can
+// be asked for a new link. Tokens sent for another purpose are unknown.
 pub async fn get_user_id_from_token(
     pool: &PgPool,
     token: &str,
-) -> Result<Option<Uuid>, anyhow::Error> {
+    purpose: TokenPurpose,
+) -> Result<Uuid, TokenLookupError> {
     let result = sqlx::query!(
-        "SELECT user_id FROM tokens \
-            WHERE token = $1",
+        r#"
+        SELECT user_id, expires_at > NOW() AS "is_fresh!"
+        FROM tokens
+        WHERE token = $1 AND purpose = $2
+        "#,
         token,
+        purpose as TokenPurpose,
     )
     .fetch_optional(pool)
     .await
-    .context("Failed to retrieve the user id associated with the provided token.")?;
-    Ok(result.map(|r| r.user_id))
+    .context("Failed to retrieve the user id associated with the provided token.")?
+    .ok_or(TokenLookupError::Unknown)?;
+
+    if !result.is_fresh {
+        return Err(TokenLookupError::Expired);
+    }
+    Ok(result.user_id)
+}
+
+// For links that sign the user in or reset their password. Only the newest one works, so an older
+// email that leaked cannot be used after the user asked again.
+#[tracing::instrument(skip(token, transaction))]
+pub async fn replace_short_lived_token(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    token: &str,
+    purpose: TokenPurpose,
+    ttl_minutes: i32,
+) -> Result<(), anyhow::Error> {
+    delete_tokens_of_user(transaction, user_id, purpose).await?;
+
+    let query = sqlx::query!(
+        r#"INSERT INTO tokens (token, user_id, purpose, expires_at)
+            VALUES ($1, $2, $3, NOW() + make_interval(mins => $4))"#,
+        token,
+        user_id,
+        purpose as TokenPurpose,
+        ttl_minutes,
+    );
+
+    transaction
+        .execute(query)
+        .await
+        .context("Failed to store a short-lived token")?;
+    Ok(())
+}
+
+// Deletes the token and returns its user if it has not expired. Rolling back the transaction
+// keeps the token usable.
+#[tracing::instrument(skip(token, transaction))]
+pub async fn consume_token(
+    transaction: &mut Transaction<'_, Postgres>,
+    token: &str,
+    purpose: TokenPurpose,
+) -> Result<Option<Uuid>, anyhow::Error> {
+    let consumed = sqlx::query!(
+        r#"
+        DELETE FROM tokens
+        WHERE token = $1 AND purpose = $2
+        RETURNING user_id, expires_at > NOW() AS "is_fresh!"
+        "#,
+        token,
+        purpose as TokenPurpose,
+    )
+    .fetch_optional(transaction.deref_mut())
+    .await
+    .context("Failed to consume a token")?;
+
+    Ok(consumed.filter(|c| c.is_fresh).map(|c| c.user_id))
+}
+
+pub async fn cleanup_expired_tokens(pool: &PgPool) -> Result<(), anyhow::Error> {
+    let deleted = sqlx::query!(r#"DELETE FROM tokens WHERE expires_at <= NOW()"#)
+        .execute(pool)
+        .await?
+        .rows_affected();
+
+    tracing::info!(deleted, "Expired token cleanup completed");
+    Ok(())
 }
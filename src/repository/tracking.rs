@@ -0,0 +1,119 @@
+use anyhow::Context;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::PgTransaction;
+use crate::domain::SubjectVariant;
+
+// A retried delivery keeps the token of its first attempt. Opens are attributed to the subject
+// line the recipient was sent, see `SubjectVariant`.
+#[tracing::instrument(skip(transaction, new_token))]
+pub async fn get_or_create_tracking_token(
+    transaction: &mut PgTransaction,
+    issue_id: Uuid,
+    email: &str,
+    new_token: &str,
+    subject_variant: SubjectVariant,
+) -> Result<String, anyhow::Error> {
+    sqlx::query_scalar!(
+        r#"
+        INSERT INTO newsletter_tracking_tokens (token, newsletter_issue_id, user_email, subject_variant)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (newsletter_issue_id, user_email) DO UPDATE
+        SET token = newsletter_tracking_tokens.token
+        RETURNING token
+        "#,
+        new_token,
+        issue_id,
+        email,
+        subject_variant.as_str()
+    )
+    .fetch_one(&mut **transaction)
+    .await
+    .context("Failed to get the tracking token of a newsletter delivery")
+}
+
+// For deliveries that ended without an email going out
+#[tracing::instrument(skip(transaction))]
+pub async fn delete_tracking_token(
+    transaction: &mut PgTransaction,
+    issue_id: Uuid,
+    email: &str,
+) -> Result<(), anyhow::Error> {
+    sqlx::query!(
+        r#"
+        DELETE FROM newsletter_tracking_tokens
+        WHERE newsletter_issue_id = $1 AND user_email = $2
+        "#,
+        issue_id,
+        email
+    )
+    .execute(&mut **transaction)
+    .await
+    .context("Failed to delete the tracking token of a newsletter delivery")?;
+
+    Ok(())
+}
+
+// False for unknown tokens. Tokens of emails that went out are kept with their issue, see
+// `cleanup_old_newsletter_issues`.
+#[tracing::instrument(skip_all)]
+pub async fn record_newsletter_open(pool: &PgPool, token: &str) -> Result<bool, anyhow::Error> {
+    let updated = sqlx::query!(
+        r#"
+        UPDATE newsletter_tracking_tokens
+        SET open_count = open_count + 1, first_opened_at = COALESCE(first_opened_at, NOW())
+        WHERE token = $1
+        "#,
+        token
+    )
+    .execute(pool)
+    .await
+    .context("Failed to record a newsletter open")?
+    .rows_affected();
+
+    Ok(updated > 0)
+}
+
+// The issue a tracking token was handed out for, as stored rather than as delivered
+#[tracing::instrument(skip_all)]
+pub async fn get_tracked_issue_html(
+    pool: &PgPool,
+    token: &str,
+) -> Result<Option<String>, anyhow::Error> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT n.html_content
+        FROM newsletter_tracking_tokens t
+        JOIN newsletter_issues n ON n.id = t.newsletter_issue_id
+        WHERE t.token = $1
+        "#,
+        token
+    )
+    .fetch_optional(pool)
+    .await
+    .context("Failed to fetch the newsletter issue of a tracking token")
+}
+
+#[tracing::instrument(skip(pool, token))]
+pub async fn record_newsletter_click(
+    pool: &PgPool,
+    token: &str,
+    link_position: i32,
+    url: &str,
+) -> Result<(), anyhow::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO newsletter_link_clicks (token, link_position, url)
+        VALUES ($1, $2, $3)
+        "#,
+        token,
+        link_position,
+        url
+    )
+    .execute(pool)
+    .await
+    .context("Failed to record a newsletter link click")?;
+
+    Ok(())
+}
@@ -1,9 +1,26 @@
+use std::ops::DerefMut;
+
 use anyhow::Context;
 use secrecy::{ExposeSecret, Secret};
 use sqlx::{Executor, PgPool, Postgres, Transaction};
 use uuid::Uuid;
 
-use crate::domain::{UserEmail, UserName};
+use crate::{
+    domain::{
+        ANONYMOUS_USER_ID, AccountDeletionPolicy, AccountDeletionSummary, AuditAction, BanSummary,
+        ExportedAccount, ProfileUpdate, Role, Suspension, TokenPurpose, UserEmail, UserName,
+        UserProfile, UserSearch, UserSearchResult, avatar_url,
+    },
+    repository,
+    routes::{AccountDeletionError, BanError, EmailChangeError, RegisterError, UserProfileError},
+};
+
+// Enforces case-insensitive uniqueness of user names
+const USER_NAME_UNIQUE_INDEX: &str = "users_user_name_unique_idx";
+
+fn is_user_name_taken(e: &sqlx::Error) -> bool {
+    matches!(e, sqlx::Error::Database(e) if e.constraint() == Some(USER_NAME_UNIQUE_INDEX))
+}
 
 #[tracing::instrument(skip_all)]
 pub async fn insert_user(
@@ -11,7 +28,7 @@ pub async fn insert_user(
     email: &UserEmail,
     password_hash: Secret<String>,
     transaction: &mut Transaction<'_, Postgres>,
-) -> Result<Uuid, anyhow::Error> {
+) -> Result<Uuid, RegisterError> {
     let user_id = Uuid::new_v4();
     let query = sqlx::query!(
         r#"
@@ -24,11 +41,13 @@ pub async fn insert_user(
         password_hash.expose_secret()
     );
 
-    transaction
-        .execute(query)
-        .await
-        .context("Failed to insert new user")?;
-    Ok(user_id)
+    match transaction.execute(query).await {
+        Ok(_) => Ok(user_id),
+        Err(e) if is_user_name_taken(&e) => Err(RegisterError::UserNameTaken),
+        Err(e) => Err(anyhow::Error::new(e)
+            .context("Failed to insert new user")
+            .into()),
+    }
 }
 
 #[tracing::instrument(skip(pool, token))]
@@ -41,14 +60,15 @@ pub async fn activate_user_and_delete_token(
         r#"
         WITH activate_user AS (
             UPDATE users
-            SET is_activated = true
+            SET is_activated = true, activated_at = NOW()
             WHERE id = $1
         )
         DELETE FROM tokens
-        WHERE token = $2 AND user_id = $1 AND is_activation = true
+        WHERE token = $2 AND user_id = $1 AND purpose = $3
         "#,
         user_id,
         token,
+        TokenPurpose::Activation as TokenPurpose,
     )
     .execute(pool)
     .await
@@ -71,10 +91,11 @@ pub async fn subscribe_user_and_delete_token(
             WHERE id = $1 and is_activated = true
         )
         DELETE FROM tokens
-        WHERE token = $2 AND user_id = $1 AND is_subscription = true
+        WHERE token = $2 AND user_id = $1 AND purpose = $3
         "#,
         user_id,
         token,
+        TokenPurpose::Subscription as TokenPurpose,
     )
     .execute(pool)
     .await
@@ -83,6 +104,306 @@ pub async fn subscribe_user_and_delete_token(
     Ok(())
 }
 
+// Issues already queued for the user are skipped when their turn comes
+#[tracing::instrument(skip(pool))]
+pub async fn unsubscribe_user(pool: &PgPool, user_id: Uuid) -> Result<(), anyhow::Error> {
+    sqlx::query!(
+        r#"UPDATE users SET is_subscribed = false WHERE id = $1"#,
+        user_id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to unsubscribe the user from the newsletter")?;
+
+    Ok(())
+}
+
+// Imported subscribers get an activated account without a usable password, so it can only be
+// claimed by resetting the password. A name already held by another account gets the new id
+// appended, like for shell users. None when the email already belongs to an account.
+#[tracing::instrument(skip(user_name, transaction))]
+pub async fn insert_imported_subscriber(
+    email: &str,
+    user_name: &UserName,
+    subscribed: bool,
+    transaction: &mut Transaction<'_, Postgres>,
+) -> Result<Option<Uuid>, anyhow::Error> {
+    sqlx::query_scalar!(
+        r#"
+        INSERT INTO users (id, user_name, email, password_hash, is_activated, is_subscribed)
+        SELECT $1::UUID,
+               CASE WHEN EXISTS (SELECT 1 FROM users WHERE LOWER(user_name) = LOWER($2::TEXT))
+                    THEN $2::TEXT || '-' || LEFT($1::UUID::TEXT, 8)
+                    ELSE $2::TEXT
+               END,
+               LOWER($3::TEXT), $4::TEXT, true, $5::BOOL
+        WHERE NOT EXISTS (SELECT 1 FROM users WHERE LOWER(email) = LOWER($3::TEXT))
+        RETURNING id
+        "#,
+        Uuid::new_v4(),
+        user_name.as_ref(),
+        email,
+        super::comment::SHELL_USER_PASSWORD_HASH,
+        subscribed
+    )
+    .fetch_optional(transaction.deref_mut())
+    .await
+    .context("Failed to create user for imported subscriber")
+}
+
+// Compared case-insensitively, like the lookups that match accounts by email
+#[tracing::instrument(skip(email, pool))]
+pub async fn is_email_taken(email: &UserEmail, pool: &PgPool) -> Result<bool, anyhow::Error> {
+    let taken = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM users WHERE LOWER(email) = LOWER($1)) AS "taken!""#,
+        email.as_ref()
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to check if email is in use")?;
+    Ok(taken)
+}
+
+// Consumes the email change token and swaps in the address it was issued for. The address is
+// checked again because another account may have claimed it since the link was sent.
+#[tracing::instrument(skip(token, pool), fields(user_id=tracing::field::Empty))]
+pub async fn confirm_email_change(token: &str, pool: &PgPool) -> Result<(), EmailChangeError> {
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to start a transaction")?;
+
+    let pending = sqlx::query!(
+        r#"
+        DELETE FROM tokens
+        WHERE token = $1 AND purpose = $2 AND expires_at > NOW()
+        RETURNING user_id, pending_email AS "pending_email!"
+        "#,
+        token,
+        TokenPurpose::EmailChange as TokenPurpose,
+    )
+    .fetch_optional(transaction.deref_mut())
+    .await
+    .context("Failed to consume the email change token")?
+    .ok_or(EmailChangeError::UnknownToken)?;
+    tracing::Span::current().record("user_id", tracing::field::display(pending.user_id));
+
+    let result = sqlx::query!(
+        r#"
+        UPDATE users
+        SET email = $2, email_undeliverable_at = NULL
+        WHERE id = $1
+        AND NOT EXISTS (SELECT 1 FROM users WHERE LOWER(email) = LOWER($2) AND id <> $1)
+        "#,
+        pending.user_id,
+        pending.pending_email
+    )
+    .execute(transaction.deref_mut())
+    .await
+    .context("Failed to update the user email")?;
+
+    if result.rows_affected() == 0 {
+        return Err(EmailChangeError::EmailTaken);
+    }
+
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit the email change")?;
+
+    Ok(())
+}
+
+pub async fn user_exists(user_id: Uuid, pool: &PgPool) -> Result<bool, anyhow::Error> {
+    let exists = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM users WHERE id = $1) AS "exists!""#,
+        user_id
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to check if user exists")?;
+    Ok(exists)
+}
+
+// Counts only what the user's public listings show, so deleted posts and comments on them are left out
+#[tracing::instrument(skip(pool))]
+pub async fn get_user_profile(
+    user_id: Uuid,
+    pool: &PgPool,
+) -> Result<Option<UserProfile>, anyhow::Error> {
+    let row = sqlx::query!(
+        r#"
+        SELECT u.id, u.user_name, u.avatar_updated_at, u.bio, u.website, u.location,
+               u.created_at AS joined_at,
+               (
+                   SELECT COUNT(*) FROM posts p
+                   WHERE p.created_by = u.id AND p.deleted_at IS NULL
+               ) AS "post_count!",
+               (
+                   SELECT COUNT(*) FROM comments c
+                   INNER JOIN posts p ON c.post_id = p.id
+                   WHERE c.created_by = u.id AND c.deleted_at IS NULL AND p.deleted_at IS NULL
+               ) AS "comment_count!"
+        FROM users u
+        WHERE u.id = $1 AND u.deleted_at IS NULL
+        "#,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await
+    .context("Failed to load user profile")?;
+
+    Ok(row.map(|r| UserProfile {
+        id: r.id,
+        user_name: r.user_name,
+        avatar_url: avatar_url(r.id, r.avatar_updated_at),
+        bio: r.bio,
+        website: r.website,
+        location: r.location,
+        joined_at: r.joined_at,
+        post_count: r.post_count,
+        comment_count: r.comment_count,
+    }))
+}
+
+// Names starting with the term come first, then names that merely resemble it. Only accounts
+// that can be mentioned are returned, so inactive, banned and deleted users are left out.
+#[tracing::instrument(skip(search, pool))]
+pub async fn search_users(
+    search: &UserSearch,
+    pool: &PgPool,
+) -> Result<(Vec<UserSearchResult>, i64), anyhow::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT COUNT(*) OVER() AS "total_count!", id, user_name, avatar_updated_at
+        FROM users
+        WHERE is_activated
+          AND (banned_at IS NULL OR banned_until <= NOW())
+          AND deleted_at IS NULL
+          AND id <> $3
+          AND (user_name ILIKE $1 OR user_name % $2)
+        ORDER BY user_name ILIKE $1 DESC, similarity(user_name, $2) DESC, user_name
+        LIMIT $4 OFFSET $5
+        "#,
+        search.term.prefix_pattern(),
+        search.term.as_ref(),
+        ANONYMOUS_USER_ID,
+        search.pagination.limit.value() as i64,
+        search.pagination.offset() as i64
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to search users")?;
+
+    let total_count = rows.first().map(|r| r.total_count).unwrap_or(0);
+    let users = rows
+        .into_iter()
+        .map(|r| UserSearchResult {
+            id: r.id,
+            user_name: r.user_name,
+            avatar_url: avatar_url(r.id, r.avatar_updated_at),
+        })
+        .collect();
+
+    Ok((users, total_count))
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn get_account_for_export(
+    user_id: Uuid,
+    pool: &PgPool,
+) -> Result<Option<ExportedAccount>, anyhow::Error> {
+    let row = sqlx::query!(
+        r#"
+        SELECT id, user_name, email, bio, website, location, avatar_updated_at, is_subscribed,
+               created_at
+        FROM users
+        WHERE id = $1 AND deleted_at IS NULL
+        "#,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await
+    .context("Failed to load account for export")?;
+
+    Ok(row.map(|r| ExportedAccount {
+        id: r.id,
+        user_name: r.user_name,
+        email: r.email,
+        bio: r.bio,
+        website: r.website,
+        location: r.location,
+        avatar_url: avatar_url(r.id, r.avatar_updated_at),
+        is_subscribed: r.is_subscribed,
+        joined_at: r.created_at,
+    }))
+}
+
+#[tracing::instrument(skip(update, pool))]
+pub async fn update_profile(
+    user_id: Uuid,
+    update: &ProfileUpdate,
+    pool: &PgPool,
+) -> Result<(), anyhow::Error> {
+    sqlx::query!(
+        r#"
+        UPDATE users
+        SET bio = CASE WHEN $2 THEN $3 ELSE bio END,
+            website = CASE WHEN $4 THEN $5 ELSE website END,
+            location = CASE WHEN $6 THEN $7 ELSE location END
+        WHERE id = $1
+        "#,
+        user_id,
+        update.bio.is_some(),
+        update
+            .bio
+            .as_ref()
+            .and_then(|b| b.as_ref().map(|b| b.as_ref())),
+        update.website.is_some(),
+        update
+            .website
+            .as_ref()
+            .and_then(|w| w.as_ref().map(|w| w.as_ref())),
+        update.location.is_some(),
+        update
+            .location
+            .as_ref()
+            .and_then(|l| l.as_ref().map(|l| l.as_ref())),
+    )
+    .execute(pool)
+    .await
+    .context("Failed to update user profile")?;
+
+    Ok(())
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn update_user_name(
+    user_id: Uuid,
+    user_name: &UserName,
+    pool: &PgPool,
+) -> Result<(), UserProfileError> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE users
+        SET user_name = $2
+        WHERE id = $1
+        "#,
+        user_id,
+        user_name.as_ref()
+    )
+    .execute(pool)
+    .await;
+
+    match result {
+        Ok(_) => Ok(()),
+        Err(e) if is_user_name_taken(&e) => Err(UserProfileError::UserNameTaken),
+        Err(e) => Err(anyhow::Error::new(e)
+            .context("Failed to update user name")
+            .into()),
+    }
+}
+
 pub async fn get_username(user_id: Uuid, pool: &PgPool) -> Result<String, anyhow::Error> {
     let row = sqlx::query!(
         r#"
@@ -113,10 +434,10 @@ pub async fn get_user_email(user_id: Uuid, pool: &PgPool) -> Result<String, anyh
     Ok(row.email)
 }
 
-pub async fn is_admin_user(user_id: Uuid, pool: &PgPool) -> Result<bool, anyhow::Error> {
+pub async fn get_user_role(user_id: Uuid, pool: &PgPool) -> Result<Role, anyhow::Error> {
     let record = sqlx::query!(
         r#"
-        SELECT is_admin
+        SELECT role AS "role: Role"
         FROM users
         WHERE id = $1
         "#,
@@ -124,13 +445,81 @@ pub async fn is_admin_user(user_id: Uuid, pool: &PgPool) -> Result<bool, anyhow:
     )
     .fetch_optional(pool)
     .await
-    .context("Failed to fetch admin flag for user")?;
+    .context("Failed to fetch role for user")?;
 
-    let is_admin = record
-        .map(|r| r.is_admin)
+    let role = record
+        .map(|r| r.role)
         .ok_or_else(|| anyhow::anyhow!("No user found"))?;
 
-    Ok(is_admin)
+    Ok(role)
+}
+
+// None if there is no such user or their account was deleted
+pub async fn find_user_role(user_id: Uuid, pool: &PgPool) -> Result<Option<Role>, anyhow::Error> {
+    let role = sqlx::query_scalar!(
+        r#"
+        SELECT role AS "role: Role"
+        FROM users
+        WHERE id = $1 AND deleted_at IS NULL
+        "#,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await
+    .context("Failed to fetch role for user")?;
+
+    Ok(role)
+}
+
+// False if there is no such user. Sessions and refresh tokens carry the role they were issued
+// with, so they are revoked for the new role to take effect.
+#[tracing::instrument(skip(pool))]
+pub async fn set_user_role(
+    user_id: Uuid,
+    role: Role,
+    admin_id: Uuid,
+    pool: &PgPool,
+) -> Result<bool, anyhow::Error> {
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+
+    // The self-join reads the role as it was before the update
+    let previous_role = sqlx::query_scalar!(
+        r#"
+        UPDATE users u
+        SET role = $2
+        FROM users previous
+        WHERE u.id = $1 AND previous.id = u.id AND u.deleted_at IS NULL
+        RETURNING previous.role AS "role: Role"
+        "#,
+        user_id,
+        role as Role
+    )
+    .fetch_optional(&mut *transaction)
+    .await
+    .context("Failed to set the role of a user")?;
+    let Some(previous_role) = previous_role else {
+        return Ok(false);
+    };
+
+    repository::revoke_all_sessions(&mut transaction, user_id, None).await?;
+    repository::revoke_refresh_tokens_of_user(&mut transaction, user_id).await?;
+    repository::record_audit_event(
+        &mut *transaction,
+        AuditAction::RoleChange,
+        Some(admin_id),
+        Some(user_id),
+        serde_json::json!({ "previous_role": previous_role, "role": role }),
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to set the role of a user")?;
+
+    Ok(true)
 }
 
 pub async fn get_stored_credentials(
@@ -154,11 +543,27 @@ pub async fn get_stored_credentials(
     Ok(row)
 }
 
+// The replaced hash moves to the password history, which is then cut back to the `keep` most
+// recent entries
 pub async fn update_password_hash(
+    transaction: &mut Transaction<'_, Postgres>,
     user_id: Uuid,
     password_hash: Secret<String>,
-    pool: &PgPool,
+    keep: i64,
 ) -> Result<(), anyhow::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO password_history (user_id, password_hash)
+        SELECT id, password_hash
+        FROM users
+        WHERE id = $1 and is_activated = true
+        "#,
+        user_id
+    )
+    .execute(transaction.deref_mut())
+    .await
+    .context("Failed to record the replaced password hash")?;
+
     sqlx::query!(
         r#"
         UPDATE users
@@ -168,8 +573,534 @@ pub async fn update_password_hash(
         password_hash.expose_secret(),
         user_id
     )
-    .execute(pool)
+    .execute(transaction.deref_mut())
     .await
     .context("Failed to change user's password")?;
+
+    sqlx::query!(
+        r#"
+        DELETE FROM password_history
+        WHERE user_id = $1
+          AND id NOT IN (
+            SELECT id
+            FROM password_history
+            WHERE user_id = $1
+            ORDER BY replaced_at DESC
+            LIMIT $2
+          )
+        "#,
+        user_id,
+        keep
+    )
+    .execute(transaction.deref_mut())
+    .await
+    .context("Failed to trim the password history")?;
+
     Ok(())
 }
+
+// The current hash and up to `previous` of the most recently replaced ones
+pub async fn get_recent_password_hashes(
+    user_id: Uuid,
+    previous: i64,
+    pool: &PgPool,
+) -> Result<Vec<Secret<String>>, anyhow::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT password_hash AS "password_hash!"
+        FROM users
+        WHERE id = $1
+        UNION ALL
+        (
+            SELECT password_hash
+            FROM password_history
+            WHERE user_id = $1
+            ORDER BY replaced_at DESC
+            LIMIT $2
+        )
+        "#,
+        user_id,
+        previous
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to retrieve the user's recent password hashes")?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| Secret::new(row.password_hash))
+        .collect())
+}
+
+// Posts and comments are removed this many at a time, each chunk reporting progress
+const BAN_PROGRESS_CHUNK_SIZE: i64 = 500;
+
+// Everything happens in one transaction so a failure half way never leaves a banned user
+// with some of their content still visible. Content is removed in chunks only to log progress
+// for users with thousands of posts or comments, the locks are held until commit either way.
+#[tracing::instrument(skip(pool))]
+pub async fn ban_user(
+    user_id: Uuid,
+    remove_content: bool,
+    suspension: &Suspension,
+    pool: &PgPool,
+) -> Result<BanSummary, BanError> {
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+
+    let user = sqlx::query!(
+        r#"
+        SELECT email, role AS "role: Role"
+        FROM users
+        WHERE id = $1 AND deleted_at IS NULL
+        FOR UPDATE
+        "#,
+        user_id
+    )
+    .fetch_optional(&mut *transaction)
+    .await
+    .context("Failed to fetch user to ban")?
+    .ok_or(BanError::NotFound)?;
+
+    if user.role == Role::Admin {
+        return Err(BanError::Forbidden);
+    }
+
+    // Banning a user who is already banned replaces the reason and expiry but keeps when the ban
+    // started. The subscription is left alone: fan-out skips users under a ban, so issues reach
+    // them again once it expires or is lifted.
+    sqlx::query!(
+        r#"
+        UPDATE users
+        SET banned_at = CASE
+                WHEN banned_at IS NULL OR banned_until <= NOW() THEN NOW()
+                ELSE banned_at
+            END,
+            banned_until = $2,
+            ban_reason = $3
+        WHERE id = $1
+        "#,
+        user_id,
+        suspension.until,
+        suspension.reason,
+    )
+    .execute(&mut *transaction)
+    .await
+    .context("Failed to mark user as banned")?;
+
+    let mut summary = BanSummary {
+        queued_deliveries_purged: sqlx::query!(
+            r#"
+            DELETE FROM issue_delivery_queue
+            WHERE user_email = $1
+            "#,
+            user.email
+        )
+        .execute(&mut *transaction)
+        .await
+        .context("Failed to purge queued deliveries of banned user")?
+        .rows_affected(),
+        ..Default::default()
+    };
+
+    if remove_content {
+        let total = sqlx::query!(
+            r#"
+            SELECT
+                (SELECT COUNT(*) FROM posts WHERE created_by = $1 AND deleted_at IS NULL) AS "posts!",
+                (SELECT COUNT(*) FROM comments WHERE created_by = $1 AND deleted_at IS NULL) AS "comments!"
+            "#,
+            user_id
+        )
+        .fetch_one(&mut *transaction)
+        .await
+        .context("Failed to count content of banned user")?;
+
+        // Chunks follow the ids, so no chunk scans the rows an earlier one already removed
+        let mut after: Option<Uuid> = None;
+        loop {
+            let removed = sqlx::query_scalar!(
+                r#"
+                WITH chunk AS (
+                    SELECT id FROM posts
+                    WHERE created_by = $1 AND deleted_at IS NULL
+                      AND ($2::UUID IS NULL OR id > $2)
+                    ORDER BY id
+                    LIMIT $3
+                )
+                UPDATE posts p
+                SET deleted_at = NOW()
+                FROM chunk
+                WHERE p.id = chunk.id
+                RETURNING p.id
+                "#,
+                user_id,
+                after,
+                BAN_PROGRESS_CHUNK_SIZE
+            )
+            .fetch_all(&mut *transaction)
+            .await
+            .context("Failed to remove posts of banned user")?;
+
+            summary.posts_removed += removed.len() as u64;
+            tracing::info!(
+                posts_removed = summary.posts_removed,
+                posts_remaining = total.posts.saturating_sub(summary.posts_removed as i64),
+                "Removing banned user posts"
+            );
+            if (removed.len() as i64) < BAN_PROGRESS_CHUNK_SIZE {
+                break;
+            }
+            after = removed.into_iter().max();
+        }
+
+        let mut after: Option<Uuid> = None;
+        loop {
+            let removed = sqlx::query_scalar!(
+                r#"
+                WITH chunk AS (
+                    SELECT id FROM comments
+                    WHERE created_by = $1 AND deleted_at IS NULL
+                      AND ($2::UUID IS NULL OR id > $2)
+                    ORDER BY id
+                    LIMIT $3
+                )
+                UPDATE comments c
+                SET deleted_at = NOW()
+                FROM chunk
+                WHERE c.id = chunk.id
+                RETURNING c.id
+                "#,
+                user_id,
+                after,
+                BAN_PROGRESS_CHUNK_SIZE
+            )
+            .fetch_all(&mut *transaction)
+            .await
+            .context("Failed to remove comments of banned user")?;
+
+            summary.comments_removed += removed.len() as u64;
+            tracing::info!(
+                comments_removed = summary.comments_removed,
+                comments_remaining = total
+                    .comments
+                    .saturating_sub(summary.comments_removed as i64),
+                "Removing banned user comments"
+            );
+            if (removed.len() as i64) < BAN_PROGRESS_CHUNK_SIZE {
+                break;
+            }
+            after = removed.into_iter().max();
+        }
+
+        summary.likes_withdrawn = sqlx::query!(
+            r#"
+            UPDATE posts
+            SET liked_by = array_remove(liked_by, $1)
+            WHERE $1 = ANY(liked_by)
+            "#,
+            user_id
+        )
+        .execute(&mut *transaction)
+        .await
+        .context("Failed to withdraw likes of banned user")?
+        .rows_affected();
+    }
+
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit ban transaction")?;
+
+    tracing::info!(?summary, "User banned");
+    Ok(summary)
+}
+
+// Returns whether the user exists. Content removed by the ban stays removed.
+#[tracing::instrument(skip(pool))]
+pub async fn unban_user(user_id: Uuid, pool: &PgPool) -> Result<bool, anyhow::Error> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE users
+        SET banned_at = NULL, banned_until = NULL, ban_reason = NULL
+        WHERE id = $1
+        "#,
+        user_id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to lift ban of user")?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+// Returns whether a user has the address. The time of the first bounce is kept.
+#[tracing::instrument(skip(pool))]
+pub async fn mark_email_undeliverable(email: &str, pool: &PgPool) -> Result<bool, anyhow::Error> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE users
+        SET email_undeliverable_at = COALESCE(email_undeliverable_at, NOW())
+        WHERE LOWER(email) = LOWER($1)
+        "#,
+        email
+    )
+    .execute(pool)
+    .await
+    .context("Failed to mark user email as undeliverable")?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+// The ban the user is under, if any. Bans past their expiry are over.
+pub async fn get_suspension(
+    user_id: Uuid,
+    pool: &PgPool,
+) -> Result<Option<Suspension>, anyhow::Error> {
+    let suspension = sqlx::query!(
+        r#"
+        SELECT ban_reason, banned_until
+        FROM users
+        WHERE id = $1
+          AND banned_at IS NOT NULL
+          AND (banned_until IS NULL OR banned_until > NOW())
+        "#,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await
+    .context("Failed to fetch suspension of user")?
+    .map(|row| Suspension {
+        reason: row.ban_reason,
+        until: row.banned_until,
+    });
+
+    Ok(suspension)
+}
+
+// Anonymizes the account in place rather than deleting the row, so whatever content the policy
+// keeps still has an author. Everything else tied to the account goes with it, newsletter
+// deliveries are forgotten like those past their retention, see `cleanup_old_newsletter_issues`.
+#[tracing::instrument(skip(pool))]
+pub async fn delete_account(
+    user_id: Uuid,
+    policy: AccountDeletionPolicy,
+    pool: &PgPool,
+) -> Result<AccountDeletionSummary, AccountDeletionError> {
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+
+    let user = sqlx::query!(
+        r#"
+        SELECT email, role AS "role: Role"
+        FROM users
+        WHERE id = $1 AND deleted_at IS NULL
+        FOR UPDATE
+        "#,
+        user_id
+    )
+    .fetch_optional(&mut *transaction)
+    .await
+    .context("Failed to fetch user to delete")?
+    .ok_or(AccountDeletionError::NotFound)?;
+
+    // Admins step down first, so the site is never left without one by accident
+    if user.role == Role::Admin {
+        return Err(AccountDeletionError::Forbidden);
+    }
+
+    let mut summary = AccountDeletionSummary {
+        queued_deliveries_purged: sqlx::query!(
+            r#"DELETE FROM issue_delivery_queue WHERE user_email = $1"#,
+            user.email
+        )
+        .execute(&mut *transaction)
+        .await
+        .context("Failed to purge queued deliveries of deleted user")?
+        .rows_affected(),
+        ..Default::default()
+    };
+
+    match policy {
+        AccountDeletionPolicy::RemoveContent => {
+            summary.posts_removed = sqlx::query!(
+                r#"
+                UPDATE posts
+                SET deleted_at = NOW()
+                WHERE created_by = $1 AND deleted_at IS NULL
+                "#,
+                user_id
+            )
+            .execute(&mut *transaction)
+            .await
+            .context("Failed to remove posts of deleted user")?
+            .rows_affected();
+
+            summary.comments_removed = sqlx::query!(
+                r#"
+                UPDATE comments
+                SET deleted_at = NOW()
+                WHERE created_by = $1 AND deleted_at IS NULL
+                "#,
+                user_id
+            )
+            .execute(&mut *transaction)
+            .await
+            .context("Failed to remove comments of deleted user")?
+            .rows_affected();
+        }
+        AccountDeletionPolicy::ReassignContent => {
+            summary.posts_reassigned = sqlx::query!(
+                r#"
+                UPDATE posts
+                SET created_by = $2
+                WHERE created_by = $1
+                "#,
+                user_id,
+                ANONYMOUS_USER_ID
+            )
+            .execute(&mut *transaction)
+            .await
+            .context("Failed to reassign posts of deleted user")?
+            .rows_affected();
+
+            summary.comments_reassigned = sqlx::query!(
+                r#"
+                UPDATE comments
+                SET created_by = $2, author_display_name = NULL
+                WHERE created_by = $1
+                "#,
+                user_id,
+                ANONYMOUS_USER_ID
+            )
+            .execute(&mut *transaction)
+            .await
+            .context("Failed to reassign comments of deleted user")?
+            .rows_affected();
+        }
+    }
+
+    summary.likes_withdrawn = sqlx::query!(
+        r#"
+        UPDATE posts
+        SET liked_by = array_remove(liked_by, $1)
+        WHERE $1 = ANY(liked_by)
+        "#,
+        user_id
+    )
+    .execute(&mut *transaction)
+    .await
+    .context("Failed to withdraw likes of deleted user")?
+    .rows_affected();
+
+    sqlx::query!(
+        r#"
+        WITH deleted_tokens AS (
+            DELETE FROM tokens WHERE user_id = $1
+        ), deleted_idempotency AS (
+            DELETE FROM idempotency WHERE user_id = $1
+        ), deleted_activation_reminders AS (
+            DELETE FROM activation_reminders WHERE user_id = $1
+        ), deleted_mentions AS (
+            DELETE FROM comment_mentions WHERE mentioned_user_id = $1
+        ), deleted_proposals AS (
+            DELETE FROM post_edit_proposals WHERE proposed_by = $1
+        ), deleted_reports AS (
+            DELETE FROM reports WHERE reporter_id = $1
+        ), deleted_follows AS (
+            DELETE FROM follows WHERE follower_id = $1 OR followed_id = $1
+        ), deleted_blocks AS (
+            DELETE FROM user_blocks WHERE blocker_id = $1 OR blocked_id = $1
+        ), deleted_conversations AS (
+            DELETE FROM conversations WHERE user_a = $1 OR user_b = $1
+        ), deleted_login_history AS (
+            DELETE FROM login_history WHERE user_id = $1
+        ), deleted_password_history AS (
+            DELETE FROM password_history WHERE user_id = $1
+        ), deleted_submissions AS (
+            DELETE FROM comment_submissions WHERE LOWER(author_email) = LOWER($2)
+        ), forgotten_deliveries AS (
+            DELETE FROM newsletter_delivery_log
+            WHERE LOWER(user_email) = LOWER($2)
+            RETURNING newsletter_issue_id, user_email, outcome
+        ), counted_deliveries AS (
+            UPDATE newsletter_issues n
+            SET forgotten_sent = n.forgotten_sent + c.sent,
+                forgotten_failed = n.forgotten_failed + c.failed,
+                forgotten_skipped = n.forgotten_skipped + c.skipped
+            FROM (
+                SELECT newsletter_issue_id,
+                       COUNT(*) FILTER (WHERE outcome = 'sent') AS sent,
+                       COUNT(*) FILTER (WHERE outcome = 'failed') AS failed,
+                       COUNT(*) FILTER (WHERE outcome = 'skipped') AS skipped
+                FROM forgotten_deliveries
+                GROUP BY newsletter_issue_id
+            ) c
+            WHERE n.id = c.newsletter_issue_id
+        ), deleted_delivery_failures AS (
+            DELETE FROM issue_delivery_failures WHERE LOWER(user_email) = LOWER($2)
+        ), deleted_tracking_tokens AS (
+            DELETE FROM newsletter_tracking_tokens t
+            WHERE LOWER(t.user_email) = LOWER($2)
+              AND NOT EXISTS (
+                  SELECT 1 FROM forgotten_deliveries d
+                  WHERE d.newsletter_issue_id = t.newsletter_issue_id
+                    AND d.user_email = t.user_email
+                    AND d.outcome = 'sent'
+              )
+        ), anonymized_tracking_tokens AS (
+            UPDATE newsletter_tracking_tokens t
+            SET user_email = NULL
+            FROM forgotten_deliveries d
+            WHERE d.newsletter_issue_id = t.newsletter_issue_id
+              AND d.user_email = t.user_email
+              AND d.outcome = 'sent'
+        )
+        DELETE FROM user_avatars WHERE user_id = $1
+        "#,
+        user_id,
+        user.email
+    )
+    .execute(&mut *transaction)
+    .await
+    .context("Failed to delete records of deleted user")?;
+
+    // Signed-in clients are logged out along with the account, whichever way they authenticate
+    repository::revoke_all_sessions(&mut transaction, user_id, None).await?;
+    repository::revoke_refresh_tokens_of_user(&mut transaction, user_id).await?;
+    repository::revoke_personal_access_tokens_of_user(&mut transaction, user_id).await?;
+
+    // The placeholder name and address keep both unique and can never be logged in with
+    sqlx::query!(
+        r#"
+        UPDATE users
+        SET user_name = 'deleted-' || id::TEXT,
+            email = id::TEXT || '@deleted.invalid',
+            password_hash = '!deleted',
+            is_activated = false,
+            is_subscribed = false,
+            bio = NULL,
+            website = NULL,
+            location = NULL,
+            avatar_updated_at = NULL,
+            deleted_at = NOW()
+        WHERE id = $1
+        "#,
+        user_id
+    )
+    .execute(&mut *transaction)
+    .await
+    .context("Failed to anonymize deleted user")?;
+
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit account deletion transaction")?;
+
+    tracing::info!(?summary, "Account deleted");
+    Ok(summary)
+}
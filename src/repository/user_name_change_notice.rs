@@ -0,0 +1,62 @@
+use std::ops::DerefMut;
+
+use anyhow::Context;
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::domain::UserNameChangeNotice;
+
+// Accounts that changed their name again since know it already and are not sent the notice
+#[tracing::instrument(skip(pool))]
+pub async fn get_unsent_user_name_change_notices(
+    pool: &PgPool,
+    limit: i64,
+) -> Result<Vec<UserNameChangeNotice>, anyhow::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT n.user_id, u.email, n.previous_user_name, n.user_name
+        FROM user_name_change_notices n
+        INNER JOIN users u ON u.id = n.user_id
+        WHERE n.sent_at IS NULL
+          AND u.user_name = n.user_name
+          AND u.deleted_at IS NULL
+        ORDER BY n.created_at ASC
+        LIMIT $1
+        "#,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch unsent user name change notices")?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| UserNameChangeNotice {
+            user_id: r.user_id,
+            email: r.email,
+            previous_user_name: r.previous_user_name,
+            user_name: r.user_name,
+        })
+        .collect())
+}
+
+// Returns `false` when the notice was already sent, e.g. by another worker instance
+#[tracing::instrument(skip(transaction))]
+pub async fn mark_user_name_change_notice_sent(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+) -> Result<bool, anyhow::Error> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE user_name_change_notices
+        SET sent_at = NOW()
+        WHERE user_id = $1 AND sent_at IS NULL
+        "#,
+        user_id
+    )
+    .execute(transaction.deref_mut())
+    .await
+    .context("Failed to mark user name change notice as sent")?;
+
+    Ok(result.rows_affected() == 1)
+}
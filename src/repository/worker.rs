@@ -0,0 +1,66 @@
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use tokio::time::Duration;
+use uuid::Uuid;
+
+use crate::domain::WorkerHeartbeat;
+
+#[tracing::instrument(skip(pool))]
+pub async fn record_worker_heartbeat(
+    pool: &PgPool,
+    worker_id: Uuid,
+    hostname: &str,
+    started_at: DateTime<Utc>,
+) -> Result<(), anyhow::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO worker_heartbeats (worker_id, hostname, started_at, last_seen_at)
+        VALUES ($1, $2, $3, NOW())
+        ON CONFLICT (worker_id) DO UPDATE SET last_seen_at = NOW()
+        "#,
+        worker_id,
+        hostname,
+        started_at
+    )
+    .execute(pool)
+    .await
+    .context("Failed to record worker heartbeat")?;
+
+    Ok(())
+}
+
+// A worker counts as alive while its last heartbeat is younger than `liveness_timeout`
+#[tracing::instrument(skip(pool))]
+pub async fn get_worker_heartbeats(
+    pool: &PgPool,
+    liveness_timeout: Duration,
+) -> Result<Vec<WorkerHeartbeat>, anyhow::Error> {
+    let workers = sqlx::query_as!(
+        WorkerHeartbeat,
+        r#"
+        SELECT worker_id, hostname, started_at, last_seen_at,
+               last_seen_at > NOW() - ($1 * INTERVAL '1 second') AS "is_alive!"
+        FROM worker_heartbeats
+        ORDER BY started_at DESC
+        "#,
+        liveness_timeout.as_secs_f64()
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch worker heartbeats")?;
+
+    Ok(workers)
+}
+
+pub async fn cleanup_stale_worker_heartbeats(pool: &PgPool) -> Result<(), anyhow::Error> {
+    let deleted = sqlx::query!(
+        r#"DELETE FROM worker_heartbeats WHERE last_seen_at < NOW() - INTERVAL '7 days'"#
+    )
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    tracing::info!(deleted, "Worker heartbeat cleanup completed");
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+use sqlx::PgPool;
+
+use crate::{
+    domain::{AuditLogFilter, GetAuditLogQuery},
+    repository, utils,
+};
+
+#[derive(thiserror::Error)]
+pub enum AuditLogError {
+    #[error("{0}")]
+    ValidationError(String),
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for AuditLogError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for AuditLogError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            AuditLogError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            AuditLogError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn get_audit_log(
+    query: web::Query<GetAuditLogQuery>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, AuditLogError> {
+    let filter: AuditLogFilter = query
+        .into_inner()
+        .try_into()
+        .map_err(AuditLogError::ValidationError)?;
+
+    let (events, total_records) = repository::get_audit_log(&filter, &pool).await?;
+
+    let metadata = filter.pagination.metadata(total_records);
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "events": events,
+        "metadata": metadata
+    })))
+}
@@ -0,0 +1,83 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+use sqlx::PgPool;
+
+use crate::{
+    domain::{ImportCommentLine, ImportCommentsQuery, ImportLineError, ImportedComment},
+    repository, utils,
+};
+
+// Larger exports should be split so a single import transaction stays short
+pub const MAX_IMPORT_LINES: usize = 10_000;
+pub const MAX_IMPORT_BYTES: usize = 10 * 1024 * 1024;
+
+#[derive(thiserror::Error)]
+pub enum CommentImportError {
+    #[error("{0}")]
+    ValidationError(String),
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for CommentImportError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for CommentImportError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            CommentImportError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            CommentImportError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+// Accepts NDJSON, one comment per line. Invalid lines are reported and skipped, the rest imported.
+#[tracing::instrument(skip(pool, body))]
+pub async fn import_comments(
+    query: web::Query<ImportCommentsQuery>,
+    body: String,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, CommentImportError> {
+    let lines: Vec<(usize, &str)> = body
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty())
+        .collect();
+
+    if lines.is_empty() {
+        return Err(CommentImportError::ValidationError(
+            "import must contain at least one comment".to_string(),
+        ));
+    }
+    if lines.len() > MAX_IMPORT_LINES {
+        return Err(CommentImportError::ValidationError(format!(
+            "import cannot exceed {MAX_IMPORT_LINES} comments"
+        )));
+    }
+
+    let mut comments = Vec::with_capacity(lines.len());
+    let mut errors = Vec::new();
+    for (line, raw) in lines {
+        let parsed = serde_json::from_str::<ImportCommentLine>(raw)
+            .map_err(|e| e.to_string())
+            .and_then(ImportedComment::try_from);
+        match parsed {
+            Ok(comment) => comments.push((line, comment)),
+            Err(error) => errors.push(ImportLineError { line, error }),
+        }
+    }
+
+    let mut report = repository::import_comments(comments, query.dry_run, &pool).await?;
+    report.errors.extend(errors);
+    report.errors.sort_by_key(|e| e.line);
+
+    Ok(HttpResponse::Ok().json(report))
+}
@@ -0,0 +1,4 @@
+mod import;
+mod moderation;
+pub use import::*;
+pub use moderation::*;
@@ -0,0 +1,70 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+use sqlx::PgPool;
+
+use crate::{
+    domain::{CommentIdBatch, ModerationCommentsQuery, ModerationFilters, RemoveCommentsPayload},
+    repository, utils,
+};
+
+#[derive(thiserror::Error)]
+pub enum CommentModerationError {
+    #[error("{0}")]
+    ValidationError(String),
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for CommentModerationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for CommentModerationError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            CommentModerationError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            CommentModerationError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn list_comments_for_moderation(
+    query: web::Query<ModerationCommentsQuery>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, CommentModerationError> {
+    let filters: ModerationFilters = query
+        .into_inner()
+        .try_into()
+        .map_err(CommentModerationError::ValidationError)?;
+
+    let (comments, total_records) =
+        repository::get_comments_for_moderation(&filters, &pool).await?;
+
+    let metadata = filters.pagination.metadata(total_records);
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "comments": comments,
+        "metadata": metadata
+    })))
+}
+
+#[tracing::instrument(skip(pool, payload))]
+pub async fn remove_comments(
+    payload: web::Json<RemoveCommentsPayload>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, CommentModerationError> {
+    let ids = CommentIdBatch::parse(payload.into_inner().comment_ids)
+        .map_err(CommentModerationError::ValidationError)?;
+
+    let removed = repository::remove_comments(&ids, &pool).await?;
+    tracing::info!(removed, "Comments removed by moderator");
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "removed": removed })))
+}
@@ -0,0 +1,88 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+use sqlx::PgPool;
+
+use crate::{
+    configuration::DatabaseMaintenanceSettings,
+    domain::{TableBloatReport, TableScanReport},
+    repository, utils,
+};
+
+#[derive(thiserror::Error)]
+pub enum DatabaseStatsError {
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for DatabaseStatsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for DatabaseStatsError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            DatabaseStatsError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn get_index_stats(pool: web::Data<PgPool>) -> Result<HttpResponse, DatabaseStatsError> {
+    let indexes = repository::get_index_usage(&pool).await?;
+    let unused_indexes: Vec<&str> = indexes
+        .iter()
+        .filter(|i| i.is_unused)
+        .map(|i| i.index.as_str())
+        .collect();
+
+    let tables: Vec<TableScanReport> = repository::get_table_scan_stats(&pool, &[])
+        .await?
+        .into_iter()
+        .map(TableScanReport::from)
+        .collect();
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "indexes": indexes,
+        "unused_indexes": unused_indexes,
+        "tables": tables,
+    })))
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn get_post_body_stats(
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, DatabaseStatsError> {
+    let stats = repository::get_post_body_stats(&pool).await?;
+    Ok(HttpResponse::Ok().json(stats))
+}
+
+#[tracing::instrument(skip(pool, settings))]
+pub async fn get_bloat_stats(
+    pool: web::Data<PgPool>,
+    settings: web::Data<DatabaseMaintenanceSettings>,
+) -> Result<HttpResponse, DatabaseStatsError> {
+    let tables: Vec<TableBloatReport> = repository::get_table_bloat_stats(&pool, &[])
+        .await?
+        .into_iter()
+        .map(|stats| {
+            TableBloatReport::new(stats, settings.max_dead_row_ratio, settings.min_dead_rows)
+        })
+        .collect();
+    let bloated_tables: Vec<&str> = tables
+        .iter()
+        .filter(|t| t.is_bloated)
+        .map(|t| t.stats.table.as_str())
+        .collect();
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "max_dead_row_ratio": settings.max_dead_row_ratio,
+        "min_dead_rows": settings.min_dead_rows,
+        "bloated_tables": bloated_tables,
+        "tables": tables,
+    })))
+}
@@ -0,0 +1,73 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+use serde::Deserialize;
+
+use crate::{
+    telemetry::{self, LogFilterError},
+    utils,
+};
+
+#[derive(thiserror::Error)]
+pub enum LoggingError {
+    #[error("{0}")]
+    ValidationError(String),
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for LoggingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for LoggingError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            LoggingError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            LoggingError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+impl From<LogFilterError> for LoggingError {
+    fn from(e: LogFilterError) -> Self {
+        match e {
+            LogFilterError::InvalidDirectives(_) => LoggingError::ValidationError(e.to_string()),
+            LogFilterError::UnexpectedError(e) => LoggingError::UnexpectedError(e),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct LogFilterPayload {
+    pub filter: String,
+}
+
+pub async fn get_log_filter() -> Result<HttpResponse, LoggingError> {
+    let filter = telemetry::current_log_filter()?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "filter": filter })))
+}
+
+// The change only applies to this instance and lasts until it restarts
+#[tracing::instrument]
+pub async fn set_log_filter(
+    payload: web::Json<LogFilterPayload>,
+) -> Result<HttpResponse, LoggingError> {
+    let directives = payload.filter.trim();
+    if directives.is_empty() {
+        return Err(LoggingError::ValidationError(
+            "log filter cannot be empty".to_string(),
+        ));
+    }
+
+    telemetry::set_log_filter(directives)?;
+    let filter = telemetry::current_log_filter()?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "filter": filter })))
+}
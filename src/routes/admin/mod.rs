@@ -1,7 +1,25 @@
+mod audit;
+mod comments;
+mod database;
+mod logging;
 mod newsletter;
 mod posts;
+mod reports;
 mod routes;
+mod submissions;
+mod suppressions;
+mod users;
+mod workers;
 
+pub use audit::*;
+pub use comments::*;
+pub use database::*;
+pub use logging::*;
 pub use newsletter::*;
 pub use posts::*;
+pub use reports::*;
 pub use routes::*;
+pub use submissions::*;
+pub use suppressions::*;
+pub use users::*;
+pub use workers::*;
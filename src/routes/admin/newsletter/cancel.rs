@@ -0,0 +1,79 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+use anyhow::Context;
+use sqlx::PgPool;
+
+use crate::{
+    authentication::UserId, domain::AuditAction, repository, routes::NewsletterPathParams, utils,
+};
+
+#[derive(thiserror::Error)]
+pub enum CancelNewsletterError {
+    #[error("newsletter issue not found")]
+    NotFound,
+
+    #[error("drafts have nothing queued, only published issues can be cancelled")]
+    NotPublished,
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for CancelNewsletterError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for CancelNewsletterError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            CancelNewsletterError::NotFound => StatusCode::NOT_FOUND,
+            CancelNewsletterError::NotPublished => StatusCode::CONFLICT,
+            CancelNewsletterError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+// For an issue published by mistake. Recipients it was already sent to are not in the queue
+// anymore, and the fan-out stops so the rest are never enqueued.
+#[tracing::instrument(skip(pool), fields(user_id=%&*user_id, issue_id=%path.id))]
+pub async fn cancel_newsletter_issue(
+    path: web::Path<NewsletterPathParams>,
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, CancelNewsletterError> {
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+
+    let Some((title, removed)) =
+        repository::cancel_newsletter_issue(&mut transaction, path.id).await?
+    else {
+        return match repository::newsletter_issue_exists(&pool, path.id).await? {
+            true => Err(CancelNewsletterError::NotPublished),
+            false => Err(CancelNewsletterError::NotFound),
+        };
+    };
+    repository::record_audit_event(
+        &mut *transaction,
+        AuditAction::NewsletterCancel,
+        Some(**user_id),
+        Some(path.id),
+        serde_json::json!({ "title": title, "removed_deliveries": removed }),
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to cancel a newsletter issue")?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "issue_id": path.id,
+        "removed_deliveries": removed,
+    })))
+}
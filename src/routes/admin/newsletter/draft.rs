@@ -0,0 +1,158 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+use anyhow::Context;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    authentication::UserId,
+    domain::{AuditAction, NewsLetterData, Newsletter},
+    repository,
+    routes::NewsletterPathParams,
+    utils,
+};
+
+#[derive(thiserror::Error)]
+pub enum NewsletterDraftError {
+    #[error("{0}")]
+    ValidationError(String),
+
+    #[error("newsletter issue not found")]
+    NotFound,
+
+    #[error("newsletter issue has already been published")]
+    AlreadyPublished,
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for NewsletterDraftError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for NewsletterDraftError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            NewsletterDraftError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            NewsletterDraftError::NotFound => StatusCode::NOT_FOUND,
+            NewsletterDraftError::AlreadyPublished => StatusCode::CONFLICT,
+            NewsletterDraftError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+// Saved without sending anything, see `publish_newsletter_draft`
+#[tracing::instrument(skip_all, fields(user_id=%&*user_id))]
+pub async fn create_newsletter_draft(
+    payload: web::Json<NewsLetterData>,
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, NewsletterDraftError> {
+    let newsletter: Newsletter = payload
+        .0
+        .try_into()
+        .map_err(NewsletterDraftError::ValidationError)?;
+
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+    let issue_id = repository::insert_newsletter_draft(
+        &mut transaction,
+        newsletter.title.as_ref(),
+        newsletter.content.text.as_ref(),
+        newsletter.content.html.as_ref(),
+    )
+    .await?;
+    repository::set_newsletter_attachments(&mut transaction, issue_id, &newsletter.attachments)
+        .await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to store a newsletter draft")?;
+
+    Ok(HttpResponse::Created().json(serde_json::json!({ "issue_id": issue_id })))
+}
+
+// Replaces the title, content and attachments, only while the issue is a draft
+#[tracing::instrument(skip(payload, pool), fields(issue_id=%path.id))]
+pub async fn update_newsletter_draft(
+    path: web::Path<NewsletterPathParams>,
+    payload: web::Json<NewsLetterData>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, NewsletterDraftError> {
+    let newsletter: Newsletter = payload
+        .0
+        .try_into()
+        .map_err(NewsletterDraftError::ValidationError)?;
+
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+    let updated = repository::update_newsletter_draft(
+        &mut transaction,
+        path.id,
+        newsletter.title.as_ref(),
+        newsletter.content.text.as_ref(),
+        newsletter.content.html.as_ref(),
+    )
+    .await?;
+    if !updated {
+        return Err(not_a_draft(&pool, path.id).await);
+    }
+    repository::set_newsletter_attachments(&mut transaction, path.id, &newsletter.attachments)
+        .await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to update a newsletter draft")?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "issue_id": path.id })))
+}
+
+// Delivery tasks are enqueued by the worker in chunks, see `repository::fan_out_next_chunk`.
+// Publishing twice is refused, so a retried request never sends the issue again.
+#[tracing::instrument(skip(pool), fields(user_id=%&*user_id, issue_id=%path.id))]
+pub async fn publish_newsletter_draft(
+    path: web::Path<NewsletterPathParams>,
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, NewsletterDraftError> {
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+
+    let Some(title) = repository::publish_newsletter_draft(&mut transaction, path.id).await? else {
+        return Err(not_a_draft(&pool, path.id).await);
+    };
+    repository::record_audit_event(
+        &mut *transaction,
+        AuditAction::NewsletterPublish,
+        Some(**user_id),
+        Some(path.id),
+        serde_json::json!({ "title": title }),
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to publish a newsletter draft")?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "issue_id": path.id })))
+}
+
+async fn not_a_draft(pool: &PgPool, issue_id: Uuid) -> NewsletterDraftError {
+    match repository::newsletter_issue_exists(pool, issue_id).await {
+        Ok(true) => NewsletterDraftError::AlreadyPublished,
+        Ok(false) => NewsletterDraftError::NotFound,
+        Err(e) => e.into(),
+    }
+}
@@ -0,0 +1,55 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+use sqlx::PgPool;
+
+use crate::{
+    domain::{PageQuery, Pagination},
+    repository, utils,
+};
+
+#[derive(thiserror::Error)]
+pub enum NewsletterListError {
+    #[error("{0}")]
+    ValidationError(String),
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for NewsletterListError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for NewsletterListError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            NewsletterListError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            NewsletterListError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn list_newsletter_issues(
+    query: web::Query<PageQuery>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, NewsletterListError> {
+    let page: Pagination = query
+        .into_inner()
+        .try_into()
+        .map_err(NewsletterListError::ValidationError)?;
+
+    let (issues, total_records) = repository::get_newsletter_issues(&page, &pool).await?;
+
+    let metadata = page.metadata(total_records);
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "issues": issues,
+        "metadata": metadata
+    })))
+}
@@ -1,2 +1,22 @@
+mod cancel;
+mod draft;
+mod list;
+mod preview;
 mod publish;
+mod retry_failed;
+mod schedule;
+mod segment;
+mod status;
+mod subscriber_import;
+mod test_send;
+pub use cancel::*;
+pub use draft::*;
+pub use list::*;
+pub use preview::preview_newsletter;
 pub use publish::publish_newsletter;
+pub use retry_failed::*;
+pub use schedule::*;
+pub use segment::*;
+pub use status::*;
+pub use subscriber_import::*;
+pub use test_send::*;
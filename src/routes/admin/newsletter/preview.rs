@@ -0,0 +1,84 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+use sqlx::PgPool;
+
+use crate::{
+    authentication::UserId,
+    domain::{NewsLetterData, Newsletter, NewsletterIssue, RecipientPlaceholders},
+    repository,
+    startup::ApplicationBaseUrl,
+    utils,
+};
+
+#[derive(thiserror::Error)]
+pub enum NewsletterPreviewError {
+    #[error("{0}")]
+    ValidationError(String),
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for NewsletterPreviewError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for NewsletterPreviewError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            NewsletterPreviewError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            NewsletterPreviewError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+// The issue as it would reach the admin were they a subscriber. The unsubscribe link does not
+// unsubscribe anyone, and nothing is tracked.
+pub(super) fn render_for_admin(
+    issue: NewsletterIssue,
+    user_name: String,
+    base_url: &str,
+) -> NewsletterIssue {
+    let unsubscribe_url = format!("{base_url}/v1/user/unsubscribe?token=preview");
+    let placeholders = RecipientPlaceholders {
+        user_name,
+        unsubscribe_url: unsubscribe_url.clone(),
+    };
+    issue
+        .with_unsubscribe_link(&unsubscribe_url)
+        .with_placeholders(&placeholders)
+}
+
+// Validates and renders content the same way publishing does, without storing anything, so
+// editors can show a live preview
+#[tracing::instrument(skip_all, fields(user_id=%&*user_id))]
+pub async fn preview_newsletter(
+    payload: web::Json<NewsLetterData>,
+    pool: web::Data<PgPool>,
+    base_url: web::Data<ApplicationBaseUrl>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, NewsletterPreviewError> {
+    let newsletter: Newsletter = payload
+        .into_inner()
+        .try_into()
+        .map_err(NewsletterPreviewError::ValidationError)?;
+
+    let issue = NewsletterIssue::new(
+        newsletter.title.as_ref().to_string(),
+        newsletter.content.text.as_ref().to_string(),
+        newsletter.content.html.as_ref().to_string(),
+    );
+    let user_name = repository::get_username(**user_id, &pool).await?;
+    let issue = render_for_admin(issue, user_name, &base_url.0);
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "title": issue.title(),
+        "html": issue.html_content(),
+        "text": issue.text_content(),
+    })))
+}
@@ -1,14 +1,19 @@
 use std::fmt::{self, Debug, Formatter};
 
 use actix_web::{HttpRequest, HttpResponse, ResponseError, http::StatusCode, web};
+use chrono::Utc;
 use sqlx::PgPool;
 
 use crate::{
     authentication::UserId,
-    domain::{NewsLetterData, Newsletter},
+    domain::{
+        AuditAction, Newsletter, PublishNewsletterPayload, validate_alternative_title,
+        validate_scheduled_at,
+    },
     idempotency,
     idempotency::{IdempotencyKey, NextAction},
     repository, utils,
+    utils::ErrorResponse,
 };
 
 #[derive(thiserror::Error)]
@@ -22,6 +27,11 @@ pub enum PublishError {
     #[error("Invalid request: {0}")]
     BadRequest(#[source] anyhow::Error),
 
+    #[error(
+        "the issue published with this idempotency key no longer exists, retry with a new key to publish it again"
+    )]
+    IdempotencyConflict,
+
     #[error(transparent)]
     UnexpectedError(#[from] anyhow::Error),
 }
@@ -38,6 +48,13 @@ impl ResponseError for PublishError {
             PublishError::ValidationError(_) => StatusCode::BAD_REQUEST,
             PublishError::AuthError(_) => StatusCode::UNAUTHORIZED,
             PublishError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            PublishError::IdempotencyConflict => {
+                return HttpResponse::Conflict().json(ErrorResponse {
+                    code: StatusCode::CONFLICT.as_u16(),
+                    message: self.to_string(),
+                    error: Some("idempotency_conflict"),
+                });
+            }
             PublishError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
         };
 
@@ -51,16 +68,36 @@ impl ResponseError for PublishError {
 )]
 pub async fn publish_newsletter(
     req: HttpRequest,
-    payload: web::Json<NewsLetterData>,
+    payload: web::Json<PublishNewsletterPayload>,
     pool: web::Data<PgPool>,
     user_id: web::ReqData<UserId>,
 ) -> Result<HttpResponse, PublishError> {
     let user_id = user_id.into_inner();
 
-    let newsletter: Newsletter = payload
-        .0
+    let PublishNewsletterPayload {
+        newsletter,
+        alternative_title,
+        scheduled_at,
+        segment_id,
+    } = payload.into_inner();
+    let newsletter: Newsletter = newsletter
         .try_into()
         .map_err(PublishError::ValidationError)?;
+    let alternative_title = alternative_title
+        .map(|title| validate_alternative_title(title, &newsletter.title))
+        .transpose()
+        .map_err(PublishError::ValidationError)?;
+    let scheduled_at = scheduled_at
+        .map(|at| validate_scheduled_at(at, Utc::now()))
+        .transpose()
+        .map_err(PublishError::ValidationError)?;
+    if let Some(segment_id) = segment_id
+        && !repository::newsletter_segment_exists(&pool, segment_id).await?
+    {
+        return Err(PublishError::ValidationError(
+            "Invalid segment_id: no such segment.".to_string(),
+        ));
+    }
 
     let idempotency_key = req
         .headers()
@@ -76,23 +113,54 @@ pub async fn publish_newsletter(
     let mut transaction =
         match idempotency::try_processing(&pool, &idempotency_key, *user_id).await? {
             NextAction::StartProcessing(t) => t,
-            NextAction::ReturnSavedResponse(saved_response) => {
-                return Ok(saved_response);
+            NextAction::ReturnSavedResponse(saved) => {
+                // Issues are cleaned up after a week, replaying would hand out a dangling id
+                if let Some(issue_id) = saved.resource_id
+                    && !repository::newsletter_issue_exists(&pool, issue_id).await?
+                {
+                    return Err(PublishError::IdempotencyConflict);
+                }
+                return Ok(saved.response);
             }
         };
 
     let issue_id = repository::insert_newsletter_issue(
         &mut transaction,
         newsletter.title.as_ref(),
+        alternative_title.as_ref().map(AsRef::as_ref),
         newsletter.content.text.as_ref(),
         newsletter.content.html.as_ref(),
+        scheduled_at,
+        segment_id,
+    )
+    .await?;
+    repository::set_newsletter_attachments(&mut transaction, issue_id, &newsletter.attachments)
+        .await?;
+    repository::record_audit_event(
+        &mut *transaction,
+        AuditAction::NewsletterPublish,
+        Some(*user_id),
+        Some(issue_id),
+        serde_json::json!({
+            "title": newsletter.title.as_ref(),
+            "alternative_title": alternative_title.as_ref().map(AsRef::<str>::as_ref),
+            "scheduled_at": scheduled_at,
+            "segment_id": segment_id,
+            "attachments": newsletter.attachments.iter().map(|a| a.name()).collect::<Vec<_>>(),
+        }),
     )
     .await?;
 
-    repository::enqueue_delivery_tasks(&mut transaction, issue_id).await?;
-
-    let response = HttpResponse::Ok().finish();
-    let response =
-        idempotency::save_response(transaction, &idempotency_key, *user_id, response).await?;
+    // Delivery tasks are enqueued by the worker in chunks once `scheduled_at` has passed, see
+    // `repository::fan_out_next_chunk`
+    let response = HttpResponse::Ok().json(serde_json::json!({ "issue_id": issue_id }));
+    let response = idempotency::save_response(
+        transaction,
+        &idempotency_key,
+        *user_id,
+        Some(issue_id),
+        response,
+    )
+    .await?;
     Ok(response)
 }
@@ -0,0 +1,68 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+use anyhow::Context;
+use sqlx::PgPool;
+
+use crate::{authentication::UserId, repository, routes::NewsletterPathParams, utils};
+
+#[derive(thiserror::Error)]
+pub enum RetryFailedDeliveriesError {
+    #[error("newsletter issue not found")]
+    NotFound,
+
+    #[error("only published issues that were not cancelled can be retried")]
+    NotDeliverable,
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for RetryFailedDeliveriesError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for RetryFailedDeliveriesError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            RetryFailedDeliveriesError::NotFound => StatusCode::NOT_FOUND,
+            RetryFailedDeliveriesError::NotDeliverable => StatusCode::CONFLICT,
+            RetryFailedDeliveriesError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+// Once whatever made the deliveries fail is fixed, e.g. an outage of the email provider. Only the
+// recipients in the dead-letter queue are sent the issue again, never those who already got it.
+#[tracing::instrument(skip(pool), fields(user_id=%&*user_id, issue_id=%path.id))]
+pub async fn retry_failed_newsletter_deliveries(
+    path: web::Path<NewsletterPathParams>,
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, RetryFailedDeliveriesError> {
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+
+    let Some(requeued) = repository::requeue_failed_deliveries(&mut transaction, path.id).await?
+    else {
+        return match repository::newsletter_issue_exists(&pool, path.id).await? {
+            true => Err(RetryFailedDeliveriesError::NotDeliverable),
+            false => Err(RetryFailedDeliveriesError::NotFound),
+        };
+    };
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to retry failed newsletter deliveries")?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "issue_id": path.id,
+        "requeued_deliveries": requeued,
+    })))
+}
@@ -0,0 +1,68 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+use chrono::Utc;
+use sqlx::PgPool;
+
+use crate::{
+    domain::{ReschedulePayload, validate_scheduled_at},
+    repository,
+    routes::NewsletterPathParams,
+    utils,
+};
+
+#[derive(thiserror::Error)]
+pub enum RescheduleError {
+    #[error("{0}")]
+    ValidationError(String),
+
+    #[error("newsletter issue not found")]
+    NotFound,
+
+    #[error("only scheduled issues that have not started sending can be rescheduled")]
+    NotScheduled,
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for RescheduleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for RescheduleError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            RescheduleError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            RescheduleError::NotFound => StatusCode::NOT_FOUND,
+            RescheduleError::NotScheduled => StatusCode::CONFLICT,
+            RescheduleError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+#[tracing::instrument(skip(pool), fields(issue_id=%path.id))]
+pub async fn reschedule_newsletter(
+    path: web::Path<NewsletterPathParams>,
+    payload: web::Json<ReschedulePayload>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, RescheduleError> {
+    let scheduled_at = validate_scheduled_at(payload.scheduled_at, Utc::now())
+        .map_err(RescheduleError::ValidationError)?;
+
+    if !repository::reschedule_newsletter_issue(&pool, path.id, scheduled_at).await? {
+        return match repository::newsletter_issue_exists(&pool, path.id).await? {
+            true => Err(RescheduleError::NotScheduled),
+            false => Err(RescheduleError::NotFound),
+        };
+    }
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "issue_id": path.id,
+        "scheduled_at": scheduled_at,
+    })))
+}
@@ -0,0 +1,66 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+use sqlx::PgPool;
+
+use crate::{
+    domain::{CreateSegmentPayload, NewsletterSegment},
+    repository, utils,
+};
+
+#[derive(thiserror::Error)]
+pub enum NewsletterSegmentError {
+    #[error("{0}")]
+    ValidationError(String),
+
+    #[error("a segment with this name already exists")]
+    NameTaken,
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for NewsletterSegmentError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for NewsletterSegmentError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            NewsletterSegmentError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            NewsletterSegmentError::NameTaken => StatusCode::CONFLICT,
+            NewsletterSegmentError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+// Issues are targeted at a segment with the `segment_id` of `publish_newsletter`
+#[tracing::instrument(skip(pool))]
+pub async fn create_newsletter_segment(
+    payload: web::Json<CreateSegmentPayload>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, NewsletterSegmentError> {
+    let segment: NewsletterSegment = payload
+        .into_inner()
+        .try_into()
+        .map_err(NewsletterSegmentError::ValidationError)?;
+
+    let segment_id = repository::insert_newsletter_segment(&pool, &segment)
+        .await?
+        .ok_or(NewsletterSegmentError::NameTaken)?;
+
+    Ok(HttpResponse::Created().json(serde_json::json!({ "segment_id": segment_id })))
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn list_newsletter_segments(
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, NewsletterSegmentError> {
+    let segments = repository::list_newsletter_segments(&pool).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "segments": segments })))
+}
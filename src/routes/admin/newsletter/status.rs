@@ -0,0 +1,51 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+use serde::Deserialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{repository, utils};
+
+#[derive(thiserror::Error)]
+pub enum NewsletterStatusError {
+    #[error("newsletter issue not found")]
+    NotFound,
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for NewsletterStatusError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for NewsletterStatusError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            NewsletterStatusError::NotFound => StatusCode::NOT_FOUND,
+            NewsletterStatusError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct NewsletterPathParams {
+    pub id: Uuid,
+}
+
+#[tracing::instrument(skip(pool), fields(issue_id=%path.id))]
+pub async fn get_newsletter_status(
+    path: web::Path<NewsletterPathParams>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, NewsletterStatusError> {
+    let status = repository::get_newsletter_issue_status(&pool, path.id)
+        .await?
+        .ok_or(NewsletterStatusError::NotFound)?;
+
+    Ok(HttpResponse::Ok().json(status))
+}
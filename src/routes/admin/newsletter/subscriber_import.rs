@@ -0,0 +1,187 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+use anyhow::Context;
+use sqlx::PgPool;
+
+use crate::{
+    domain::{
+        ImportSubscribersQuery, SubscriberImportReport, SubscriberImportRow,
+        SubscriberImportStatus, UserEmail, UserName, parse_subscriber_csv,
+    },
+    email_client::{BatchEmail, EmailClient, MAX_BATCH_SIZE},
+    repository,
+    routes::{SUBSCRIPTION_EMAIL_SUBJECT, subscription_email_content},
+    startup::ApplicationBaseUrl,
+    utils,
+};
+
+// Larger lists should be split so a single import transaction stays short
+pub const MAX_SUBSCRIBER_IMPORT_ROWS: usize = 10_000;
+pub const MAX_SUBSCRIBER_IMPORT_BYTES: usize = 2 * 1024 * 1024;
+
+#[derive(thiserror::Error)]
+pub enum SubscriberImportError {
+    #[error("{0}")]
+    ValidationError(String),
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for SubscriberImportError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for SubscriberImportError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            SubscriberImportError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            SubscriberImportError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+// A confirmation email still to be sent for an imported row
+struct PendingConfirmation {
+    position: usize,
+    email: UserEmail,
+    token: String,
+}
+
+// Accepts CSV with the email in the first column. Invalid rows and addresses that already belong
+// to an account are reported and skipped, the rest imported.
+#[tracing::instrument(skip(pool, email_client, base_url, body))]
+pub async fn import_subscribers(
+    query: web::Query<ImportSubscribersQuery>,
+    body: String,
+    pool: web::Data<PgPool>,
+    email_client: web::Data<EmailClient>,
+    base_url: web::Data<ApplicationBaseUrl>,
+) -> Result<HttpResponse, SubscriberImportError> {
+    let rows = parse_subscriber_csv(&body);
+    if rows.is_empty() {
+        return Err(SubscriberImportError::ValidationError(
+            "import must contain at least one email".to_string(),
+        ));
+    }
+    if rows.len() > MAX_SUBSCRIBER_IMPORT_ROWS {
+        return Err(SubscriberImportError::ValidationError(format!(
+            "import cannot exceed {MAX_SUBSCRIBER_IMPORT_ROWS} emails"
+        )));
+    }
+
+    let double_opt_in = query.double_opt_in;
+    let mut results = Vec::with_capacity(rows.len());
+    let mut confirmations = Vec::new();
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to start a transaction")?;
+
+    for (row, raw) in rows {
+        let parsed =
+            UserEmail::parse(raw.clone()).and_then(|email| Ok((stub_user_name(&email)?, email)));
+        let (user_name, email) = match parsed {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                results.push(SubscriberImportRow {
+                    row,
+                    email: raw,
+                    status: SubscriberImportStatus::Invalid,
+                    error: Some(error),
+                });
+                continue;
+            }
+        };
+
+        let user_id = repository::insert_imported_subscriber(
+            email.as_ref(),
+            &user_name,
+            !double_opt_in,
+            &mut transaction,
+        )
+        .await?;
+
+        let row_email = email.as_ref().to_lowercase();
+        let status = match user_id {
+            None => SubscriberImportStatus::Existing,
+            Some(_) if !double_opt_in => SubscriberImportStatus::Subscribed,
+            Some(user_id) => {
+                let token = utils::generate_token();
+                repository::store_subscription_token(&mut *transaction, user_id, &token).await?;
+                confirmations.push(PendingConfirmation {
+                    position: results.len(),
+                    email,
+                    token,
+                });
+                SubscriberImportStatus::PendingConfirmation
+            }
+        };
+        results.push(SubscriberImportRow {
+            row,
+            email: row_email,
+            status,
+            error: None,
+        });
+    }
+
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit subscriber import")?;
+
+    send_confirmations(&email_client, &base_url.0, &confirmations, &mut results).await;
+
+    Ok(HttpResponse::Ok().json(SubscriberImportReport::new(double_opt_in, results)))
+}
+
+// The local part of the address, the only name known for an imported subscriber
+fn stub_user_name(email: &UserEmail) -> Result<UserName, String> {
+    let local_part = email.as_ref().split('@').next().unwrap_or_default();
+    UserName::parse(local_part.to_string())
+}
+
+// Subscribers stay imported when their confirmation could not be sent, the failure is reported
+// on their row instead
+async fn send_confirmations(
+    email_client: &EmailClient,
+    base_url: &str,
+    confirmations: &[PendingConfirmation],
+    results: &mut [SubscriberImportRow],
+) {
+    for chunk in confirmations.chunks(MAX_BATCH_SIZE) {
+        let contents: Vec<(String, String)> = chunk
+            .iter()
+            .map(|c| subscription_email_content(base_url, &c.token))
+            .collect();
+        let emails: Vec<BatchEmail> = chunk
+            .iter()
+            .zip(&contents)
+            .map(|(c, (html, text))| BatchEmail {
+                recipient: &c.email,
+                subject: SUBSCRIPTION_EMAIL_SUBJECT,
+                html_content: html,
+                text_content: text,
+            })
+            .collect();
+
+        let outcomes = match email_client.send_batch(&emails).await {
+            Ok(outcomes) => outcomes,
+            Err(e) => {
+                tracing::error!(error.cause_chain = ?e, "Failed to send subscription emails");
+                vec![Err(e.to_string()); chunk.len()]
+            }
+        };
+        for (confirmation, outcome) in chunk.iter().zip(outcomes) {
+            if let Err(e) = outcome {
+                results[confirmation.position].error =
+                    Some(format!("confirmation email could not be sent: {e}"));
+            }
+        }
+    }
+}
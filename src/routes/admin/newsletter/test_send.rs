@@ -0,0 +1,75 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+use anyhow::Context;
+use sqlx::PgPool;
+
+use super::preview::render_for_admin;
+use crate::{
+    authentication::UserId, domain::UserEmail, email_client::EmailClient, repository,
+    routes::NewsletterPathParams, startup::ApplicationBaseUrl, utils,
+};
+
+#[derive(thiserror::Error)]
+pub enum TestSendError {
+    #[error("newsletter issue not found")]
+    NotFound,
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for TestSendError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for TestSendError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            TestSendError::NotFound => StatusCode::NOT_FOUND,
+            TestSendError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+// Sends the issue, drafts included, to the requesting admin alone, see `render_for_admin`.
+// Nothing is queued, so the issue's delivery status and stats are left as they are.
+#[tracing::instrument(skip(pool, email_client, base_url), fields(user_id=%&*user_id, issue_id=%path.id))]
+pub async fn test_send_newsletter(
+    path: web::Path<NewsletterPathParams>,
+    pool: web::Data<PgPool>,
+    email_client: web::Data<EmailClient>,
+    base_url: web::Data<ApplicationBaseUrl>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, TestSendError> {
+    let issue = repository::find_newsletter_issue(&pool, path.id)
+        .await?
+        .ok_or(TestSendError::NotFound)?;
+
+    let email = repository::get_user_email(**user_id, &pool).await?;
+    let recipient = UserEmail::parse(email.clone())
+        .map_err(|e| anyhow::anyhow!(e))
+        .context("Admin email is invalid")?;
+    let user_name = repository::get_username(**user_id, &pool).await?;
+    let issue = render_for_admin(issue, user_name, &base_url.0);
+
+    email_client
+        .send_email_with_attachments(
+            &recipient,
+            &format!("[Test] {}", issue.title()),
+            issue.html_content(),
+            issue.text_content(),
+            issue.attachments(),
+        )
+        .await
+        .context("Failed to send a test newsletter")?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "issue_id": path.id,
+        "sent_to": email,
+    })))
+}
@@ -2,6 +2,7 @@ use actix_web::{HttpResponse, web};
 use sqlx::PgPool;
 
 use crate::{
+    authentication::UserId,
     repository,
     routes::{PostError, PostPathParams},
 };
@@ -9,10 +10,11 @@ use crate::{
 pub async fn hard_delete_post(
     path: web::Path<PostPathParams>,
     pool: web::Data<PgPool>,
+    admin_id: web::ReqData<UserId>,
 ) -> Result<HttpResponse, PostError> {
     let post_id = path.id;
 
-    let deleted = repository::hard_delete_post(post_id, &pool).await?;
+    let deleted = repository::hard_delete_post(post_id, **admin_id, &pool).await?;
     if !deleted {
         return Err(PostError::NotFound);
     }
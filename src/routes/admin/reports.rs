@@ -0,0 +1,55 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+use sqlx::PgPool;
+
+use crate::{
+    domain::{PageQuery, Pagination},
+    repository, utils,
+};
+
+#[derive(thiserror::Error)]
+pub enum ReportReviewError {
+    #[error("{0}")]
+    ValidationError(String),
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for ReportReviewError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for ReportReviewError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            ReportReviewError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            ReportReviewError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn list_comment_reports(
+    query: web::Query<PageQuery>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, ReportReviewError> {
+    let page: Pagination = query
+        .into_inner()
+        .try_into()
+        .map_err(ReportReviewError::ValidationError)?;
+
+    let (reports, total_records) = repository::get_comment_reports(&page, &pool).await?;
+
+    let metadata = page.metadata(total_records);
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "reports": reports,
+        "metadata": metadata
+    })))
+}
@@ -1,18 +1,250 @@
-use actix_web::{middleware, web};
+use actix_web::{Route, middleware, web};
 
-use crate::{authentication, routes};
+use crate::{
+    authentication,
+    domain::{MAX_NEWSLETTER_PAYLOAD_BYTES, Permission},
+    routes,
+};
+
+// Restricts `route` to the roles with `permission`
+fn restricted(permission: Permission, route: Route) -> Route {
+    route.wrap(middleware::from_fn(move |req, next| {
+        authentication::require_permission(permission, req, next)
+    }))
+}
 
 pub fn admin_routes(cfg: &mut web::ServiceConfig) {
+    use Permission::*;
+
     cfg.service(
         web::scope("/me")
-            .wrap(middleware::from_fn(authentication::reject_non_admin_users))
+            .wrap(middleware::from_fn(authentication::reject_anonymous_users))
+            // Newsletter issues carry their attachments inline
+            .app_data(web::JsonConfig::default().limit(MAX_NEWSLETTER_PAYLOAD_BYTES))
+            .route(
+                "/newsletters",
+                restricted(
+                    PublishNewsletters,
+                    web::get().to(routes::list_newsletter_issues),
+                ),
+            )
+            .route(
+                "/newsletters",
+                restricted(
+                    PublishNewsletters,
+                    web::post().to(routes::create_newsletter_draft),
+                ),
+            )
             .route(
                 "/newsletters/publish",
-                web::post().to(routes::publish_newsletter),
+                restricted(
+                    PublishNewsletters,
+                    web::post().to(routes::publish_newsletter),
+                ),
+            )
+            .route(
+                "/newsletters/preview",
+                restricted(
+                    PublishNewsletters,
+                    web::post().to(routes::preview_newsletter),
+                ),
+            )
+            .route(
+                "/newsletters/segments",
+                restricted(
+                    PublishNewsletters,
+                    web::get().to(routes::list_newsletter_segments),
+                ),
+            )
+            .route(
+                "/newsletters/segments",
+                restricted(
+                    PublishNewsletters,
+                    web::post().to(routes::create_newsletter_segment),
+                ),
+            )
+            .route(
+                "/newsletters/{id}",
+                restricted(
+                    PublishNewsletters,
+                    web::put().to(routes::update_newsletter_draft),
+                ),
+            )
+            .route(
+                "/newsletters/{id}/publish",
+                restricted(
+                    PublishNewsletters,
+                    web::post().to(routes::publish_newsletter_draft),
+                ),
+            )
+            .route(
+                "/newsletters/{id}/schedule",
+                restricted(
+                    PublishNewsletters,
+                    web::put().to(routes::reschedule_newsletter),
+                ),
+            )
+            .route(
+                "/newsletters/{id}/queue",
+                restricted(
+                    PublishNewsletters,
+                    web::delete().to(routes::cancel_newsletter_issue),
+                ),
+            )
+            .route(
+                "/newsletters/{id}/retry-failed",
+                restricted(
+                    PublishNewsletters,
+                    web::post().to(routes::retry_failed_newsletter_deliveries),
+                ),
+            )
+            .route(
+                "/newsletters/{id}/test-send",
+                restricted(
+                    PublishNewsletters,
+                    web::post().to(routes::test_send_newsletter),
+                ),
+            )
+            .route(
+                "/newsletters/{id}/status",
+                restricted(
+                    PublishNewsletters,
+                    web::get().to(routes::get_newsletter_status),
+                ),
+            )
+            .route(
+                "/suppressions",
+                restricted(
+                    PublishNewsletters,
+                    web::get().to(routes::list_suppressed_emails),
+                ),
+            )
+            .route(
+                "/suppressions",
+                restricted(
+                    PublishNewsletters,
+                    web::post().to(routes::add_suppressed_email),
+                ),
+            )
+            .route(
+                "/suppressions/{email}",
+                restricted(
+                    PublishNewsletters,
+                    web::delete().to(routes::remove_suppressed_email),
+                ),
+            )
+            .service(
+                web::resource("/subscribers/import")
+                    .app_data(web::PayloadConfig::new(routes::MAX_SUBSCRIBER_IMPORT_BYTES))
+                    .route(restricted(
+                        PublishNewsletters,
+                        web::post().to(routes::import_subscribers),
+                    )),
             )
             .route(
                 "/posts/delete/{id}",
-                web::delete().to(routes::hard_delete_post),
+                restricted(ManagePosts, web::delete().to(routes::hard_delete_post)),
+            )
+            .route(
+                "/comments",
+                restricted(
+                    ModerateComments,
+                    web::get().to(routes::list_comments_for_moderation),
+                ),
+            )
+            .route(
+                "/comments/remove",
+                restricted(ModerateComments, web::post().to(routes::remove_comments)),
+            )
+            .service(
+                web::resource("/comments/import")
+                    .app_data(web::PayloadConfig::new(routes::MAX_IMPORT_BYTES))
+                    .route(restricted(
+                        OperateService,
+                        web::post().to(routes::import_comments),
+                    )),
+            )
+            .route(
+                "/reports/comments",
+                restricted(
+                    ModerateComments,
+                    web::get().to(routes::list_comment_reports),
+                ),
+            )
+            .route(
+                "/submissions",
+                restricted(
+                    ModerateComments,
+                    web::get().to(routes::list_comment_submissions),
+                ),
+            )
+            .route(
+                "/submissions/{id}/approve",
+                restricted(
+                    ModerateComments,
+                    web::post().to(routes::approve_comment_submission),
+                ),
+            )
+            .route(
+                "/submissions/{id}/reject",
+                restricted(
+                    ModerateComments,
+                    web::post().to(routes::reject_comment_submission),
+                ),
+            )
+            .route(
+                "/users/ban/{id}",
+                restricted(ManageUsers, web::post().to(routes::ban_user)),
+            )
+            .route(
+                "/users/unban/{id}",
+                restricted(ManageUsers, web::post().to(routes::unban_user)),
+            )
+            .route(
+                "/users/{id}/role",
+                restricted(ManageUsers, web::put().to(routes::set_user_role)),
+            )
+            .route(
+                "/users/{id}/impersonate",
+                restricted(
+                    ImpersonateUsers,
+                    web::post().to(routes::start_impersonation),
+                ),
+            )
+            .route(
+                "/users/activation-reminders",
+                restricted(
+                    OperateService,
+                    web::get().to(routes::get_activation_reminder_stats),
+                ),
+            )
+            .route(
+                "/audit-log",
+                restricted(ReviewAuditLog, web::get().to(routes::get_audit_log)),
+            )
+            .route(
+                "/logging",
+                restricted(OperateService, web::get().to(routes::get_log_filter)),
+            )
+            .route(
+                "/logging",
+                restricted(OperateService, web::put().to(routes::set_log_filter)),
+            )
+            .route(
+                "/workers",
+                restricted(OperateService, web::get().to(routes::get_workers)),
+            )
+            .route(
+                "/db/indexes",
+                restricted(OperateService, web::get().to(routes::get_index_stats)),
+            )
+            .route(
+                "/db/bloat",
+                restricted(OperateService, web::get().to(routes::get_bloat_stats)),
+            )
+            .route(
+                "/db/post-bodies",
+                restricted(OperateService, web::get().to(routes::get_post_body_stats)),
             ),
     );
 }
@@ -0,0 +1,86 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+use serde::Deserialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    domain::{PageQuery, Pagination},
+    repository, utils,
+};
+
+#[derive(thiserror::Error)]
+pub enum SubmissionReviewError {
+    #[error("{0}")]
+    ValidationError(String),
+
+    #[error("pending submission not found")]
+    NotFound,
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for SubmissionReviewError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for SubmissionReviewError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            SubmissionReviewError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            SubmissionReviewError::NotFound => StatusCode::NOT_FOUND,
+            SubmissionReviewError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SubmissionPathParams {
+    pub id: Uuid,
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn list_comment_submissions(
+    query: web::Query<PageQuery>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, SubmissionReviewError> {
+    let page: Pagination = query
+        .into_inner()
+        .try_into()
+        .map_err(SubmissionReviewError::ValidationError)?;
+
+    let (submissions, total_records) = repository::get_pending_submissions(&page, &pool).await?;
+
+    let metadata = page.metadata(total_records);
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "submissions": submissions,
+        "metadata": metadata
+    })))
+}
+
+#[tracing::instrument(skip(pool), fields(submission_id=%path.id))]
+pub async fn approve_comment_submission(
+    path: web::Path<SubmissionPathParams>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, SubmissionReviewError> {
+    let comment_id = repository::approve_submission(path.id, &pool).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "comment_id": comment_id })))
+}
+
+#[tracing::instrument(skip(pool), fields(submission_id=%path.id))]
+pub async fn reject_comment_submission(
+    path: web::Path<SubmissionPathParams>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, SubmissionReviewError> {
+    repository::reject_submission(path.id, &pool).await?;
+
+    Ok(HttpResponse::Ok().finish())
+}
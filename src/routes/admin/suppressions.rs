@@ -0,0 +1,102 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+use sqlx::PgPool;
+
+use crate::{
+    domain::{PageQuery, Pagination, SuppressEmailPayload, SuppressionReason, UserEmail},
+    repository, utils,
+};
+
+#[derive(thiserror::Error)]
+pub enum SuppressionError {
+    #[error("{0}")]
+    ValidationError(String),
+
+    #[error("email is already suppressed")]
+    AlreadySuppressed,
+
+    #[error("email is not suppressed")]
+    NotSuppressed,
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for SuppressionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for SuppressionError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            SuppressionError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            SuppressionError::AlreadySuppressed => StatusCode::CONFLICT,
+            SuppressionError::NotSuppressed => StatusCode::NOT_FOUND,
+            SuppressionError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+#[derive(serde::Deserialize, Debug)]
+pub struct SuppressionPathParams {
+    pub email: String,
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn list_suppressed_emails(
+    query: web::Query<PageQuery>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, SuppressionError> {
+    let page: Pagination = query
+        .into_inner()
+        .try_into()
+        .map_err(SuppressionError::ValidationError)?;
+
+    let (emails, total_records) = repository::get_suppressed_emails(&page, &pool).await?;
+
+    let metadata = page.metadata(total_records);
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "suppressed_emails": emails,
+        "metadata": metadata
+    })))
+}
+
+// Nothing is sent to the address anymore, newsletters nor account emails, until it is removed
+// from the list again
+#[tracing::instrument(skip(pool))]
+pub async fn add_suppressed_email(
+    payload: web::Json<SuppressEmailPayload>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, SuppressionError> {
+    let email =
+        UserEmail::parse(payload.into_inner().email).map_err(SuppressionError::ValidationError)?;
+
+    if !repository::suppress_email(email.as_ref(), SuppressionReason::Manual, &pool).await? {
+        return Err(SuppressionError::AlreadySuppressed);
+    }
+
+    Ok(HttpResponse::Created().json(serde_json::json!({
+        "email": email.as_ref().to_lowercase(),
+        "reason": SuppressionReason::Manual.as_str(),
+    })))
+}
+
+// Whatever the address was suppressed for, e.g. to send again to an address whose mailbox was
+// full when it bounced
+#[tracing::instrument(skip(pool))]
+pub async fn remove_suppressed_email(
+    path: web::Path<SuppressionPathParams>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, SuppressionError> {
+    if !repository::unsuppress_email(&path.email, &pool).await? {
+        return Err(SuppressionError::NotSuppressed);
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
@@ -0,0 +1,48 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+use sqlx::PgPool;
+
+use crate::{repository, utils};
+
+#[derive(thiserror::Error)]
+pub enum ActivationReminderStatsError {
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for ActivationReminderStatsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for ActivationReminderStatsError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            ActivationReminderStatsError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn get_activation_reminder_stats(
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, ActivationReminderStatsError> {
+    let reminders: Vec<serde_json::Value> = repository::get_activation_reminder_stats(&pool)
+        .await?
+        .into_iter()
+        .map(|stats| {
+            serde_json::json!({
+                "reminder_number": stats.reminder_number,
+                "sent": stats.sent,
+                "converted": stats.converted,
+                "conversion_rate": stats.conversion_rate(),
+            })
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "reminders": reminders })))
+}
@@ -0,0 +1,86 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+use chrono::Utc;
+use serde::Deserialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    domain::{BanUserPayload, Suspension},
+    repository, utils,
+};
+
+#[derive(thiserror::Error)]
+pub enum BanError {
+    #[error("{0}")]
+    ValidationError(String),
+
+    #[error("user not found")]
+    NotFound,
+
+    #[error("admins cannot be banned")]
+    Forbidden,
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for BanError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for BanError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            BanError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            BanError::NotFound => StatusCode::NOT_FOUND,
+            BanError::Forbidden => StatusCode::FORBIDDEN,
+            BanError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct UserPathParams {
+    pub id: Uuid,
+}
+
+// Banned users cannot log in, and their existing sessions and tokens are turned away with the
+// reason until the ban expires or is lifted
+#[tracing::instrument(skip(pool), fields(user_id=%path.id))]
+pub async fn ban_user(
+    path: web::Path<UserPathParams>,
+    payload: web::Json<BanUserPayload>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, BanError> {
+    let payload = payload.into_inner();
+    // Content removal cannot be undone, so it is reserved for bans that are never lifted by
+    // themselves
+    if payload.remove_content && payload.expires_at.is_some() {
+        return Err(BanError::ValidationError(
+            "Invalid remove_content: content can only be removed by a permanent ban.".to_string(),
+        ));
+    }
+    let suspension = Suspension::parse(payload.reason, payload.expires_at, Utc::now())
+        .map_err(BanError::ValidationError)?;
+    let summary = repository::ban_user(path.id, payload.remove_content, &suspension, &pool).await?;
+
+    Ok(HttpResponse::Ok().json(summary))
+}
+
+#[tracing::instrument(skip(pool), fields(user_id=%path.id))]
+pub async fn unban_user(
+    path: web::Path<UserPathParams>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, BanError> {
+    if !repository::unban_user(path.id, &pool).await? {
+        return Err(BanError::NotFound);
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
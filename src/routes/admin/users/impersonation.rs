@@ -0,0 +1,141 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpRequest, HttpResponse, ResponseError, http::StatusCode, web};
+use sqlx::PgPool;
+
+use crate::{
+    authentication::UserId,
+    domain::{AuditAction, Role},
+    repository,
+    routes::{UserPathParams, device_metadata},
+    session_state::TypedSession,
+    utils,
+};
+
+#[derive(thiserror::Error)]
+pub enum ImpersonationError {
+    #[error("user not found")]
+    NotFound,
+
+    #[error("admins cannot impersonate themselves")]
+    OwnAccount,
+
+    // Otherwise impersonation could be chained to act as any admin
+    #[error("admins cannot be impersonated")]
+    AdminTarget,
+
+    // Bearer tokens carry a fixed user, only a session can switch to another
+    #[error("impersonation requires a session cookie")]
+    SessionRequired,
+
+    #[error("not impersonating anyone")]
+    NotImpersonating,
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for ImpersonationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for ImpersonationError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            ImpersonationError::NotFound => StatusCode::NOT_FOUND,
+            ImpersonationError::OwnAccount => StatusCode::BAD_REQUEST,
+            ImpersonationError::AdminTarget => StatusCode::FORBIDDEN,
+            ImpersonationError::SessionRequired => StatusCode::BAD_REQUEST,
+            ImpersonationError::NotImpersonating => StatusCode::BAD_REQUEST,
+            ImpersonationError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+// Switches the admin's session to the user until `end_impersonation`. Sudo mode is dropped, and
+// the admin cannot enter it again without the user's password, so the user's sensitive settings
+// stay out of reach.
+#[tracing::instrument(skip(req, pool, session), fields(admin_id=%&*admin_id, user_id=%path.id))]
+pub async fn start_impersonation(
+    req: HttpRequest,
+    path: web::Path<UserPathParams>,
+    pool: web::Data<PgPool>,
+    session: TypedSession,
+    admin_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, ImpersonationError> {
+    if path.id == **admin_id {
+        return Err(ImpersonationError::OwnAccount);
+    }
+    if session.get_user_id()? != Some(**admin_id) {
+        return Err(ImpersonationError::SessionRequired);
+    }
+
+    let role = repository::find_user_role(path.id, &pool)
+        .await?
+        .ok_or(ImpersonationError::NotFound)?;
+    if role == Role::Admin {
+        return Err(ImpersonationError::AdminTarget);
+    }
+
+    let device = device_metadata(&req);
+    repository::record_audit_event(
+        &**pool,
+        AuditAction::ImpersonationStart,
+        Some(**admin_id),
+        Some(path.id),
+        serde_json::json!({
+            "ip_address": device.ip_address,
+            "user_agent": device.user_agent,
+        }),
+    )
+    .await?;
+
+    session.remove_sudo_until();
+    session.insert_impersonator_id(**admin_id)?;
+    session.insert_user_id(path.id)?;
+    session.insert_role(role)?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "id": path.id,
+        "role": role,
+        "impersonated_by": **admin_id,
+    })))
+}
+
+// Returns the session to the admin who started the impersonation
+#[tracing::instrument(skip(req, pool, session), fields(user_id=%&*user_id))]
+pub async fn end_impersonation(
+    req: HttpRequest,
+    pool: web::Data<PgPool>,
+    session: TypedSession,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, ImpersonationError> {
+    let admin_id = session
+        .get_impersonator_id()?
+        .ok_or(ImpersonationError::NotImpersonating)?;
+    let role = repository::get_user_role(admin_id, &pool).await?;
+
+    let device = device_metadata(&req);
+    repository::record_audit_event(
+        &**pool,
+        AuditAction::ImpersonationEnd,
+        Some(admin_id),
+        Some(**user_id),
+        serde_json::json!({
+            "ip_address": device.ip_address,
+            "user_agent": device.user_agent,
+        }),
+    )
+    .await?;
+
+    session.remove_sudo_until();
+    session.remove_impersonator_id();
+    session.insert_user_id(admin_id)?;
+    session.insert_role(role)?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "id": admin_id, "role": role })))
+}
@@ -0,0 +1,8 @@
+mod activation_reminders;
+mod ban;
+mod impersonation;
+mod role;
+pub use activation_reminders::*;
+pub use ban::*;
+pub use impersonation::*;
+pub use role::*;
@@ -0,0 +1,67 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+use sqlx::PgPool;
+
+use crate::{
+    authentication::UserId,
+    domain::{Role, SetRolePayload},
+    repository,
+    routes::UserPathParams,
+    utils,
+};
+
+#[derive(thiserror::Error)]
+pub enum SetRoleError {
+    #[error("{0}")]
+    ValidationError(String),
+
+    #[error("user not found")]
+    NotFound,
+
+    // Otherwise the last admin could lock everyone out of administration
+    #[error("admins cannot change their own role")]
+    OwnRole,
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for SetRoleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for SetRoleError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            SetRoleError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            SetRoleError::NotFound => StatusCode::NOT_FOUND,
+            SetRoleError::OwnRole => StatusCode::FORBIDDEN,
+            SetRoleError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+// The user is logged out everywhere, and logs back in with the new role
+#[tracing::instrument(skip(pool), fields(admin_id=%&*admin_id, user_id=%path.id))]
+pub async fn set_user_role(
+    path: web::Path<UserPathParams>,
+    payload: web::Json<SetRolePayload>,
+    pool: web::Data<PgPool>,
+    admin_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, SetRoleError> {
+    let role = Role::parse(&payload.role).map_err(SetRoleError::ValidationError)?;
+    if path.id == **admin_id {
+        return Err(SetRoleError::OwnRole);
+    }
+
+    if !repository::set_user_role(path.id, role, **admin_id, &pool).await? {
+        return Err(SetRoleError::NotFound);
+    }
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "id": path.id, "role": role })))
+}
@@ -0,0 +1,35 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+use sqlx::PgPool;
+
+use crate::{newsletter_delivery_worker::WORKER_LIVENESS_TIMEOUT, repository, utils};
+
+#[derive(thiserror::Error)]
+pub enum WorkersError {
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for WorkersError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for WorkersError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            WorkersError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn get_workers(pool: web::Data<PgPool>) -> Result<HttpResponse, WorkersError> {
+    let workers = repository::get_worker_heartbeats(&pool, WORKER_LIVENESS_TIMEOUT).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "workers": workers })))
+}
@@ -0,0 +1,105 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpRequest, HttpResponse, ResponseError, http::StatusCode, web};
+use anyhow::Context;
+use secrecy::ExposeSecret;
+use sqlx::PgPool;
+use thiserror;
+
+use crate::{
+    captcha_client::CaptchaClient,
+    client_ip::client_address,
+    configuration::AnonymousCommentSettings,
+    domain::{AnonymousCommentPayload, CommentSubmission, SubmissionSource},
+    repository, utils,
+};
+
+#[derive(thiserror::Error)]
+pub enum AnonymousCommentError {
+    #[error("{0}")]
+    ValidationError(String),
+
+    #[error("anonymous commenting is disabled")]
+    Disabled,
+
+    #[error("captcha verification failed")]
+    CaptchaFailed,
+
+    #[error("too many submissions, try again later")]
+    RateLimited,
+
+    #[error("post not found")]
+    NotFound,
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for AnonymousCommentError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for AnonymousCommentError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            AnonymousCommentError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            AnonymousCommentError::Disabled | AnonymousCommentError::CaptchaFailed => {
+                StatusCode::FORBIDDEN
+            }
+            AnonymousCommentError::RateLimited => StatusCode::TOO_MANY_REQUESTS,
+            AnonymousCommentError::NotFound => StatusCode::NOT_FOUND,
+            AnonymousCommentError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+// Anonymous comments always go to the moderation queue, they are published once approved
+#[tracing::instrument(skip_all)]
+pub async fn create_anonymous_comment(
+    req: HttpRequest,
+    payload: web::Json<AnonymousCommentPayload>,
+    pool: web::Data<PgPool>,
+    settings: web::Data<AnonymousCommentSettings>,
+    captcha_client: web::Data<CaptchaClient>,
+) -> Result<HttpResponse, AnonymousCommentError> {
+    if !settings.enabled {
+        return Err(AnonymousCommentError::Disabled);
+    }
+
+    let ip_address = client_address(&req);
+
+    let payload = payload.into_inner();
+    let captcha_token = payload.captcha_token.clone();
+    let submission =
+        CommentSubmission::anonymous(payload).map_err(AnonymousCommentError::ValidationError)?;
+
+    // Checked before the CAPTCHA so a flood of requests does not also flood the provider
+    let recent =
+        repository::count_recent_submissions(SubmissionSource::Anonymous, &ip_address, &pool)
+            .await?;
+    if recent >= settings.max_submissions_per_hour {
+        tracing::warn!(ip_address, recent, "Anonymous comment rate limit reached");
+        return Err(AnonymousCommentError::RateLimited);
+    }
+
+    let is_human = captcha_client
+        .verify(captcha_token.expose_secret(), &ip_address)
+        .await
+        .context("Failed to verify captcha token")?;
+    if !is_human {
+        return Err(AnonymousCommentError::CaptchaFailed);
+    }
+
+    let id = repository::insert_comment_submission(&submission, None, &ip_address, &pool)
+        .await?
+        .ok_or(AnonymousCommentError::NotFound)?;
+
+    Ok(HttpResponse::Accepted().json(serde_json::json!({
+        "id": id,
+        "status": "pending"
+    })))
+}
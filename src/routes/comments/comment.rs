@@ -7,9 +7,14 @@ use thiserror;
 use uuid::Uuid;
 
 use crate::{
-    authentication::{IsAdmin, UserId},
-    domain::{Comment, CreateCommentPayload, CreateCommentResponseBody},
-    repository, utils,
+    authentication::UserId,
+    domain::{
+        Comment, CommentsPage, CreateCommentPayload, CreateCommentResponseBody, GetCommentsQuery,
+        Permission, Role,
+    },
+    repository,
+    session_state::TypedSession,
+    utils,
 };
 
 #[derive(thiserror::Error)]
@@ -51,18 +56,32 @@ pub struct CommentPathParams {
     pub id: Uuid,
 }
 
-#[tracing::instrument(skip(pool), fields(post_id=%path.id))]
+#[tracing::instrument(skip(pool, session), fields(post_id=%path.id))]
 pub async fn show_comments_for_post(
     path: web::Path<CommentPathParams>,
+    query: web::Query<GetCommentsQuery>,
     pool: web::Data<PgPool>,
+    session: TypedSession,
 ) -> Result<HttpResponse, CommentError> {
     let post_id = path.id;
+    // Signed-in viewers do not see comments by users they blocked
+    let viewer_id = session.get_user_id()?;
+    let page: CommentsPage = query
+        .into_inner()
+        .try_into()
+        .map_err(CommentError::ValidationError)?;
 
-    let comments = repository::get_comments_for_post(post_id, &pool)
-        .await
-        .map_err(CommentError::UnexpectedError)?;
+    let (comments, total_records) =
+        repository::get_comments_for_post(post_id, viewer_id, &page, &pool)
+            .await
+            .map_err(CommentError::UnexpectedError)?;
+
+    let metadata = page.pagination.metadata(total_records);
 
-    Ok(HttpResponse::Ok().json(serde_json::json!({ "comments": comments })))
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "comments": comments,
+        "metadata": metadata
+    })))
 }
 
 #[tracing::instrument(skip(pool), fields(user_id=%&*user_id))]
@@ -78,10 +97,22 @@ pub async fn create_comment(
         .try_into()
         .map_err(CommentError::ValidationError)?;
 
+    if repository::is_blocked_by_post_author(comment.post_id, *user_id, &pool).await? {
+        return Err(CommentError::Forbidden);
+    }
+
     let (id, created_at) = repository::insert_comment(&comment, *user_id, &pool)
         .await
         .map_err(CommentError::UnexpectedError)?;
 
+    // Mentions are notifications only, so failing to record them must not fail the comment
+    let mentions = comment.text.mentions();
+    if !mentions.is_empty()
+        && let Err(e) = repository::record_comment_mentions(id, *user_id, &mentions, &pool).await
+    {
+        tracing::error!(error.cause_chain = ?e, "Failed to record comment mentions");
+    }
+
     let resp = CreateCommentResponseBody {
         id,
         text: comment.text.as_ref(),
@@ -98,14 +129,13 @@ pub async fn delete_comment(
     path: web::Path<CommentPathParams>,
     pool: web::Data<PgPool>,
     user_id: web::ReqData<UserId>,
-    is_admin: web::ReqData<IsAdmin>,
+    role: web::ReqData<Role>,
 ) -> Result<HttpResponse, CommentError> {
     let comment_id = path.id;
     let user_id = user_id.into_inner();
-    let is_admin = *is_admin.into_inner();
 
-    // If not admin, verify ownership
-    if !is_admin {
+    // Others' comments may only be deleted by roles that moderate comments
+    if !role.has(Permission::ModerateComments) {
         let is_owner = repository::did_user_create_the_comment(comment_id, *user_id, &pool).await?;
 
         if !is_owner {
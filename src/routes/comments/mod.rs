@@ -1,4 +1,8 @@
+pub mod anonymous;
 pub mod comment;
+pub mod report;
 pub mod routes;
+pub use anonymous::*;
 pub use comment::*;
+pub use report::*;
 pub use routes::*;
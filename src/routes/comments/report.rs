@@ -0,0 +1,62 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+use sqlx::PgPool;
+
+use crate::{
+    authentication::UserId,
+    domain::{ReportPayload, ReportReason},
+    repository,
+    routes::CommentPathParams,
+    utils,
+};
+
+#[derive(thiserror::Error)]
+pub enum ReportError {
+    #[error("{0}")]
+    ValidationError(String),
+
+    #[error("comment not found")]
+    NotFound,
+
+    #[error("you have already reported this comment")]
+    AlreadyReported,
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for ReportError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for ReportError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            ReportError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            ReportError::NotFound => StatusCode::NOT_FOUND,
+            ReportError::AlreadyReported => StatusCode::CONFLICT,
+            ReportError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+#[tracing::instrument(skip(pool, payload, user_id), fields(comment_id=%path.id))]
+pub async fn report_comment(
+    path: web::Path<CommentPathParams>,
+    payload: web::Json<ReportPayload>,
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, ReportError> {
+    let reason =
+        ReportReason::parse(payload.into_inner().reason).map_err(ReportError::ValidationError)?;
+
+    let report_id =
+        repository::insert_comment_report(path.id, *user_id.into_inner(), &reason, &pool).await?;
+
+    Ok(HttpResponse::Created().json(serde_json::json!({ "id": report_id })))
+}
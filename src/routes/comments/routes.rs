@@ -1,6 +1,10 @@
 use actix_web::{middleware, web};
 
-use crate::{authentication, routes};
+use crate::{
+    authentication,
+    rate_limiter::{RateLimitedRoute, rate_limit},
+    routes,
+};
 
 pub fn comment_routes(cfg: &mut web::ServiceConfig) {
     cfg
@@ -9,11 +13,23 @@ pub fn comment_routes(cfg: &mut web::ServiceConfig) {
             "/get/posts/{id}",
             web::get().to(routes::show_comments_for_post),
         )
+        .route(
+            "/anonymous",
+            web::post().to(routes::create_anonymous_comment),
+        )
         // Protected routes (require authentication)
         .service(
             web::scope("/me")
                 .wrap(middleware::from_fn(authentication::reject_anonymous_users))
-                .route("/create", web::post().to(routes::create_comment))
-                .route("/delete/{id}", web::delete().to(routes::delete_comment)),
+                .route(
+                    "/create",
+                    web::post()
+                        .to(routes::create_comment)
+                        .wrap(middleware::from_fn(|req, next| {
+                            rate_limit(RateLimitedRoute::CommentCreation, req, next)
+                        })),
+                )
+                .route("/delete/{id}", web::delete().to(routes::delete_comment))
+                .route("/report/{id}", web::post().to(routes::report_comment)),
         );
 }
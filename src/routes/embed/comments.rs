@@ -0,0 +1,102 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{
+    HttpRequest, HttpResponse, ResponseError,
+    http::{StatusCode, header},
+    web,
+};
+use sqlx::PgPool;
+use thiserror;
+
+use crate::{
+    client_ip::client_address,
+    configuration::EmbedSettings,
+    domain::{CommentSubmission, EmbedCommentPayload, SubmissionSource},
+    repository,
+    routes::CommentPathParams,
+    utils,
+};
+
+#[derive(thiserror::Error)]
+pub enum EmbedError {
+    #[error("{0}")]
+    ValidationError(String),
+
+    #[error("anonymous posting is disabled")]
+    PostingDisabled,
+
+    #[error("submissions must come from an allowed origin")]
+    OriginNotAllowed,
+
+    #[error("too many submissions, try again later")]
+    RateLimited,
+
+    #[error("post not found")]
+    NotFound,
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for EmbedError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for EmbedError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            EmbedError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            EmbedError::PostingDisabled | EmbedError::OriginNotAllowed => StatusCode::FORBIDDEN,
+            EmbedError::RateLimited => StatusCode::TOO_MANY_REQUESTS,
+            EmbedError::NotFound => StatusCode::NOT_FOUND,
+            EmbedError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+// Stores an anonymous comment for moderation, it is not visible until an admin approves it
+#[tracing::instrument(skip(req, payload, pool, settings), fields(post_id=%path.id))]
+pub async fn submit_embed_comment(
+    req: HttpRequest,
+    path: web::Path<CommentPathParams>,
+    payload: web::Json<EmbedCommentPayload>,
+    pool: web::Data<PgPool>,
+    settings: web::Data<EmbedSettings>,
+) -> Result<HttpResponse, EmbedError> {
+    if !settings.allow_anonymous_posting {
+        return Err(EmbedError::PostingDisabled);
+    }
+
+    // CORS already turns away other browser origins, this also rejects requests sent without one
+    let origin = req
+        .headers()
+        .get(header::ORIGIN)
+        .and_then(|origin| origin.to_str().ok())
+        .filter(|origin| settings.allowed_origins.iter().any(|o| o == origin))
+        .ok_or(EmbedError::OriginNotAllowed)?;
+
+    let ip_address = client_address(&req);
+
+    let submission = CommentSubmission::embed(path.id, payload.into_inner())
+        .map_err(EmbedError::ValidationError)?;
+
+    let recent =
+        repository::count_recent_submissions(SubmissionSource::Embed, &ip_address, &pool).await?;
+    if recent >= settings.max_submissions_per_hour {
+        tracing::warn!(ip_address, recent, "Embed submission rate limit reached");
+        return Err(EmbedError::RateLimited);
+    }
+
+    let id = repository::insert_comment_submission(&submission, Some(origin), &ip_address, &pool)
+        .await?
+        .ok_or(EmbedError::NotFound)?;
+
+    Ok(HttpResponse::Accepted().json(serde_json::json!({
+        "id": id,
+        "status": "pending"
+    })))
+}
@@ -0,0 +1,5 @@
+mod comments;
+mod routes;
+
+pub use comments::*;
+pub use routes::*;
@@ -0,0 +1,33 @@
+use actix_cors::Cors;
+use actix_web::{dev::HttpServiceFactory, http::header, web};
+
+use crate::{configuration::EmbedSettings, routes};
+
+// The embed scope is mounted at `/v1/embed` with its own CORS policy, so browsers on the allowed
+// origins can call it while the rest of the API stays same-origin only
+pub fn embed_routes(settings: &EmbedSettings) -> impl HttpServiceFactory + use<> {
+    web::scope("/v1/embed")
+        .wrap(embed_cors(settings))
+        .route(
+            "/posts/{id}/comments",
+            web::get().to(routes::show_comments_for_post),
+        )
+        .route(
+            "/posts/{id}/comments",
+            web::post().to(routes::submit_embed_comment),
+        )
+}
+
+fn embed_cors(settings: &EmbedSettings) -> Cors {
+    let cors = Cors::default()
+        .allowed_methods(["GET", "POST"])
+        .allowed_header(header::CONTENT_TYPE)
+        // Requests from other origins never reach the handlers
+        .block_on_origin_mismatch(true)
+        .max_age(3600);
+
+    settings
+        .allowed_origins
+        .iter()
+        .fold(cors, |cors, origin| cors.allowed_origin(origin))
+}
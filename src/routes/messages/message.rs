@@ -0,0 +1,135 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+use serde::Deserialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    authentication::UserId,
+    configuration::MessageSettings,
+    domain::{NewMessage, PageQuery, Pagination, SendMessagePayload},
+    repository, utils,
+};
+
+#[derive(thiserror::Error)]
+pub enum MessageError {
+    #[error("{0}")]
+    ValidationError(String),
+
+    #[error("recipient not found")]
+    RecipientNotFound,
+
+    #[error("conversation not found")]
+    ConversationNotFound,
+
+    #[error("not allowed to message this user")]
+    Forbidden,
+
+    #[error("too many messages, try again later")]
+    RateLimited,
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for MessageError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for MessageError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            MessageError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            MessageError::RecipientNotFound | MessageError::ConversationNotFound => {
+                StatusCode::NOT_FOUND
+            }
+            MessageError::Forbidden => StatusCode::FORBIDDEN,
+            MessageError::RateLimited => StatusCode::TOO_MANY_REQUESTS,
+            MessageError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ConversationPathParams {
+    pub id: Uuid,
+}
+
+#[tracing::instrument(skip(payload, pool, settings), fields(user_id=%&*user_id))]
+pub async fn send_message(
+    payload: web::Json<SendMessagePayload>,
+    pool: web::Data<PgPool>,
+    settings: web::Data<MessageSettings>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, MessageError> {
+    let sender_id = **user_id;
+    let message: NewMessage = payload
+        .into_inner()
+        .try_into()
+        .map_err(MessageError::ValidationError)?;
+
+    if message.recipient_id == sender_id {
+        return Err(MessageError::ValidationError(
+            "You cannot message yourself.".to_string(),
+        ));
+    }
+
+    // Either side blocking the other ends the conversation, without saying who blocked whom
+    if repository::is_block_between(sender_id, message.recipient_id, &pool).await? {
+        return Err(MessageError::Forbidden);
+    }
+
+    let recent = repository::count_recent_messages(sender_id, &pool).await?;
+    if recent >= settings.max_messages_per_hour {
+        tracing::warn!(recent, "Direct message rate limit reached");
+        return Err(MessageError::RateLimited);
+    }
+
+    let sent = repository::insert_message(sender_id, &message, &pool)
+        .await?
+        .ok_or(MessageError::RecipientNotFound)?;
+
+    Ok(HttpResponse::Created().json(sent))
+}
+
+#[tracing::instrument(skip(pool), fields(user_id=%&*user_id))]
+pub async fn get_own_messages(
+    query: web::Query<PageQuery>,
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, MessageError> {
+    let page: Pagination = query
+        .into_inner()
+        .try_into()
+        .map_err(MessageError::ValidationError)?;
+
+    let (conversations, total_records) =
+        repository::get_conversations(**user_id, &page, &pool).await?;
+    let unread_count = repository::get_unread_message_count(**user_id, &pool).await?;
+
+    let metadata = page.metadata(total_records);
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "conversations": conversations,
+        "unread_count": unread_count,
+        "metadata": metadata
+    })))
+}
+
+#[tracing::instrument(skip(pool), fields(user_id=%&*user_id, conversation_id=%path.id))]
+pub async fn mark_conversation_read(
+    path: web::Path<ConversationPathParams>,
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, MessageError> {
+    if !repository::mark_conversation_read(**user_id, path.id, &pool).await? {
+        return Err(MessageError::ConversationNotFound);
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
@@ -0,0 +1,5 @@
+mod message;
+mod routes;
+
+pub use message::*;
+pub use routes::*;
@@ -0,0 +1,12 @@
+use actix_web::{middleware, web};
+
+use crate::{authentication, routes};
+
+pub fn message_routes(cfg: &mut web::ServiceConfig) {
+    // Protected routes (require authentication), the inbox itself lives under `/v1/user/me`
+    cfg.service(
+        web::resource("")
+            .wrap(middleware::from_fn(authentication::reject_anonymous_users))
+            .route(web::post().to(routes::send_message)),
+    );
+}
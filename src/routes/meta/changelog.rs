@@ -0,0 +1,10 @@
+use actix_web::HttpResponse;
+
+use crate::changelog::CHANGELOG;
+
+pub async fn get_changelog() -> HttpResponse {
+    HttpResponse::Ok().json(serde_json::json!({
+        "server_version": env!("CARGO_PKG_VERSION"),
+        "releases": CHANGELOG,
+    }))
+}
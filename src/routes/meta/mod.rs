@@ -0,0 +1,7 @@
+mod changelog;
+mod routes;
+mod time;
+
+pub use changelog::*;
+pub use routes::*;
+pub use time::*;
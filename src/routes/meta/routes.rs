@@ -0,0 +1,8 @@
+use actix_web::web;
+
+use crate::routes;
+
+pub fn meta_routes(cfg: &mut web::ServiceConfig) {
+    cfg.route("/changelog", web::get().to(routes::get_changelog))
+        .route("/time", web::get().to(routes::get_server_time));
+}
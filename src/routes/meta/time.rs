@@ -0,0 +1,50 @@
+use actix_web::{
+    HttpResponse,
+    body::MessageBody,
+    dev::{ServiceRequest, ServiceResponse},
+    error,
+    http::header::{HeaderMap, HeaderName, HeaderValue},
+    middleware::Next,
+};
+use chrono::{DateTime, SecondsFormat, Utc};
+
+pub const SERVER_TIME_HEADER: HeaderName = HeaderName::from_static("x-server-time");
+
+// `Date` only has second precision, too coarse for clients measuring their own clock skew
+fn format_server_time(now: DateTime<Utc>) -> String {
+    now.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+pub async fn get_server_time() -> HttpResponse {
+    let now = Utc::now();
+    HttpResponse::Ok().json(serde_json::json!({
+        "server_time": format_server_time(now),
+        "unix_millis": now.timestamp_millis(),
+    }))
+}
+
+// Stamps every response, including errors raised by inner middleware, so clients can compare
+// clocks on whatever request they happened to make
+pub async fn add_server_time_header(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, actix_web::Error> {
+    match next.call(req).await {
+        Ok(mut response) => {
+            stamp_server_time(response.headers_mut());
+            Ok(response)
+        }
+        // Errors only become responses outside the app, so build it here to attach the header
+        Err(e) => {
+            let mut response = e.error_response();
+            stamp_server_time(response.headers_mut());
+            Err(error::InternalError::from_response(e, response).into())
+        }
+    }
+}
+
+fn stamp_server_time(headers: &mut HeaderMap) {
+    if let Ok(value) = HeaderValue::from_str(&format_server_time(Utc::now())) {
+        headers.insert(SERVER_TIME_HEADER, value);
+    }
+}
@@ -2,11 +2,23 @@ mod health_check;
 
 mod admin;
 mod comments;
+mod embed;
+mod messages;
+mod meta;
+mod newsletters;
 mod posts;
+mod tracking;
 mod users;
+mod webhooks;
 
 pub use admin::*;
 pub use comments::*;
+pub use embed::*;
 pub use health_check::*;
+pub use messages::*;
+pub use meta::*;
+pub use newsletters::*;
 pub use posts::*;
+pub use tracking::*;
 pub use users::*;
+pub use webhooks::*;
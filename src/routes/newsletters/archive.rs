@@ -0,0 +1,100 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{
+    HttpResponse, ResponseError,
+    http::{
+        StatusCode,
+        header::{self, CacheControl, CacheDirective},
+    },
+    web,
+};
+use sqlx::PgPool;
+
+use crate::{
+    domain::{PageQuery, Pagination, render_archive_page, render_archived_newsletter},
+    repository,
+    routes::NewsletterPathParams,
+    utils,
+};
+
+// Issues are authored by admins but served from our own origin, so nothing in them may run
+const ARCHIVE_CONTENT_SECURITY_POLICY: &str =
+    "default-src 'none'; img-src https: data:; style-src 'unsafe-inline'";
+// Published issues never change, new ones show up on the index within this long
+const ARCHIVE_MAX_AGE_SECS: u32 = 300;
+
+#[derive(thiserror::Error)]
+pub enum NewsletterArchiveError {
+    #[error("{0}")]
+    ValidationError(String),
+
+    #[error("newsletter issue not found")]
+    NotFound,
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for NewsletterArchiveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for NewsletterArchiveError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            NewsletterArchiveError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            NewsletterArchiveError::NotFound => StatusCode::NOT_FOUND,
+            NewsletterArchiveError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn get_newsletter_archive(
+    query: web::Query<PageQuery>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, NewsletterArchiveError> {
+    let page: Pagination = query
+        .into_inner()
+        .try_into()
+        .map_err(NewsletterArchiveError::ValidationError)?;
+
+    let (issues, total_records) = repository::get_public_newsletters(&page, &pool).await?;
+    let has_next_page = i64::from(page.offset() + page.limit.value()) < total_records;
+
+    Ok(archive_response(render_archive_page(
+        &issues,
+        page.page.value(),
+        has_next_page,
+    )))
+}
+
+#[tracing::instrument(skip(pool), fields(issue_id=%path.id))]
+pub async fn get_archived_newsletter(
+    path: web::Path<NewsletterPathParams>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, NewsletterArchiveError> {
+    let issue = repository::get_public_newsletter(&pool, path.id)
+        .await?
+        .ok_or(NewsletterArchiveError::NotFound)?;
+
+    Ok(archive_response(render_archived_newsletter(&issue)))
+}
+
+fn archive_response(html: String) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .insert_header(CacheControl(vec![
+            CacheDirective::Public,
+            CacheDirective::MaxAge(ARCHIVE_MAX_AGE_SECS),
+        ]))
+        .insert_header((
+            header::CONTENT_SECURITY_POLICY,
+            ARCHIVE_CONTENT_SECURITY_POLICY,
+        ))
+        .body(html)
+}
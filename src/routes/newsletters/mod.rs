@@ -0,0 +1,5 @@
+mod archive;
+mod routes;
+
+pub use archive::*;
+pub use routes::*;
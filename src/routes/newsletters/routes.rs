@@ -0,0 +1,9 @@
+use actix_web::web;
+
+use crate::routes;
+
+// Public archive of published issues, no login required
+pub fn newsletter_routes(cfg: &mut web::ServiceConfig) {
+    cfg.route("", web::get().to(routes::get_newsletter_archive))
+        .route("/{id}", web::get().to(routes::get_archived_newsletter));
+}
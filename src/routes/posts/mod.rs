@@ -1,5 +1,9 @@
 mod post;
+mod proposal;
 mod routes;
+mod suggestion;
 
 pub use post::*;
+pub use proposal::*;
 pub use routes::*;
+pub use suggestion::*;
@@ -1,19 +1,27 @@
-use std::fmt::{self, Debug, Formatter};
+use std::{
+    fmt::{self, Debug, Formatter},
+    sync::Arc,
+};
 
 use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
 use anyhow::Context;
+use rand::Rng;
 use serde::Deserialize;
 use sqlx::PgPool;
 use tracing::Span;
 use uuid::Uuid;
 
 use crate::{
-    authentication::{IsAdmin, UserId},
+    authentication::UserId,
     domain::{
-        CreatePostPayload, CreatePostResponse, GetAllPostsQuery, Metadata, Post, PostQuery,
-        UpdatePostPayload,
+        CreatePostPayload, CreatePostResponse, GetAllPostsQuery, Metadata, Permission, Post,
+        PostQuery, Role, UpdatePostPayload,
     },
-    repository, utils,
+    post_views::PostViewCounter,
+    repository,
+    session_state::TypedSession,
+    startup::{DefaultPostLicense, ImpressionSampleRate, PostListingFlights},
+    utils,
 };
 
 #[derive(thiserror::Error)]
@@ -54,27 +62,43 @@ impl ResponseError for PostError {
     }
 }
 
-#[tracing::instrument(skip(pool))]
+#[tracing::instrument(skip(pool, session, sample_rate, flights))]
 pub async fn get_all_posts(
     query: web::Query<GetAllPostsQuery>,
     pool: web::Data<PgPool>,
+    session: TypedSession,
+    sample_rate: web::Data<ImpressionSampleRate>,
+    flights: web::Data<PostListingFlights>,
 ) -> Result<HttpResponse, PostError> {
     let parsed_query =
         PostQuery::try_from(query.into_inner()).map_err(PostError::ValidationError)?;
+    let key = parsed_query.flight_key();
+    let page = parsed_query.filters.page.value();
+    let limit = parsed_query.filters.limit.value();
+
+    // Identical listings requested concurrently (e.g. a busy front page) share one db query
+    let query_pool = pool.clone();
+    let listing = flights
+        .run(key, async move {
+            let listing = repository::get_all_posts(
+                parsed_query.title.as_ref(),
+                parsed_query.created_by_id.as_ref(),
+                parsed_query.license,
+                &parsed_query.filters,
+                &query_pool,
+            )
+            .await?;
+            Ok(Arc::new(listing))
+        })
+        .await?;
+    let (posts, total_records) = listing.as_ref();
+
+    if rand::thread_rng().gen_bool(sample_rate.0) {
+        let post_ids: Vec<Uuid> = posts.iter().map(|p| p.id).collect();
+        record_impressions(&session, &post_ids, &pool).await;
+    }
 
-    let (posts, total_records) = repository::get_all_posts(
-        parsed_query.title.as_ref(),
-        parsed_query.created_by_id.as_ref(),
-        &parsed_query.filters,
-        &pool,
-    )
-    .await?;
-
-    let metadata = Metadata::calculate(
-        total_records,
-        parsed_query.filters.page.value(),
-        parsed_query.filters.limit.value(),
-    );
+    let metadata = Metadata::calculate(*total_records, page, limit);
 
     Ok(HttpResponse::Ok().json(serde_json::json!({
         "posts": posts,
@@ -90,36 +114,61 @@ pub struct PostPathParams {
 pub async fn get_post(
     path: web::Path<PostPathParams>,
     pool: web::Data<PgPool>,
+    post_views: web::Data<PostViewCounter>,
 ) -> Result<HttpResponse, PostError> {
     let post_id = path.id;
 
     let post = repository::get_post(post_id, &pool).await?;
+    post_views.record(post_id);
 
     Ok(HttpResponse::Ok().json(serde_json::json!({"posts": post})))
 }
 
+// Impressions only feed ranking, so failing to record them must not fail the listing
+async fn record_impressions(session: &TypedSession, post_ids: &[Uuid], pool: &PgPool) {
+    let user_id = session.get_user_id().ok().flatten();
+
+    if let Err(e) = repository::insert_impressions(user_id, post_ids, pool).await {
+        tracing::warn!(error.cause_chain = ?e, "Failed to record feed impressions");
+    }
+}
+
 #[tracing::instrument(
-    skip(pool),
+    skip(pool, default_license),
     fields(user_id=%&*user_id)
 )]
 pub async fn create_post(
     payload: web::Json<CreatePostPayload>,
     pool: web::Data<PgPool>,
+    default_license: web::Data<DefaultPostLicense>,
     user_id: web::ReqData<UserId>,
 ) -> Result<HttpResponse, PostError> {
     let user_id = user_id.into_inner();
+    let is_community = payload.community;
     let post: Post = payload.0.try_into().map_err(PostError::ValidationError)?;
-
-    let (id, created_at) =
-        repository::insert_post(&post.title, &post.text, &post.img, user_id, &pool)
-            .await
-            .context("Failed to insert posts record")?;
+    let license = post.license.unwrap_or(default_license.0);
+
+    let (id, created_at) = repository::insert_post(
+        &post.title,
+        &post.text,
+        &post.excerpt,
+        &post.img,
+        license,
+        is_community,
+        user_id,
+        &pool,
+    )
+    .await
+    .context("Failed to insert posts record")?;
 
     let response = CreatePostResponse {
         id,
         title: post.title.as_ref(),
         post_text: post.text.as_ref(),
+        excerpt: post.excerpt.as_ref(),
         img: post.img.as_ref(),
+        license,
+        is_community,
         created_at,
         created_by: *user_id,
     };
@@ -136,16 +185,15 @@ pub async fn update_post(
     payload: web::Json<UpdatePostPayload>,
     pool: web::Data<PgPool>,
     user_id: web::ReqData<UserId>,
-    is_admin: web::ReqData<IsAdmin>,
+    role: web::ReqData<Role>,
 ) -> Result<HttpResponse, PostError> {
     let post_id = path.id;
     let user_id = user_id.into_inner();
-    let is_admin = *is_admin.into_inner();
 
     Span::current().record("user_id", tracing::field::display(&user_id));
 
-    // If not admin, verify ownership
-    if !is_admin {
+    // Others' posts may only be edited by roles that manage posts
+    if !role.has(Permission::ManagePosts) {
         let is_owner = repository::did_user_create_the_post(post_id, *user_id, &pool).await?;
 
         if !is_owner {
@@ -160,7 +208,9 @@ pub async fn update_post(
         post.id,
         &validated_post.title,
         &validated_post.text,
+        &validated_post.excerpt,
         &validated_post.img,
+        validated_post.license,
         post.version,
         &pool,
     )
@@ -168,7 +218,11 @@ pub async fn update_post(
 
     post.title = validated_post.title.as_ref().to_string();
     post.text = validated_post.text.as_ref().to_string();
+    post.excerpt = validated_post.excerpt.as_ref().to_string();
     post.img = validated_post.img.as_ref().to_string();
+    if let Some(license) = validated_post.license {
+        post.license = license.to_string();
+    }
 
     Ok(HttpResponse::Ok().json(serde_json::json!({ "posts": post })))
 }
@@ -177,14 +231,13 @@ pub async fn delete_post(
     path: web::Path<PostPathParams>,
     pool: web::Data<PgPool>,
     user_id: web::ReqData<UserId>,
-    is_admin: web::ReqData<IsAdmin>,
+    role: web::ReqData<Role>,
 ) -> Result<HttpResponse, PostError> {
     let post_id = path.id;
     let user_id = *user_id.into_inner();
-    let is_admin = *is_admin.into_inner();
 
-    // if not admin, then verify ownership
-    if !is_admin {
+    // Others' posts may only be deleted by roles that manage posts
+    if !role.has(Permission::ManagePosts) {
         let is_owner = repository::post::did_user_create_the_post(post_id, user_id, &pool).await?;
         if !is_owner {
             return Err(PostError::Forbidden);
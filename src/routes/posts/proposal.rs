@@ -0,0 +1,149 @@
+use actix_web::{HttpResponse, web};
+use serde::Deserialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    authentication::UserId,
+    domain::{Permission, Post, ProposalReview, ProposeEditPayload, Role},
+    repository,
+    routes::{PostError, PostPathParams},
+};
+
+#[derive(Deserialize, Debug)]
+pub struct ProposalPathParams {
+    pub id: Uuid,
+}
+
+// Proposals are reviewed by the post's author, roles that manage posts may review any of them
+async fn ensure_reviewer(
+    post_id: Uuid,
+    user_id: Uuid,
+    role: Role,
+    pool: &PgPool,
+) -> Result<(), PostError> {
+    if !role.has(Permission::ManagePosts)
+        && !repository::did_user_create_the_post(post_id, user_id, pool).await?
+    {
+        return Err(PostError::Forbidden);
+    }
+    Ok(())
+}
+
+// Stored against the post's current version so a stale proposal is caught when approved
+#[tracing::instrument(
+    skip(payload, pool, user_id),
+    fields(post_id=%path.id, user_id=%&*user_id)
+)]
+pub async fn propose_post_edit(
+    path: web::Path<PostPathParams>,
+    payload: web::Json<ProposeEditPayload>,
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, PostError> {
+    let user_id = *user_id.into_inner();
+    let post = repository::get_post(path.id, &pool).await?;
+
+    if !post.is_community {
+        return Err(PostError::Forbidden);
+    }
+    if post.created_by == user_id {
+        return Err(PostError::ValidationError(
+            "Authors edit their own posts directly.".to_string(),
+        ));
+    }
+
+    let edit: Post = payload
+        .into_inner()
+        .try_into()
+        .map_err(PostError::ValidationError)?;
+    let id = repository::insert_edit_proposal(post.id, user_id, &edit, post.version, &pool).await?;
+
+    Ok(HttpResponse::Accepted().json(serde_json::json!({
+        "id": id,
+        "status": "pending"
+    })))
+}
+
+#[tracing::instrument(
+    skip(pool, user_id, role),
+    fields(post_id=%path.id, user_id=%&*user_id)
+)]
+pub async fn get_post_edit_proposals(
+    path: web::Path<PostPathParams>,
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+    role: web::ReqData<Role>,
+) -> Result<HttpResponse, PostError> {
+    let post = repository::get_post(path.id, &pool).await?;
+    ensure_reviewer(post.id, *user_id.into_inner(), role.into_inner(), &pool).await?;
+
+    let proposals = repository::get_pending_edit_proposals(post.id, &pool).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "proposals": proposals })))
+}
+
+// Applied like an edit by the author, so it fails with a conflict once the post has changed
+// since the proposal was written
+#[tracing::instrument(
+    skip(pool, user_id, role),
+    fields(proposal_id=%path.id, user_id=%&*user_id)
+)]
+pub async fn approve_post_edit_proposal(
+    path: web::Path<ProposalPathParams>,
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+    role: web::ReqData<Role>,
+) -> Result<HttpResponse, PostError> {
+    let proposal = repository::get_pending_edit_proposal(path.id, &pool)
+        .await?
+        .ok_or(PostError::NotFound)?;
+    let post_id = proposal.post_id;
+    let base_version = proposal.base_version;
+    ensure_reviewer(post_id, *user_id.into_inner(), role.into_inner(), &pool).await?;
+
+    let edit: Post = proposal.try_into().map_err(PostError::ValidationError)?;
+    repository::update_post(
+        post_id,
+        &edit.title,
+        &edit.text,
+        &edit.excerpt,
+        &edit.img,
+        None,
+        base_version,
+        &pool,
+    )
+    .await?;
+    repository::review_edit_proposal(path.id, ProposalReview::Approved, &pool).await?;
+
+    let post = repository::get_post(post_id, &pool).await?;
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "posts": post })))
+}
+
+#[tracing::instrument(
+    skip(pool, user_id, role),
+    fields(proposal_id=%path.id, user_id=%&*user_id)
+)]
+pub async fn reject_post_edit_proposal(
+    path: web::Path<ProposalPathParams>,
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+    role: web::ReqData<Role>,
+) -> Result<HttpResponse, PostError> {
+    let proposal = repository::get_pending_edit_proposal(path.id, &pool)
+        .await?
+        .ok_or(PostError::NotFound)?;
+    ensure_reviewer(
+        proposal.post_id,
+        *user_id.into_inner(),
+        role.into_inner(),
+        &pool,
+    )
+    .await?;
+
+    if !repository::review_edit_proposal(path.id, ProposalReview::Rejected, &pool).await? {
+        return Err(PostError::NotFound);
+    }
+
+    Ok(HttpResponse::Ok().finish())
+}
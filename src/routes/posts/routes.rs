@@ -12,9 +12,26 @@ pub fn post_routes(cfg: &mut web::ServiceConfig) {
             web::scope("/me")
                 .wrap(middleware::from_fn(authentication::reject_anonymous_users))
                 .route("/create", web::post().to(routes::create_post))
+                .route(
+                    "/suggest-metadata",
+                    web::post().to(routes::suggest_post_metadata),
+                )
                 .route("/update/{id}", web::patch().to(routes::update_post))
                 .route("/delete/{id}", web::delete().to(routes::delete_post))
                 .route("/like/{id}", web::patch().to(routes::like_post))
-                .route("/dislike/{id}", web::patch().to(routes::dislike_post)),
+                .route("/dislike/{id}", web::patch().to(routes::dislike_post))
+                .route("/{id}/proposals", web::post().to(routes::propose_post_edit))
+                .route(
+                    "/{id}/proposals",
+                    web::get().to(routes::get_post_edit_proposals),
+                )
+                .route(
+                    "/proposals/{id}/approve",
+                    web::patch().to(routes::approve_post_edit_proposal),
+                )
+                .route(
+                    "/proposals/{id}/reject",
+                    web::patch().to(routes::reject_post_edit_proposal),
+                ),
         );
 }
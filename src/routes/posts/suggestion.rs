@@ -0,0 +1,49 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+
+use crate::{
+    domain::{PostText, SuggestMetadataPayload},
+    suggester::Suggester,
+    utils,
+};
+
+#[derive(thiserror::Error)]
+pub enum SuggestionError {
+    #[error("{0}")]
+    ValidationError(String),
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for SuggestionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for SuggestionError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            SuggestionError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            SuggestionError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+// Nothing is stored, the draft is only analyzed
+#[tracing::instrument(skip_all)]
+pub async fn suggest_post_metadata(
+    payload: web::Json<SuggestMetadataPayload>,
+    suggester: web::Data<dyn Suggester>,
+) -> Result<HttpResponse, SuggestionError> {
+    let text =
+        PostText::parse(payload.into_inner().text).map_err(SuggestionError::ValidationError)?;
+
+    let suggestions = suggester.suggest(&text).await?;
+
+    Ok(HttpResponse::Ok().json(suggestions))
+}
@@ -0,0 +1,67 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{
+    HttpResponse, ResponseError,
+    http::{StatusCode, header},
+    web,
+};
+use sqlx::PgPool;
+
+use super::TrackingPathParams;
+use crate::{domain, repository, utils};
+
+#[derive(thiserror::Error)]
+pub enum ClickTrackingError {
+    #[error("link not found")]
+    NotFound,
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for ClickTrackingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for ClickTrackingError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            ClickTrackingError::NotFound => StatusCode::NOT_FOUND,
+            ClickTrackingError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+// The token is the recipient's tracking token followed by the position of the link. The
+// destination is looked up in the issue rather than carried in the link, so only links the
+// issue really contains are ever redirected to.
+#[tracing::instrument(skip_all)]
+pub async fn track_newsletter_click(
+    path: web::Path<TrackingPathParams>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, ClickTrackingError> {
+    let (token, position) = path
+        .token
+        .rsplit_once('.')
+        .and_then(|(token, position)| Some((token, position.parse::<i32>().ok()?)))
+        .ok_or(ClickTrackingError::NotFound)?;
+
+    let html = repository::get_tracked_issue_html(&pool, token)
+        .await?
+        .ok_or(ClickTrackingError::NotFound)?;
+    let url = usize::try_from(position)
+        .ok()
+        .and_then(|position| domain::tracked_links(&html).into_iter().nth(position))
+        .ok_or(ClickTrackingError::NotFound)?;
+
+    repository::record_newsletter_click(&pool, token, position, &url).await?;
+
+    Ok(HttpResponse::Found()
+        .insert_header((header::LOCATION, url))
+        .insert_header((header::CACHE_CONTROL, "no-store"))
+        .finish())
+}
@@ -0,0 +1,7 @@
+mod click;
+mod open;
+mod routes;
+
+pub use click::*;
+pub use open::*;
+pub use routes::*;
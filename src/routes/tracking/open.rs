@@ -0,0 +1,58 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{
+    HttpResponse, ResponseError,
+    http::{StatusCode, header},
+    web,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use crate::{repository, utils};
+
+// Transparent 1x1 GIF
+const TRACKING_PIXEL: &[u8] = &[
+    0x47, 0x49, 0x46, 0x38, 0x39, 0x61, 0x01, 0x00, 0x01, 0x00, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0xff, 0xff, 0xff, 0x21, 0xf9, 0x04, 0x01, 0x00, 0x00, 0x00, 0x00, 0x2c, 0x00, 0x00, 0x00, 0x00,
+    0x01, 0x00, 0x01, 0x00, 0x00, 0x02, 0x02, 0x44, 0x01, 0x00, 0x3b,
+];
+
+#[derive(thiserror::Error)]
+pub enum TrackingError {
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for TrackingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for TrackingError {
+    fn error_response(&self) -> HttpResponse {
+        utils::build_error_response(StatusCode::INTERNAL_SERVER_ERROR, self.to_string())
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TrackingPathParams {
+    pub token: String,
+}
+
+// The pixel is served for unknown tokens too, so old issues never show a broken image
+#[tracing::instrument(skip_all)]
+pub async fn track_newsletter_open(
+    path: web::Path<TrackingPathParams>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, TrackingError> {
+    if !repository::record_newsletter_open(&pool, &path.token).await? {
+        tracing::info!("Newsletter opened with an unknown tracking token");
+    }
+
+    Ok(HttpResponse::Ok()
+        .content_type("image/gif")
+        // Every open reaches us, rather than a cached copy
+        .insert_header((header::CACHE_CONTROL, "no-store"))
+        .body(TRACKING_PIXEL))
+}
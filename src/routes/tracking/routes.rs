@@ -0,0 +1,15 @@
+use actix_web::web;
+
+use crate::routes;
+
+// Followed from delivered newsletters, so public and outside `/v1`
+pub fn tracking_routes(cfg: &mut web::ServiceConfig) {
+    cfg.route(
+        "/open/{token}",
+        web::get().to(routes::track_newsletter_open),
+    )
+    .route(
+        "/click/{token}",
+        web::get().to(routes::track_newsletter_click),
+    );
+}
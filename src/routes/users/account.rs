@@ -0,0 +1,100 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{
+    HttpRequest, HttpResponse, ResponseError,
+    http::{StatusCode, header},
+    web,
+};
+use sqlx::PgPool;
+
+use crate::{
+    authentication::UserId,
+    configuration::LoginThrottleSettings,
+    domain::{AccountDeletionPolicy, SudoData, UserPassword},
+    repository,
+    routes::{LoginError, reconfirm_password},
+    session_state::TypedSession,
+    utils,
+};
+
+#[derive(thiserror::Error)]
+pub enum AccountDeletionError {
+    #[error("Authentication failed")]
+    AuthError(#[source] anyhow::Error),
+
+    #[error("Too many login attempts, try again in {retry_after_secs} seconds")]
+    RateLimited { retry_after_secs: i32 },
+
+    #[error("user not found")]
+    NotFound,
+
+    #[error("admin accounts cannot be deleted")]
+    Forbidden,
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for AccountDeletionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for AccountDeletionError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            AccountDeletionError::AuthError(_) => StatusCode::UNAUTHORIZED,
+            AccountDeletionError::RateLimited { .. } => StatusCode::TOO_MANY_REQUESTS,
+            AccountDeletionError::NotFound => StatusCode::NOT_FOUND,
+            AccountDeletionError::Forbidden => StatusCode::FORBIDDEN,
+            AccountDeletionError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        let mut response = utils::build_error_response(status_code, self.to_string());
+        if let AccountDeletionError::RateLimited { retry_after_secs } = self {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, (*retry_after_secs).into());
+        }
+        response
+    }
+}
+
+// The password is asked for again even in sudo mode, as there is no undoing this
+#[tracing::instrument(
+    skip(req, payload, pool, session, policy, throttle),
+    fields(user_id=%&*user_id)
+)]
+pub async fn delete_own_account(
+    req: HttpRequest,
+    payload: web::Json<SudoData>,
+    pool: web::Data<PgPool>,
+    session: TypedSession,
+    policy: web::Data<AccountDeletionPolicy>,
+    throttle: web::Data<LoginThrottleSettings>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, AccountDeletionError> {
+    // Same generic error as login so a malformed password reveals nothing
+    let password: UserPassword = payload
+        .0
+        .try_into()
+        .map_err(|_| AccountDeletionError::AuthError(anyhow::anyhow!("Invalid credentials")))?;
+
+    reconfirm_password(&req, **user_id, password.into_secret(), &throttle, &pool)
+        .await
+        .map_err(|e| match e {
+            LoginError::RateLimited { retry_after_secs } => {
+                AccountDeletionError::RateLimited { retry_after_secs }
+            }
+            LoginError::AuthError(_) | LoginError::Suspended(_) => {
+                AccountDeletionError::AuthError(e.into())
+            }
+            LoginError::UnexpectedError(_) => AccountDeletionError::UnexpectedError(e.into()),
+        })?;
+
+    let summary = repository::delete_account(**user_id, **policy, &pool).await?;
+    session.log_out();
+
+    Ok(HttpResponse::Ok().json(summary))
+}
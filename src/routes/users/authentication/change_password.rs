@@ -1,13 +1,17 @@
 use std::fmt::{self, Debug, Formatter};
 
 use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+use anyhow::Context;
 use sqlx::PgPool;
 
 use crate::{
     authentication,
     authentication::{AuthError, Credentials, UserId},
     domain::ChangePasswordData,
-    repository, utils,
+    repository,
+    session_state::TypedSession,
+    startup::{MinPasswordScore, PasswordHistorySize},
+    utils,
 };
 
 #[derive(thiserror::Error)]
@@ -45,6 +49,9 @@ pub async fn change_password(
     payload: web::Json<ChangePasswordData>,
     pool: web::Data<PgPool>,
     user_id: web::ReqData<UserId>,
+    min_password_score: web::Data<MinPasswordScore>,
+    password_history_size: web::Data<PasswordHistorySize>,
+    session: TypedSession,
 ) -> Result<HttpResponse, ChangePasswordError> {
     let user_id = user_id.into_inner();
     let username = repository::get_username(*user_id, &pool).await?;
@@ -53,6 +60,10 @@ pub async fn change_password(
         .0
         .try_into()
         .map_err(ChangePasswordError::BadRequest)?;
+    let email = repository::get_user_email(*user_id, &pool).await?;
+    new_password
+        .ensure_strong(min_password_score.0, &[&username, &email])
+        .map_err(ChangePasswordError::BadRequest)?;
 
     let credentials = Credentials {
         user_name: username,
@@ -66,7 +77,44 @@ pub async fn change_password(
         };
     }
 
-    authentication::change_password(*user_id, new_password.into_secret(), &pool).await?;
+    let new_password = new_password.into_secret();
+    if authentication::is_recently_used_password(
+        *user_id,
+        new_password.clone(),
+        password_history_size.0,
+        &pool,
+    )
+    .await?
+    {
+        return Err(ChangePasswordError::BadRequest(reused_password_message(
+            password_history_size.0,
+        )));
+    }
+
+    // The caller just proved they know the password, so their own session is kept
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+    authentication::change_password(
+        &mut transaction,
+        *user_id,
+        new_password,
+        password_history_size.0,
+        session.get_session_id()?,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to change a password")?;
 
     Ok(HttpResponse::Ok().finish())
 }
+
+pub fn reused_password_message(history_size: u16) -> String {
+    match history_size {
+        1 => "Invalid user password: must differ from the current password".to_string(),
+        n => format!("Invalid user password: must differ from your last {n} passwords"),
+    }
+}
@@ -1,15 +1,25 @@
 use std::fmt::{self, Debug, Formatter};
 
-use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+use actix_web::{
+    HttpRequest, HttpResponse, ResponseError,
+    http::{StatusCode, header},
+    web,
+};
+use anyhow::Context;
+use secrecy::Secret;
 use sqlx::PgPool;
 use tracing::Span;
+use uuid::Uuid;
 
 use crate::{
     authentication,
-    authentication::{AuthError, Credentials},
-    domain::LoginData,
+    authentication::{AuthError, Credentials, UserId},
+    client_ip::client_address,
+    configuration::{JwtSettings, LoginThrottleSettings, SessionSettings},
+    domain::{AuditAction, LoginData, Suspension},
     repository,
-    session_state::TypedSession,
+    routes::{device_metadata, issue_token_pair},
+    session_state::{self, TypedSession},
     utils,
 };
 
@@ -17,6 +27,10 @@ use crate::{
 pub enum LoginError {
     #[error("Authentication failed")]
     AuthError(#[source] anyhow::Error),
+    #[error("{0}")]
+    Suspended(Suspension),
+    #[error("Too many login attempts, try again in {retry_after_secs} seconds")]
+    RateLimited { retry_after_secs: i32 },
     #[error(transparent)]
     UnexpectedError(#[from] anyhow::Error),
 }
@@ -29,12 +43,24 @@ impl Debug for LoginError {
 
 impl ResponseError for LoginError {
     fn error_response(&self) -> HttpResponse {
+        if let LoginError::Suspended(suspension) = self {
+            return authentication::suspended_response(suspension);
+        }
+
         let status_code = match self {
             LoginError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             LoginError::AuthError(_) => StatusCode::UNAUTHORIZED,
+            LoginError::Suspended(_) => StatusCode::FORBIDDEN,
+            LoginError::RateLimited { .. } => StatusCode::TOO_MANY_REQUESTS,
         };
 
-        utils::build_error_response(status_code, self.to_string())
+        let mut response = utils::build_error_response(status_code, self.to_string());
+        if let LoginError::RateLimited { retry_after_secs } = self {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, (*retry_after_secs).into());
+        }
+        response
     }
 }
 
@@ -43,37 +69,254 @@ impl ResponseError for LoginError {
     fields(user_name=tracing::field::Empty)
 )]
 pub async fn login(
+    req: HttpRequest,
     payload: web::Json<LoginData>,
     pool: web::Data<PgPool>,
     session: TypedSession,
+    throttle: web::Data<LoginThrottleSettings>,
+    session_settings: web::Data<SessionSettings>,
 ) -> Result<HttpResponse, LoginError> {
+    let remember_me = payload.remember_me;
+    let user_id = authenticate(&req, payload.0, &pool, &throttle).await?;
+
+    let remember_token = remember_me.then(utils::generate_token);
+    start_session(&req, &session, user_id, remember_token.as_deref(), &pool).await?;
+
+    let mut response = HttpResponse::Ok();
+    if let Some(token) = remember_token {
+        response.cookie(session_state::remember_me_cookie(
+            token,
+            session_settings.remember_me_lifetime(),
+        ));
+    }
+    Ok(response.finish())
+}
+
+// Logs `user_id` in on the caller's browser, once they proved who they are
+pub async fn start_session(
+    req: &HttpRequest,
+    session: &TypedSession,
+    user_id: Uuid,
+    remember_token: Option<&str>,
+    pool: &PgPool,
+) -> Result<(), anyhow::Error> {
+    let role = repository::get_user_role(user_id, pool).await?;
+    let session_id = repository::insert_session(
+        user_id,
+        &device_metadata(req),
+        remember_token.map(utils::hash_token).as_deref(),
+        pool,
+    )
+    .await?;
+
+    session.renew();
+    session.insert_user_id(user_id)?;
+    session.insert_role(role)?;
+    session.insert_session_id(session_id)?;
+    Ok(())
+}
+
+// The same credential check and throttling as `login`, answered with a bearer token instead of
+// a session for clients that cannot keep cookies. Each call starts a new refresh token family.
+#[tracing::instrument(
+    skip_all,
+    fields(user_name=tracing::field::Empty)
+)]
+pub async fn issue_access_token(
+    req: HttpRequest,
+    payload: web::Json<LoginData>,
+    pool: web::Data<PgPool>,
+    throttle: web::Data<LoginThrottleSettings>,
+    jwt_settings: web::Data<JwtSettings>,
+) -> Result<HttpResponse, LoginError> {
+    let user_id = authenticate(&req, payload.0, &pool, &throttle).await?;
+    let role = repository::get_user_role(user_id, &pool).await?;
+
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+    let tokens = issue_token_pair(
+        &mut transaction,
+        user_id,
+        role,
+        Uuid::new_v4(),
+        &device_metadata(&req),
+        &jwt_settings,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to store a refresh token")?;
+
+    Ok(HttpResponse::Ok().json(tokens))
+}
+
+// Records the user name on the caller's span, and the attempt in the account's login history
+async fn authenticate(
+    req: &HttpRequest,
+    payload: LoginData,
+    pool: &PgPool,
+    throttle: &LoginThrottleSettings,
+) -> Result<Uuid, LoginError> {
+    // Both limits are checked before the password is, that is what they protect
+    let ip_address = client_address(req);
+    throttle_login_attempt(
+        &format!("ip:{ip_address}"),
+        throttle.max_attempts_per_ip,
+        throttle,
+        pool,
+    )
+    .await?;
+
     // Validate payload (returns generic auth error on validation failure)
     let credentials: Credentials = payload
-        .0
         .try_into()
         .map_err(|_| LoginError::AuthError(anyhow::anyhow!("Invalid credentials")))?;
 
     Span::current().record("user_name", tracing::field::display(&credentials.user_name));
 
-    let user_id = authentication::validate_credentials(credentials, &pool)
+    // User names are unique regardless of case, so are their attempts
+    throttle_login_attempt(
+        &format!("user:{}", credentials.user_name.to_lowercase()),
+        throttle.max_attempts_per_user_name,
+        throttle,
+        pool,
+    )
+    .await?;
+
+    let user_name = credentials.user_name.clone();
+    let device = device_metadata(req);
+    let user_id = match authentication::validate_credentials(credentials, pool).await {
+        Ok(user_id) => user_id,
+        Err(e @ AuthError::InvalidCredentials(_)) => {
+            repository::record_failed_login(&user_name, &device, pool).await?;
+            return Err(LoginError::AuthError(e.into()));
+        }
+        Err(e @ AuthError::UnexpectedError(_)) => {
+            return Err(LoginError::UnexpectedError(e.into()));
+        }
+    };
+
+    // Only checked once the password matched, so the ban is never revealed to anyone else
+    if let Some(suspension) = repository::get_suspension(user_id, pool).await? {
+        repository::record_failed_login(&user_name, &device, pool).await?;
+        return Err(LoginError::Suspended(suspension));
+    }
+
+    repository::record_successful_login(user_id, &device, pool).await?;
+    repository::record_audit_event(
+        pool,
+        AuditAction::Login,
+        Some(user_id),
+        Some(user_id),
+        serde_json::json!({
+            "method": "password",
+            "ip_address": device.ip_address,
+            "user_agent": device.user_agent,
+        }),
+    )
+    .await?;
+    Ok(user_id)
+}
+
+// Asks a signed-in user for their password again before a sensitive operation. Counted against
+// the same budgets as `login`, so a stolen session guesses the password no faster than the login
+// form does.
+pub async fn reconfirm_password(
+    req: &HttpRequest,
+    user_id: Uuid,
+    password: Secret<String>,
+    throttle: &LoginThrottleSettings,
+    pool: &PgPool,
+) -> Result<(), LoginError> {
+    throttle_login_attempt(
+        &format!("ip:{}", client_address(req)),
+        throttle.max_attempts_per_ip,
+        throttle,
+        pool,
+    )
+    .await?;
+
+    let user_name = repository::get_username(user_id, pool).await?;
+    throttle_login_attempt(
+        &format!("user:{}", user_name.to_lowercase()),
+        throttle.max_attempts_per_user_name,
+        throttle,
+        pool,
+    )
+    .await?;
+
+    let credentials = Credentials {
+        user_name,
+        password,
+    };
+    authentication::validate_credentials(credentials, pool)
         .await
         .map_err(|e| match e {
             AuthError::InvalidCredentials(_) => LoginError::AuthError(e.into()),
             AuthError::UnexpectedError(_) => LoginError::UnexpectedError(e.into()),
         })?;
+    Ok(())
+}
 
-    let is_admin = repository::is_admin_user(user_id, &pool).await?;
-
-    session.renew();
-    session.insert_user_id(user_id)?;
-    session.insert_is_admin(is_admin)?;
+async fn throttle_login_attempt(
+    key: &str,
+    max_attempts: i32,
+    throttle: &LoginThrottleSettings,
+    pool: &PgPool,
+) -> Result<(), LoginError> {
+    let attempts = repository::record_login_attempt(key, throttle.window_seconds, pool).await?;
+    if attempts.count > max_attempts {
+        tracing::warn!(key, attempts = attempts.count, "Login attempts throttled");
+        return Err(LoginError::RateLimited {
+            retry_after_secs: attempts.retry_after_secs,
+        });
+    }
+    Ok(())
+}
 
-    Ok(HttpResponse::Ok().finish())
+#[tracing::instrument(skip(session, pool), fields(user_id=%&*user_id))]
+pub async fn log_out(
+    session: TypedSession,
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, LoginError> {
+    if let Some(session_id) = session.get_session_id()? {
+        repository::revoke_session(**user_id, session_id, &pool).await?;
+    }
+    session.log_out();
+    Ok(HttpResponse::Ok()
+        .cookie(session_state::remember_me_removal_cookie())
+        .finish())
 }
 
-pub async fn log_out(session: TypedSession) -> Result<HttpResponse, LoginError> {
+// Signs the user out of every browser and bearer-token client, including this one. Access tokens
+// already issued stay valid until they expire.
+#[tracing::instrument(skip(session, pool), fields(user_id=%&*user_id))]
+pub async fn log_out_everywhere(
+    session: TypedSession,
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, LoginError> {
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+    let sessions = repository::revoke_all_sessions(&mut transaction, **user_id, None).await?;
+    let refresh_tokens =
+        repository::revoke_refresh_tokens_of_user(&mut transaction, **user_id).await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to log out everywhere")?;
+    tracing::info!(sessions, refresh_tokens, "Logged out everywhere");
+
     session.log_out();
-    Ok(HttpResponse::Ok().finish())
+    Ok(HttpResponse::Ok()
+        .cookie(session_state::remember_me_removal_cookie())
+        .finish())
 }
 
 #[tracing::instrument()]
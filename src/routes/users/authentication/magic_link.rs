@@ -0,0 +1,146 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpRequest, HttpResponse, ResponseError, http::StatusCode, web};
+use anyhow::Context;
+use sqlx::PgPool;
+
+use crate::{
+    authentication,
+    domain::{
+        AuditAction, MagicLinkLoginPayload, MagicLinkPayload, Suspension, TokenPurpose, UserEmail,
+    },
+    email_client::{EmailClient, EmailError, SendOutcome},
+    repository,
+    routes::{device_metadata, start_session},
+    session_state::TypedSession,
+    utils,
+};
+
+// Sign-in links are only valid this long after being sent
+pub const MAGIC_LINK_TOKEN_TTL_MINUTES: i32 = 15;
+
+#[derive(thiserror::Error)]
+pub enum MagicLinkError {
+    #[error("{0}")]
+    ValidationError(String),
+
+    #[error("Invalid or expired sign-in link.")]
+    InvalidToken,
+
+    #[error("{0}")]
+    Suspended(Suspension),
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for MagicLinkError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for MagicLinkError {
+    fn error_response(&self) -> HttpResponse {
+        if let MagicLinkError::Suspended(suspension) = self {
+            return authentication::suspended_response(suspension);
+        }
+
+        let status_code = match self {
+            MagicLinkError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            MagicLinkError::InvalidToken => StatusCode::UNAUTHORIZED,
+            MagicLinkError::Suspended(_) => StatusCode::FORBIDDEN,
+            MagicLinkError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+// Answers the same, and as fast, whether or not an account uses the address, like
+// `forgot_password`
+#[tracing::instrument(skip_all)]
+pub async fn request_magic_link(
+    payload: web::Json<MagicLinkPayload>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, MagicLinkError> {
+    let email =
+        UserEmail::parse(payload.into_inner().email).map_err(MagicLinkError::ValidationError)?;
+
+    repository::queue_account_email(&email, TokenPurpose::MagicLink, &pool).await?;
+
+    Ok(HttpResponse::Accepted().finish())
+}
+
+// The token is used up even when the account turns out to be suspended
+#[tracing::instrument(skip_all, fields(user_id = tracing::field::Empty))]
+pub async fn login_with_magic_link(
+    req: HttpRequest,
+    payload: web::Json<MagicLinkLoginPayload>,
+    pool: web::Data<PgPool>,
+    session: TypedSession,
+) -> Result<HttpResponse, MagicLinkError> {
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+    let user_id =
+        repository::consume_token(&mut transaction, &payload.token, TokenPurpose::MagicLink)
+            .await?
+            .ok_or(MagicLinkError::InvalidToken)?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to consume a sign-in token")?;
+    tracing::Span::current().record("user_id", tracing::field::display(user_id));
+
+    if let Some(suspension) = repository::get_suspension(user_id, &pool).await? {
+        return Err(MagicLinkError::Suspended(suspension));
+    }
+
+    let device = device_metadata(&req);
+    repository::record_successful_login(user_id, &device, &pool).await?;
+    repository::record_audit_event(
+        &**pool,
+        AuditAction::Login,
+        Some(user_id),
+        Some(user_id),
+        serde_json::json!({
+            "method": "magic_link",
+            "ip_address": device.ip_address,
+            "user_agent": device.user_agent,
+        }),
+    )
+    .await?;
+
+    start_session(&req, &session, user_id, None, &pool).await?;
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[tracing::instrument(
+    skip_all,
+    fields(user_email = %user_email)
+)]
+pub async fn send_magic_link_email(
+    email_client: &EmailClient,
+    user_email: UserEmail,
+    base_url: &str,
+    token: &str,
+) -> Result<SendOutcome, EmailError> {
+    // The client serves this page and posts the token back, so mail scanners that follow links
+    // cannot use it up
+    let magic_link = format!("{base_url}/login/magic-link?token={token}");
+    let plain_body = format!(
+        "Someone asked to sign in to your TechHub account.\n\
+        Visit {magic_link} within {MAGIC_LINK_TOKEN_TTL_MINUTES} minutes to sign in. \
+        If it was not you, ignore this email.",
+    );
+    let html_body = format!(
+        "Someone asked to sign in to your TechHub account.<br />\
+        Click <a href=\"{magic_link}\">here</a> within {MAGIC_LINK_TOKEN_TTL_MINUTES} minutes \
+        to sign in. If it was not you, ignore this email.",
+    );
+    email_client
+        .send_email(&user_email, "Sign in to TechHub", &html_body, &plain_body)
+        .await
+}
@@ -1,7 +1,15 @@
 pub mod change_password;
 pub mod login;
+pub mod magic_link;
+pub mod password_reset;
 pub mod register;
+pub mod sudo;
+pub mod token;
 
 pub use change_password::*;
 pub use login::*;
+pub use magic_link::*;
+pub use password_reset::*;
 pub use register::*;
+pub use sudo::*;
+pub use token::*;
@@ -0,0 +1,153 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+use anyhow::Context;
+use secrecy::ExposeSecret;
+use sqlx::PgPool;
+
+use crate::{
+    authentication,
+    domain::{ForgotPasswordPayload, ResetPasswordPayload, TokenPurpose, UserEmail, UserPassword},
+    email_client::{EmailClient, EmailError, SendOutcome},
+    repository,
+    routes::reused_password_message,
+    startup::{MinPasswordScore, PasswordHistorySize},
+    utils,
+};
+
+// Reset links are only valid this long after being sent
+pub const PASSWORD_RESET_TOKEN_TTL_MINUTES: i32 = 60;
+
+#[derive(thiserror::Error)]
+pub enum PasswordResetError {
+    #[error("{0}")]
+    ValidationError(String),
+
+    #[error("Invalid or expired password reset token.")]
+    InvalidToken,
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for PasswordResetError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for PasswordResetError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            PasswordResetError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            PasswordResetError::InvalidToken => StatusCode::UNAUTHORIZED,
+            PasswordResetError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+// Answers the same, and as fast, whether or not an account uses the address, so the endpoint
+// cannot be used to find out who is registered. The worker sends the email, see
+// `newsletter_delivery_worker::send_account_emails`.
+#[tracing::instrument(skip_all)]
+pub async fn forgot_password(
+    payload: web::Json<ForgotPasswordPayload>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, PasswordResetError> {
+    let email = UserEmail::parse(payload.into_inner().email)
+        .map_err(PasswordResetError::ValidationError)?;
+
+    repository::queue_account_email(&email, TokenPurpose::PasswordReset, &pool).await?;
+
+    Ok(HttpResponse::Accepted().finish())
+}
+
+// A rejected password rolls the token back, so it does not cost the user their link
+#[tracing::instrument(skip_all, fields(user_id = tracing::field::Empty))]
+pub async fn reset_password(
+    payload: web::Json<ResetPasswordPayload>,
+    pool: web::Data<PgPool>,
+    min_password_score: web::Data<MinPasswordScore>,
+    password_history_size: web::Data<PasswordHistorySize>,
+) -> Result<HttpResponse, PasswordResetError> {
+    let payload = payload.into_inner();
+    let new_password = UserPassword::parse(payload.new_password.expose_secret().to_string())
+        .map_err(PasswordResetError::ValidationError)?;
+
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+    let user_id = repository::consume_token(
+        &mut transaction,
+        &payload.token,
+        TokenPurpose::PasswordReset,
+    )
+    .await?
+    .ok_or(PasswordResetError::InvalidToken)?;
+    tracing::Span::current().record("user_id", tracing::field::display(user_id));
+
+    let user_name = repository::get_username(user_id, &pool).await?;
+    let email = repository::get_user_email(user_id, &pool).await?;
+    new_password
+        .ensure_strong(min_password_score.0, &[&user_name, &email])
+        .map_err(PasswordResetError::ValidationError)?;
+
+    let new_password = new_password.into_secret();
+    if authentication::is_recently_used_password(
+        user_id,
+        new_password.clone(),
+        password_history_size.0,
+        &pool,
+    )
+    .await?
+    {
+        return Err(PasswordResetError::ValidationError(
+            reused_password_message(password_history_size.0),
+        ));
+    }
+
+    authentication::change_password(
+        &mut transaction,
+        user_id,
+        new_password,
+        password_history_size.0,
+        None,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to reset a password")?;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[tracing::instrument(
+    skip_all,
+    fields(user_email = %user_email)
+)]
+pub async fn send_password_reset_email(
+    email_client: &EmailClient,
+    user_email: UserEmail,
+    base_url: &str,
+    token: &str,
+) -> Result<SendOutcome, EmailError> {
+    // The client serves this page and posts the token back with the new password
+    let reset_link = format!("{base_url}/reset-password?token={token}");
+    let plain_body = format!(
+        "Someone asked to reset the password of your TechHub account.\n\
+        Visit {reset_link} within {PASSWORD_RESET_TOKEN_TTL_MINUTES} minutes to choose a new one. \
+        If it was not you, ignore this email.",
+    );
+    let html_body = format!(
+        "Someone asked to reset the password of your TechHub account.<br />\
+        Click <a href=\"{reset_link}\">here</a> within {PASSWORD_RESET_TOKEN_TTL_MINUTES} minutes \
+        to choose a new one. If it was not you, ignore this email.",
+    );
+    email_client
+        .send_email(&user_email, "Reset your password", &html_body, &plain_body)
+        .await
+}
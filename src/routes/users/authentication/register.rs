@@ -1,16 +1,20 @@
 use std::fmt::{self, Debug, Formatter};
 
-use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+use actix_web::{HttpRequest, HttpResponse, ResponseError, http::StatusCode, web};
 use anyhow::Context;
+use secrecy::ExposeSecret;
 use sqlx::PgPool;
 use tracing::{Span, field};
 
 use crate::{
     authentication,
-    domain::{NewUser, UserData, UserEmail},
-    email_client::{EmailClient, EmailError},
-    repository,
-    startup::ApplicationBaseUrl,
+    captcha_client::CaptchaClient,
+    client_ip::client_address,
+    configuration::RegistrationSettings,
+    domain::{NewUser, TokenPurpose, UserData, UserEmail},
+    email_client::{EmailClient, EmailError, SendOutcome},
+    repository::{self, TokenLookupError},
+    startup::{ApplicationBaseUrl, MinPasswordScore},
     telemetry, utils,
 };
 
@@ -20,6 +24,12 @@ pub enum RegisterError {
     #[error("{0}")]
     ValidationError(String),
 
+    #[error("user name is already taken")]
+    UserNameTaken,
+
+    #[error("captcha verification failed")]
+    CaptchaFailed,
+
     #[error(transparent)]
     UnexpectedError(#[from] anyhow::Error),
 }
@@ -34,6 +44,8 @@ impl ResponseError for RegisterError {
     fn error_response(&self) -> HttpResponse {
         let status_code = match self {
             RegisterError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            RegisterError::UserNameTaken => StatusCode::CONFLICT,
+            RegisterError::CaptchaFailed => StatusCode::FORBIDDEN,
             RegisterError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
         };
 
@@ -41,6 +53,7 @@ impl ResponseError for RegisterError {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 #[tracing::instrument(
     skip_all,
     fields(
@@ -49,24 +62,47 @@ impl ResponseError for RegisterError {
     )
 )]
 pub async fn register_user(
+    req: HttpRequest,
     payload: web::Json<UserData>,
     pool: web::Data<PgPool>,
     email_client: web::Data<EmailClient>,
     base_url: web::Data<ApplicationBaseUrl>,
+    min_password_score: web::Data<MinPasswordScore>,
+    settings: web::Data<RegistrationSettings>,
+    captcha_client: web::Data<CaptchaClient>,
 ) -> Result<HttpResponse, RegisterError> {
+    let mut payload = payload.into_inner();
+    let captcha_token = payload.captcha_token.take();
+
     // ValidationError doesn't have a from or source hence we have to map this error to the correct enum variant
     let NewUser {
         user_name: name,
         email,
         password,
-    } = payload
-        .0
-        .try_into()
+    } = payload.try_into().map_err(RegisterError::ValidationError)?;
+    password
+        .ensure_strong(min_password_score.0, &[name.as_ref(), email.as_ref()])
         .map_err(RegisterError::ValidationError)?;
 
     Span::current().record("user_name", field::display(&name));
     Span::current().record("user_email", field::display(&email));
 
+    // Checked once the payload is known to be valid, so malformed requests never reach the
+    // provider, and before anything is hashed or stored
+    if settings.require_captcha {
+        let captcha_token = captcha_token
+            .ok_or_else(|| RegisterError::ValidationError("Missing captcha_token".to_string()))?;
+        let ip_address = client_address(&req);
+
+        let is_human = captcha_client
+            .verify(captcha_token.expose_secret(), &ip_address)
+            .await
+            .context("Failed to verify captcha token")?;
+        if !is_human {
+            return Err(RegisterError::CaptchaFailed);
+        }
+    }
+
     let password_hash = telemetry::spawn_blocking_with_tracing(move || {
         authentication::compute_password_hash(password.into_secret())
     })
@@ -106,7 +142,7 @@ pub async fn send_activation_email(
     user_email: UserEmail,
     base_url: &str,
     token: &str,
-) -> Result<(), EmailError> {
+) -> Result<SendOutcome, EmailError> {
     let confirmation_link = format!("{base_url}/v1/user/activate?token={token}");
     let plain_body =
         format!("Welcome to TechHub!\nVisit {confirmation_link} to activate your account.",);
@@ -119,6 +155,40 @@ pub async fn send_activation_email(
         .await
 }
 
+// Sent by the worker to accounts still not activated a while after registering, each with a
+// fresh link replacing the previous one and a link to stop the reminders
+#[tracing::instrument(
+    skip_all,
+    fields(user_email = %user_email)
+)]
+pub async fn send_activation_reminder_email(
+    email_client: &EmailClient,
+    user_email: UserEmail,
+    base_url: &str,
+    token: &str,
+    opt_out_token: &str,
+) -> Result<SendOutcome, EmailError> {
+    let confirmation_link = format!("{base_url}/v1/user/activate?token={token}");
+    let opt_out_link =
+        format!("{base_url}/v1/user/activation-reminders/opt-out?token={opt_out_token}");
+    let plain_body = format!(
+        "Your TechHub account is not activated yet.\nVisit {confirmation_link} to activate it. Earlier activation links no longer work.\n\nTo stop these reminders, visit {opt_out_link}",
+    );
+    let html_body = format!(
+        "Your TechHub account is not activated yet.<br />\
+        Click <a href=\"{confirmation_link}\">here</a> to activate it. Earlier activation links no longer work.<br /><br />\
+        <a href=\"{opt_out_link}\">Stop these reminders</a>",
+    );
+    email_client
+        .send_email(
+            &user_email,
+            "Activate your TechHub account",
+            &html_body,
+            &plain_body,
+        )
+        .await
+}
+
 #[derive(serde::Deserialize)]
 pub struct ActivationParameters {
     token: String,
@@ -129,10 +199,23 @@ pub enum UserActivationError {
     #[error("There is no user associated with the provided token.")]
     UnknownToken,
 
+    #[error("This activation link has expired, request a new one.")]
+    ExpiredToken,
+
     #[error(transparent)]
     UnexpectedError(#[from] anyhow::Error),
 }
 
+impl From<TokenLookupError> for UserActivationError {
+    fn from(e: TokenLookupError) -> Self {
+        match e {
+            TokenLookupError::Unknown => UserActivationError::UnknownToken,
+            TokenLookupError::Expired => UserActivationError::ExpiredToken,
+            TokenLookupError::UnexpectedError(e) => UserActivationError::UnexpectedError(e),
+        }
+    }
+}
+
 impl Debug for UserActivationError {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         utils::error_chain_fmt(self, f)
@@ -143,6 +226,7 @@ impl ResponseError for UserActivationError {
     fn error_response(&self) -> HttpResponse {
         let status_code = match self {
             UserActivationError::UnknownToken => StatusCode::UNAUTHORIZED,
+            UserActivationError::ExpiredToken => StatusCode::GONE,
             UserActivationError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
         };
 
@@ -158,12 +242,69 @@ pub async fn activate_user(
     parameters: web::Query<ActivationParameters>,
     pool: web::Data<PgPool>,
 ) -> Result<HttpResponse, UserActivationError> {
-    let user_id = repository::get_user_id_from_token(&pool, &parameters.token)
-        .await?
-        // Domain error (invalid token), so a new `UserConfirmError::UnknownToken` error is created as there's no existing error to wrap in an `anyhow::Error`
-        .ok_or(UserActivationError::UnknownToken)?;
+    let user_id =
+        repository::get_user_id_from_token(&pool, &parameters.token, TokenPurpose::Activation)
+            .await?;
     Span::current().record("user_id", field::display(user_id));
 
     repository::activate_user_and_delete_token(&pool, user_id, &parameters.token).await?;
     Ok(HttpResponse::Ok().finish())
 }
+
+#[derive(thiserror::Error)]
+pub enum ReminderOptOutError {
+    #[error("There is no user associated with the provided token.")]
+    UnknownToken,
+
+    #[error("This link has expired, no more reminders will be sent.")]
+    ExpiredToken,
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl From<TokenLookupError> for ReminderOptOutError {
+    fn from(e: TokenLookupError) -> Self {
+        match e {
+            TokenLookupError::Unknown => ReminderOptOutError::UnknownToken,
+            TokenLookupError::Expired => ReminderOptOutError::ExpiredToken,
+            TokenLookupError::UnexpectedError(e) => ReminderOptOutError::UnexpectedError(e),
+        }
+    }
+}
+
+impl Debug for ReminderOptOutError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for ReminderOptOutError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            ReminderOptOutError::UnknownToken => StatusCode::UNAUTHORIZED,
+            ReminderOptOutError::ExpiredToken => StatusCode::GONE,
+            ReminderOptOutError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+// The link keeps working, so clicking it twice is harmless
+#[tracing::instrument(
+    skip_all,
+    fields(user_id=tracing::field::Empty)
+)]
+pub async fn opt_out_of_activation_reminders(
+    parameters: web::Query<ActivationParameters>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, ReminderOptOutError> {
+    let user_id =
+        repository::get_user_id_from_token(&pool, &parameters.token, TokenPurpose::ReminderOptOut)
+            .await?;
+    Span::current().record("user_id", field::display(user_id));
+
+    repository::opt_out_of_activation_reminders(&pool, user_id).await?;
+    Ok(HttpResponse::Ok().finish())
+}
@@ -0,0 +1,84 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{
+    HttpRequest, HttpResponse, ResponseError,
+    http::{StatusCode, header},
+    web,
+};
+use chrono::Utc;
+use sqlx::PgPool;
+
+use crate::{
+    authentication::{SUDO_MODE_TTL, UserId},
+    configuration::LoginThrottleSettings,
+    domain::{SudoData, UserPassword},
+    routes::{LoginError, reconfirm_password},
+    session_state::TypedSession,
+    utils,
+};
+
+#[derive(thiserror::Error)]
+pub enum SudoError {
+    #[error("Authentication failed")]
+    AuthError(#[source] anyhow::Error),
+    #[error("Too many login attempts, try again in {retry_after_secs} seconds")]
+    RateLimited { retry_after_secs: i32 },
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for SudoError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for SudoError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            SudoError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            SudoError::AuthError(_) => StatusCode::UNAUTHORIZED,
+            SudoError::RateLimited { .. } => StatusCode::TOO_MANY_REQUESTS,
+        };
+
+        let mut response = utils::build_error_response(status_code, self.to_string());
+        if let SudoError::RateLimited { retry_after_secs } = self {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, (*retry_after_secs).into());
+        }
+        response
+    }
+}
+
+#[tracing::instrument(skip(req, payload, pool, session, throttle), fields(user_id=%&*user_id))]
+pub async fn enter_sudo_mode(
+    req: HttpRequest,
+    payload: web::Json<SudoData>,
+    pool: web::Data<PgPool>,
+    session: TypedSession,
+    throttle: web::Data<LoginThrottleSettings>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, SudoError> {
+    // Same generic error as login so a malformed password reveals nothing
+    let password: UserPassword = payload
+        .0
+        .try_into()
+        .map_err(|_| SudoError::AuthError(anyhow::anyhow!("Invalid credentials")))?;
+
+    reconfirm_password(&req, **user_id, password.into_secret(), &throttle, &pool)
+        .await
+        .map_err(|e| match e {
+            LoginError::RateLimited { retry_after_secs } => {
+                SudoError::RateLimited { retry_after_secs }
+            }
+            LoginError::AuthError(_) | LoginError::Suspended(_) => SudoError::AuthError(e.into()),
+            LoginError::UnexpectedError(_) => SudoError::UnexpectedError(e.into()),
+        })?;
+
+    let sudo_until = Utc::now() + SUDO_MODE_TTL;
+    session.insert_sudo_until(sudo_until)?;
+
+    // Server clock time, so clients compare it against `X-Server-Time` rather than their own clock
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "sudo_until": sudo_until })))
+}
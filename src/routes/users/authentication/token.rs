@@ -0,0 +1,176 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{
+    HttpRequest, HttpResponse, ResponseError,
+    http::{StatusCode, header},
+    web,
+};
+use anyhow::Context;
+use chrono::{Duration, Utc};
+use secrecy::ExposeSecret;
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::{
+    authentication::{self, AccessClaims},
+    client_ip::client_address,
+    configuration::JwtSettings,
+    domain::{
+        AccessTokenResponse, DeviceMetadata, REFRESH_TOKEN_PREFIX, RefreshTokenPayload, Role,
+    },
+    repository, utils,
+};
+
+#[derive(thiserror::Error)]
+pub enum TokenRefreshError {
+    #[error("Invalid refresh token")]
+    InvalidToken,
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for TokenRefreshError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for TokenRefreshError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            TokenRefreshError::InvalidToken => StatusCode::UNAUTHORIZED,
+            TokenRefreshError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+pub fn device_metadata(req: &HttpRequest) -> DeviceMetadata {
+    let user_agent = req
+        .headers()
+        .get(header::USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let ip_address = client_address(req);
+
+    DeviceMetadata {
+        user_agent,
+        ip_address,
+    }
+}
+
+// A new access token, and a refresh token stored in `family_id`, the sign-in it continues
+pub async fn issue_token_pair(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    role: Role,
+    family_id: Uuid,
+    device: &DeviceMetadata,
+    jwt_settings: &JwtSettings,
+) -> Result<AccessTokenResponse, anyhow::Error> {
+    let now = Utc::now();
+    let ttl = Duration::minutes(jwt_settings.expiry_minutes);
+    let claims = AccessClaims::new(user_id, role, now, ttl);
+
+    let refresh_token = format!("{REFRESH_TOKEN_PREFIX}{}", utils::generate_token());
+    repository::insert_refresh_token(
+        transaction,
+        family_id,
+        user_id,
+        &utils::hash_token(&refresh_token),
+        device,
+        now + Duration::days(jwt_settings.refresh_token_expiry_days),
+    )
+    .await?;
+
+    Ok(AccessTokenResponse {
+        access_token: authentication::encode_access_token(&claims, &jwt_settings.signing_key),
+        token_type: "Bearer",
+        expires_in: ttl.num_seconds(),
+        refresh_token,
+    })
+}
+
+// Every refresh token works once. Presenting one that was already exchanged means someone kept
+// a copy, and since it cannot be told whether that is the client or an attacker, the whole
+// sign-in is ended.
+#[tracing::instrument(skip_all, fields(user_id = tracing::field::Empty))]
+pub async fn refresh_access_token(
+    req: HttpRequest,
+    payload: web::Json<RefreshTokenPayload>,
+    pool: web::Data<PgPool>,
+    jwt_settings: web::Data<JwtSettings>,
+) -> Result<HttpResponse, TokenRefreshError> {
+    let token_hash = utils::hash_token(payload.refresh_token.expose_secret());
+
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+    let stored = repository::get_refresh_token_for_update(&mut transaction, &token_hash)
+        .await?
+        .ok_or(TokenRefreshError::InvalidToken)?;
+    tracing::Span::current().record("user_id", tracing::field::display(stored.user_id));
+
+    if stored.revoked_at.is_some() || stored.expires_at <= Utc::now() {
+        return Err(TokenRefreshError::InvalidToken);
+    }
+
+    if stored.rotated_at.is_some() {
+        let revoked =
+            repository::revoke_refresh_token_family(&mut transaction, stored.family_id).await?;
+        transaction
+            .commit()
+            .await
+            .context("Failed to commit SQL transaction to revoke a refresh token family")?;
+        tracing::warn!(
+            family_id = %stored.family_id,
+            revoked,
+            "Rotated refresh token reused, revoked its family"
+        );
+        return Err(TokenRefreshError::InvalidToken);
+    }
+
+    repository::mark_refresh_token_rotated(&mut transaction, stored.id).await?;
+    let tokens = issue_token_pair(
+        &mut transaction,
+        stored.user_id,
+        stored.role,
+        stored.family_id,
+        &device_metadata(&req),
+        &jwt_settings,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to rotate a refresh token")?;
+
+    Ok(HttpResponse::Ok().json(tokens))
+}
+
+// Signs a bearer-token client out. Unknown tokens are accepted too, there is nothing to end.
+#[tracing::instrument(skip_all)]
+pub async fn revoke_refresh_token(
+    payload: web::Json<RefreshTokenPayload>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, TokenRefreshError> {
+    let token_hash = utils::hash_token(payload.refresh_token.expose_secret());
+
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+    if let Some(stored) =
+        repository::get_refresh_token_for_update(&mut transaction, &token_hash).await?
+    {
+        repository::revoke_refresh_token_family(&mut transaction, stored.family_id).await?;
+    }
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to revoke a refresh token family")?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
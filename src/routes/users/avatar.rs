@@ -0,0 +1,106 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{
+    HttpRequest, HttpResponse, ResponseError,
+    http::{
+        StatusCode,
+        header::{self, CacheControl, CacheDirective},
+    },
+    web,
+};
+use sqlx::PgPool;
+
+use crate::{
+    authentication::UserId,
+    domain::{Avatar, avatar_url},
+    repository,
+    routes::UserPathParams,
+    utils,
+};
+
+#[derive(thiserror::Error)]
+pub enum AvatarError {
+    #[error("{0}")]
+    ValidationError(String),
+
+    #[error("avatar not found")]
+    NotFound,
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for AvatarError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for AvatarError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            AvatarError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            AvatarError::NotFound => StatusCode::NOT_FOUND,
+            AvatarError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+// The image is sent as the raw request body, typed by its `Content-Type` header
+#[tracing::instrument(skip(req, body, pool), fields(user_id=%*user_id))]
+pub async fn upload_avatar(
+    req: HttpRequest,
+    body: web::Bytes,
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, AvatarError> {
+    let content_type = req
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    let avatar =
+        Avatar::parse(content_type, body.to_vec()).map_err(AvatarError::ValidationError)?;
+
+    let user_id = **user_id;
+    let updated_at = repository::store_avatar(user_id, &avatar, &pool).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "avatar_url": avatar_url(user_id, Some(updated_at)),
+    })))
+}
+
+#[tracing::instrument(skip(pool), fields(user_id=%*user_id))]
+pub async fn delete_avatar(
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, AvatarError> {
+    if !repository::delete_avatar(**user_id, &pool).await? {
+        return Err(AvatarError::NotFound);
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[tracing::instrument(skip(pool), fields(user_id=%path.id))]
+pub async fn get_avatar(
+    path: web::Path<UserPathParams>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, AvatarError> {
+    let avatar = repository::get_avatar(path.id, &pool)
+        .await?
+        .ok_or(AvatarError::NotFound)?;
+
+    // Avatar URLs change with every upload, so whatever is served under one never changes
+    Ok(HttpResponse::Ok()
+        .content_type(avatar.content_type)
+        .insert_header(CacheControl(vec![
+            CacheDirective::Public,
+            CacheDirective::MaxAge(365 * 24 * 3600),
+            CacheDirective::Extension("immutable".to_string(), None),
+        ]))
+        .insert_header((header::X_CONTENT_TYPE_OPTIONS, "nosniff"))
+        .body(avatar.image))
+}
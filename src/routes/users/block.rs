@@ -0,0 +1,76 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+use sqlx::PgPool;
+
+use crate::{authentication::UserId, repository, routes::UserPathParams, utils};
+
+#[derive(thiserror::Error)]
+pub enum BlockError {
+    #[error("{0}")]
+    ValidationError(String),
+
+    #[error("user not found")]
+    NotFound,
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for BlockError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for BlockError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            BlockError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            BlockError::NotFound => StatusCode::NOT_FOUND,
+            BlockError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+#[tracing::instrument(skip(pool), fields(user_id=%&*user_id, blocked_id=%path.id))]
+pub async fn block_user(
+    path: web::Path<UserPathParams>,
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, BlockError> {
+    if path.id == **user_id {
+        return Err(BlockError::ValidationError(
+            "You cannot block yourself.".to_string(),
+        ));
+    }
+
+    if !repository::block_user(**user_id, path.id, &pool).await? {
+        return Err(BlockError::NotFound);
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[tracing::instrument(skip(pool), fields(user_id=%&*user_id, blocked_id=%path.id))]
+pub async fn unblock_user(
+    path: web::Path<UserPathParams>,
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, BlockError> {
+    repository::unblock_user(**user_id, path.id, &pool).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[tracing::instrument(skip(pool), fields(user_id=%&*user_id))]
+pub async fn get_own_blocks(
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, BlockError> {
+    let blocked_users = repository::get_blocked_users(**user_id, &pool).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "blocked_users": blocked_users })))
+}
@@ -0,0 +1,66 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+use sqlx::PgPool;
+
+use crate::{
+    domain::{CommentsPage, GetCommentsQuery},
+    repository,
+    routes::UserPathParams,
+    utils,
+};
+
+#[derive(thiserror::Error)]
+pub enum UserCommentsError {
+    #[error("{0}")]
+    ValidationError(String),
+
+    #[error("user not found")]
+    NotFound,
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for UserCommentsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for UserCommentsError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            UserCommentsError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            UserCommentsError::NotFound => StatusCode::NOT_FOUND,
+            UserCommentsError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+#[tracing::instrument(skip(pool), fields(user_id=%path.id))]
+pub async fn get_user_comments(
+    path: web::Path<UserPathParams>,
+    query: web::Query<GetCommentsQuery>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, UserCommentsError> {
+    let page: CommentsPage = query
+        .into_inner()
+        .try_into()
+        .map_err(UserCommentsError::ValidationError)?;
+
+    if !repository::user_exists(path.id, &pool).await? {
+        return Err(UserCommentsError::NotFound);
+    }
+
+    let (comments, total_records) = repository::get_comments_by_user(path.id, &page, &pool).await?;
+
+    let metadata = page.pagination.metadata(total_records);
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "comments": comments,
+        "metadata": metadata
+    })))
+}
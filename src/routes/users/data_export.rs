@@ -0,0 +1,183 @@
+use actix_web::{HttpRequest, HttpResponse, web, web::Bytes};
+use chrono::{DateTime, Utc};
+use futures_util::stream;
+use serde::Serialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::export::{EXPORT_BATCH_SIZE, ExportError, attachment_response};
+use crate::{
+    authentication::UserId, domain::ExportedAccount, repository, startup::ExportCompressionLevel,
+};
+
+// A JSON archive of everything the user has on the site, for data portability requests.
+// The account is loaded up front so a missing one is still a proper 404, the rest is
+// streamed section by section like the post export.
+#[tracing::instrument(skip(req, pool, compression_level), fields(user_id=%&*user_id))]
+pub async fn export_own_data(
+    req: HttpRequest,
+    pool: web::Data<PgPool>,
+    compression_level: web::Data<ExportCompressionLevel>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, ExportError> {
+    let account = repository::get_account_for_export(**user_id, &pool)
+        .await?
+        .ok_or(ExportError::NotFound)?;
+
+    let exporter = DataExporter {
+        pool: pool.get_ref().clone(),
+        user_id: **user_id,
+        account: Some(account),
+        post_cursor: None,
+        comment_cursor: None,
+        like_cursor: None,
+        state: ArchiveState::NotStarted,
+    };
+
+    let body = stream::unfold(exporter, |mut exporter| async move {
+        match exporter.next_chunk().await {
+            Ok(Some(chunk)) => Some((Ok(chunk), exporter)),
+            Ok(None) => None,
+            Err(e) => {
+                tracing::error!(error.cause_chain = ?e, "Failed to export account data");
+                exporter.state = ArchiveState::Finished;
+                Some((Err(ExportError::UnexpectedError(e)), exporter))
+            }
+        }
+    });
+
+    attachment_response(
+        &req,
+        "application/json",
+        "techhub-account.json",
+        body,
+        &compression_level,
+    )
+}
+
+#[derive(Clone, Copy)]
+enum Section {
+    Posts,
+    Comments,
+    Likes,
+}
+
+enum ArchiveState {
+    NotStarted,
+    // Tracks whether the current section already has an item, so items get comma-separated
+    InSection { section: Section, wrote_any: bool },
+    Finished,
+}
+
+struct DataExporter {
+    pool: PgPool,
+    user_id: Uuid,
+    // Taken when the archive header is written
+    account: Option<ExportedAccount>,
+    post_cursor: Option<(DateTime<Utc>, Uuid)>,
+    comment_cursor: Option<(DateTime<Utc>, Uuid)>,
+    like_cursor: Option<Uuid>,
+    state: ArchiveState,
+}
+
+impl DataExporter {
+    async fn next_chunk(&mut self) -> Result<Option<Bytes>, anyhow::Error> {
+        let (section, wrote_any) = match self.state {
+            ArchiveState::Finished => return Ok(None),
+            ArchiveState::NotStarted => {
+                let header = format!(
+                    r#"{{"exported_at":{},"account":{},"posts":["#,
+                    serde_json::to_string(&Utc::now())?,
+                    serde_json::to_string(&self.account.take())?,
+                );
+                self.state = ArchiveState::InSection {
+                    section: Section::Posts,
+                    wrote_any: false,
+                };
+                return Ok(Some(Bytes::from(header)));
+            }
+            ArchiveState::InSection { section, wrote_any } => (section, wrote_any),
+        };
+
+        let chunk = match section {
+            Section::Posts => {
+                let posts = repository::get_user_posts_for_export(
+                    self.user_id,
+                    self.post_cursor,
+                    EXPORT_BATCH_SIZE,
+                    &self.pool,
+                )
+                .await?;
+                if let Some(last) = posts.last() {
+                    self.post_cursor = Some((last.created_at, last.id));
+                }
+                join_items(&posts, wrote_any)?
+            }
+            Section::Comments => {
+                let comments = repository::get_user_comments_for_export(
+                    self.user_id,
+                    self.comment_cursor,
+                    EXPORT_BATCH_SIZE,
+                    &self.pool,
+                )
+                .await?;
+                if let Some(last) = comments.last() {
+                    self.comment_cursor = Some((last.created_at, last.id));
+                }
+                join_items(&comments, wrote_any)?
+            }
+            Section::Likes => {
+                let likes = repository::get_liked_posts_for_export(
+                    self.user_id,
+                    self.like_cursor,
+                    EXPORT_BATCH_SIZE,
+                    &self.pool,
+                )
+                .await?;
+                if let Some(last) = likes.last() {
+                    self.like_cursor = Some(last.post_id);
+                }
+                join_items(&likes, wrote_any)?
+            }
+        };
+
+        // An empty batch means the section is done, so close it and open the next one
+        let Some(chunk) = chunk else {
+            let (next, separator) = match section {
+                Section::Posts => (Some(Section::Comments), r#"],"comments":["#),
+                Section::Comments => (Some(Section::Likes), r#"],"likes":["#),
+                Section::Likes => (None, "]}"),
+            };
+            self.state = match next {
+                Some(section) => ArchiveState::InSection {
+                    section,
+                    wrote_any: false,
+                },
+                None => ArchiveState::Finished,
+            };
+            return Ok(Some(Bytes::from(separator)));
+        };
+
+        self.state = ArchiveState::InSection {
+            section,
+            wrote_any: true,
+        };
+        Ok(Some(Bytes::from(chunk)))
+    }
+}
+
+// `None` for an empty batch, otherwise the items as a comma-separated JSON fragment
+fn join_items<T: Serialize>(items: &[T], wrote_any: bool) -> Result<Option<String>, anyhow::Error> {
+    if items.is_empty() {
+        return Ok(None);
+    }
+
+    let mut chunk = String::new();
+    for (i, item) in items.iter().enumerate() {
+        if wrote_any || i > 0 {
+            chunk.push(',');
+        }
+        chunk.push_str(&serde_json::to_string(item)?);
+    }
+    Ok(Some(chunk))
+}
@@ -0,0 +1,129 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+use anyhow::Context;
+use sqlx::PgPool;
+
+use crate::{
+    authentication::UserId,
+    domain::{ChangeEmailPayload, UserEmail},
+    email_client::{EmailClient, EmailError, SendOutcome},
+    repository,
+    startup::ApplicationBaseUrl,
+    utils,
+};
+
+#[derive(serde::Deserialize)]
+pub struct ConfirmEmailParameters {
+    token: String,
+}
+
+#[derive(thiserror::Error)]
+pub enum EmailChangeError {
+    #[error("{0}")]
+    ValidationError(String),
+
+    #[error("email is already in use")]
+    EmailTaken,
+
+    #[error("Invalid email change token.")]
+    UnknownToken,
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for EmailChangeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for EmailChangeError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            EmailChangeError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            EmailChangeError::EmailTaken => StatusCode::CONFLICT,
+            EmailChangeError::UnknownToken => StatusCode::UNAUTHORIZED,
+            EmailChangeError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+// The current address stays in use for logins, newsletters and recovery until the new one is
+// confirmed from its own inbox
+#[tracing::instrument(
+    skip_all,
+    fields(user_id=%&*user_id)
+)]
+pub async fn request_email_change(
+    payload: web::Json<ChangeEmailPayload>,
+    pool: web::Data<PgPool>,
+    email_client: web::Data<EmailClient>,
+    base_url: web::Data<ApplicationBaseUrl>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, EmailChangeError> {
+    let email =
+        UserEmail::parse(payload.into_inner().email).map_err(EmailChangeError::ValidationError)?;
+
+    if repository::is_email_taken(&email, &pool).await? {
+        return Err(EmailChangeError::EmailTaken);
+    }
+
+    let token = utils::generate_token();
+
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+    repository::replace_email_change_token(&mut transaction, **user_id, &token, &email).await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to store an email change token")?;
+
+    send_email_change_email(&email_client, email, &base_url.0, &token)
+        .await
+        .context("Failed to send an email change confirmation email")?;
+
+    Ok(HttpResponse::Accepted().finish())
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn confirm_email_change(
+    parameters: web::Query<ConfirmEmailParameters>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, EmailChangeError> {
+    repository::confirm_email_change(&parameters.token, &pool).await?;
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[tracing::instrument(
+    skip_all,
+    fields(user_email = %user_email)
+)]
+pub async fn send_email_change_email(
+    email_client: &EmailClient,
+    user_email: UserEmail,
+    base_url: &str,
+    token: &str,
+) -> Result<SendOutcome, EmailError> {
+    let confirmation_link = format!("{base_url}/v1/user/confirm-email?token={token}");
+    let plain_body = format!(
+        "You asked to use this address for your TechHub account.\nVisit {confirmation_link} to confirm the change.",
+    );
+    let html_body = format!(
+        "You asked to use this address for your TechHub account.<br />\
+        Click <a href=\"{confirmation_link}\">here</a> to confirm the change.",
+    );
+    email_client
+        .send_email(
+            &user_email,
+            "Confirm your new email",
+            &html_body,
+            &plain_body,
+        )
+        .await
+}
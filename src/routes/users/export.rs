@@ -0,0 +1,233 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Debug, Formatter},
+};
+
+use actix_web::{
+    HttpRequest, HttpResponse, ResponseError,
+    http::{StatusCode, header},
+    web,
+    web::Bytes,
+};
+use chrono::{DateTime, Utc};
+use futures_util::{Stream, stream};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    authentication::UserId,
+    compression::{ZstdStream, accepts_zstd},
+    domain::{ExportFormat, ExportPostsQuery, ExportedComment, ExportedPost},
+    repository,
+    startup::ExportCompressionLevel,
+    utils,
+};
+
+pub(super) const EXPORT_BATCH_SIZE: i64 = 100;
+
+#[derive(thiserror::Error)]
+pub enum ExportError {
+    #[error("{0}")]
+    ValidationError(String),
+
+    #[error("account not found")]
+    NotFound,
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for ExportError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for ExportError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            ExportError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            ExportError::NotFound => StatusCode::NOT_FOUND,
+            ExportError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+#[tracing::instrument(skip(req, pool, compression_level), fields(user_id=%&*user_id))]
+pub async fn export_own_posts(
+    req: HttpRequest,
+    query: web::Query<ExportPostsQuery>,
+    pool: web::Data<PgPool>,
+    compression_level: web::Data<ExportCompressionLevel>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, ExportError> {
+    let query = query.into_inner();
+    let format = ExportFormat::parse(&query.format).map_err(ExportError::ValidationError)?;
+
+    let content_type = format.content_type();
+    let filename = format!("techhub-posts.{}", format.file_extension());
+
+    let exporter = PostExporter {
+        pool: pool.get_ref().clone(),
+        user_id: **user_id,
+        format,
+        include_comments: query.include_comments,
+        cursor: None,
+        state: ExportState::NotStarted,
+    };
+
+    // Posts are fetched and rendered batch by batch as the client reads the body,
+    // so exporting thousands of posts never holds them all in memory
+    let body = stream::unfold(exporter, |mut exporter| async move {
+        match exporter.next_chunk().await {
+            Ok(Some(chunk)) => Some((Ok(chunk), exporter)),
+            Ok(None) => None,
+            Err(e) => {
+                tracing::error!(error.cause_chain = ?e, "Failed to export posts");
+                exporter.state = ExportState::Finished;
+                Some((Err(ExportError::UnexpectedError(e)), exporter))
+            }
+        }
+    });
+
+    attachment_response(&req, content_type, &filename, body, &compression_level)
+}
+
+// Streams an export as a file download, zstd compressed when the client accepts it
+pub(super) fn attachment_response<S>(
+    req: &HttpRequest,
+    content_type: &str,
+    filename: &str,
+    body: S,
+    compression_level: &ExportCompressionLevel,
+) -> Result<HttpResponse, ExportError>
+where
+    S: Stream<Item = Result<Bytes, ExportError>> + 'static,
+{
+    let mut response = HttpResponse::Ok();
+    response
+        .content_type(content_type)
+        .insert_header((
+            "Content-Disposition",
+            format!("attachment; filename=\"{filename}\""),
+        ))
+        .insert_header((header::VARY, "Accept-Encoding"));
+
+    if accepts_zstd(req) {
+        let body = ZstdStream::new(body, compression_level.0)?;
+        return Ok(response
+            .insert_header((header::CONTENT_ENCODING, "zstd"))
+            .streaming(body));
+    }
+
+    Ok(response.streaming(body))
+}
+
+enum ExportState {
+    NotStarted,
+    // Tracks whether a post was already written, so JSON items get comma-separated
+    InProgress { wrote_any: bool },
+    Finished,
+}
+
+struct PostExporter {
+    pool: PgPool,
+    user_id: Uuid,
+    format: ExportFormat,
+    include_comments: bool,
+    cursor: Option<(DateTime<Utc>, Uuid)>,
+    state: ExportState,
+}
+
+impl PostExporter {
+    async fn next_chunk(&mut self) -> Result<Option<Bytes>, anyhow::Error> {
+        let wrote_any = match self.state {
+            ExportState::Finished => return Ok(None),
+            ExportState::NotStarted => {
+                self.state = ExportState::InProgress { wrote_any: false };
+                return Ok(Some(Bytes::from(self.header())));
+            }
+            ExportState::InProgress { wrote_any } => wrote_any,
+        };
+
+        let mut posts = repository::get_user_posts_for_export(
+            self.user_id,
+            self.cursor,
+            EXPORT_BATCH_SIZE,
+            &self.pool,
+        )
+        .await?;
+
+        if posts.is_empty() {
+            self.state = ExportState::Finished;
+            return Ok(Some(Bytes::from(self.footer())));
+        }
+
+        if let Some(last) = posts.last() {
+            self.cursor = Some((last.created_at, last.id));
+        }
+
+        if self.include_comments {
+            self.attach_comments(&mut posts).await?;
+        }
+
+        let mut chunk = String::new();
+        for (i, post) in posts.iter().enumerate() {
+            match self.format {
+                ExportFormat::Json => {
+                    if wrote_any || i > 0 {
+                        chunk.push(',');
+                    }
+                    chunk.push_str(&serde_json::to_string(post)?);
+                }
+                ExportFormat::Markdown => chunk.push_str(&post.to_markdown()),
+            }
+        }
+
+        self.state = ExportState::InProgress { wrote_any: true };
+        Ok(Some(Bytes::from(chunk)))
+    }
+
+    async fn attach_comments(&self, posts: &mut [ExportedPost]) -> Result<(), anyhow::Error> {
+        let post_ids: Vec<Uuid> = posts.iter().map(|p| p.id).collect();
+        let records = repository::get_comments_for_posts(&post_ids, &self.pool).await?;
+
+        let mut by_post: HashMap<Uuid, Vec<ExportedComment>> = HashMap::new();
+        for record in records {
+            by_post
+                .entry(record.post_id)
+                .or_default()
+                .push(ExportedComment {
+                    id: record.id,
+                    text: record.text,
+                    created_at: record.created_at,
+                    created_by_name: record.user_name,
+                });
+        }
+
+        for post in posts.iter_mut() {
+            post.comments = Some(by_post.remove(&post.id).unwrap_or_default());
+        }
+
+        Ok(())
+    }
+
+    fn header(&self) -> String {
+        match self.format {
+            ExportFormat::Json => "[".to_string(),
+            ExportFormat::Markdown => format!(
+                "<!-- Exported from TechHub on {} -->\n\n",
+                Utc::now().format("%Y-%m-%d %H:%M UTC")
+            ),
+        }
+    }
+
+    fn footer(&self) -> String {
+        match self.format {
+            ExportFormat::Json => "]".to_string(),
+            ExportFormat::Markdown => String::new(),
+        }
+    }
+}
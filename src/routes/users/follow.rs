@@ -0,0 +1,100 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+use sqlx::PgPool;
+
+use crate::{
+    authentication::UserId,
+    domain::{Filters, GetFeedQuery, Metadata},
+    repository,
+    routes::UserPathParams,
+    utils,
+};
+
+#[derive(thiserror::Error)]
+pub enum FollowError {
+    #[error("{0}")]
+    ValidationError(String),
+
+    #[error("user not found")]
+    NotFound,
+
+    #[error("not allowed to follow this user")]
+    Forbidden,
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for FollowError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for FollowError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            FollowError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            FollowError::NotFound => StatusCode::NOT_FOUND,
+            FollowError::Forbidden => StatusCode::FORBIDDEN,
+            FollowError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+#[tracing::instrument(skip(pool), fields(user_id=%&*user_id, followed_id=%path.id))]
+pub async fn follow_user(
+    path: web::Path<UserPathParams>,
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, FollowError> {
+    if path.id == **user_id {
+        return Err(FollowError::ValidationError(
+            "You cannot follow yourself.".to_string(),
+        ));
+    }
+
+    // Either side blocking the other rules out following, without saying who blocked whom
+    if repository::is_block_between(**user_id, path.id, &pool).await? {
+        return Err(FollowError::Forbidden);
+    }
+
+    if !repository::follow_user(**user_id, path.id, &pool).await? {
+        return Err(FollowError::NotFound);
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+// Unfollowing someone not followed is a no-op, so retries always succeed
+#[tracing::instrument(skip(pool), fields(user_id=%&*user_id, followed_id=%path.id))]
+pub async fn unfollow_user(
+    path: web::Path<UserPathParams>,
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, FollowError> {
+    repository::unfollow_user(**user_id, path.id, &pool).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[tracing::instrument(skip(pool), fields(user_id=%&*user_id))]
+pub async fn get_own_feed(
+    query: web::Query<GetFeedQuery>,
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, FollowError> {
+    let filters = Filters::try_from(query.into_inner()).map_err(FollowError::ValidationError)?;
+
+    let (posts, total_records) = repository::get_feed_posts(**user_id, &filters, &pool).await?;
+
+    let metadata = Metadata::calculate(total_records, filters.page.value(), filters.limit.value());
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "posts": posts,
+        "metadata": metadata
+    })))
+}
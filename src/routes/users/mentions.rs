@@ -0,0 +1,58 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+use sqlx::PgPool;
+
+use crate::{
+    authentication::UserId,
+    domain::{PageQuery, Pagination},
+    repository, utils,
+};
+
+#[derive(thiserror::Error)]
+pub enum MentionsError {
+    #[error("{0}")]
+    ValidationError(String),
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for MentionsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for MentionsError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            MentionsError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            MentionsError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+#[tracing::instrument(skip(pool, user_id))]
+pub async fn get_own_mentions(
+    query: web::Query<PageQuery>,
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, MentionsError> {
+    let page: Pagination = query
+        .into_inner()
+        .try_into()
+        .map_err(MentionsError::ValidationError)?;
+
+    let (mentions, total_records) =
+        repository::get_mentions_for_user(*user_id.into_inner(), &page, &pool).await?;
+
+    let metadata = page.metadata(total_records);
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "mentions": mentions,
+        "metadata": metadata
+    })))
+}
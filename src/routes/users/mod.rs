@@ -1,7 +1,35 @@
+mod account;
 mod authentication;
+mod avatar;
+mod block;
+mod comments;
+mod data_export;
+mod email;
+mod export;
+mod follow;
+mod mentions;
+mod personal_access_token;
+mod profile;
 mod routes;
+mod search;
+mod security;
+mod session;
 mod subscription;
 
+pub use account::*;
 pub use authentication::*;
+pub use avatar::*;
+pub use block::*;
+pub use comments::*;
+pub use data_export::*;
+pub use email::*;
+pub use export::*;
+pub use follow::*;
+pub use mentions::*;
+pub use personal_access_token::*;
+pub use profile::*;
 pub use routes::*;
+pub use search::*;
+pub use security::*;
+pub use session::*;
 pub use subscription::*;
@@ -0,0 +1,106 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    authentication::UserId,
+    domain::{
+        CreatePersonalAccessTokenPayload, CreatedPersonalAccessToken, NewPersonalAccessToken,
+        PERSONAL_ACCESS_TOKEN_PREFIX, PersonalAccessTokenSummary,
+    },
+    repository, utils,
+};
+
+#[derive(serde::Deserialize, Debug)]
+pub struct PersonalAccessTokenPathParams {
+    pub id: Uuid,
+}
+
+#[derive(thiserror::Error)]
+pub enum PersonalAccessTokenError {
+    #[error("{0}")]
+    ValidationError(String),
+
+    #[error("token not found")]
+    NotFound,
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for PersonalAccessTokenError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for PersonalAccessTokenError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            PersonalAccessTokenError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            PersonalAccessTokenError::NotFound => StatusCode::NOT_FOUND,
+            PersonalAccessTokenError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+// The token is in the response only, it cannot be retrieved again
+#[tracing::instrument(skip(payload, pool), fields(user_id=%&*user_id))]
+pub async fn create_personal_access_token(
+    payload: web::Json<CreatePersonalAccessTokenPayload>,
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, PersonalAccessTokenError> {
+    let new_token: NewPersonalAccessToken = payload
+        .into_inner()
+        .try_into()
+        .map_err(PersonalAccessTokenError::ValidationError)?;
+
+    let token = format!("{PERSONAL_ACCESS_TOKEN_PREFIX}{}", utils::generate_token());
+    let (id, created_at) = repository::insert_personal_access_token(
+        **user_id,
+        &new_token,
+        &utils::hash_token(&token),
+        &pool,
+    )
+    .await?;
+
+    Ok(HttpResponse::Created().json(CreatedPersonalAccessToken {
+        token,
+        summary: PersonalAccessTokenSummary {
+            id,
+            name: new_token.name,
+            scopes: new_token.scopes,
+            created_at,
+            last_used_at: None,
+        },
+    }))
+}
+
+#[tracing::instrument(skip(pool), fields(user_id=%&*user_id))]
+pub async fn get_own_personal_access_tokens(
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, PersonalAccessTokenError> {
+    let tokens = repository::get_personal_access_tokens(**user_id, &pool).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "tokens": tokens })))
+}
+
+// Takes effect on the token's next request
+#[tracing::instrument(skip(pool), fields(user_id=%&*user_id, token_id=%path.id))]
+pub async fn revoke_personal_access_token(
+    path: web::Path<PersonalAccessTokenPathParams>,
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, PersonalAccessTokenError> {
+    if !repository::revoke_personal_access_token(**user_id, path.id, &pool).await? {
+        return Err(PersonalAccessTokenError::NotFound);
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
@@ -0,0 +1,123 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+use sqlx::PgPool;
+
+use crate::{
+    authentication::UserId,
+    domain::{ChangeUserNamePayload, ProfileUpdate, UpdateProfilePayload, UserEmail, UserName},
+    email_client::{EmailClient, EmailError, SendOutcome},
+    repository,
+    routes::UserPathParams,
+    utils,
+};
+
+#[derive(thiserror::Error)]
+pub enum UserProfileError {
+    #[error("{0}")]
+    ValidationError(String),
+
+    #[error("user not found")]
+    NotFound,
+
+    #[error("user name is already taken")]
+    UserNameTaken,
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for UserProfileError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for UserProfileError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            UserProfileError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            UserProfileError::NotFound => StatusCode::NOT_FOUND,
+            UserProfileError::UserNameTaken => StatusCode::CONFLICT,
+            UserProfileError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+#[tracing::instrument(skip(pool), fields(user_id=%path.id))]
+pub async fn get_user_profile(
+    path: web::Path<UserPathParams>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, UserProfileError> {
+    let profile = repository::get_user_profile(path.id, &pool)
+        .await?
+        .ok_or(UserProfileError::NotFound)?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "user": profile })))
+}
+
+// Responds with the updated public profile
+#[tracing::instrument(skip(payload, pool), fields(user_id=%*user_id))]
+pub async fn update_own_profile(
+    payload: web::Json<UpdateProfilePayload>,
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, UserProfileError> {
+    let update: ProfileUpdate = payload
+        .into_inner()
+        .try_into()
+        .map_err(UserProfileError::ValidationError)?;
+
+    repository::update_profile(**user_id, &update, &pool).await?;
+
+    let profile = repository::get_user_profile(**user_id, &pool)
+        .await?
+        .ok_or(UserProfileError::NotFound)?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "user": profile })))
+}
+
+// Names are unique regardless of case, so a name held by someone else is a conflict
+#[tracing::instrument(skip(payload, pool), fields(user_id=%*user_id))]
+pub async fn change_own_user_name(
+    payload: web::Json<ChangeUserNamePayload>,
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, UserProfileError> {
+    let user_name = UserName::parse(payload.into_inner().user_name)
+        .map_err(UserProfileError::ValidationError)?;
+
+    repository::update_user_name(**user_id, &user_name, &pool).await?;
+
+    let profile = repository::get_user_profile(**user_id, &pool)
+        .await?
+        .ok_or(UserProfileError::NotFound)?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "user": profile })))
+}
+
+// Tells an account renamed when user names became unique which name it logs in with now
+pub async fn send_user_name_change_email(
+    email_client: &EmailClient,
+    user_email: &UserEmail,
+    previous_user_name: &str,
+    user_name: &str,
+) -> Result<SendOutcome, EmailError> {
+    let plain_body = format!(
+        "User names on TechHub are now unique, and another account already had the name {previous_user_name}.\nYour account was renamed to {user_name}, use it to log in from now on. You can choose a different name in your profile.",
+    );
+    let html_body = format!(
+        "User names on TechHub are now unique, and another account already had the name <b>{previous_user_name}</b>.<br />\
+        Your account was renamed to <b>{user_name}</b>, use it to log in from now on. You can choose a different name in your profile.",
+    );
+    email_client
+        .send_email(
+            user_email,
+            "Your TechHub user name has changed",
+            &html_body,
+            &plain_body,
+        )
+        .await
+}
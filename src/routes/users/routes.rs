@@ -1,24 +1,162 @@
 use actix_web::{middleware, web};
 
-use crate::{authentication, routes};
+use crate::{
+    authentication,
+    domain::MAX_AVATAR_BYTES,
+    rate_limiter::{RateLimitedRoute, rate_limit},
+    routes,
+};
 
 pub fn user_routes(cfg: &mut web::ServiceConfig) {
     cfg
         // Public routes
-        .route("/login", web::post().to(routes::login))
-        .route("/register", web::post().to(routes::register_user))
+        .route(
+            "/login",
+            web::post()
+                .to(routes::login)
+                .wrap(middleware::from_fn(|req, next| {
+                    rate_limit(RateLimitedRoute::Login, req, next)
+                })),
+        )
+        .route(
+            "/login/magic-link",
+            web::post()
+                .to(routes::request_magic_link)
+                .wrap(middleware::from_fn(|req, next| {
+                    rate_limit(RateLimitedRoute::AccountEmail, req, next)
+                })),
+        )
+        .route(
+            "/login/magic-link/verify",
+            web::post().to(routes::login_with_magic_link),
+        )
+        .route("/token", web::post().to(routes::issue_access_token))
+        .route(
+            "/token/refresh",
+            web::post().to(routes::refresh_access_token),
+        )
+        .route(
+            "/token/revoke",
+            web::post().to(routes::revoke_refresh_token),
+        )
+        .route(
+            "/register",
+            web::post()
+                .to(routes::register_user)
+                .wrap(middleware::from_fn(|req, next| {
+                    rate_limit(RateLimitedRoute::Registration, req, next)
+                })),
+        )
         .route("/activate", web::get().to(routes::activate_user))
+        .route(
+            "/activation-reminders/opt-out",
+            web::get().to(routes::opt_out_of_activation_reminders),
+        )
         .route("/subscribe", web::get().to(routes::subscribe_user))
+        .route(
+            "/unsubscribe",
+            web::get().to(routes::unsubscribe_with_token),
+        )
+        .route(
+            "/forgot-password",
+            web::post()
+                .to(routes::forgot_password)
+                .wrap(middleware::from_fn(|req, next| {
+                    rate_limit(RateLimitedRoute::AccountEmail, req, next)
+                })),
+        )
+        .route("/reset-password", web::post().to(routes::reset_password))
+        .route(
+            "/confirm-email",
+            web::get().to(routes::confirm_email_change),
+        )
+        .route("/search", web::get().to(routes::search_users))
+        .route("/{id}/comments", web::get().to(routes::get_user_comments))
+        .route("/{id}/avatar", web::get().to(routes::get_avatar))
         // Protected routes (require authentication)
         .service(
             web::scope("/me")
                 .wrap(middleware::from_fn(authentication::reject_anonymous_users))
-                .route("/change-password", web::post().to(routes::change_password))
+                .route("", web::delete().to(routes::delete_own_account))
+                .route("/sudo", web::post().to(routes::enter_sudo_mode))
+                .service(
+                    web::resource("/change-password")
+                        .wrap(middleware::from_fn(authentication::require_sudo_mode))
+                        .route(web::post().to(routes::change_password)),
+                )
+                .service(
+                    web::resource("/email")
+                        .wrap(middleware::from_fn(authentication::require_sudo_mode))
+                        .route(web::post().to(routes::request_email_change)),
+                )
+                .route("/email/unsubscribe", web::post().to(routes::unsubscribe))
+                .route(
+                    "/impersonation/end",
+                    web::post().to(routes::end_impersonation),
+                )
                 .route("/logout", web::post().to(routes::log_out))
+                .route("/logout-all", web::post().to(routes::log_out_everywhere))
+                .route("/export", web::get().to(routes::export_own_data))
+                .route("/posts/export", web::get().to(routes::export_own_posts))
+                .route("/mentions", web::get().to(routes::get_own_mentions))
+                .route("/feed", web::get().to(routes::get_own_feed))
+                .route("/messages", web::get().to(routes::get_own_messages))
+                .route(
+                    "/messages/{id}/read",
+                    web::patch().to(routes::mark_conversation_read),
+                )
+                .route("/blocks", web::get().to(routes::get_own_blocks))
+                .service(
+                    web::resource("/blocks/{id}")
+                        .route(web::put().to(routes::block_user))
+                        .route(web::delete().to(routes::unblock_user)),
+                )
+                .service(
+                    web::resource("/following/{id}")
+                        .route(web::put().to(routes::follow_user))
+                        .route(web::delete().to(routes::unfollow_user)),
+                )
+                .route("/profile", web::patch().to(routes::update_own_profile))
+                .route("/username", web::put().to(routes::change_own_user_name))
+                .service(
+                    web::resource("/avatar")
+                        .app_data(web::PayloadConfig::new(MAX_AVATAR_BYTES))
+                        .route(web::put().to(routes::upload_avatar))
+                        .route(web::delete().to(routes::delete_avatar)),
+                )
                 .route(
                     "/request-subscription",
-                    web::get().to(routes::request_subscription),
+                    web::get()
+                        .to(routes::request_subscription)
+                        .wrap(middleware::from_fn(|req, next| {
+                            rate_limit(RateLimitedRoute::SubscriptionEmail, req, next)
+                        })),
+                )
+                // Creating a token needs a recently entered password, so a token cannot mint more
+                .service(
+                    web::resource("/tokens")
+                        .route(web::get().to(routes::get_own_personal_access_tokens))
+                        .route(
+                            web::post()
+                                .to(routes::create_personal_access_token)
+                                .wrap(middleware::from_fn(authentication::require_sudo_mode)),
+                        ),
+                )
+                .route(
+                    "/tokens/{id}",
+                    web::delete().to(routes::revoke_personal_access_token),
+                )
+                .route("/sessions", web::get().to(routes::get_own_sessions))
+                .route(
+                    "/sessions/{id}",
+                    web::delete().to(routes::revoke_own_session),
+                )
+                .route(
+                    "/security/activity",
+                    web::get().to(routes::get_own_security_activity),
                 )
                 .route("/protected", web::get().to(routes::protected_endpoint)),
-        );
+        )
+        // Last, so the catch-all segment never shadows the named routes above
+        .route("/{id}", web::get().to(routes::get_user_profile));
 }
@@ -0,0 +1,71 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpRequest, HttpResponse, ResponseError, http::StatusCode, web};
+use sqlx::PgPool;
+
+use crate::{
+    client_ip::client_address,
+    domain::{SearchUsersQuery, UserSearch},
+    repository,
+    startup::UserSearchRateLimiter,
+    utils,
+};
+
+#[derive(thiserror::Error)]
+pub enum UserSearchError {
+    #[error("{0}")]
+    ValidationError(String),
+
+    #[error("too many searches, try again later")]
+    RateLimited,
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for UserSearchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for UserSearchError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            UserSearchError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            UserSearchError::RateLimited => StatusCode::TOO_MANY_REQUESTS,
+            UserSearchError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+// Autocompletion fires a search per keystroke, so each client IP gets a per-minute budget
+#[tracing::instrument(skip(req, pool, rate_limiter))]
+pub async fn search_users(
+    req: HttpRequest,
+    query: web::Query<SearchUsersQuery>,
+    pool: web::Data<PgPool>,
+    rate_limiter: web::Data<UserSearchRateLimiter>,
+) -> Result<HttpResponse, UserSearchError> {
+    let ip_address = client_address(&req);
+    if !rate_limiter.0.try_acquire(&ip_address) {
+        tracing::warn!(ip_address, "User search rate limit reached");
+        return Err(UserSearchError::RateLimited);
+    }
+
+    let search: UserSearch = query
+        .into_inner()
+        .try_into()
+        .map_err(UserSearchError::ValidationError)?;
+
+    let (users, total_records) = repository::search_users(&search, &pool).await?;
+
+    let metadata = search.pagination.metadata(total_records);
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "users": users,
+        "metadata": metadata
+    })))
+}
@@ -0,0 +1,42 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+use sqlx::PgPool;
+
+use crate::{authentication::UserId, repository, utils};
+
+// Enough to cover the recent past without paging
+const RECENT_LOGINS_LIMIT: i64 = 50;
+
+#[derive(thiserror::Error)]
+pub enum SecurityActivityError {
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for SecurityActivityError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for SecurityActivityError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            SecurityActivityError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+// Failed attempts are listed too, so the user notices someone guessing their password
+#[tracing::instrument(skip(pool), fields(user_id=%&*user_id))]
+pub async fn get_own_security_activity(
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, SecurityActivityError> {
+    let activity = repository::get_security_activity(**user_id, RECENT_LOGINS_LIMIT, &pool).await?;
+
+    Ok(HttpResponse::Ok().json(activity))
+}
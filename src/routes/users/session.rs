@@ -0,0 +1,80 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{HttpResponse, ResponseError, http::StatusCode, web};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    authentication::UserId,
+    configuration::SessionSettings,
+    repository,
+    session_state::{self, TypedSession},
+    utils,
+};
+
+#[derive(serde::Deserialize, Debug)]
+pub struct SessionPathParams {
+    pub id: Uuid,
+}
+
+#[derive(thiserror::Error)]
+pub enum SessionError {
+    #[error("session not found")]
+    NotFound,
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for SessionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for SessionError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            SessionError::NotFound => StatusCode::NOT_FOUND,
+            SessionError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        utils::build_error_response(status_code, self.to_string())
+    }
+}
+
+// Bearer-token requests have no session of their own, so none is marked current for them
+#[tracing::instrument(skip(session, settings, pool), fields(user_id=%&*user_id))]
+pub async fn get_own_sessions(
+    session: TypedSession,
+    settings: web::Data<SessionSettings>,
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, SessionError> {
+    let sessions =
+        repository::get_active_sessions(**user_id, session.get_session_id()?, &settings, &pool)
+            .await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "sessions": sessions })))
+}
+
+// Other sessions are logged out on their next request, revoking the current one logs it out now
+#[tracing::instrument(skip(session, pool), fields(user_id=%&*user_id, session_id=%path.id))]
+pub async fn revoke_own_session(
+    path: web::Path<SessionPathParams>,
+    session: TypedSession,
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, SessionError> {
+    if !repository::revoke_session(**user_id, path.id, &pool).await? {
+        return Err(SessionError::NotFound);
+    }
+    if session.get_session_id()? == Some(path.id) {
+        session.log_out();
+        return Ok(HttpResponse::NoContent()
+            .cookie(session_state::remember_me_removal_cookie())
+            .finish());
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
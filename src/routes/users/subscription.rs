@@ -7,9 +7,9 @@ use tracing::{Span, field};
 
 use crate::{
     authentication::UserId,
-    domain::UserEmail,
-    email_client::{EmailClient, EmailError},
-    repository,
+    domain::{TokenPurpose, UserEmail},
+    email_client::{EmailClient, EmailError, SendOutcome},
+    repository::{self, TokenLookupError},
     startup::ApplicationBaseUrl,
     utils,
 };
@@ -27,10 +27,23 @@ pub enum SubscriptionError {
     #[error("Invalid subscription token.")]
     UnknownToken,
 
+    #[error("This subscription link has expired, request a new one.")]
+    ExpiredToken,
+
     #[error(transparent)]
     UnexpectedError(#[from] anyhow::Error),
 }
 
+impl From<TokenLookupError> for SubscriptionError {
+    fn from(e: TokenLookupError) -> Self {
+        match e {
+            TokenLookupError::Unknown => SubscriptionError::UnknownToken,
+            TokenLookupError::Expired => SubscriptionError::ExpiredToken,
+            TokenLookupError::UnexpectedError(e) => SubscriptionError::UnexpectedError(e),
+        }
+    }
+}
+
 impl Debug for SubscriptionError {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         utils::error_chain_fmt(self, f)
@@ -42,6 +55,7 @@ impl ResponseError for SubscriptionError {
         let status_code = match self {
             SubscriptionError::ValidationError(_) => StatusCode::BAD_REQUEST,
             SubscriptionError::UnknownToken => StatusCode::UNAUTHORIZED,
+            SubscriptionError::ExpiredToken => StatusCode::GONE,
             SubscriptionError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
         };
 
@@ -57,16 +71,46 @@ pub async fn subscribe_user(
     parameters: web::Query<SubscribeUserParameters>,
     pool: web::Data<PgPool>,
 ) -> Result<HttpResponse, SubscriptionError> {
-    let user_id = repository::get_user_id_from_token(&pool, &parameters.token)
-        .await?
-        // Domain error (invalid token), so a new `UserConfirmError::UnknownToken` error is created instead of wrapping an `anyhow::Error`
-        .ok_or(SubscriptionError::UnknownToken)?;
+    let user_id =
+        repository::get_user_id_from_token(&pool, &parameters.token, TokenPurpose::Subscription)
+            .await?;
     Span::current().record("user_id", field::display(user_id));
 
     repository::subscribe_user_and_delete_token(&pool, user_id, &parameters.token).await?;
     Ok(HttpResponse::Ok().finish())
 }
 
+// Followed from the link at the bottom of every issue. The token is not used up, so following it
+// again is harmless.
+#[tracing::instrument(
+    skip_all,
+    fields(user_id=tracing::field::Empty)
+)]
+pub async fn unsubscribe_with_token(
+    parameters: web::Query<SubscribeUserParameters>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, SubscriptionError> {
+    let user_id =
+        repository::get_user_id_from_token(&pool, &parameters.token, TokenPurpose::Unsubscribe)
+            .await?;
+    Span::current().record("user_id", field::display(user_id));
+
+    repository::unsubscribe_user(&pool, user_id).await?;
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[tracing::instrument(
+    skip_all,
+    fields(user_id=%&*user_id)
+)]
+pub async fn unsubscribe(
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, SubscriptionError> {
+    repository::unsubscribe_user(&pool, **user_id).await?;
+    Ok(HttpResponse::Ok().finish())
+}
+
 #[tracing::instrument(
     skip_all,
     fields(user_id=%&*user_id)
@@ -83,7 +127,7 @@ pub async fn request_subscription(
 
     let activation_token = utils::generate_token();
 
-    repository::store_subscription_token(&pool, *user_id, &activation_token).await?;
+    repository::store_subscription_token(pool.get_ref(), *user_id, &activation_token).await?;
 
     send_subscription_email(&email_client, email, &base_url.0, &activation_token)
         .await
@@ -101,7 +145,22 @@ pub async fn send_subscription_email(
     user_email: UserEmail,
     base_url: &str,
     token: &str,
-) -> Result<(), EmailError> {
+) -> Result<SendOutcome, EmailError> {
+    let (html_body, plain_body) = subscription_email_content(base_url, token);
+    email_client
+        .send_email(
+            &user_email,
+            SUBSCRIPTION_EMAIL_SUBJECT,
+            &html_body,
+            &plain_body,
+        )
+        .await
+}
+
+pub const SUBSCRIPTION_EMAIL_SUBJECT: &str = "Welcome!";
+
+// The HTML and plain text bodies of the email confirming a subscription
+pub fn subscription_email_content(base_url: &str, token: &str) -> (String, String) {
     let confirmation_link = format!("{base_url}/v1/user/subscribe?token={token}");
     let plain_body = format!(
         "Welcome to TechHub Newsletter!\nVisit {confirmation_link} to confirm your subscription to our newsletter.",
@@ -110,7 +169,5 @@ pub async fn send_subscription_email(
         "Welcome to TechHub Newsletter!<br />\
         Click <a href=\"{confirmation_link}\">here</a> to confirm your subscription to our newsletter.",
     );
-    email_client
-        .send_email(&user_email, "Welcome!", &html_body, &plain_body)
-        .await
+    (html_body, plain_body)
 }
@@ -0,0 +1,5 @@
+mod postmark;
+mod routes;
+
+pub use postmark::*;
+pub use routes::*;
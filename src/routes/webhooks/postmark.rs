@@ -0,0 +1,91 @@
+use std::fmt::{self, Debug, Formatter};
+
+use actix_web::{
+    HttpRequest, HttpResponse, ResponseError,
+    http::{StatusCode, header},
+    web,
+};
+use sqlx::PgPool;
+
+use crate::{
+    domain::{PostmarkWebhookEvent, SuppressionReason, verify_webhook_credentials},
+    repository,
+    startup::PostmarkWebhookCredentials,
+    utils,
+};
+
+#[derive(thiserror::Error)]
+pub enum PostmarkWebhookError {
+    #[error("invalid webhook credentials")]
+    InvalidCredentials,
+
+    #[error("invalid webhook event: {0}")]
+    InvalidEvent(String),
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for PostmarkWebhookError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        utils::error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for PostmarkWebhookError {
+    fn error_response(&self) -> HttpResponse {
+        let status_code = match self {
+            PostmarkWebhookError::InvalidCredentials => StatusCode::UNAUTHORIZED,
+            PostmarkWebhookError::InvalidEvent(_) => StatusCode::BAD_REQUEST,
+            PostmarkWebhookError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        let mut response = utils::build_error_response(status_code, self.to_string());
+        if let PostmarkWebhookError::InvalidCredentials = self {
+            response.headers_mut().insert(
+                header::WWW_AUTHENTICATE,
+                header::HeaderValue::from_static(r#"Basic realm="postmark""#),
+            );
+        }
+        response
+    }
+}
+
+// Permanently bounced addresses and addresses that complained of spam are suppressed, nothing is
+// sent to them anymore. Bounced addresses are also marked undeliverable on their user, for the
+// newsletter fan-out.
+// Anything else Postmark reports is acknowledged so it is not retried.
+#[tracing::instrument(skip_all)]
+pub async fn handle_postmark_webhook(
+    request: HttpRequest,
+    body: web::Bytes,
+    pool: web::Data<PgPool>,
+    credentials: web::Data<PostmarkWebhookCredentials>,
+) -> Result<HttpResponse, PostmarkWebhookError> {
+    let authorization = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .ok_or(PostmarkWebhookError::InvalidCredentials)?;
+    if !verify_webhook_credentials(&credentials.username, &credentials.password, authorization) {
+        return Err(PostmarkWebhookError::InvalidCredentials);
+    }
+
+    let event: PostmarkWebhookEvent = serde_json::from_slice(&body)
+        .map_err(|e| PostmarkWebhookError::InvalidEvent(e.to_string()))?;
+
+    if let Some(email) = event.permanently_bounced_email() {
+        if repository::mark_email_undeliverable(email, &pool).await? {
+            tracing::info!("Bounced email marked as undeliverable");
+        } else {
+            tracing::info!("Bounced email belongs to no user");
+        }
+        repository::suppress_email(email, SuppressionReason::HardBounce, &pool).await?;
+    }
+    if let Some(email) = event.spam_complaint_email() {
+        repository::suppress_email(email, SuppressionReason::SpamComplaint, &pool).await?;
+        tracing::info!("Email suppressed after a spam complaint");
+    }
+
+    Ok(HttpResponse::Ok().finish())
+}
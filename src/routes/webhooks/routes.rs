@@ -0,0 +1,8 @@
+use actix_web::web;
+
+use crate::routes;
+
+// Called by third-party services, which authenticate with a signature rather than a session
+pub fn webhook_routes(cfg: &mut web::ServiceConfig) {
+    cfg.route("/postmark", web::post().to(routes::handle_postmark_webhook));
+}
@@ -1,15 +1,45 @@
 use std::future::{Ready, ready};
 
 use actix_session::{Session, SessionExt};
-use actix_web::{FromRequest, HttpRequest, dev::Payload};
+use actix_web::{
+    FromRequest, HttpRequest,
+    cookie::{Cookie, SameSite, time},
+    dev::Payload,
+};
 use anyhow::Context;
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
+use crate::domain::Role;
+
+// Holds the remember-me token of a session logged in with "remember me"
+pub const REMEMBER_ME_COOKIE: &str = "remember_me";
+
+// Kept by the browser across restarts, unlike the session cookie
+pub fn remember_me_cookie(token: String, lifetime: chrono::Duration) -> Cookie<'static> {
+    Cookie::build(REMEMBER_ME_COOKIE, token)
+        .path("/")
+        .http_only(true)
+        .secure(true)
+        .same_site(SameSite::Lax)
+        .max_age(time::Duration::seconds(lifetime.num_seconds()))
+        .finish()
+}
+
+pub fn remember_me_removal_cookie() -> Cookie<'static> {
+    let mut cookie = Cookie::build(REMEMBER_ME_COOKIE, "").path("/").finish();
+    cookie.make_removal();
+    cookie
+}
+
 pub struct TypedSession(Session);
 
 impl TypedSession {
     const USER_ID_KEY: &'static str = "user_id";
-    const IS_ADMIN_KEY: &'static str = "is_admin";
+    const ROLE_KEY: &'static str = "role";
+    const SUDO_UNTIL_KEY: &'static str = "sudo_until";
+    const SESSION_ID_KEY: &'static str = "session_id";
+    const IMPERSONATOR_ID_KEY: &'static str = "impersonator_id";
 
     pub fn renew(&self) {
         self.0.renew();
@@ -21,10 +51,10 @@ impl TypedSession {
             .context("Failed to insert user id into the session")
     }
 
-    pub fn insert_is_admin(&self, is_admin: bool) -> Result<(), anyhow::Error> {
+    pub fn insert_role(&self, role: Role) -> Result<(), anyhow::Error> {
         self.0
-            .insert(Self::IS_ADMIN_KEY, is_admin)
-            .context("Failed to insert admin flag into the session")
+            .insert(Self::ROLE_KEY, role)
+            .context("Failed to insert role into the session")
     }
 
     pub fn get_user_id(&self) -> Result<Option<Uuid>, anyhow::Error> {
@@ -33,10 +63,55 @@ impl TypedSession {
             .context("Failed to get user id from the session")
     }
 
-    pub fn get_is_admin(&self) -> Result<Option<bool>, anyhow::Error> {
+    pub fn get_role(&self) -> Result<Option<Role>, anyhow::Error> {
+        self.0
+            .get(Self::ROLE_KEY)
+            .context("Failed to get role from the session")
+    }
+
+    pub fn insert_session_id(&self, session_id: Uuid) -> Result<(), anyhow::Error> {
+        self.0
+            .insert(Self::SESSION_ID_KEY, session_id)
+            .context("Failed to insert session id into the session")
+    }
+
+    pub fn get_session_id(&self) -> Result<Option<Uuid>, anyhow::Error> {
+        self.0
+            .get(Self::SESSION_ID_KEY)
+            .context("Failed to get session id from the session")
+    }
+
+    pub fn insert_sudo_until(&self, sudo_until: DateTime<Utc>) -> Result<(), anyhow::Error> {
+        self.0
+            .insert(Self::SUDO_UNTIL_KEY, sudo_until)
+            .context("Failed to insert sudo mode expiry into the session")
+    }
+
+    pub fn get_sudo_until(&self) -> Result<Option<DateTime<Utc>>, anyhow::Error> {
+        self.0
+            .get(Self::SUDO_UNTIL_KEY)
+            .context("Failed to get sudo mode expiry from the session")
+    }
+
+    pub fn remove_sudo_until(&self) {
+        self.0.remove(Self::SUDO_UNTIL_KEY);
+    }
+
+    // Set while an admin acts as the session's user, holds the admin's own id
+    pub fn insert_impersonator_id(&self, admin_id: Uuid) -> Result<(), anyhow::Error> {
         self.0
-            .get(Self::IS_ADMIN_KEY)
-            .context("Failed to get admin flag from the session")
+            .insert(Self::IMPERSONATOR_ID_KEY, admin_id)
+            .context("Failed to insert impersonator id into the session")
+    }
+
+    pub fn get_impersonator_id(&self) -> Result<Option<Uuid>, anyhow::Error> {
+        self.0
+            .get(Self::IMPERSONATOR_ID_KEY)
+            .context("Failed to get impersonator id from the session")
+    }
+
+    pub fn remove_impersonator_id(&self) {
+        self.0.remove(Self::IMPERSONATOR_ID_KEY);
     }
 
     pub fn log_out(self) {
@@ -0,0 +1,232 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use futures_util::{
+    FutureExt,
+    future::{BoxFuture, Shared},
+};
+use tokio::time::{self, Duration};
+
+type SharedResult<V> = Shared<BoxFuture<'static, Result<V, Arc<anyhow::Error>>>>;
+
+// Coalesces identical concurrent reads: the first caller for a key runs the query and every
+// caller arriving while it is in flight awaits that same result instead of hitting the db.
+pub struct SingleFlight<V: Clone> {
+    in_flight: Mutex<HashMap<String, SharedResult<V>>>,
+    // How long a duplicate waits on the in-flight query before running its own
+    wait_timeout: Duration,
+    coalesced: AtomicU64,
+    timed_out: AtomicU64,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SingleFlightStats {
+    pub coalesced: u64,
+    pub timed_out: u64,
+}
+
+impl<V> SingleFlight<V>
+where
+    V: Clone + Send + Sync + 'static,
+{
+    pub fn new(wait_timeout: Duration) -> Self {
+        Self {
+            in_flight: Mutex::new(HashMap::new()),
+            wait_timeout,
+            coalesced: AtomicU64::new(0),
+            timed_out: AtomicU64::new(0),
+        }
+    }
+
+    pub async fn run<F>(&self, key: String, query: F) -> Result<V, anyhow::Error>
+    where
+        F: Future<Output = Result<V, anyhow::Error>> + Send + 'static,
+    {
+        let mut query = Some(query);
+
+        let (shared, is_leader) = {
+            let mut in_flight = self.in_flight.lock().expect("single flight lock poisoned");
+            match in_flight.get(&key) {
+                Some(shared) => (shared.clone(), false),
+                None => {
+                    let query = query.take().expect("query is only taken once");
+                    let shared = query.map(|r| r.map_err(Arc::new)).boxed().shared();
+                    in_flight.insert(key.clone(), shared.clone());
+                    (shared, true)
+                }
+            }
+        };
+
+        if is_leader {
+            // Removes the entry even if this request is cancelled, so a finished result is never
+            // served to later callers. Followers already holding the shared future still get it.
+            let _guard = InFlightGuard {
+                flights: self,
+                key,
+                shared: shared.clone(),
+            };
+            return shared.await.map_err(shared_error);
+        }
+
+        let coalesced = self.coalesced.fetch_add(1, Ordering::Relaxed) + 1;
+        tracing::debug!(%key, coalesced, "Coalesced with in-flight query");
+
+        match time::timeout(self.wait_timeout, shared).await {
+            Ok(result) => result.map_err(shared_error),
+            Err(_) => {
+                self.timed_out.fetch_add(1, Ordering::Relaxed);
+                tracing::warn!(%key, "Timed out waiting on in-flight query, running it again");
+                query.take().expect("query is only taken once").await
+            }
+        }
+    }
+
+    pub fn stats(&self) -> SingleFlightStats {
+        SingleFlightStats {
+            coalesced: self.coalesced.load(Ordering::Relaxed),
+            timed_out: self.timed_out.load(Ordering::Relaxed),
+        }
+    }
+}
+
+struct InFlightGuard<'a, V: Clone> {
+    flights: &'a SingleFlight<V>,
+    key: String,
+    shared: SharedResult<V>,
+}
+
+impl<V: Clone> Drop for InFlightGuard<'_, V> {
+    fn drop(&mut self) {
+        if let Ok(mut in_flight) = self.flights.in_flight.lock()
+            && in_flight
+                .get(&self.key)
+                .is_some_and(|s| s.ptr_eq(&self.shared))
+        {
+            in_flight.remove(&self.key);
+        }
+    }
+}
+
+fn shared_error(e: Arc<anyhow::Error>) -> anyhow::Error {
+    anyhow::anyhow!("{e:#}")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    };
+
+    use tokio::time::{self, Duration};
+
+    use super::{SingleFlight, SingleFlightStats};
+
+    fn counted_query(
+        runs: &Arc<AtomicU64>,
+        delay: Duration,
+    ) -> impl Future<Output = Result<u64, anyhow::Error>> + Send + 'static {
+        let runs = runs.clone();
+        async move {
+            let n = runs.fetch_add(1, Ordering::SeqCst) + 1;
+            time::sleep(delay).await;
+            Ok(n)
+        }
+    }
+
+    #[tokio::test]
+    async fn concurrent_identical_queries_run_once() {
+        let flights = SingleFlight::new(Duration::from_secs(5));
+        let runs = Arc::new(AtomicU64::new(0));
+
+        let results = futures_util::future::join_all((0..5).map(|_| {
+            flights.run(
+                "feed".into(),
+                counted_query(&runs, Duration::from_millis(50)),
+            )
+        }))
+        .await;
+
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+        assert!(results.into_iter().all(|r| r.unwrap() == 1));
+        assert_eq!(
+            flights.stats(),
+            SingleFlightStats {
+                coalesced: 4,
+                timed_out: 0
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn different_keys_are_not_coalesced() {
+        let flights = SingleFlight::new(Duration::from_secs(5));
+        let runs = Arc::new(AtomicU64::new(0));
+
+        let (a, b) = tokio::join!(
+            flights.run("a".into(), counted_query(&runs, Duration::from_millis(20))),
+            flights.run("b".into(), counted_query(&runs, Duration::from_millis(20))),
+        );
+
+        a.unwrap();
+        b.unwrap();
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn finished_results_are_not_reused() {
+        let flights = SingleFlight::new(Duration::from_secs(5));
+        let runs = Arc::new(AtomicU64::new(0));
+
+        let first = flights
+            .run("feed".into(), counted_query(&runs, Duration::ZERO))
+            .await;
+        let second = flights
+            .run("feed".into(), counted_query(&runs, Duration::ZERO))
+            .await;
+
+        assert_eq!(first.unwrap(), 1);
+        assert_eq!(second.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn duplicates_run_their_own_query_after_timeout() {
+        let flights = SingleFlight::new(Duration::from_millis(10));
+        let runs = Arc::new(AtomicU64::new(0));
+
+        let (slow, fallback) = tokio::join!(
+            flights.run(
+                "feed".into(),
+                counted_query(&runs, Duration::from_millis(200))
+            ),
+            flights.run("feed".into(), counted_query(&runs, Duration::ZERO)),
+        );
+
+        assert_eq!(slow.unwrap(), 1);
+        assert_eq!(fallback.unwrap(), 2);
+        assert_eq!(flights.stats().timed_out, 1);
+    }
+
+    #[tokio::test]
+    async fn errors_are_shared_with_duplicates() {
+        let flights = SingleFlight::<u64>::new(Duration::from_secs(5));
+
+        let failing = || async {
+            time::sleep(Duration::from_millis(20)).await;
+            Err(anyhow::anyhow!("db is down"))
+        };
+        let (a, b) = tokio::join!(
+            flights.run("feed".into(), failing()),
+            flights.run("feed".into(), failing()),
+        );
+
+        assert!(a.unwrap_err().to_string().contains("db is down"));
+        assert!(b.unwrap_err().to_string().contains("db is down"));
+    }
+}
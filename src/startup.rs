@@ -1,34 +1,64 @@
-use std::net::TcpListener;
+use std::{
+    net::{IpAddr, TcpListener},
+    sync::Arc,
+};
 
-use actix_session::{SessionMiddleware, storage::RedisSessionStore};
+use actix_session::{
+    SessionMiddleware,
+    config::{BrowserSession, TtlExtensionPolicy},
+    storage::RedisSessionStore,
+};
 use actix_web::{
     App, HttpServer,
-    cookie::Key,
+    cookie::{Key, time},
     dev::Server,
-    web,
+    middleware, web,
     web::{Data, ServiceConfig},
 };
 use anyhow::Context;
 use secrecy::{ExposeSecret, Secret};
 use sqlx::{PgPool, postgres::PgPoolOptions};
+use tokio::time::Duration;
 use tracing_actix_web::TracingLogger;
 
 use crate::{
-    configuration::{Configuration, DatabaseConfigs},
+    client_ip::TrustedProxies,
+    configuration::{
+        AnonymousCommentSettings, Configuration, DatabaseConfigs, DatabaseMaintenanceSettings,
+        EmbedSettings, JwtSettings, LoginThrottleSettings, MessageSettings, RateLimitSettings,
+        RegistrationSettings, SessionSettings, UserSearchSettings,
+    },
+    domain::{AccountDeletionPolicy, PostLicense, PostSummaryResponse},
     email_client::EmailClient,
+    post_views::{self, PostViewCounter},
+    rate_limiter::RateLimiter,
     routes,
+    single_flight::SingleFlight,
+    suggester::{HeuristicSuggester, Suggester},
 };
 
+// Duplicate post listings wait this long on an identical in-flight query before running their own
+const POST_LISTING_WAIT_TIMEOUT: Duration = Duration::from_secs(5);
+
 pub struct Application {
     port: u16,
     server: Server,
+    db_pool: PgPool,
+    post_views: PostViewCounter,
 }
 
 impl Application {
     pub async fn build(config: Configuration) -> Result<Self, anyhow::Error> {
         let connection_pool = get_connection_pool(&config.database);
 
-        let email_client = config.email_client.client();
+        let postmark_webhook_credentials = PostmarkWebhookCredentials {
+            username: config.email_client.webhook_username.clone(),
+            password: config.email_client.webhook_password.clone(),
+        };
+        let email_client = config
+            .email_client
+            .client()
+            .with_suppression_list(connection_pool.clone());
 
         let address = format!("{}:{}", config.application.host, config.application.port);
         let listener = TcpListener::bind(address)
@@ -37,27 +67,63 @@ impl Application {
             .local_addr()
             .with_context(|| "Failed to read local address of TCP listener")?
             .port();
+        let post_views = PostViewCounter::default();
+        tokio::spawn(post_views::run_flush_loop(
+            post_views.clone(),
+            connection_pool.clone(),
+        ));
+
         let server = run(
             listener,
-            connection_pool,
+            connection_pool.clone(),
+            post_views.clone(),
             email_client,
+            postmark_webhook_credentials,
             config.application.base_url,
             config.application.hmac_secret,
             config.application.redis_uri,
+            config.application.impression_sample_rate,
+            config.application.default_post_license,
+            config.application.account_deletion_policy,
+            config.application.export_compression_level,
+            config.application.min_password_score,
+            config.application.password_history_size,
+            config.application.trusted_proxies,
+            config.embed,
+            config.anonymous_comments,
+            config.registration,
+            config.jwt,
+            config.messages,
+            config.user_search,
+            config.login_throttle,
+            config.rate_limits,
+            config.session,
+            config.database_maintenance,
         )
         .await
         .context("Failed to run Actix web server")?;
 
-        Ok(Self { port, server })
+        Ok(Self {
+            port,
+            server,
+            db_pool: connection_pool,
+            post_views,
+        })
     }
 
     pub fn port(&self) -> u16 {
         self.port
     }
 
+    pub fn post_views(&self) -> PostViewCounter {
+        self.post_views.clone()
+    }
+
     pub async fn run_until_stopped(self) -> Result<(), anyhow::Error> {
         // run returns a Server type, which implements Future trait
-        self.server.await.context("Server stopped with an error")
+        self.server.await.context("Server stopped with an error")?;
+        // Views counted since the last flush would otherwise be lost on every deploy
+        self.post_views.flush(&self.db_pool).await
     }
 }
 
@@ -67,17 +133,82 @@ pub fn get_connection_pool(config: &DatabaseConfigs) -> PgPool {
 
 pub struct ApplicationBaseUrl(pub String);
 
+pub struct PostmarkWebhookCredentials {
+    pub username: String,
+    pub password: Secret<String>,
+}
+
+pub struct ImpressionSampleRate(pub f64);
+
+pub struct DefaultPostLicense(pub PostLicense);
+
+pub struct ExportCompressionLevel(pub i32);
+
+pub struct MinPasswordScore(pub u8);
+
+pub struct PasswordHistorySize(pub u16);
+
+pub struct UserSearchRateLimiter(pub RateLimiter);
+
+pub type PostListingFlights = SingleFlight<Arc<(Vec<PostSummaryResponse>, i64)>>;
+
+#[allow(clippy::too_many_arguments)]
 async fn run(
     tcp_listener: TcpListener,
     db_pool: PgPool,
+    post_views: PostViewCounter,
     email_client: EmailClient,
+    postmark_webhook_credentials: PostmarkWebhookCredentials,
     base_url: String,
     hmac_secret: Secret<String>,
     redis_uri: Secret<String>,
+    impression_sample_rate: f64,
+    default_post_license: PostLicense,
+    account_deletion_policy: AccountDeletionPolicy,
+    export_compression_level: i32,
+    min_password_score: u8,
+    password_history_size: u16,
+    trusted_proxies: Vec<IpAddr>,
+    embed_settings: EmbedSettings,
+    anonymous_comment_settings: AnonymousCommentSettings,
+    registration_settings: RegistrationSettings,
+    jwt_settings: JwtSettings,
+    message_settings: MessageSettings,
+    user_search_settings: UserSearchSettings,
+    login_throttle_settings: LoginThrottleSettings,
+    rate_limit_settings: RateLimitSettings,
+    session_settings: SessionSettings,
+    database_maintenance_settings: DatabaseMaintenanceSettings,
 ) -> Result<Server, anyhow::Error> {
     let db_pool = Data::new(db_pool);
+    let post_views = Data::new(post_views);
     let email_client = Data::new(email_client);
+    let postmark_webhook_credentials = Data::new(postmark_webhook_credentials);
     let base_url = Data::new(ApplicationBaseUrl(base_url));
+    let impression_sample_rate = Data::new(ImpressionSampleRate(impression_sample_rate));
+    let default_post_license = Data::new(DefaultPostLicense(default_post_license));
+    let account_deletion_policy = Data::new(account_deletion_policy);
+    let export_compression_level = Data::new(ExportCompressionLevel(export_compression_level));
+    let min_password_score = Data::new(MinPasswordScore(min_password_score));
+    let password_history_size = Data::new(PasswordHistorySize(password_history_size));
+    let trusted_proxies = Data::new(TrustedProxies(trusted_proxies));
+    let post_listing_flights = Data::new(PostListingFlights::new(POST_LISTING_WAIT_TIMEOUT));
+    let embed_settings = Data::new(embed_settings);
+    let captcha_client = Data::new(anonymous_comment_settings.captcha_client());
+    let anonymous_comment_settings = Data::new(anonymous_comment_settings);
+    let registration_settings = Data::new(registration_settings);
+    let jwt_settings = Data::new(jwt_settings);
+    let message_settings = Data::new(message_settings);
+    let suggester: Data<dyn Suggester> =
+        Data::from(Arc::new(HeuristicSuggester) as Arc<dyn Suggester>);
+    let user_search_rate_limiter =
+        Data::new(UserSearchRateLimiter(user_search_settings.rate_limiter()));
+    let login_throttle_settings = Data::new(login_throttle_settings);
+    let route_rate_limiters = Data::new(rate_limit_settings.rate_limiters());
+    // The session store drops a session once it has been idle this long
+    let session_idle_ttl = time::Duration::minutes(session_settings.idle_timeout_minutes);
+    let session_settings = Data::new(session_settings);
+    let database_maintenance_settings = Data::new(database_maintenance_settings);
 
     let secret_key = Key::from(hmac_secret.expose_secret().as_bytes());
 
@@ -88,15 +219,45 @@ async fn run(
     let server = HttpServer::new(move || {
         App::new()
             .wrap(TracingLogger::default())
-            .wrap(SessionMiddleware::new(
-                redis_store.clone(),
-                secret_key.clone(),
-            ))
+            .wrap(
+                SessionMiddleware::builder(redis_store.clone(), secret_key.clone())
+                    .session_lifecycle(
+                        BrowserSession::default()
+                            .state_ttl(session_idle_ttl)
+                            .state_ttl_extension_policy(TtlExtensionPolicy::OnEveryRequest),
+                    )
+                    .build(),
+            )
+            .wrap(middleware::from_fn(routes::add_server_time_header))
+            // Registered before `/v1` so the embed scope is not shadowed by it
+            .service(routes::embed_routes(&embed_settings))
             .configure(configure_routes)
             // register the db connection as part of the application state
             .app_data(db_pool.clone())
+            .app_data(post_views.clone())
             .app_data(email_client.clone())
+            .app_data(postmark_webhook_credentials.clone())
             .app_data(base_url.clone())
+            .app_data(impression_sample_rate.clone())
+            .app_data(default_post_license.clone())
+            .app_data(account_deletion_policy.clone())
+            .app_data(export_compression_level.clone())
+            .app_data(min_password_score.clone())
+            .app_data(password_history_size.clone())
+            .app_data(trusted_proxies.clone())
+            .app_data(post_listing_flights.clone())
+            .app_data(embed_settings.clone())
+            .app_data(captcha_client.clone())
+            .app_data(anonymous_comment_settings.clone())
+            .app_data(registration_settings.clone())
+            .app_data(jwt_settings.clone())
+            .app_data(message_settings.clone())
+            .app_data(suggester.clone())
+            .app_data(user_search_rate_limiter.clone())
+            .app_data(login_throttle_settings.clone())
+            .app_data(route_rate_limiters.clone())
+            .app_data(session_settings.clone())
+            .app_data(database_maintenance_settings.clone())
     })
     .listen(tcp_listener)
     .with_context(|| "Failed to bind Actix server to TCP listener")?
@@ -110,11 +271,16 @@ pub struct HmacSecret(pub Secret<String>);
 
 pub fn configure_routes(cfg: &mut ServiceConfig) {
     cfg.route("/health_check", web::get().to(routes::health_check))
+        .service(web::scope("/t").configure(routes::tracking_routes))
         .service(
             web::scope("/v1")
                 .service(web::scope("/user").configure(routes::user_routes))
                 .service(web::scope("/admin").configure(routes::admin_routes))
                 .service(web::scope("/posts").configure(routes::post_routes))
-                .service(web::scope("/comment").configure(routes::comment_routes)),
+                .service(web::scope("/comment").configure(routes::comment_routes))
+                .service(web::scope("/messages").configure(routes::message_routes))
+                .service(web::scope("/meta").configure(routes::meta_routes))
+                .service(web::scope("/newsletters").configure(routes::newsletter_routes))
+                .service(web::scope("/webhooks").configure(routes::webhook_routes)),
         );
 }
@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+
+use futures_util::{FutureExt, future::BoxFuture};
+
+use crate::domain::{MetadataSuggestions, PostExcerpt, PostText, PostTitle};
+
+const MAX_TITLES: usize = 3;
+const MAX_TAGS: usize = 5;
+// A first sentence longer than this is cut, titles are meant to be scanned at a glance
+const MAX_TITLE_WORDS: usize = 12;
+// Words mentioned once are passing references rather than what the post is about
+const MIN_KEYWORD_COUNT: usize = 2;
+// Headings name the topic of what follows, so their words count for more than body words
+const HEADING_WEIGHT: usize = 3;
+
+// Too common to say anything about a post's topic, sorted for binary search
+const STOP_WORDS: [&str; 100] = [
+    "about", "above", "after", "again", "all", "also", "and", "any", "are", "because", "been",
+    "before", "being", "below", "between", "both", "but", "can", "could", "did", "does", "doing",
+    "down", "during", "each", "even", "few", "for", "from", "further", "get", "got", "had", "has",
+    "have", "having", "her", "here", "hers", "him", "his", "how", "into", "its", "just", "like",
+    "more", "most", "much", "must", "need", "not", "now", "off", "once", "one", "only", "other",
+    "our", "out", "over", "own", "same", "she", "should", "some", "such", "than", "that", "the",
+    "their", "them", "then", "there", "these", "they", "this", "those", "through", "too", "under",
+    "until", "use", "using", "very", "was", "way", "were", "what", "when", "where", "which",
+    "while", "who", "why", "will", "with", "would", "you", "your",
+];
+
+// Suggests a title and tags for a draft from its body. The route only depends on this trait so a
+// smarter implementation, e.g. one backed by a language model, can replace the heuristics.
+pub trait Suggester: Send + Sync {
+    fn suggest<'a>(
+        &'a self,
+        text: &'a PostText,
+    ) -> BoxFuture<'a, Result<MetadataSuggestions, anyhow::Error>>;
+}
+
+// Titles come from the headings and the opening sentence, tags from the words the body keeps
+// coming back to. Runs in-process and never fails.
+pub struct HeuristicSuggester;
+
+impl Suggester for HeuristicSuggester {
+    fn suggest<'a>(
+        &'a self,
+        text: &'a PostText,
+    ) -> BoxFuture<'a, Result<MetadataSuggestions, anyhow::Error>> {
+        let (headings, body) = split_headings(text.as_ref());
+        let suggestions = MetadataSuggestions {
+            titles: suggest_titles(&headings, &body),
+            tags: suggest_tags(&headings, &body),
+        };
+
+        futures_util::future::ready(Ok(suggestions)).boxed()
+    }
+}
+
+// Markdown headings apart from the rest of the body, with their `#` markers removed
+fn split_headings(text: &str) -> (Vec<String>, String) {
+    let mut headings = Vec::new();
+    let mut body = String::with_capacity(text.len());
+
+    for line in text.lines() {
+        match line.trim_start().strip_prefix('#') {
+            Some(heading) => {
+                headings.push(PostExcerpt::plain_text(heading.trim_start_matches('#')))
+            }
+            None => {
+                body.push_str(line);
+                body.push('\n');
+            }
+        }
+    }
+
+    headings.retain(|h| !h.is_empty());
+    (headings, body)
+}
+
+// The first heading, the opening sentence, then the remaining headings
+fn suggest_titles(headings: &[String], body: &str) -> Vec<String> {
+    let opening = first_sentence(&PostExcerpt::plain_text(body));
+    let candidates = headings
+        .first()
+        .cloned()
+        .into_iter()
+        .chain(opening)
+        .chain(headings.iter().skip(1).cloned());
+
+    let mut titles: Vec<String> = Vec::new();
+    for candidate in candidates {
+        let Ok(title) = PostTitle::parse(candidate) else {
+            continue;
+        };
+        let title = title.as_ref();
+        if !titles.iter().any(|t| t.eq_ignore_ascii_case(title)) {
+            titles.push(title.to_string());
+        }
+        if titles.len() == MAX_TITLES {
+            break;
+        }
+    }
+
+    titles
+}
+
+// Up to the first `.`, `!` or `?` that ends a word, without a trailing full stop
+fn first_sentence(plain: &str) -> Option<String> {
+    let mut chars = plain.char_indices().peekable();
+    let mut end = plain.len();
+    while let Some((i, c)) = chars.next() {
+        let ends_word = chars.peek().is_none_or(|(_, next)| next.is_whitespace());
+        if matches!(c, '.' | '!' | '?') && ends_word {
+            end = if c == '.' { i } else { i + c.len_utf8() };
+            break;
+        }
+    }
+
+    let words: Vec<&str> = plain[..end].split_whitespace().collect();
+    if words.is_empty() {
+        return None;
+    }
+    if words.len() > MAX_TITLE_WORDS {
+        return Some(format!("{}…", words[..MAX_TITLE_WORDS].join(" ")));
+    }
+    Some(words.join(" "))
+}
+
+// Most frequent meaningful words first, ties going to the word seen first
+fn suggest_tags(headings: &[String], body: &str) -> Vec<String> {
+    // word -> (weighted count, order of first appearance)
+    let mut counts: HashMap<String, (usize, usize)> = HashMap::new();
+    let sources = [
+        (HEADING_WEIGHT, headings.join(" ")),
+        (1, PostExcerpt::plain_text(body)),
+    ];
+
+    for (weight, source) in &sources {
+        for word in keywords(source) {
+            let seen = counts.len();
+            counts.entry(word).or_insert((0, seen)).0 += weight;
+        }
+    }
+
+    let mut ranked: Vec<(String, (usize, usize))> = counts
+        .into_iter()
+        .filter(|(_, (count, _))| *count >= MIN_KEYWORD_COUNT)
+        .collect();
+    ranked.sort_by(|(_, (a_count, a_seen)), (_, (b_count, b_seen))| {
+        b_count.cmp(a_count).then(a_seen.cmp(b_seen))
+    });
+
+    ranked
+        .into_iter()
+        .take(MAX_TAGS)
+        .map(|(word, _)| word)
+        .collect()
+}
+
+// Lowercased words of three or more characters that are neither numbers nor stop words.
+// `-` and `+` are kept inside words so `async-await` and `c++` stay whole.
+fn keywords(plain: &str) -> impl Iterator<Item = String> + '_ {
+    plain
+        .split(|c: char| !(c.is_alphanumeric() || c == '-' || c == '+'))
+        .map(|w| w.trim_matches('-').to_lowercase())
+        .filter(|w| w.chars().count() >= 3)
+        .filter(|w| !w.chars().all(|c| c.is_numeric()))
+        .filter(|w| STOP_WORDS.binary_search(&w.as_str()).is_err())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HeuristicSuggester, MAX_TITLE_WORDS, STOP_WORDS, Suggester};
+    use crate::domain::{MetadataSuggestions, PostText};
+
+    async fn suggest(text: &str) -> MetadataSuggestions {
+        let text = PostText::parse(text.to_string()).unwrap();
+        HeuristicSuggester.suggest(&text).await.unwrap()
+    }
+
+    #[test]
+    fn stop_words_are_sorted_and_lowercase() {
+        assert!(STOP_WORDS.is_sorted());
+        assert!(STOP_WORDS.iter().all(|w| *w == w.to_lowercase()));
+    }
+
+    #[tokio::test]
+    async fn first_heading_is_the_first_title() {
+        let suggestions =
+            suggest("# Taming the **borrow checker**\n\nLifetimes confuse everyone at first.")
+                .await;
+        assert_eq!(
+            suggestions.titles,
+            [
+                "Taming the borrow checker",
+                "Lifetimes confuse everyone at first"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn opening_sentence_is_used_without_headings() {
+        let suggestions = suggest("Why I moved to Postgres! It was slow before.").await;
+        assert_eq!(suggestions.titles, ["Why I moved to Postgres!"]);
+    }
+
+    #[tokio::test]
+    async fn long_opening_sentence_is_cut() {
+        let sentence = "word ".repeat(MAX_TITLE_WORDS + 5);
+        let suggestions = suggest(&sentence).await;
+
+        let title = &suggestions.titles[0];
+        assert!(title.ends_with('…'));
+        assert_eq!(
+            title.trim_end_matches('…').split_whitespace().count(),
+            MAX_TITLE_WORDS
+        );
+    }
+
+    #[tokio::test]
+    async fn duplicate_and_invalid_titles_are_skipped() {
+        let suggestions = suggest("# Intro\n\nintro\n\n# 2024\n\n## Closing thoughts").await;
+        assert_eq!(suggestions.titles, ["Intro", "Closing thoughts"]);
+    }
+
+    #[tokio::test]
+    async fn recurring_words_become_tags() {
+        let suggestions = suggest(
+            "Rust makes async code safe. With tokio, async Rust is fast, \
+             and tokio is the runtime most Rust services pick. The database is mentioned once.",
+        )
+        .await;
+        assert_eq!(suggestions.tags, ["rust", "async", "tokio"]);
+    }
+
+    #[tokio::test]
+    async fn heading_words_outrank_body_words() {
+        let suggestions =
+            suggest("# Postgres indexing\n\nQueries were slow. Queries got faster.").await;
+        assert_eq!(suggestions.tags, ["postgres", "indexing", "queries"]);
+    }
+
+    #[tokio::test]
+    async fn stop_words_and_numbers_never_become_tags() {
+        let suggestions = suggest("There were 2024 reasons, and there were 2024 more.").await;
+        assert!(suggestions.tags.is_empty());
+    }
+}
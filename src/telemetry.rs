@@ -1,10 +1,17 @@
-use std::io::{self, Write};
+use std::{
+    io::{self, Write},
+    sync::OnceLock,
+};
 
+use anyhow::Context;
 use tokio::{task, task::JoinHandle};
 use tracing::{Span, Subscriber, subscriber};
 use tracing_bunyan_formatter::{BunyanFormattingLayer, JsonStorageLayer};
 use tracing_log::LogTracer;
-use tracing_subscriber::{EnvFilter, Registry, fmt::MakeWriter, layer::SubscriberExt};
+use tracing_subscriber::{EnvFilter, Registry, fmt::MakeWriter, layer::SubscriberExt, reload};
+
+// Handle to the filter of the first subscriber built, which is the one installed globally
+static LOG_FILTER: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
 
 pub fn get_subscriber<Sink>(
     name: String,
@@ -16,6 +23,8 @@ where
 {
     let env_filter =
         EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(env_filter));
+    let (env_filter, handle) = reload::Layer::new(env_filter);
+    let _ = LOG_FILTER.set(handle);
 
     let formatting_layer = BunyanFormattingLayer::new(
         name,
@@ -37,6 +46,39 @@ pub fn init_subscriber(subscriber: impl Subscriber + Send + Sync) {
     subscriber::set_global_default(subscriber).expect("Failed to set subscriber");
 }
 
+// Replaces the active filter directives, e.g. `info,techhub::repository=debug,sqlx=warn`
+pub fn set_log_filter(directives: &str) -> Result<(), LogFilterError> {
+    let env_filter = EnvFilter::try_new(directives)
+        .map_err(|e| LogFilterError::InvalidDirectives(e.to_string()))?;
+
+    LOG_FILTER
+        .get()
+        .context("No reloadable log filter has been installed")?
+        .reload(env_filter)
+        .context("Failed to reload log filter")?;
+
+    tracing::info!(directives, "Log filter updated");
+    Ok(())
+}
+
+pub fn current_log_filter() -> Result<String, LogFilterError> {
+    let directives = LOG_FILTER
+        .get()
+        .context("No reloadable log filter has been installed")?
+        .with_current(|filter| filter.to_string())
+        .context("Failed to read log filter")?;
+
+    Ok(directives)
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum LogFilterError {
+    #[error("invalid log filter: {0}")]
+    InvalidDirectives(String),
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
 // NewlineWriter: A wrapper that adds \n after every write
 //
 // MakeNewlineWriter: A factory that produces these wrappers
@@ -6,17 +6,22 @@ use std::{
 
 use actix_web::{HttpResponse, error, http::StatusCode};
 use rand::{Rng, distributions::Alphanumeric};
+use sha2::{Digest, Sha256};
 
 #[derive(serde::Serialize)]
 pub struct ErrorResponse {
     pub code: u16,
     pub message: String,
+    // Machine readable reason, for errors clients are expected to handle specifically
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<&'static str>,
 }
 
 pub fn build_error_response(status_code: StatusCode, message: String) -> HttpResponse {
     let error_response = ErrorResponse {
         code: status_code.as_u16(),
         message,
+        error: None,
     };
     HttpResponse::build(status_code).json(error_response)
 }
@@ -45,6 +50,12 @@ pub fn generate_token() -> String {
         .collect()
 }
 
+// For tokens from `generate_token` handed to clients, only the hash is stored. They are long and
+// random, so a fast hash is as good as a slow one and lets a presented token be looked up directly.
+pub fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
 // Generic error helper that wraps any error into an appropriate Actix error while preserving root causes
 pub fn app_error<T>(status: StatusCode, e: T) -> actix_web::Error
 where
@@ -58,3 +69,15 @@ where
         _ => error::ErrorInternalServerError(e),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::hash_token;
+
+    #[test]
+    fn hashes_are_hex_sha256() {
+        let hash = hash_token("thpat_token");
+        assert_eq!(hash.len(), 64);
+        assert_ne!(hash, hash_token("thpat_other"));
+    }
+}
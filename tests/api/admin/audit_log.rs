@@ -0,0 +1,152 @@
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::helpers;
+
+async fn admin_id(app: &helpers::TestApp) -> Uuid {
+    sqlx::query_scalar!("SELECT id FROM users WHERE user_name = 'athfan'")
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap()
+}
+
+async fn audit_events(app: &helpers::TestApp, query: &str) -> Vec<Value> {
+    let response = app.get_audit_log(query).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    body["events"].as_array().unwrap().clone()
+}
+
+#[tokio::test]
+async fn logins_and_password_changes_are_recorded() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    app.enter_sudo_mode(&json!({ "password": &app.test_user.password }))
+        .await;
+    let response = app
+        .change_password(&json!({
+            "current_password": &app.test_user.password,
+            "new_password": "a-brand-new-password",
+        }))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    app.logout().await;
+
+    app.login_admin().await;
+    let events = audit_events(&app, &format!("?actor_id={}", app.test_user.user_id)).await;
+
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0]["action"], "password_change");
+    assert_eq!(events[1]["action"], "login");
+    assert_eq!(events[1]["target_id"], app.test_user.user_id.to_string());
+    assert_eq!(events[1]["metadata"]["ip_address"], "127.0.0.1");
+}
+
+#[tokio::test]
+async fn role_changes_record_the_admin_and_both_roles() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+    let response = app
+        .set_user_role(&app.test_user.user_id, &json!({ "role": "moderator" }))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let events = audit_events(&app, "?action=role_change").await;
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["actor_id"], admin_id(&app).await.to_string());
+    assert_eq!(events[0]["target_id"], app.test_user.user_id.to_string());
+    assert_eq!(
+        events[0]["metadata"],
+        json!({ "previous_role": "user", "role": "moderator" })
+    );
+}
+
+#[tokio::test]
+async fn hard_deleted_posts_are_recorded_with_their_title() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+    let post_id = app.create_sample_post().await;
+    let response = app.hard_delete_post(&post_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let events = audit_events(&app, &format!("?target_id={post_id}")).await;
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["action"], "post_hard_delete");
+    assert_eq!(events[0]["metadata"]["title"], "Post for comments");
+}
+
+#[tokio::test]
+async fn hard_deleting_a_missing_post_records_nothing() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+    let response = app.hard_delete_post(&Uuid::new_v4()).await;
+    assert_eq!(response.status().as_u16(), 404);
+
+    assert!(
+        audit_events(&app, "?action=post_hard_delete")
+            .await
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn published_newsletters_are_recorded() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+    let newsletter_body = json!({
+        "title": "Audited newsletter",
+        "content": {
+            "text": "Plain text",
+            "html": "<p>HTML</p>"
+        }
+    });
+    let key = Uuid::new_v4().to_string();
+    let response = app.publish_newsletters(&newsletter_body, Some(&key)).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body: Value = response.json().await.unwrap();
+
+    // Replaying the request publishes nothing new
+    app.publish_newsletters(&newsletter_body, Some(&key)).await;
+    let events = audit_events(&app, "?action=newsletter_publish").await;
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["target_id"], body["issue_id"]);
+    assert_eq!(events[0]["metadata"]["title"], "Audited newsletter");
+}
+
+#[tokio::test]
+async fn audit_log_is_append_only() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let result = sqlx::query!("DELETE FROM audit_log")
+        .execute(&app.db_pool)
+        .await;
+
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn audit_log_rejects_unknown_actions() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    let response = app.get_audit_log("?action=everything").await;
+
+    assert_eq!(response.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn audit_log_is_admin_only() {
+    let app = helpers::spawn_app().await;
+
+    let response = app.get_audit_log("").await;
+    assert_eq!(response.status().as_u16(), 401);
+
+    app.login().await;
+    let response = app.get_audit_log("").await;
+    assert_eq!(response.status().as_u16(), 403);
+}
@@ -0,0 +1,188 @@
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::helpers;
+
+fn ndjson_line(post_id: &Uuid, email: &str, text: &str, created_at: &str) -> String {
+    serde_json::json!({
+        "post_id": post_id.to_string(),
+        "author_email": email,
+        "author_name": email.split('@').next().unwrap(),
+        "text": text,
+        "created_at": created_at
+    })
+    .to_string()
+}
+
+#[tokio::test]
+async fn import_creates_comments_with_original_timestamps_and_shell_users() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+    let post_id = app.create_sample_post().await;
+
+    let ndjson = [
+        ndjson_line(
+            &post_id,
+            "jane@example.com",
+            "First!",
+            "2019-03-01T10:00:00Z",
+        ),
+        ndjson_line(
+            &post_id,
+            "jane@example.com",
+            "Me again",
+            "2019-03-02T10:00:00Z",
+        ),
+        ndjson_line(
+            &post_id,
+            &app.test_user.email,
+            "Existing user",
+            "2019-03-03T10:00:00Z",
+        ),
+    ]
+    .join("\n");
+
+    let response = app.import_comments(ndjson, "").await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["dry_run"], false);
+    assert_eq!(body["imported"], 3);
+    assert_eq!(body["users_created"], 1);
+    assert!(body["errors"].as_array().unwrap().is_empty());
+
+    let body: Value = app
+        .get_comments_with_query(&post_id, "?sort=oldest")
+        .await
+        .json()
+        .await
+        .unwrap();
+    let comments = body["comments"].as_array().unwrap();
+    assert_eq!(comments.len(), 3);
+    assert_eq!(comments[0]["text"], "First!");
+    assert_eq!(comments[0]["created_at"], "2019-03-01T10:00:00Z");
+    assert_eq!(comments[2]["created_by"], app.test_user.user_id.to_string());
+
+    let shell_user =
+        sqlx::query!("SELECT is_activated FROM users WHERE email = 'jane@example.com'")
+            .fetch_one(&app.db_pool)
+            .await
+            .unwrap();
+    assert!(!shell_user.is_activated);
+}
+
+#[tokio::test]
+async fn import_suffixes_shell_user_names_already_taken() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+    let post_id = app.create_sample_post().await;
+
+    // Named `athfan` after the email, like the seeded admin
+    let ndjson = ndjson_line(
+        &post_id,
+        "athfan@example.com",
+        "Same name",
+        "2019-03-01T10:00:00Z",
+    );
+
+    let response = app.import_comments(ndjson, "").await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let shell_user =
+        sqlx::query!("SELECT id, user_name FROM users WHERE email = 'athfan@example.com'")
+            .fetch_one(&app.db_pool)
+            .await
+            .unwrap();
+    assert_eq!(
+        shell_user.user_name,
+        format!("athfan-{}", &shell_user.id.to_string()[..8])
+    );
+}
+
+#[tokio::test]
+async fn import_reports_invalid_lines_and_imports_the_rest() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+    let post_id = app.create_sample_post().await;
+
+    let ndjson = [
+        ndjson_line(&post_id, "jane@example.com", "Fine", "2019-03-01T10:00:00Z"),
+        "{not json".to_string(),
+        ndjson_line(
+            &post_id,
+            "not-an-email",
+            "Bad author",
+            "2019-03-01T10:00:00Z",
+        ),
+        String::new(),
+        ndjson_line(
+            &Uuid::new_v4(),
+            "jane@example.com",
+            "No post",
+            "2019-03-01T10:00:00Z",
+        ),
+    ]
+    .join("\n");
+
+    let body: Value = app.import_comments(ndjson, "").await.json().await.unwrap();
+    assert_eq!(body["imported"], 1);
+
+    let lines: Vec<u64> = body["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["line"].as_u64().unwrap())
+        .collect();
+    assert_eq!(lines, vec![2, 3, 5]);
+}
+
+#[tokio::test]
+async fn dry_run_validates_without_writing() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+    let post_id = app.create_sample_post().await;
+
+    let ndjson = ndjson_line(
+        &post_id,
+        "jane@example.com",
+        "Hello",
+        "2019-03-01T10:00:00Z",
+    );
+
+    let body: Value = app
+        .import_comments(ndjson, "?dry_run=true")
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["dry_run"], true);
+    assert_eq!(body["imported"], 1);
+    assert_eq!(body["users_created"], 1);
+
+    let body: Value = app.get_comments(&post_id).await.json().await.unwrap();
+    assert!(body["comments"].as_array().unwrap().is_empty());
+
+    let users = sqlx::query!("SELECT id FROM users WHERE email = 'jane@example.com'")
+        .fetch_optional(&app.db_pool)
+        .await
+        .unwrap();
+    assert!(users.is_none());
+}
+
+#[tokio::test]
+async fn empty_import_returns_400() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    let response = app.import_comments("\n\n".to_string(), "").await;
+    assert_eq!(response.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn import_returns_403_for_non_admins() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let response = app.import_comments("{}".to_string(), "").await;
+    assert_eq!(response.status().as_u16(), 403);
+}
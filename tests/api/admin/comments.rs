@@ -0,0 +1,174 @@
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::helpers;
+
+async fn comment_on(app: &helpers::TestApp, post_id: &Uuid, text: &str) -> Uuid {
+    let payload = serde_json::json!({
+        "text": text,
+        "post_id": post_id.to_string()
+    });
+    let body: Value = app.create_comment(&payload).await.json().await.unwrap();
+    Uuid::parse_str(body["id"].as_str().unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn moderation_queue_lists_comments_across_posts() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let first_post = app.create_sample_post().await;
+    let second_post = app.create_sample_post().await;
+    comment_on(&app, &first_post, "On the first post").await;
+    comment_on(&app, &second_post, "On the second post").await;
+
+    app.logout().await;
+    app.login_admin().await;
+
+    let response = app.get_moderation_comments("").await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    let comments = body["comments"].as_array().unwrap();
+    assert_eq!(comments.len(), 2);
+    assert_eq!(comments[0]["text"], "On the second post");
+    assert_eq!(comments[0]["user_name"], app.test_user.user_name);
+    assert_eq!(body["metadata"]["total_records"], 2);
+}
+
+#[tokio::test]
+async fn moderation_queue_filters_by_user_post_and_date() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let first_post = app.create_sample_post().await;
+    let second_post = app.create_sample_post().await;
+    comment_on(&app, &first_post, "User on first post").await;
+    comment_on(&app, &second_post, "User on second post").await;
+
+    app.logout().await;
+    app.login_admin().await;
+    comment_on(&app, &first_post, "Admin on first post").await;
+
+    let query = format!("?user_id={}", app.test_user.user_id);
+    let body: Value = app
+        .get_moderation_comments(&query)
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["comments"].as_array().unwrap().len(), 2);
+
+    let query = format!("?post_id={first_post}");
+    let body: Value = app
+        .get_moderation_comments(&query)
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["comments"].as_array().unwrap().len(), 2);
+
+    let query = format!("?user_id={}&post_id={first_post}", app.test_user.user_id);
+    let body: Value = app
+        .get_moderation_comments(&query)
+        .await
+        .json()
+        .await
+        .unwrap();
+    let comments = body["comments"].as_array().unwrap();
+    assert_eq!(comments.len(), 1);
+    assert_eq!(comments[0]["text"], "User on first post");
+
+    let body: Value = app
+        .get_moderation_comments("?since=2999-01-01T00:00:00Z")
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert!(body["comments"].as_array().unwrap().is_empty());
+
+    let body: Value = app
+        .get_moderation_comments("?until=2999-01-01T00:00:00Z")
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["comments"].as_array().unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn moderation_queue_returns_400_for_invalid_filters() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    for query in [
+        "?since=2025-02-01T00:00:00Z&until=2025-01-01T00:00:00Z",
+        "?user_id=not-a-uuid",
+        "?limit=0",
+    ] {
+        let response = app.get_moderation_comments(query).await;
+        assert_eq!(
+            response.status().as_u16(),
+            400,
+            "Expected 400 for query {query}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn bulk_remove_hides_comments_from_posts_and_queue() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let post_id = app.create_sample_post().await;
+    let spam = comment_on(&app, &post_id, "Spam").await;
+    let more_spam = comment_on(&app, &post_id, "More spam").await;
+    comment_on(&app, &post_id, "Legit").await;
+
+    app.logout().await;
+    app.login_admin().await;
+
+    let payload = serde_json::json!({ "comment_ids": [spam, more_spam] });
+    let response = app.remove_comments(&payload).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["removed"], 2);
+
+    let body: Value = app.get_comments(&post_id).await.json().await.unwrap();
+    let comments = body["comments"].as_array().unwrap();
+    assert_eq!(comments.len(), 1);
+    assert_eq!(comments[0]["text"], "Legit");
+
+    let body: Value = app.get_moderation_comments("").await.json().await.unwrap();
+    assert_eq!(body["metadata"]["total_records"], 1);
+
+    // Already removed comments are not counted again
+    let response = app.remove_comments(&payload).await;
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["removed"], 0);
+}
+
+#[tokio::test]
+async fn bulk_remove_returns_400_for_empty_list() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    let response = app
+        .remove_comments(&serde_json::json!({ "comment_ids": [] }))
+        .await;
+    assert_eq!(response.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn moderation_endpoints_return_403_for_non_admins() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let response = app.get_moderation_comments("").await;
+    assert_eq!(response.status().as_u16(), 403);
+
+    let response = app
+        .remove_comments(&serde_json::json!({ "comment_ids": [Uuid::new_v4()] }))
+        .await;
+    assert_eq!(response.status().as_u16(), 403);
+}
@@ -0,0 +1,103 @@
+use serde_json::Value;
+use techhub::{configuration::DatabaseMaintenanceSettings, newsletter_delivery_worker};
+
+use crate::helpers;
+
+#[tokio::test]
+async fn index_stats_report_indexes_and_tables() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    let response = app.get_index_stats().await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    let indexes = body["indexes"].as_array().unwrap();
+    let comments_index = indexes
+        .iter()
+        .find(|i| i["index"] == "comments_post_id_idx")
+        .expect("comments_post_id_idx should be reported");
+    assert_eq!(comments_index["table"], "comments");
+    assert!(comments_index["scans"].is_i64());
+    assert!(comments_index["size_bytes"].as_i64().unwrap() > 0);
+
+    // Primary keys enforce uniqueness and are never flagged, however rarely they are scanned
+    let unused = body["unused_indexes"].as_array().unwrap();
+    assert!(!unused.iter().any(|i| i == "users_pkey"));
+
+    let tables = body["tables"].as_array().unwrap();
+    let posts = tables.iter().find(|t| t["table"] == "posts").unwrap();
+    assert!(posts["seq_scans"].is_i64());
+    assert!(posts["index_scans"].is_i64());
+    assert_eq!(posts["is_seq_scan_heavy"], false);
+}
+
+#[tokio::test]
+async fn index_stats_return_403_for_non_admins() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let response = app.get_index_stats().await;
+    assert_eq!(response.status().as_u16(), 403);
+}
+
+#[tokio::test]
+async fn bloat_stats_report_dead_rows_per_table() {
+    let app = helpers::spawn_app_with(|c| c.database_maintenance.min_dead_rows = 500).await;
+    app.login_admin().await;
+
+    let response = app.get_bloat_stats().await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["min_dead_rows"], 500);
+    let tables = body["tables"].as_array().unwrap();
+    let queue = tables
+        .iter()
+        .find(|t| t["table"] == "issue_delivery_queue")
+        .expect("issue_delivery_queue should be reported");
+    assert!(queue["live_rows"].is_i64());
+    assert!(queue["dead_rows"].is_i64());
+    assert!(queue["dead_row_ratio"].is_f64());
+    assert!(queue["estimated_bloat_bytes"].is_i64());
+    // A fresh database has nothing to vacuum
+    assert_eq!(queue["is_bloated"], false);
+    assert!(body["bloated_tables"].as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn bloat_stats_return_403_for_non_admins() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let response = app.get_bloat_stats().await;
+    assert_eq!(response.status().as_u16(), 403);
+}
+
+#[tokio::test]
+async fn bloated_tables_are_vacuumed_when_configured() {
+    let app = helpers::spawn_app().await;
+    // Zero thresholds flag every watched table, so the vacuum itself is exercised
+    let settings = DatabaseMaintenanceSettings {
+        max_dead_row_ratio: 0.0,
+        min_dead_rows: 0,
+        vacuum_bloated_tables: true,
+    };
+
+    let bloated = app.check_table_bloat(&settings).await;
+
+    let tables: Vec<&str> = bloated.iter().map(|t| t.table.as_str()).collect();
+    assert_eq!(tables, newsletter_delivery_worker::BLOAT_WATCHED_TABLES);
+}
+
+#[tokio::test]
+async fn tables_below_the_thresholds_are_not_reported() {
+    let app = helpers::spawn_app().await;
+    let settings = DatabaseMaintenanceSettings {
+        max_dead_row_ratio: 0.2,
+        min_dead_rows: 10_000,
+        vacuum_bloated_tables: true,
+    };
+
+    assert!(app.check_table_bloat(&settings).await.is_empty());
+}
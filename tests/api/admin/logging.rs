@@ -0,0 +1,53 @@
+use serde_json::Value;
+
+use crate::helpers;
+
+// The log filter is process wide and shared by every test, so these tests only ever
+// set it to the same level the test subscriber starts with
+const TEST_FILTER: &str = "info,techhub::repository=info";
+
+#[tokio::test]
+async fn set_log_filter_updates_active_directives() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    let response = app
+        .set_log_filter(&serde_json::json!({ "filter": TEST_FILTER }))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = app.get_log_filter().await.json().await.unwrap();
+    let filter = body["filter"].as_str().unwrap();
+    assert!(filter.contains("techhub::repository=info"), "got {filter}");
+}
+
+#[tokio::test]
+async fn set_log_filter_returns_400_for_invalid_directives() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    for filter in ["techhub=notalevel", "", "   "] {
+        let response = app
+            .set_log_filter(&serde_json::json!({ "filter": filter }))
+            .await;
+        assert_eq!(
+            response.status().as_u16(),
+            400,
+            "Expected 400 for filter {filter:?}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn logging_endpoints_return_403_for_non_admins() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let response = app
+        .set_log_filter(&serde_json::json!({ "filter": TEST_FILTER }))
+        .await;
+    assert_eq!(response.status().as_u16(), 403);
+
+    let response = app.get_log_filter().await;
+    assert_eq!(response.status().as_u16(), 403);
+}
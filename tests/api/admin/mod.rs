@@ -1,2 +1,12 @@
+mod audit_log;
+mod comment_import;
+mod comments;
+mod database;
+mod logging;
 mod news_letter;
 mod posts;
+mod submissions;
+mod subscriber_import;
+mod suppressions;
+mod users;
+mod workers;
@@ -0,0 +1,130 @@
+use serde_json::{Value, json};
+use uuid::Uuid;
+use wiremock::{Mock, ResponseTemplate, matchers};
+
+use crate::helpers;
+
+// "%PDF-1.7", base64 encoded
+const PDF_CONTENT: &str = "JVBERi0xLjc=";
+
+fn newsletter_with(attachments: Value) -> Value {
+    json!({
+        "title": "Monthly Digest",
+        "content": {
+            "text": "The digest is attached.",
+            "html": "<p>The digest is attached.</p>"
+        },
+        "attachments": attachments
+    })
+}
+
+#[tokio::test]
+async fn attachments_are_sent_with_every_email_of_the_issue() {
+    let app = helpers::spawn_app().await;
+    app.create_active_subscriber().await;
+    app.login_admin().await;
+
+    Mock::given(matchers::path("/email"))
+        .and(matchers::body_partial_json(json!({
+            "Attachments": [{
+                "Name": "digest.pdf",
+                "Content": PDF_CONTENT,
+                "ContentType": "application/pdf"
+            }]
+        })))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1..)
+        .mount(&app.email_server)
+        .await;
+
+    let payload = newsletter_with(json!([{
+        "name": "digest.pdf",
+        "content_type": "application/pdf",
+        "content": PDF_CONTENT
+    }]));
+    let key = Uuid::new_v4().to_string();
+    let response = app.publish_newsletters(&payload, Some(&key)).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body: Value = response.json().await.unwrap();
+    let issue_id = Uuid::parse_str(body["issue_id"].as_str().unwrap()).unwrap();
+
+    app.dispatch_all_pending_newsletter_emails().await;
+
+    let body: Value = app
+        .get_newsletter_status(&issue_id)
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["sent"], body["enqueued"]);
+    assert_eq!(body["failed"], 0);
+}
+
+#[tokio::test]
+async fn draft_attachments_are_included_in_test_sends() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    let response = app
+        .create_newsletter_draft(&newsletter_with(json!([{
+            "name": "notes.txt",
+            "content_type": "text/plain",
+            "content": "bm90ZXM="
+        }])))
+        .await;
+    assert_eq!(response.status().as_u16(), 201);
+    let body: Value = response.json().await.unwrap();
+    let issue_id = Uuid::parse_str(body["issue_id"].as_str().unwrap()).unwrap();
+
+    Mock::given(matchers::path("/email"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&app.email_server)
+        .await;
+    let response = app.test_send_newsletter(&issue_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let email_requests = app.email_server.received_requests().await.unwrap();
+    let email: Value = serde_json::from_slice(&email_requests[0].body).unwrap();
+    assert_eq!(email["Attachments"][0]["Name"], "notes.txt");
+    assert_eq!(email["Attachments"][0]["Content"], "bm90ZXM=");
+}
+
+#[tokio::test]
+async fn invalid_attachments_are_rejected() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    let test_cases = vec![
+        (
+            json!([{ "name": "setup.exe", "content_type": "application/octet-stream", "content": "TVo=" }]),
+            "unsupported type",
+        ),
+        (
+            json!([{ "name": "digest.pdf", "content_type": "application/pdf", "content": "TVo=" }]),
+            "content not matching its type",
+        ),
+        (
+            json!([{ "name": "digest.pdf", "content_type": "application/pdf", "content": "not base64!" }]),
+            "content not base64 encoded",
+        ),
+        (
+            json!([{ "name": "", "content_type": "application/pdf", "content": PDF_CONTENT }]),
+            "empty name",
+        ),
+    ];
+
+    for (attachments, description) in test_cases {
+        let key = Uuid::new_v4().to_string();
+        let response = app
+            .publish_newsletters(&newsletter_with(attachments), Some(&key))
+            .await;
+
+        assert_eq!(
+            response.status().as_u16(),
+            400,
+            "The API did not fail with 400 Bad Request when the attachment had {}.",
+            description
+        );
+    }
+}
@@ -0,0 +1,137 @@
+use serde_json::{Value, json};
+use techhub::domain::ANONYMOUS_USER_ID;
+use uuid::Uuid;
+use wiremock::{Mock, Request, Respond, ResponseTemplate, matchers};
+
+use crate::helpers;
+
+// Accepts every message of a batch except the ones sent to `rejected`
+struct BatchResponder {
+    rejected: Option<String>,
+}
+
+impl Respond for BatchResponder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let messages: Vec<Value> = serde_json::from_slice(&request.body).unwrap();
+        let results: Vec<Value> = messages
+            .iter()
+            .map(|message| {
+                if self.rejected.as_deref() == message["To"].as_str() {
+                    json!({ "ErrorCode": 406, "Message": "Inactive recipient" })
+                } else {
+                    json!({ "ErrorCode": 0, "Message": "OK" })
+                }
+            })
+            .collect();
+        ResponseTemplate::new(200).set_body_json(results)
+    }
+}
+
+async fn spawn_batching_app() -> helpers::TestApp {
+    let app = helpers::spawn_app_with(|c| {
+        c.newsletter_delivery.batch_size = 100;
+        // Dead-lettered on their first failure
+        c.newsletter_delivery.max_retries = 0;
+    })
+    .await;
+    sqlx::query!(
+        "UPDATE users SET is_activated = true, is_subscribed = true WHERE id <> $1",
+        ANONYMOUS_USER_ID
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+    app.login_admin().await;
+    app
+}
+
+async fn publish(app: &helpers::TestApp) -> Uuid {
+    let newsletter_body = json!({
+        "title": "Batched Newsletter",
+        "content": {
+            "text": "Hello subscribers!",
+            "html": "<p>Hello subscribers!</p>"
+        }
+    });
+
+    let key = Uuid::new_v4().to_string();
+    let response = app.publish_newsletters(&newsletter_body, Some(&key)).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    Uuid::parse_str(body["issue_id"].as_str().unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn batch_mode_delivers_every_recipient_in_a_single_request() {
+    let app = spawn_batching_app().await;
+
+    Mock::given(matchers::path("/email/batch"))
+        .and(matchers::method("POST"))
+        .respond_with(BatchResponder { rejected: None })
+        .expect(1)
+        .mount(&app.email_server)
+        .await;
+    Mock::given(matchers::path("/email"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&app.email_server)
+        .await;
+
+    let issue_id = publish(&app).await;
+    app.dispatch_all_pending_newsletter_emails().await;
+
+    let requests = app.email_server.received_requests().await.unwrap();
+    let messages: Vec<Value> = serde_json::from_slice(&requests[0].body).unwrap();
+    assert!(messages.len() > 1);
+
+    let response = app.get_newsletter_status(&issue_id).await;
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["sent"], body["enqueued"]);
+    assert_eq!(body["sent"], messages.len());
+    assert_eq!(body["is_complete"], true);
+}
+
+#[tokio::test]
+async fn messages_rejected_in_a_batch_fail_on_their_own() {
+    let app = spawn_batching_app().await;
+
+    Mock::given(matchers::path("/email/batch"))
+        .respond_with(BatchResponder {
+            rejected: Some(app.test_user.email.clone()),
+        })
+        .mount(&app.email_server)
+        .await;
+
+    let issue_id = publish(&app).await;
+    app.dispatch_all_pending_newsletter_emails().await;
+
+    let response = app.get_newsletter_status(&issue_id).await;
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["failed"], 1);
+    assert_eq!(body["sent"], body["enqueued"].as_i64().unwrap() - 1);
+    assert_eq!(body["failures"][0]["user_email"], app.test_user.email);
+    assert_eq!(
+        body["failures"][0]["error"],
+        "Postmark error 406: Inactive recipient"
+    );
+}
+
+#[tokio::test]
+async fn a_failed_batch_request_retries_every_message_of_the_batch() {
+    let app = spawn_batching_app().await;
+
+    Mock::given(matchers::path("/email/batch"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(1)
+        .mount(&app.email_server)
+        .await;
+
+    let issue_id = publish(&app).await;
+    app.dispatch_all_pending_newsletter_emails().await;
+
+    let response = app.get_newsletter_status(&issue_id).await;
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["sent"], 0);
+    assert_eq!(body["failed"], body["enqueued"]);
+}
@@ -0,0 +1,180 @@
+use serde_json::{Value, json};
+use techhub::{domain::ANONYMOUS_USER_ID, newsletter_delivery_worker};
+use uuid::Uuid;
+use wiremock::{Mock, ResponseTemplate, matchers};
+
+use crate::helpers;
+
+async fn publish(app: &helpers::TestApp) -> Uuid {
+    let newsletter_body = json!({
+        "title": "Mistaken Newsletter",
+        "content": {
+            "text": "Hello subscribers!",
+            "html": "<p>Hello subscribers!</p>"
+        }
+    });
+
+    let key = Uuid::new_v4().to_string();
+    let response = app.publish_newsletters(&newsletter_body, Some(&key)).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    Uuid::parse_str(body["issue_id"].as_str().unwrap()).unwrap()
+}
+
+// The placeholder author of anonymous comments is not a real user, so it stays unsubscribed
+async fn subscribe_all_users(app: &helpers::TestApp) {
+    sqlx::query!(
+        "UPDATE users SET is_activated = true, is_subscribed = true WHERE id <> $1",
+        ANONYMOUS_USER_ID
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+}
+
+async fn status(app: &helpers::TestApp, issue_id: &Uuid) -> Value {
+    let response = app.get_newsletter_status(issue_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+    response.json().await.unwrap()
+}
+
+async fn cancel(app: &helpers::TestApp, issue_id: &Uuid) -> Value {
+    let response = app.cancel_newsletter_issue(issue_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+    response.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn cancelling_removes_queued_deliveries_but_keeps_those_already_sent() {
+    let app = helpers::spawn_app().await;
+    // The test user and the seeded admin are both subscribed
+    subscribe_all_users(&app).await;
+    app.login_admin().await;
+
+    Mock::given(matchers::path("/email"))
+        .and(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&app.email_server)
+        .await;
+
+    let issue_id = publish(&app).await;
+    app.fan_out_pending_newsletters().await;
+    newsletter_delivery_worker::try_execute_task(
+        &app.db_pool,
+        &app.email_client,
+        &app.address,
+        &app.newsletter_delivery,
+    )
+    .await
+    .unwrap();
+
+    let body = cancel(&app, &issue_id).await;
+    assert_eq!(body["removed_deliveries"], 1);
+    app.dispatch_all_pending_newsletter_emails().await;
+
+    let body = status(&app, &issue_id).await;
+    assert_eq!(body["fan_out_status"], "cancelled");
+    assert_eq!(body["sent"], 1);
+    assert_eq!(body["pending_deliveries"], 0);
+}
+
+#[tokio::test]
+async fn cancelling_before_fan_out_stops_it() {
+    let app = helpers::spawn_app().await;
+    app.create_active_subscriber().await;
+    app.login_admin().await;
+    let issue_id = publish(&app).await;
+
+    let body = cancel(&app, &issue_id).await;
+    assert_eq!(body["removed_deliveries"], 0);
+    app.fan_out_pending_newsletters().await;
+
+    let body = status(&app, &issue_id).await;
+    assert_eq!(body["enqueued"], 0);
+}
+
+#[tokio::test]
+async fn deliveries_in_flight_when_cancelled_are_not_retried() {
+    let app = helpers::spawn_app().await;
+    app.create_active_subscriber().await;
+    app.login_admin().await;
+
+    Mock::given(matchers::path("/email"))
+        .and(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&app.email_server)
+        .await;
+
+    let issue_id = publish(&app).await;
+    app.fan_out_pending_newsletters().await;
+
+    // Held by the worker while it sends, the delivery is left in the queue
+    let mut in_flight = app.db_pool.begin().await.unwrap();
+    sqlx::query!(
+        "SELECT user_email FROM issue_delivery_queue WHERE newsletter_issue_id = $1 FOR UPDATE",
+        issue_id
+    )
+    .fetch_all(&mut *in_flight)
+    .await
+    .unwrap();
+    let body = cancel(&app, &issue_id).await;
+    assert_eq!(body["removed_deliveries"], 0);
+    in_flight.rollback().await.unwrap();
+
+    app.dispatch_all_pending_newsletter_emails().await;
+
+    let body = status(&app, &issue_id).await;
+    assert_eq!(body["skipped"], 1);
+    assert_eq!(body["pending_deliveries"], 0);
+}
+
+#[tokio::test]
+async fn cancellations_are_audited() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+    let issue_id = publish(&app).await;
+
+    cancel(&app, &issue_id).await;
+
+    let body: Value = app
+        .get_audit_log(&format!("?action=newsletter_cancel&target_id={issue_id}"))
+        .await
+        .json()
+        .await
+        .unwrap();
+    let events = body["events"].as_array().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["metadata"]["title"], "Mistaken Newsletter");
+    assert_eq!(events[0]["metadata"]["removed_deliveries"], 0);
+}
+
+#[tokio::test]
+async fn drafts_cannot_be_cancelled() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+    let response = app
+        .create_newsletter_draft(&json!({
+            "title": "Draft issue",
+            "content": { "text": "Hello!", "html": "<p>Hello!</p>" }
+        }))
+        .await;
+    let body: Value = response.json().await.unwrap();
+    let issue_id = Uuid::parse_str(body["issue_id"].as_str().unwrap()).unwrap();
+
+    let response = app.cancel_newsletter_issue(&issue_id).await;
+
+    assert_eq!(response.status().as_u16(), 409);
+}
+
+#[tokio::test]
+async fn cancelling_an_unknown_issue_returns_404() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    let response = app.cancel_newsletter_issue(&Uuid::new_v4()).await;
+
+    assert_eq!(response.status().as_u16(), 404);
+}
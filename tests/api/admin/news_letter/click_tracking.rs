@@ -0,0 +1,166 @@
+use reqwest::{Client, redirect::Policy};
+use serde_json::{Value, json};
+use uuid::Uuid;
+use wiremock::{Mock, ResponseTemplate, matchers};
+
+use crate::helpers;
+
+async fn publish_and_deliver(app: &helpers::TestApp) -> Uuid {
+    Mock::given(matchers::path("/email"))
+        .and(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&app.email_server)
+        .await;
+
+    let newsletter_body = json!({
+        "title": "Newsletter With Links",
+        "content": {
+            "text": "Read the post and the docs.",
+            "html": "<p>Read <a href=\"https://example.com/post?a=1&amp;b=2\">the post</a> and <a href=\"https://example.com/docs\">the docs</a>.</p>"
+        }
+    });
+    let key = Uuid::new_v4().to_string();
+    let response = app.publish_newsletters(&newsletter_body, Some(&key)).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body: Value = response.json().await.unwrap();
+
+    app.fan_out_pending_newsletters().await;
+    app.dispatch_all_pending_newsletter_emails().await;
+
+    Uuid::parse_str(body["issue_id"].as_str().unwrap()).unwrap()
+}
+
+// The links of the issue delivered last, preceded by the subscriber's confirmation
+async fn delivered_links(app: &helpers::TestApp) -> Vec<String> {
+    let email_requests = app.email_server.received_requests().await.unwrap();
+    let body: Value = serde_json::from_slice(&email_requests.last().unwrap().body).unwrap();
+    let html = body["HtmlBody"].as_str().unwrap();
+
+    html.split("<a href=\"")
+        .skip(1)
+        .map(|rest| rest[..rest.find('"').unwrap()].to_string())
+        .collect()
+}
+
+// Destinations are not followed, they are outside the test environment
+async fn click(app: &helpers::TestApp, link: &str) -> reqwest::Response {
+    let path = &link[link.find("/t/click/").unwrap()..];
+    Client::builder()
+        .redirect(Policy::none())
+        .build()
+        .unwrap()
+        .get(format!("{}{path}", app.address))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+#[tokio::test]
+async fn clicks_redirect_to_the_original_link_and_are_counted() {
+    let app = helpers::spawn_app().await;
+    app.create_active_subscriber().await;
+    app.login_admin().await;
+    let issue_id = publish_and_deliver(&app).await;
+
+    let links = delivered_links(&app).await;
+    assert!(links[0].contains("/t/click/"));
+    assert!(links[1].contains("/t/click/"));
+
+    for (link, destination) in [
+        (&links[0], "https://example.com/post?a=1&b=2"),
+        (&links[0], "https://example.com/post?a=1&b=2"),
+        (&links[1], "https://example.com/docs"),
+    ] {
+        let response = click(&app, link).await;
+        assert_eq!(response.status().as_u16(), 302);
+        assert_eq!(response.headers()["location"], destination);
+    }
+
+    let response = app.get_newsletter_status(&issue_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["clicks"], 3);
+    assert_eq!(body["clicked"], 1);
+    assert_eq!(body["click_rate"], 1.0);
+    assert_eq!(body["links"][0]["url"], "https://example.com/post?a=1&b=2");
+    assert_eq!(body["links"][0]["clicks"], 2);
+    assert_eq!(body["links"][0]["unique_clicks"], 1);
+    assert_eq!(body["links"][1]["url"], "https://example.com/docs");
+    assert_eq!(body["links"][1]["clicks"], 1);
+}
+
+#[tokio::test]
+async fn unsubscribe_link_is_not_tracked() {
+    let app = helpers::spawn_app().await;
+    app.create_active_subscriber().await;
+    app.login_admin().await;
+    publish_and_deliver(&app).await;
+
+    let links = delivered_links(&app).await;
+
+    assert_eq!(links.len(), 3);
+    assert!(links[2].contains("/v1/user/unsubscribe?token="));
+}
+
+#[tokio::test]
+async fn links_outside_the_issue_are_not_redirected_to() {
+    let app = helpers::spawn_app().await;
+    app.create_active_subscriber().await;
+    app.login_admin().await;
+    publish_and_deliver(&app).await;
+
+    let links = delivered_links(&app).await;
+    let past_last_link = format!("{}.2", links[1].strip_suffix(".1").unwrap());
+
+    for link in [
+        past_last_link,
+        "/t/click/not-a-real-token.0".to_string(),
+        "/t/click/no-position".to_string(),
+    ] {
+        let response = click(&app, &link).await;
+        assert_eq!(response.status().as_u16(), 404, "{link}");
+    }
+}
+
+#[tokio::test]
+async fn links_and_counts_outlive_the_recipients_of_an_issue_past_its_retention() {
+    let app = helpers::spawn_app().await;
+    app.create_active_subscriber().await;
+    app.login_admin().await;
+    let issue_id = publish_and_deliver(&app).await;
+    let links = delivered_links(&app).await;
+    click(&app, &links[0]).await;
+
+    sqlx::query!(
+        "UPDATE newsletter_issues SET created_at = NOW() - INTERVAL '8 days' WHERE id = $1",
+        issue_id
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+    app.cleanup_old_newsletter_issues().await;
+
+    let response = click(&app, &links[1]).await;
+    assert_eq!(response.status().as_u16(), 302);
+    assert_eq!(response.headers()["location"], "https://example.com/docs");
+
+    let response = app.get_newsletter_status(&issue_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["sent"], 1);
+    assert_eq!(body["clicks"], 2);
+    assert_eq!(body["clicked"], 1);
+
+    let recipients = sqlx::query_scalar!(
+        r#"
+        SELECT (SELECT COUNT(*) FROM newsletter_delivery_log WHERE newsletter_issue_id = $1)
+             + (SELECT COUNT(*) FROM newsletter_tracking_tokens
+                WHERE newsletter_issue_id = $1 AND user_email IS NOT NULL) AS "count!"
+        "#,
+        issue_id
+    )
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap();
+    assert_eq!(recipients, 0);
+}
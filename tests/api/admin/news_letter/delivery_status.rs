@@ -0,0 +1,162 @@
+use serde_json::Value;
+use techhub::domain::ANONYMOUS_USER_ID;
+use uuid::Uuid;
+use wiremock::{Mock, ResponseTemplate, matchers};
+
+use crate::helpers::{self, TestUser};
+
+async fn publish(app: &helpers::TestApp) -> Uuid {
+    let newsletter_body = serde_json::json!({
+        "title": "Status Newsletter",
+        "content": {
+            "text": "Hello subscribers!",
+            "html": "<p>Hello subscribers!</p>"
+        }
+    });
+
+    let key = Uuid::new_v4().to_string();
+    let response = app.publish_newsletters(&newsletter_body, Some(&key)).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    Uuid::parse_str(body["issue_id"].as_str().unwrap()).unwrap()
+}
+
+async fn subscribe_all_users(app: &helpers::TestApp) {
+    sqlx::query!(
+        "UPDATE users SET is_activated = true, is_subscribed = true WHERE id <> $1",
+        ANONYMOUS_USER_ID
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+}
+
+async fn status(app: &helpers::TestApp, issue_id: &Uuid) -> Value {
+    let response = app.get_newsletter_status(issue_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+    response.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn status_counts_sent_failed_and_skipped_deliveries() {
+    let app = helpers::spawn_app().await;
+    let undeliverable = TestUser::generate();
+    undeliverable.store(&app.db_pool).await.unwrap();
+    let unsubscribing = TestUser::generate();
+    unsubscribing.store(&app.db_pool).await.unwrap();
+    subscribe_all_users(&app).await;
+    sqlx::query!(
+        "UPDATE users SET email = 'not-an-email' WHERE id = $1",
+        undeliverable.user_id
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+    app.login_admin().await;
+
+    Mock::given(matchers::path("/email"))
+        .and(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&app.email_server)
+        .await;
+
+    let issue_id = publish(&app).await;
+    app.fan_out_pending_newsletters().await;
+    sqlx::query!(
+        "UPDATE users SET is_subscribed = false WHERE id = $1",
+        unsubscribing.user_id
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+    app.dispatch_all_pending_newsletter_emails().await;
+
+    let body = status(&app, &issue_id).await;
+    // Everyone else subscribed receives it
+    assert_eq!(body["sent"], body["enqueued"].as_i64().unwrap() - 2);
+    assert_eq!(body["failed"], 1);
+    assert_eq!(body["skipped"], 1);
+    assert_eq!(body["pending_deliveries"], 0);
+    assert_eq!(body["is_complete"], true);
+    assert_eq!(body["failures"][0]["user_email"], "not-an-email");
+    assert_eq!(body["failures"][0]["error"], "Invalid subscriber email");
+}
+
+#[tokio::test]
+async fn deliveries_failing_after_the_last_retry_are_listed_with_their_error() {
+    let app = helpers::spawn_app().await;
+    subscribe_all_users(&app).await;
+    app.login_admin().await;
+
+    Mock::given(matchers::path("/email"))
+        .and(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&app.email_server)
+        .await;
+
+    let issue_id = publish(&app).await;
+    app.dispatch_all_pending_newsletter_emails().await;
+
+    let body = status(&app, &issue_id).await;
+    let recipients = body["enqueued"].as_i64().unwrap();
+    assert_eq!(body["pending_deliveries"], recipients);
+    assert_eq!(body["retrying_deliveries"], recipients);
+    assert_eq!(body["failed"], 0);
+    assert_eq!(body["is_complete"], false);
+
+    sqlx::query!(
+        "UPDATE issue_delivery_queue SET n_retries = $1, execute_after = NOW()",
+        app.newsletter_delivery.max_retries
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+    app.dispatch_all_pending_newsletter_emails().await;
+
+    let body = status(&app, &issue_id).await;
+    assert_eq!(body["failed"], recipients);
+    assert_eq!(body["sent"], 0);
+    assert_eq!(body["is_complete"], true);
+    let failure = &body["failures"][0];
+    assert_eq!(failure["attempts"], app.newsletter_delivery.max_retries + 1);
+    assert!(!failure["error"].as_str().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn suppressed_recipients_are_counted_as_skipped_without_a_tracking_token() {
+    let app = helpers::spawn_app().await;
+    let suppressed = TestUser::generate();
+    suppressed.store(&app.db_pool).await.unwrap();
+    subscribe_all_users(&app).await;
+    app.login_admin().await;
+
+    Mock::given(matchers::path("/email"))
+        .and(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&app.email_server)
+        .await;
+
+    let issue_id = publish(&app).await;
+    app.fan_out_pending_newsletters().await;
+    // Suppressed between fan-out and delivery
+    let response = app.suppress_email(&suppressed.email).await;
+    assert_eq!(response.status().as_u16(), 201);
+    app.dispatch_all_pending_newsletter_emails().await;
+
+    let body = status(&app, &issue_id).await;
+    assert_eq!(body["sent"], body["enqueued"].as_i64().unwrap() - 1);
+    assert_eq!(body["skipped"], 1);
+    assert_eq!(body["is_complete"], true);
+
+    let tokens = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!" FROM newsletter_tracking_tokens
+        WHERE newsletter_issue_id = $1 AND LOWER(user_email) = LOWER($2)"#,
+        issue_id,
+        suppressed.email
+    )
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap();
+    assert_eq!(tokens, 0);
+}
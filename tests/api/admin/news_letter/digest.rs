@@ -0,0 +1,101 @@
+use chrono::{Duration, Utc};
+use serde_json::{Value, json};
+use uuid::Uuid;
+use wiremock::{Mock, ResponseTemplate, matchers};
+
+use crate::helpers;
+
+async fn comment_on(app: &helpers::TestApp, post_id: &Uuid) {
+    let payload = json!({ "text": "Great read", "post_id": post_id.to_string() });
+    let response = app.create_comment(&payload).await;
+    assert_eq!(response.status().as_u16(), 201);
+}
+
+async fn issue_count(app: &helpers::TestApp) -> i64 {
+    sqlx::query_scalar!(r#"SELECT COUNT(*) AS "count!" FROM newsletter_issues"#)
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn digest_features_the_weeks_most_engaged_posts_and_is_delivered() {
+    let app = helpers::spawn_app().await;
+    app.create_active_subscriber().await;
+    app.login().await;
+    let liked_and_discussed = app
+        .create_sample_post_custom("Liked and discussed", "A post people liked and discussed")
+        .await;
+    let discussed = app
+        .create_sample_post_custom("Only discussed", "A post people only discussed")
+        .await;
+    let ignored = app
+        .create_sample_post_custom("Ignored", "A post nobody engaged with")
+        .await;
+    app.like_post_as_user(&liked_and_discussed).await;
+    comment_on(&app, &liked_and_discussed).await;
+    comment_on(&app, &discussed).await;
+
+    let issue_id = app.send_weekly_digest(Utc::now()).await.unwrap();
+
+    let issue = sqlx::query!(
+        "SELECT title, html_content FROM newsletter_issues WHERE id = $1",
+        issue_id
+    )
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap();
+    assert!(issue.title.starts_with("Top posts of the week ending"));
+    let first = issue
+        .html_content
+        .find(&liked_and_discussed.to_string())
+        .unwrap();
+    let second = issue.html_content.find(&discussed.to_string()).unwrap();
+    assert!(first < second);
+    assert!(!issue.html_content.contains(&ignored.to_string()));
+
+    Mock::given(matchers::path("/email"))
+        .and(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&app.email_server)
+        .await;
+    app.fan_out_pending_newsletters().await;
+    app.dispatch_all_pending_newsletter_emails().await;
+
+    let email_requests = app.email_server.received_requests().await.unwrap();
+    let body: Value = serde_json::from_slice(&email_requests.last().unwrap().body).unwrap();
+    assert_eq!(body["Subject"], issue.title);
+}
+
+#[tokio::test]
+async fn digest_is_created_once_per_scheduled_run() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let post_id = app.create_sample_post().await;
+    app.like_post_as_user(&post_id).await;
+    let scheduled_for = Utc::now();
+
+    assert!(app.send_weekly_digest(scheduled_for).await.is_some());
+    // As another worker instance reaching the same run
+    assert_eq!(app.send_weekly_digest(scheduled_for).await, None);
+
+    assert_eq!(issue_count(&app).await, 1);
+}
+
+#[tokio::test]
+async fn no_digest_is_created_without_engaging_posts_of_the_week() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    app.create_sample_post_custom("Ignored", "A post nobody engaged with")
+        .await;
+    let liked = app.create_sample_post().await;
+    app.like_post_as_user(&liked).await;
+
+    // The liked post is from the week before
+    assert_eq!(
+        app.send_weekly_digest(Utc::now() + Duration::days(8)).await,
+        None
+    );
+    assert_eq!(issue_count(&app).await, 0);
+}
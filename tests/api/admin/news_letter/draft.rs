@@ -0,0 +1,158 @@
+use serde_json::{Value, json};
+use uuid::Uuid;
+use wiremock::{Mock, ResponseTemplate, matchers};
+
+use crate::helpers;
+
+fn newsletter(title: &str) -> Value {
+    json!({
+        "title": title,
+        "content": {
+            "text": "Hello subscribers!",
+            "html": "<p>Hello subscribers!</p>"
+        }
+    })
+}
+
+async fn create_draft(app: &helpers::TestApp, title: &str) -> Uuid {
+    let response = app.create_newsletter_draft(&newsletter(title)).await;
+    assert_eq!(response.status().as_u16(), 201);
+
+    let body: Value = response.json().await.unwrap();
+    Uuid::parse_str(body["issue_id"].as_str().unwrap()).unwrap()
+}
+
+async fn status(app: &helpers::TestApp, issue_id: &Uuid) -> Value {
+    let response = app.get_newsletter_status(issue_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+    response.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn drafts_are_only_delivered_once_published() {
+    let app = helpers::spawn_app().await;
+    app.create_active_subscriber().await;
+    app.login_admin().await;
+
+    Mock::given(matchers::path("/email"))
+        .and(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&app.email_server)
+        .await;
+
+    let issue_id = create_draft(&app, "Draft issue").await;
+    app.dispatch_all_pending_newsletter_emails().await;
+
+    let body = status(&app, &issue_id).await;
+    assert_eq!(body["fan_out_status"], "draft");
+    assert_eq!(body["enqueued"], 0);
+    assert!(body["published_at"].is_null());
+
+    let response = app.publish_newsletter_draft(&issue_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+    app.dispatch_all_pending_newsletter_emails().await;
+
+    let body = status(&app, &issue_id).await;
+    assert_eq!(body["sent"], 1);
+    assert_eq!(body["is_complete"], true);
+    assert!(body["published_at"].is_string());
+}
+
+#[tokio::test]
+async fn drafts_can_be_edited_until_published() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+    let issue_id = create_draft(&app, "First title").await;
+
+    let response = app
+        .update_newsletter_draft(&issue_id, &newsletter("Second title"))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = app.get_newsletter_issues("").await.json().await.unwrap();
+    assert_eq!(body["issues"][0]["title"], "Second title");
+    assert!(body["issues"][0]["published_at"].is_null());
+
+    app.publish_newsletter_draft(&issue_id).await;
+    let response = app
+        .update_newsletter_draft(&issue_id, &newsletter("Third title"))
+        .await;
+    assert_eq!(response.status().as_u16(), 409);
+}
+
+#[tokio::test]
+async fn publishing_twice_returns_409_and_is_audited_once() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+    let issue_id = create_draft(&app, "Draft issue").await;
+
+    let response = app.publish_newsletter_draft(&issue_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let response = app.publish_newsletter_draft(&issue_id).await;
+    assert_eq!(response.status().as_u16(), 409);
+
+    let body: Value = app
+        .get_audit_log(&format!("?target_id={issue_id}"))
+        .await
+        .json()
+        .await
+        .unwrap();
+    let events = body["events"].as_array().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["action"], "newsletter_publish");
+    assert_eq!(events[0]["metadata"]["title"], "Draft issue");
+}
+
+#[tokio::test]
+async fn unknown_drafts_return_404() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+    let issue_id = Uuid::new_v4();
+
+    let response = app
+        .update_newsletter_draft(&issue_id, &newsletter("Title"))
+        .await;
+    assert_eq!(response.status().as_u16(), 404);
+
+    let response = app.publish_newsletter_draft(&issue_id).await;
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn invalid_drafts_are_rejected() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    let response = app.create_newsletter_draft(&newsletter("")).await;
+
+    assert_eq!(response.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn non_admins_cannot_create_drafts() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let response = app.create_newsletter_draft(&newsletter("Title")).await;
+
+    assert_eq!(response.status().as_u16(), 403);
+}
+
+#[tokio::test]
+async fn old_drafts_are_not_cleaned_up() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+    let issue_id = create_draft(&app, "Old draft").await;
+    sqlx::query!(
+        "UPDATE newsletter_issues SET created_at = NOW() - INTERVAL '8 days' WHERE id = $1",
+        issue_id
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+
+    app.cleanup_old_newsletter_issues().await;
+
+    let response = app.get_newsletter_status(&issue_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+}
@@ -0,0 +1,166 @@
+use serde_json::Value;
+use techhub::{
+    domain::{ANONYMOUS_USER_ID, FanOutOutcome},
+    repository,
+};
+use uuid::Uuid;
+use wiremock::{Mock, ResponseTemplate, matchers};
+
+use crate::helpers;
+
+async fn publish(app: &helpers::TestApp) -> Uuid {
+    let newsletter_body = serde_json::json!({
+        "title": "Fan-out Newsletter",
+        "content": {
+            "text": "Hello subscribers!",
+            "html": "<p>Hello subscribers!</p>"
+        }
+    });
+
+    let key = Uuid::new_v4().to_string();
+    let response = app.publish_newsletters(&newsletter_body, Some(&key)).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    Uuid::parse_str(body["issue_id"].as_str().unwrap()).unwrap()
+}
+
+// The placeholder author of anonymous comments is not a real user, so it stays unsubscribed
+async fn subscribe_all_users(app: &helpers::TestApp) {
+    sqlx::query!(
+        "UPDATE users SET is_activated = true, is_subscribed = true WHERE id <> $1",
+        ANONYMOUS_USER_ID
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+}
+
+async fn queue_size(app: &helpers::TestApp) -> i64 {
+    sqlx::query_scalar!(r#"SELECT COUNT(*) AS "count!" FROM issue_delivery_queue"#)
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn publish_returns_immediately_without_enqueueing_deliveries() {
+    let app = helpers::spawn_app().await;
+    app.create_active_subscriber().await;
+    app.login_admin().await;
+
+    let issue_id = publish(&app).await;
+
+    assert_eq!(queue_size(&app).await, 0);
+
+    let response = app.get_newsletter_status(&issue_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["fan_out_status"], "pending");
+    assert_eq!(body["enqueued"], 0);
+}
+
+#[tokio::test]
+async fn fan_out_enqueues_subscribers_in_chunks_and_tracks_progress() {
+    let app = helpers::spawn_app().await;
+    subscribe_all_users(&app).await;
+    app.login_admin().await;
+    let issue_id = publish(&app).await;
+
+    // The test user and the seeded admin are both subscribed
+    let outcome = repository::fan_out_next_chunk(&app.db_pool, 1, 100)
+        .await
+        .unwrap();
+    assert_eq!(
+        outcome,
+        FanOutOutcome::ChunkEnqueued {
+            enqueued: 1,
+            finished: false
+        }
+    );
+    assert_eq!(queue_size(&app).await, 1);
+
+    repository::fan_out_next_chunk(&app.db_pool, 1, 100)
+        .await
+        .unwrap();
+    let outcome = repository::fan_out_next_chunk(&app.db_pool, 1, 100)
+        .await
+        .unwrap();
+    assert_eq!(
+        outcome,
+        FanOutOutcome::ChunkEnqueued {
+            enqueued: 0,
+            finished: true
+        }
+    );
+
+    let outcome = repository::fan_out_next_chunk(&app.db_pool, 1, 100)
+        .await
+        .unwrap();
+    assert_eq!(outcome, FanOutOutcome::NothingPending);
+
+    let body: Value = app
+        .get_newsletter_status(&issue_id)
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["fan_out_status"], "done");
+    assert_eq!(body["enqueued"], 2);
+    assert_eq!(body["pending_deliveries"], 2);
+}
+
+#[tokio::test]
+async fn fan_out_is_throttled_while_the_queue_is_deep() {
+    let app = helpers::spawn_app().await;
+    subscribe_all_users(&app).await;
+    app.login_admin().await;
+    publish(&app).await;
+
+    repository::fan_out_next_chunk(&app.db_pool, 1, 1)
+        .await
+        .unwrap();
+    let outcome = repository::fan_out_next_chunk(&app.db_pool, 1, 1)
+        .await
+        .unwrap();
+
+    assert_eq!(outcome, FanOutOutcome::Throttled);
+    assert_eq!(queue_size(&app).await, 1);
+}
+
+#[tokio::test]
+async fn worker_fans_out_and_delivers_published_issue() {
+    let app = helpers::spawn_app().await;
+    app.create_active_subscriber().await;
+    app.login_admin().await;
+
+    Mock::given(matchers::path("/email"))
+        .and(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&app.email_server)
+        .await;
+
+    let issue_id = publish(&app).await;
+    app.dispatch_all_pending_newsletter_emails().await;
+
+    let body: Value = app
+        .get_newsletter_status(&issue_id)
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["fan_out_status"], "done");
+    assert_eq!(body["enqueued"], 1);
+    assert_eq!(body["pending_deliveries"], 0);
+}
+
+#[tokio::test]
+async fn newsletter_status_returns_404_for_unknown_issue() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    let response = app.get_newsletter_status(&Uuid::new_v4()).await;
+
+    assert_eq!(response.status().as_u16(), 404);
+}
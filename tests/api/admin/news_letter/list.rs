@@ -0,0 +1,89 @@
+use serde_json::Value;
+use uuid::Uuid;
+use wiremock::{Mock, ResponseTemplate, matchers};
+
+use crate::helpers;
+
+async fn publish(app: &helpers::TestApp, title: &str) {
+    let newsletter_body = serde_json::json!({
+        "title": title,
+        "content": {
+            "text": "Hello subscribers!",
+            "html": "<p>Hello subscribers!</p>"
+        }
+    });
+
+    let key = Uuid::new_v4().to_string();
+    let response = app.publish_newsletters(&newsletter_body, Some(&key)).await;
+    assert_eq!(response.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn issues_are_listed_newest_first_with_their_delivery_counts() {
+    let app = helpers::spawn_app().await;
+    app.create_active_subscriber().await;
+    app.login_admin().await;
+
+    Mock::given(matchers::path("/email"))
+        .and(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&app.email_server)
+        .await;
+
+    publish(&app, "First issue").await;
+    app.dispatch_all_pending_newsletter_emails().await;
+    publish(&app, "Second issue").await;
+    app.fan_out_pending_newsletters().await;
+
+    let response = app.get_newsletter_issues("").await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body: Value = response.json().await.unwrap();
+    let issues = body["issues"].as_array().unwrap();
+
+    assert_eq!(issues.len(), 2);
+    assert_eq!(issues[0]["title"], "Second issue");
+    assert_eq!(issues[0]["enqueued"], 1);
+    assert_eq!(issues[0]["pending_deliveries"], 1);
+    assert_eq!(issues[0]["completed_deliveries"], 0);
+    assert_eq!(issues[1]["title"], "First issue");
+    assert_eq!(issues[1]["fan_out_status"], "done");
+    assert_eq!(issues[1]["completed_deliveries"], 1);
+    assert!(issues[1]["published_at"].is_string());
+    assert_eq!(body["metadata"]["total_records"], 2);
+}
+
+#[tokio::test]
+async fn issues_are_paginated() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+    publish(&app, "First issue").await;
+    publish(&app, "Second issue").await;
+
+    let response = app.get_newsletter_issues("?page=2&limit=1").await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body: Value = response.json().await.unwrap();
+
+    assert_eq!(body["issues"].as_array().unwrap().len(), 1);
+    assert_eq!(body["issues"][0]["title"], "First issue");
+    assert_eq!(body["metadata"]["last_page"], 2);
+}
+
+#[tokio::test]
+async fn listing_issues_returns_400_for_invalid_pagination() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    let response = app.get_newsletter_issues("?limit=0").await;
+
+    assert_eq!(response.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn non_admins_cannot_list_issues() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let response = app.get_newsletter_issues("").await;
+
+    assert_eq!(response.status().as_u16(), 403);
+}
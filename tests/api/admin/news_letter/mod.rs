@@ -1 +1,19 @@
+mod attachments;
+mod batch;
+mod cancel;
+mod click_tracking;
+mod delivery_status;
+mod digest;
+mod draft;
+mod fan_out;
+mod list;
+mod open_tracking;
+mod personalization;
+mod preview;
 mod publish;
+mod queue;
+mod retry_failed;
+mod schedule;
+mod segment;
+mod subject_test;
+mod test_send;
@@ -0,0 +1,80 @@
+use serde_json::{Value, json};
+use uuid::Uuid;
+use wiremock::{Mock, ResponseTemplate, matchers};
+
+use crate::helpers;
+
+async fn publish_and_deliver(app: &helpers::TestApp) -> Uuid {
+    Mock::given(matchers::path("/email"))
+        .and(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&app.email_server)
+        .await;
+
+    let newsletter_body = json!({
+        "title": "Tracked Newsletter",
+        "content": {
+            "text": "Hello subscribers!",
+            "html": "<p>Hello subscribers!</p>"
+        }
+    });
+    let key = Uuid::new_v4().to_string();
+    let response = app.publish_newsletters(&newsletter_body, Some(&key)).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body: Value = response.json().await.unwrap();
+
+    app.fan_out_pending_newsletters().await;
+    app.dispatch_all_pending_newsletter_emails().await;
+
+    Uuid::parse_str(body["issue_id"].as_str().unwrap()).unwrap()
+}
+
+// The pixel of the issue delivered last, preceded by the subscriber's confirmation
+async fn tracking_pixel_path(app: &helpers::TestApp) -> String {
+    let email_requests = app.email_server.received_requests().await.unwrap();
+    let body: Value = serde_json::from_slice(&email_requests.last().unwrap().body).unwrap();
+    let html = body["HtmlBody"].as_str().unwrap();
+
+    let start = html.find("/t/open/").unwrap();
+    let end = start + html[start..].find('"').unwrap();
+    html[start + 1..end].to_string()
+}
+
+async fn status(app: &helpers::TestApp, issue_id: &Uuid) -> Value {
+    let response = app.get_newsletter_status(issue_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+    response.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn opens_are_counted_once_per_recipient() {
+    let app = helpers::spawn_app().await;
+    app.create_active_subscriber().await;
+    app.login_admin().await;
+    let issue_id = publish_and_deliver(&app).await;
+
+    let body = status(&app, &issue_id).await;
+    assert_eq!(body["opened"], 0);
+    assert_eq!(body["open_rate"], 0.0);
+
+    let pixel = tracking_pixel_path(&app).await;
+    for _ in 0..2 {
+        let response = app.send_get(&pixel).await;
+        assert_eq!(response.status().as_u16(), 200);
+        assert_eq!(response.headers()["content-type"], "image/gif");
+    }
+
+    let body = status(&app, &issue_id).await;
+    assert_eq!(body["opened"], 1);
+    assert_eq!(body["open_rate"], 1.0);
+}
+
+#[tokio::test]
+async fn unknown_tracking_tokens_still_get_the_pixel() {
+    let app = helpers::spawn_app().await;
+
+    let response = app.send_get("t/open/not-a-real-token").await;
+
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(response.headers()["content-type"], "image/gif");
+}
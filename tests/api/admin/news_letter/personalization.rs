@@ -0,0 +1,80 @@
+use serde_json::{Value, json};
+use uuid::Uuid;
+use wiremock::{Mock, ResponseTemplate, matchers};
+
+use crate::helpers;
+
+#[tokio::test]
+async fn placeholders_are_replaced_for_each_recipient() {
+    let app = helpers::spawn_app().await;
+    app.create_active_subscriber().await;
+    app.login_admin().await;
+    Mock::given(matchers::path("/email"))
+        .and(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&app.email_server)
+        .await;
+
+    let newsletter_body = json!({
+        "title": "Personal Newsletter",
+        "content": {
+            "text": "Hi {{user_name}}, leave at {{unsubscribe_url}}",
+            "html": "<p>Hi {{ user_name }}, <a href=\"{{unsubscribe_url}}\">leave</a></p>"
+        }
+    });
+    let key = Uuid::new_v4().to_string();
+    let response = app.publish_newsletters(&newsletter_body, Some(&key)).await;
+    assert_eq!(response.status().as_u16(), 200);
+    app.fan_out_pending_newsletters().await;
+    app.dispatch_all_pending_newsletter_emails().await;
+
+    let user_name: String =
+        sqlx::query_scalar!("SELECT user_name FROM users WHERE is_subscribed = true")
+            .fetch_one(&app.db_pool)
+            .await
+            .unwrap();
+    // Preceded by the confirmation email of the subscriber
+    let email_requests = app.email_server.received_requests().await.unwrap();
+    let body: Value = serde_json::from_slice(&email_requests.last().unwrap().body).unwrap();
+    let text = body["TextBody"].as_str().unwrap();
+    let html = body["HtmlBody"].as_str().unwrap();
+
+    // The placeholder gives the same link as the one added to every issue
+    let (personal, footer) = text.split_once("\n\n").unwrap();
+    let unsubscribe_url = footer
+        .strip_prefix("To stop receiving this newsletter, visit ")
+        .unwrap();
+    assert_eq!(
+        personal,
+        format!("Hi {user_name}, leave at {unsubscribe_url}")
+    );
+    assert!(html.starts_with(&format!(
+        "<p>Hi {user_name}, <a href=\"{}\">leave</a></p>",
+        unsubscribe_url.replace('&', "&amp;")
+    )));
+}
+
+#[tokio::test]
+async fn content_with_unknown_placeholders_is_rejected() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    let newsletter_body = json!({
+        "title": "Personal Newsletter",
+        "content": {
+            "text": "Hi {{first_name}}",
+            "html": "<p>Hi {{first_name}}</p>"
+        }
+    });
+    let key = Uuid::new_v4().to_string();
+    let response = app.publish_newsletters(&newsletter_body, Some(&key)).await;
+    assert_eq!(response.status().as_u16(), 400);
+    let body: Value = response.json().await.unwrap();
+    assert!(
+        body["message"].as_str().unwrap().contains("{{first_name}}"),
+        "{body}"
+    );
+
+    let response = app.create_newsletter_draft(&newsletter_body).await;
+    assert_eq!(response.status().as_u16(), 400);
+}
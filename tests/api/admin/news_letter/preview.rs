@@ -0,0 +1,66 @@
+use serde_json::{Value, json};
+
+use crate::helpers;
+
+#[tokio::test]
+async fn preview_renders_content_without_storing_it() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    let response = app
+        .preview_newsletter(&json!({
+            "title": "Weekly",
+            "content": { "markdown": "Hi **{{user_name}}**" }
+        }))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body: Value = response.json().await.unwrap();
+
+    assert_eq!(body["title"], "Weekly");
+    let html = body["html"].as_str().unwrap();
+    assert!(
+        html.starts_with("<p>Hi <strong>athfan</strong></p>"),
+        "{html}"
+    );
+    assert!(html.contains(">Unsubscribe</a>"), "{html}");
+    let text = body["text"].as_str().unwrap();
+    assert!(text.starts_with("Hi athfan\n\nTo stop receiving"), "{text}");
+
+    let body: Value = app.get_newsletter_issues("").await.json().await.unwrap();
+    assert_eq!(body["issues"].as_array().unwrap().len(), 0);
+}
+
+#[tokio::test]
+async fn preview_rejects_content_that_could_not_be_published() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    for (payload, reason) in [
+        (
+            json!({ "title": "Weekly", "content": { "markdown": "Hi {{nickname}}" } }),
+            "unknown placeholder",
+        ),
+        (
+            json!({ "title": "", "content": { "markdown": "Hi" } }),
+            "empty title",
+        ),
+        (
+            json!({ "title": "Weekly", "content": { "html": "<p>Hi</p>" } }),
+            "html without text",
+        ),
+    ] {
+        let response = app.preview_newsletter(&payload).await;
+        assert_eq!(response.status().as_u16(), 400, "{reason}");
+    }
+}
+
+#[tokio::test]
+async fn preview_requires_authentication() {
+    let app = helpers::spawn_app().await;
+
+    let response = app
+        .preview_newsletter(&json!({ "title": "Weekly", "content": { "markdown": "Hi" } }))
+        .await;
+
+    assert_eq!(response.status().as_u16(), 401);
+}
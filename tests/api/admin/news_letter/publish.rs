@@ -227,6 +227,26 @@ async fn publish_newsletter_returns_400_for_invalid_data() {
             }),
             "html without valid tags",
         ),
+        // Markdown authoring
+        (
+            serde_json::json!({
+                "title": "Newsletter!",
+                "content": {
+                    "markdown": "   "
+                }
+            }),
+            "empty markdown",
+        ),
+        (
+            serde_json::json!({
+                "title": "Newsletter!",
+                "content": {
+                    "markdown": "Body",
+                    "html": "<p>HTML</p>"
+                }
+            }),
+            "markdown alongside html",
+        ),
     ];
 
     for (invalid_body, desc) in invalid_cases {
@@ -386,6 +406,49 @@ async fn publish_newsletter_delivers_to_active_subscriber_full_flow() {
     app.dispatch_all_pending_newsletter_emails().await;
 }
 
+#[tokio::test]
+async fn markdown_newsletters_are_delivered_with_derived_html_and_text() {
+    let app = helpers::spawn_app().await;
+    app.create_active_subscriber().await;
+    app.login_admin().await;
+
+    Mock::given(matchers::path("/email"))
+        .and(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&app.email_server)
+        .await;
+
+    let newsletter_body = serde_json::json!({
+        "title": "Markdown Newsletter",
+        "content": {
+            "markdown": "Hello **subscribers**, see [the blog](https://example.com)!"
+        }
+    });
+
+    let key = Uuid::new_v4().to_string();
+    let response = app.publish_newsletters(&newsletter_body, Some(&key)).await;
+    assert_eq!(response.status().as_u16(), 200);
+    app.fan_out_pending_newsletters().await;
+    app.dispatch_all_pending_newsletter_emails().await;
+
+    // Preceded by the confirmation email of the subscriber
+    let email_requests = app.email_server.received_requests().await.unwrap();
+    let body: serde_json::Value =
+        serde_json::from_slice(&email_requests.last().unwrap().body).unwrap();
+    // The link goes through click tracking
+    let html = body["HtmlBody"].as_str().unwrap();
+    assert!(html.starts_with(r#"<p>Hello <strong>subscribers</strong>, see <a href=""#));
+    assert!(html.contains(r#"/t/click/"#));
+    assert!(html.contains(r#"">the blog</a>!</p>"#));
+    assert!(
+        body["TextBody"]
+            .as_str()
+            .unwrap()
+            .starts_with("Hello subscribers, see the blog (https://example.com)!")
+    );
+}
+
 #[tokio::test]
 async fn publish_newsletter_is_idempotent() {
     let app = helpers::spawn_app().await;
@@ -455,6 +518,73 @@ async fn publish_newsletter_handles_concurrent_requests_gracefully() {
     app.dispatch_all_pending_newsletter_emails().await;
 }
 
+#[tokio::test]
+async fn publish_newsletter_retry_returns_409_once_the_issue_is_cleaned_up() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    let newsletter_body = serde_json::json!({
+        "title": "Test Newsletter",
+        "content": {
+            "text": "Hello subscribers!",
+            "html": "<p>Hello subscribers!</p>"
+        }
+    });
+
+    let key = Uuid::new_v4().to_string();
+    let response = app.publish_newsletters(&newsletter_body, Some(&key)).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    let issue_id: Uuid = body["issue_id"].as_str().unwrap().parse().unwrap();
+    app.fan_out_pending_newsletters().await;
+
+    // Age the issue past the retention window so the cleanup removes it
+    sqlx::query!(
+        "UPDATE newsletter_issues SET created_at = NOW() - INTERVAL '8 days' WHERE id = $1",
+        issue_id
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+    app.cleanup_old_newsletter_issues().await;
+
+    let response = app.publish_newsletters(&newsletter_body, Some(&key)).await;
+    assert_eq!(response.status().as_u16(), 409);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["error"], "idempotency_conflict");
+
+    // A fresh key publishes the issue again
+    let new_key = Uuid::new_v4().to_string();
+    let response = app
+        .publish_newsletters(&newsletter_body, Some(&new_key))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn publish_newsletter_retry_replays_the_saved_response_while_the_issue_exists() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    let newsletter_body = serde_json::json!({
+        "title": "Test Newsletter",
+        "content": {
+            "text": "Hello subscribers!",
+            "html": "<p>Hello subscribers!</p>"
+        }
+    });
+
+    let key = Uuid::new_v4().to_string();
+    let first = app.publish_newsletters(&newsletter_body, Some(&key)).await;
+    let first: serde_json::Value = first.json().await.unwrap();
+
+    let retry = app.publish_newsletters(&newsletter_body, Some(&key)).await;
+    assert_eq!(retry.status().as_u16(), 200);
+    let retry: serde_json::Value = retry.json().await.unwrap();
+
+    assert_eq!(first["issue_id"], retry["issue_id"]);
+}
+
 #[tokio::test]
 async fn publish_newsletter_retries_failed_delivery_with_back_off() {
     let app = helpers::spawn_app().await;
@@ -478,6 +608,7 @@ async fn publish_newsletter_retries_failed_delivery_with_back_off() {
     let key = Uuid::new_v4().to_string();
     let response = app.publish_newsletters(&newsletter_body, Some(&key)).await;
     assert_eq!(response.status().as_u16(), 200);
+    app.fan_out_pending_newsletters().await;
 
     // Fetch the single delivery task created
     let tasks = sqlx::query!(
@@ -576,3 +707,38 @@ async fn cleanup_old_newsletter_issues_deletes_issues_older_than_7_days() {
     .unwrap();
     assert!(new_exists, "Recent newsletter issue was wrongly deleted");
 }
+
+#[tokio::test]
+async fn cleanup_old_newsletter_issues_keeps_issues_still_being_sent() {
+    let app = helpers::spawn_app().await;
+    app.create_active_subscriber().await;
+    app.login_admin().await;
+
+    let newsletter_body = serde_json::json!({
+        "title": "Test Newsletter",
+        "content": {
+            "text": "Hello subscribers!",
+            "html": "<p>Hello subscribers!</p>"
+        }
+    });
+    let key = Uuid::new_v4().to_string();
+    let response = app.publish_newsletters(&newsletter_body, Some(&key)).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    let issue_id: Uuid = body["issue_id"].as_str().unwrap().parse().unwrap();
+    // Fanned out, but its delivery is still queued
+    app.fan_out_pending_newsletters().await;
+    sqlx::query!(
+        "UPDATE newsletter_issues SET created_at = NOW() - INTERVAL '8 days' WHERE id = $1",
+        issue_id
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+
+    app.cleanup_old_newsletter_issues().await;
+
+    let response = app.get_newsletter_status(&issue_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["enqueued"], 1);
+}
@@ -0,0 +1,125 @@
+use techhub::repository;
+use uuid::Uuid;
+use wiremock::{Mock, ResponseTemplate, matchers};
+
+use crate::helpers;
+
+async fn publish_failing_newsletter(app: &helpers::TestApp) {
+    app.create_active_subscriber().await;
+    app.login_admin().await;
+
+    Mock::given(matchers::path("/email"))
+        .and(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&app.email_server)
+        .await;
+
+    let newsletter_body = serde_json::json!({
+        "title": "Queued Newsletter",
+        "content": {
+            "text": "Hello subscribers!",
+            "html": "<p>Hello subscribers!</p>"
+        }
+    });
+
+    let key = Uuid::new_v4().to_string();
+    let response = app.publish_newsletters(&newsletter_body, Some(&key)).await;
+    assert_eq!(response.status().as_u16(), 200);
+    app.fan_out_pending_newsletters().await;
+}
+
+#[tokio::test]
+async fn delivery_is_not_paused_by_default() {
+    let app = helpers::spawn_app().await;
+
+    let is_paused = repository::is_delivery_paused(&app.db_pool).await.unwrap();
+
+    assert!(!is_paused);
+}
+
+#[tokio::test]
+async fn delivery_can_be_paused_and_resumed() {
+    let app = helpers::spawn_app().await;
+
+    repository::set_delivery_paused(&app.db_pool, true)
+        .await
+        .unwrap();
+    assert!(repository::is_delivery_paused(&app.db_pool).await.unwrap());
+
+    repository::set_delivery_paused(&app.db_pool, false)
+        .await
+        .unwrap();
+    assert!(!repository::is_delivery_paused(&app.db_pool).await.unwrap());
+}
+
+#[tokio::test]
+async fn queue_stats_report_pending_tasks_per_issue() {
+    let app = helpers::spawn_app().await;
+    publish_failing_newsletter(&app).await;
+
+    let stats =
+        repository::get_delivery_queue_stats(&app.db_pool, app.newsletter_delivery.max_retries)
+            .await
+            .unwrap();
+
+    assert_eq!(stats.total, 1);
+    assert_eq!(stats.ready, 1);
+    assert_eq!(stats.retrying, 0);
+    assert_eq!(stats.retries_distribution, vec![(0, 1)]);
+    assert_eq!(stats.pending_issues.len(), 1);
+    assert_eq!(stats.pending_issues[0].title, "Queued Newsletter");
+    assert_eq!(stats.pending_issues[0].pending, 1);
+    assert!(stats.recent_errors.is_empty());
+}
+
+#[tokio::test]
+async fn queue_stats_report_last_error_of_failed_delivery() {
+    let app = helpers::spawn_app().await;
+    publish_failing_newsletter(&app).await;
+
+    app.dispatch_all_pending_newsletter_emails().await;
+
+    let stats =
+        repository::get_delivery_queue_stats(&app.db_pool, app.newsletter_delivery.max_retries)
+            .await
+            .unwrap();
+
+    assert_eq!(stats.total, 1);
+    assert_eq!(
+        stats.ready, 0,
+        "Failed task should be scheduled in the future"
+    );
+    assert_eq!(stats.retrying, 1);
+    assert_eq!(stats.recent_errors.len(), 1);
+
+    let failure = &stats.recent_errors[0];
+    assert_eq!(failure.n_retries, 1);
+    assert!(!failure.last_error.is_empty());
+    assert!(failure.last_attempted_at.is_some());
+}
+
+#[tokio::test]
+async fn tasks_failing_past_the_configured_retries_move_to_the_dead_letter_queue() {
+    let app = helpers::spawn_app_with(|c| c.newsletter_delivery.max_retries = 1).await;
+    publish_failing_newsletter(&app).await;
+
+    app.dispatch_all_pending_newsletter_emails().await;
+    sqlx::query!("UPDATE issue_delivery_queue SET execute_after = NOW()")
+        .execute(&app.db_pool)
+        .await
+        .unwrap();
+    app.dispatch_all_pending_newsletter_emails().await;
+
+    let stats = repository::get_delivery_queue_stats(&app.db_pool, 1)
+        .await
+        .unwrap();
+    assert_eq!(stats.total, 0);
+    assert_eq!(stats.dead_lettered, 1);
+
+    let failure = sqlx::query!("SELECT n_retries, last_error FROM issue_delivery_failures")
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(failure.n_retries, 2);
+    assert!(!failure.last_error.is_empty());
+}
@@ -0,0 +1,120 @@
+use serde_json::{Value, json};
+use techhub::domain::ANONYMOUS_USER_ID;
+use uuid::Uuid;
+use wiremock::{Mock, ResponseTemplate, matchers};
+
+use crate::helpers;
+
+async fn publish(app: &helpers::TestApp) -> Uuid {
+    let newsletter_body = json!({
+        "title": "Retried Newsletter",
+        "content": {
+            "text": "Hello subscribers!",
+            "html": "<p>Hello subscribers!</p>"
+        }
+    });
+
+    let key = Uuid::new_v4().to_string();
+    let response = app.publish_newsletters(&newsletter_body, Some(&key)).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    Uuid::parse_str(body["issue_id"].as_str().unwrap()).unwrap()
+}
+
+async fn status(app: &helpers::TestApp, issue_id: &Uuid) -> Value {
+    let response = app.get_newsletter_status(issue_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+    response.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn only_failed_recipients_are_sent_the_issue_again() {
+    // Dead-lettered on their first failure
+    let app = helpers::spawn_app_with(|c| c.newsletter_delivery.max_retries = 0).await;
+    sqlx::query!(
+        "UPDATE users SET is_activated = true, is_subscribed = true WHERE id <> $1",
+        ANONYMOUS_USER_ID
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+    app.login_admin().await;
+
+    Mock::given(matchers::path("/email"))
+        .and(matchers::body_partial_json(
+            json!({ "To": app.test_user.email }),
+        ))
+        .respond_with(ResponseTemplate::new(500))
+        .with_priority(1)
+        .mount(&app.email_server)
+        .await;
+    Mock::given(matchers::path("/email"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&app.email_server)
+        .await;
+
+    let issue_id = publish(&app).await;
+    app.dispatch_all_pending_newsletter_emails().await;
+
+    let body = status(&app, &issue_id).await;
+    let recipients = body["enqueued"].as_i64().unwrap();
+    assert_eq!(body["failed"], 1);
+    assert_eq!(body["failures"][0]["user_email"], app.test_user.email);
+
+    app.email_server.reset().await;
+    Mock::given(matchers::path("/email"))
+        .and(matchers::body_partial_json(
+            json!({ "To": app.test_user.email }),
+        ))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&app.email_server)
+        .await;
+
+    let response = app.retry_failed_newsletter_deliveries(&issue_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["requeued_deliveries"], 1);
+
+    let body = status(&app, &issue_id).await;
+    assert_eq!(body["failed"], 0);
+    assert_eq!(body["pending_deliveries"], 1);
+    assert!(body["failures"].as_array().unwrap().is_empty());
+
+    app.dispatch_all_pending_newsletter_emails().await;
+
+    let body = status(&app, &issue_id).await;
+    assert_eq!(body["sent"], recipients);
+    assert_eq!(body["is_complete"], true);
+}
+
+#[tokio::test]
+async fn issues_without_failures_have_nothing_to_retry() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+    let issue_id = publish(&app).await;
+
+    let response = app.retry_failed_newsletter_deliveries(&issue_id).await;
+
+    assert_eq!(response.status().as_u16(), 200);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["requeued_deliveries"], 0);
+}
+
+#[tokio::test]
+async fn cancelled_and_unknown_issues_cannot_be_retried() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+    let issue_id = publish(&app).await;
+    let response = app.cancel_newsletter_issue(&issue_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let response = app.retry_failed_newsletter_deliveries(&issue_id).await;
+    assert_eq!(response.status().as_u16(), 409);
+
+    let response = app
+        .retry_failed_newsletter_deliveries(&Uuid::new_v4())
+        .await;
+    assert_eq!(response.status().as_u16(), 404);
+}
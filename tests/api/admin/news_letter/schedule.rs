@@ -0,0 +1,160 @@
+use chrono::{DateTime, Duration, Utc};
+use serde_json::{Value, json};
+use uuid::Uuid;
+use wiremock::{Mock, ResponseTemplate, matchers};
+
+use crate::helpers;
+
+async fn publish(app: &helpers::TestApp, scheduled_at: Option<DateTime<Utc>>) -> Uuid {
+    let newsletter_body = json!({
+        "title": "Scheduled Newsletter",
+        "content": {
+            "text": "Hello subscribers!",
+            "html": "<p>Hello subscribers!</p>"
+        },
+        "scheduled_at": scheduled_at,
+    });
+
+    let key = Uuid::new_v4().to_string();
+    let response = app.publish_newsletters(&newsletter_body, Some(&key)).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    Uuid::parse_str(body["issue_id"].as_str().unwrap()).unwrap()
+}
+
+async fn status(app: &helpers::TestApp, issue_id: &Uuid) -> Value {
+    let response = app.get_newsletter_status(issue_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+    response.json().await.unwrap()
+}
+
+fn scheduled_at(status: &Value) -> DateTime<Utc> {
+    status["scheduled_at"].as_str().unwrap().parse().unwrap()
+}
+
+#[tokio::test]
+async fn scheduled_issues_are_only_delivered_once_their_time_has_passed() {
+    let app = helpers::spawn_app().await;
+    app.create_active_subscriber().await;
+    app.login_admin().await;
+
+    Mock::given(matchers::path("/email"))
+        .and(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&app.email_server)
+        .await;
+
+    let issue_id = publish(&app, Some(Utc::now() + Duration::hours(1))).await;
+    app.dispatch_all_pending_newsletter_emails().await;
+
+    let body = status(&app, &issue_id).await;
+    assert_eq!(body["fan_out_status"], "pending");
+    assert_eq!(body["enqueued"], 0);
+
+    sqlx::query!(
+        "UPDATE newsletter_issues SET scheduled_at = NOW() - INTERVAL '1 second' WHERE id = $1",
+        issue_id
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+    app.dispatch_all_pending_newsletter_emails().await;
+
+    let body = status(&app, &issue_id).await;
+    assert_eq!(body["sent"], 1);
+    assert_eq!(body["is_complete"], true);
+}
+
+#[tokio::test]
+async fn publishing_for_a_past_time_returns_400() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    let response = app
+        .publish_newsletters(
+            &json!({
+                "title": "Late Newsletter",
+                "content": { "text": "Hello!", "html": "<p>Hello!</p>" },
+                "scheduled_at": Utc::now() - Duration::minutes(1),
+            }),
+            Some(&Uuid::new_v4().to_string()),
+        )
+        .await;
+
+    assert_eq!(response.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn scheduled_issues_can_be_rescheduled() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+    let issue_id = publish(&app, Some(Utc::now() + Duration::hours(1))).await;
+    let new_time = Utc::now() + Duration::days(1);
+
+    let response = app
+        .reschedule_newsletter(&issue_id, &json!({ "scheduled_at": new_time }))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body = status(&app, &issue_id).await;
+    assert_eq!(scheduled_at(&body).timestamp(), new_time.timestamp());
+
+    let response = app
+        .reschedule_newsletter(
+            &issue_id,
+            &json!({ "scheduled_at": Utc::now() - Duration::hours(1) }),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn issues_already_sending_cannot_be_rescheduled() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+    let issue_id = publish(&app, None).await;
+
+    let response = app
+        .reschedule_newsletter(
+            &issue_id,
+            &json!({ "scheduled_at": Utc::now() + Duration::hours(1) }),
+        )
+        .await;
+
+    assert_eq!(response.status().as_u16(), 409);
+}
+
+#[tokio::test]
+async fn rescheduling_an_unknown_issue_returns_404() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    let response = app
+        .reschedule_newsletter(
+            &Uuid::new_v4(),
+            &json!({ "scheduled_at": Utc::now() + Duration::hours(1) }),
+        )
+        .await;
+
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn scheduled_issues_are_not_cleaned_up_before_they_are_sent() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+    let issue_id = publish(&app, Some(Utc::now() + Duration::days(10))).await;
+    sqlx::query!(
+        "UPDATE newsletter_issues SET created_at = NOW() - INTERVAL '8 days' WHERE id = $1",
+        issue_id
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+
+    app.cleanup_old_newsletter_issues().await;
+
+    let response = app.get_newsletter_status(&issue_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+}
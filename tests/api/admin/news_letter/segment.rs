@@ -0,0 +1,141 @@
+use chrono::{Duration, Utc};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::helpers;
+
+async fn create_segment(app: &helpers::TestApp, payload: &Value) -> String {
+    let response = app.create_newsletter_segment(payload).await;
+    assert_eq!(response.status().as_u16(), 201);
+    let body: Value = response.json().await.unwrap();
+    body["segment_id"].as_str().unwrap().to_string()
+}
+
+// How many deliveries the issue was enqueued for
+async fn publish_to_segment(app: &helpers::TestApp, segment_id: &str) -> i64 {
+    let newsletter_body = json!({
+        "title": "Newsletter title",
+        "content": {
+            "text": "Newsletter body as plain text",
+            "html": "<p>Newsletter body as HTML</p>"
+        },
+        "segment_id": segment_id
+    });
+    let key = Uuid::new_v4().to_string();
+    let response = app.publish_newsletters(&newsletter_body, Some(&key)).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body: Value = response.json().await.unwrap();
+    app.fan_out_pending_newsletters().await;
+
+    let issue_id = Uuid::parse_str(body["issue_id"].as_str().unwrap()).unwrap();
+    let body: Value = app
+        .get_newsletter_status(&issue_id)
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["segment_id"], segment_id);
+    body["enqueued"].as_i64().unwrap()
+}
+
+#[tokio::test]
+async fn created_segments_are_listed() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    let segment_id = create_segment(
+        &app,
+        &json!({ "name": "Active readers", "active_within_days": 30 }),
+    )
+    .await;
+
+    let response = app.get_newsletter_segments().await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["segments"][0]["id"], segment_id);
+    assert_eq!(body["segments"][0]["name"], "Active readers");
+    assert_eq!(body["segments"][0]["active_within_days"], 30);
+}
+
+#[tokio::test]
+async fn issues_targeted_at_a_segment_go_only_to_its_subscribers() {
+    let app = helpers::spawn_app().await;
+    app.create_active_subscriber().await;
+    app.login_admin().await;
+
+    // The subscriber just signed up and logged in
+    let active = create_segment(
+        &app,
+        &json!({ "name": "Active readers", "active_within_days": 7 }),
+    )
+    .await;
+    let veterans = create_segment(
+        &app,
+        &json!({ "name": "Veterans", "signed_up_before": Utc::now() - Duration::days(365) }),
+    )
+    .await;
+
+    assert_eq!(publish_to_segment(&app, &active).await, 1);
+    assert_eq!(publish_to_segment(&app, &veterans).await, 0);
+}
+
+#[tokio::test]
+async fn publishing_to_an_unknown_segment_is_rejected() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    let newsletter_body = json!({
+        "title": "Newsletter title",
+        "content": {
+            "text": "Newsletter body as plain text",
+            "html": "<p>Newsletter body as HTML</p>"
+        },
+        "segment_id": Uuid::new_v4()
+    });
+    let key = Uuid::new_v4().to_string();
+    let response = app.publish_newsletters(&newsletter_body, Some(&key)).await;
+
+    assert_eq!(response.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn segment_names_are_unique() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+    let payload = json!({ "name": "Active readers", "active_within_days": 30 });
+    create_segment(&app, &payload).await;
+
+    let response = app.create_newsletter_segment(&payload).await;
+
+    assert_eq!(response.status().as_u16(), 409);
+}
+
+#[tokio::test]
+async fn invalid_segments_are_rejected() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    for (payload, reason) in [
+        (json!({ "name": "Everyone" }), "no criteria"),
+        (
+            json!({ "name": "", "active_within_days": 30 }),
+            "empty name",
+        ),
+        (
+            json!({ "name": "Never", "active_within_days": 0 }),
+            "empty activity window",
+        ),
+    ] {
+        let response = app.create_newsletter_segment(&payload).await;
+        assert_eq!(response.status().as_u16(), 400, "{reason}");
+    }
+}
+
+#[tokio::test]
+async fn segments_require_authentication() {
+    let app = helpers::spawn_app().await;
+
+    let response = app.get_newsletter_segments().await;
+
+    assert_eq!(response.status().as_u16(), 401);
+}
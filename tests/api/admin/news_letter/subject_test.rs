@@ -0,0 +1,175 @@
+use serde_json::{Value, json};
+use techhub::domain::SubjectVariant;
+use uuid::Uuid;
+use wiremock::{Mock, ResponseTemplate, matchers};
+
+use crate::helpers::{self, TestApp};
+
+async fn insert_subscribers(app: &TestApp, count: usize) {
+    for i in 0..count {
+        sqlx::query!(
+            r#"
+            INSERT INTO users (id, user_name, password_hash, email, is_activated, is_subscribed)
+            VALUES ($1, $2, 'not-a-hash', $3, true, true)
+            "#,
+            Uuid::new_v4(),
+            format!("reader{i}"),
+            format!("reader{i}@example.com"),
+        )
+        .execute(&app.db_pool)
+        .await
+        .unwrap();
+    }
+}
+
+async fn publish(app: &TestApp, alternative_title: Option<&str>) -> Uuid {
+    let newsletter_body = json!({
+        "title": "Weekly update",
+        "alternative_title": alternative_title,
+        "content": {
+            "text": "Hello subscribers!",
+            "html": "<p>Hello subscribers!</p>"
+        }
+    });
+    let key = Uuid::new_v4().to_string();
+    let response = app.publish_newsletters(&newsletter_body, Some(&key)).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    Uuid::parse_str(body["issue_id"].as_str().unwrap()).unwrap()
+}
+
+async fn deliver(app: &TestApp) -> Vec<Value> {
+    Mock::given(matchers::path("/email"))
+        .and(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&app.email_server)
+        .await;
+
+    app.fan_out_pending_newsletters().await;
+    app.dispatch_all_pending_newsletter_emails().await;
+
+    app.email_server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .map(|request| serde_json::from_slice(&request.body).unwrap())
+        .collect()
+}
+
+fn tracking_pixel_path(email: &Value) -> String {
+    let html = email["HtmlBody"].as_str().unwrap();
+    let start = html.find("/t/open/").unwrap();
+    let end = start + html[start..].find('"').unwrap();
+    html[start + 1..end].to_string()
+}
+
+async fn status(app: &TestApp, issue_id: &Uuid) -> Value {
+    let response = app.get_newsletter_status(issue_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+    response.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn recipients_are_split_between_both_subject_lines() {
+    let app = helpers::spawn_app().await;
+    insert_subscribers(&app, 20).await;
+    app.login_admin().await;
+    let issue_id = publish(&app, Some("This week in Rust")).await;
+
+    let emails = deliver(&app).await;
+
+    assert_eq!(emails.len(), 20);
+    for email in &emails {
+        let expected = match SubjectVariant::for_recipient(issue_id, email["To"].as_str().unwrap())
+        {
+            SubjectVariant::A => "Weekly update",
+            SubjectVariant::B => "This week in Rust",
+        };
+        assert_eq!(email["Subject"], expected);
+    }
+
+    let body = status(&app, &issue_id).await;
+    let variants = body["subject_test"]["variants"].as_array().unwrap();
+    assert_eq!(variants[0]["variant"], "a");
+    assert_eq!(variants[0]["title"], "Weekly update");
+    assert_eq!(variants[1]["variant"], "b");
+    assert_eq!(variants[1]["title"], "This week in Rust");
+    let sent_b = emails
+        .iter()
+        .filter(|email| email["Subject"] == "This week in Rust")
+        .count();
+    assert_eq!(variants[0]["sent"], 20 - sent_b);
+    assert_eq!(variants[1]["sent"], sent_b);
+}
+
+#[tokio::test]
+async fn the_subject_line_opened_more_often_wins() {
+    let app = helpers::spawn_app().await;
+    insert_subscribers(&app, 20).await;
+    app.login_admin().await;
+    let issue_id = publish(&app, Some("This week in Rust")).await;
+    let emails = deliver(&app).await;
+
+    let body = status(&app, &issue_id).await;
+    assert_eq!(body["subject_test"]["winner"], Value::Null);
+
+    for email in emails
+        .iter()
+        .filter(|email| email["Subject"] == "This week in Rust")
+    {
+        let response = app.send_get(&tracking_pixel_path(email)).await;
+        assert_eq!(response.status().as_u16(), 200);
+    }
+
+    let body = status(&app, &issue_id).await;
+    let variants = body["subject_test"]["variants"].as_array().unwrap();
+    assert_eq!(variants[0]["opened"], 0);
+    assert_eq!(variants[1]["opened"], variants[1]["sent"]);
+    assert_eq!(variants[1]["open_rate"], 1.0);
+    assert_eq!(body["subject_test"]["winner"], "b");
+}
+
+#[tokio::test]
+async fn issues_without_an_alternative_title_are_not_tested() {
+    let app = helpers::spawn_app().await;
+    insert_subscribers(&app, 4).await;
+    app.login_admin().await;
+    let issue_id = publish(&app, None).await;
+
+    let emails = deliver(&app).await;
+
+    assert!(
+        emails
+            .iter()
+            .all(|email| email["Subject"] == "Weekly update")
+    );
+    let body = status(&app, &issue_id).await;
+    assert_eq!(body["subject_test"], Value::Null);
+}
+
+#[tokio::test]
+async fn alternative_title_must_be_valid_and_differ_from_the_title() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    for alternative_title in ["Weekly update", ""] {
+        let newsletter_body = json!({
+            "title": "Weekly update",
+            "alternative_title": alternative_title,
+            "content": {
+                "text": "Hello subscribers!",
+                "html": "<p>Hello subscribers!</p>"
+            }
+        });
+        let key = Uuid::new_v4().to_string();
+        let response = app.publish_newsletters(&newsletter_body, Some(&key)).await;
+
+        assert_eq!(
+            response.status().as_u16(),
+            400,
+            "alternative_title {alternative_title:?} was accepted"
+        );
+    }
+}
@@ -0,0 +1,83 @@
+use serde_json::{Value, json};
+use uuid::Uuid;
+use wiremock::{Mock, ResponseTemplate, matchers};
+
+use crate::helpers;
+
+#[tokio::test]
+async fn test_send_goes_only_to_the_requesting_admin() {
+    let app = helpers::spawn_app().await;
+    app.create_active_subscriber().await;
+    app.login_admin().await;
+    let response = app
+        .create_newsletter_draft(&json!({
+            "title": "Draft Newsletter",
+            "content": {
+                "text": "Hi {{user_name}}",
+                "html": "<p>Hi {{user_name}}</p>"
+            }
+        }))
+        .await;
+    assert_eq!(response.status().as_u16(), 201);
+    let body: Value = response.json().await.unwrap();
+    let issue_id = Uuid::parse_str(body["issue_id"].as_str().unwrap()).unwrap();
+
+    Mock::given(matchers::path("/email"))
+        .and(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&app.email_server)
+        .await;
+    let response = app.test_send_newsletter(&issue_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let admin_email: String =
+        sqlx::query_scalar!("SELECT email FROM users WHERE user_name = 'athfan'")
+            .fetch_one(&app.db_pool)
+            .await
+            .unwrap();
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["sent_to"], admin_email);
+
+    let email_requests = app.email_server.received_requests().await.unwrap();
+    let email: Value = serde_json::from_slice(&email_requests.last().unwrap().body).unwrap();
+    assert_eq!(email["To"], admin_email);
+    assert_eq!(email["Subject"], "[Test] Draft Newsletter");
+    assert!(
+        email["HtmlBody"]
+            .as_str()
+            .unwrap()
+            .starts_with("<p>Hi athfan</p>")
+    );
+
+    // Still a draft, with nothing queued for subscribers
+    app.fan_out_pending_newsletters().await;
+    let body: Value = app
+        .get_newsletter_status(&issue_id)
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["fan_out_status"], "draft");
+    assert_eq!(body["enqueued"], 0);
+    assert_eq!(body["sent"], 0);
+}
+
+#[tokio::test]
+async fn test_send_of_an_unknown_issue_is_not_found() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    let response = app.test_send_newsletter(&Uuid::new_v4()).await;
+
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn test_send_requires_authentication() {
+    let app = helpers::spawn_app().await;
+
+    let response = app.test_send_newsletter(&Uuid::new_v4()).await;
+
+    assert_eq!(response.status().as_u16(), 401);
+}
@@ -0,0 +1,174 @@
+use serde_json::Value;
+use techhub::domain::ANONYMOUS_USER_ID;
+use uuid::Uuid;
+
+use crate::helpers::{self, EMBED_ORIGIN};
+
+async fn submit_pending_comment(app: &helpers::TestApp, email: &str) -> (Uuid, Uuid) {
+    app.login().await;
+    let post_id = app.create_sample_post().await;
+    app.logout().await;
+
+    let payload = serde_json::json!({
+        "author_name": "blog-reader",
+        "author_email": email,
+        "text": "Great write-up!"
+    });
+    let response = app
+        .submit_embed_comment(&post_id, &payload, Some(EMBED_ORIGIN))
+        .await;
+    assert_eq!(response.status().as_u16(), 202);
+    let body: Value = response.json().await.unwrap();
+    let submission_id = Uuid::parse_str(body["id"].as_str().unwrap()).unwrap();
+
+    (post_id, submission_id)
+}
+
+#[tokio::test]
+async fn pending_submissions_are_listed_for_admins() {
+    let app = helpers::spawn_app().await;
+    let (post_id, submission_id) = submit_pending_comment(&app, "reader@example.com").await;
+    app.login_admin().await;
+
+    let response = app.get_comment_submissions().await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    let submissions = body["submissions"].as_array().unwrap();
+    assert_eq!(submissions.len(), 1);
+    assert_eq!(submissions[0]["id"], submission_id.to_string());
+    assert_eq!(submissions[0]["post_id"], post_id.to_string());
+    assert_eq!(submissions[0]["origin"], EMBED_ORIGIN);
+    assert_eq!(body["metadata"]["total_records"], 1);
+}
+
+#[tokio::test]
+async fn non_admins_cannot_review_submissions() {
+    let app = helpers::spawn_app().await;
+    let (_, submission_id) = submit_pending_comment(&app, "reader@example.com").await;
+    app.login().await;
+
+    let response = app.get_comment_submissions().await;
+    assert_eq!(response.status().as_u16(), 403);
+
+    let response = app.approve_comment_submission(&submission_id).await;
+    assert_eq!(response.status().as_u16(), 403);
+}
+
+#[tokio::test]
+async fn approving_a_submission_publishes_it_as_a_shell_user_comment() {
+    let app = helpers::spawn_app().await;
+    let (post_id, submission_id) = submit_pending_comment(&app, "Reader@Example.com").await;
+    app.login_admin().await;
+
+    let response = app.approve_comment_submission(&submission_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = app.get_comments(&post_id).await.json().await.unwrap();
+    let comments = body["comments"].as_array().unwrap();
+    assert_eq!(comments.len(), 1);
+    assert_eq!(comments[0]["text"], "Great write-up!");
+
+    let author =
+        sqlx::query!("SELECT email, is_activated FROM users WHERE user_name = 'blog-reader'")
+            .fetch_one(&app.db_pool)
+            .await
+            .unwrap();
+    assert_eq!(author.email, "reader@example.com");
+    assert!(!author.is_activated);
+
+    let body: Value = app.get_comment_submissions().await.json().await.unwrap();
+    assert!(body["submissions"].as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn approving_a_submission_from_a_known_email_does_not_attribute_it_to_that_account() {
+    let app = helpers::spawn_app().await;
+    let email = app.test_user.email.clone();
+    let (post_id, submission_id) = submit_pending_comment(&app, &email).await;
+    app.login_admin().await;
+
+    let response = app.approve_comment_submission(&submission_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = app.get_comments(&post_id).await.json().await.unwrap();
+    assert_eq!(
+        body["comments"][0]["created_by"],
+        ANONYMOUS_USER_ID.to_string()
+    );
+    assert_eq!(body["comments"][0]["author_display_name"], "blog-reader");
+
+    let shell_users = sqlx::query_scalar!(
+        "SELECT COUNT(*) AS \"count!\" FROM users WHERE user_name = 'blog-reader'"
+    )
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap();
+    assert_eq!(shell_users, 0);
+}
+
+#[tokio::test]
+async fn rejected_submissions_are_not_published() {
+    let app = helpers::spawn_app().await;
+    let (post_id, submission_id) = submit_pending_comment(&app, "reader@example.com").await;
+    app.login_admin().await;
+
+    let response = app.reject_comment_submission(&submission_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = app.get_comments(&post_id).await.json().await.unwrap();
+    assert!(body["comments"].as_array().unwrap().is_empty());
+
+    // Already reviewed, so it can no longer be approved
+    let response = app.approve_comment_submission(&submission_id).await;
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn reviewing_a_missing_submission_returns_404() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    let response = app.approve_comment_submission(&Uuid::new_v4()).await;
+    assert_eq!(response.status().as_u16(), 404);
+
+    let response = app.reject_comment_submission(&Uuid::new_v4()).await;
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn approving_an_anonymous_submission_keeps_the_display_name() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let post_id = app.create_sample_post().await;
+    app.logout().await;
+    app.mock_captcha(true).await;
+
+    let payload = serde_json::json!({
+        "post_id": post_id.to_string(),
+        "text": "Loved this, thanks!",
+        "author_display_name": "Passing reader",
+        "captcha_token": "captcha-token"
+    });
+    let body: Value = app
+        .create_anonymous_comment(&payload)
+        .await
+        .json()
+        .await
+        .unwrap();
+    let submission_id = Uuid::parse_str(body["id"].as_str().unwrap()).unwrap();
+
+    app.login_admin().await;
+    let body: Value = app.get_comment_submissions().await.json().await.unwrap();
+    assert_eq!(body["submissions"][0]["source"], "anonymous");
+    assert!(body["submissions"][0]["author_email"].is_null());
+
+    let response = app.approve_comment_submission(&submission_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = app.get_comments(&post_id).await.json().await.unwrap();
+    let comment = &body["comments"][0];
+    assert_eq!(comment["text"], "Loved this, thanks!");
+    assert_eq!(comment["author_display_name"], "Passing reader");
+    assert_eq!(comment["created_by"], ANONYMOUS_USER_ID.to_string());
+}
@@ -0,0 +1,149 @@
+use serde_json::{Value, json};
+use wiremock::{Mock, Request, Respond, ResponseTemplate, matchers};
+
+use crate::helpers;
+
+// Accepts every message of a Postmark batch
+struct BatchResponder;
+
+impl Respond for BatchResponder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let messages: Vec<Value> = serde_json::from_slice(&request.body).unwrap();
+        let results: Vec<Value> = messages
+            .iter()
+            .map(|_| json!({ "ErrorCode": 0, "Message": "OK" }))
+            .collect();
+        ResponseTemplate::new(200).set_body_json(results)
+    }
+}
+
+async fn subscription_state(app: &helpers::TestApp, email: &str) -> (bool, bool) {
+    let row = sqlx::query!(
+        "SELECT is_activated, is_subscribed FROM users WHERE email = $1",
+        email
+    )
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap();
+    (row.is_activated, row.is_subscribed)
+}
+
+#[tokio::test]
+async fn import_subscribes_new_addresses_and_reports_every_row() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+    Mock::given(matchers::any())
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&app.email_server)
+        .await;
+
+    let csv = format!(
+        "email,name\nJane@Example.com,Jane\nnot-an-email,Nobody\n\n{},Existing\n",
+        app.test_user.email
+    );
+    let response = app.import_subscribers(&csv, "").await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["double_opt_in"], false);
+    assert_eq!(body["subscribed"], 1);
+    assert_eq!(body["existing"], 1);
+    assert_eq!(body["invalid"], 1);
+
+    let rows = body["rows"].as_array().unwrap();
+    assert_eq!(rows[0]["row"], 2);
+    assert_eq!(rows[0]["email"], "jane@example.com");
+    assert_eq!(rows[0]["status"], "subscribed");
+    assert_eq!(rows[1]["row"], 3);
+    assert_eq!(rows[1]["status"], "invalid");
+    assert!(rows[1]["error"].is_string());
+    assert_eq!(rows[2]["row"], 5);
+    assert_eq!(rows[2]["status"], "existing");
+
+    assert_eq!(
+        subscription_state(&app, "jane@example.com").await,
+        (true, true)
+    );
+    // Importing an existing account's address leaves its subscription as it is
+    assert!(!subscription_state(&app, &app.test_user.email).await.1);
+}
+
+#[tokio::test]
+async fn double_opt_in_import_subscribes_addresses_once_confirmed() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+    Mock::given(matchers::path("/email/batch"))
+        .and(matchers::method("POST"))
+        .respond_with(BatchResponder)
+        .expect(1)
+        .mount(&app.email_server)
+        .await;
+
+    let response = app
+        .import_subscribers(
+            "jane@example.com\njohn@example.com\n",
+            "?double_opt_in=true",
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["pending_confirmation"], 2);
+    assert_eq!(body["rows"][0]["status"], "pending_confirmation");
+    assert!(body["rows"][0].get("error").is_none());
+    assert_eq!(
+        subscription_state(&app, "jane@example.com").await,
+        (true, false)
+    );
+
+    let token = sqlx::query_scalar!(
+        "SELECT t.token FROM tokens t JOIN users u ON u.id = t.user_id WHERE u.email = $1",
+        "jane@example.com"
+    )
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap();
+    let response = app
+        .send_get(&format!("v1/user/subscribe?token={token}"))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    assert_eq!(
+        subscription_state(&app, "jane@example.com").await,
+        (true, true)
+    );
+    assert_eq!(
+        subscription_state(&app, "john@example.com").await,
+        (true, false)
+    );
+}
+
+#[tokio::test]
+async fn rows_whose_confirmation_could_not_be_sent_are_reported() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+    Mock::given(matchers::path("/email/batch"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&app.email_server)
+        .await;
+
+    let body: Value = app
+        .import_subscribers("jane@example.com\n", "?double_opt_in=true")
+        .await
+        .json()
+        .await
+        .unwrap();
+
+    assert_eq!(body["rows"][0]["status"], "pending_confirmation");
+    assert!(body["rows"][0]["error"].is_string());
+}
+
+#[tokio::test]
+async fn import_without_emails_is_rejected() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    let response = app.import_subscribers("email\n\n", "").await;
+    assert_eq!(response.status().as_u16(), 400);
+}
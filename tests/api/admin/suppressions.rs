@@ -0,0 +1,90 @@
+use serde_json::Value;
+use wiremock::{Mock, ResponseTemplate, matchers};
+
+use crate::helpers;
+
+#[tokio::test]
+async fn manually_suppressed_addresses_are_listed_and_not_sent_account_emails() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+    Mock::given(matchers::path("/email"))
+        .and(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&app.email_server)
+        .await;
+
+    let response = app
+        .suppress_email(&app.test_user.email.to_uppercase())
+        .await;
+    assert_eq!(response.status().as_u16(), 201);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["email"], app.test_user.email.to_lowercase());
+    assert_eq!(body["reason"], "manual");
+
+    let body: Value = app.get_suppressed_emails("").await.json().await.unwrap();
+    let emails = body["suppressed_emails"].as_array().unwrap();
+    assert_eq!(emails.len(), 1);
+    assert_eq!(emails[0]["email"], app.test_user.email.to_lowercase());
+    assert_eq!(emails[0]["reason"], "manual");
+    assert_eq!(body["metadata"]["total_records"], 1);
+
+    // Reported as sent all the same
+    let response = app.forgot_password(&app.test_user.email).await;
+    app.send_account_emails().await;
+    assert_eq!(response.status().as_u16(), 202);
+}
+
+#[tokio::test]
+async fn removed_addresses_are_sent_emails_again() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+    Mock::given(matchers::path("/email"))
+        .and(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&app.email_server)
+        .await;
+
+    let response = app.suppress_email(&app.test_user.email).await;
+    assert_eq!(response.status().as_u16(), 201);
+    let response = app.unsuppress_email(&app.test_user.email).await;
+    assert_eq!(response.status().as_u16(), 204);
+
+    let body: Value = app.get_suppressed_emails("").await.json().await.unwrap();
+    assert!(body["suppressed_emails"].as_array().unwrap().is_empty());
+
+    let response = app.forgot_password(&app.test_user.email).await;
+    app.send_account_emails().await;
+    assert_eq!(response.status().as_u16(), 202);
+}
+
+#[tokio::test]
+async fn suppressing_an_address_twice_is_rejected() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    let response = app.suppress_email("jane@example.com").await;
+    assert_eq!(response.status().as_u16(), 201);
+
+    let response = app.suppress_email("Jane@Example.com").await;
+    assert_eq!(response.status().as_u16(), 409);
+}
+
+#[tokio::test]
+async fn invalid_addresses_are_rejected() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    let response = app.suppress_email("not-an-email").await;
+    assert_eq!(response.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn removing_an_address_that_is_not_suppressed_returns_404() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    let response = app.unsuppress_email("jane@example.com").await;
+    assert_eq!(response.status().as_u16(), 404);
+}
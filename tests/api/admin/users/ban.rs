@@ -0,0 +1,390 @@
+use chrono::{Duration, Utc};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::helpers;
+
+// Creates a user who authored a post and a comment and liked the test user's post,
+// returning their id and login payload along with the test user's post id
+async fn create_user_with_content(app: &helpers::TestApp) -> (Uuid, Value, Uuid) {
+    app.login().await;
+    let liked_post_id = app.create_sample_post().await;
+    app.logout().await;
+
+    let payload = app.create_activated_user().await;
+    let response = app.login_with(&payload).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let own_post_id = app.create_sample_post().await;
+    let response = app
+        .create_comment(&serde_json::json!({
+            "text": "A comment to be removed",
+            "post_id": own_post_id.to_string()
+        }))
+        .await;
+    assert_eq!(response.status().as_u16(), 201);
+    let response = app.like_post(&liked_post_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+    app.logout().await;
+
+    let user_id = sqlx::query_scalar!(
+        "SELECT id FROM users WHERE user_name = $1",
+        payload["user_name"].as_str().unwrap()
+    )
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap();
+
+    (user_id, payload, liked_post_id)
+}
+
+#[tokio::test]
+async fn ban_with_remove_content_removes_posts_comments_and_likes() {
+    let app = helpers::spawn_app().await;
+    let (user_id, _, liked_post_id) = create_user_with_content(&app).await;
+    app.login_admin().await;
+
+    let response = app
+        .ban_user(&user_id, &serde_json::json!({ "remove_content": true }))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["posts_removed"], 1);
+    assert_eq!(body["comments_removed"], 1);
+    assert_eq!(body["likes_withdrawn"], 1);
+
+    let remaining = sqlx::query!(
+        r#"
+        SELECT
+            (SELECT COUNT(*) FROM posts WHERE created_by = $1 AND deleted_at IS NULL) AS "posts!",
+            (SELECT COUNT(*) FROM comments WHERE created_by = $1 AND deleted_at IS NULL) AS "comments!",
+            (SELECT COUNT(*) FROM posts WHERE $1 = ANY(liked_by)) AS "likes!"
+        "#,
+        user_id
+    )
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap();
+    assert_eq!(remaining.posts, 0);
+    assert_eq!(remaining.comments, 0);
+    assert_eq!(remaining.likes, 0);
+
+    let response = app.get_post(&liked_post_id).await;
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["posts"]["liked_by"], serde_json::json!([]));
+}
+
+#[tokio::test]
+async fn ban_with_remove_content_removes_content_spanning_several_chunks() {
+    let app = helpers::spawn_app().await;
+    let (user_id, _, _) = create_user_with_content(&app).await;
+    sqlx::query!(
+        r#"
+        WITH posts AS (
+            INSERT INTO posts (id, title, post_text, img, created_by)
+            SELECT gen_random_uuid(), 'Bulk post', 'Bulk body', 'img.png', $1
+            FROM generate_series(1, 1200)
+            RETURNING id
+        )
+        INSERT INTO comments (id, text, post_id, created_by)
+        SELECT gen_random_uuid(), 'Bulk comment', id, $1 FROM posts
+        "#,
+        user_id
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+    app.login_admin().await;
+
+    let response = app
+        .ban_user(&user_id, &serde_json::json!({ "remove_content": true }))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["posts_removed"], 1201);
+    assert_eq!(body["comments_removed"], 1201);
+}
+
+#[tokio::test]
+async fn ban_user_returns_404_for_deleted_accounts() {
+    let app = helpers::spawn_app().await;
+    let (user_id, payload, _) = create_user_with_content(&app).await;
+    app.login_with(&payload).await;
+    let response = app
+        .delete_account(payload["password"].as_str().unwrap())
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    app.login_admin().await;
+
+    let response = app.ban_user(&user_id, &serde_json::json!({})).await;
+
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn ban_without_remove_content_keeps_content() {
+    let app = helpers::spawn_app().await;
+    let (user_id, _, _) = create_user_with_content(&app).await;
+    app.login_admin().await;
+
+    let response = app.ban_user(&user_id, &serde_json::json!({})).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["posts_removed"], 0);
+
+    let posts = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!" FROM posts WHERE created_by = $1 AND deleted_at IS NULL"#,
+        user_id
+    )
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap();
+    assert_eq!(posts, 1);
+}
+
+#[tokio::test]
+async fn banned_user_cannot_log_in() {
+    let app = helpers::spawn_app().await;
+    let (user_id, payload, _) = create_user_with_content(&app).await;
+    app.login_admin().await;
+
+    let response = app
+        .ban_user(&user_id, &serde_json::json!({ "reason": "Spamming links" }))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    app.logout().await;
+
+    let response = app.login_with(&payload).await;
+    assert_eq!(response.status().as_u16(), 403);
+
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["error"], "account_suspended");
+    assert_eq!(body["message"], "Your account is suspended: Spamming links");
+}
+
+#[tokio::test]
+async fn banned_user_with_wrong_password_is_not_told_about_the_ban() {
+    let app = helpers::spawn_app().await;
+    let (user_id, mut payload, _) = create_user_with_content(&app).await;
+    app.login_admin().await;
+
+    let response = app.ban_user(&user_id, &serde_json::json!({})).await;
+    assert_eq!(response.status().as_u16(), 200);
+    app.logout().await;
+
+    payload["password"] = Value::from("wrong-password");
+    let response = app.login_with(&payload).await;
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn banned_user_tokens_are_rejected_until_the_ban_is_lifted() {
+    let app = helpers::spawn_app().await;
+    let (user_id, payload, _) = create_user_with_content(&app).await;
+    let response = app.issue_access_token(&payload).await;
+    let body: Value = response.json().await.unwrap();
+    let token = body["access_token"].as_str().unwrap().to_string();
+    app.login_admin().await;
+
+    let expires_at = Utc::now() + Duration::days(3);
+    let response = app
+        .ban_user(
+            &user_id,
+            &serde_json::json!({ "reason": "Cooling off", "expires_at": expires_at }),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let response = app.access_protected_with_token(&token).await;
+    assert_eq!(response.status().as_u16(), 403);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["error"], "account_suspended");
+    assert!(
+        body["message"]
+            .as_str()
+            .unwrap()
+            .ends_with("UTC: Cooling off")
+    );
+
+    let response = app.unban_user(&user_id).await;
+    assert_eq!(response.status().as_u16(), 204);
+
+    let response = app.access_protected_with_token(&token).await;
+    assert_eq!(response.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn banned_user_can_log_in_once_the_ban_expires() {
+    let app = helpers::spawn_app().await;
+    let (user_id, payload, _) = create_user_with_content(&app).await;
+    app.login_admin().await;
+
+    let expires_at = Utc::now() + Duration::hours(1);
+    let response = app
+        .ban_user(&user_id, &serde_json::json!({ "expires_at": expires_at }))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    app.logout().await;
+
+    sqlx::query!(
+        "UPDATE users SET banned_until = NOW() - INTERVAL '1 minute' WHERE id = $1",
+        user_id
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+
+    let response = app.login_with(&payload).await;
+    assert_eq!(response.status().as_u16(), 200);
+}
+
+// Publishes an issue to every subscriber and returns how many deliveries it enqueued
+async fn publish_to_subscribers(app: &helpers::TestApp) -> Value {
+    let response = app
+        .publish_newsletters(
+            &serde_json::json!({
+                "title": "Weekly",
+                "content": { "text": "Hello", "html": "<p>Hello</p>" }
+            }),
+            Some(&Uuid::new_v4().to_string()),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body: Value = response.json().await.unwrap();
+    app.fan_out_pending_newsletters().await;
+
+    let issue_id: Uuid = body["issue_id"].as_str().unwrap().parse().unwrap();
+    let body: Value = app
+        .get_newsletter_status(&issue_id)
+        .await
+        .json()
+        .await
+        .unwrap();
+    body["enqueued"].clone()
+}
+
+#[tokio::test]
+async fn suspended_subscribers_get_issues_again_once_the_ban_expires() {
+    let app = helpers::spawn_app().await;
+    sqlx::query!(
+        "UPDATE users SET is_subscribed = true WHERE id = $1",
+        app.test_user.user_id
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+    app.login_admin().await;
+
+    let expires_at = Utc::now() + Duration::hours(1);
+    let response = app
+        .ban_user(
+            &app.test_user.user_id,
+            &serde_json::json!({ "expires_at": expires_at }),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(publish_to_subscribers(&app).await, 0);
+
+    sqlx::query!(
+        "UPDATE users SET banned_until = NOW() - INTERVAL '1 minute' WHERE id = $1",
+        app.test_user.user_id
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+    assert_eq!(publish_to_subscribers(&app).await, 1);
+}
+
+#[tokio::test]
+async fn ban_user_returns_400_for_past_expiry() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    let expires_at = Utc::now() - Duration::hours(1);
+    let response = app
+        .ban_user(
+            &app.test_user.user_id,
+            &serde_json::json!({ "expires_at": expires_at }),
+        )
+        .await;
+
+    assert_eq!(response.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn ban_user_returns_400_for_content_removal_with_an_expiry() {
+    let app = helpers::spawn_app().await;
+    let (user_id, payload, _) = create_user_with_content(&app).await;
+    app.login_admin().await;
+
+    let expires_at = Utc::now() + Duration::hours(1);
+    let response = app
+        .ban_user(
+            &user_id,
+            &serde_json::json!({ "remove_content": true, "expires_at": expires_at }),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 400);
+
+    // Neither banned nor stripped of their content
+    app.logout().await;
+    assert_eq!(app.login_with(&payload).await.status().as_u16(), 200);
+    let posts = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!" FROM posts WHERE created_by = $1 AND deleted_at IS NULL"#,
+        user_id
+    )
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap();
+    assert_eq!(posts, 1);
+}
+
+#[tokio::test]
+async fn unban_user_returns_404_for_unknown_user() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    let response = app.unban_user(&Uuid::new_v4()).await;
+
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn ban_user_returns_404_for_unknown_user() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    let response = app.ban_user(&Uuid::new_v4(), &serde_json::json!({})).await;
+
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn ban_user_returns_403_for_admins() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    let admin_id = sqlx::query_scalar!("SELECT id FROM users WHERE role = 'admin' LIMIT 1")
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+
+    let response = app.ban_user(&admin_id, &serde_json::json!({})).await;
+
+    assert_eq!(response.status().as_u16(), 403);
+}
+
+#[tokio::test]
+async fn ban_user_returns_403_for_non_admins() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let response = app
+        .ban_user(&app.test_user.user_id, &serde_json::json!({}))
+        .await;
+
+    assert_eq!(response.status().as_u16(), 403);
+}
@@ -0,0 +1,137 @@
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::helpers;
+
+async fn admin_id(app: &helpers::TestApp) -> Uuid {
+    sqlx::query_scalar!("SELECT id FROM users WHERE user_name = 'athfan'")
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap()
+}
+
+async fn current_user_name(app: &helpers::TestApp) -> String {
+    let response = app.export_own_data().await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    body["account"]["user_name"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn admins_act_as_the_user_until_they_end_the_impersonation() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+    let admin_id = admin_id(&app).await;
+
+    let response = app.start_impersonation(&app.test_user.user_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["role"], "user");
+    assert_eq!(body["impersonated_by"], admin_id.to_string());
+
+    assert_eq!(current_user_name(&app).await, app.test_user.user_name);
+    let response = app.access_protected().await;
+    assert_eq!(
+        response.headers()["X-Impersonated-By"],
+        admin_id.to_string().as_str()
+    );
+    // The admin has the user's permissions, not their own
+    let response = app.get_audit_log("").await;
+    assert_eq!(response.status().as_u16(), 403);
+    assert!(response.headers().contains_key("X-Impersonated-By"));
+
+    let response = app.end_impersonation().await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    assert_eq!(current_user_name(&app).await, "athfan");
+    let response = app.get_audit_log("?action=impersonation_start").await;
+    assert_eq!(response.status().as_u16(), 200);
+    assert!(!response.headers().contains_key("X-Impersonated-By"));
+}
+
+#[tokio::test]
+async fn impersonations_are_recorded_in_the_audit_log() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+    app.start_impersonation(&app.test_user.user_id).await;
+    app.end_impersonation().await;
+
+    let response = app
+        .get_audit_log(&format!("?target_id={}", app.test_user.user_id))
+        .await;
+    let body: Value = response.json().await.unwrap();
+    let events = body["events"].as_array().unwrap();
+
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0]["action"], "impersonation_end");
+    assert_eq!(events[1]["action"], "impersonation_start");
+    for event in events {
+        assert_eq!(event["actor_id"], admin_id(&app).await.to_string());
+        assert_eq!(event["metadata"]["ip_address"], "127.0.0.1");
+    }
+}
+
+#[tokio::test]
+async fn sudo_mode_does_not_carry_over_to_the_impersonated_user() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+    app.enter_sudo_mode(&json!({ "password": "athfan123" }))
+        .await;
+    app.start_impersonation(&app.test_user.user_id).await;
+
+    let response = app
+        .change_password(&json!({
+            "current_password": &app.test_user.password,
+            "new_password": "a-brand-new-password",
+        }))
+        .await;
+
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn only_admins_may_impersonate() {
+    let app = helpers::spawn_app().await;
+    let other_user = app.create_activated_user().await;
+    let other_user_id = app.user_id_of(&other_user).await;
+    app.login().await;
+
+    let response = app.start_impersonation(&other_user_id).await;
+
+    assert_eq!(response.status().as_u16(), 403);
+}
+
+#[tokio::test]
+async fn admins_cannot_be_impersonated() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+    app.set_user_role(&app.test_user.user_id, &json!({ "role": "admin" }))
+        .await;
+
+    let response = app.start_impersonation(&app.test_user.user_id).await;
+    assert_eq!(response.status().as_u16(), 403);
+
+    let response = app.start_impersonation(&admin_id(&app).await).await;
+    assert_eq!(response.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn impersonating_an_unknown_user_returns_404() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    let response = app.start_impersonation(&Uuid::new_v4()).await;
+
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn ending_without_an_impersonation_returns_400() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let response = app.end_impersonation().await;
+
+    assert_eq!(response.status().as_u16(), 400);
+}
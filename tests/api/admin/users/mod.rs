@@ -0,0 +1,3 @@
+mod ban;
+mod impersonation;
+mod role;
@@ -0,0 +1,136 @@
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::helpers;
+
+async fn make_moderator(app: &helpers::TestApp) {
+    app.login_admin().await;
+    let response = app
+        .set_user_role(
+            &app.test_user.user_id,
+            &serde_json::json!({ "role": "moderator" }),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    app.logout().await;
+}
+
+#[tokio::test]
+async fn admin_can_change_a_users_role() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    let response = app
+        .set_user_role(
+            &app.test_user.user_id,
+            &serde_json::json!({ "role": "Moderator" }),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["role"], "moderator");
+}
+
+#[tokio::test]
+async fn changing_a_role_logs_the_user_out_everywhere() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    app.logout().await;
+
+    make_moderator(&app).await;
+
+    let active = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!" FROM user_sessions WHERE user_id = $1 AND revoked_at IS NULL"#,
+        app.test_user.user_id
+    )
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap();
+    assert_eq!(active, 0);
+}
+
+#[tokio::test]
+async fn moderators_can_moderate_comments() {
+    let app = helpers::spawn_app().await;
+    make_moderator(&app).await;
+    app.login().await;
+
+    let response = app.get_moderation_comments("").await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let response = app.get_comment_reports().await;
+    assert_eq!(response.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn moderators_cannot_manage_users_or_the_service() {
+    let app = helpers::spawn_app().await;
+    make_moderator(&app).await;
+    app.login().await;
+
+    let response = app.ban_user(&Uuid::new_v4(), &serde_json::json!({})).await;
+    assert_eq!(response.status().as_u16(), 403);
+
+    let response = app.get_workers().await;
+    assert_eq!(response.status().as_u16(), 403);
+}
+
+#[tokio::test]
+async fn set_role_returns_400_for_unknown_roles() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    let response = app
+        .set_user_role(
+            &app.test_user.user_id,
+            &serde_json::json!({ "role": "owner" }),
+        )
+        .await;
+
+    assert_eq!(response.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn set_role_returns_404_for_unknown_user() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    let response = app
+        .set_user_role(&Uuid::new_v4(), &serde_json::json!({ "role": "user" }))
+        .await;
+
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn admins_cannot_change_their_own_role() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    let admin_id = sqlx::query_scalar!("SELECT id FROM users WHERE role = 'admin' LIMIT 1")
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+
+    let response = app
+        .set_user_role(&admin_id, &serde_json::json!({ "role": "user" }))
+        .await;
+
+    assert_eq!(response.status().as_u16(), 403);
+}
+
+#[tokio::test]
+async fn set_role_returns_403_for_non_admins() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let response = app
+        .set_user_role(
+            &app.test_user.user_id,
+            &serde_json::json!({ "role": "admin" }),
+        )
+        .await;
+
+    assert_eq!(response.status().as_u16(), 403);
+}
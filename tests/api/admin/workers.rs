@@ -0,0 +1,75 @@
+use chrono::Utc;
+use serde_json::Value;
+use techhub::repository;
+use uuid::Uuid;
+
+use crate::helpers;
+
+#[tokio::test]
+async fn workers_are_listed_with_liveness() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    let live_worker = Uuid::new_v4();
+    repository::record_worker_heartbeat(&app.db_pool, live_worker, "worker-a", Utc::now())
+        .await
+        .unwrap();
+
+    let dead_worker = Uuid::new_v4();
+    sqlx::query!(
+        r#"
+        INSERT INTO worker_heartbeats (worker_id, hostname, started_at, last_seen_at)
+        VALUES ($1, 'worker-b', NOW() - INTERVAL '1 hour', NOW() - INTERVAL '10 minutes')
+        "#,
+        dead_worker
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+
+    let response = app.get_workers().await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    let workers = body["workers"].as_array().unwrap();
+    assert_eq!(workers.len(), 2);
+
+    let find = |id: Uuid| {
+        workers
+            .iter()
+            .find(|w| w["worker_id"] == id.to_string())
+            .unwrap()
+    };
+    assert_eq!(find(live_worker)["is_alive"], true);
+    assert_eq!(find(live_worker)["hostname"], "worker-a");
+    assert_eq!(find(dead_worker)["is_alive"], false);
+}
+
+#[tokio::test]
+async fn repeated_heartbeats_update_the_same_worker() {
+    let app = helpers::spawn_app().await;
+    let worker_id = Uuid::new_v4();
+    let started_at = Utc::now();
+
+    for _ in 0..3 {
+        repository::record_worker_heartbeat(&app.db_pool, worker_id, "worker-a", started_at)
+            .await
+            .unwrap();
+    }
+
+    let workers =
+        repository::get_worker_heartbeats(&app.db_pool, std::time::Duration::from_secs(90))
+            .await
+            .unwrap();
+    assert_eq!(workers.len(), 1);
+    assert_eq!(workers[0].worker_id, worker_id);
+}
+
+#[tokio::test]
+async fn workers_endpoint_returns_403_for_non_admins() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let response = app.get_workers().await;
+    assert_eq!(response.status().as_u16(), 403);
+}
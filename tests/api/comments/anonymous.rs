@@ -0,0 +1,132 @@
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::helpers;
+
+fn anonymous_comment(post_id: &Uuid) -> Value {
+    serde_json::json!({
+        "post_id": post_id.to_string(),
+        "text": "Loved this, thanks!",
+        "author_display_name": "Passing reader",
+        "captcha_token": "captcha-token"
+    })
+}
+
+async fn pending_submissions(app: &helpers::TestApp) -> i64 {
+    sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!" FROM comment_submissions WHERE status = 'pending'"#
+    )
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn anonymous_comment_is_held_for_moderation() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let post_id = app.create_sample_post().await;
+    app.logout().await;
+    app.mock_captcha(true).await;
+
+    let response = app
+        .create_anonymous_comment(&anonymous_comment(&post_id))
+        .await;
+    assert_eq!(response.status().as_u16(), 202);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["status"], "pending");
+
+    let body: Value = app.get_comments(&post_id).await.json().await.unwrap();
+    assert!(body["comments"].as_array().unwrap().is_empty());
+    assert_eq!(pending_submissions(&app).await, 1);
+}
+
+#[tokio::test]
+async fn anonymous_comment_with_a_rejected_captcha_returns_403() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let post_id = app.create_sample_post().await;
+    app.mock_captcha(false).await;
+
+    let response = app
+        .create_anonymous_comment(&anonymous_comment(&post_id))
+        .await;
+
+    assert_eq!(response.status().as_u16(), 403);
+    assert_eq!(pending_submissions(&app).await, 0);
+}
+
+#[tokio::test]
+async fn anonymous_comment_returns_403_when_disabled() {
+    let app = helpers::spawn_app_with(|c| c.anonymous_comments.enabled = false).await;
+    app.login().await;
+    let post_id = app.create_sample_post().await;
+    app.mock_captcha(true).await;
+
+    let response = app
+        .create_anonymous_comment(&anonymous_comment(&post_id))
+        .await;
+
+    assert_eq!(response.status().as_u16(), 403);
+}
+
+#[tokio::test]
+async fn anonymous_comment_returns_400_for_invalid_data() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let post_id = app.create_sample_post().await;
+    app.mock_captcha(true).await;
+
+    let mut invalid_name = anonymous_comment(&post_id);
+    invalid_name["author_display_name"] = "<script>".into();
+    let mut empty_text = anonymous_comment(&post_id);
+    empty_text["text"] = "".into();
+    let mut missing_token = anonymous_comment(&post_id);
+    missing_token
+        .as_object_mut()
+        .unwrap()
+        .remove("captcha_token");
+
+    for (payload, description) in [
+        (invalid_name, "invalid display name"),
+        (empty_text, "empty text"),
+        (missing_token, "missing captcha token"),
+    ] {
+        let response = app.create_anonymous_comment(&payload).await;
+        assert_eq!(
+            response.status().as_u16(),
+            400,
+            "Did not return 400 for {description}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn anonymous_comment_for_a_missing_post_returns_404() {
+    let app = helpers::spawn_app().await;
+    app.mock_captcha(true).await;
+
+    let response = app
+        .create_anonymous_comment(&anonymous_comment(&Uuid::new_v4()))
+        .await;
+
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn anonymous_comments_are_rate_limited_per_address() {
+    let app = helpers::spawn_app_with(|c| c.anonymous_comments.max_submissions_per_hour = 1).await;
+    app.login().await;
+    let post_id = app.create_sample_post().await;
+    app.mock_captcha(true).await;
+
+    let response = app
+        .create_anonymous_comment(&anonymous_comment(&post_id))
+        .await;
+    assert_eq!(response.status().as_u16(), 202);
+
+    let response = app
+        .create_anonymous_comment(&anonymous_comment(&post_id))
+        .await;
+    assert_eq!(response.status().as_u16(), 429);
+}
@@ -126,6 +126,7 @@ async fn get_comments_returns_200_with_comment_list() {
     let comments = body["comments"].as_array().unwrap();
     assert_eq!(comments.len(), 3);
     assert!(comments[0]["text"].is_string());
+    assert_eq!(comments[0]["created_by_name"], app.test_user.user_name);
 }
 
 #[tokio::test]
@@ -143,6 +144,98 @@ async fn get_comments_returns_empty_array_for_post_with_no_comments() {
     assert!(body["comments"].as_array().unwrap().is_empty());
 }
 
+#[tokio::test]
+async fn get_comments_paginates_newest_first_with_metadata() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let post_id = app.create_sample_post().await;
+
+    for i in 0..5 {
+        let payload = serde_json::json!({
+            "text": format!("Comment {}", i),
+            "post_id": post_id.to_string()
+        });
+        let resp = app.create_comment(&payload).await;
+        assert_eq!(resp.status().as_u16(), 201);
+    }
+
+    let response = app.get_comments_with_query(&post_id, "?limit=2").await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body: Value = response.json().await.unwrap();
+    let comments = body["comments"].as_array().unwrap();
+    assert_eq!(comments.len(), 2);
+    assert_eq!(comments[0]["text"], "Comment 4");
+    assert_eq!(body["metadata"]["total_records"], 5);
+    assert_eq!(body["metadata"]["last_page"], 3);
+
+    let response = app
+        .get_comments_with_query(&post_id, "?limit=2&page=3")
+        .await;
+    let body: Value = response.json().await.unwrap();
+    let comments = body["comments"].as_array().unwrap();
+    assert_eq!(comments.len(), 1);
+    assert_eq!(comments[0]["text"], "Comment 0");
+    assert_eq!(body["metadata"]["current_page"], 3);
+}
+
+#[tokio::test]
+async fn get_comments_can_be_sorted_oldest_first() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let post_id = app.create_sample_post().await;
+
+    for i in 0..3 {
+        let payload = serde_json::json!({
+            "text": format!("Comment {}", i),
+            "post_id": post_id.to_string()
+        });
+        app.create_comment(&payload).await;
+    }
+
+    let response = app.get_comments_with_query(&post_id, "?sort=oldest").await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body: Value = response.json().await.unwrap();
+    let texts: Vec<_> = body["comments"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["text"].as_str().unwrap())
+        .collect();
+    assert_eq!(texts, ["Comment 0", "Comment 1", "Comment 2"]);
+
+    let response = app.get_comments_with_query(&post_id, "?sort=newest").await;
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["comments"][0]["text"], "Comment 2");
+}
+
+#[tokio::test]
+async fn get_comments_returns_400_for_invalid_sort() {
+    let app = helpers::spawn_app().await;
+    let post_id = Uuid::new_v4();
+
+    let response = app.get_comments_with_query(&post_id, "?sort=-id").await;
+    assert_eq!(response.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn get_comments_returns_400_for_invalid_pagination() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let post_id = app.create_sample_post().await;
+
+    for query in ["?page=0", "?limit=0", "?limit=101"] {
+        let response = app.get_comments_with_query(&post_id, query).await;
+        assert_eq!(
+            response.status().as_u16(),
+            400,
+            "Expected 400 for query {query}"
+        );
+    }
+}
+
 // ============================================================================
 // Delete Comment
 // ============================================================================
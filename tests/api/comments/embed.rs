@@ -0,0 +1,147 @@
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::helpers::{self, EMBED_ORIGIN};
+
+fn embed_comment() -> Value {
+    serde_json::json!({
+        "author_name": "blog-reader",
+        "author_email": "reader@example.com",
+        "text": "Great write-up!"
+    })
+}
+
+#[tokio::test]
+async fn embed_comments_are_readable_from_an_allowed_origin() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let post_id = app.create_sample_post().await;
+    app.create_comment(&serde_json::json!({
+        "text": "Visible everywhere",
+        "post_id": post_id.to_string()
+    }))
+    .await;
+
+    let response = app.get_embed_comments(&post_id, EMBED_ORIGIN).await;
+
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(
+        response.headers()["access-control-allow-origin"],
+        EMBED_ORIGIN
+    );
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["comments"][0]["text"], "Visible everywhere");
+}
+
+#[tokio::test]
+async fn embed_comments_reject_origins_outside_the_allow_list() {
+    let app = helpers::spawn_app().await;
+    let post_id = Uuid::new_v4();
+
+    let response = app
+        .get_embed_comments(&post_id, "https://evil.example.com")
+        .await;
+
+    assert_eq!(response.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn embed_submission_is_held_for_moderation() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let post_id = app.create_sample_post().await;
+
+    let response = app
+        .submit_embed_comment(&post_id, &embed_comment(), Some(EMBED_ORIGIN))
+        .await;
+    assert_eq!(response.status().as_u16(), 202);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["status"], "pending");
+
+    let body: Value = app.get_comments(&post_id).await.json().await.unwrap();
+    assert!(body["comments"].as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn embed_submission_without_an_origin_returns_403() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let post_id = app.create_sample_post().await;
+
+    let response = app
+        .submit_embed_comment(&post_id, &embed_comment(), None)
+        .await;
+
+    assert_eq!(response.status().as_u16(), 403);
+}
+
+#[tokio::test]
+async fn embed_submission_returns_403_when_anonymous_posting_is_disabled() {
+    let app = helpers::spawn_app_with(|c| c.embed.allow_anonymous_posting = false).await;
+    app.login().await;
+    let post_id = app.create_sample_post().await;
+
+    let response = app
+        .submit_embed_comment(&post_id, &embed_comment(), Some(EMBED_ORIGIN))
+        .await;
+
+    assert_eq!(response.status().as_u16(), 403);
+}
+
+#[tokio::test]
+async fn embed_submission_returns_400_for_invalid_data() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let post_id = app.create_sample_post().await;
+    let test_cases = vec![
+        (
+            serde_json::json!({"author_name": "reader", "author_email": "not-an-email", "text": "Hi"}),
+            "invalid email",
+        ),
+        (
+            serde_json::json!({"author_name": "reader", "author_email": "reader@example.com", "text": ""}),
+            "empty text",
+        ),
+    ];
+
+    for (payload, description) in test_cases {
+        let response = app
+            .submit_embed_comment(&post_id, &payload, Some(EMBED_ORIGIN))
+            .await;
+        assert_eq!(
+            response.status().as_u16(),
+            400,
+            "Did not return 400 for {description}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn embed_submission_for_a_missing_post_returns_404() {
+    let app = helpers::spawn_app().await;
+
+    let response = app
+        .submit_embed_comment(&Uuid::new_v4(), &embed_comment(), Some(EMBED_ORIGIN))
+        .await;
+
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn embed_submissions_are_rate_limited_per_address() {
+    let app = helpers::spawn_app_with(|c| c.embed.max_submissions_per_hour = 2).await;
+    app.login().await;
+    let post_id = app.create_sample_post().await;
+
+    for _ in 0..2 {
+        let response = app
+            .submit_embed_comment(&post_id, &embed_comment(), Some(EMBED_ORIGIN))
+            .await;
+        assert_eq!(response.status().as_u16(), 202);
+    }
+
+    let response = app
+        .submit_embed_comment(&post_id, &embed_comment(), Some(EMBED_ORIGIN))
+        .await;
+    assert_eq!(response.status().as_u16(), 429);
+}
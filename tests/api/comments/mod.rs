@@ -1 +1,4 @@
+mod anonymous;
 mod comment;
+mod embed;
+mod report;
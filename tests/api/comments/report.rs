@@ -0,0 +1,114 @@
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::helpers;
+
+async fn create_comment_on_new_post(app: &helpers::TestApp) -> Uuid {
+    let post_id = app.create_sample_post().await;
+    let payload = serde_json::json!({
+        "text": "Buy cheap watches",
+        "post_id": post_id.to_string()
+    });
+    let body: Value = app.create_comment(&payload).await.json().await.unwrap();
+    Uuid::parse_str(body["id"].as_str().unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn reported_comment_shows_up_for_admins_with_context() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let comment_id = create_comment_on_new_post(&app).await;
+
+    let response = app
+        .report_comment(&comment_id, &serde_json::json!({ "reason": "Spam" }))
+        .await;
+    assert_eq!(response.status().as_u16(), 201);
+
+    app.logout().await;
+    app.login_admin().await;
+
+    let response = app.get_comment_reports().await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    let reports = body["reports"].as_array().unwrap();
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0]["comment_id"], comment_id.to_string());
+    assert_eq!(reports[0]["comment_text"], "Buy cheap watches");
+    assert_eq!(reports[0]["post_title"], "Post for comments");
+    assert_eq!(reports[0]["reporter_name"], app.test_user.user_name);
+    assert_eq!(reports[0]["reason"], "Spam");
+}
+
+#[tokio::test]
+async fn reporting_the_same_comment_twice_returns_409() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let comment_id = create_comment_on_new_post(&app).await;
+    let payload = serde_json::json!({ "reason": "Spam" });
+
+    let response = app.report_comment(&comment_id, &payload).await;
+    assert_eq!(response.status().as_u16(), 201);
+
+    let response = app.report_comment(&comment_id, &payload).await;
+    assert_eq!(response.status().as_u16(), 409);
+}
+
+#[tokio::test]
+async fn reporting_a_missing_comment_returns_404() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let response = app
+        .report_comment(&Uuid::new_v4(), &serde_json::json!({ "reason": "Spam" }))
+        .await;
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn reporting_without_a_reason_returns_400() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let comment_id = create_comment_on_new_post(&app).await;
+
+    let response = app
+        .report_comment(&comment_id, &serde_json::json!({ "reason": "  " }))
+        .await;
+    assert_eq!(response.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn reporting_requires_authentication() {
+    let app = helpers::spawn_app().await;
+
+    let response = app
+        .report_comment(&Uuid::new_v4(), &serde_json::json!({ "reason": "Spam" }))
+        .await;
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn removed_comments_drop_out_of_the_report_queue() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let comment_id = create_comment_on_new_post(&app).await;
+    app.report_comment(&comment_id, &serde_json::json!({ "reason": "Spam" }))
+        .await;
+
+    app.logout().await;
+    app.login_admin().await;
+    app.remove_comments(&serde_json::json!({ "comment_ids": [comment_id] }))
+        .await;
+
+    let body: Value = app.get_comment_reports().await.json().await.unwrap();
+    assert!(body["reports"].as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn comment_reports_return_403_for_non_admins() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let response = app.get_comment_reports().await;
+    assert_eq!(response.status().as_u16(), 403);
+}
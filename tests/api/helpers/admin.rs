@@ -1,8 +1,14 @@
+use chrono::{DateTime, Utc};
 use reqwest::{Response, header::HeaderMap};
 use serde_json::Value;
 use techhub::{
-    newsletter_delivery_worker, newsletter_delivery_worker::ExecutionOutcome, repository,
+    configuration::{DatabaseMaintenanceSettings, NewsletterDigestSettings},
+    domain::{FanOutOutcome, TableBloatStats},
+    newsletter_delivery_worker,
+    newsletter_delivery_worker::ExecutionOutcome,
+    repository,
 };
+use uuid::Uuid;
 
 use crate::helpers::TestApp;
 
@@ -35,16 +41,194 @@ impl TestApp {
 
     pub async fn dispatch_all_pending_newsletter_emails(&self) {
         loop {
-            if let ExecutionOutcome::EmptyQueue =
-                newsletter_delivery_worker::try_execute_task(&self.db_pool, &self.email_client)
-                    .await
-                    .unwrap()
+            if let ExecutionOutcome::EmptyQueue = newsletter_delivery_worker::try_execute_task(
+                &self.db_pool,
+                &self.email_client,
+                &self.address,
+                &self.newsletter_delivery,
+            )
+            .await
+            .unwrap()
             {
                 break;
             }
         }
     }
 
+    pub async fn fan_out_pending_newsletters(&self) {
+        while repository::fan_out_next_chunk(&self.db_pool, 1000, 10_000)
+            .await
+            .unwrap()
+            != FanOutOutcome::NothingPending
+        {}
+    }
+
+    pub async fn create_newsletter_draft(&self, payload: &Value) -> Response {
+        self.send_post("v1/admin/me/newsletters", payload).await
+    }
+
+    pub async fn update_newsletter_draft(&self, id: &Uuid, payload: &Value) -> Response {
+        self.send_put_with_payload(&format!("v1/admin/me/newsletters/{id}"), payload)
+            .await
+    }
+
+    pub async fn publish_newsletter_draft(&self, id: &Uuid) -> Response {
+        self.send_post(
+            &format!("v1/admin/me/newsletters/{id}/publish"),
+            &serde_json::json!({}),
+        )
+        .await
+    }
+
+    pub async fn reschedule_newsletter(&self, id: &Uuid, payload: &Value) -> Response {
+        self.send_put_with_payload(&format!("v1/admin/me/newsletters/{id}/schedule"), payload)
+            .await
+    }
+
+    pub async fn cancel_newsletter_issue(&self, id: &Uuid) -> Response {
+        self.send_delete(&format!("v1/admin/me/newsletters/{id}/queue"))
+            .await
+    }
+
+    pub async fn create_newsletter_segment(&self, payload: &Value) -> Response {
+        self.send_post("v1/admin/me/newsletters/segments", payload)
+            .await
+    }
+
+    pub async fn get_newsletter_segments(&self) -> Response {
+        self.send_get("v1/admin/me/newsletters/segments").await
+    }
+
+    pub async fn preview_newsletter(&self, payload: &Value) -> Response {
+        self.send_post("v1/admin/me/newsletters/preview", payload)
+            .await
+    }
+
+    pub async fn retry_failed_newsletter_deliveries(&self, id: &Uuid) -> Response {
+        self.send_post(
+            &format!("v1/admin/me/newsletters/{id}/retry-failed"),
+            &serde_json::json!({}),
+        )
+        .await
+    }
+
+    pub async fn test_send_newsletter(&self, id: &Uuid) -> Response {
+        self.send_post(
+            &format!("v1/admin/me/newsletters/{id}/test-send"),
+            &serde_json::json!({}),
+        )
+        .await
+    }
+
+    pub async fn get_newsletter_issues(&self, query: &str) -> Response {
+        self.send_get(&format!("v1/admin/me/newsletters{query}"))
+            .await
+    }
+
+    pub async fn get_newsletter_status(&self, id: &Uuid) -> Response {
+        self.send_get(&format!("v1/admin/me/newsletters/{id}/status"))
+            .await
+    }
+
+    pub async fn get_suppressed_emails(&self, query: &str) -> Response {
+        self.send_get(&format!("v1/admin/me/suppressions{query}"))
+            .await
+    }
+
+    pub async fn suppress_email(&self, email: &str) -> Response {
+        self.send_post(
+            "v1/admin/me/suppressions",
+            &serde_json::json!({ "email": email }),
+        )
+        .await
+    }
+
+    pub async fn unsuppress_email(&self, email: &str) -> Response {
+        self.send_delete(&format!("v1/admin/me/suppressions/{email}"))
+            .await
+    }
+
+    pub async fn get_moderation_comments(&self, query: &str) -> Response {
+        self.send_get(&format!("v1/admin/me/comments{query}")).await
+    }
+
+    pub async fn remove_comments(&self, payload: &Value) -> Response {
+        self.send_post("v1/admin/me/comments/remove", payload).await
+    }
+
+    pub async fn get_index_stats(&self) -> Response {
+        self.send_get("v1/admin/me/db/indexes").await
+    }
+
+    pub async fn get_bloat_stats(&self) -> Response {
+        self.send_get("v1/admin/me/db/bloat").await
+    }
+
+    pub async fn check_table_bloat(
+        &self,
+        settings: &DatabaseMaintenanceSettings,
+    ) -> Vec<TableBloatStats> {
+        newsletter_delivery_worker::check_table_bloat(&self.db_pool, settings)
+            .await
+            .expect("Failed to check table bloat")
+    }
+
+    pub async fn get_post_body_stats(&self) -> Response {
+        self.send_get("v1/admin/me/db/post-bodies").await
+    }
+
+    pub async fn get_comment_reports(&self) -> Response {
+        self.send_get("v1/admin/me/reports/comments").await
+    }
+
+    pub async fn import_subscribers(&self, csv: &str, query: &str) -> Response {
+        self.api_client
+            .post(format!(
+                "{}/v1/admin/me/subscribers/import{query}",
+                self.address
+            ))
+            .header("Content-Type", "text/csv")
+            .body(csv.to_string())
+            .send()
+            .await
+            .expect("POST request failed")
+    }
+
+    pub async fn import_comments(&self, ndjson: String, query: &str) -> Response {
+        self.api_client
+            .post(format!(
+                "{}/v1/admin/me/comments/import{query}",
+                self.address
+            ))
+            .header("Content-Type", "application/x-ndjson")
+            .body(ndjson)
+            .send()
+            .await
+            .expect("POST request failed")
+    }
+
+    pub async fn get_workers(&self) -> Response {
+        self.send_get("v1/admin/me/workers").await
+    }
+
+    pub async fn get_audit_log(&self, query: &str) -> Response {
+        self.send_get(&format!("v1/admin/me/audit-log{query}"))
+            .await
+    }
+
+    pub async fn get_log_filter(&self) -> Response {
+        self.send_get("v1/admin/me/logging").await
+    }
+
+    pub async fn set_log_filter(&self, payload: &Value) -> Response {
+        self.api_client
+            .put(format!("{}/v1/admin/me/logging", self.address))
+            .json(payload)
+            .send()
+            .await
+            .expect("PUT request failed")
+    }
+
     pub async fn cleanup_old_newsletter_issues(&self) {
         repository::cleanup_old_newsletter_issues(&self.db_pool)
             .await
@@ -56,4 +240,89 @@ impl TestApp {
             .await
             .unwrap();
     }
+
+    pub async fn cleanup_expired_tokens(&self) {
+        repository::cleanup_expired_tokens(&self.db_pool)
+            .await
+            .unwrap();
+    }
+
+    pub async fn ban_user(&self, id: &Uuid, payload: &Value) -> Response {
+        self.send_post(&format!("v1/admin/me/users/ban/{id}"), payload)
+            .await
+    }
+
+    pub async fn unban_user(&self, id: &Uuid) -> Response {
+        self.send_post(
+            &format!("v1/admin/me/users/unban/{id}"),
+            &serde_json::json!({}),
+        )
+        .await
+    }
+
+    pub async fn set_user_role(&self, id: &Uuid, payload: &Value) -> Response {
+        self.send_put_with_payload(&format!("v1/admin/me/users/{id}/role"), payload)
+            .await
+    }
+
+    pub async fn start_impersonation(&self, id: &Uuid) -> Response {
+        self.send_post(
+            &format!("v1/admin/me/users/{id}/impersonate"),
+            &serde_json::json!({}),
+        )
+        .await
+    }
+
+    pub async fn end_impersonation(&self) -> Response {
+        self.send_post("v1/user/me/impersonation/end", &serde_json::json!({}))
+            .await
+    }
+
+    pub async fn send_activation_reminders(&self) -> usize {
+        newsletter_delivery_worker::send_activation_reminders(
+            &self.db_pool,
+            &self.email_client,
+            "http://127.0.0.1",
+        )
+        .await
+        .unwrap()
+    }
+
+    pub async fn send_account_emails(&self) -> usize {
+        newsletter_delivery_worker::send_account_emails(
+            &self.db_pool,
+            &self.email_client,
+            "http://127.0.0.1",
+        )
+        .await
+        .unwrap()
+    }
+
+    pub async fn send_user_name_change_notices(&self) -> usize {
+        newsletter_delivery_worker::send_user_name_change_notices(&self.db_pool, &self.email_client)
+            .await
+            .unwrap()
+    }
+
+    // Runs the weekly digest as the worker would at `scheduled_for`, featuring up to two posts
+    pub async fn send_weekly_digest(&self, scheduled_for: DateTime<Utc>) -> Option<Uuid> {
+        let settings = NewsletterDigestSettings {
+            enabled: true,
+            schedule: "0 0 9 * * Mon".to_string(),
+            max_posts: 2,
+        };
+        newsletter_delivery_worker::send_weekly_digest(
+            &self.db_pool,
+            &settings,
+            "http://127.0.0.1",
+            scheduled_for,
+        )
+        .await
+        .unwrap()
+    }
+
+    pub async fn get_activation_reminder_stats(&self) -> Response {
+        self.send_get("v1/admin/me/users/activation-reminders")
+            .await
+    }
 }
@@ -14,7 +14,17 @@ impl TestApp {
             .await
     }
 
+    pub async fn report_comment(&self, id: &Uuid, payload: &Value) -> Response {
+        self.send_post(&format!("v1/comment/me/report/{id}"), payload)
+            .await
+    }
+
     pub async fn get_comments(&self, id: &Uuid) -> Response {
         self.send_get(&format!("v1/comment/get/posts/{id}")).await
     }
+
+    pub async fn get_comments_with_query(&self, id: &Uuid, query: &str) -> Response {
+        self.send_get(&format!("v1/comment/get/posts/{id}{query}"))
+            .await
+    }
 }
@@ -1,5 +1,6 @@
 use linkify::{LinkFinder, LinkKind};
 use reqwest::{Response, Url, header::HeaderMap};
+use secrecy::ExposeSecret;
 use serde_json::Value;
 use uuid::Uuid;
 use wiremock::{Mock, Request, ResponseTemplate, matchers};
@@ -166,6 +167,36 @@ impl TestApp {
             .expect("POST request with headers failed")
     }
 
+    // Signed with the configured secret unless a `signature` is given
+    pub async fn get_newsletter_archive(&self, query: &str) -> Response {
+        self.send_get(&format!("v1/newsletters{query}")).await
+    }
+
+    pub async fn get_archived_newsletter(&self, id: &Uuid) -> Response {
+        self.send_get(&format!("v1/newsletters/{id}")).await
+    }
+
+    // Posts with the configured credentials, or with `credentials` as `(username, password)`
+    pub async fn post_postmark_webhook(
+        &self,
+        body: &str,
+        credentials: Option<(&str, &str)>,
+    ) -> Response {
+        let (username, password) = credentials.unwrap_or((
+            &self.postmark_webhook_username,
+            self.postmark_webhook_password.expose_secret(),
+        ));
+
+        self.api_client
+            .post(format!("{}/v1/webhooks/postmark", self.address))
+            .header("Content-Type", "application/json")
+            .basic_auth(username, Some(password))
+            .body(body.to_string())
+            .send()
+            .await
+            .expect("POST request to the Postmark webhook failed")
+    }
+
     pub async fn send_patch(&self, endpoint: &str) -> Response {
         self.api_client
             .patch(format!("{}/{}", &self.address, endpoint))
@@ -174,6 +205,23 @@ impl TestApp {
             .expect("Failed to execute PATCH request.")
     }
 
+    pub async fn send_put(&self, endpoint: &str) -> Response {
+        self.api_client
+            .put(format!("{}/{}", &self.address, endpoint))
+            .send()
+            .await
+            .expect("Failed to execute PUT request.")
+    }
+
+    pub async fn send_put_with_payload(&self, endpoint: &str, payload: &Value) -> Response {
+        self.api_client
+            .put(format!("{}/{}", &self.address, endpoint))
+            .json(payload)
+            .send()
+            .await
+            .expect("Failed to execute PUT request.")
+    }
+
     pub async fn send_patch_with_payload(&self, endpoint: &str, payload: &Value) -> Response {
         self.api_client
             .patch(format!("{}/{}", &self.address, endpoint))
@@ -190,4 +238,13 @@ impl TestApp {
             .await
             .expect("Failed to execute DELETE request.")
     }
+
+    pub async fn send_delete_with_payload(&self, endpoint: &str, payload: &Value) -> Response {
+        self.api_client
+            .delete(format!("{}/{}", &self.address, endpoint))
+            .json(payload)
+            .send()
+            .await
+            .expect("Failed to execute DELETE request.")
+    }
 }
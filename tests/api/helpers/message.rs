@@ -0,0 +1,20 @@
+use reqwest::Response;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::helpers::TestApp;
+
+impl TestApp {
+    pub async fn send_message(&self, payload: &Value) -> Response {
+        self.send_post("v1/messages", payload).await
+    }
+
+    pub async fn get_messages(&self, query: &str) -> Response {
+        self.send_get(&format!("v1/user/me/messages{query}")).await
+    }
+
+    pub async fn mark_conversation_read(&self, id: &Uuid) -> Response {
+        self.send_patch(&format!("v1/user/me/messages/{id}/read"))
+            .await
+    }
+}
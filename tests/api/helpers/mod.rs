@@ -1,7 +1,9 @@
 mod admin;
 mod comment;
 mod http;
+mod message;
 mod post;
+mod submission;
 mod user;
 
 use std::{env, io, sync::OnceLock};
@@ -11,8 +13,13 @@ use reqwest::{Client, Url};
 use secrecy::Secret;
 use sqlx::{Connection, Executor, PgConnection, PgPool};
 use techhub::{
-    configuration, configuration::DatabaseConfigs, email_client::EmailClient, startup,
-    startup::Application, telemetry,
+    configuration,
+    configuration::{Configuration, DatabaseConfigs, NewsletterDeliverySettings},
+    email_client::EmailClient,
+    post_views::PostViewCounter,
+    startup,
+    startup::Application,
+    telemetry,
 };
 use uuid::Uuid;
 use wiremock::MockServer;
@@ -67,10 +74,15 @@ pub struct TestApp {
     pub address: String,
     pub db_pool: PgPool,
     pub email_server: MockServer,
+    pub captcha_server: MockServer,
     pub port: u16,
     pub test_user: TestUser,
     pub api_client: Client,
     pub email_client: EmailClient,
+    pub postmark_webhook_username: String,
+    pub postmark_webhook_password: Secret<String>,
+    pub newsletter_delivery: NewsletterDeliverySettings,
+    pub post_views: PostViewCounter,
 }
 
 pub struct ConfirmationLinks {
@@ -103,16 +115,32 @@ pub fn init_tracing() {
     });
 }
 
+// Origin the test configuration allows to call the embed endpoints
+pub const EMBED_ORIGIN: &str = "https://blog.example.com";
+
 pub async fn spawn_app() -> TestApp {
+    spawn_app_with(|_| {}).await
+}
+
+// Spawns the app after letting the test adjust the configuration
+pub async fn spawn_app_with(customise: impl FnOnce(&mut Configuration)) -> TestApp {
     init_tracing();
 
     let email_server = MockServer::start().await;
+    let captcha_server = MockServer::start().await;
 
     let configuration = {
         let mut c = configuration::get_config().expect("Failed to read configuration.");
         c.database.database_name = Uuid::new_v4().to_string();
         c.application.port = 0;
+        // Lets tests pose as different clients through `X-Forwarded-For`
+        c.application.trusted_proxies = vec!["127.0.0.1".parse().unwrap()];
         c.email_client.base_url = email_server.uri();
+        c.embed.allowed_origins = vec![EMBED_ORIGIN.to_string()];
+        c.embed.allow_anonymous_posting = true;
+        c.anonymous_comments.enabled = true;
+        c.anonymous_comments.captcha_verify_url = format!("{}/siteverify", captcha_server.uri());
+        customise(&mut c);
         c
     };
 
@@ -122,6 +150,7 @@ pub async fn spawn_app() -> TestApp {
         .await
         .expect("Failed to build application.");
     let application_port = application.port();
+    let post_views = application.post_views();
     tokio::spawn(application.run_until_stopped());
 
     let client = Client::builder().cookie_store(true).build().unwrap();
@@ -131,9 +160,17 @@ pub async fn spawn_app() -> TestApp {
         port: application_port,
         db_pool: startup::get_connection_pool(&configuration.database),
         email_server,
+        captcha_server,
         test_user: TestUser::generate(),
         api_client: client,
-        email_client: configuration.email_client.client(),
+        postmark_webhook_username: configuration.email_client.webhook_username.clone(),
+        postmark_webhook_password: configuration.email_client.webhook_password.clone(),
+        newsletter_delivery: configuration.newsletter_delivery.clone(),
+        post_views,
+        email_client: configuration
+            .email_client
+            .client()
+            .with_suppression_list(startup::get_connection_pool(&configuration.database)),
     };
 
     test_app
@@ -9,6 +9,14 @@ impl TestApp {
         self.send_post("v1/posts/me/create", payload).await
     }
 
+    pub async fn suggest_post_metadata(&self, text: &str) -> Response {
+        self.send_post(
+            "v1/posts/me/suggest-metadata",
+            &serde_json::json!({ "text": text }),
+        )
+        .await
+    }
+
     pub async fn update_post(&self, id: &Uuid, payload: &Value) -> Response {
         self.send_patch_with_payload(&format!("v1/posts/me/update/{id}"), payload)
             .await
@@ -38,4 +46,23 @@ impl TestApp {
     pub async fn get_all_posts(&self, query: &str) -> Response {
         self.send_get(&format!("v1/posts/get/all{query}")).await
     }
+
+    pub async fn propose_post_edit(&self, id: &Uuid, payload: &Value) -> Response {
+        self.send_post(&format!("v1/posts/me/{id}/proposals"), payload)
+            .await
+    }
+
+    pub async fn get_post_edit_proposals(&self, id: &Uuid) -> Response {
+        self.send_get(&format!("v1/posts/me/{id}/proposals")).await
+    }
+
+    pub async fn approve_post_edit_proposal(&self, id: &Uuid) -> Response {
+        self.send_patch(&format!("v1/posts/me/proposals/{id}/approve"))
+            .await
+    }
+
+    pub async fn reject_post_edit_proposal(&self, id: &Uuid) -> Response {
+        self.send_patch(&format!("v1/posts/me/proposals/{id}/reject"))
+            .await
+    }
 }
@@ -0,0 +1,73 @@
+use reqwest::{Response, header::HeaderMap};
+use serde_json::Value;
+use uuid::Uuid;
+use wiremock::{Mock, ResponseTemplate, matchers};
+
+use crate::helpers::TestApp;
+
+impl TestApp {
+    pub async fn get_embed_comments(&self, post_id: &Uuid, origin: &str) -> Response {
+        self.api_client
+            .get(format!(
+                "{}/v1/embed/posts/{post_id}/comments",
+                self.address
+            ))
+            .header("Origin", origin)
+            .send()
+            .await
+            .expect("GET request failed")
+    }
+
+    pub async fn submit_embed_comment(
+        &self,
+        post_id: &Uuid,
+        payload: &Value,
+        origin: Option<&str>,
+    ) -> Response {
+        let mut headers = HeaderMap::new();
+        if let Some(origin) = origin {
+            headers.insert("Origin", origin.parse().unwrap());
+        }
+        self.send_post_with_headers(
+            &format!("v1/embed/posts/{post_id}/comments"),
+            payload,
+            &headers,
+        )
+        .await
+    }
+
+    pub async fn create_anonymous_comment(&self, payload: &Value) -> Response {
+        self.send_post("v1/comment/anonymous", payload).await
+    }
+
+    // Makes the CAPTCHA provider accept or reject every token
+    pub async fn mock_captcha(&self, success: bool) {
+        Mock::given(matchers::path("/siteverify"))
+            .and(matchers::method("POST"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "success": success })),
+            )
+            .mount(&self.captcha_server)
+            .await;
+    }
+
+    pub async fn get_comment_submissions(&self) -> Response {
+        self.send_get("v1/admin/me/submissions").await
+    }
+
+    pub async fn approve_comment_submission(&self, id: &Uuid) -> Response {
+        self.send_post(
+            &format!("v1/admin/me/submissions/{id}/approve"),
+            &serde_json::json!({}),
+        )
+        .await
+    }
+
+    pub async fn reject_comment_submission(&self, id: &Uuid) -> Response {
+        self.send_post(
+            &format!("v1/admin/me/submissions/{id}/reject"),
+            &serde_json::json!({}),
+        )
+        .await
+    }
+}
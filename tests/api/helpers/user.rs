@@ -1,5 +1,6 @@
-use reqwest::Response;
+use reqwest::{Method, Response, header::HeaderMap};
 use serde_json::Value;
+use uuid::Uuid;
 
 use crate::helpers::TestApp;
 
@@ -8,6 +9,13 @@ impl TestApp {
         self.send_post("v1/user/register", payload).await
     }
 
+    pub async fn register_user_from(&self, ip_address: &str, payload: &Value) -> Response {
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Forwarded-For", ip_address.parse().unwrap());
+        self.send_post_with_headers("v1/user/register", payload, &headers)
+            .await
+    }
+
     pub async fn login(&self) {
         let body = serde_json::json!({
             "user_name": &self.test_user.user_name,
@@ -21,20 +29,248 @@ impl TestApp {
         self.send_post("v1/user/login", creds).await
     }
 
+    // As a client behind the load balancer at `ip_address`
+    pub async fn login_from(&self, ip_address: &str, creds: &Value) -> Response {
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Forwarded-For", ip_address.parse().unwrap());
+        self.send_post_with_headers("v1/user/login", creds, &headers)
+            .await
+    }
+
     pub async fn logout(&self) -> Response {
-        self.send_post("v1/user/me/logout", &serde_json::json!({}))
+        self.api_client
+            .post(format!("{}/v1/user/me/logout", self.address))
+            .send()
             .await
+            .expect("POST request failed")
+    }
+
+    pub async fn forgot_password(&self, email: &str) -> Response {
+        self.send_post(
+            "v1/user/forgot-password",
+            &serde_json::json!({ "email": email }),
+        )
+        .await
+    }
+
+    pub async fn request_magic_link(&self, email: &str) -> Response {
+        self.send_post(
+            "v1/user/login/magic-link",
+            &serde_json::json!({ "email": email }),
+        )
+        .await
+    }
+
+    pub async fn login_with_magic_link(&self, token: &str) -> Response {
+        self.send_post(
+            "v1/user/login/magic-link/verify",
+            &serde_json::json!({ "token": token }),
+        )
+        .await
+    }
+
+    pub async fn reset_password(&self, token: &str, new_password: &str) -> Response {
+        self.send_post(
+            "v1/user/reset-password",
+            &serde_json::json!({ "token": token, "new_password": new_password }),
+        )
+        .await
+    }
+
+    pub async fn enter_sudo_mode(&self, payload: &Value) -> Response {
+        self.send_post("v1/user/me/sudo", payload).await
     }
 
     pub async fn change_password(&self, payload: &Value) -> Response {
         self.send_post("v1/user/me/change-password", payload).await
     }
 
+    pub async fn unsubscribe(&self) -> Response {
+        self.send_post("v1/user/me/email/unsubscribe", &serde_json::json!({}))
+            .await
+    }
+
     pub async fn request_subscription_email(&self) -> Response {
         self.send_get("v1/user/me/request-subscription").await
     }
 
+    pub async fn export_posts(&self, query: &str) -> Response {
+        self.send_get(&format!("v1/user/me/posts/export{query}"))
+            .await
+    }
+
+    pub async fn user_id_of(&self, payload: &Value) -> Uuid {
+        sqlx::query_scalar!(
+            "SELECT id FROM users WHERE user_name = $1",
+            payload["user_name"].as_str().unwrap()
+        )
+        .fetch_one(&self.db_pool)
+        .await
+        .unwrap()
+    }
+
+    pub async fn block_user(&self, id: &Uuid) -> Response {
+        self.send_put(&format!("v1/user/me/blocks/{id}")).await
+    }
+
+    pub async fn unblock_user(&self, id: &Uuid) -> Response {
+        self.send_delete(&format!("v1/user/me/blocks/{id}")).await
+    }
+
+    pub async fn get_blocks(&self) -> Response {
+        self.send_get("v1/user/me/blocks").await
+    }
+
+    pub async fn follow_user(&self, id: &Uuid) -> Response {
+        self.send_put(&format!("v1/user/me/following/{id}")).await
+    }
+
+    pub async fn unfollow_user(&self, id: &Uuid) -> Response {
+        self.send_delete(&format!("v1/user/me/following/{id}"))
+            .await
+    }
+
+    pub async fn get_feed(&self, query: &str) -> Response {
+        self.send_get(&format!("v1/user/me/feed{query}")).await
+    }
+
+    pub async fn export_own_data(&self) -> Response {
+        self.send_get("v1/user/me/export").await
+    }
+
+    pub async fn export_posts_with_encoding(&self, query: &str, accept_encoding: &str) -> Response {
+        self.api_client
+            .get(format!("{}/v1/user/me/posts/export{query}", self.address))
+            .header("Accept-Encoding", accept_encoding)
+            .send()
+            .await
+            .expect("GET request failed")
+    }
+
+    pub async fn get_mentions(&self, query: &str) -> Response {
+        self.send_get(&format!("v1/user/me/mentions{query}")).await
+    }
+
+    pub async fn search_users(&self, query: &str) -> Response {
+        self.send_get(&format!("v1/user/search{query}")).await
+    }
+
+    pub async fn get_user_profile(&self, id: &Uuid) -> Response {
+        self.send_get(&format!("v1/user/{id}")).await
+    }
+
+    pub async fn update_profile(&self, payload: &Value) -> Response {
+        self.send_patch_with_payload("v1/user/me/profile", payload)
+            .await
+    }
+
+    pub async fn delete_account(&self, password: &str) -> Response {
+        self.send_delete_with_payload("v1/user/me", &serde_json::json!({ "password": password }))
+            .await
+    }
+
+    pub async fn request_email_change(&self, email: &str) -> Response {
+        self.send_post("v1/user/me/email", &serde_json::json!({ "email": email }))
+            .await
+    }
+
+    pub async fn change_user_name(&self, payload: &Value) -> Response {
+        self.send_put_with_payload("v1/user/me/username", payload)
+            .await
+    }
+
+    pub async fn upload_avatar(&self, image: Vec<u8>, content_type: &str) -> Response {
+        self.api_client
+            .put(format!("{}/v1/user/me/avatar", self.address))
+            .header("Content-Type", content_type)
+            .body(image)
+            .send()
+            .await
+            .expect("PUT request failed")
+    }
+
+    pub async fn delete_avatar(&self) -> Response {
+        self.send_delete("v1/user/me/avatar").await
+    }
+
+    pub async fn get_avatar(&self, id: &Uuid) -> Response {
+        self.send_get(&format!("v1/user/{id}/avatar")).await
+    }
+
+    pub async fn get_user_comments(&self, id: &Uuid, query: &str) -> Response {
+        self.send_get(&format!("v1/user/{id}/comments{query}"))
+            .await
+    }
+
     pub async fn access_protected(&self) -> Response {
         self.send_get("v1/user/me/protected").await
     }
+
+    pub async fn issue_access_token(&self, creds: &Value) -> Response {
+        self.send_post("v1/user/token", creds).await
+    }
+
+    pub async fn refresh_access_token(&self, refresh_token: &str) -> Response {
+        self.send_post(
+            "v1/user/token/refresh",
+            &serde_json::json!({ "refresh_token": refresh_token }),
+        )
+        .await
+    }
+
+    pub async fn revoke_refresh_token(&self, refresh_token: &str) -> Response {
+        self.send_post(
+            "v1/user/token/revoke",
+            &serde_json::json!({ "refresh_token": refresh_token }),
+        )
+        .await
+    }
+
+    pub async fn access_protected_with_token(&self, token: &str) -> Response {
+        self.api_client
+            .get(format!("{}/v1/user/me/protected", self.address))
+            .bearer_auth(token)
+            .send()
+            .await
+            .expect("GET request failed")
+    }
+
+    pub async fn create_personal_access_token(&self, payload: &Value) -> Response {
+        self.send_post("v1/user/me/tokens", payload).await
+    }
+
+    pub async fn get_personal_access_tokens(&self) -> Response {
+        self.send_get("v1/user/me/tokens").await
+    }
+
+    pub async fn revoke_personal_access_token(&self, id: &str) -> Response {
+        self.send_delete(&format!("v1/user/me/tokens/{id}")).await
+    }
+
+    pub async fn log_out_everywhere(&self) -> Response {
+        self.send_post("v1/user/me/logout-all", &serde_json::json!({}))
+            .await
+    }
+
+    pub async fn get_sessions(&self) -> Response {
+        self.send_get("v1/user/me/sessions").await
+    }
+
+    pub async fn get_security_activity(&self) -> Response {
+        self.send_get("v1/user/me/security/activity").await
+    }
+
+    pub async fn revoke_session(&self, id: &str) -> Response {
+        self.send_delete(&format!("v1/user/me/sessions/{id}")).await
+    }
+
+    // From a client without the session cookies of `api_client`
+    pub async fn send_with_token(&self, method: Method, endpoint: &str, token: &str) -> Response {
+        reqwest::Client::new()
+            .request(method, format!("{}/{}", self.address, endpoint))
+            .bearer_auth(token)
+            .send()
+            .await
+            .expect("Request with bearer token failed")
+    }
 }
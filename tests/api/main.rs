@@ -4,5 +4,10 @@ mod comments;
 mod health_check;
 mod helpers;
 mod idempotency;
+mod messages;
+mod meta;
+mod newsletters;
 mod posts;
+mod rate_limits;
 mod users;
+mod webhooks;
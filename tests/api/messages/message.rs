@@ -0,0 +1,177 @@
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::helpers;
+
+#[tokio::test]
+async fn send_message_returns_401_if_unauthenticated() {
+    let app = helpers::spawn_app().await;
+
+    let response = app
+        .send_message(&json!({
+            "recipient_id": app.test_user.user_id.to_string(),
+            "text": "Hello"
+        }))
+        .await;
+
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn send_message_returns_400_for_invalid_data() {
+    let app = helpers::spawn_app().await;
+    let recipient = app.create_activated_user().await;
+    let recipient_id = app.user_id_of(&recipient).await.to_string();
+    app.login().await;
+
+    let test_cases = [
+        (
+            json!({"recipient_id": "not-a-uuid", "text": "Hi"}),
+            "invalid recipient",
+        ),
+        (
+            json!({"recipient_id": recipient_id, "text": "   "}),
+            "empty text",
+        ),
+        (
+            json!({"recipient_id": recipient_id, "text": "a".repeat(1001)}),
+            "text too long",
+        ),
+        (
+            json!({"recipient_id": app.test_user.user_id.to_string(), "text": "Hi"}),
+            "messaging yourself",
+        ),
+    ];
+
+    for (payload, description) in test_cases {
+        let response = app.send_message(&payload).await;
+        assert_eq!(response.status().as_u16(), 400, "{description}");
+    }
+}
+
+#[tokio::test]
+async fn send_message_returns_404_for_unknown_recipient() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let response = app
+        .send_message(&json!({
+            "recipient_id": Uuid::new_v4().to_string(),
+            "text": "Hello?"
+        }))
+        .await;
+
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn messages_are_threaded_by_conversation_with_unread_counts() {
+    let app = helpers::spawn_app().await;
+    let friend = app.create_activated_user().await;
+    let friend_id = app.user_id_of(&friend).await;
+
+    app.login().await;
+    let response = app
+        .send_message(&json!({"recipient_id": friend_id.to_string(), "text": "First"}))
+        .await;
+    assert_eq!(response.status().as_u16(), 201);
+    let first: Value = response.json().await.unwrap();
+    app.logout().await;
+
+    app.login_with(&friend).await;
+    let response = app
+        .send_message(&json!({
+            "recipient_id": app.test_user.user_id.to_string(),
+            "text": "Reply"
+        }))
+        .await;
+    let reply: Value = response.json().await.unwrap();
+    assert_eq!(first["conversation_id"], reply["conversation_id"]);
+    app.logout().await;
+
+    app.login().await;
+    let body: Value = app.get_messages("").await.json().await.unwrap();
+    assert_eq!(body["unread_count"], 1);
+
+    let conversations = body["conversations"].as_array().unwrap();
+    assert_eq!(conversations.len(), 1);
+    assert_eq!(conversations[0]["other_user_id"], friend_id.to_string());
+    assert_eq!(conversations[0]["unread_count"], 1);
+
+    let texts: Vec<&str> = conversations[0]["messages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| m["text"].as_str().unwrap())
+        .collect();
+    assert_eq!(texts, ["First", "Reply"]);
+}
+
+#[tokio::test]
+async fn marking_a_conversation_read_clears_its_unread_count() {
+    let app = helpers::spawn_app().await;
+    let friend = app.create_activated_user().await;
+
+    app.login_with(&friend).await;
+    let response = app
+        .send_message(&json!({
+            "recipient_id": app.test_user.user_id.to_string(),
+            "text": "Ping"
+        }))
+        .await;
+    let sent: Value = response.json().await.unwrap();
+    let conversation_id: Uuid = sent["conversation_id"].as_str().unwrap().parse().unwrap();
+
+    // The sender's own messages never count as unread for them
+    let body: Value = app.get_messages("").await.json().await.unwrap();
+    assert_eq!(body["unread_count"], 0);
+    app.logout().await;
+
+    app.login().await;
+    let response = app.mark_conversation_read(&conversation_id).await;
+    assert_eq!(response.status().as_u16(), 204);
+
+    let body: Value = app.get_messages("").await.json().await.unwrap();
+    assert_eq!(body["unread_count"], 0);
+    assert!(body["conversations"][0]["messages"][0]["read_at"].is_string());
+}
+
+#[tokio::test]
+async fn marking_someone_elses_conversation_read_returns_404() {
+    let app = helpers::spawn_app().await;
+    let friend = app.create_activated_user().await;
+    let friend_id = app.user_id_of(&friend).await;
+
+    app.login_admin().await;
+    let response = app
+        .send_message(&json!({"recipient_id": friend_id.to_string(), "text": "Private"}))
+        .await;
+    let sent: Value = response.json().await.unwrap();
+    let conversation_id: Uuid = sent["conversation_id"].as_str().unwrap().parse().unwrap();
+    app.logout().await;
+
+    app.login().await;
+    let response = app.mark_conversation_read(&conversation_id).await;
+
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn send_message_is_rate_limited() {
+    let app = helpers::spawn_app_with(|c| c.messages.max_messages_per_hour = 2).await;
+    let friend = app.create_activated_user().await;
+    let friend_id = app.user_id_of(&friend).await.to_string();
+    app.login().await;
+
+    for _ in 0..2 {
+        let response = app
+            .send_message(&json!({"recipient_id": friend_id, "text": "Hi"}))
+            .await;
+        assert_eq!(response.status().as_u16(), 201);
+    }
+
+    let response = app
+        .send_message(&json!({"recipient_id": friend_id, "text": "Hi"}))
+        .await;
+    assert_eq!(response.status().as_u16(), 429);
+}
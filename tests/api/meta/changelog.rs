@@ -0,0 +1,43 @@
+use reqwest::Client;
+
+use crate::helpers;
+
+#[tokio::test]
+async fn changelog_reports_server_version_and_releases() {
+    let app = helpers::spawn_app().await;
+
+    let response = Client::new()
+        .get(format!("{}/v1/meta/changelog", app.address))
+        .send()
+        .await
+        .expect("Failed to execute request");
+
+    assert_eq!(response.status().as_u16(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["server_version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(body["releases"][0]["version"], env!("CARGO_PKG_VERSION"));
+}
+
+#[tokio::test]
+async fn changelog_entries_are_structured() {
+    let app = helpers::spawn_app().await;
+
+    let response = Client::new()
+        .get(format!("{}/v1/meta/changelog", app.address))
+        .send()
+        .await
+        .expect("Failed to execute request");
+
+    let body: serde_json::Value = response.json().await.unwrap();
+    let changes = body["releases"][0]["changes"].as_array().unwrap();
+    assert!(
+        changes
+            .iter()
+            .any(|c| { c["kind"] == "added" && c["endpoint"] == "GET /v1/meta/changelog" })
+    );
+    for change in changes {
+        assert!(change["kind"].is_string());
+        assert!(change["endpoint"].is_string());
+        assert!(change["description"].is_string());
+    }
+}
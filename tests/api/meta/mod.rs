@@ -0,0 +1,2 @@
+mod changelog;
+mod time;
@@ -0,0 +1,45 @@
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+
+use crate::helpers;
+
+#[tokio::test]
+async fn time_returns_the_server_clock_with_millisecond_precision() {
+    let app = helpers::spawn_app().await;
+
+    let before = Utc::now();
+    let response = Client::new()
+        .get(format!("{}/v1/meta/time", app.address))
+        .send()
+        .await
+        .expect("Failed to execute request");
+    let after = Utc::now();
+
+    assert_eq!(response.status().as_u16(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    let server_time = body["server_time"].as_str().unwrap();
+    // e.g. 2025-10-31T09:30:00.123Z
+    assert_eq!(server_time.len(), 24);
+    let server_time = DateTime::parse_from_rfc3339(server_time).unwrap();
+    assert!(server_time.timestamp_millis() >= before.timestamp_millis());
+    assert!(server_time.timestamp_millis() <= after.timestamp_millis());
+    assert_eq!(body["unix_millis"], server_time.timestamp_millis());
+}
+
+#[tokio::test]
+async fn every_response_carries_the_server_time_header() {
+    let app = helpers::spawn_app().await;
+
+    // Successful responses as well as errors raised by the auth middleware
+    for (endpoint, status) in [
+        ("health_check", 200),
+        ("v1/meta/changelog", 200),
+        ("v1/user/me/protected", 401),
+    ] {
+        let response = app.send_get(endpoint).await;
+        assert_eq!(response.status().as_u16(), status, "{endpoint}");
+
+        let header = response.headers()["x-server-time"].to_str().unwrap();
+        assert!(DateTime::parse_from_rfc3339(header).is_ok(), "{endpoint}");
+    }
+}
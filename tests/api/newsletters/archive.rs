@@ -0,0 +1,160 @@
+use chrono::{Duration, Utc};
+use serde_json::{Value, json};
+use techhub::repository;
+use uuid::Uuid;
+
+use crate::helpers;
+
+async fn publish(app: &helpers::TestApp, title: &str, extra: Value) -> Uuid {
+    let mut newsletter_body = json!({
+        "title": title,
+        "content": {
+            "text": "Hi {{user_name}}, here is what happened.",
+            "html": "<p>Hi {{user_name}}, here is what happened.</p><a href=\"{{unsubscribe_url}}\">leave</a>"
+        }
+    });
+    newsletter_body
+        .as_object_mut()
+        .unwrap()
+        .extend(extra.as_object().unwrap().clone());
+
+    let key = Uuid::new_v4().to_string();
+    let response = app.publish_newsletters(&newsletter_body, Some(&key)).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body: Value = response.json().await.unwrap();
+    Uuid::parse_str(body["issue_id"].as_str().unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn only_published_issues_sent_to_everyone_are_public() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    publish(&app, "Public Issue", json!({})).await;
+    let cancelled = publish(&app, "Cancelled Issue", json!({})).await;
+    assert_eq!(
+        app.cancel_newsletter_issue(&cancelled)
+            .await
+            .status()
+            .as_u16(),
+        200
+    );
+    publish(
+        &app,
+        "Scheduled Issue",
+        json!({ "scheduled_at": Utc::now() + Duration::days(1) }),
+    )
+    .await;
+    let response = app
+        .create_newsletter_segment(&json!({ "name": "Recent", "active_within_days": 7 }))
+        .await;
+    let body: Value = response.json().await.unwrap();
+    publish(
+        &app,
+        "Segment Issue",
+        json!({ "segment_id": body["segment_id"] }),
+    )
+    .await;
+    let draft = json!({
+        "title": "Draft Issue",
+        "content": { "text": "Not yet", "html": "<p>Not yet</p>" }
+    });
+    assert_eq!(
+        app.create_newsletter_draft(&draft).await.status().as_u16(),
+        201
+    );
+    app.logout().await;
+
+    let response = app.get_newsletter_archive("").await;
+
+    assert_eq!(response.status().as_u16(), 200);
+    assert!(
+        response.headers()["content-type"]
+            .to_str()
+            .unwrap()
+            .starts_with("text/html")
+    );
+    let html = response.text().await.unwrap();
+    assert!(html.contains("Public Issue"));
+    for title in [
+        "Cancelled Issue",
+        "Scheduled Issue",
+        "Segment Issue",
+        "Draft Issue",
+    ] {
+        assert!(!html.contains(title), "{title}");
+    }
+}
+
+#[tokio::test]
+async fn issues_are_rendered_without_anyones_details() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+    let issue_id = publish(&app, "Public Issue", json!({})).await;
+    app.logout().await;
+
+    let response = app.get_archived_newsletter(&issue_id).await;
+
+    assert_eq!(response.status().as_u16(), 200);
+    assert!(response.headers().contains_key("content-security-policy"));
+    let html = response.text().await.unwrap();
+    assert!(html.contains("<title>Public Issue</title>"));
+    assert!(html.contains("<p>Hi reader, here is what happened.</p>"));
+    assert!(!html.contains("{{"));
+
+    let response = app.get_archived_newsletter(&Uuid::new_v4()).await;
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn issues_stay_public_after_they_are_cleaned_up() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+    let issue_id = publish(&app, "Old Issue", json!({})).await;
+    let cancelled = publish(&app, "Old Cancelled Issue", json!({})).await;
+    app.cancel_newsletter_issue(&cancelled).await;
+    app.fan_out_pending_newsletters().await;
+    sqlx::query!("UPDATE newsletter_issues SET created_at = NOW() - INTERVAL '8 days'")
+        .execute(&app.db_pool)
+        .await
+        .unwrap();
+
+    repository::cleanup_old_newsletter_issues(&app.db_pool)
+        .await
+        .unwrap();
+
+    let response = app.get_newsletter_status(&issue_id).await;
+    assert_eq!(response.status().as_u16(), 404);
+    let response = app.get_archived_newsletter(&issue_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let response = app.get_archived_newsletter(&cancelled).await;
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn archive_is_paginated_newest_first() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+    for title in ["First Issue", "Second Issue", "Third Issue"] {
+        publish(&app, title, json!({})).await;
+    }
+
+    let html = app
+        .get_newsletter_archive("?page=1&limit=2")
+        .await
+        .text()
+        .await
+        .unwrap();
+    assert!(html.find("Third Issue").unwrap() < html.find("Second Issue").unwrap());
+    assert!(!html.contains("First Issue"));
+    assert!(html.contains("?page=2"));
+
+    let html = app
+        .get_newsletter_archive("?page=2&limit=2")
+        .await
+        .text()
+        .await
+        .unwrap();
+    assert!(html.contains("First Issue"));
+    assert!(html.contains("?page=1"));
+}
@@ -336,6 +336,59 @@ async fn get_all_posts_sorts_by_likes_count_descending() {
     );
 }
 
+#[tokio::test]
+async fn get_all_posts_sorts_by_comment_count_descending() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let quiet = app.create_sample_post_custom("Quiet", "No comments").await;
+    let busy = app.create_sample_post_custom("Busy", "Two comments").await;
+    let some = app.create_sample_post_custom("Some", "One comment").await;
+
+    for post_id in [&busy, &busy, &some] {
+        let payload = serde_json::json!({
+            "text": "A comment",
+            "post_id": post_id.to_string()
+        });
+        app.create_comment(&payload).await;
+    }
+
+    let response = app.get_all_posts("?sort=-comments").await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    let posts = body["posts"].as_array().unwrap();
+    assert_eq!(posts[0]["id"], busy.to_string());
+    assert_eq!(posts[0]["comment_count"], 2);
+    assert_eq!(posts[1]["id"], some.to_string());
+    assert_eq!(posts[1]["comment_count"], 1);
+    assert_eq!(posts[2]["id"], quiet.to_string());
+    assert_eq!(posts[2]["comment_count"], 0);
+}
+
+#[tokio::test]
+async fn concurrent_identical_listings_return_the_same_page() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    for _ in 0..3 {
+        app.create_sample_post().await;
+    }
+
+    let responses =
+        futures_util::future::join_all((0..5).map(|_| app.get_all_posts("?sort=-created_at")))
+            .await;
+
+    let mut bodies = Vec::new();
+    for response in responses {
+        assert_eq!(response.status().as_u16(), 200);
+        let body: Value = response.json().await.unwrap();
+        bodies.push(body);
+    }
+    assert_eq!(bodies[0]["posts"].as_array().unwrap().len(), 3);
+    assert!(bodies.iter().all(|b| b["posts"] == bodies[0]["posts"]));
+}
+
 // ============================================================================
 // Title Search
 // ============================================================================
@@ -505,7 +558,11 @@ async fn get_all_posts_returns_correct_post_structure() {
     // Verify all expected fields exist
     assert!(post["id"].is_string());
     assert!(post["title"].is_string());
-    assert!(post["text"].is_string());
+    assert!(post["excerpt"].is_string());
+    assert!(
+        post["text"].is_null(),
+        "List responses should not ship the full text"
+    );
     assert!(post["img"].is_string());
     assert!(post["version"].is_number());
     assert!(post["created_at"].is_string());
@@ -514,6 +571,29 @@ async fn get_all_posts_returns_correct_post_structure() {
     assert!(post["liked_by"].is_array());
 }
 
+#[tokio::test]
+async fn get_all_posts_returns_plain_text_excerpt() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let long_text = format!(
+        "<p>**Intro** to [Rust](https://rust-lang.org)</p> {}",
+        "word ".repeat(60)
+    );
+    app.create_sample_post_custom("Excerpt Post", &long_text)
+        .await;
+
+    let response = app.get_all_posts("").await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    let excerpt = body["posts"][0]["excerpt"].as_str().unwrap();
+
+    assert!(excerpt.starts_with("Intro to Rust word"));
+    assert!(excerpt.ends_with('…'), "Long posts should be truncated");
+    assert_eq!(excerpt.trim_end_matches('…').split_whitespace().count(), 40);
+}
+
 // ============================================================================
 // Combined Filters
 // ============================================================================
@@ -0,0 +1,144 @@
+use techhub::repository;
+use uuid::Uuid;
+
+use crate::helpers;
+
+// Impressions and views of the post, raw and rolled up
+async fn engagement(app: &helpers::TestApp, post_id: Uuid) -> (i64, i64) {
+    let engagement = repository::get_post_engagement(post_id, &app.db_pool)
+        .await
+        .unwrap();
+    (engagement.impressions, engagement.views)
+}
+
+#[tokio::test]
+async fn listing_posts_records_an_impression_per_post_in_order() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let first = app
+        .create_sample_post_custom("First Post", "First body")
+        .await;
+    let second = app
+        .create_sample_post_custom("Second Post", "Second body")
+        .await;
+
+    let response = app.get_all_posts("?sort=created_at").await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let impressions = sqlx::query!(
+        r#"
+        SELECT post_id, user_id, position
+        FROM impressions
+        ORDER BY position
+        "#
+    )
+    .fetch_all(&app.db_pool)
+    .await
+    .unwrap();
+
+    assert_eq!(impressions.len(), 2);
+    assert_eq!(impressions[0].post_id, first);
+    assert_eq!(impressions[0].position, 1);
+    assert_eq!(impressions[1].post_id, second);
+    assert_eq!(impressions[1].position, 2);
+    assert!(
+        impressions
+            .iter()
+            .all(|i| i.user_id == Some(app.test_user.user_id))
+    );
+}
+
+#[tokio::test]
+async fn anonymous_listing_records_impressions_without_user() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let post_id = app.create_sample_post().await;
+    app.logout().await;
+
+    app.get_all_posts("").await;
+
+    let (impressions, _) = engagement(&app, post_id).await;
+    assert_eq!(impressions, 1);
+
+    let user_id = sqlx::query_scalar!("SELECT user_id FROM impressions")
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(user_id, None::<Uuid>);
+}
+
+#[tokio::test]
+async fn opening_a_post_counts_as_a_view_once_flushed() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let post_id = app.create_sample_post().await;
+
+    app.get_all_posts("").await;
+    app.get_all_posts("").await;
+    app.get_post(&post_id).await;
+    app.get_post(&post_id).await;
+
+    // Counted in memory until flushed
+    assert_eq!(engagement(&app, post_id).await, (2, 0));
+    app.post_views.flush(&app.db_pool).await.unwrap();
+    assert_eq!(engagement(&app, post_id).await, (2, 2));
+}
+
+#[tokio::test]
+async fn ctr_counts_recent_and_rolled_up_impressions() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let post_id = app.create_sample_post().await;
+
+    for _ in 0..4 {
+        app.get_all_posts("").await;
+    }
+    // Half of the impressions have been rolled up, the others are still raw
+    sqlx::query!(
+        r#"
+        UPDATE impressions SET shown_at = NOW() - INTERVAL '31 days'
+        WHERE ctid IN (SELECT ctid FROM impressions LIMIT 2)
+        "#
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+    repository::aggregate_expired_impressions(&app.db_pool)
+        .await
+        .unwrap();
+    app.get_post(&post_id).await;
+    app.post_views.flush(&app.db_pool).await.unwrap();
+
+    let engagement = repository::get_post_engagement(post_id, &app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(engagement.ctr(), Some(0.25));
+}
+
+#[tokio::test]
+async fn expired_impressions_are_rolled_up_into_anonymous_daily_counts() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let post_id = app.create_sample_post().await;
+
+    app.get_all_posts("").await;
+    app.get_all_posts("").await;
+    sqlx::query!("UPDATE impressions SET shown_at = NOW() - INTERVAL '31 days'")
+        .execute(&app.db_pool)
+        .await
+        .unwrap();
+    app.get_all_posts("").await;
+
+    repository::aggregate_expired_impressions(&app.db_pool)
+        .await
+        .unwrap();
+
+    let remaining = sqlx::query_scalar!(r#"SELECT COUNT(*) AS "count!" FROM impressions"#)
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(remaining, 1, "Only recent impressions should be kept raw");
+
+    let (impressions, _) = engagement(&app, post_id).await;
+    assert_eq!(impressions, 3, "Aggregation must not lose impressions");
+}
@@ -0,0 +1,111 @@
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::helpers;
+
+async fn create_post_with_license(app: &helpers::TestApp, title: &str, license: &str) -> Uuid {
+    let payload = serde_json::json!({
+        "title": title,
+        "text": "Some text about Rust",
+        "img": "https://example.com/rust.jpg",
+        "license": license
+    });
+    let response = app.create_post(&payload).await;
+    assert_eq!(response.status().as_u16(), 201);
+    let body: Value = response.json().await.unwrap();
+    Uuid::parse_str(body["id"].as_str().unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn posts_without_a_license_get_the_configured_default() {
+    let app = helpers::spawn_app_with(|c| {
+        c.application.default_post_license = techhub::domain::PostLicense::CcBy;
+    })
+    .await;
+    app.login().await;
+
+    let post_id = app.create_sample_post().await;
+
+    let body: Value = app.get_post(&post_id).await.json().await.unwrap();
+    assert_eq!(body["posts"]["license"], "cc-by");
+}
+
+#[tokio::test]
+async fn create_post_stores_the_chosen_license() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let post_id = create_post_with_license(&app, "Reusable post", "CC-BY-SA").await;
+
+    let body: Value = app.get_post(&post_id).await.json().await.unwrap();
+    assert_eq!(body["posts"]["license"], "cc-by-sa");
+}
+
+#[tokio::test]
+async fn create_post_returns_400_for_an_unknown_license() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let payload = serde_json::json!({
+        "title": "Reusable post",
+        "text": "Some text about Rust",
+        "img": "https://example.com/rust.jpg",
+        "license": "gpl"
+    });
+    let response = app.create_post(&payload).await;
+
+    assert_eq!(response.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn update_post_changes_the_license_only_when_given() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let post_id = create_post_with_license(&app, "Reusable post", "cc0").await;
+
+    let payload = serde_json::json!({
+        "title": "Renamed post",
+        "text": "Some text about Rust",
+        "img": "https://example.com/rust.jpg"
+    });
+    app.update_post(&post_id, &payload).await;
+    let body: Value = app.get_post(&post_id).await.json().await.unwrap();
+    assert_eq!(body["posts"]["license"], "cc0");
+
+    let payload = serde_json::json!({
+        "title": "Renamed post",
+        "text": "Some text about Rust",
+        "img": "https://example.com/rust.jpg",
+        "license": "cc-by-nd"
+    });
+    let response = app.update_post(&post_id, &payload).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body: Value = app.get_post(&post_id).await.json().await.unwrap();
+    assert_eq!(body["posts"]["license"], "cc-by-nd");
+}
+
+#[tokio::test]
+async fn get_all_posts_filters_by_license() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    create_post_with_license(&app, "Open post", "cc-by").await;
+    create_post_with_license(&app, "Closed post", "all-rights-reserved").await;
+
+    let response = app.get_all_posts("?license=cc-by").await;
+
+    assert_eq!(response.status().as_u16(), 200);
+    let body: Value = response.json().await.unwrap();
+    let posts = body["posts"].as_array().unwrap();
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0]["title"], "Open post");
+    assert_eq!(posts[0]["license"], "cc-by");
+}
+
+#[tokio::test]
+async fn get_all_posts_returns_400_for_an_unknown_license_filter() {
+    let app = helpers::spawn_app().await;
+
+    let response = app.get_all_posts("?license=gpl").await;
+
+    assert_eq!(response.status().as_u16(), 400);
+}
@@ -1,2 +1,7 @@
 mod get_all_posts;
+mod impression;
+mod license;
 mod post;
+mod post_bodies;
+mod proposals;
+mod suggestion;
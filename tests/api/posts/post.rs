@@ -67,6 +67,7 @@ async fn create_post_persists_valid_post_and_returns_201() {
 
     assert_eq!(body["title"], "My first blog posts");
     assert_eq!(body["post_text"], "This is a test posts");
+    assert_eq!(body["excerpt"], "This is a test posts");
     assert_eq!(body["img"], "https://example.com/img.jpg");
     assert!(body.get("id").is_some(), "Missing 'id' field in response");
     assert!(
@@ -264,6 +265,7 @@ async fn update_post_persists_changes_and_returns_200() {
 
     assert_eq!(post["title"], "Updated Title");
     assert_eq!(post["text"], "Updated posts content");
+    assert_eq!(post["excerpt"], "Updated posts content");
     assert_eq!(post["img"], "https://example.com/updated.jpg");
 
     let record = query!(
@@ -634,6 +636,30 @@ async fn get_post_returns_post_data_successfully() {
     assert!(body["posts"]["title"].is_string());
 }
 
+#[tokio::test]
+async fn get_post_includes_comment_count() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let post_id = app.create_sample_post().await;
+    let payload = serde_json::json!({
+        "text": "A comment",
+        "post_id": post_id.to_string()
+    });
+    let response = app.create_comment(&payload).await;
+    let comment: Value = response.json().await.unwrap();
+    app.create_comment(&payload).await;
+
+    let body: Value = app.get_post(&post_id).await.json().await.unwrap();
+    assert_eq!(body["posts"]["comment_count"], 2);
+
+    let comment_id = Uuid::parse_str(comment["id"].as_str().unwrap()).unwrap();
+    app.delete_comment(&comment_id).await;
+
+    let body: Value = app.get_post(&post_id).await.json().await.unwrap();
+    assert_eq!(body["posts"]["comment_count"], 1);
+}
+
 #[tokio::test]
 async fn get_post_returns_404_for_nonexistent_post() {
     let app = helpers::spawn_app().await;
@@ -0,0 +1,129 @@
+use serde_json::Value;
+use techhub::repository::LARGE_POST_BODY_BYTES;
+use uuid::Uuid;
+
+use crate::helpers;
+
+fn large_text() -> String {
+    "Rust ownership explained. ".repeat(LARGE_POST_BODY_BYTES / 20)
+}
+
+async fn create_post_with_text(app: &helpers::TestApp, text: &str) -> Uuid {
+    let payload = serde_json::json!({
+        "title": "Long read",
+        "text": text,
+        "img": "https://example.com/long.jpg"
+    });
+    let response = app.create_post(&payload).await;
+    assert_eq!(response.status().as_u16(), 201);
+    let body: Value = response.json().await.unwrap();
+    Uuid::parse_str(body["id"].as_str().unwrap()).unwrap()
+}
+
+async fn stored_body_hash(app: &helpers::TestApp, post_id: &Uuid) -> Option<String> {
+    sqlx::query_scalar!("SELECT body_hash FROM posts WHERE id = $1", post_id)
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn large_post_bodies_are_stored_separately_and_read_back() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let text = large_text();
+
+    let post_id = create_post_with_text(&app, &text).await;
+
+    assert!(stored_body_hash(&app, &post_id).await.is_some());
+    let body: Value = app.get_post(&post_id).await.json().await.unwrap();
+    assert_eq!(body["posts"]["text"], text.trim());
+}
+
+#[tokio::test]
+async fn small_post_bodies_stay_inline() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let post_id = app.create_sample_post().await;
+
+    assert!(stored_body_hash(&app, &post_id).await.is_none());
+}
+
+#[tokio::test]
+async fn identical_large_bodies_are_stored_once() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let text = large_text();
+
+    let first = create_post_with_text(&app, &text).await;
+    let second = create_post_with_text(&app, &text).await;
+
+    assert_eq!(
+        stored_body_hash(&app, &first).await,
+        stored_body_hash(&app, &second).await
+    );
+
+    app.logout().await;
+    app.login_admin().await;
+    let response = app.get_post_body_stats().await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let stats: Value = response.json().await.unwrap();
+    let body_bytes = text.trim().len() as i64;
+    assert_eq!(stats["stored_bodies"], 1);
+    assert_eq!(stats["posts_using_stored_bodies"], 2);
+    assert_eq!(stats["logical_bytes"], 2 * body_bytes);
+    assert_eq!(stats["saved_bytes"], body_bytes);
+}
+
+#[tokio::test]
+async fn shrinking_a_large_post_moves_its_body_back_inline() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let post_id = create_post_with_text(&app, &large_text()).await;
+
+    let payload = serde_json::json!({
+        "title": "Short read",
+        "text": "Now it fits in a tweet",
+        "img": "https://example.com/short.jpg"
+    });
+    let response = app.update_post(&post_id, &payload).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    assert!(stored_body_hash(&app, &post_id).await.is_none());
+    let body: Value = app.get_post(&post_id).await.json().await.unwrap();
+    assert_eq!(body["posts"]["text"], "Now it fits in a tweet");
+}
+
+#[tokio::test]
+async fn orphaned_post_bodies_are_pruned_after_the_grace_period() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let post_id = create_post_with_text(&app, &large_text()).await;
+    let hash = stored_body_hash(&app, &post_id).await.unwrap();
+
+    let payload = serde_json::json!({
+        "title": "Short read",
+        "text": "Now it fits in a tweet",
+        "img": "https://example.com/short.jpg"
+    });
+    app.update_post(&post_id, &payload).await;
+    sqlx::query!(
+        "UPDATE post_bodies SET touched_at = NOW() - INTERVAL '2 hours' WHERE hash = $1",
+        hash
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+
+    techhub::repository::cleanup_orphaned_post_bodies(&app.db_pool)
+        .await
+        .unwrap();
+
+    let remaining = sqlx::query_scalar!(r#"SELECT COUNT(*) AS "count!" FROM post_bodies"#)
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(remaining, 0);
+}
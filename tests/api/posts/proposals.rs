@@ -0,0 +1,184 @@
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::helpers;
+
+async fn create_community_post(app: &helpers::TestApp) -> Uuid {
+    let payload = serde_json::json!({
+        "title": "Community guide",
+        "text": "A guide anyone can improve",
+        "img": "https://example.com/guide.jpg",
+        "community": true
+    });
+    let response = app.create_post(&payload).await;
+    assert_eq!(response.status().as_u16(), 201);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["is_community"], true);
+    Uuid::parse_str(body["id"].as_str().unwrap()).unwrap()
+}
+
+fn edit(title: &str) -> Value {
+    serde_json::json!({
+        "title": title,
+        "text": "A guide anyone can improve, now with more detail",
+        "img": "https://example.com/guide.jpg"
+    })
+}
+
+// Logs in as a second user and proposes an edit, returning the proposal id
+async fn propose_as_other_user(app: &helpers::TestApp, post_id: &Uuid, title: &str) -> Uuid {
+    app.logout().await;
+    let other_user = app.create_activated_user().await;
+    app.login_with(&other_user).await;
+
+    let response = app.propose_post_edit(post_id, &edit(title)).await;
+    assert_eq!(response.status().as_u16(), 202);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["status"], "pending");
+
+    app.logout().await;
+    app.login().await;
+    Uuid::parse_str(body["id"].as_str().unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn approved_proposal_is_applied_to_the_post() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let post_id = create_community_post(&app).await;
+    let proposal_id = propose_as_other_user(&app, &post_id, "Improved guide").await;
+
+    let body: Value = app
+        .get_post_edit_proposals(&post_id)
+        .await
+        .json()
+        .await
+        .unwrap();
+    let proposals = body["proposals"].as_array().unwrap();
+    assert_eq!(proposals.len(), 1);
+    assert_eq!(proposals[0]["title"], "Improved guide");
+    assert_eq!(proposals[0]["is_conflicting"], false);
+
+    let response = app.approve_post_edit_proposal(&proposal_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["posts"]["title"], "Improved guide");
+    assert_eq!(body["posts"]["version"], 2);
+
+    let body: Value = app
+        .get_post_edit_proposals(&post_id)
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert!(body["proposals"].as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn approving_a_proposal_written_against_an_older_version_returns_409() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let post_id = create_community_post(&app).await;
+    let first = propose_as_other_user(&app, &post_id, "First take").await;
+    let second = propose_as_other_user(&app, &post_id, "Second take").await;
+
+    app.approve_post_edit_proposal(&first).await;
+
+    let body: Value = app
+        .get_post_edit_proposals(&post_id)
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["proposals"][0]["is_conflicting"], true);
+
+    let response = app.approve_post_edit_proposal(&second).await;
+    assert_eq!(response.status().as_u16(), 409);
+    let body: Value = app.get_post(&post_id).await.json().await.unwrap();
+    assert_eq!(body["posts"]["title"], "First take");
+}
+
+#[tokio::test]
+async fn rejected_proposal_leaves_the_post_unchanged() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let post_id = create_community_post(&app).await;
+    let proposal_id = propose_as_other_user(&app, &post_id, "Unwanted change").await;
+
+    let response = app.reject_post_edit_proposal(&proposal_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let response = app.approve_post_edit_proposal(&proposal_id).await;
+    assert_eq!(response.status().as_u16(), 404);
+    let body: Value = app.get_post(&post_id).await.json().await.unwrap();
+    assert_eq!(body["posts"]["title"], "Community guide");
+}
+
+#[tokio::test]
+async fn only_the_author_reviews_proposals() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let post_id = create_community_post(&app).await;
+    let proposal_id = propose_as_other_user(&app, &post_id, "Improved guide").await;
+
+    app.logout().await;
+    let bystander = app.create_activated_user().await;
+    app.login_with(&bystander).await;
+
+    let response = app.get_post_edit_proposals(&post_id).await;
+    assert_eq!(response.status().as_u16(), 403);
+    let response = app.approve_post_edit_proposal(&proposal_id).await;
+    assert_eq!(response.status().as_u16(), 403);
+    let response = app.reject_post_edit_proposal(&proposal_id).await;
+    assert_eq!(response.status().as_u16(), 403);
+}
+
+#[tokio::test]
+async fn proposals_are_only_accepted_for_community_posts() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let post_id = app.create_sample_post().await;
+
+    app.logout().await;
+    let other_user = app.create_activated_user().await;
+    app.login_with(&other_user).await;
+
+    let response = app.propose_post_edit(&post_id, &edit("Improved")).await;
+    assert_eq!(response.status().as_u16(), 403);
+}
+
+#[tokio::test]
+async fn authors_cannot_propose_edits_to_their_own_posts() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let post_id = create_community_post(&app).await;
+
+    let response = app.propose_post_edit(&post_id, &edit("Improved")).await;
+
+    assert_eq!(response.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn proposals_are_validated_like_post_edits() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let post_id = create_community_post(&app).await;
+
+    app.logout().await;
+    let other_user = app.create_activated_user().await;
+    app.login_with(&other_user).await;
+
+    let response = app.propose_post_edit(&post_id, &edit("")).await;
+    assert_eq!(response.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn proposing_an_edit_requires_login() {
+    let app = helpers::spawn_app().await;
+
+    let response = app
+        .propose_post_edit(&Uuid::new_v4(), &edit("Improved"))
+        .await;
+
+    assert_eq!(response.status().as_u16(), 401);
+}
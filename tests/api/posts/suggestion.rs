@@ -0,0 +1,57 @@
+use serde_json::Value;
+
+use crate::helpers;
+
+#[tokio::test]
+async fn suggest_metadata_returns_401_if_unauthenticated() {
+    let app = helpers::spawn_app().await;
+
+    let response = app.suggest_post_metadata("# A heading").await;
+
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn suggest_metadata_returns_400_for_an_empty_body() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let response = app.suggest_post_metadata("   ").await;
+
+    assert_eq!(response.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn suggest_metadata_returns_titles_and_tags_for_a_draft() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let response = app
+        .suggest_post_metadata(
+            "# Connection pooling in Postgres\n\n\
+             Opening a connection per request is slow. A pool keeps connections open, \
+             and Postgres only sees a handful of them.",
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(
+        body["titles"],
+        serde_json::json!([
+            "Connection pooling in Postgres",
+            "Opening a connection per request is slow"
+        ])
+    );
+    assert_eq!(
+        body["tags"],
+        serde_json::json!(["connection", "postgres", "pooling"])
+    );
+
+    // Only analyzed, never stored as a post
+    let posts = sqlx::query_scalar!(r#"SELECT COUNT(*) AS "count!" FROM posts"#)
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(posts, 0);
+}
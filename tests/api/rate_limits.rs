@@ -0,0 +1,118 @@
+use reqwest::Response;
+use uuid::Uuid;
+use wiremock::{Mock, ResponseTemplate, matchers};
+
+use crate::helpers;
+
+fn header(response: &Response, name: &str) -> u64 {
+    response.headers()[name].to_str().unwrap().parse().unwrap()
+}
+
+#[tokio::test]
+async fn registration_returns_429_with_retry_after_once_the_budget_is_spent() {
+    let app = helpers::spawn_app_with(|c| c.rate_limits.registration.max_requests = 2).await;
+    let payload = serde_json::json!({ "user_name": "", "email": "", "password": "" });
+
+    for remaining in [1, 0] {
+        let response = app.register_user(&payload).await;
+        assert_eq!(response.status().as_u16(), 400);
+        assert_eq!(header(&response, "RateLimit-Limit"), 2);
+        assert_eq!(header(&response, "RateLimit-Remaining"), remaining);
+    }
+
+    let response = app.register_user(&payload).await;
+    assert_eq!(response.status().as_u16(), 429);
+    assert_eq!(header(&response, "RateLimit-Remaining"), 0);
+    let retry_after = header(&response, "Retry-After");
+    assert!((1..=3600).contains(&retry_after), "{retry_after}");
+    assert_eq!(header(&response, "RateLimit-Reset"), retry_after);
+}
+
+#[tokio::test]
+async fn registration_budget_is_counted_per_client_address() {
+    let app = helpers::spawn_app_with(|c| c.rate_limits.registration.max_requests = 1).await;
+    let payload = serde_json::json!({ "user_name": "", "email": "", "password": "" });
+
+    for (ip_address, status) in [
+        ("203.0.113.7", 400),
+        ("203.0.113.7", 429),
+        ("198.51.100.1", 400),
+    ] {
+        let response = app.register_user_from(ip_address, &payload).await;
+        assert_eq!(response.status().as_u16(), status, "{ip_address}");
+    }
+}
+
+#[tokio::test]
+async fn login_returns_429_once_the_budget_is_spent() {
+    let app = helpers::spawn_app_with(|c| c.rate_limits.login.max_requests = 1).await;
+    let wrong_password = serde_json::json!({
+        "user_name": &app.test_user.user_name,
+        "password": Uuid::new_v4().to_string(),
+    });
+
+    let response = app.login_with(&wrong_password).await;
+    assert_eq!(response.status().as_u16(), 401);
+    assert_eq!(header(&response, "RateLimit-Remaining"), 0);
+
+    // The password is not checked once the budget is spent
+    let payload = serde_json::json!({
+        "user_name": &app.test_user.user_name,
+        "password": &app.test_user.password,
+    });
+    let response = app.login_with(&payload).await;
+    assert_eq!(response.status().as_u16(), 429);
+    assert!(response.headers().contains_key("Retry-After"));
+}
+
+#[tokio::test]
+async fn subscription_email_requests_return_429_once_the_budget_is_spent() {
+    let app = helpers::spawn_app_with(|c| c.rate_limits.subscription_email.max_requests = 1).await;
+    app.login().await;
+
+    Mock::given(matchers::path("/email"))
+        .and(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&app.email_server)
+        .await;
+
+    let response = app.request_subscription_email().await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let response = app.request_subscription_email().await;
+    assert_eq!(response.status().as_u16(), 429);
+}
+
+#[tokio::test]
+async fn password_reset_and_sign_in_link_requests_share_a_budget() {
+    let app = helpers::spawn_app_with(|c| c.rate_limits.account_email.max_requests = 2).await;
+
+    let response = app.forgot_password(&app.test_user.email).await;
+    assert_eq!(response.status().as_u16(), 202);
+    let response = app.request_magic_link("nobody@example.com").await;
+    assert_eq!(response.status().as_u16(), 202);
+
+    let response = app.forgot_password("nobody@example.com").await;
+    assert_eq!(response.status().as_u16(), 429);
+    let response = app.request_magic_link(&app.test_user.email).await;
+    assert_eq!(response.status().as_u16(), 429);
+}
+
+#[tokio::test]
+async fn comment_creation_budget_is_counted_per_user() {
+    let app = helpers::spawn_app_with(|c| c.rate_limits.comment_creation.max_requests = 1).await;
+    let other_user = app.create_activated_user().await;
+    app.login().await;
+    let post_id = app.create_sample_post().await;
+    let payload = serde_json::json!({ "text": "Nice post", "post_id": post_id });
+
+    assert_eq!(app.create_comment(&payload).await.status().as_u16(), 201);
+    assert_eq!(app.create_comment(&payload).await.status().as_u16(), 429);
+
+    // Another user from the same address has a budget of their own
+    app.logout().await;
+    let response = app.login_with(&other_user).await;
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(app.create_comment(&payload).await.status().as_u16(), 201);
+}
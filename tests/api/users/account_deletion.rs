@@ -0,0 +1,307 @@
+use serde_json::Value;
+use techhub::domain::{ANONYMOUS_USER_ID, AccountDeletionPolicy};
+use uuid::Uuid;
+
+use crate::helpers;
+
+// The test user writes a post and a comment, likes the post, and is queued for a newsletter
+async fn create_content(app: &helpers::TestApp) -> Uuid {
+    app.login().await;
+    let post_id = app.create_sample_post().await;
+    let response = app
+        .create_comment(&serde_json::json!({
+            "text": "A comment by the soon deleted user",
+            "post_id": post_id.to_string()
+        }))
+        .await;
+    assert_eq!(response.status().as_u16(), 201);
+    app.like_post(&post_id).await;
+
+    let issue_id = Uuid::new_v4();
+    sqlx::query!(
+        r#"
+        INSERT INTO newsletter_issues (id, title, text_content, html_content)
+        VALUES ($1, 'Issue', 'Text', '<p>Html</p>')
+        "#,
+        issue_id
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+    sqlx::query!(
+        "INSERT INTO issue_delivery_queue (newsletter_issue_id, user_email) VALUES ($1, $2)",
+        issue_id,
+        app.test_user.email
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+
+    post_id
+}
+
+#[tokio::test]
+async fn deleted_account_is_anonymized_and_its_content_removed() {
+    let app = helpers::spawn_app().await;
+    let post_id = create_content(&app).await;
+
+    let response = app.delete_account(&app.test_user.password).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["posts_removed"], 1);
+    assert_eq!(body["comments_removed"], 1);
+    assert_eq!(body["likes_withdrawn"], 1);
+    assert_eq!(body["queued_deliveries_purged"], 1);
+
+    let user = sqlx::query!(
+        "SELECT user_name, email, is_activated FROM users WHERE id = $1",
+        app.test_user.user_id
+    )
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap();
+    assert_ne!(user.user_name, app.test_user.user_name);
+    assert_ne!(user.email, app.test_user.email);
+    assert!(!user.is_activated);
+
+    assert_eq!(app.get_post(&post_id).await.status().as_u16(), 404);
+    let response = app.get_user_profile(&app.test_user.user_id).await;
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn deleted_account_leaves_no_password_history_or_submissions_behind() {
+    let app = helpers::spawn_app().await;
+    let post_id = create_content(&app).await;
+    sqlx::query!(
+        "INSERT INTO password_history (user_id, password_hash) VALUES ($1, 'old-hash')",
+        app.test_user.user_id
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+    sqlx::query!(
+        r#"
+        INSERT INTO comment_submissions
+            (id, post_id, author_name, author_email, text, ip_address, source)
+        VALUES ($1, $2, 'reader', $3, 'Nice post', '203.0.113.7', 'embed')
+        "#,
+        Uuid::new_v4(),
+        post_id,
+        app.test_user.email.to_uppercase()
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+
+    let response = app.delete_account(&app.test_user.password).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let leftovers = sqlx::query_scalar!(
+        r#"
+        SELECT (SELECT COUNT(*) FROM password_history WHERE user_id = $1)
+             + (SELECT COUNT(*) FROM comment_submissions WHERE LOWER(author_email) = LOWER($2))
+             AS "count!"
+        "#,
+        app.test_user.user_id,
+        app.test_user.email
+    )
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap();
+    assert_eq!(leftovers, 0);
+}
+
+#[tokio::test]
+async fn deleted_account_leaves_no_newsletter_deliveries_behind() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let email = app.test_user.email.to_uppercase();
+    let (sent_issue, failed_issue) = (Uuid::new_v4(), Uuid::new_v4());
+    sqlx::query!(
+        r#"
+        INSERT INTO newsletter_issues (id, title, text_content, html_content)
+        VALUES ($1, 'Sent', 'Text', '<p>Html</p>'), ($2, 'Failed', 'Text', '<p>Html</p>')
+        "#,
+        sent_issue,
+        failed_issue
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+    sqlx::query!(
+        r#"
+        WITH deliveries AS (
+            INSERT INTO newsletter_delivery_log (newsletter_issue_id, user_email, outcome, attempts)
+            VALUES ($1, $3, 'sent', 1), ($2, $3, 'failed', 3)
+        ), failures AS (
+            INSERT INTO issue_delivery_failures (newsletter_issue_id, user_email, n_retries, last_error)
+            VALUES ($2, $3, 3, 'Mailbox unavailable')
+        )
+        INSERT INTO newsletter_tracking_tokens (token, newsletter_issue_id, user_email)
+        VALUES ('sent-token', $1, $3), ('failed-token', $2, $3)
+        "#,
+        sent_issue,
+        failed_issue,
+        email
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+
+    let response = app.delete_account(&app.test_user.password).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let leftovers = sqlx::query_scalar!(
+        r#"
+        SELECT (SELECT COUNT(*) FROM newsletter_delivery_log WHERE LOWER(user_email) = LOWER($1))
+             + (SELECT COUNT(*) FROM newsletter_tracking_tokens WHERE LOWER(user_email) = LOWER($1))
+             + (SELECT COUNT(*) FROM issue_delivery_failures WHERE LOWER(user_email) = LOWER($1))
+             AS "count!"
+        "#,
+        app.test_user.email
+    )
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap();
+    assert_eq!(leftovers, 0);
+
+    // The email that went out still counts towards its issue, the token left without an address
+    let tokens = sqlx::query_scalar!("SELECT token FROM newsletter_tracking_tokens")
+        .fetch_all(&app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(tokens, vec!["sent-token".to_string()]);
+    let issues = sqlx::query!(
+        "SELECT forgotten_sent, forgotten_failed FROM newsletter_issues WHERE id IN ($1, $2) ORDER BY title DESC",
+        sent_issue,
+        failed_issue
+    )
+    .fetch_all(&app.db_pool)
+    .await
+    .unwrap();
+    assert_eq!(
+        issues
+            .iter()
+            .map(|issue| (issue.forgotten_sent, issue.forgotten_failed))
+            .collect::<Vec<_>>(),
+        vec![(1, 0), (0, 1)]
+    );
+}
+
+#[tokio::test]
+async fn deleted_account_is_logged_out_and_cannot_log_in_again() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    app.delete_account(&app.test_user.password).await;
+
+    assert_eq!(app.access_protected().await.status().as_u16(), 401);
+    let response = app
+        .login_with(&serde_json::json!({
+            "user_name": &app.test_user.user_name,
+            "password": &app.test_user.password,
+        }))
+        .await;
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn deleted_account_has_every_session_and_token_revoked() {
+    let app = helpers::spawn_app().await;
+    let credentials = serde_json::json!({
+        "user_name": &app.test_user.user_name,
+        "password": &app.test_user.password,
+    });
+    let response = app.issue_access_token(&credentials).await;
+    assert_eq!(response.status().as_u16(), 200);
+    app.login().await;
+    app.enter_sudo_mode(&serde_json::json!({ "password": &app.test_user.password }))
+        .await;
+    let response = app
+        .create_personal_access_token(&serde_json::json!({ "name": "ci", "scopes": ["read"] }))
+        .await;
+    assert_eq!(response.status().as_u16(), 201);
+
+    app.delete_account(&app.test_user.password).await;
+
+    let active = sqlx::query_scalar!(
+        r#"
+        SELECT (SELECT COUNT(*) FROM user_sessions WHERE user_id = $1 AND revoked_at IS NULL)
+             + (SELECT COUNT(*) FROM refresh_tokens WHERE user_id = $1 AND revoked_at IS NULL)
+             + (SELECT COUNT(*) FROM personal_access_tokens WHERE user_id = $1 AND revoked_at IS NULL)
+             AS "count!"
+        "#,
+        app.test_user.user_id
+    )
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap();
+    assert_eq!(active, 0);
+}
+
+#[tokio::test]
+async fn reassign_policy_keeps_content_under_the_anonymous_author() {
+    let app = helpers::spawn_app_with(|c| {
+        c.application.account_deletion_policy = AccountDeletionPolicy::ReassignContent;
+    })
+    .await;
+    let post_id = create_content(&app).await;
+
+    let response = app.delete_account(&app.test_user.password).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["posts_reassigned"], 1);
+    assert_eq!(body["comments_reassigned"], 1);
+
+    let body: Value = app.get_post(&post_id).await.json().await.unwrap();
+    assert_eq!(body["posts"]["created_by"], ANONYMOUS_USER_ID.to_string());
+    assert_eq!(body["posts"]["comment_count"], 1);
+}
+
+#[tokio::test]
+async fn account_deletion_returns_401_for_a_wrong_password() {
+    let app = helpers::spawn_app().await;
+    let post_id = create_content(&app).await;
+
+    let response = app.delete_account(&Uuid::new_v4().to_string()).await;
+
+    assert_eq!(response.status().as_u16(), 401);
+    assert_eq!(app.get_post(&post_id).await.status().as_u16(), 200);
+    assert_eq!(app.access_protected().await.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn account_deletion_password_checks_are_throttled() {
+    let app = helpers::spawn_app_with(|c| c.login_throttle.max_attempts_per_user_name = 3).await;
+    app.login().await;
+
+    for _ in 0..2 {
+        let response = app.delete_account(&Uuid::new_v4().to_string()).await;
+        assert_eq!(response.status().as_u16(), 401);
+    }
+
+    let response = app.delete_account(&app.test_user.password).await;
+    assert_eq!(response.status().as_u16(), 429);
+    assert!(response.headers().contains_key("retry-after"));
+    assert_eq!(app.access_protected().await.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn admin_accounts_cannot_be_deleted() {
+    let app = helpers::spawn_app().await;
+    app.login_admin().await;
+
+    let response = app.delete_account("athfan123").await;
+
+    assert_eq!(response.status().as_u16(), 403);
+}
+
+#[tokio::test]
+async fn account_deletion_requires_login() {
+    let app = helpers::spawn_app().await;
+
+    let response = app.delete_account(&app.test_user.password).await;
+
+    assert_eq!(response.status().as_u16(), 401);
+}
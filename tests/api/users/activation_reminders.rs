@@ -0,0 +1,251 @@
+use linkify::{LinkFinder, LinkKind};
+use reqwest::Url;
+use serde_json::Value;
+use wiremock::{Mock, ResponseTemplate, matchers};
+
+use crate::helpers::{self, TestApp};
+
+struct ReminderLinks {
+    activation: Url,
+    opt_out: Url,
+}
+
+async fn registered_hours_ago(app: &TestApp, user: &Value, hours: i32) {
+    sqlx::query!(
+        "UPDATE users SET created_at = NOW() - make_interval(hours => $2) WHERE email = $1",
+        user["email"].as_str().unwrap(),
+        hours
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+}
+
+async fn reminded_hours_ago(app: &TestApp, user: &Value, hours: i32) {
+    sqlx::query!(
+        r#"
+        UPDATE activation_reminders SET sent_at = NOW() - make_interval(hours => $2)
+        WHERE user_id = (SELECT id FROM users WHERE email = $1)
+        "#,
+        user["email"].as_str().unwrap(),
+        hours
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+}
+
+async fn last_email_links(app: &TestApp) -> ReminderLinks {
+    let email_request = app
+        .email_server
+        .received_requests()
+        .await
+        .unwrap()
+        .pop()
+        .unwrap();
+    let body: Value = serde_json::from_slice(&email_request.body).unwrap();
+
+    let mut links = LinkFinder::new()
+        .links(body["HtmlBody"].as_str().unwrap())
+        .filter(|l| *l.kind() == LinkKind::Url)
+        .map(|l| {
+            let mut link = Url::parse(l.as_str()).unwrap();
+            link.set_port(Some(app.port)).unwrap();
+            link
+        });
+    let links = ReminderLinks {
+        activation: links.next().unwrap(),
+        opt_out: links.next().unwrap(),
+    };
+    assert_eq!(links.activation.path(), "/v1/user/activate");
+    assert_eq!(
+        links.opt_out.path(),
+        "/v1/user/activation-reminders/opt-out"
+    );
+    links
+}
+
+async fn mock_email_delivery(app: &TestApp) {
+    Mock::given(matchers::path("/email"))
+        .and(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&app.email_server)
+        .await;
+}
+
+#[tokio::test]
+async fn unactivated_users_are_reminded_a_day_after_registering_with_a_fresh_link() {
+    let app = helpers::spawn_app().await;
+    let (user, original_links) = app.create_inactivated_user().await;
+    registered_hours_ago(&app, &user, 25).await;
+    mock_email_delivery(&app).await;
+
+    assert_eq!(app.send_activation_reminders().await, 1);
+
+    let reminder_links = last_email_links(&app).await;
+    assert_ne!(reminder_links.activation, original_links.html);
+
+    let response = reqwest::get(original_links.html).await.unwrap();
+    assert_eq!(response.status().as_u16(), 401);
+    let response = reqwest::get(reminder_links.activation).await.unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+
+    let is_activated = sqlx::query_scalar!(
+        "SELECT is_activated FROM users WHERE email = $1",
+        user["email"].as_str().unwrap()
+    )
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap();
+    assert!(is_activated);
+}
+
+#[tokio::test]
+async fn users_are_not_reminded_within_a_day_of_registering() {
+    let app = helpers::spawn_app().await;
+    let (user, _) = app.create_inactivated_user().await;
+    registered_hours_ago(&app, &user, 23).await;
+
+    assert_eq!(app.send_activation_reminders().await, 0);
+}
+
+#[tokio::test]
+async fn users_are_reminded_at_most_twice() {
+    let app = helpers::spawn_app().await;
+    let (user, _) = app.create_inactivated_user().await;
+    mock_email_delivery(&app).await;
+
+    // Already past both delays when first picked up, the second reminder still waits its turn
+    registered_hours_ago(&app, &user, 80).await;
+    assert_eq!(app.send_activation_reminders().await, 1);
+    assert_eq!(app.send_activation_reminders().await, 0);
+
+    reminded_hours_ago(&app, &user, 49).await;
+    assert_eq!(app.send_activation_reminders().await, 1);
+
+    reminded_hours_ago(&app, &user, 100).await;
+    registered_hours_ago(&app, &user, 150).await;
+    assert_eq!(app.send_activation_reminders().await, 0);
+}
+
+#[tokio::test]
+async fn activated_users_are_not_reminded() {
+    let app = helpers::spawn_app().await;
+    let user = app.create_activated_user().await;
+    registered_hours_ago(&app, &user, 25).await;
+
+    assert_eq!(app.send_activation_reminders().await, 0);
+}
+
+#[tokio::test]
+async fn users_registered_over_a_week_ago_are_not_reminded() {
+    let app = helpers::spawn_app().await;
+    let (user, _) = app.create_inactivated_user().await;
+    registered_hours_ago(&app, &user, 8 * 24).await;
+
+    assert_eq!(app.send_activation_reminders().await, 0);
+}
+
+#[tokio::test]
+async fn failed_reminders_are_retried_on_the_next_run() {
+    let app = helpers::spawn_app().await;
+    let (user, _) = app.create_inactivated_user().await;
+    registered_hours_ago(&app, &user, 25).await;
+
+    let failing_guard = Mock::given(matchers::path("/email"))
+        .and(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount_as_scoped(&app.email_server)
+        .await;
+    assert_eq!(app.send_activation_reminders().await, 0);
+    drop(failing_guard);
+
+    mock_email_delivery(&app).await;
+    assert_eq!(app.send_activation_reminders().await, 1);
+
+    let reminder_links = last_email_links(&app).await;
+    let response = reqwest::get(reminder_links.activation).await.unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn users_who_opt_out_are_not_reminded_again() {
+    let app = helpers::spawn_app().await;
+    let (user, _) = app.create_inactivated_user().await;
+    registered_hours_ago(&app, &user, 80).await;
+    mock_email_delivery(&app).await;
+    assert_eq!(app.send_activation_reminders().await, 1);
+
+    let reminder_links = last_email_links(&app).await;
+    let response = reqwest::get(reminder_links.opt_out.clone()).await.unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+    // Clicking the link twice is harmless
+    let response = reqwest::get(reminder_links.opt_out).await.unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+
+    reminded_hours_ago(&app, &user, 49).await;
+    assert_eq!(app.send_activation_reminders().await, 0);
+
+    // Opting out does not stop the user from activating
+    let response = reqwest::get(reminder_links.activation).await.unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn opting_out_with_an_unknown_token_is_rejected() {
+    let app = helpers::spawn_app().await;
+
+    let response = app
+        .send_get("v1/user/activation-reminders/opt-out?token=unknown")
+        .await;
+
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn activation_links_cannot_be_used_to_opt_out() {
+    let app = helpers::spawn_app().await;
+    let (user, _) = app.create_inactivated_user().await;
+    registered_hours_ago(&app, &user, 25).await;
+    mock_email_delivery(&app).await;
+    assert_eq!(app.send_activation_reminders().await, 1);
+
+    let reminder_links = last_email_links(&app).await;
+    let mut opt_out = reminder_links.opt_out;
+    opt_out.set_query(reminder_links.activation.query());
+    let response = reqwest::get(opt_out).await.unwrap();
+
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn reminder_stats_report_conversions() {
+    let app = helpers::spawn_app().await;
+    let (converting_user, _) = app.create_inactivated_user().await;
+    let (ignoring_user, _) = app.create_inactivated_user().await;
+    mock_email_delivery(&app).await;
+
+    registered_hours_ago(&app, &converting_user, 25).await;
+    assert_eq!(app.send_activation_reminders().await, 1);
+    let reminder_links = last_email_links(&app).await;
+    reqwest::get(reminder_links.activation)
+        .await
+        .unwrap()
+        .error_for_status()
+        .unwrap();
+
+    registered_hours_ago(&app, &ignoring_user, 25).await;
+    assert_eq!(app.send_activation_reminders().await, 1);
+
+    app.login_admin().await;
+    let response = app.get_activation_reminder_stats().await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    let reminders = body["reminders"].as_array().unwrap();
+    assert_eq!(reminders.len(), 1);
+    assert_eq!(reminders[0]["reminder_number"], 1);
+    assert_eq!(reminders[0]["sent"], 2);
+    assert_eq!(reminders[0]["converted"], 1);
+    assert_eq!(reminders[0]["conversion_rate"], 0.5);
+}
@@ -28,6 +28,10 @@ async fn change_password_returns_401_for_invalid_current_password() {
     let wrong_password = Uuid::new_v4().to_string();
 
     app.login().await;
+    let response = app
+        .enter_sudo_mode(&serde_json::json!({ "password": &app.test_user.password }))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
 
     let response = app
         .change_password(&serde_json::json!({
@@ -49,6 +53,10 @@ async fn change_password_changes_password_and_returns_200() {
     let new_password = Uuid::new_v4().to_string();
 
     app.login().await;
+    let response = app
+        .enter_sudo_mode(&serde_json::json!({ "password": &app.test_user.password }))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
 
     // Change password
     let response = app
@@ -76,3 +84,133 @@ async fn change_password_changes_password_and_returns_200() {
     let response = app.access_protected().await;
     assert_eq!(response.status().as_u16(), 200);
 }
+
+#[tokio::test]
+async fn change_password_requires_reauthentication() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let response = app
+        .change_password(&serde_json::json!({
+            "current_password": &app.test_user.password,
+            "new_password": Uuid::new_v4().to_string(),
+        }))
+        .await;
+
+    assert_eq!(response.status().as_u16(), 401);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["error"], "reauthentication_required");
+}
+
+#[tokio::test]
+async fn change_password_returns_400_for_easily_guessed_new_password() {
+    let app = helpers::spawn_app().await;
+
+    app.login().await;
+    let response = app
+        .enter_sudo_mode(&serde_json::json!({ "password": &app.test_user.password }))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let response = app
+        .change_password(&serde_json::json!({
+        "current_password": &app.test_user.password,
+        "new_password": "P@ssw0rd2024",
+        }))
+        .await;
+    assert_eq!(response.status().as_u16(), 400);
+
+    // The current password still works
+    app.logout().await;
+    app.login().await;
+}
+
+#[tokio::test]
+async fn change_password_returns_400_for_the_current_password() {
+    let app = helpers::spawn_app().await;
+
+    app.login().await;
+    app.enter_sudo_mode(&serde_json::json!({ "password": &app.test_user.password }))
+        .await;
+
+    let response = app
+        .change_password(&serde_json::json!({
+        "current_password": &app.test_user.password,
+        "new_password": &app.test_user.password,
+        }))
+        .await;
+    assert_eq!(response.status().as_u16(), 400);
+
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert!(
+        body["message"]
+            .as_str()
+            .unwrap()
+            .contains("must differ from your last 5 passwords")
+    );
+}
+
+#[tokio::test]
+async fn change_password_rejects_recent_passwords_but_allows_older_ones() {
+    let app = helpers::spawn_app_with(|c| c.application.password_history_size = 2).await;
+    let first = app.test_user.password.clone();
+    let second = Uuid::new_v4().to_string();
+    let third = Uuid::new_v4().to_string();
+
+    app.login().await;
+    app.enter_sudo_mode(&serde_json::json!({ "password": &first }))
+        .await;
+
+    assert_eq!(
+        change_password_from(&app, &first, &second)
+            .await
+            .status()
+            .as_u16(),
+        200
+    );
+    // The password before the current one is still within the last 2
+    assert_eq!(
+        change_password_from(&app, &second, &first)
+            .await
+            .status()
+            .as_u16(),
+        400
+    );
+    assert_eq!(
+        change_password_from(&app, &second, &third)
+            .await
+            .status()
+            .as_u16(),
+        200
+    );
+    // Three passwords back, so it can be used again
+    assert_eq!(
+        change_password_from(&app, &third, &first)
+            .await
+            .status()
+            .as_u16(),
+        200
+    );
+
+    // Only the hashes a reuse check can look at are kept
+    let history_size = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!" FROM password_history WHERE user_id = $1"#,
+        app.test_user.user_id
+    )
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap();
+    assert_eq!(history_size, 1);
+}
+
+async fn change_password_from(
+    app: &helpers::TestApp,
+    current: &str,
+    new: &str,
+) -> reqwest::Response {
+    app.change_password(&serde_json::json!({
+        "current_password": current,
+        "new_password": new,
+    }))
+    .await
+}
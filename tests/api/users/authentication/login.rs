@@ -67,6 +67,32 @@ async fn login_does_not_leak_internal_error_details_on_auth_failure() {
     );
 }
 
+#[tokio::test]
+async fn login_returns_unauthorized_for_imported_user_without_a_password() {
+    let app = helpers::spawn_app().await;
+
+    // Comment imports create users with a placeholder instead of a password hash
+    sqlx::query!(
+        r#"
+        INSERT INTO users (id, user_name, email, password_hash, is_activated)
+        VALUES ($1, 'imported-user', 'imported@example.com', '!imported', true)
+        "#,
+        Uuid::new_v4(),
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+
+    let payload = serde_json::json!({
+        "user_name": "imported-user",
+        "password": "!imported",
+    });
+
+    let response = app.login_with(&payload).await;
+
+    assert_eq!(response.status().as_u16(), 401);
+}
+
 #[tokio::test]
 async fn logout_clears_session_state() {
     let app = helpers::spawn_app().await;
@@ -246,3 +272,116 @@ async fn login_rejects_wrong_field_names() {
         "Expected 400 or 422 for incorrect field names in JSON"
     );
 }
+
+#[tokio::test]
+async fn login_is_throttled_per_client_address() {
+    let app = helpers::spawn_app_with(|c| c.login_throttle.max_attempts_per_ip = 3).await;
+    let wrong_password = serde_json::json!({
+        "user_name": Uuid::new_v4().to_string(),
+        "password": Uuid::new_v4().to_string(),
+    });
+
+    for _ in 0..3 {
+        let response = app.login_from("203.0.113.7", &wrong_password).await;
+        assert_eq!(response.status().as_u16(), 401);
+    }
+
+    // Even the right password is not checked once the address is over its limit
+    let payload = serde_json::json!({
+        "user_name": &app.test_user.user_name,
+        "password": &app.test_user.password,
+    });
+    let response = app.login_from("203.0.113.7", &payload).await;
+    assert_eq!(response.status().as_u16(), 429);
+
+    let retry_after: i32 = response.headers()["Retry-After"]
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!((1..=60).contains(&retry_after), "{retry_after}");
+
+    // Other clients are unaffected
+    let response = app.login_from("198.51.100.1", &payload).await;
+    assert_eq!(response.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn forwarded_addresses_from_untrusted_peers_do_not_escape_the_throttle() {
+    let app = helpers::spawn_app_with(|c| {
+        c.login_throttle.max_attempts_per_ip = 1;
+        c.application.trusted_proxies = vec![];
+    })
+    .await;
+    let wrong_password = serde_json::json!({
+        "user_name": Uuid::new_v4().to_string(),
+        "password": Uuid::new_v4().to_string(),
+    });
+
+    let response = app.login_from("203.0.113.7", &wrong_password).await;
+    assert_eq!(response.status().as_u16(), 401);
+
+    // Without a trusted proxy in front, every attempt comes from the peer whatever the header says
+    let response = app.login_from("198.51.100.1", &wrong_password).await;
+    assert_eq!(response.status().as_u16(), 429);
+}
+
+#[tokio::test]
+async fn login_is_throttled_per_user_name_across_addresses() {
+    let app = helpers::spawn_app_with(|c| c.login_throttle.max_attempts_per_user_name = 2).await;
+    let wrong_password = serde_json::json!({
+        "user_name": &app.test_user.user_name,
+        "password": Uuid::new_v4().to_string(),
+    });
+
+    for ip_address in ["203.0.113.1", "203.0.113.2"] {
+        let response = app.login_from(ip_address, &wrong_password).await;
+        assert_eq!(response.status().as_u16(), 401);
+    }
+
+    // User names are matched regardless of case
+    let payload = serde_json::json!({
+        "user_name": app.test_user.user_name.to_uppercase(),
+        "password": &app.test_user.password,
+    });
+    let response = app.login_from("203.0.113.3", &payload).await;
+    assert_eq!(response.status().as_u16(), 429);
+    assert!(response.headers().contains_key("Retry-After"));
+}
+
+#[tokio::test]
+async fn login_attempts_are_counted_again_once_the_window_ends() {
+    let app = helpers::spawn_app_with(|c| {
+        c.login_throttle.max_attempts_per_ip = 1;
+        c.login_throttle.window_seconds = 1;
+    })
+    .await;
+    let payload = serde_json::json!({
+        "user_name": &app.test_user.user_name,
+        "password": &app.test_user.password,
+    });
+
+    assert_eq!(
+        app.login_from("203.0.113.7", &payload)
+            .await
+            .status()
+            .as_u16(),
+        200
+    );
+    assert_eq!(
+        app.login_from("203.0.113.7", &payload)
+            .await
+            .status()
+            .as_u16(),
+        429
+    );
+
+    tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+    assert_eq!(
+        app.login_from("203.0.113.7", &payload)
+            .await
+            .status()
+            .as_u16(),
+        200
+    );
+}
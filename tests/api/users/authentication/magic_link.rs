@@ -0,0 +1,150 @@
+use serde_json::{Value, json};
+use wiremock::{Mock, ResponseTemplate, matchers};
+
+use crate::helpers::{self, TestApp};
+
+async fn spawn_app_with_email() -> TestApp {
+    let app = helpers::spawn_app().await;
+
+    Mock::given(matchers::path("/email"))
+        .and(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&app.email_server)
+        .await;
+
+    app
+}
+
+// The token carried by the sign-in link of the `nth` email sent
+async fn magic_link_token(app: &TestApp, nth: usize) -> String {
+    let email_request = &app.email_server.received_requests().await.unwrap()[nth];
+    let link = app.get_confirmation_links(email_request).html;
+    assert_eq!(link.path(), "/login/magic-link");
+
+    link.query_pairs()
+        .find(|(key, _)| key == "token")
+        .map(|(_, token)| token.into_owned())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn emailed_link_logs_the_user_in() {
+    let app = spawn_app_with_email().await;
+
+    let response = app.request_magic_link(&app.test_user.email).await;
+    app.send_account_emails().await;
+    assert_eq!(response.status().as_u16(), 202);
+    assert_eq!(app.access_protected().await.status().as_u16(), 401);
+
+    let token = magic_link_token(&app, 0).await;
+    let response = app.login_with_magic_link(&token).await;
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(app.access_protected().await.status().as_u16(), 200);
+
+    let body: Value = app.get_security_activity().await.json().await.unwrap();
+    assert_eq!(body["recent_logins"][0]["succeeded"], true);
+}
+
+#[tokio::test]
+async fn links_can_only_be_used_once() {
+    let app = spawn_app_with_email().await;
+    app.request_magic_link(&app.test_user.email).await;
+    app.send_account_emails().await;
+    let token = magic_link_token(&app, 0).await;
+
+    let response = app.login_with_magic_link(&token).await;
+    assert_eq!(response.status().as_u16(), 200);
+    app.logout().await;
+
+    let response = app.login_with_magic_link(&token).await;
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn only_the_newest_link_works() {
+    let app = spawn_app_with_email().await;
+    app.request_magic_link(&app.test_user.email).await;
+    app.send_account_emails().await;
+    app.request_magic_link(&app.test_user.email).await;
+    app.send_account_emails().await;
+
+    let response = app
+        .login_with_magic_link(&magic_link_token(&app, 0).await)
+        .await;
+    assert_eq!(response.status().as_u16(), 401);
+
+    let response = app
+        .login_with_magic_link(&magic_link_token(&app, 1).await)
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn expired_links_are_rejected() {
+    let app = spawn_app_with_email().await;
+    app.request_magic_link(&app.test_user.email).await;
+    app.send_account_emails().await;
+    let token = magic_link_token(&app, 0).await;
+
+    sqlx::query!(
+        "UPDATE tokens SET expires_at = NOW() - INTERVAL '1 minute' WHERE token = $1",
+        token
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+
+    let response = app.login_with_magic_link(&token).await;
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn password_reset_links_cannot_be_used_to_log_in() {
+    let app = spawn_app_with_email().await;
+    app.forgot_password(&app.test_user.email).await;
+    app.send_account_emails().await;
+    let email_request = &app.email_server.received_requests().await.unwrap()[0];
+    let link = app.get_confirmation_links(email_request).html;
+    let (_, token) = link.query_pairs().find(|(key, _)| key == "token").unwrap();
+
+    let response = app.login_with_magic_link(&token).await;
+
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn requesting_a_link_does_not_reveal_unknown_addresses() {
+    let app = spawn_app_with_email().await;
+
+    let response = app.request_magic_link("nobody@example.com").await;
+    app.send_account_emails().await;
+
+    assert_eq!(response.status().as_u16(), 202);
+    assert!(
+        app.email_server
+            .received_requests()
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn suspended_users_cannot_log_in_with_a_link() {
+    let app = spawn_app_with_email().await;
+    app.request_magic_link(&app.test_user.email).await;
+    app.send_account_emails().await;
+    let token = magic_link_token(&app, 0).await;
+
+    app.login_admin().await;
+    let response = app
+        .ban_user(&app.test_user.user_id, &json!({ "reason": "spam" }))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    app.logout().await;
+
+    let response = app.login_with_magic_link(&token).await;
+    assert_eq!(response.status().as_u16(), 403);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["error"], "account_suspended");
+}
@@ -1,3 +1,7 @@
 mod change_password;
 mod login;
+mod magic_link;
+mod password_reset;
 mod register;
+mod sudo;
+mod token;
@@ -0,0 +1,236 @@
+use serde_json::{Value, json};
+use wiremock::{Mock, ResponseTemplate, matchers};
+
+use crate::helpers::{self, TestApp};
+
+const NEW_PASSWORD: &str = "a-brand-new-password";
+
+async fn spawn_app_with_email() -> TestApp {
+    let app = helpers::spawn_app().await;
+
+    Mock::given(matchers::path("/email"))
+        .and(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&app.email_server)
+        .await;
+
+    app
+}
+
+// The token carried by the reset link of the `nth` email sent
+async fn reset_token(app: &TestApp, nth: usize) -> String {
+    let email_request = &app.email_server.received_requests().await.unwrap()[nth];
+    let link = app.get_confirmation_links(email_request).html;
+    assert_eq!(link.path(), "/reset-password");
+
+    link.query_pairs()
+        .find(|(key, _)| key == "token")
+        .map(|(_, token)| token.into_owned())
+        .unwrap()
+}
+
+async fn login_status(app: &TestApp, password: &str) -> u16 {
+    app.login_with(&json!({
+        "user_name": &app.test_user.user_name,
+        "password": password,
+    }))
+    .await
+    .status()
+    .as_u16()
+}
+
+#[tokio::test]
+async fn password_can_be_reset_from_the_emailed_link() {
+    let app = spawn_app_with_email().await;
+
+    let response = app.forgot_password(&app.test_user.email).await;
+    app.send_account_emails().await;
+    assert_eq!(response.status().as_u16(), 202);
+
+    let email_request = &app.email_server.received_requests().await.unwrap()[0];
+    let body: Value = serde_json::from_slice(&email_request.body).unwrap();
+    assert_eq!(body["To"], app.test_user.email);
+
+    let token = reset_token(&app, 0).await;
+    let response = app.reset_password(&token, NEW_PASSWORD).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    assert_eq!(login_status(&app, &app.test_user.password).await, 401);
+    assert_eq!(login_status(&app, NEW_PASSWORD).await, 200);
+}
+
+#[tokio::test]
+async fn resetting_the_password_logs_out_every_session() {
+    let app = spawn_app_with_email().await;
+    app.login().await;
+
+    app.forgot_password(&app.test_user.email).await;
+    app.send_account_emails().await;
+    let token = reset_token(&app, 0).await;
+    let response = app.reset_password(&token, NEW_PASSWORD).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    assert_eq!(app.access_protected().await.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn forgot_password_matches_the_email_regardless_of_case() {
+    let app = spawn_app_with_email().await;
+
+    let response = app
+        .forgot_password(&app.test_user.email.to_uppercase())
+        .await;
+    app.send_account_emails().await;
+
+    assert_eq!(response.status().as_u16(), 202);
+    assert_eq!(app.email_server.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn forgot_password_does_not_reveal_unknown_addresses() {
+    let app = spawn_app_with_email().await;
+
+    let response = app.forgot_password("nobody@example.com").await;
+    app.send_account_emails().await;
+
+    assert_eq!(response.status().as_u16(), 202);
+    assert!(
+        app.email_server
+            .received_requests()
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn requests_made_before_the_email_goes_out_are_sent_once() {
+    let app = spawn_app_with_email().await;
+
+    app.forgot_password(&app.test_user.email).await;
+    app.forgot_password(&app.test_user.email).await;
+
+    assert_eq!(app.send_account_emails().await, 1);
+    assert_eq!(app.email_server.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn forgot_password_returns_400_for_an_invalid_address() {
+    let app = spawn_app_with_email().await;
+
+    let response = app.forgot_password("not-an-email").await;
+
+    assert_eq!(response.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn reset_token_only_works_once() {
+    let app = spawn_app_with_email().await;
+    app.forgot_password(&app.test_user.email).await;
+    app.send_account_emails().await;
+    let token = reset_token(&app, 0).await;
+
+    app.reset_password(&token, NEW_PASSWORD).await;
+    let response = app.reset_password(&token, "yet-another-password").await;
+
+    assert_eq!(response.status().as_u16(), 401);
+    assert_eq!(login_status(&app, NEW_PASSWORD).await, 200);
+}
+
+#[tokio::test]
+async fn requesting_another_reset_invalidates_the_previous_link() {
+    let app = spawn_app_with_email().await;
+    app.forgot_password(&app.test_user.email).await;
+    app.send_account_emails().await;
+    app.forgot_password(&app.test_user.email).await;
+    app.send_account_emails().await;
+
+    let response = app
+        .reset_password(&reset_token(&app, 0).await, NEW_PASSWORD)
+        .await;
+    assert_eq!(response.status().as_u16(), 401);
+
+    let response = app
+        .reset_password(&reset_token(&app, 1).await, NEW_PASSWORD)
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn changing_the_password_invalidates_outstanding_reset_links() {
+    let app = spawn_app_with_email().await;
+    app.forgot_password(&app.test_user.email).await;
+    app.send_account_emails().await;
+    let token = reset_token(&app, 0).await;
+
+    app.login().await;
+    app.enter_sudo_mode(&json!({ "password": &app.test_user.password }))
+        .await;
+    let response = app
+        .change_password(&json!({
+            "current_password": &app.test_user.password,
+            "new_password": NEW_PASSWORD,
+        }))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let response = app.reset_password(&token, "yet-another-new-password").await;
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn tokens_sent_for_another_purpose_are_rejected() {
+    let app = spawn_app_with_email().await;
+    app.forgot_password(&app.test_user.email).await;
+    app.send_account_emails().await;
+    let token = reset_token(&app, 0).await;
+
+    let response = reqwest::get(format!("{}/v1/user/activate?token={token}", app.address))
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 401);
+
+    let response = app.reset_password(&token, NEW_PASSWORD).await;
+    assert_eq!(response.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn expired_reset_token_is_rejected() {
+    let app = spawn_app_with_email().await;
+    app.forgot_password(&app.test_user.email).await;
+    app.send_account_emails().await;
+    let token = reset_token(&app, 0).await;
+
+    sqlx::query!(
+        "UPDATE tokens SET expires_at = NOW() - INTERVAL '1 minute' WHERE token = $1",
+        token
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+
+    let response = app.reset_password(&token, NEW_PASSWORD).await;
+
+    assert_eq!(response.status().as_u16(), 401);
+    assert_eq!(login_status(&app, &app.test_user.password).await, 200);
+}
+
+#[tokio::test]
+async fn invalid_new_password_keeps_the_token_usable() {
+    let app = spawn_app_with_email().await;
+    app.forgot_password(&app.test_user.email).await;
+    app.send_account_emails().await;
+    let token = reset_token(&app, 0).await;
+
+    let response = app.reset_password(&token, "short").await;
+    assert_eq!(response.status().as_u16(), 400);
+
+    let response = app.reset_password(&token, "password1234").await;
+    assert_eq!(response.status().as_u16(), 400);
+
+    let response = app.reset_password(&token, &app.test_user.password).await;
+    assert_eq!(response.status().as_u16(), 400);
+
+    let response = app.reset_password(&token, NEW_PASSWORD).await;
+    assert_eq!(response.status().as_u16(), 200);
+}
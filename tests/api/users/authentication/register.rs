@@ -151,6 +151,61 @@ async fn register_user_returns_400_when_fields_are_invalid() {
     }
 }
 
+#[tokio::test]
+async fn register_user_returns_400_for_easily_guessed_passwords() {
+    let app = helpers::spawn_app().await;
+    let user = TestUser::generate();
+    let test_cases = vec![
+        ("password1234".to_string(), "a common password"),
+        ("qwertyuiop12".to_string(), "a keyboard walk"),
+        (format!("{}2024", user.user_name), "the user name"),
+    ];
+
+    for (password, description) in test_cases {
+        let response = app
+            .register_user(&serde_json::json!({
+                "user_name": user.user_name,
+                "email": user.email,
+                "password": password,
+            }))
+            .await;
+        assert_eq!(
+            400,
+            response.status().as_u16(),
+            "The API did not fail with 400 Bad Request when the password was {description}."
+        );
+
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert!(
+            body["message"]
+                .as_str()
+                .unwrap()
+                .contains("too easy to guess")
+        );
+    }
+}
+
+#[tokio::test]
+async fn register_user_accepts_weak_passwords_when_no_minimum_score_is_configured() {
+    let app = helpers::spawn_app_with(|c| c.application.min_password_score = 0).await;
+    let user = TestUser::generate();
+
+    Mock::given(matchers::path("/email"))
+        .and(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&app.email_server)
+        .await;
+
+    let response = app
+        .register_user(&serde_json::json!({
+            "user_name": user.user_name,
+            "email": user.email,
+            "password": "password1234",
+        }))
+        .await;
+    assert!(response.status().is_success());
+}
+
 #[tokio::test]
 async fn register_user_sends_confirmation_email_with_activation_link() {
     let app = helpers::spawn_app().await;
@@ -218,6 +273,20 @@ async fn register_user_returns_500_on_fatal_database_error() {
     assert_eq!(response.status().as_u16(), 500);
 }
 
+#[tokio::test]
+async fn register_user_returns_409_if_the_user_name_is_taken() {
+    let app = helpers::spawn_app().await;
+    let user = TestUser::generate();
+    let payload = serde_json::json!({
+        "user_name": app.test_user.user_name.to_uppercase(),
+        "email": user.email,
+        "password": user.password
+    });
+
+    let response = app.register_user(&payload).await;
+    assert_eq!(response.status().as_u16(), 409);
+}
+
 #[tokio::test]
 async fn activate_user_activates_user_with_emailed_token() {
     let app = helpers::spawn_app().await;
@@ -316,7 +385,7 @@ async fn activate_user_deletes_activation_token_after_successful_activation() {
     assert_eq!(response.status().as_u16(), 200);
 
     let remaining_tokens = sqlx::query!(
-        r#"SELECT COUNT(*) as count FROM tokens WHERE user_id = $1 AND is_activation = true"#,
+        r#"SELECT COUNT(*) as count FROM tokens WHERE user_id = $1 AND purpose = 'activation'"#,
         app.test_user.user_id,
     )
     .fetch_one(&app.db_pool)
@@ -325,3 +394,110 @@ async fn activate_user_deletes_activation_token_after_successful_activation() {
 
     assert_eq!(remaining_tokens.count, Some(0));
 }
+
+#[tokio::test]
+async fn activate_user_returns_410_for_expired_activation_token() {
+    let app = helpers::spawn_app().await;
+    let (_, confirmation_links) = app.create_inactivated_user().await;
+
+    sqlx::query!(
+        "UPDATE tokens SET expires_at = NOW() - INTERVAL '1 minute' WHERE purpose = 'activation'"
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+
+    let response = reqwest::get(confirmation_links.html.clone()).await.unwrap();
+    assert_eq!(response.status().as_u16(), 410);
+
+    app.cleanup_expired_tokens().await;
+
+    let response = reqwest::get(confirmation_links.html).await.unwrap();
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn register_user_with_captcha_required_accepts_a_verified_token() {
+    let app = helpers::spawn_app_with(|c| c.registration.require_captcha = true).await;
+    app.mock_captcha(true).await;
+    Mock::given(matchers::path("/email"))
+        .and(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&app.email_server)
+        .await;
+
+    let user = TestUser::generate();
+    let response = app
+        .register_user(&serde_json::json!({
+            "user_name": user.user_name,
+            "email": user.email,
+            "password": user.password,
+            "captcha_token": "token",
+        }))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn register_user_with_captcha_required_returns_403_for_a_rejected_token() {
+    let app = helpers::spawn_app_with(|c| c.registration.require_captcha = true).await;
+    app.mock_captcha(false).await;
+
+    let user = TestUser::generate();
+    let response = app
+        .register_user(&serde_json::json!({
+            "user_name": user.user_name,
+            "email": user.email,
+            "password": user.password,
+            "captcha_token": "bad-token",
+        }))
+        .await;
+    assert_eq!(response.status().as_u16(), 403);
+
+    let saved = sqlx::query!("SELECT id FROM users WHERE email = $1", user.email)
+        .fetch_optional(&app.db_pool)
+        .await
+        .unwrap();
+    assert!(saved.is_none());
+}
+
+#[tokio::test]
+async fn register_user_with_captcha_required_returns_400_without_a_token() {
+    let app = helpers::spawn_app_with(|c| c.registration.require_captcha = true).await;
+
+    let user = TestUser::generate();
+    let response = app
+        .register_user(&serde_json::json!({
+            "user_name": user.user_name,
+            "email": user.email,
+            "password": user.password,
+        }))
+        .await;
+    assert_eq!(response.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn register_user_does_not_ask_the_captcha_provider_unless_required() {
+    let app = helpers::spawn_app().await;
+    Mock::given(matchers::path("/siteverify"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&app.captcha_server)
+        .await;
+    Mock::given(matchers::path("/email"))
+        .and(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&app.email_server)
+        .await;
+
+    let user = TestUser::generate();
+    let response = app
+        .register_user(&serde_json::json!({
+            "user_name": user.user_name,
+            "email": user.email,
+            "password": user.password,
+            "captcha_token": "token",
+        }))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+}
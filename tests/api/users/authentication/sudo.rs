@@ -0,0 +1,113 @@
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::helpers;
+
+#[tokio::test]
+async fn sudo_returns_401_for_anonymous_users() {
+    let app = helpers::spawn_app().await;
+
+    let response = app
+        .enter_sudo_mode(&serde_json::json!({ "password": &app.test_user.password }))
+        .await;
+
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn sudo_returns_401_for_wrong_password() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let response = app
+        .enter_sudo_mode(&serde_json::json!({ "password": Uuid::new_v4().to_string() }))
+        .await;
+    assert_eq!(response.status().as_u16(), 401);
+
+    let body: Value = response.json().await.unwrap();
+    assert!(
+        body.get("error").is_none(),
+        "A failed re-authentication is a plain auth error"
+    );
+}
+
+#[tokio::test]
+async fn sudo_shares_the_login_throttle_of_the_user() {
+    let app = helpers::spawn_app_with(|c| c.login_throttle.max_attempts_per_user_name = 3).await;
+    app.login().await;
+
+    for _ in 0..2 {
+        let response = app
+            .enter_sudo_mode(&serde_json::json!({ "password": Uuid::new_v4().to_string() }))
+            .await;
+        assert_eq!(response.status().as_u16(), 401);
+    }
+
+    // Once the login and the guesses used up the budget, not even the right password gets in
+    let response = app
+        .enter_sudo_mode(&serde_json::json!({ "password": &app.test_user.password }))
+        .await;
+    assert_eq!(response.status().as_u16(), 429);
+    assert!(response.headers().contains_key("retry-after"));
+}
+
+#[tokio::test]
+async fn sudo_with_correct_password_unlocks_sensitive_operations() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let response = app
+        .enter_sudo_mode(&serde_json::json!({ "password": &app.test_user.password }))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let response = app
+        .change_password(&serde_json::json!({
+            "current_password": &app.test_user.password,
+            "new_password": Uuid::new_v4().to_string(),
+        }))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn sudo_mode_does_not_survive_logout() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    app.enter_sudo_mode(&serde_json::json!({ "password": &app.test_user.password }))
+        .await;
+
+    app.logout().await;
+    app.login().await;
+
+    let response = app
+        .change_password(&serde_json::json!({
+            "current_password": &app.test_user.password,
+            "new_password": Uuid::new_v4().to_string(),
+        }))
+        .await;
+    assert_eq!(response.status().as_u16(), 401);
+
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["error"], "reauthentication_required");
+}
+
+#[tokio::test]
+async fn sudo_reports_when_it_expires_by_the_server_clock() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let response = app
+        .enter_sudo_mode(&serde_json::json!({ "password": &app.test_user.password }))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let server_time = response.headers()["x-server-time"].to_str().unwrap();
+    let server_time = chrono::DateTime::parse_from_rfc3339(server_time).unwrap();
+    let body: serde_json::Value = response.json().await.unwrap();
+    let sudo_until =
+        chrono::DateTime::parse_from_rfc3339(body["sudo_until"].as_str().unwrap()).unwrap();
+
+    let remaining = sudo_until - server_time;
+    assert!(remaining > chrono::Duration::minutes(4) && remaining <= chrono::Duration::minutes(5));
+}
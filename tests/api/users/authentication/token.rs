@@ -0,0 +1,240 @@
+use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::helpers::{self, TestApp};
+
+async fn issue_tokens(app: &TestApp) -> Value {
+    let response = app
+        .issue_access_token(&serde_json::json!({
+            "user_name": &app.test_user.user_name,
+            "password": &app.test_user.password,
+        }))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    response.json().await.unwrap()
+}
+
+async fn issue_token(app: &TestApp) -> String {
+    issue_tokens(app).await["access_token"]
+        .as_str()
+        .unwrap()
+        .to_string()
+}
+
+async fn refresh_token(app: &TestApp) -> String {
+    issue_tokens(app).await["refresh_token"]
+        .as_str()
+        .unwrap()
+        .to_string()
+}
+
+#[tokio::test]
+async fn issued_token_authenticates_without_a_session() {
+    let app = helpers::spawn_app().await;
+
+    let response = app
+        .issue_access_token(&serde_json::json!({
+            "user_name": &app.test_user.user_name,
+            "password": &app.test_user.password,
+        }))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["token_type"], "Bearer");
+    assert_eq!(body["expires_in"], 15 * 60);
+
+    // No session cookie was handed out
+    let response = app.access_protected().await;
+    assert_eq!(response.status().as_u16(), 401);
+
+    let token = body["access_token"].as_str().unwrap();
+    let response = app.access_protected_with_token(token).await;
+    assert_eq!(response.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn token_is_not_issued_for_invalid_credentials() {
+    let app = helpers::spawn_app().await;
+
+    let response = app
+        .issue_access_token(&serde_json::json!({
+            "user_name": &app.test_user.user_name,
+            "password": Uuid::new_v4().to_string(),
+        }))
+        .await;
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn token_issuance_is_throttled_like_login() {
+    let app = helpers::spawn_app_with(|c| c.login_throttle.max_attempts_per_user_name = 1).await;
+
+    issue_token(&app).await;
+    let response = app
+        .issue_access_token(&serde_json::json!({
+            "user_name": &app.test_user.user_name,
+            "password": &app.test_user.password,
+        }))
+        .await;
+    assert_eq!(response.status().as_u16(), 429);
+}
+
+#[tokio::test]
+async fn tampered_or_unknown_tokens_are_rejected() {
+    let app = helpers::spawn_app().await;
+    let token = issue_token(&app).await;
+
+    let (unsigned, _) = token.rsplit_once('.').unwrap();
+    for token in [format!("{unsigned}.AAAA"), "not-a-token".to_string()] {
+        let response = app.access_protected_with_token(&token).await;
+        assert_eq!(response.status().as_u16(), 401, "{token}");
+    }
+}
+
+#[tokio::test]
+async fn tokens_signed_with_another_key_are_rejected() {
+    let other = helpers::spawn_app_with(|c| {
+        c.jwt.signing_key = secrecy::Secret::new("another-signing-key".to_string())
+    })
+    .await;
+    let app = helpers::spawn_app().await;
+
+    let response = other
+        .issue_access_token(&serde_json::json!({
+            "user_name": &other.test_user.user_name,
+            "password": &other.test_user.password,
+        }))
+        .await;
+    let body: Value = response.json().await.unwrap();
+
+    let response = app
+        .access_protected_with_token(body["access_token"].as_str().unwrap())
+        .await;
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn expired_tokens_are_rejected() {
+    let app = helpers::spawn_app_with(|c| c.jwt.expiry_minutes = 0).await;
+    let token = issue_token(&app).await;
+
+    let response = app.access_protected_with_token(&token).await;
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn refresh_token_is_exchanged_for_a_new_pair() {
+    let app = helpers::spawn_app().await;
+    let refresh_token = refresh_token(&app).await;
+    assert!(refresh_token.starts_with("thrt_"));
+
+    let response = app.refresh_access_token(&refresh_token).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    assert_ne!(body["refresh_token"], refresh_token.as_str());
+    let response = app
+        .access_protected_with_token(body["access_token"].as_str().unwrap())
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn reusing_a_rotated_refresh_token_revokes_the_whole_family() {
+    let app = helpers::spawn_app().await;
+    let stolen = refresh_token(&app).await;
+
+    let response = app.refresh_access_token(&stolen).await;
+    let body: Value = response.json().await.unwrap();
+    let latest = body["refresh_token"].as_str().unwrap();
+
+    let response = app.refresh_access_token(&stolen).await;
+    assert_eq!(response.status().as_u16(), 401);
+
+    // The legitimate client is signed out too, it cannot be told apart from the attacker
+    let response = app.refresh_access_token(latest).await;
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn reuse_does_not_affect_other_sign_ins() {
+    let app = helpers::spawn_app().await;
+    let reused = refresh_token(&app).await;
+    let other = refresh_token(&app).await;
+
+    app.refresh_access_token(&reused).await;
+    app.refresh_access_token(&reused).await;
+
+    let response = app.refresh_access_token(&other).await;
+    assert_eq!(response.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn revoked_refresh_tokens_are_refused() {
+    let app = helpers::spawn_app().await;
+    let refresh_token = refresh_token(&app).await;
+
+    let response = app.revoke_refresh_token(&refresh_token).await;
+    assert_eq!(response.status().as_u16(), 204);
+
+    let response = app.refresh_access_token(&refresh_token).await;
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn revoking_an_unknown_refresh_token_succeeds() {
+    let app = helpers::spawn_app().await;
+
+    let response = app.revoke_refresh_token("thrt_unknown").await;
+    assert_eq!(response.status().as_u16(), 204);
+}
+
+#[tokio::test]
+async fn expired_refresh_tokens_are_refused() {
+    let app = helpers::spawn_app_with(|c| c.jwt.refresh_token_expiry_days = 0).await;
+    let refresh_token = refresh_token(&app).await;
+
+    let response = app.refresh_access_token(&refresh_token).await;
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn unknown_refresh_tokens_are_refused() {
+    let app = helpers::spawn_app().await;
+
+    let response = app.refresh_access_token("thrt_unknown").await;
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn refresh_tokens_are_stored_hashed_with_the_device() {
+    let app = helpers::spawn_app().await;
+    let mut headers = HeaderMap::new();
+    headers.insert(USER_AGENT, HeaderValue::from_static("techhub-cli/1.0"));
+
+    let response = app
+        .send_post_with_headers(
+            "v1/user/token",
+            &serde_json::json!({
+                "user_name": &app.test_user.user_name,
+                "password": &app.test_user.password,
+            }),
+            &headers,
+        )
+        .await;
+    let body: Value = response.json().await.unwrap();
+    let refresh_token = body["refresh_token"].as_str().unwrap();
+
+    let row = sqlx::query!(
+        "SELECT token_hash, user_agent FROM refresh_tokens WHERE user_id = $1",
+        app.test_user.user_id
+    )
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap();
+    assert_ne!(row.token_hash, refresh_token);
+    assert_eq!(row.user_agent.as_deref(), Some("techhub-cli/1.0"));
+}
@@ -0,0 +1,131 @@
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::helpers;
+
+const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR\0\0\0\x01\0\0\0\x01";
+
+#[tokio::test]
+async fn uploaded_avatar_is_served_back_with_its_content_type() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let response = app.upload_avatar(PNG.to_vec(), "image/png").await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body: Value = response.json().await.unwrap();
+    let avatar_url = body["avatar_url"].as_str().unwrap();
+    assert!(avatar_url.starts_with(&format!("/v1/user/{}/avatar?v=", app.test_user.user_id)));
+
+    let response = app.send_get(avatar_url.trim_start_matches('/')).await;
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(response.headers()["content-type"], "image/png");
+    assert_eq!(response.headers()["x-content-type-options"], "nosniff");
+    assert_eq!(response.bytes().await.unwrap().as_ref(), PNG);
+}
+
+#[tokio::test]
+async fn avatar_url_is_included_in_profile_post_and_comment_responses() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let post_id = app.create_sample_post().await;
+    let payload = serde_json::json!({ "text": "Hello", "post_id": post_id.to_string() });
+    app.create_comment(&payload).await;
+
+    let body: Value = app.get_post(&post_id).await.json().await.unwrap();
+    assert!(body["posts"]["created_by_avatar_url"].is_null());
+
+    let body: Value = app
+        .upload_avatar(PNG.to_vec(), "image/png")
+        .await
+        .json()
+        .await
+        .unwrap();
+    let avatar_url = body["avatar_url"].clone();
+
+    let body: Value = app
+        .get_user_profile(&app.test_user.user_id)
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["user"]["avatar_url"], avatar_url);
+
+    let body: Value = app.get_post(&post_id).await.json().await.unwrap();
+    assert_eq!(body["posts"]["created_by_avatar_url"], avatar_url);
+
+    let body: Value = app.get_all_posts("").await.json().await.unwrap();
+    assert_eq!(body["posts"][0]["created_by_avatar_url"], avatar_url);
+
+    let body: Value = app.get_comments(&post_id).await.json().await.unwrap();
+    assert_eq!(body["comments"][0]["created_by_avatar_url"], avatar_url);
+}
+
+#[tokio::test]
+async fn avatar_must_match_an_allowed_image_type() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let cases = [
+        (b"<svg onload=alert(1)>".to_vec(), "image/svg+xml"),
+        (b"<html></html>".to_vec(), "image/png"),
+        (PNG.to_vec(), "image/jpeg"),
+        (Vec::new(), "image/png"),
+    ];
+    for (image, content_type) in cases {
+        let response = app.upload_avatar(image, content_type).await;
+        assert_eq!(response.status().as_u16(), 400, "{content_type}");
+    }
+}
+
+#[tokio::test]
+async fn oversized_avatar_is_rejected() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let mut image = PNG.to_vec();
+    image.resize(256 * 1024 + 1, 0);
+    let response = app.upload_avatar(image, "image/png").await;
+
+    assert_eq!(response.status().as_u16(), 413);
+}
+
+#[tokio::test]
+async fn avatar_upload_requires_login() {
+    let app = helpers::spawn_app().await;
+
+    let response = app.upload_avatar(PNG.to_vec(), "image/png").await;
+
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn deleted_avatar_is_no_longer_served() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    app.upload_avatar(PNG.to_vec(), "image/png").await;
+
+    let response = app.delete_avatar().await;
+    assert_eq!(response.status().as_u16(), 204);
+
+    let response = app.get_avatar(&app.test_user.user_id).await;
+    assert_eq!(response.status().as_u16(), 404);
+    let body: Value = app
+        .get_user_profile(&app.test_user.user_id)
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert!(body["user"]["avatar_url"].is_null());
+
+    let response = app.delete_avatar().await;
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn avatar_returns_404_for_user_without_one() {
+    let app = helpers::spawn_app().await;
+
+    let response = app.get_avatar(&Uuid::new_v4()).await;
+
+    assert_eq!(response.status().as_u16(), 404);
+}
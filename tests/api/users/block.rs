@@ -0,0 +1,168 @@
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::helpers;
+
+#[tokio::test]
+async fn block_user_returns_401_if_unauthenticated() {
+    let app = helpers::spawn_app().await;
+
+    let response = app.block_user(&app.test_user.user_id).await;
+
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn block_user_returns_400_when_blocking_yourself() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let response = app.block_user(&app.test_user.user_id).await;
+
+    assert_eq!(response.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn block_user_returns_404_for_unknown_user() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let response = app.block_user(&Uuid::new_v4()).await;
+
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn blocked_users_are_listed_until_unblocked() {
+    let app = helpers::spawn_app().await;
+    let other = app.create_activated_user().await;
+    let other_id = app.user_id_of(&other).await;
+    app.login().await;
+
+    assert_eq!(app.block_user(&other_id).await.status().as_u16(), 204);
+    // Blocking twice is a no-op
+    assert_eq!(app.block_user(&other_id).await.status().as_u16(), 204);
+
+    let body: Value = app.get_blocks().await.json().await.unwrap();
+    let blocked = body["blocked_users"].as_array().unwrap();
+    assert_eq!(blocked.len(), 1);
+    assert_eq!(blocked[0]["id"], other_id.to_string());
+
+    assert_eq!(app.unblock_user(&other_id).await.status().as_u16(), 204);
+    let body: Value = app.get_blocks().await.json().await.unwrap();
+    assert_eq!(body["blocked_users"], json!([]));
+}
+
+#[tokio::test]
+async fn blocked_users_comments_are_hidden_from_the_blocker_only() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let post_id = app.create_sample_post().await;
+    app.logout().await;
+
+    let other = app.create_activated_user().await;
+    let other_id = app.user_id_of(&other).await;
+    app.login_with(&other).await;
+    let response = app
+        .create_comment(&json!({"text": "Blocked comment", "post_id": post_id.to_string()}))
+        .await;
+    assert_eq!(response.status().as_u16(), 201);
+    app.logout().await;
+
+    app.login().await;
+    app.block_user(&other_id).await;
+    let body: Value = app.get_comments(&post_id).await.json().await.unwrap();
+    assert_eq!(body["comments"], json!([]));
+    app.logout().await;
+
+    // Everyone else still sees the comment
+    let body: Value = app.get_comments(&post_id).await.json().await.unwrap();
+    assert_eq!(body["comments"].as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn blocked_users_cannot_comment_on_the_blockers_posts() {
+    let app = helpers::spawn_app().await;
+    let other = app.create_activated_user().await;
+    let other_id = app.user_id_of(&other).await;
+
+    app.login().await;
+    let post_id = app.create_sample_post().await;
+    app.block_user(&other_id).await;
+    app.logout().await;
+
+    app.login_with(&other).await;
+    let response = app
+        .create_comment(&json!({"text": "Let me in", "post_id": post_id.to_string()}))
+        .await;
+
+    assert_eq!(response.status().as_u16(), 403);
+}
+
+#[tokio::test]
+async fn blocked_users_cannot_message_each_other() {
+    let app = helpers::spawn_app().await;
+    let other = app.create_activated_user().await;
+    let other_id = app.user_id_of(&other).await;
+
+    app.login().await;
+    app.block_user(&other_id).await;
+    let response = app
+        .send_message(&json!({"recipient_id": other_id.to_string(), "text": "Hi"}))
+        .await;
+    assert_eq!(response.status().as_u16(), 403);
+    app.logout().await;
+
+    app.login_with(&other).await;
+    let response = app
+        .send_message(&json!({
+            "recipient_id": app.test_user.user_id.to_string(),
+            "text": "Hi"
+        }))
+        .await;
+    assert_eq!(response.status().as_u16(), 403);
+}
+
+#[tokio::test]
+async fn blocking_removes_follows_both_ways() {
+    let app = helpers::spawn_app().await;
+    let other = app.create_activated_user().await;
+    let other_id = app.user_id_of(&other).await;
+
+    app.login_with(&other).await;
+    app.follow_user(&app.test_user.user_id).await;
+    app.logout().await;
+
+    app.login().await;
+    app.follow_user(&other_id).await;
+    app.block_user(&other_id).await;
+
+    let count = sqlx::query_scalar!(r#"SELECT COUNT(*) AS "count!" FROM follows"#)
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(count, 0);
+}
+
+#[tokio::test]
+async fn blocked_users_cannot_follow_each_other() {
+    let app = helpers::spawn_app().await;
+    let other = app.create_activated_user().await;
+    let other_id = app.user_id_of(&other).await;
+
+    app.login().await;
+    app.block_user(&other_id).await;
+    let response = app.follow_user(&other_id).await;
+    assert_eq!(response.status().as_u16(), 403);
+    app.logout().await;
+
+    app.login_with(&other).await;
+    let response = app.follow_user(&app.test_user.user_id).await;
+    assert_eq!(response.status().as_u16(), 403);
+
+    let count = sqlx::query_scalar!(r#"SELECT COUNT(*) AS "count!" FROM follows"#)
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(count, 0);
+}
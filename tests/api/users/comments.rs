@@ -0,0 +1,113 @@
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::helpers;
+
+#[tokio::test]
+async fn user_comments_are_listed_with_their_post_title() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let post_id = app.create_sample_post().await;
+    for text in ["First thoughts", "Second thoughts"] {
+        let payload = serde_json::json!({ "text": text, "post_id": post_id.to_string() });
+        assert_eq!(app.create_comment(&payload).await.status().as_u16(), 201);
+    }
+    app.logout().await;
+
+    let response = app.get_user_comments(&app.test_user.user_id, "").await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    let comments = body["comments"].as_array().unwrap();
+    assert_eq!(comments.len(), 2);
+    assert_eq!(comments[0]["text"], "Second thoughts");
+    assert_eq!(comments[0]["post_id"], post_id.to_string());
+    assert_eq!(comments[0]["post_title"], "Post for comments");
+    assert_eq!(body["metadata"]["total_records"], 2);
+}
+
+#[tokio::test]
+async fn user_comments_only_include_that_users_comments() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let post_id = app.create_sample_post().await;
+    let payload = serde_json::json!({ "text": "Mine", "post_id": post_id.to_string() });
+    app.create_comment(&payload).await;
+    app.logout().await;
+
+    let other_user = app.create_activated_user().await;
+    app.login_with(&other_user).await;
+    let payload = serde_json::json!({ "text": "Theirs", "post_id": post_id.to_string() });
+    app.create_comment(&payload).await;
+
+    let body: Value = app
+        .get_user_comments(&app.test_user.user_id, "")
+        .await
+        .json()
+        .await
+        .unwrap();
+    let comments = body["comments"].as_array().unwrap();
+    assert_eq!(comments.len(), 1);
+    assert_eq!(comments[0]["text"], "Mine");
+}
+
+#[tokio::test]
+async fn user_comments_are_paginated_and_sortable() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let post_id = app.create_sample_post().await;
+    for i in 0..3 {
+        let payload =
+            serde_json::json!({ "text": format!("Comment {i}"), "post_id": post_id.to_string() });
+        app.create_comment(&payload).await;
+    }
+
+    let body: Value = app
+        .get_user_comments(&app.test_user.user_id, "?sort=oldest&limit=2&page=2")
+        .await
+        .json()
+        .await
+        .unwrap();
+    let comments = body["comments"].as_array().unwrap();
+    assert_eq!(comments.len(), 1);
+    assert_eq!(comments[0]["text"], "Comment 2");
+    assert_eq!(body["metadata"]["last_page"], 2);
+}
+
+#[tokio::test]
+async fn comments_on_deleted_posts_are_not_listed() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let post_id = app.create_sample_post().await;
+    let payload = serde_json::json!({ "text": "Gone soon", "post_id": post_id.to_string() });
+    app.create_comment(&payload).await;
+    assert_eq!(app.delete_post(&post_id).await.status().as_u16(), 200);
+
+    let body: Value = app
+        .get_user_comments(&app.test_user.user_id, "")
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert!(body["comments"].as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn user_comments_return_404_for_unknown_user() {
+    let app = helpers::spawn_app().await;
+
+    let response = app.get_user_comments(&Uuid::new_v4(), "").await;
+
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn user_comments_return_400_for_invalid_query() {
+    let app = helpers::spawn_app().await;
+
+    let response = app
+        .get_user_comments(&app.test_user.user_id, "?sort=popular")
+        .await;
+
+    assert_eq!(response.status().as_u16(), 400);
+}
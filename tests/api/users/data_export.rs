@@ -0,0 +1,75 @@
+use serde_json::Value;
+
+use crate::helpers;
+
+#[tokio::test]
+async fn export_own_data_returns_401_if_unauthenticated() {
+    let app = helpers::spawn_app().await;
+
+    let response = app.export_own_data().await;
+
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn export_own_data_contains_account_posts_comments_and_likes() {
+    let app = helpers::spawn_app().await;
+
+    // Someone else's post, which the exporting user only comments on and likes
+    let other_user = app.create_activated_user().await;
+    app.login_with(&other_user).await;
+    let other_post = app.create_sample_post_custom("Not Mine", "Other").await;
+    app.logout().await;
+
+    app.login().await;
+    let own_post = app.create_sample_post_custom("Mine", "First").await;
+    let payload = serde_json::json!({
+        "text": "Exported comment",
+        "post_id": other_post.to_string()
+    });
+    assert_eq!(app.create_comment(&payload).await.status().as_u16(), 201);
+    app.like_post_as_user(&other_post).await;
+
+    let response = app.export_own_data().await;
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(
+        response.headers()["content-disposition"],
+        "attachment; filename=\"techhub-account.json\""
+    );
+
+    let body: Value = response.json().await.unwrap();
+    assert!(body["exported_at"].is_string());
+
+    let account = &body["account"];
+    assert_eq!(account["id"], app.test_user.user_id.to_string());
+    assert_eq!(account["user_name"], app.test_user.user_name);
+    assert_eq!(account["email"], app.test_user.email);
+    assert_eq!(account["is_subscribed"], false);
+
+    let posts = body["posts"].as_array().unwrap();
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0]["id"], own_post.to_string());
+
+    let comments = body["comments"].as_array().unwrap();
+    assert_eq!(comments.len(), 1);
+    assert_eq!(comments[0]["text"], "Exported comment");
+    assert_eq!(comments[0]["post_title"], "Not Mine");
+
+    let likes = body["likes"].as_array().unwrap();
+    assert_eq!(likes.len(), 1);
+    assert_eq!(likes[0]["post_id"], other_post.to_string());
+}
+
+#[tokio::test]
+async fn export_own_data_has_empty_sections_for_a_new_account() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let response = app.export_own_data().await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    for section in ["posts", "comments", "likes"] {
+        assert_eq!(body[section], serde_json::json!([]), "{section}");
+    }
+}
@@ -0,0 +1,126 @@
+use serde_json::Value;
+use wiremock::{Mock, ResponseTemplate, matchers};
+
+use crate::helpers;
+
+async fn spawn_app_in_sudo_mode() -> helpers::TestApp {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    app.enter_sudo_mode(&serde_json::json!({ "password": &app.test_user.password }))
+        .await;
+
+    Mock::given(matchers::path("/email"))
+        .and(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&app.email_server)
+        .await;
+
+    app
+}
+
+async fn stored_email(app: &helpers::TestApp) -> String {
+    sqlx::query_scalar!(
+        "SELECT email FROM users WHERE id = $1",
+        app.test_user.user_id
+    )
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn email_change_only_applies_once_the_new_address_is_confirmed() {
+    let app = spawn_app_in_sudo_mode().await;
+
+    let response = app.request_email_change("new-address@example.com").await;
+    assert_eq!(response.status().as_u16(), 202);
+    assert_eq!(stored_email(&app).await, app.test_user.email);
+
+    let email_request = &app.email_server.received_requests().await.unwrap()[0];
+    let body: Value = serde_json::from_slice(&email_request.body).unwrap();
+    assert_eq!(body["To"], "new-address@example.com");
+
+    let confirmation_links = app.get_confirmation_links(email_request);
+    let response = reqwest::get(confirmation_links.html).await.unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(stored_email(&app).await, "new-address@example.com");
+}
+
+#[tokio::test]
+async fn email_change_link_only_works_once() {
+    let app = spawn_app_in_sudo_mode().await;
+    app.request_email_change("new-address@example.com").await;
+
+    let email_request = &app.email_server.received_requests().await.unwrap()[0];
+    let confirmation_links = app.get_confirmation_links(email_request);
+    reqwest::get(confirmation_links.html.clone()).await.unwrap();
+
+    let response = reqwest::get(confirmation_links.html).await.unwrap();
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn requesting_another_email_change_invalidates_the_previous_link() {
+    let app = spawn_app_in_sudo_mode().await;
+    app.request_email_change("typo@exmaple.com").await;
+    app.request_email_change("new-address@example.com").await;
+
+    let email_requests = app.email_server.received_requests().await.unwrap();
+    let first_link = app.get_confirmation_links(&email_requests[0]).html;
+    let response = reqwest::get(first_link).await.unwrap();
+    assert_eq!(response.status().as_u16(), 401);
+
+    let second_link = app.get_confirmation_links(&email_requests[1]).html;
+    let response = reqwest::get(second_link).await.unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(stored_email(&app).await, "new-address@example.com");
+}
+
+#[tokio::test]
+async fn email_change_returns_409_when_another_account_uses_the_address() {
+    let app = spawn_app_in_sudo_mode().await;
+
+    // The seeded admin's address, compared regardless of case
+    let response = app.request_email_change("Athfan@gmail.com").await;
+
+    assert_eq!(response.status().as_u16(), 409);
+    assert!(
+        app.email_server
+            .received_requests()
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn email_change_returns_400_for_an_invalid_address() {
+    let app = spawn_app_in_sudo_mode().await;
+
+    let response = app.request_email_change("not-an-email").await;
+
+    assert_eq!(response.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn email_change_requires_sudo_mode() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let response = app.request_email_change("new-address@example.com").await;
+
+    assert_eq!(response.status().as_u16(), 401);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["error"], "reauthentication_required");
+}
+
+#[tokio::test]
+async fn confirm_email_returns_401_for_an_unknown_token() {
+    let app = helpers::spawn_app().await;
+
+    let response = app
+        .send_get("v1/user/confirm-email?token=unknown-token")
+        .await;
+
+    assert_eq!(response.status().as_u16(), 401);
+}
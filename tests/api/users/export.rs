@@ -0,0 +1,169 @@
+use serde_json::Value;
+
+use crate::helpers;
+
+#[tokio::test]
+async fn export_posts_returns_401_if_unauthenticated() {
+    let app = helpers::spawn_app().await;
+
+    let response = app.export_posts("").await;
+
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn export_posts_returns_400_for_unknown_format() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let response = app.export_posts("?format=csv").await;
+
+    assert_eq!(response.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn export_posts_as_json_returns_only_own_active_posts() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    app.create_sample_post_custom("Mine One", "First").await;
+    let deleted = app.create_sample_post_custom("Mine Deleted", "Gone").await;
+    app.delete_post(&deleted).await;
+    app.logout().await;
+
+    // Another user's post must not leak into the export
+    let other_user = app.create_activated_user().await;
+    app.login_with(&other_user).await;
+    app.create_sample_post_custom("Not Mine", "Other").await;
+    app.logout().await;
+
+    app.login().await;
+    let response = app.export_posts("?format=json").await;
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(
+        response.headers()["content-disposition"],
+        "attachment; filename=\"techhub-posts.json\""
+    );
+
+    let body: Value = response.json().await.unwrap();
+    let posts = body.as_array().unwrap();
+
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0]["title"], "Mine One");
+    assert_eq!(posts[0]["text"], "First");
+    assert!(posts[0].get("comments").is_none());
+}
+
+#[tokio::test]
+async fn export_posts_includes_comments_when_requested() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let post_id = app.create_sample_post().await;
+    let payload = serde_json::json!({
+        "text": "Exported comment",
+        "post_id": post_id.to_string()
+    });
+    assert_eq!(app.create_comment(&payload).await.status().as_u16(), 201);
+
+    let response = app.export_posts("?format=json&include_comments=true").await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    let comments = body[0]["comments"].as_array().unwrap();
+
+    assert_eq!(comments.len(), 1);
+    assert_eq!(comments[0]["text"], "Exported comment");
+    assert_eq!(comments[0]["created_by_name"], app.test_user.user_name);
+}
+
+#[tokio::test]
+async fn export_posts_as_json_spans_multiple_batches() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    for i in 0..105 {
+        sqlx::query(
+            "INSERT INTO posts (id, title, post_text, img, created_by) VALUES ($1, $2, 'Text', 'https://example.com/a.jpg', $3)",
+        )
+        .bind(uuid::Uuid::new_v4())
+        .bind(format!("Bulk {i}"))
+        .bind(app.test_user.user_id)
+        .execute(&app.db_pool)
+        .await
+        .unwrap();
+    }
+
+    let response = app.export_posts("").await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body.as_array().unwrap().len(), 105);
+}
+
+#[tokio::test]
+async fn export_posts_as_markdown_renders_each_post() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    app.create_sample_post_custom("Markdown Post", "Markdown body")
+        .await;
+
+    let response = app.export_posts("?format=markdown").await;
+    assert_eq!(response.status().as_u16(), 200);
+    assert!(
+        response.headers()["content-type"]
+            .to_str()
+            .unwrap()
+            .starts_with("text/markdown")
+    );
+
+    let body = response.text().await.unwrap();
+    assert!(body.contains("# Markdown Post"));
+    assert!(body.contains("Markdown body"));
+}
+
+#[tokio::test]
+async fn export_posts_is_zstd_compressed_when_the_client_accepts_it() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    for i in 0..3 {
+        app.create_sample_post_custom(&format!("Compressed {i}"), "Same body every time")
+            .await;
+    }
+
+    let plain = app.export_posts("?format=json").await;
+    assert!(plain.headers().get("content-encoding").is_none());
+    let plain: Value = plain.json().await.unwrap();
+
+    let response = app
+        .export_posts_with_encoding("?format=json", "gzip, zstd")
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(response.headers()["content-encoding"], "zstd");
+    assert_eq!(response.headers()["vary"], "Accept-Encoding");
+
+    let compressed = response.bytes().await.unwrap();
+    let decoded: Value =
+        serde_json::from_slice(&zstd::decode_all(compressed.as_ref()).unwrap()).unwrap();
+
+    assert_eq!(decoded, plain);
+}
+
+#[tokio::test]
+async fn export_posts_is_not_compressed_when_zstd_is_refused() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    app.create_sample_post().await;
+
+    let response = app
+        .export_posts_with_encoding("?format=json", "zstd;q=0, gzip")
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    assert!(response.headers().get("content-encoding").is_none());
+
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body.as_array().unwrap().len(), 1);
+}
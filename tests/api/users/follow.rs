@@ -0,0 +1,139 @@
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::helpers;
+
+#[tokio::test]
+async fn follow_user_returns_401_if_unauthenticated() {
+    let app = helpers::spawn_app().await;
+
+    let response = app.follow_user(&app.test_user.user_id).await;
+
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn follow_user_returns_400_when_following_yourself() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let response = app.follow_user(&app.test_user.user_id).await;
+
+    assert_eq!(response.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn follow_user_returns_404_for_unknown_user() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let response = app.follow_user(&Uuid::new_v4()).await;
+
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn following_twice_is_a_no_op() {
+    let app = helpers::spawn_app().await;
+    let author = app.create_activated_user().await;
+    let author_id = app.user_id_of(&author).await;
+    app.login().await;
+
+    assert_eq!(app.follow_user(&author_id).await.status().as_u16(), 204);
+    assert_eq!(app.follow_user(&author_id).await.status().as_u16(), 204);
+
+    let count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!" FROM follows WHERE follower_id = $1"#,
+        app.test_user.user_id
+    )
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap();
+    assert_eq!(count, 1);
+}
+
+#[tokio::test]
+async fn feed_lists_only_posts_by_followed_authors() {
+    let app = helpers::spawn_app().await;
+
+    let followed = app.create_activated_user().await;
+    let followed_id = app.user_id_of(&followed).await;
+    app.login_with(&followed).await;
+    let followed_post = app.create_sample_post_custom("Followed", "Body").await;
+    app.logout().await;
+
+    let stranger = app.create_activated_user().await;
+    app.login_with(&stranger).await;
+    app.create_sample_post_custom("Stranger", "Body").await;
+    app.logout().await;
+
+    app.login().await;
+    app.create_sample_post_custom("Own", "Body").await;
+    assert_eq!(app.follow_user(&followed_id).await.status().as_u16(), 204);
+
+    let response = app.get_feed("").await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    let posts = body["posts"].as_array().unwrap();
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0]["id"], followed_post.to_string());
+    assert_eq!(body["metadata"]["total_records"], 1);
+}
+
+#[tokio::test]
+async fn feed_is_empty_after_unfollowing() {
+    let app = helpers::spawn_app().await;
+
+    let followed = app.create_activated_user().await;
+    let followed_id = app.user_id_of(&followed).await;
+    app.login_with(&followed).await;
+    app.create_sample_post().await;
+    app.logout().await;
+
+    app.login().await;
+    app.follow_user(&followed_id).await;
+    assert_eq!(app.unfollow_user(&followed_id).await.status().as_u16(), 204);
+
+    let body: Value = app.get_feed("").await.json().await.unwrap();
+    assert_eq!(body["posts"], serde_json::json!([]));
+}
+
+#[tokio::test]
+async fn feed_is_paginated() {
+    let app = helpers::spawn_app().await;
+
+    let followed = app.create_activated_user().await;
+    let followed_id = app.user_id_of(&followed).await;
+    app.login_with(&followed).await;
+    for i in 0..3 {
+        app.create_sample_post_custom(&format!("Post {i}"), "Body")
+            .await;
+    }
+    app.logout().await;
+
+    app.login().await;
+    app.follow_user(&followed_id).await;
+
+    let body: Value = app
+        .get_feed("?page=2&limit=2&sort=created_at")
+        .await
+        .json()
+        .await
+        .unwrap();
+
+    let posts = body["posts"].as_array().unwrap();
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0]["title"], "Post 2");
+    assert_eq!(body["metadata"]["last_page"], 2);
+}
+
+#[tokio::test]
+async fn feed_returns_400_for_invalid_pagination() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let response = app.get_feed("?limit=0").await;
+
+    assert_eq!(response.status().as_u16(), 400);
+}
@@ -0,0 +1,81 @@
+use serde_json::Value;
+
+use crate::helpers;
+
+#[tokio::test]
+async fn mentioned_user_sees_the_comment() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let post_id = app.create_sample_post().await;
+    let payload = serde_json::json!({
+        "text": "@athfan could you take a look?",
+        "post_id": post_id.to_string()
+    });
+    let response = app.create_comment(&payload).await;
+    assert_eq!(response.status().as_u16(), 201);
+
+    app.logout().await;
+    app.login_admin().await;
+
+    let response = app.get_mentions("").await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    let mentions = body["mentions"].as_array().unwrap();
+    assert_eq!(mentions.len(), 1);
+    assert_eq!(mentions[0]["post_id"], post_id.to_string());
+    assert_eq!(mentions[0]["text"], "@athfan could you take a look?");
+    assert_eq!(
+        mentions[0]["mentioned_by"],
+        app.test_user.user_id.to_string()
+    );
+    assert_eq!(mentions[0]["mentioned_by_name"], app.test_user.user_name);
+    assert_eq!(body["metadata"]["total_records"], 1);
+}
+
+#[tokio::test]
+async fn self_and_unknown_mentions_are_ignored() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let post_id = app.create_sample_post().await;
+    let payload = serde_json::json!({
+        "text": format!("@{} talking to myself and @nobody-here", app.test_user.user_name),
+        "post_id": post_id.to_string()
+    });
+    let response = app.create_comment(&payload).await;
+    assert_eq!(response.status().as_u16(), 201);
+
+    let body: Value = app.get_mentions("").await.json().await.unwrap();
+    assert!(body["mentions"].as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn mentions_of_deleted_comments_are_hidden() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let post_id = app.create_sample_post().await;
+    let payload = serde_json::json!({
+        "text": "@athfan spam",
+        "post_id": post_id.to_string()
+    });
+    let body: Value = app.create_comment(&payload).await.json().await.unwrap();
+    let comment_id = uuid::Uuid::parse_str(body["id"].as_str().unwrap()).unwrap();
+    app.delete_comment(&comment_id).await;
+
+    app.logout().await;
+    app.login_admin().await;
+
+    let body: Value = app.get_mentions("").await.json().await.unwrap();
+    assert!(body["mentions"].as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn mentions_require_authentication() {
+    let app = helpers::spawn_app().await;
+
+    let response = app.get_mentions("").await;
+    assert_eq!(response.status().as_u16(), 401);
+}
@@ -1,2 +1,18 @@
+mod account_deletion;
+mod activation_reminders;
 mod authentication;
+mod avatar;
+mod block;
+mod comments;
+mod data_export;
+mod email;
+mod export;
+mod follow;
+mod mentions;
+mod personal_access_tokens;
+mod profile;
+mod search;
+mod security;
+mod sessions;
 mod subscription;
+mod unsubscribe;
@@ -0,0 +1,169 @@
+use reqwest::Method;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::helpers::{self, TestApp};
+
+// Logs in, enters sudo mode and creates a token with `scopes`, returning the created token's body
+async fn create_token(app: &TestApp, scopes: &[&str]) -> Value {
+    app.login().await;
+    app.enter_sudo_mode(&json!({ "password": &app.test_user.password }))
+        .await;
+
+    let response = app
+        .create_personal_access_token(&json!({ "name": "ci", "scopes": scopes }))
+        .await;
+    assert_eq!(response.status().as_u16(), 201);
+    response.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn creating_a_token_requires_sudo_mode() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let response = app
+        .create_personal_access_token(&json!({ "name": "ci", "scopes": ["read"] }))
+        .await;
+    assert_eq!(response.status().as_u16(), 401);
+
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["error"], "reauthentication_required");
+}
+
+#[tokio::test]
+async fn creating_a_token_returns_400_for_invalid_payloads() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    app.enter_sudo_mode(&json!({ "password": &app.test_user.password }))
+        .await;
+
+    for payload in [
+        json!({ "name": "", "scopes": ["read"] }),
+        json!({ "name": "ci", "scopes": [] }),
+        json!({ "name": "ci", "scopes": ["admin"] }),
+    ] {
+        let response = app.create_personal_access_token(&payload).await;
+        assert_eq!(response.status().as_u16(), 400, "{payload}");
+    }
+}
+
+#[tokio::test]
+async fn created_token_authenticates_and_is_listed_without_its_secret() {
+    let app = helpers::spawn_app().await;
+    let created = create_token(&app, &["read", "write"]).await;
+
+    let token = created["token"].as_str().unwrap();
+    assert!(token.starts_with("thpat_"));
+    assert_eq!(created["name"], "ci");
+    assert_eq!(created["scopes"], json!(["read", "write"]));
+    assert!(created["last_used_at"].is_null());
+
+    let response = app
+        .send_with_token(Method::GET, "v1/user/me/protected", token)
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = app.get_personal_access_tokens().await.json().await.unwrap();
+    let tokens = body["tokens"].as_array().unwrap();
+    assert_eq!(tokens.len(), 1);
+    assert_eq!(tokens[0]["id"], created["id"]);
+    assert!(tokens[0].get("token").is_none());
+    assert!(!tokens[0]["last_used_at"].is_null());
+
+    // Only the hash is stored
+    let stored = sqlx::query_scalar!("SELECT token_hash FROM personal_access_tokens")
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+    assert_ne!(stored, token);
+}
+
+#[tokio::test]
+async fn read_only_tokens_cannot_make_changes() {
+    let app = helpers::spawn_app().await;
+    let read_only = create_token(&app, &["read"]).await;
+    let read_write = create_token(&app, &["write"]).await;
+    let endpoint = format!("v1/user/me/tokens/{}", Uuid::new_v4());
+
+    let response = app
+        .send_with_token(
+            Method::DELETE,
+            &endpoint,
+            read_only["token"].as_str().unwrap(),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 403);
+
+    // Authenticated, the token simply does not exist
+    let response = app
+        .send_with_token(
+            Method::DELETE,
+            &endpoint,
+            read_write["token"].as_str().unwrap(),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn revoked_tokens_are_rejected() {
+    let app = helpers::spawn_app().await;
+    let created = create_token(&app, &["read"]).await;
+
+    let response = app
+        .revoke_personal_access_token(created["id"].as_str().unwrap())
+        .await;
+    assert_eq!(response.status().as_u16(), 204);
+
+    let response = app
+        .send_with_token(
+            Method::GET,
+            "v1/user/me/protected",
+            created["token"].as_str().unwrap(),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 401);
+
+    let body: Value = app.get_personal_access_tokens().await.json().await.unwrap();
+    assert_eq!(body["tokens"], json!([]));
+
+    // Already revoked
+    let response = app
+        .revoke_personal_access_token(created["id"].as_str().unwrap())
+        .await;
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn unknown_tokens_are_rejected() {
+    let app = helpers::spawn_app().await;
+
+    let response = app
+        .send_with_token(Method::GET, "v1/user/me/protected", "thpat_unknown")
+        .await;
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn tokens_of_banned_users_are_rejected() {
+    let app = helpers::spawn_app().await;
+    let created = create_token(&app, &["read"]).await;
+
+    sqlx::query!(
+        "UPDATE users SET banned_at = NOW() WHERE id = $1",
+        app.test_user.user_id
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+
+    let response = app
+        .send_with_token(
+            Method::GET,
+            "v1/user/me/protected",
+            created["token"].as_str().unwrap(),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 401);
+}
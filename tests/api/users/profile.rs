@@ -0,0 +1,273 @@
+use serde_json::Value;
+use uuid::Uuid;
+use wiremock::{Mock, ResponseTemplate, matchers};
+
+use crate::helpers;
+
+#[tokio::test]
+async fn profile_returns_user_name_and_activity_counts() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let post_id = app.create_sample_post().await;
+    app.create_sample_post().await;
+    let payload = serde_json::json!({ "text": "My own post", "post_id": post_id.to_string() });
+    assert_eq!(app.create_comment(&payload).await.status().as_u16(), 201);
+    app.logout().await;
+
+    let response = app.get_user_profile(&app.test_user.user_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    let user = &body["user"];
+    assert_eq!(user["id"], app.test_user.user_id.to_string());
+    assert_eq!(user["user_name"], app.test_user.user_name);
+    assert!(user["joined_at"].is_string());
+    assert_eq!(user["post_count"], 2);
+    assert_eq!(user["comment_count"], 1);
+}
+
+#[tokio::test]
+async fn profile_counts_leave_out_deleted_posts_and_their_comments() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let post_id = app.create_sample_post().await;
+    let payload = serde_json::json!({ "text": "Gone soon", "post_id": post_id.to_string() });
+    app.create_comment(&payload).await;
+    assert_eq!(app.delete_post(&post_id).await.status().as_u16(), 200);
+
+    let body: Value = app
+        .get_user_profile(&app.test_user.user_id)
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["user"]["post_count"], 0);
+    assert_eq!(body["user"]["comment_count"], 0);
+}
+
+#[tokio::test]
+async fn profile_returns_404_for_unknown_user() {
+    let app = helpers::spawn_app().await;
+
+    let response = app.get_user_profile(&Uuid::new_v4()).await;
+
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn updated_profile_fields_are_shown_on_the_public_profile() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let response = app
+        .update_profile(&serde_json::json!({
+            "bio": "  Writes about Rust.\nAnd Postgres.  ",
+            "website": "https://athfan.dev",
+            "location": "Colombo",
+        }))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["user"]["bio"], "Writes about Rust.\nAnd Postgres.");
+
+    let body: Value = app
+        .get_user_profile(&app.test_user.user_id)
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["user"]["bio"], "Writes about Rust.\nAnd Postgres.");
+    assert_eq!(body["user"]["website"], "https://athfan.dev");
+    assert_eq!(body["user"]["location"], "Colombo");
+}
+
+#[tokio::test]
+async fn profile_update_keeps_omitted_fields_and_clears_empty_ones() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    app.update_profile(&serde_json::json!({
+        "bio": "Rustacean",
+        "website": "https://athfan.dev",
+    }))
+    .await;
+
+    let response = app
+        .update_profile(&serde_json::json!({ "website": "" }))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["user"]["bio"], "Rustacean");
+    assert!(body["user"]["website"].is_null());
+    assert!(body["user"]["location"].is_null());
+}
+
+#[tokio::test]
+async fn profile_update_returns_400_for_invalid_fields() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let cases = [
+        serde_json::json!({ "website": "javascript:alert(1)" }),
+        serde_json::json!({ "bio": "a".repeat(501) }),
+        serde_json::json!({ "location": "Line one\nLine two" }),
+        serde_json::json!({}),
+    ];
+    for payload in cases {
+        let response = app.update_profile(&payload).await;
+        assert_eq!(response.status().as_u16(), 400, "{payload}");
+    }
+}
+
+#[tokio::test]
+async fn profile_update_requires_login() {
+    let app = helpers::spawn_app().await;
+
+    let response = app
+        .update_profile(&serde_json::json!({ "bio": "Rustacean" }))
+        .await;
+
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn user_name_change_is_used_for_the_next_login() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let response = app
+        .change_user_name(&serde_json::json!({ "user_name": "renamed-rustacean" }))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["user"]["user_name"], "renamed-rustacean");
+
+    app.logout().await;
+    let response = app
+        .login_with(&serde_json::json!({
+            "user_name": "renamed-rustacean",
+            "password": &app.test_user.password,
+        }))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn user_name_change_returns_409_when_another_account_has_the_name() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    // The seeded admin is called `athfan`, names are compared regardless of case
+    let response = app
+        .change_user_name(&serde_json::json!({ "user_name": "Athfan" }))
+        .await;
+
+    assert_eq!(response.status().as_u16(), 409);
+    let body: Value = app
+        .get_user_profile(&app.test_user.user_id)
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["user"]["user_name"], app.test_user.user_name);
+}
+
+#[tokio::test]
+async fn user_name_change_allows_changing_the_case_of_your_own_name() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let upper = app.test_user.user_name.to_uppercase();
+
+    let response = app
+        .change_user_name(&serde_json::json!({ "user_name": upper }))
+        .await;
+
+    assert_eq!(response.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn user_name_change_returns_400_for_invalid_names() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    for name in ["", "   ", "name{with}braces"] {
+        let response = app
+            .change_user_name(&serde_json::json!({ "user_name": name }))
+            .await;
+        assert_eq!(response.status().as_u16(), 400, "{name}");
+    }
+}
+
+#[tokio::test]
+async fn user_name_change_requires_login() {
+    let app = helpers::spawn_app().await;
+
+    let response = app
+        .change_user_name(&serde_json::json!({ "user_name": "renamed-rustacean" }))
+        .await;
+
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn accounts_renamed_when_names_became_unique_are_sent_their_new_name_once() {
+    let app = helpers::spawn_app().await;
+    let renamed = format!(
+        "{}-{}",
+        app.test_user.user_name,
+        &app.test_user.user_id.to_string()[..8]
+    );
+    sqlx::query!(
+        "UPDATE users SET user_name = $2 WHERE id = $1",
+        app.test_user.user_id,
+        renamed
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+    sqlx::query!(
+        r#"
+        INSERT INTO user_name_change_notices (user_id, previous_user_name, user_name)
+        VALUES ($1, $2, $3)
+        "#,
+        app.test_user.user_id,
+        app.test_user.user_name,
+        renamed
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+    Mock::given(matchers::path("/email"))
+        .and(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&app.email_server)
+        .await;
+
+    assert_eq!(app.send_user_name_change_notices().await, 1);
+    assert_eq!(app.send_user_name_change_notices().await, 0);
+
+    let email_request = &app.email_server.received_requests().await.unwrap()[0];
+    let body: Value = serde_json::from_slice(&email_request.body).unwrap();
+    assert_eq!(body["To"], app.test_user.email);
+    assert!(body["TextBody"].as_str().unwrap().contains(&renamed));
+}
+
+#[tokio::test]
+async fn accounts_that_chose_another_name_since_are_not_sent_the_notice() {
+    let app = helpers::spawn_app().await;
+    sqlx::query!(
+        r#"
+        INSERT INTO user_name_change_notices (user_id, previous_user_name, user_name)
+        VALUES ($1, $2, $3)
+        "#,
+        app.test_user.user_id,
+        "previous-name",
+        "previous-name-12345678"
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+
+    assert_eq!(app.send_user_name_change_notices().await, 0);
+}
@@ -0,0 +1,124 @@
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::helpers::{self, TestApp};
+
+async fn insert_user(app: &TestApp, user_name: &str, is_activated: bool) -> Uuid {
+    let id = Uuid::new_v4();
+    sqlx::query!(
+        r#"
+        INSERT INTO users (id, user_name, password_hash, email, is_activated)
+        VALUES ($1, $2, 'not-a-hash', $3, $4)
+        "#,
+        id,
+        user_name,
+        format!("{id}@example.com"),
+        is_activated,
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+    id
+}
+
+async fn found_names(app: &TestApp, query: &str) -> Vec<String> {
+    let response = app.search_users(query).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    body["users"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|u| u["user_name"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn prefix_matches_come_before_similar_names() {
+    let app = helpers::spawn_app().await;
+    for name in ["malice", "alicia", "alice", "bob"] {
+        insert_user(&app, name, true).await;
+    }
+
+    assert_eq!(
+        found_names(&app, "?q=alice").await,
+        ["alice", "alicia", "malice"]
+    );
+}
+
+#[tokio::test]
+async fn search_ignores_case_and_a_leading_at_sign() {
+    let app = helpers::spawn_app().await;
+    for name in ["alice", "alicia", "malice"] {
+        insert_user(&app, name, true).await;
+    }
+
+    assert_eq!(found_names(&app, "?q=@ALI").await, ["alice", "alicia"]);
+}
+
+#[tokio::test]
+async fn search_skips_users_that_cannot_be_mentioned() {
+    let app = helpers::spawn_app().await;
+    insert_user(&app, "alice", true).await;
+    insert_user(&app, "alicorn", false).await;
+    let banned = insert_user(&app, "alibaba", true).await;
+    let deleted = insert_user(&app, "alistair", true).await;
+    sqlx::query!("UPDATE users SET banned_at = NOW() WHERE id = $1", banned)
+        .execute(&app.db_pool)
+        .await
+        .unwrap();
+    sqlx::query!("UPDATE users SET deleted_at = NOW() WHERE id = $1", deleted)
+        .execute(&app.db_pool)
+        .await
+        .unwrap();
+
+    assert_eq!(found_names(&app, "?q=ali").await, ["alice"]);
+}
+
+#[tokio::test]
+async fn like_wildcards_in_the_term_match_literally() {
+    let app = helpers::spawn_app().await;
+    insert_user(&app, "alice", true).await;
+
+    assert!(found_names(&app, "?q=%25").await.is_empty());
+    assert!(found_names(&app, "?q=_").await.is_empty());
+}
+
+#[tokio::test]
+async fn search_results_are_paginated() {
+    let app = helpers::spawn_app().await;
+    for name in ["alice", "alicia"] {
+        insert_user(&app, name, true).await;
+    }
+
+    let response = app.search_users("?q=ali&page=2&limit=1").await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["users"][0]["user_name"], "alicia");
+    assert_eq!(body["metadata"]["total_records"], 2);
+    assert_eq!(body["metadata"]["last_page"], 2);
+}
+
+#[tokio::test]
+async fn search_returns_400_for_invalid_queries() {
+    let app = helpers::spawn_app().await;
+
+    for query in ["", "?q=", "?q=%20%20", "?q=@", "?q=ali&limit=0"] {
+        let response = app.search_users(query).await;
+        assert_eq!(response.status().as_u16(), 400, "{query}");
+    }
+}
+
+#[tokio::test]
+async fn search_returns_429_once_the_rate_limit_is_reached() {
+    let app = helpers::spawn_app_with(|c| c.user_search.max_requests_per_minute = 2).await;
+
+    for _ in 0..2 {
+        assert_eq!(app.search_users("?q=ali").await.status().as_u16(), 200);
+    }
+
+    let response = app.search_users("?q=ali").await;
+    assert_eq!(response.status().as_u16(), 429);
+}
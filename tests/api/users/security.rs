@@ -0,0 +1,68 @@
+use serde_json::{Value, json};
+
+use crate::helpers;
+
+#[tokio::test]
+async fn security_activity_lists_successful_and_failed_logins_newest_first() {
+    let app = helpers::spawn_app().await;
+
+    let response = app
+        .login_with(&json!({
+            "user_name": &app.test_user.user_name,
+            "password": "not-the-password",
+        }))
+        .await;
+    assert_eq!(response.status().as_u16(), 401);
+    app.login().await;
+
+    let response = app.get_security_activity().await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    assert!(body["last_login_at"].is_string());
+    let logins = body["recent_logins"].as_array().unwrap();
+    assert_eq!(logins.len(), 2);
+    assert_eq!(logins[0]["succeeded"], true);
+    assert_eq!(logins[1]["succeeded"], false);
+    assert_eq!(logins[0]["ip_address"], "127.0.0.1");
+}
+
+#[tokio::test]
+async fn bearer_token_logins_are_recorded_too() {
+    let app = helpers::spawn_app().await;
+    let response = app
+        .issue_access_token(&json!({
+            "user_name": &app.test_user.user_name,
+            "password": &app.test_user.password,
+        }))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    app.login().await;
+
+    let body: Value = app.get_security_activity().await.json().await.unwrap();
+
+    assert_eq!(body["recent_logins"].as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn other_users_logins_are_not_listed() {
+    let app = helpers::spawn_app().await;
+    let payload = app.create_activated_user().await;
+    let response = app.login_with(&payload).await;
+    assert_eq!(response.status().as_u16(), 200);
+    app.logout().await;
+
+    app.login().await;
+    let body: Value = app.get_security_activity().await.json().await.unwrap();
+
+    assert_eq!(body["recent_logins"].as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn security_activity_requires_login() {
+    let app = helpers::spawn_app().await;
+
+    let response = app.get_security_activity().await;
+
+    assert_eq!(response.status().as_u16(), 401);
+}
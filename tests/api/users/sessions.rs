@@ -0,0 +1,430 @@
+use std::time::Duration;
+
+use reqwest::{
+    Client, Response, StatusCode,
+    header::{COOKIE, SET_COOKIE, USER_AGENT},
+};
+use serde_json::{Value, json};
+use sqlx::postgres::types::PgInterval;
+use uuid::Uuid;
+
+use crate::helpers::{self, TestApp};
+
+// Logs the test user in from a separate browser, returning its cookie-keeping client
+async fn login_elsewhere(app: &TestApp, user_agent: &str) -> Client {
+    let client = Client::builder().cookie_store(true).build().unwrap();
+    let response = client
+        .post(format!("{}/v1/user/login", app.address))
+        .header(USER_AGENT, user_agent)
+        .json(&json!({
+            "user_name": &app.test_user.user_name,
+            "password": &app.test_user.password,
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+    client
+}
+
+async fn access_protected_from(app: &TestApp, client: &Client) -> StatusCode {
+    client
+        .get(format!("{}/v1/user/me/protected", app.address))
+        .send()
+        .await
+        .unwrap()
+        .status()
+}
+
+async fn sessions(app: &TestApp) -> Vec<Value> {
+    let response = app.get_sessions().await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    body["sessions"].as_array().unwrap().clone()
+}
+
+#[tokio::test]
+async fn sessions_are_listed_with_their_device() {
+    let app = helpers::spawn_app().await;
+    let response = app
+        .login_from(
+            "203.0.113.7",
+            &json!({
+                "user_name": &app.test_user.user_name,
+                "password": &app.test_user.password,
+            }),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    login_elsewhere(&app, "Firefox/140.0").await;
+
+    let sessions = sessions(&app).await;
+    assert_eq!(sessions.len(), 2);
+
+    let current: Vec<&Value> = sessions.iter().filter(|s| s["current"] == true).collect();
+    assert_eq!(current.len(), 1);
+    assert_eq!(current[0]["ip_address"], "203.0.113.7");
+
+    let other = sessions.iter().find(|s| s["current"] == false).unwrap();
+    assert_eq!(other["user_agent"], "Firefox/140.0");
+    assert!(other["created_at"].is_string());
+    assert!(other["last_seen_at"].is_string());
+}
+
+#[tokio::test]
+async fn listing_sessions_requires_login() {
+    let app = helpers::spawn_app().await;
+
+    let response = app.get_sessions().await;
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn revoked_session_is_logged_out_on_its_next_request() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+    let other = login_elsewhere(&app, "Firefox/140.0").await;
+    assert_eq!(access_protected_from(&app, &other).await.as_u16(), 200);
+
+    let sessions = sessions(&app).await;
+    let other_id = sessions.iter().find(|s| s["current"] == false).unwrap()["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let response = app.revoke_session(&other_id).await;
+    assert_eq!(response.status().as_u16(), 204);
+
+    assert_eq!(access_protected_from(&app, &other).await.as_u16(), 401);
+    assert_eq!(app.access_protected().await.status().as_u16(), 200);
+    assert_eq!(self::sessions(&app).await.len(), 1);
+}
+
+#[tokio::test]
+async fn revoking_the_current_session_logs_it_out() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let sessions = sessions(&app).await;
+    let response = app
+        .revoke_session(sessions[0]["id"].as_str().unwrap())
+        .await;
+    assert_eq!(response.status().as_u16(), 204);
+
+    assert_eq!(app.access_protected().await.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn logging_out_removes_the_session_from_the_list() {
+    let app = helpers::spawn_app().await;
+    let other = login_elsewhere(&app, "Firefox/140.0").await;
+    app.login().await;
+    app.logout().await;
+
+    let response = other
+        .get(format!("{}/v1/user/me/sessions", app.address))
+        .send()
+        .await
+        .unwrap();
+    let body: Value = response.json().await.unwrap();
+    let sessions = body["sessions"].as_array().unwrap();
+    assert_eq!(sessions.len(), 1);
+    assert_eq!(sessions[0]["current"], true);
+}
+
+#[tokio::test]
+async fn revoking_an_unknown_session_returns_404() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    let response = app.revoke_session(&Uuid::new_v4().to_string()).await;
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn sessions_of_other_users_cannot_be_revoked() {
+    let app = helpers::spawn_app().await;
+    let other = login_elsewhere(&app, "Firefox/140.0").await;
+    let session_id = sqlx::query_scalar!(
+        "SELECT id FROM user_sessions WHERE user_id = $1",
+        app.test_user.user_id
+    )
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap();
+
+    let intruder = helpers::spawn_app().await;
+    intruder.login().await;
+    let response = intruder.revoke_session(&session_id.to_string()).await;
+    assert_eq!(response.status().as_u16(), 404);
+
+    assert_eq!(access_protected_from(&app, &other).await.as_u16(), 200);
+}
+
+// Moves the test user's sessions `created` and `last_seen` into the past
+async fn age_sessions(app: &TestApp, created: Duration, last_seen: Duration) {
+    sqlx::query!(
+        r#"
+        UPDATE user_sessions
+        SET created_at = NOW() - $2::interval, last_seen_at = NOW() - $3::interval
+        WHERE user_id = $1
+        "#,
+        app.test_user.user_id,
+        PgInterval::try_from(created).unwrap(),
+        PgInterval::try_from(last_seen).unwrap()
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn idle_sessions_are_logged_out() {
+    let app = helpers::spawn_app_with(|c| c.session.idle_timeout_minutes = 30).await;
+    app.login().await;
+
+    age_sessions(
+        &app,
+        Duration::from_secs(3600),
+        Duration::from_secs(31 * 60),
+    )
+    .await;
+
+    assert_eq!(app.access_protected().await.status().as_u16(), 401);
+    assert_eq!(app.get_sessions().await.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn activity_restarts_the_idle_timeout() {
+    let app = helpers::spawn_app_with(|c| c.session.idle_timeout_minutes = 30).await;
+    app.login().await;
+
+    age_sessions(
+        &app,
+        Duration::from_secs(3600),
+        Duration::from_secs(29 * 60),
+    )
+    .await;
+    assert_eq!(app.access_protected().await.status().as_u16(), 200);
+
+    let idle = sqlx::query_scalar!(
+        r#"SELECT NOW() - last_seen_at AS "idle!" FROM user_sessions WHERE user_id = $1"#,
+        app.test_user.user_id
+    )
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap();
+    assert!(idle.microseconds < 60_000_000);
+}
+
+#[tokio::test]
+async fn sessions_are_logged_out_after_the_absolute_timeout_despite_activity() {
+    let app = helpers::spawn_app_with(|c| c.session.absolute_timeout_hours = 1).await;
+    app.login().await;
+
+    age_sessions(&app, Duration::from_secs(61 * 60), Duration::ZERO).await;
+
+    assert_eq!(app.access_protected().await.status().as_u16(), 401);
+}
+
+// The value of the remember-me cookie set by `response`, if any
+fn remember_me_cookie(response: &Response) -> Option<String> {
+    response
+        .headers()
+        .get_all(SET_COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .find(|value| value.starts_with("remember_me="))
+        .map(str::to_string)
+}
+
+async fn login_remembered(app: &TestApp) -> String {
+    let response = app
+        .login_with(&json!({
+            "user_name": &app.test_user.user_name,
+            "password": &app.test_user.password,
+            "remember_me": true,
+        }))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let cookie = remember_me_cookie(&response).unwrap();
+    let (pair, _) = cookie.split_once(';').unwrap();
+    pair.trim_start_matches("remember_me=").to_string()
+}
+
+// A browser that was closed and reopened, with only the remember-me cookie left
+async fn access_protected_after_restart(app: &TestApp, token: &str) -> (Client, StatusCode) {
+    let client = Client::builder().cookie_store(true).build().unwrap();
+    let status = client
+        .get(format!("{}/v1/user/me/protected", app.address))
+        .header(COOKIE, format!("remember_me={token}"))
+        .send()
+        .await
+        .unwrap()
+        .status();
+    (client, status)
+}
+
+#[tokio::test]
+async fn remember_me_sets_a_persistent_cookie() {
+    let app = helpers::spawn_app().await;
+
+    let response = app
+        .login_with(&json!({
+            "user_name": &app.test_user.user_name,
+            "password": &app.test_user.password,
+            "remember_me": true,
+        }))
+        .await;
+    let cookie = remember_me_cookie(&response).unwrap();
+    assert!(cookie.contains(&format!("Max-Age={}", 30 * 24 * 3600)));
+    assert!(cookie.contains("HttpOnly"));
+}
+
+#[tokio::test]
+async fn logins_without_remember_me_set_no_persistent_cookie() {
+    let app = helpers::spawn_app().await;
+
+    let response = app
+        .login_with(&json!({
+            "user_name": &app.test_user.user_name,
+            "password": &app.test_user.password,
+        }))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    assert!(remember_me_cookie(&response).is_none());
+}
+
+#[tokio::test]
+async fn remembered_session_resumes_after_the_browser_restarts() {
+    let app = helpers::spawn_app().await;
+    let token = login_remembered(&app).await;
+
+    let (client, status) = access_protected_after_restart(&app, &token).await;
+    assert_eq!(status.as_u16(), 200);
+
+    // Resumed under a new session cookie, as the same session
+    let response = client
+        .get(format!("{}/v1/user/me/sessions", app.address))
+        .send()
+        .await
+        .unwrap();
+    let body: Value = response.json().await.unwrap();
+    let sessions = body["sessions"].as_array().unwrap();
+    assert_eq!(sessions.len(), 1);
+    assert_eq!(sessions[0]["current"], true);
+    assert_eq!(sessions[0]["remember_me"], true);
+}
+
+#[tokio::test]
+async fn remembered_sessions_end_after_their_lifetime() {
+    let app = helpers::spawn_app_with(|c| c.session.remember_me_days = 0).await;
+    let token = login_remembered(&app).await;
+
+    let (_, status) = access_protected_after_restart(&app, &token).await;
+    assert_eq!(status.as_u16(), 401);
+}
+
+#[tokio::test]
+async fn remembered_sessions_outlive_the_idle_timeout() {
+    let app = helpers::spawn_app_with(|c| c.session.idle_timeout_minutes = 30).await;
+    let token = login_remembered(&app).await;
+
+    age_sessions(
+        &app,
+        Duration::from_secs(3600),
+        Duration::from_secs(31 * 60),
+    )
+    .await;
+
+    let (_, status) = access_protected_after_restart(&app, &token).await;
+    assert_eq!(status.as_u16(), 200);
+}
+
+#[tokio::test]
+async fn revoked_remembered_sessions_cannot_be_resumed() {
+    let app = helpers::spawn_app().await;
+    let token = login_remembered(&app).await;
+
+    let sessions = sessions(&app).await;
+    app.revoke_session(sessions[0]["id"].as_str().unwrap())
+        .await;
+
+    let (_, status) = access_protected_after_restart(&app, &token).await;
+    assert_eq!(status.as_u16(), 401);
+}
+
+#[tokio::test]
+async fn logging_out_clears_the_remember_me_cookie() {
+    let app = helpers::spawn_app().await;
+    let token = login_remembered(&app).await;
+
+    let response = app.logout().await;
+    let cookie = remember_me_cookie(&response).unwrap();
+    assert!(cookie.contains("Max-Age=0"));
+
+    let (_, status) = access_protected_after_restart(&app, &token).await;
+    assert_eq!(status.as_u16(), 401);
+}
+
+async fn issued_refresh_token(app: &TestApp) -> String {
+    let response = app
+        .issue_access_token(&json!({
+            "user_name": &app.test_user.user_name,
+            "password": &app.test_user.password,
+        }))
+        .await;
+    let body: Value = response.json().await.unwrap();
+    body["refresh_token"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn logging_out_everywhere_ends_every_session_and_refresh_token() {
+    let app = helpers::spawn_app().await;
+    let other = login_elsewhere(&app, "Firefox/140.0").await;
+    let refresh_token = issued_refresh_token(&app).await;
+    app.login().await;
+
+    let response = app.log_out_everywhere().await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    assert_eq!(app.access_protected().await.status().as_u16(), 401);
+    assert_eq!(access_protected_from(&app, &other).await.as_u16(), 401);
+    let response = app.refresh_access_token(&refresh_token).await;
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn logging_out_everywhere_ends_remembered_sessions() {
+    let app = helpers::spawn_app().await;
+    let token = login_remembered(&app).await;
+
+    app.log_out_everywhere().await;
+
+    let (_, status) = access_protected_after_restart(&app, &token).await;
+    assert_eq!(status.as_u16(), 401);
+}
+
+#[tokio::test]
+async fn changing_the_password_logs_out_every_other_session() {
+    let app = helpers::spawn_app().await;
+    let other = login_elsewhere(&app, "Firefox/140.0").await;
+    let refresh_token = issued_refresh_token(&app).await;
+    app.login().await;
+    app.enter_sudo_mode(&json!({ "password": &app.test_user.password }))
+        .await;
+
+    let response = app
+        .change_password(&json!({
+            "current_password": &app.test_user.password,
+            "new_password": "a-brand-new-password",
+        }))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    assert_eq!(app.access_protected().await.status().as_u16(), 200);
+    assert_eq!(access_protected_from(&app, &other).await.as_u16(), 401);
+    let response = app.refresh_access_token(&refresh_token).await;
+    assert_eq!(response.status().as_u16(), 401);
+}
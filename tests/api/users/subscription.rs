@@ -87,7 +87,7 @@ async fn subscribe_user_deletes_subscription_token_after_successful_subscription
     reqwest::get(confirmation_links.html).await.unwrap();
 
     let remaining_tokens = sqlx::query!(
-        r#"SELECT COUNT(*) as count FROM tokens WHERE user_id = $1 AND is_subscription = true"#,
+        r#"SELECT COUNT(*) as count FROM tokens WHERE user_id = $1 AND purpose = 'subscription'"#,
         app.test_user.user_id,
     )
     .fetch_one(&app.db_pool)
@@ -97,6 +97,43 @@ async fn subscribe_user_deletes_subscription_token_after_successful_subscription
     assert_eq!(remaining_tokens.count, Some(0));
 }
 
+#[tokio::test]
+async fn subscribe_user_returns_410_for_expired_subscription_token() {
+    let app = helpers::spawn_app().await;
+    app.login().await;
+
+    Mock::given(matchers::path("/email"))
+        .and(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&app.email_server)
+        .await;
+
+    app.request_subscription_email().await;
+
+    let email_request = &app.email_server.received_requests().await.unwrap()[0];
+    let confirmation_links = app.get_confirmation_links(email_request);
+
+    sqlx::query!(
+        "UPDATE tokens SET expires_at = NOW() - INTERVAL '1 minute' WHERE user_id = $1",
+        app.test_user.user_id,
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+
+    let response = reqwest::get(confirmation_links.html).await.unwrap();
+    assert_eq!(response.status().as_u16(), 410);
+
+    let is_subscribed = sqlx::query_scalar!(
+        "SELECT is_subscribed FROM users WHERE id = $1",
+        app.test_user.user_id,
+    )
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap();
+    assert!(!is_subscribed);
+}
+
 #[tokio::test]
 async fn request_subscription_returns_500_if_email_sending_fails() {
     let app = helpers::spawn_app().await;
@@ -0,0 +1,167 @@
+use linkify::{LinkFinder, LinkKind};
+use reqwest::Url;
+use serde_json::Value;
+use uuid::Uuid;
+use wiremock::{Mock, ResponseTemplate, matchers};
+
+use crate::helpers::{self, TestApp};
+
+async fn subscribe_test_user(app: &TestApp) {
+    sqlx::query!(
+        "UPDATE users SET is_subscribed = true WHERE id = $1",
+        app.test_user.user_id
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+}
+
+async fn is_subscribed(app: &TestApp) -> bool {
+    sqlx::query_scalar!(
+        "SELECT is_subscribed FROM users WHERE id = $1",
+        app.test_user.user_id
+    )
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap()
+}
+
+// Publishes an issue as the admin and delivers it, leaving the admin logged out
+async fn publish_and_deliver(app: &TestApp) {
+    app.login_admin().await;
+    let newsletter_body = serde_json::json!({
+        "title": "Newsletter title",
+        "content": {
+            "text": "Plain text",
+            "html": "<p>HTML</p>"
+        }
+    });
+    let key = Uuid::new_v4().to_string();
+    let response = app.publish_newsletters(&newsletter_body, Some(&key)).await;
+    assert_eq!(response.status().as_u16(), 200);
+    app.logout().await;
+
+    app.fan_out_pending_newsletters().await;
+    app.dispatch_all_pending_newsletter_emails().await;
+}
+
+async fn mock_email_delivery(app: &TestApp) {
+    Mock::given(matchers::path("/email"))
+        .and(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&app.email_server)
+        .await;
+}
+
+// The unsubscribe link at the bottom of every issue delivered to the test user
+async fn unsubscribe_links(app: &TestApp) -> Vec<Url> {
+    let mut links = Vec::new();
+    for request in app.email_server.received_requests().await.unwrap() {
+        let body: Value = serde_json::from_slice(&request.body).unwrap();
+        if body["To"] != app.test_user.email {
+            continue;
+        }
+
+        for body in [&body["HtmlBody"], &body["TextBody"]] {
+            let link = LinkFinder::new()
+                .links(body.as_str().unwrap())
+                .filter(|l| *l.kind() == LinkKind::Url)
+                .last()
+                .unwrap();
+            let link = Url::parse(link.as_str()).unwrap();
+            assert_eq!(link.path(), "/v1/user/unsubscribe");
+            links.push(link);
+        }
+    }
+    links
+}
+
+#[tokio::test]
+async fn issues_carry_a_link_that_unsubscribes_without_logging_in() {
+    let app = helpers::spawn_app().await;
+    subscribe_test_user(&app).await;
+    mock_email_delivery(&app).await;
+
+    publish_and_deliver(&app).await;
+    let links = unsubscribe_links(&app).await;
+    assert_eq!(links.len(), 2);
+
+    let response = reqwest::get(links[0].clone()).await.unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+    assert!(!is_subscribed(&app).await);
+
+    // Following the link again is harmless
+    let response = reqwest::get(links[1].clone()).await.unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn every_issue_carries_the_same_link() {
+    let app = helpers::spawn_app().await;
+    subscribe_test_user(&app).await;
+    mock_email_delivery(&app).await;
+
+    publish_and_deliver(&app).await;
+    publish_and_deliver(&app).await;
+
+    let links = unsubscribe_links(&app).await;
+    assert_eq!(links.len(), 4);
+    assert!(links.iter().all(|link| *link == links[0]));
+}
+
+#[tokio::test]
+async fn users_who_unsubscribe_after_an_issue_was_queued_do_not_receive_it() {
+    let app = helpers::spawn_app().await;
+    subscribe_test_user(&app).await;
+    mock_email_delivery(&app).await;
+
+    app.login_admin().await;
+    let newsletter_body = serde_json::json!({
+        "title": "Newsletter title",
+        "content": {
+            "text": "Plain text",
+            "html": "<p>HTML</p>"
+        }
+    });
+    let key = Uuid::new_v4().to_string();
+    app.publish_newsletters(&newsletter_body, Some(&key)).await;
+    app.logout().await;
+    app.fan_out_pending_newsletters().await;
+
+    app.login().await;
+    let response = app.unsubscribe().await;
+    assert_eq!(response.status().as_u16(), 200);
+    app.dispatch_all_pending_newsletter_emails().await;
+
+    assert!(unsubscribe_links(&app).await.is_empty());
+}
+
+#[tokio::test]
+async fn logged_in_users_can_unsubscribe() {
+    let app = helpers::spawn_app().await;
+    subscribe_test_user(&app).await;
+    app.login().await;
+
+    let response = app.unsubscribe().await;
+
+    assert_eq!(response.status().as_u16(), 200);
+    assert!(!is_subscribed(&app).await);
+}
+
+#[tokio::test]
+async fn unsubscribing_requires_login() {
+    let app = helpers::spawn_app().await;
+
+    let response = app.unsubscribe().await;
+
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn unsubscribing_with_an_unknown_token_is_rejected() {
+    let app = helpers::spawn_app().await;
+
+    let response = app.send_get("v1/user/unsubscribe?token=unknown").await;
+
+    assert_eq!(response.status().as_u16(), 401);
+}
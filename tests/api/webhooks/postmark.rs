@@ -0,0 +1,199 @@
+use secrecy::ExposeSecret;
+use serde_json::{Value, json};
+use uuid::Uuid;
+use wiremock::{Mock, ResponseTemplate, matchers};
+
+use crate::helpers;
+
+async fn subscriber_email(app: &helpers::TestApp) -> String {
+    sqlx::query_scalar!("SELECT email FROM users WHERE is_subscribed = true")
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap()
+}
+
+async fn is_undeliverable(app: &helpers::TestApp, email: &str) -> bool {
+    sqlx::query_scalar!(
+        "SELECT email_undeliverable_at IS NOT NULL AS \"undeliverable!\" FROM users WHERE email = $1",
+        email
+    )
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap()
+}
+
+fn bounce(email: &str, bounce_type: &str) -> String {
+    json!({
+        "RecordType": "Bounce",
+        "Type": bounce_type,
+        "TypeCode": 1,
+        "Email": email,
+        "BouncedAt": "2025-12-06T09:30:00Z",
+        "Description": "The server was unable to deliver your message."
+    })
+    .to_string()
+}
+
+// How many deliveries a newly published issue was enqueued for
+async fn publish_and_fan_out(app: &helpers::TestApp) -> i64 {
+    app.login_admin().await;
+    let newsletter_body = json!({
+        "title": "Newsletter title",
+        "content": {
+            "text": "Newsletter body as plain text",
+            "html": "<p>Newsletter body as HTML</p>"
+        }
+    });
+    let key = Uuid::new_v4().to_string();
+    let response = app.publish_newsletters(&newsletter_body, Some(&key)).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body: Value = response.json().await.unwrap();
+    app.fan_out_pending_newsletters().await;
+
+    let issue_id = Uuid::parse_str(body["issue_id"].as_str().unwrap()).unwrap();
+    let body: Value = app
+        .get_newsletter_status(&issue_id)
+        .await
+        .json()
+        .await
+        .unwrap();
+    body["enqueued"].as_i64().unwrap()
+}
+
+fn spam_complaint(email: &str) -> String {
+    json!({
+        "RecordType": "SpamComplaint",
+        "Type": "SpamComplaint",
+        "TypeCode": 512,
+        "Email": email,
+        "BouncedAt": "2025-12-07T09:30:00Z"
+    })
+    .to_string()
+}
+
+#[tokio::test]
+async fn permanently_bounced_addresses_are_not_sent_newsletters() {
+    let app = helpers::spawn_app().await;
+    app.create_active_subscriber().await;
+    let email = subscriber_email(&app).await;
+
+    let response = app
+        .post_postmark_webhook(&bounce(&email, "HardBounce"), None)
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    assert!(is_undeliverable(&app, &email).await);
+
+    assert_eq!(publish_and_fan_out(&app).await, 0);
+}
+
+#[tokio::test]
+async fn permanently_bounced_addresses_are_suppressed_until_removed_from_the_list() {
+    let app = helpers::spawn_app().await;
+    app.create_active_subscriber().await;
+    let email = subscriber_email(&app).await;
+
+    app.post_postmark_webhook(&bounce(&email, "HardBounce"), None)
+        .await;
+    app.login_admin().await;
+    let body: Value = app.get_suppressed_emails("").await.json().await.unwrap();
+    assert_eq!(body["suppressed_emails"][0]["email"], email.to_lowercase());
+    assert_eq!(body["suppressed_emails"][0]["reason"], "hard_bounce");
+
+    let response = app.unsuppress_email(&email).await;
+    assert_eq!(response.status().as_u16(), 204);
+    assert!(!is_undeliverable(&app, &email).await);
+
+    assert_eq!(publish_and_fan_out(&app).await, 1);
+}
+
+#[tokio::test]
+async fn soft_bounces_and_other_events_leave_the_address_deliverable() {
+    let app = helpers::spawn_app().await;
+    app.create_active_subscriber().await;
+    let email = subscriber_email(&app).await;
+
+    for event in [
+        bounce(&email, "SoftBounce"),
+        json!({ "RecordType": "Delivery", "Email": email }).to_string(),
+    ] {
+        let response = app.post_postmark_webhook(&event, None).await;
+        assert_eq!(response.status().as_u16(), 200);
+    }
+
+    assert!(!is_undeliverable(&app, &email).await);
+}
+
+#[tokio::test]
+async fn events_without_valid_credentials_are_rejected() {
+    let app = helpers::spawn_app().await;
+    app.create_active_subscriber().await;
+    let email = subscriber_email(&app).await;
+    let password = app.postmark_webhook_password.expose_secret();
+
+    for credentials in [
+        (app.postmark_webhook_username.as_str(), "wrong-password"),
+        ("someone-else", password.as_str()),
+    ] {
+        let response = app
+            .post_postmark_webhook(&bounce(&email, "HardBounce"), Some(credentials))
+            .await;
+        assert_eq!(response.status().as_u16(), 401);
+        assert!(response.headers().contains_key("WWW-Authenticate"));
+    }
+
+    let response = app
+        .api_client
+        .post(format!("{}/v1/webhooks/postmark", app.address))
+        .header("Content-Type", "application/json")
+        .body(bounce(&email, "HardBounce"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 401);
+
+    assert!(!is_undeliverable(&app, &email).await);
+}
+
+#[tokio::test]
+async fn malformed_events_are_rejected() {
+    let app = helpers::spawn_app().await;
+
+    let response = app.post_postmark_webhook("{\"Email\": 42", None).await;
+
+    assert_eq!(response.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn addresses_that_complained_of_spam_are_not_sent_newsletters() {
+    let app = helpers::spawn_app().await;
+    app.create_active_subscriber().await;
+    let email = subscriber_email(&app).await;
+
+    let response = app
+        .post_postmark_webhook(&spam_complaint(&email.to_uppercase()), None)
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    assert_eq!(publish_and_fan_out(&app).await, 0);
+}
+
+#[tokio::test]
+async fn addresses_that_complained_of_spam_are_not_sent_account_emails() {
+    let app = helpers::spawn_app().await;
+    Mock::given(matchers::path("/email"))
+        .and(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&app.email_server)
+        .await;
+
+    let response = app
+        .post_postmark_webhook(&spam_complaint(&app.test_user.email), None)
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    // Reported as sent all the same
+    let response = app.forgot_password(&app.test_user.email).await;
+    app.send_account_emails().await;
+    assert_eq!(response.status().as_u16(), 202);
+}